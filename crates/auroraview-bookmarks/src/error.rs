@@ -13,10 +13,31 @@ pub enum BookmarkError {
     #[error("Folder not found: {0}")]
     FolderNotFound(String),
 
+    /// Invalid folder move
+    #[error("Invalid move: {0}")]
+    InvalidMove(String),
+
     /// Invalid URL
     #[error("Invalid URL: {0}")]
     InvalidUrl(String),
 
+    /// Unrecognized or malformed import file
+    #[error("Invalid format: {0}")]
+    InvalidFormat(String),
+
+    /// The remote changed since it was pulled
+    #[error("Sync conflict: expected remote revision {expected}, found {found}")]
+    SyncConflict {
+        /// Revision the push was based on
+        expected: u64,
+        /// Revision found on the remote
+        found: u64,
+    },
+
+    /// Sync error
+    #[error("Sync error: {0}")]
+    Sync(String),
+
     /// Storage error
     #[error("Storage error: {0}")]
     Storage(String),
@@ -0,0 +1,198 @@
+//! Chrome/Edge `Bookmarks` JSON
+//!
+//! The profile file holds three roots (`bookmark_bar`, `other`, `synced`).
+//! Dates are strings counting microseconds since 1601-01-01 (the Windows
+//! epoch).
+
+use chrono::{DateTime, Utc};
+use serde_json::{json, Value};
+
+use super::{bookmark, folder, from_micros, number, split_roots};
+use crate::folder::special_folders;
+use crate::tree::BookmarkNode;
+use crate::{BookmarkError, Result};
+
+/// Microseconds between 1601-01-01 and 1970-01-01
+const WINDOWS_EPOCH_OFFSET_US: i64 = 11_644_473_600_000_000;
+
+// ========== Parsing ==========
+
+/// Parse a `Bookmarks` file
+pub(super) fn parse(json: &str) -> Result<Vec<BookmarkNode>> {
+    let value: Value = serde_json::from_str(json)?;
+    let roots = value
+        .get("roots")
+        .and_then(Value::as_object)
+        .ok_or_else(|| BookmarkError::InvalidFormat("missing \"roots\" object".into()))?;
+
+    let mut nodes = Vec::new();
+    for (key, id) in [
+        ("bookmark_bar", Some(special_folders::BOOKMARKS_BAR)),
+        ("other", Some(special_folders::OTHER_BOOKMARKS)),
+        ("synced", None),
+    ] {
+        let Some(root) = roots.get(key) else {
+            continue;
+        };
+        let children = children(root);
+        // Only keep the mobile folder when it has content
+        if id.is_none() && children.is_empty() {
+            continue;
+        }
+        let name = root["name"].as_str().unwrap_or("Mobile bookmarks");
+        let folder = folder(id, name, date(&root["date_added"]));
+        nodes.push(BookmarkNode::folder(folder, children));
+    }
+    Ok(number(nodes))
+}
+
+fn children(node: &Value) -> Vec<BookmarkNode> {
+    let nodes = node["children"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(parse_node)
+        .collect();
+    number(nodes)
+}
+
+fn parse_node(node: &Value) -> Option<BookmarkNode> {
+    let name = node["name"].as_str().unwrap_or_default();
+    match node["type"].as_str()? {
+        "url" => {
+            let url = node["url"].as_str().filter(|u| !u.is_empty())?;
+            Some(BookmarkNode::Bookmark(bookmark(
+                name,
+                url,
+                date(&node["date_added"]),
+                date(&node["date_modified"]),
+            )))
+        }
+        "folder" => {
+            let folder = folder(None, name, date(&node["date_added"]));
+            Some(BookmarkNode::folder(folder, children(node)))
+        }
+        _ => None,
+    }
+}
+
+fn date(value: &Value) -> Option<DateTime<Utc>> {
+    let us: i64 = match value {
+        Value::String(s) => s.parse().ok()?,
+        Value::Number(n) => n.as_i64()?,
+        _ => return None,
+    };
+    from_micros(us.checked_sub(WINDOWS_EPOCH_OFFSET_US)?)
+}
+
+// ========== Writing ==========
+
+/// Sequential node IDs, as Chrome expects
+struct Writer {
+    next_id: u64,
+}
+
+impl Writer {
+    fn id(&mut self) -> String {
+        self.next_id += 1;
+        self.next_id.to_string()
+    }
+
+    fn root(&mut self, name: &str, added: DateTime<Utc>, children: &[&BookmarkNode]) -> Value {
+        json!({
+            "children": children.iter().map(|c| self.node(c)).collect::<Vec<_>>(),
+            "date_added": stamp(added),
+            "date_modified": "0",
+            "guid": uuid::Uuid::new_v4().to_string(),
+            "id": self.id(),
+            "name": name,
+            "type": "folder",
+        })
+    }
+
+    fn node(&mut self, node: &BookmarkNode) -> Value {
+        match node {
+            BookmarkNode::Bookmark(b) => json!({
+                "date_added": stamp(b.created_at),
+                "date_last_used": "0",
+                "guid": guid(&b.id),
+                "id": self.id(),
+                "name": b.title,
+                "type": "url",
+                "url": b.url,
+            }),
+            BookmarkNode::Folder(f) => json!({
+                "children": f.children.iter().map(|c| self.node(c)).collect::<Vec<_>>(),
+                "date_added": stamp(f.folder.created_at),
+                "date_modified": "0",
+                "guid": guid(&f.folder.id),
+                "id": self.id(),
+                "name": f.folder.name,
+                "type": "folder",
+            }),
+        }
+    }
+}
+
+/// Write a `Bookmarks` file
+///
+/// Root items and folders other than the bookmarks bar go to "Other
+/// bookmarks". The checksum is omitted; Chrome accepts files without one.
+pub(super) fn write(roots: &[BookmarkNode]) -> Result<String> {
+    let (bar, mut other, rest) = split_roots(roots);
+    other.extend(rest);
+
+    let now = Utc::now();
+    let added = |id: &str| {
+        roots
+            .iter()
+            .find(|n| n.id() == id)
+            .map_or(now, BookmarkNode::created_at)
+    };
+
+    let mut writer = Writer { next_id: 0 };
+    let value = json!({
+        "roots": {
+            "bookmark_bar": writer.root(
+                "Bookmarks bar",
+                added(special_folders::BOOKMARKS_BAR),
+                &bar,
+            ),
+            "other": writer.root(
+                "Other bookmarks",
+                added(special_folders::OTHER_BOOKMARKS),
+                &other,
+            ),
+            "synced": writer.root("Mobile bookmarks", now, &[]),
+        },
+        "version": 1,
+    });
+    Ok(serde_json::to_string_pretty(&value)?)
+}
+
+fn stamp(time: DateTime<Utc>) -> String {
+    (time.timestamp_micros() + WINDOWS_EPOCH_OFFSET_US).to_string()
+}
+
+/// Reuse UUID-shaped IDs as GUIDs so re-exports stay stable
+fn guid(id: &str) -> String {
+    uuid::Uuid::parse_str(id)
+        .unwrap_or_else(|_| uuid::Uuid::new_v4())
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_date_round_trip() {
+        let time = DateTime::from_timestamp(1_700_000_000, 123_000).unwrap();
+        assert_eq!(date(&Value::String(stamp(time))), Some(time));
+        assert_eq!(date(&json!("0")), None);
+        assert_eq!(
+            date(&json!("13345000000000000")).unwrap().timestamp(),
+            1_700_526_400
+        );
+    }
+}
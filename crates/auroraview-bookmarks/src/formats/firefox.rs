@@ -0,0 +1,256 @@
+//! Firefox JSON backup (`bookmarks-YYYY-MM-DD.json`)
+//!
+//! A single `placesRoot` container holds the menu, toolbar, unfiled and
+//! mobile roots. Dates are microseconds since the Unix epoch and tags are a
+//! comma-separated string.
+
+use chrono::{DateTime, Utc};
+use serde_json::{json, Map, Value};
+
+use super::{bookmark, folder, from_micros, number, split_roots};
+use crate::folder::special_folders;
+use crate::tree::BookmarkNode;
+use crate::{BookmarkError, Result};
+
+const TYPE_BOOKMARK: u64 = 1;
+const TYPE_FOLDER: u64 = 2;
+
+const CONTAINER: &str = "text/x-moz-place-container";
+const PLACE: &str = "text/x-moz-place";
+
+// ========== Parsing ==========
+
+/// Parse a JSON backup
+pub(super) fn parse(json: &str) -> Result<Vec<BookmarkNode>> {
+    let value: Value = serde_json::from_str(json)?;
+    if !is_folder(&value) {
+        return Err(BookmarkError::InvalidFormat(
+            "expected a places root container".into(),
+        ));
+    }
+
+    let mut nodes = Vec::new();
+    for root in value["children"].as_array().into_iter().flatten() {
+        let (id, name) = match root["root"].as_str() {
+            Some("toolbarFolder") => (Some(special_folders::BOOKMARKS_BAR), "Bookmarks Toolbar"),
+            Some("unfiledBookmarksFolder") => {
+                (Some(special_folders::OTHER_BOOKMARKS), "Other Bookmarks")
+            }
+            Some("bookmarksMenuFolder") => (None, "Bookmarks Menu"),
+            Some("mobileFolder") => (None, "Mobile Bookmarks"),
+            // Tags are imported from each bookmark's "tags" field
+            Some("tagsFolder") => continue,
+            _ => {
+                nodes.extend(parse_node(root));
+                continue;
+            }
+        };
+        let children = children(root);
+        if id.is_none() && children.is_empty() {
+            continue;
+        }
+        let folder = folder(id, name, date(&root["dateAdded"]));
+        nodes.push(BookmarkNode::folder(folder, children));
+    }
+    Ok(number(nodes))
+}
+
+fn is_folder(node: &Value) -> bool {
+    node["typeCode"].as_u64() == Some(TYPE_FOLDER) || node["type"].as_str() == Some(CONTAINER)
+}
+
+fn children(node: &Value) -> Vec<BookmarkNode> {
+    let nodes = node["children"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(parse_node)
+        .collect();
+    number(nodes)
+}
+
+fn parse_node(node: &Value) -> Option<BookmarkNode> {
+    let title = node["title"].as_str().unwrap_or_default();
+    if is_folder(node) {
+        let folder = folder(None, title, date(&node["dateAdded"]));
+        return Some(BookmarkNode::folder(folder, children(node)));
+    }
+    if node["typeCode"].as_u64() != Some(TYPE_BOOKMARK) && node["type"].as_str() != Some(PLACE) {
+        // Separators
+        return None;
+    }
+
+    let url = node["uri"].as_str().filter(|u| !u.is_empty())?;
+    // Smart bookmarks (saved queries)
+    if url.starts_with("place:") {
+        return None;
+    }
+    let mut bookmark = bookmark(
+        title,
+        url,
+        date(&node["dateAdded"]),
+        date(&node["lastModified"]),
+    );
+    if let Some(tags) = node["tags"].as_str() {
+        bookmark.tags = tags
+            .split(',')
+            .map(str::trim)
+            .filter(|t| !t.is_empty())
+            .map(String::from)
+            .collect();
+    }
+    bookmark.favicon = node["iconUri"].as_str().map(String::from);
+    Some(BookmarkNode::Bookmark(bookmark))
+}
+
+fn date(value: &Value) -> Option<DateTime<Utc>> {
+    from_micros(value.as_i64()?)
+}
+
+// ========== Writing ==========
+
+/// Sequential item IDs and indices
+struct Writer {
+    next_id: u64,
+}
+
+impl Writer {
+    fn id(&mut self) -> u64 {
+        self.next_id += 1;
+        self.next_id
+    }
+
+    fn root(
+        &mut self,
+        guid: &str,
+        root: &str,
+        title: &str,
+        index: usize,
+        added: DateTime<Utc>,
+        children: &[&BookmarkNode],
+    ) -> Value {
+        json!({
+            "guid": guid,
+            "title": title,
+            "index": index,
+            "dateAdded": added.timestamp_micros(),
+            "lastModified": added.timestamp_micros(),
+            "id": self.id(),
+            "typeCode": TYPE_FOLDER,
+            "type": CONTAINER,
+            "root": root,
+            "children": self.children(children),
+        })
+    }
+
+    fn children(&mut self, children: &[&BookmarkNode]) -> Vec<Value> {
+        children
+            .iter()
+            .enumerate()
+            .map(|(index, child)| self.node(child, index))
+            .collect()
+    }
+
+    fn node(&mut self, node: &BookmarkNode, index: usize) -> Value {
+        match node {
+            BookmarkNode::Bookmark(b) => {
+                let mut map = Map::new();
+                map.insert("guid".into(), json!(guid(&b.id)));
+                map.insert("title".into(), json!(b.title));
+                map.insert("index".into(), json!(index));
+                map.insert("dateAdded".into(), json!(b.created_at.timestamp_micros()));
+                map.insert(
+                    "lastModified".into(),
+                    json!(b.modified_at.timestamp_micros()),
+                );
+                map.insert("id".into(), json!(self.id()));
+                map.insert("typeCode".into(), json!(TYPE_BOOKMARK));
+                map.insert("type".into(), json!(PLACE));
+                map.insert("uri".into(), json!(b.url));
+                if !b.tags.is_empty() {
+                    map.insert("tags".into(), json!(b.tags.join(",")));
+                }
+                if let Some(icon) = &b.favicon {
+                    map.insert("iconUri".into(), json!(icon));
+                }
+                Value::Object(map)
+            }
+            BookmarkNode::Folder(f) => {
+                let children: Vec<_> = f.children.iter().collect();
+                json!({
+                    "guid": guid(&f.folder.id),
+                    "title": f.folder.name,
+                    "index": index,
+                    "dateAdded": f.folder.created_at.timestamp_micros(),
+                    "lastModified": f.folder.created_at.timestamp_micros(),
+                    "id": self.id(),
+                    "typeCode": TYPE_FOLDER,
+                    "type": CONTAINER,
+                    "children": self.children(&children),
+                })
+            }
+        }
+    }
+}
+
+/// Write a JSON backup
+///
+/// Root items and folders other than the special folders go to the
+/// bookmarks menu.
+pub(super) fn write(roots: &[BookmarkNode]) -> Result<String> {
+    let (bar, other, rest) = split_roots(roots);
+    let now = Utc::now();
+    let added = |id: &str| {
+        roots
+            .iter()
+            .find(|n| n.id() == id)
+            .map_or(now, BookmarkNode::created_at)
+    };
+
+    let mut writer = Writer { next_id: 0 };
+    let root_id = writer.id();
+    let children = vec![
+        writer.root("menu________", "bookmarksMenuFolder", "menu", 0, now, &rest),
+        writer.root(
+            "toolbar_____",
+            "toolbarFolder",
+            "toolbar",
+            1,
+            added(special_folders::BOOKMARKS_BAR),
+            &bar,
+        ),
+        writer.root(
+            "unfiled_____",
+            "unfiledBookmarksFolder",
+            "unfiled",
+            3,
+            added(special_folders::OTHER_BOOKMARKS),
+            &other,
+        ),
+        writer.root("mobile______", "mobileFolder", "mobile", 4, now, &[]),
+    ];
+
+    let value = json!({
+        "guid": "root________",
+        "title": "",
+        "index": 0,
+        "dateAdded": now.timestamp_micros(),
+        "lastModified": now.timestamp_micros(),
+        "id": root_id,
+        "typeCode": TYPE_FOLDER,
+        "type": CONTAINER,
+        "root": "placesRoot",
+        "children": children,
+    });
+    Ok(serde_json::to_string_pretty(&value)?)
+}
+
+/// Firefox GUIDs are 12 URL-safe characters
+fn guid(id: &str) -> String {
+    let hex: String = id.chars().filter(char::is_ascii_alphanumeric).collect();
+    if hex.len() >= 12 {
+        hex[hex.len() - 12..].to_string()
+    } else {
+        uuid::Uuid::new_v4().simple().to_string()[..12].to_string()
+    }
+}
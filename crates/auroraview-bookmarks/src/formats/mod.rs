@@ -0,0 +1,181 @@
+//! Bookmark import/export formats
+//!
+//! Every format is converted to and from a list of root [`BookmarkNode`]s.
+//! The bookmarks bar and "other bookmarks" folders are represented by
+//! folders with the IDs from [`special_folders`](crate::folder::special_folders),
+//! so they land in the matching AuroraView folders on import.
+
+mod chrome;
+mod firefox;
+mod netscape;
+
+use std::collections::HashMap;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::tree::{self, BookmarkNode};
+use crate::{Bookmark, BookmarkError, BookmarkFolder, BookmarkId, Result};
+
+/// Supported bookmark file formats
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum BookmarkFormat {
+    /// AuroraView's own JSON (`BookmarkManager::export`)
+    Native,
+    /// Netscape bookmark HTML, exported by every major browser
+    NetscapeHtml,
+    /// Chrome/Edge `Bookmarks` profile file
+    ChromeJson,
+    /// Firefox JSON backup (`bookmarks-*.json`)
+    FirefoxJson,
+}
+
+impl BookmarkFormat {
+    /// Guess the format of file contents
+    pub fn detect(content: &str) -> Option<Self> {
+        let trimmed = content.trim_start_matches('\u{feff}').trim_start();
+        if trimmed.starts_with('<') {
+            // By characters: a byte cut could split a multi-byte character
+            let head = trimmed
+                .chars()
+                .take(512)
+                .collect::<String>()
+                .to_ascii_uppercase();
+            return (head.contains("NETSCAPE-BOOKMARK-FILE") || head.contains("<DL"))
+                .then_some(Self::NetscapeHtml);
+        }
+
+        let value: serde_json::Value = serde_json::from_str(trimmed).ok()?;
+        if value.get("roots").is_some() {
+            Some(Self::ChromeJson)
+        } else if value.get("root").is_some() || value.get("typeCode").is_some() {
+            Some(Self::FirefoxJson)
+        } else if value.get("bookmarks").is_some() && value.get("folders").is_some() {
+            Some(Self::Native)
+        } else {
+            None
+        }
+    }
+
+    /// Get the usual file extension
+    pub fn extension(&self) -> &'static str {
+        match self {
+            Self::NetscapeHtml => "html",
+            Self::Native | Self::ChromeJson | Self::FirefoxJson => "json",
+        }
+    }
+}
+
+/// Result of merging imported bookmarks
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ImportSummary {
+    /// Bookmarks added
+    pub added: usize,
+    /// Bookmarks skipped because their URL was already bookmarked
+    pub duplicates: usize,
+    /// Folders created
+    pub folders_created: usize,
+    /// Imported folders merged into an existing folder of the same name
+    pub folders_merged: usize,
+}
+
+/// Native export layout
+#[derive(Deserialize)]
+struct NativeData {
+    bookmarks: HashMap<BookmarkId, Bookmark>,
+    folders: HashMap<BookmarkId, BookmarkFolder>,
+}
+
+/// Parse file contents into root nodes
+pub(crate) fn parse(format: BookmarkFormat, content: &str) -> Result<Vec<BookmarkNode>> {
+    let content = content.trim_start_matches('\u{feff}');
+    match format {
+        BookmarkFormat::Native => {
+            let data: NativeData = serde_json::from_str(content)?;
+            Ok(tree::build(&data.bookmarks, &data.folders))
+        }
+        BookmarkFormat::NetscapeHtml => Ok(netscape::parse(content)),
+        BookmarkFormat::ChromeJson => chrome::parse(content),
+        BookmarkFormat::FirefoxJson => firefox::parse(content),
+    }
+}
+
+/// Serialize root nodes
+///
+/// `Native` is handled by the manager, which exports its flat maps directly.
+pub(crate) fn write(format: BookmarkFormat, roots: &[BookmarkNode]) -> Result<String> {
+    match format {
+        BookmarkFormat::Native => Err(BookmarkError::InvalidFormat(
+            "native export is written by BookmarkManager::export".into(),
+        )),
+        BookmarkFormat::NetscapeHtml => Ok(netscape::write(roots)),
+        BookmarkFormat::ChromeJson => chrome::write(roots),
+        BookmarkFormat::FirefoxJson => firefox::write(roots),
+    }
+}
+
+/// Split root nodes into bookmarks bar children, other bookmarks children
+/// and everything else
+fn split_roots(
+    roots: &[BookmarkNode],
+) -> (Vec<&BookmarkNode>, Vec<&BookmarkNode>, Vec<&BookmarkNode>) {
+    let (mut bar, mut other, mut rest) = (Vec::new(), Vec::new(), Vec::new());
+    for node in roots {
+        match node {
+            BookmarkNode::Folder(f) if f.is_bookmarks_bar() => bar.extend(&f.children),
+            BookmarkNode::Folder(f) if f.is_other_bookmarks() => other.extend(&f.children),
+            node => rest.push(node),
+        }
+    }
+    (bar, other, rest)
+}
+
+/// Build a bookmark from imported fields
+fn bookmark(
+    title: &str,
+    url: &str,
+    added: Option<DateTime<Utc>>,
+    modified: Option<DateTime<Utc>>,
+) -> Bookmark {
+    let mut bookmark = Bookmark::new(title, url);
+    if let Some(added) = added {
+        bookmark.created_at = added;
+        bookmark.modified_at = added;
+    }
+    if let Some(modified) = modified {
+        bookmark.modified_at = modified;
+    }
+    bookmark
+}
+
+/// Build a folder from imported fields
+fn folder(id: Option<&str>, name: &str, added: Option<DateTime<Utc>>) -> BookmarkFolder {
+    let mut folder = match id {
+        Some(id) => BookmarkFolder::with_id(id, name),
+        None => BookmarkFolder::new(name),
+    };
+    if let Some(added) = added {
+        folder.created_at = added;
+    }
+    folder
+}
+
+/// Set sequential positions on parsed children
+fn number(mut nodes: Vec<BookmarkNode>) -> Vec<BookmarkNode> {
+    for (i, node) in nodes.iter_mut().enumerate() {
+        match node {
+            BookmarkNode::Bookmark(b) => b.position = i as u32,
+            BookmarkNode::Folder(f) => f.folder.position = i as u32,
+        }
+    }
+    nodes
+}
+
+/// Microseconds since the Unix epoch
+fn from_micros(us: i64) -> Option<DateTime<Utc>> {
+    (us > 0)
+        .then(|| DateTime::from_timestamp_micros(us))
+        .flatten()
+}
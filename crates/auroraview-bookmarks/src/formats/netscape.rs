@@ -0,0 +1,394 @@
+//! Netscape bookmark HTML
+//!
+//! The format is loose HTML: folders are `<DT><H3>` headings followed by a
+//! `<DL>` list, bookmarks are `<DT><A>` links. Attributes carry dates as Unix
+//! seconds (`ADD_DATE`, `LAST_MODIFIED`), comma-separated `TAGS` and the
+//! favicon URL (`ICON_URI`). The bookmarks bar is flagged with
+//! `PERSONAL_TOOLBAR_FOLDER` and Firefox's unsorted folder with
+//! `UNFILED_BOOKMARKS_FOLDER`.
+
+use std::collections::HashMap;
+use std::fmt::Write as _;
+
+use chrono::{DateTime, Utc};
+
+use super::{bookmark, folder, number, split_roots};
+use crate::folder::special_folders;
+use crate::tree::BookmarkNode;
+
+const HEADER: &str = "<!DOCTYPE NETSCAPE-Bookmark-file-1>
+<!-- This is an automatically generated file.
+     It will be read and overwritten.
+     DO NOT EDIT! -->
+<META HTTP-EQUIV=\"Content-Type\" CONTENT=\"text/html; charset=UTF-8\">
+<TITLE>Bookmarks</TITLE>
+<H1>Bookmarks</H1>
+";
+
+// ========== Parsing ==========
+
+/// Folder heading waiting for its `<DL>`
+struct Heading {
+    attrs: HashMap<String, String>,
+    name: String,
+}
+
+/// Open `<DL>` list
+struct Frame {
+    /// `None` for lists without a heading (the outermost one)
+    heading: Option<Heading>,
+    children: Vec<BookmarkNode>,
+}
+
+/// Parse bookmark HTML
+///
+/// Parsing is lenient: unknown tags are ignored and unclosed lists are
+/// closed at the end of input.
+pub(super) fn parse(html: &str) -> Vec<BookmarkNode> {
+    let mut stack = vec![Frame {
+        heading: None,
+        children: Vec::new(),
+    }];
+    let mut pending: Option<Heading> = None;
+    let mut rest = html;
+
+    while let Some(start) = rest.find('<') {
+        rest = &rest[start..];
+        let Some(end) = rest.find('>') else {
+            break;
+        };
+        let (name, attrs) = parse_tag(&rest[1..end]);
+        rest = &rest[end + 1..];
+
+        match name.as_str() {
+            "H3" => {
+                flush(&mut stack, pending.take());
+                let (text, after) = inner_text(rest, "</H3");
+                rest = after;
+                pending = Some(Heading { attrs, name: text });
+            }
+            "A" => {
+                flush(&mut stack, pending.take());
+                let (text, after) = inner_text(rest, "</A");
+                rest = after;
+                if let Some(node) = link(&attrs, &text) {
+                    top(&mut stack).children.push(node);
+                }
+            }
+            "DL" => stack.push(Frame {
+                heading: pending.take(),
+                children: Vec::new(),
+            }),
+            "/DL" => {
+                flush(&mut stack, pending.take());
+                if stack.len() > 1 {
+                    let frame = stack.pop().unwrap();
+                    close(&mut stack, frame);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    flush(&mut stack, pending.take());
+    while stack.len() > 1 {
+        let frame = stack.pop().unwrap();
+        close(&mut stack, frame);
+    }
+    number(stack.pop().unwrap().children)
+}
+
+fn top(stack: &mut [Frame]) -> &mut Frame {
+    stack.last_mut().expect("root frame")
+}
+
+/// Add a heading that never got a list as an empty folder
+fn flush(stack: &mut [Frame], pending: Option<Heading>) {
+    if let Some(heading) = pending {
+        let node = folder_node(heading, Vec::new());
+        top(stack).children.push(node);
+    }
+}
+
+/// Attach a closed list to its parent
+fn close(stack: &mut [Frame], frame: Frame) {
+    match frame.heading {
+        Some(heading) => {
+            let node = folder_node(heading, number(frame.children));
+            top(stack).children.push(node);
+        }
+        // Anonymous lists (e.g. the outermost one) are flattened
+        None => top(stack).children.extend(frame.children),
+    }
+}
+
+fn folder_node(heading: Heading, children: Vec<BookmarkNode>) -> BookmarkNode {
+    let flag = |name: &str| heading.attrs.get(name).is_some_and(|v| v != "false");
+    let id = if flag("PERSONAL_TOOLBAR_FOLDER") {
+        Some(special_folders::BOOKMARKS_BAR)
+    } else if flag("UNFILED_BOOKMARKS_FOLDER") {
+        Some(special_folders::OTHER_BOOKMARKS)
+    } else {
+        None
+    };
+    let folder = folder(id, &heading.name, date(&heading.attrs, "ADD_DATE"));
+    BookmarkNode::folder(folder, children)
+}
+
+fn link(attrs: &HashMap<String, String>, title: &str) -> Option<BookmarkNode> {
+    let url = attrs.get("HREF").filter(|u| !u.is_empty())?;
+    // Firefox smart folders
+    if url.starts_with("place:") {
+        return None;
+    }
+    let mut bookmark = bookmark(
+        title,
+        url,
+        date(attrs, "ADD_DATE"),
+        date(attrs, "LAST_MODIFIED"),
+    );
+    if let Some(tags) = attrs.get("TAGS") {
+        bookmark.tags = tags
+            .split(',')
+            .map(str::trim)
+            .filter(|t| !t.is_empty())
+            .map(String::from)
+            .collect();
+    }
+    bookmark.favicon = attrs
+        .get("ICON_URI")
+        .or_else(|| attrs.get("ICON"))
+        .filter(|i| !i.is_empty())
+        .cloned();
+    Some(BookmarkNode::Bookmark(bookmark))
+}
+
+fn date(attrs: &HashMap<String, String>, name: &str) -> Option<DateTime<Utc>> {
+    let secs: i64 = attrs.get(name)?.trim().parse().ok()?;
+    (secs > 0)
+        .then(|| DateTime::from_timestamp(secs, 0))
+        .flatten()
+}
+
+/// Split a tag body into its upper-cased name and attributes
+fn parse_tag(body: &str) -> (String, HashMap<String, String>) {
+    let body = body.trim();
+    let name_end = body.find(|c: char| c.is_whitespace()).unwrap_or(body.len());
+    let name = body[..name_end].trim_end_matches('/').to_ascii_uppercase();
+
+    let mut attrs = HashMap::new();
+    let mut rest = body[name_end..].trim_start();
+    while !rest.is_empty() {
+        let key_end = rest
+            .find(|c: char| c == '=' || c.is_whitespace())
+            .unwrap_or(rest.len());
+        let key = rest[..key_end].to_ascii_uppercase();
+        rest = rest[key_end..].trim_start();
+
+        let value = if let Some(after) = rest.strip_prefix('=') {
+            let after = after.trim_start();
+            let (value, remaining) = match after.chars().next() {
+                Some(q @ ('"' | '\'')) => {
+                    let inner = &after[1..];
+                    let close = inner.find(q).unwrap_or(inner.len());
+                    (&inner[..close], inner.get(close + 1..).unwrap_or(""))
+                }
+                _ => {
+                    let end = after
+                        .find(|c: char| c.is_whitespace())
+                        .unwrap_or(after.len());
+                    (&after[..end], &after[end..])
+                }
+            };
+            rest = remaining.trim_start();
+            decode(value)
+        } else {
+            String::new()
+        };
+
+        if !key.is_empty() {
+            attrs.insert(key, value);
+        } else {
+            break;
+        }
+    }
+    (name, attrs)
+}
+
+/// Read text up to a closing tag (case-insensitive)
+fn inner_text<'a>(rest: &'a str, closing: &str) -> (String, &'a str) {
+    let end = find_ci(rest, closing).unwrap_or(rest.len());
+    let text = decode(rest[..end].trim());
+    let after = &rest[end..];
+    let after = after.find('>').map_or("", |i| &after[i + 1..]);
+    (text, after)
+}
+
+fn find_ci(haystack: &str, needle: &str) -> Option<usize> {
+    let needle = needle.as_bytes();
+    haystack
+        .as_bytes()
+        .windows(needle.len())
+        .position(|w| w.eq_ignore_ascii_case(needle))
+}
+
+/// Decode HTML entities
+fn decode(text: &str) -> String {
+    if !text.contains('&') {
+        return text.to_string();
+    }
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(amp) = rest.find('&') {
+        out.push_str(&rest[..amp]);
+        rest = &rest[amp..];
+        let entity = rest
+            .find(';')
+            .filter(|i| *i <= 10)
+            .and_then(|i| entity(&rest[1..i]).map(|c| (c, i)));
+        match entity {
+            Some((c, i)) => {
+                out.push(c);
+                rest = &rest[i + 1..];
+            }
+            None => {
+                out.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+fn entity(name: &str) -> Option<char> {
+    match name {
+        "amp" => Some('&'),
+        "lt" => Some('<'),
+        "gt" => Some('>'),
+        "quot" => Some('"'),
+        "apos" => Some('\''),
+        "nbsp" => Some('\u{a0}'),
+        _ => {
+            let code = match name.strip_prefix('#')? {
+                hex if hex.starts_with(['x', 'X']) => u32::from_str_radix(&hex[1..], 16).ok()?,
+                dec => dec.parse().ok()?,
+            };
+            char::from_u32(code)
+        }
+    }
+}
+
+// ========== Writing ==========
+
+/// Write bookmark HTML
+pub(super) fn write(roots: &[BookmarkNode]) -> String {
+    let (bar, other, rest) = split_roots(roots);
+    let mut out = String::from(HEADER);
+    out.push_str("<DL><p>\n");
+
+    for node in roots {
+        if let BookmarkNode::Folder(f) = node {
+            if f.is_bookmarks_bar() {
+                write_folder(&mut out, node, &bar, "PERSONAL_TOOLBAR_FOLDER", 1);
+            } else if f.is_other_bookmarks() {
+                write_folder(&mut out, node, &other, "UNFILED_BOOKMARKS_FOLDER", 1);
+            }
+        }
+    }
+    for node in rest {
+        write_node(&mut out, node, 1);
+    }
+
+    out.push_str("</DL><p>\n");
+    out
+}
+
+fn write_node(out: &mut String, node: &BookmarkNode, depth: usize) {
+    let indent = "    ".repeat(depth);
+    match node {
+        BookmarkNode::Bookmark(b) => {
+            let _ = write!(
+                out,
+                "{indent}<DT><A HREF=\"{}\" ADD_DATE=\"{}\" LAST_MODIFIED=\"{}\"",
+                encode(&b.url),
+                b.created_at.timestamp(),
+                b.modified_at.timestamp()
+            );
+            if let Some(icon) = &b.favicon {
+                let _ = write!(out, " ICON_URI=\"{}\"", encode(icon));
+            }
+            if !b.tags.is_empty() {
+                let _ = write!(out, " TAGS=\"{}\"", encode(&b.tags.join(",")));
+            }
+            let _ = writeln!(out, ">{}</A>", encode(&b.title));
+        }
+        BookmarkNode::Folder(f) => {
+            let children: Vec<_> = f.children.iter().collect();
+            write_folder(out, node, &children, "", depth);
+        }
+    }
+}
+
+fn write_folder(
+    out: &mut String,
+    node: &BookmarkNode,
+    children: &[&BookmarkNode],
+    flag: &str,
+    depth: usize,
+) {
+    let BookmarkNode::Folder(f) = node else {
+        return;
+    };
+    let indent = "    ".repeat(depth);
+    let _ = write!(
+        out,
+        "{indent}<DT><H3 ADD_DATE=\"{}\"",
+        f.folder.created_at.timestamp()
+    );
+    if !flag.is_empty() {
+        let _ = write!(out, " {flag}=\"true\"");
+    }
+    let _ = writeln!(out, ">{}</H3>", encode(&f.folder.name));
+    let _ = writeln!(out, "{indent}<DL><p>");
+    for child in children {
+        write_node(out, child, depth + 1);
+    }
+    let _ = writeln!(out, "{indent}</DL><p>");
+}
+
+fn encode(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_tag() {
+        let (name, attrs) =
+            parse_tag(r#"a HREF="https://x.com/?a=1&amp;b=2" add_date=123 PRIVATE"#);
+        assert_eq!(name, "A");
+        assert_eq!(attrs["HREF"], "https://x.com/?a=1&b=2");
+        assert_eq!(attrs["ADD_DATE"], "123");
+        assert_eq!(attrs["PRIVATE"], "");
+    }
+
+    #[test]
+    fn test_decode() {
+        assert_eq!(decode("Tom &amp; Jerry"), "Tom & Jerry");
+        assert_eq!(decode("&#39;q&#x27; &lt;b&gt;"), "'q' <b>");
+        assert_eq!(decode("a & b &unknown;"), "a & b &unknown;");
+    }
+}
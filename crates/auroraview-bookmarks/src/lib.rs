@@ -9,6 +9,8 @@
 //! - Persistent storage (JSON file)
 //! - Thread-safe operations
 //! - Favicon support
+//! - Import/export of Netscape HTML, Chrome and Firefox bookmark files
+//! - Multi-device sync with a change log and three-way merge
 //!
 //! # Example
 //!
@@ -30,7 +32,10 @@
 mod bookmark;
 mod error;
 mod folder;
+mod formats;
 mod manager;
+mod sync;
+mod tree;
 
 /// Single bookmark entry with URL, title, and metadata.
 pub use bookmark::Bookmark;
@@ -38,8 +43,17 @@ pub use bookmark::Bookmark;
 pub use error::{BookmarkError, Result};
 /// Bookmark folder for hierarchical organization.
 pub use folder::BookmarkFolder;
+/// Import/export file formats and merge results.
+pub use formats::{BookmarkFormat, ImportSummary};
 /// Bookmark manager for CRUD operations and persistence.
 pub use manager::BookmarkManager;
+/// Change log, sync backends and three-way merge.
+pub use sync::{
+    Change, ChangeKind, FileSystemBackend, ItemKind, RemoteState, SyncBackend, SyncReport,
+    SyncSnapshot,
+};
+/// Hierarchical view of bookmarks and folders.
+pub use tree::{BookmarkNode, FolderNode};
 
 /// Unique identifier for bookmarks and folders
 pub type BookmarkId = String;
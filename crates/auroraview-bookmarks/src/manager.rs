@@ -6,6 +6,12 @@ use std::sync::Arc;
 
 use parking_lot::RwLock;
 
+use crate::folder::special_folders;
+use crate::formats::{self, BookmarkFormat, ImportSummary};
+use crate::sync::{
+    self, Change, ChangeKind, ItemKind, SyncBackend, SyncReport, SyncSnapshot, SyncState,
+};
+use crate::tree::{self, BookmarkNode, FolderNode};
 use crate::{Bookmark, BookmarkError, BookmarkFolder, BookmarkId, Result};
 
 /// Bookmark manager
@@ -21,6 +27,24 @@ pub struct BookmarkManager {
 struct BookmarkStore {
     bookmarks: HashMap<BookmarkId, Bookmark>,
     folders: HashMap<BookmarkId, BookmarkFolder>,
+    /// Change log and last synced tree, when sync is enabled
+    sync: Option<SyncState>,
+}
+
+impl BookmarkStore {
+    /// Record a change for sync
+    fn record(&mut self, item_id: &str, item: ItemKind, kind: ChangeKind) {
+        if let Some(sync) = &mut self.sync {
+            sync.record(item_id, item, kind);
+        }
+    }
+
+    fn snapshot(&self) -> SyncSnapshot {
+        SyncSnapshot {
+            bookmarks: self.bookmarks.clone(),
+            folders: self.folders.clone(),
+        }
+    }
 }
 
 impl BookmarkManager {
@@ -50,8 +74,6 @@ impl BookmarkManager {
 
     /// Initialize special folders (bookmarks bar, other bookmarks)
     fn init_special_folders(&self) {
-        use crate::folder::special_folders;
-
         let mut store = self.inner.write();
 
         // Bookmarks bar
@@ -75,6 +97,7 @@ impl BookmarkManager {
 
         let mut store = self.inner.write();
         store.bookmarks.insert(id.clone(), bookmark);
+        store.record(&id, ItemKind::Bookmark, ChangeKind::Add);
         drop(store);
 
         let _ = self.save();
@@ -99,6 +122,7 @@ impl BookmarkManager {
 
         let mut store = self.inner.write();
         store.bookmarks.insert(id.clone(), bookmark);
+        store.record(&id, ItemKind::Bookmark, ChangeKind::Add);
         drop(store);
 
         let _ = self.save();
@@ -126,6 +150,12 @@ impl BookmarkManager {
             bookmark.set_url(url);
         }
 
+        if title.is_some() {
+            store.record(id, ItemKind::Bookmark, ChangeKind::Rename);
+        }
+        if url.is_some() {
+            store.record(id, ItemKind::Bookmark, ChangeKind::Edit);
+        }
         drop(store);
         let _ = self.save();
         Ok(())
@@ -135,6 +165,9 @@ impl BookmarkManager {
     pub fn remove(&self, id: &str) -> bool {
         let mut store = self.inner.write();
         let removed = store.bookmarks.remove(id).is_some();
+        if removed {
+            store.record(id, ItemKind::Bookmark, ChangeKind::Delete);
+        }
         drop(store);
 
         if removed {
@@ -215,6 +248,7 @@ impl BookmarkManager {
             .ok_or_else(|| BookmarkError::NotFound(bookmark_id.to_string()))?;
 
         bookmark.set_parent(folder_id.map(String::from));
+        store.record(bookmark_id, ItemKind::Bookmark, ChangeKind::Move);
         drop(store);
 
         let _ = self.save();
@@ -230,6 +264,7 @@ impl BookmarkManager {
 
         let mut store = self.inner.write();
         store.folders.insert(id.clone(), folder);
+        store.record(&id, ItemKind::Folder, ChangeKind::Add);
         drop(store);
 
         let _ = self.save();
@@ -249,6 +284,7 @@ impl BookmarkManager {
 
         let mut store = self.inner.write();
         store.folders.insert(id.clone(), folder);
+        store.record(&id, ItemKind::Folder, ChangeKind::Add);
         drop(store);
 
         let _ = self.save();
@@ -288,6 +324,19 @@ impl BookmarkManager {
             return Err(BookmarkError::FolderNotFound(id.to_string()));
         }
 
+        let bookmarks: Vec<BookmarkId> = store
+            .bookmarks
+            .values()
+            .filter(|b| b.parent_id.as_deref() == Some(id))
+            .map(|b| b.id.clone())
+            .collect();
+        let folders: Vec<BookmarkId> = store
+            .folders
+            .values()
+            .filter(|f| f.parent_id.as_deref() == Some(id))
+            .map(|f| f.id.clone())
+            .collect();
+
         if delete_contents {
             // Delete all bookmarks in folder
             store
@@ -312,7 +361,20 @@ impl BookmarkManager {
             }
         }
 
+        let kind = if delete_contents {
+            ChangeKind::Delete
+        } else {
+            ChangeKind::Move
+        };
+        for bookmark in &bookmarks {
+            store.record(bookmark, ItemKind::Bookmark, kind);
+        }
+        for folder in &folders {
+            store.record(folder, ItemKind::Folder, kind);
+        }
+
         store.folders.remove(id);
+        store.record(id, ItemKind::Folder, ChangeKind::Delete);
         drop(store);
 
         let _ = self.save();
@@ -328,6 +390,39 @@ impl BookmarkManager {
             .ok_or_else(|| BookmarkError::FolderNotFound(id.to_string()))?;
 
         folder.set_name(name);
+        store.record(id, ItemKind::Folder, ChangeKind::Rename);
+        drop(store);
+
+        let _ = self.save();
+        Ok(())
+    }
+
+    /// Move a folder into another folder (or to the root)
+    pub fn move_folder(&self, id: &str, parent_id: Option<&str>) -> Result<()> {
+        let mut store = self.inner.write();
+        if !store.folders.contains_key(id) {
+            return Err(BookmarkError::FolderNotFound(id.to_string()));
+        }
+
+        // Walk up from the new parent to reject moves into a descendant
+        let mut ancestor = parent_id;
+        while let Some(current) = ancestor {
+            if current == id {
+                return Err(BookmarkError::InvalidMove(format!(
+                    "folder {id} cannot be moved into itself"
+                )));
+            }
+            let folder = store
+                .folders
+                .get(current)
+                .ok_or_else(|| BookmarkError::FolderNotFound(current.to_string()))?;
+            ancestor = folder.parent_id.as_deref();
+        }
+
+        if let Some(folder) = store.folders.get_mut(id) {
+            folder.set_parent(parent_id.map(String::from));
+        }
+        store.record(id, ItemKind::Folder, ChangeKind::Move);
         drop(store);
 
         let _ = self.save();
@@ -363,6 +458,11 @@ impl BookmarkManager {
         let json = serde_json::to_string_pretty(&data)?;
         std::fs::write(path, json)?;
 
+        if let Some(ref sync) = store.sync {
+            let json = serde_json::to_string_pretty(sync)?;
+            std::fs::write(sync_state_path(path), json)?;
+        }
+
         Ok(())
     }
 
@@ -422,6 +522,12 @@ impl BookmarkManager {
         let data: Data = serde_json::from_str(json)?;
 
         let mut store = self.inner.write();
+        for id in data.bookmarks.keys() {
+            store.record(id, ItemKind::Bookmark, ChangeKind::Add);
+        }
+        for id in data.folders.keys() {
+            store.record(id, ItemKind::Folder, ChangeKind::Add);
+        }
         store.bookmarks.extend(data.bookmarks);
         store.folders.extend(data.folders);
         drop(store);
@@ -430,9 +536,174 @@ impl BookmarkManager {
         Ok(())
     }
 
+    // ========== Sync ==========
+
+    /// Enable sync, recording every change under `device_id`
+    ///
+    /// With persistent storage, the change log and the state of the last sync
+    /// are kept in `bookmarks-sync-state.json` next to the bookmarks.
+    pub fn with_sync(self, device_id: impl Into<String>) -> Self {
+        let device_id = device_id.into();
+        let saved = self
+            .storage_path
+            .as_deref()
+            .map(sync_state_path)
+            .and_then(|path| std::fs::read_to_string(path).ok())
+            .and_then(|json| serde_json::from_str::<SyncState>(&json).ok());
+
+        let state = match saved {
+            Some(mut state) => {
+                state.device_id = device_id;
+                state
+            }
+            None => SyncState::new(device_id),
+        };
+        self.inner.write().sync = Some(state);
+        self
+    }
+
+    /// Get the device ID used for sync
+    pub fn device_id(&self) -> Option<String> {
+        let store = self.inner.read();
+        store.sync.as_ref().map(|s| s.device_id.clone())
+    }
+
+    /// Get changes not pushed yet
+    pub fn pending_changes(&self) -> Vec<Change> {
+        let store = self.inner.read();
+        store
+            .sync
+            .as_ref()
+            .map(|s| s.pending.clone())
+            .unwrap_or_default()
+    }
+
+    /// Get the remote revision of the last sync
+    pub fn sync_revision(&self) -> Option<u64> {
+        let store = self.inner.read();
+        store.sync.as_ref().map(|s| s.revision)
+    }
+
+    /// Synchronize with a remote
+    ///
+    /// Pulls the remote state, merges it with local bookmarks and pushes the
+    /// result. If another device pushes in between, the sync is retried.
+    pub fn sync(&self, backend: &dyn SyncBackend) -> Result<SyncReport> {
+        const ATTEMPTS: usize = 3;
+
+        let mut attempt = 1;
+        loop {
+            let remote = backend.pull()?;
+            let (local, state) = {
+                let store = self.inner.read();
+                let state = store
+                    .sync
+                    .clone()
+                    .ok_or_else(|| BookmarkError::Sync("sync is not enabled".to_string()))?;
+                (store.snapshot(), state)
+            };
+
+            let plan = sync::plan(&state, &local, remote);
+            if let Some(ref push) = plan.push {
+                match backend.push(push, plan.expected) {
+                    Err(BookmarkError::SyncConflict { .. }) if attempt < ATTEMPTS => {
+                        attempt += 1;
+                        continue;
+                    }
+                    result => result?,
+                }
+            }
+
+            self.apply_sync(&local, plan.merged, &plan.report);
+            let _ = self.save();
+            return Ok(plan.report);
+        }
+    }
+
+    /// Adopt a merged tree after a successful sync
+    fn apply_sync(&self, local: &SyncSnapshot, merged: SyncSnapshot, report: &SyncReport) {
+        let mut store = self.inner.write();
+        let Some(mut state) = store.sync.take() else {
+            return;
+        };
+        let pushed = report.pushed.min(state.pending.len());
+        state.pending.drain(..pushed);
+
+        // Keep edits made while the sync was running
+        let current = store.snapshot();
+        let tree = if current == *local {
+            merged.clone()
+        } else {
+            sync::merge(local, &current, &merged, &state.pending, &[]).0
+        };
+
+        store.bookmarks = tree.bookmarks;
+        store.folders = tree.folders;
+        state.revision = report.revision;
+        state.base = merged;
+        store.sync = Some(state);
+        drop(store);
+
+        self.init_special_folders();
+    }
+
+    // ========== Import/Export ==========
+
+    /// Get all bookmarks and folders as a tree
+    pub fn tree(&self) -> Vec<BookmarkNode> {
+        let store = self.inner.read();
+        tree::build(&store.bookmarks, &store.folders)
+    }
+
+    /// Export bookmarks in the given format
+    pub fn export_to(&self, format: BookmarkFormat) -> Result<String> {
+        match format {
+            BookmarkFormat::Native => self.export(),
+            format => formats::write(format, &self.tree()),
+        }
+    }
+
+    /// Import bookmarks in the given format
+    ///
+    /// Imported items are merged into the existing tree: bookmarks whose URL
+    /// is already bookmarked are skipped (their tags are added to the
+    /// existing bookmark), and folders are merged with a same-named folder
+    /// under the same parent.
+    pub fn import_from(&self, format: BookmarkFormat, content: &str) -> Result<ImportSummary> {
+        let nodes = formats::parse(format, content)?;
+
+        let mut store = self.inner.write();
+        let mut merge = Merge::new(&store);
+        merge.nodes(&mut store, None, nodes);
+        drop(store);
+
+        let _ = self.save();
+        Ok(merge.summary)
+    }
+
+    /// Import a bookmark file, detecting its format
+    pub fn import_file(&self, path: impl AsRef<Path>) -> Result<ImportSummary> {
+        let content = std::fs::read_to_string(path)?;
+        let format = BookmarkFormat::detect(&content).ok_or_else(|| {
+            BookmarkError::InvalidFormat("unrecognized bookmark file".to_string())
+        })?;
+        self.import_from(format, &content)
+    }
+
+    /// Export bookmarks to a file in the given format
+    pub fn export_file(&self, path: impl AsRef<Path>, format: BookmarkFormat) -> Result<()> {
+        let content = self.export_to(format)?;
+        std::fs::write(path, content)?;
+        Ok(())
+    }
+
     /// Clear all bookmarks (keeps special folders)
     pub fn clear(&self) {
         let mut store = self.inner.write();
+        let ids: Vec<BookmarkId> = store.bookmarks.keys().cloned().collect();
+        for id in &ids {
+            store.record(id, ItemKind::Bookmark, ChangeKind::Delete);
+        }
         store.bookmarks.clear();
         drop(store);
 
@@ -453,6 +724,140 @@ impl BookmarkManager {
     }
 }
 
+/// Path of the sync state file next to the bookmarks file
+fn sync_state_path(storage_path: &Path) -> PathBuf {
+    storage_path.with_file_name("bookmarks-sync-state.json")
+}
+
+/// State of an import merge
+struct Merge {
+    /// Bookmark ID for each URL already in the store
+    urls: HashMap<String, BookmarkId>,
+    /// Next free position in each folder
+    positions: HashMap<Option<BookmarkId>, u32>,
+    summary: ImportSummary,
+}
+
+impl Merge {
+    fn new(store: &BookmarkStore) -> Self {
+        Self {
+            urls: store
+                .bookmarks
+                .values()
+                .map(|b| (b.url.clone(), b.id.clone()))
+                .collect(),
+            positions: HashMap::new(),
+            summary: ImportSummary::default(),
+        }
+    }
+
+    fn next_position(&mut self, store: &BookmarkStore, parent: &Option<BookmarkId>) -> u32 {
+        let next = self.positions.entry(parent.clone()).or_insert_with(|| {
+            let bookmarks = store
+                .bookmarks
+                .values()
+                .filter(|b| &b.parent_id == parent)
+                .map(|b| b.position + 1);
+            let folders = store
+                .folders
+                .values()
+                .filter(|f| &f.parent_id == parent)
+                .map(|f| f.position + 1);
+            bookmarks.chain(folders).max().unwrap_or(0)
+        });
+        let position = *next;
+        *next += 1;
+        position
+    }
+
+    fn nodes(
+        &mut self,
+        store: &mut BookmarkStore,
+        parent: Option<BookmarkId>,
+        nodes: Vec<BookmarkNode>,
+    ) {
+        for node in nodes {
+            match node {
+                BookmarkNode::Bookmark(bookmark) => self.bookmark(store, &parent, bookmark),
+                BookmarkNode::Folder(folder) => self.folder(store, &parent, folder),
+            }
+        }
+    }
+
+    fn bookmark(
+        &mut self,
+        store: &mut BookmarkStore,
+        parent: &Option<BookmarkId>,
+        mut bookmark: Bookmark,
+    ) {
+        let existing = self
+            .urls
+            .get(&bookmark.url)
+            .and_then(|id| store.bookmarks.get_mut(id));
+        if let Some(existing) = existing {
+            let before = existing.tags.len();
+            for tag in bookmark.tags {
+                if !existing.tags.contains(&tag) {
+                    existing.tags.push(tag);
+                }
+            }
+            if existing.tags.len() != before {
+                existing.modified_at = chrono::Utc::now();
+                let id = existing.id.clone();
+                store.record(&id, ItemKind::Bookmark, ChangeKind::Edit);
+            }
+            self.summary.duplicates += 1;
+            return;
+        }
+
+        if store.bookmarks.contains_key(&bookmark.id) {
+            bookmark.id = uuid::Uuid::new_v4().to_string();
+        }
+        bookmark.parent_id = parent.clone();
+        bookmark.position = self.next_position(store, parent);
+        self.urls.insert(bookmark.url.clone(), bookmark.id.clone());
+        store.record(&bookmark.id, ItemKind::Bookmark, ChangeKind::Add);
+        store.bookmarks.insert(bookmark.id.clone(), bookmark);
+        self.summary.added += 1;
+    }
+
+    fn folder(&mut self, store: &mut BookmarkStore, parent: &Option<BookmarkId>, node: FolderNode) {
+        let FolderNode {
+            mut folder,
+            children,
+        } = node;
+
+        let special = matches!(
+            folder.id.as_str(),
+            special_folders::BOOKMARKS_BAR | special_folders::OTHER_BOOKMARKS
+        ) && store.folders.contains_key(&folder.id);
+
+        let id = if special {
+            folder.id
+        } else if let Some(existing) = store
+            .folders
+            .values()
+            .find(|f| &f.parent_id == parent && f.name == folder.name)
+        {
+            self.summary.folders_merged += 1;
+            existing.id.clone()
+        } else {
+            if store.folders.contains_key(&folder.id) {
+                folder.id = uuid::Uuid::new_v4().to_string();
+            }
+            folder.parent_id = parent.clone();
+            folder.position = self.next_position(store, parent);
+            let id = folder.id.clone();
+            store.folders.insert(id.clone(), folder);
+            store.record(&id, ItemKind::Folder, ChangeKind::Add);
+            self.summary.folders_created += 1;
+            id
+        };
+
+        self.nodes(store, Some(id), children);
+    }
+}
+
 impl Clone for BookmarkManager {
     fn clone(&self) -> Self {
         Self {
@@ -0,0 +1,139 @@
+//! File system sync backend
+//!
+//! Stores the remote state as a JSON file in a directory, typically a shared
+//! network folder every workstation can reach. Pushes are serialized with a
+//! lock file and written atomically (temp file + rename).
+
+use std::fs::{self, OpenOptions};
+use std::io::{ErrorKind, Write};
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use super::{RemoteState, SyncBackend};
+use crate::{BookmarkError, Result};
+
+/// Remote state file name
+const STATE_FILE: &str = "bookmarks-sync.json";
+
+/// Lock file name
+const LOCK_FILE: &str = "bookmarks-sync.lock";
+
+/// Locks older than this are left over from a crashed writer
+const STALE_LOCK: Duration = Duration::from_secs(60);
+
+/// Sync backend backed by a (shared) directory
+#[derive(Debug, Clone)]
+pub struct FileSystemBackend {
+    dir: PathBuf,
+    lock_timeout: Duration,
+}
+
+impl FileSystemBackend {
+    /// Create a backend storing its state in `dir`
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self {
+            dir: dir.into(),
+            lock_timeout: Duration::from_secs(10),
+        }
+    }
+
+    /// Set how long a push waits for another device's lock
+    pub fn with_lock_timeout(mut self, timeout: Duration) -> Self {
+        self.lock_timeout = timeout;
+        self
+    }
+
+    /// Get the sync directory
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Get the path of the remote state file
+    pub fn state_path(&self) -> PathBuf {
+        self.dir.join(STATE_FILE)
+    }
+
+    fn read(&self) -> Result<Option<RemoteState>> {
+        match fs::read_to_string(self.state_path()) {
+            Ok(json) => Ok(Some(serde_json::from_str(&json)?)),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    fn lock(&self) -> Result<LockGuard> {
+        fs::create_dir_all(&self.dir)?;
+        let path = self.dir.join(LOCK_FILE);
+        let deadline = Instant::now() + self.lock_timeout;
+
+        loop {
+            match OpenOptions::new().write(true).create_new(true).open(&path) {
+                Ok(mut file) => {
+                    let _ = writeln!(file, "{}", std::process::id());
+                    return Ok(LockGuard(path));
+                }
+                Err(e) if e.kind() == ErrorKind::AlreadyExists => {
+                    if is_stale(&path) {
+                        let _ = fs::remove_file(&path);
+                        continue;
+                    }
+                    if Instant::now() >= deadline {
+                        return Err(BookmarkError::Sync(format!(
+                            "timed out waiting for lock {}",
+                            path.display()
+                        )));
+                    }
+                    thread::sleep(Duration::from_millis(50));
+                }
+                Err(e) => return Err(e.into()),
+            }
+        }
+    }
+}
+
+impl SyncBackend for FileSystemBackend {
+    fn pull(&self) -> Result<Option<RemoteState>> {
+        self.read()
+    }
+
+    fn push(&self, state: &RemoteState, expected_revision: u64) -> Result<()> {
+        let _lock = self.lock()?;
+
+        let found = self.read()?.map_or(0, |s| s.revision);
+        if found != expected_revision {
+            return Err(BookmarkError::SyncConflict {
+                expected: expected_revision,
+                found,
+            });
+        }
+
+        let path = self.state_path();
+        let tmp = self
+            .dir
+            .join(format!("{STATE_FILE}.{}.tmp", uuid::Uuid::new_v4()));
+        fs::write(&tmp, serde_json::to_string_pretty(state)?)?;
+        if let Err(e) = fs::rename(&tmp, &path) {
+            let _ = fs::remove_file(&tmp);
+            return Err(e.into());
+        }
+        Ok(())
+    }
+}
+
+/// Removes the lock file when dropped
+struct LockGuard(PathBuf);
+
+impl Drop for LockGuard {
+    fn drop(&mut self) {
+        let _ = fs::remove_file(&self.0);
+    }
+}
+
+fn is_stale(path: &Path) -> bool {
+    fs::metadata(path)
+        .and_then(|m| m.modified())
+        .ok()
+        .and_then(|t| t.elapsed().ok())
+        .is_some_and(|age| age > STALE_LOCK)
+}
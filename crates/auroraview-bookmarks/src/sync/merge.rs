@@ -0,0 +1,407 @@
+//! Three-way merge of bookmark trees
+
+use std::collections::{BTreeSet, HashMap, HashSet};
+
+use chrono::{DateTime, Utc};
+
+use super::{Change, ChangeKind, SyncSnapshot};
+use crate::{Bookmark, BookmarkFolder, BookmarkId};
+
+/// Latest change time of each kind per item
+type ChangeTimes = HashMap<(BookmarkId, ChangeKind), DateTime<Utc>>;
+
+/// Merge `local` and `remote`, which both derive from `base`
+///
+/// Returns the merged tree and the number of conflicts resolved.
+pub(crate) fn merge(
+    base: &SyncSnapshot,
+    local: &SyncSnapshot,
+    remote: &SyncSnapshot,
+    local_changes: &[Change],
+    remote_changes: &[Change],
+) -> (SyncSnapshot, usize) {
+    let mut merger = Merger {
+        base,
+        local,
+        remote,
+        local_times: times(local_changes),
+        remote_times: times(remote_changes),
+        conflicts: 0,
+    };
+
+    let mut merged = SyncSnapshot::default();
+    for id in ids(&base.bookmarks, &local.bookmarks, &remote.bookmarks) {
+        let item = merger.resolve(
+            base.bookmarks.get(id),
+            local.bookmarks.get(id),
+            remote.bookmarks.get(id),
+            Merger::bookmark,
+        );
+        if let Some(bookmark) = item {
+            merged.bookmarks.insert(id.clone(), bookmark);
+        }
+    }
+    for id in ids(&base.folders, &local.folders, &remote.folders) {
+        let item = merger.resolve(
+            base.folders.get(id),
+            local.folders.get(id),
+            remote.folders.get(id),
+            Merger::folder,
+        );
+        if let Some(folder) = item {
+            merged.folders.insert(id.clone(), folder);
+        }
+    }
+
+    merger.dedup_urls(&mut merged);
+    merger.restore_parents(&mut merged);
+    merger.break_cycles(&mut merged);
+    (merged, merger.conflicts)
+}
+
+fn times(changes: &[Change]) -> ChangeTimes {
+    let mut times = ChangeTimes::new();
+    for change in changes {
+        times
+            .entry((change.item_id.clone(), change.kind))
+            .and_modify(|t| *t = (*t).max(change.timestamp))
+            .or_insert(change.timestamp);
+    }
+    times
+}
+
+/// All IDs in sorted order, so merges are deterministic
+fn ids<'a, T>(
+    base: &'a HashMap<BookmarkId, T>,
+    local: &'a HashMap<BookmarkId, T>,
+    remote: &'a HashMap<BookmarkId, T>,
+) -> BTreeSet<&'a BookmarkId> {
+    base.keys()
+        .chain(local.keys())
+        .chain(remote.keys())
+        .collect()
+}
+
+struct Merger<'a> {
+    base: &'a SyncSnapshot,
+    local: &'a SyncSnapshot,
+    remote: &'a SyncSnapshot,
+    local_times: ChangeTimes,
+    remote_times: ChangeTimes,
+    conflicts: usize,
+}
+
+impl Merger<'_> {
+    /// Merge one item present in any of the three trees
+    fn resolve<T: Clone + PartialEq>(
+        &mut self,
+        base: Option<&T>,
+        local: Option<&T>,
+        remote: Option<&T>,
+        merge: fn(&mut Self, &T, &T, &T) -> T,
+    ) -> Option<T> {
+        match (base, local, remote) {
+            (Some(base), Some(local), Some(remote)) => Some(merge(self, base, local, remote)),
+            // Added on both sides with the same ID (e.g. the special
+            // folders): remote values win
+            (None, Some(local), Some(remote)) => Some(merge(self, local, local, remote)),
+            (None, Some(item), None) | (None, None, Some(item)) => Some(item.clone()),
+            // Deleted on one side: an edit on the other side wins
+            (Some(base), Some(kept), None) | (Some(base), None, Some(kept)) => {
+                if kept == base {
+                    None
+                } else {
+                    self.conflicts += 1;
+                    Some(kept.clone())
+                }
+            }
+            (_, None, None) => None,
+        }
+    }
+
+    /// Whether a conflicting local edit wins over the remote one
+    fn prefer_local(&self, id: &str, kind: ChangeKind) -> bool {
+        let key = (id.to_string(), kind);
+        self.local_times.get(&key) > self.remote_times.get(&key)
+    }
+
+    /// Three-way merge of one field
+    fn pick<T: Clone + PartialEq>(
+        &mut self,
+        id: &str,
+        kind: ChangeKind,
+        base: T,
+        local: T,
+        remote: T,
+    ) -> T {
+        if local == remote || remote == base {
+            local
+        } else if local == base {
+            remote
+        } else {
+            self.conflicts += 1;
+            if self.prefer_local(id, kind) {
+                local
+            } else {
+                remote
+            }
+        }
+    }
+
+    fn bookmark(&mut self, base: &Bookmark, local: &Bookmark, remote: &Bookmark) -> Bookmark {
+        let id = local.id.as_str();
+        let location = |b: &Bookmark| (b.parent_id.clone(), b.position);
+        let (parent_id, position) = self.pick(
+            id,
+            ChangeKind::Move,
+            location(base),
+            location(local),
+            location(remote),
+        );
+
+        Bookmark {
+            id: local.id.clone(),
+            title: self
+                .pick(
+                    id,
+                    ChangeKind::Rename,
+                    &base.title,
+                    &local.title,
+                    &remote.title,
+                )
+                .clone(),
+            url: self
+                .pick(id, ChangeKind::Edit, &base.url, &local.url, &remote.url)
+                .clone(),
+            favicon: self
+                .pick(
+                    id,
+                    ChangeKind::Edit,
+                    &base.favicon,
+                    &local.favicon,
+                    &remote.favicon,
+                )
+                .clone(),
+            parent_id,
+            created_at: local.created_at.min(remote.created_at),
+            modified_at: local.modified_at.max(remote.modified_at),
+            position,
+            tags: merge_tags(&base.tags, &local.tags, &remote.tags),
+        }
+    }
+
+    fn folder(
+        &mut self,
+        base: &BookmarkFolder,
+        local: &BookmarkFolder,
+        remote: &BookmarkFolder,
+    ) -> BookmarkFolder {
+        let id = local.id.as_str();
+        let location = |f: &BookmarkFolder| (f.parent_id.clone(), f.position);
+        let (parent_id, position) = self.pick(
+            id,
+            ChangeKind::Move,
+            location(base),
+            location(local),
+            location(remote),
+        );
+
+        BookmarkFolder {
+            id: local.id.clone(),
+            name: self
+                .pick(
+                    id,
+                    ChangeKind::Rename,
+                    &base.name,
+                    &local.name,
+                    &remote.name,
+                )
+                .clone(),
+            parent_id,
+            created_at: local.created_at.min(remote.created_at),
+            position,
+            icon: self
+                .pick(id, ChangeKind::Edit, &base.icon, &local.icon, &remote.icon)
+                .clone(),
+        }
+    }
+
+    /// Drop local additions whose URL was also added remotely
+    fn dedup_urls(&self, merged: &mut SyncSnapshot) {
+        let remote_urls: HashMap<String, BookmarkId> = merged
+            .bookmarks
+            .values()
+            .filter(|b| self.remote.bookmarks.contains_key(&b.id))
+            .map(|b| (b.url.clone(), b.id.clone()))
+            .collect();
+
+        let duplicates: Vec<(BookmarkId, BookmarkId)> = merged
+            .bookmarks
+            .values()
+            .filter(|b| {
+                !self.base.bookmarks.contains_key(&b.id)
+                    && !self.remote.bookmarks.contains_key(&b.id)
+            })
+            .filter_map(|b| Some((b.id.clone(), remote_urls.get(&b.url)?.clone())))
+            .collect();
+
+        for (duplicate, kept) in duplicates {
+            let Some(duplicate) = merged.bookmarks.remove(&duplicate) else {
+                continue;
+            };
+            if let Some(kept) = merged.bookmarks.get_mut(&kept) {
+                for tag in duplicate.tags {
+                    if !kept.tags.contains(&tag) {
+                        kept.tags.push(tag);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Bring back deleted folders that still have content
+    fn restore_parents(&mut self, merged: &mut SyncSnapshot) {
+        loop {
+            let missing: BTreeSet<BookmarkId> = merged
+                .bookmarks
+                .values()
+                .filter_map(|b| b.parent_id.clone())
+                .chain(merged.folders.values().filter_map(|f| f.parent_id.clone()))
+                .filter(|p| !merged.folders.contains_key(p))
+                .collect();
+            if missing.is_empty() {
+                return;
+            }
+
+            for id in missing {
+                let folder = self
+                    .local
+                    .folders
+                    .get(&id)
+                    .or_else(|| self.remote.folders.get(&id))
+                    .or_else(|| self.base.folders.get(&id));
+                match folder {
+                    Some(folder) => {
+                        self.conflicts += 1;
+                        merged.folders.insert(id, folder.clone());
+                    }
+                    // Unknown everywhere: move the content to the root
+                    None => {
+                        let orphaned = |parent: &mut Option<BookmarkId>| {
+                            if parent.as_deref() == Some(id.as_str()) {
+                                *parent = None;
+                            }
+                        };
+                        merged
+                            .bookmarks
+                            .values_mut()
+                            .for_each(|b| orphaned(&mut b.parent_id));
+                        merged
+                            .folders
+                            .values_mut()
+                            .for_each(|f| orphaned(&mut f.parent_id));
+                    }
+                }
+            }
+        }
+    }
+
+    /// Undo moves that put a folder inside itself
+    ///
+    /// Happens when two devices move folders into each other.
+    fn break_cycles(&mut self, merged: &mut SyncSnapshot) {
+        let ids: BTreeSet<BookmarkId> = merged.folders.keys().cloned().collect();
+        for id in ids {
+            if !in_cycle(&merged.folders, &id) {
+                continue;
+            }
+            self.conflicts += 1;
+
+            let base_parent = self
+                .base
+                .folders
+                .get(&id)
+                .and_then(|f| f.parent_id.clone())
+                .filter(|p| merged.folders.contains_key(p));
+            if let Some(folder) = merged.folders.get_mut(&id) {
+                folder.parent_id = base_parent;
+            }
+            if in_cycle(&merged.folders, &id) {
+                if let Some(folder) = merged.folders.get_mut(&id) {
+                    folder.parent_id = None;
+                }
+            }
+        }
+    }
+}
+
+/// Whether a folder is its own ancestor
+fn in_cycle(folders: &HashMap<BookmarkId, BookmarkFolder>, id: &str) -> bool {
+    let mut seen = HashSet::new();
+    let mut current = folders.get(id).and_then(|f| f.parent_id.as_deref());
+    while let Some(parent) = current {
+        if parent == id {
+            return true;
+        }
+        // A cycle further up that does not include `id`
+        if !seen.insert(parent) {
+            return false;
+        }
+        current = folders.get(parent).and_then(|f| f.parent_id.as_deref());
+    }
+    false
+}
+
+/// Merge tag lists: additions from both sides are kept, removals from
+/// either side are applied
+fn merge_tags(base: &[String], local: &[String], remote: &[String]) -> Vec<String> {
+    let mut tags: Vec<String> = local
+        .iter()
+        .filter(|t| !base.contains(t) || remote.contains(t))
+        .cloned()
+        .collect();
+    for tag in remote {
+        if !base.contains(tag) && !tags.contains(tag) {
+            tags.push(tag.clone());
+        }
+    }
+    tags
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tags(items: &[&str]) -> Vec<String> {
+        items.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn test_merge_tags() {
+        let merged = merge_tags(
+            &tags(&["a", "b", "c"]),
+            &tags(&["a", "c", "d"]),
+            &tags(&["b", "c", "e"]),
+        );
+        assert_eq!(merged, tags(&["c", "d", "e"]));
+    }
+
+    #[test]
+    fn test_in_cycle() {
+        let mut folders = HashMap::new();
+        for (id, parent) in [
+            ("a", Some("b")),
+            ("b", Some("a")),
+            ("c", Some("a")),
+            ("d", None),
+        ] {
+            let mut folder = BookmarkFolder::with_id(id, id);
+            folder.parent_id = parent.map(String::from);
+            folders.insert(id.to_string(), folder);
+        }
+        assert!(in_cycle(&folders, "a"));
+        assert!(in_cycle(&folders, "b"));
+        assert!(!in_cycle(&folders, "c"));
+        assert!(!in_cycle(&folders, "d"));
+    }
+}
@@ -0,0 +1,231 @@
+//! Bookmark synchronization
+//!
+//! When sync is enabled with [`BookmarkManager::with_sync`](crate::BookmarkManager::with_sync),
+//! every local edit is recorded in a change log tagged with the device ID.
+//! [`BookmarkManager::sync`](crate::BookmarkManager::sync) pulls the remote
+//! state through a [`SyncBackend`], three-way merges it with the local tree
+//! using the state of the last sync as the common ancestor, and pushes the
+//! merged tree together with the local changes.
+//!
+//! Conflicting edits of the same field are resolved in favor of the side
+//! whose change log has the most recent change of that kind; edits win over
+//! deletions, so no bookmark is lost.
+
+mod fs;
+mod merge;
+
+use std::collections::HashMap;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::{Bookmark, BookmarkFolder, BookmarkId, Result};
+
+pub use fs::FileSystemBackend;
+pub(crate) use merge::merge;
+
+/// Maximum number of changes kept in a change log
+pub(crate) const MAX_CHANGES: usize = 10_000;
+
+/// Kind of recorded change
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ChangeKind {
+    /// Item created
+    Add,
+    /// Item moved to another folder or position
+    Move,
+    /// Bookmark title or folder name changed
+    Rename,
+    /// URL, tags, favicon or icon changed
+    Edit,
+    /// Item deleted
+    Delete,
+}
+
+/// Kind of changed item
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ItemKind {
+    /// A bookmark
+    Bookmark,
+    /// A folder
+    Folder,
+}
+
+/// A change log entry
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Change {
+    /// Changed bookmark or folder
+    pub item_id: BookmarkId,
+    /// Kind of the changed item
+    pub item: ItemKind,
+    /// What changed
+    pub kind: ChangeKind,
+    /// Device that made the change
+    pub device_id: String,
+    /// When the change was made
+    pub timestamp: DateTime<Utc>,
+    /// Remote revision that published the change (`None` while pending)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub revision: Option<u64>,
+}
+
+/// Bookmarks and folders at one point in time
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct SyncSnapshot {
+    /// Bookmarks by ID
+    pub bookmarks: HashMap<BookmarkId, Bookmark>,
+    /// Folders by ID
+    pub folders: HashMap<BookmarkId, BookmarkFolder>,
+}
+
+/// State stored by a sync backend
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RemoteState {
+    /// Incremented on every push
+    pub revision: u64,
+    /// The merged tree
+    pub snapshot: SyncSnapshot,
+    /// Published changes of all devices, oldest first
+    #[serde(default)]
+    pub changes: Vec<Change>,
+}
+
+/// Outcome of a sync
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SyncReport {
+    /// Remote revision after the sync
+    pub revision: u64,
+    /// Local changes published
+    pub pushed: usize,
+    /// Changes from other devices merged in
+    pub pulled: usize,
+    /// Conflicting edits that had to be resolved
+    pub conflicts: usize,
+}
+
+/// Remote storage for synced bookmarks
+pub trait SyncBackend: Send + Sync {
+    /// Fetch the remote state (`None` if nothing was pushed yet)
+    fn pull(&self) -> Result<Option<RemoteState>>;
+
+    /// Publish a new remote state
+    ///
+    /// Must fail with [`BookmarkError::SyncConflict`](crate::BookmarkError::SyncConflict)
+    /// without writing anything if the stored revision is no longer
+    /// `expected_revision` (0 when nothing was stored).
+    fn push(&self, state: &RemoteState, expected_revision: u64) -> Result<()>;
+}
+
+/// Local sync state, persisted next to the bookmarks
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct SyncState {
+    /// This device
+    pub device_id: String,
+    /// Changes not pushed yet, oldest first
+    pub pending: Vec<Change>,
+    /// Remote revision of the last sync (0 before the first one)
+    pub revision: u64,
+    /// Tree of the last sync, the common ancestor for merges
+    pub base: SyncSnapshot,
+}
+
+impl SyncState {
+    pub fn new(device_id: impl Into<String>) -> Self {
+        Self {
+            device_id: device_id.into(),
+            ..Self::default()
+        }
+    }
+
+    /// Append a change to the pending log
+    pub fn record(&mut self, item_id: &str, item: ItemKind, kind: ChangeKind) {
+        self.pending.push(Change {
+            item_id: item_id.to_string(),
+            item,
+            kind,
+            device_id: self.device_id.clone(),
+            timestamp: Utc::now(),
+            revision: None,
+        });
+        trim(&mut self.pending);
+    }
+}
+
+/// Result of reconciling the local tree with the remote state
+pub(crate) struct SyncPlan {
+    /// The merged tree
+    pub merged: SyncSnapshot,
+    /// State to push, if the remote needs updating
+    pub push: Option<RemoteState>,
+    /// Remote revision the push is based on
+    pub expected: u64,
+    pub report: SyncReport,
+}
+
+/// Merge the local tree with the remote state
+pub(crate) fn plan(
+    state: &SyncState,
+    local: &SyncSnapshot,
+    remote: Option<RemoteState>,
+) -> SyncPlan {
+    let exists = remote.is_some();
+    let RemoteState {
+        revision: expected,
+        snapshot: remote,
+        changes: mut log,
+    } = remote.unwrap_or_default();
+
+    let remote_changes: Vec<Change> = log
+        .iter()
+        .filter(|c| c.revision.is_some_and(|r| r > state.revision))
+        .cloned()
+        .collect();
+
+    // Nothing changed remotely since the last sync (or the remote is empty)
+    let (merged, conflicts) = if !exists || expected == state.revision {
+        (local.clone(), 0)
+    } else {
+        merge(&state.base, local, &remote, &state.pending, &remote_changes)
+    };
+
+    let pushed = state.pending.len();
+    let needs_push = !exists || pushed > 0 || merged != remote;
+    let revision = if needs_push { expected + 1 } else { expected };
+    let push = needs_push.then(|| {
+        log.extend(state.pending.iter().cloned().map(|mut c| {
+            c.revision = Some(revision);
+            c
+        }));
+        trim(&mut log);
+        RemoteState {
+            revision,
+            snapshot: merged.clone(),
+            changes: log,
+        }
+    });
+
+    SyncPlan {
+        merged,
+        push,
+        expected,
+        report: SyncReport {
+            revision,
+            pushed,
+            pulled: remote_changes.len(),
+            conflicts,
+        },
+    }
+}
+
+/// Drop the oldest changes beyond [`MAX_CHANGES`]
+fn trim(changes: &mut Vec<Change>) {
+    if changes.len() > MAX_CHANGES {
+        changes.drain(..changes.len() - MAX_CHANGES);
+    }
+}
@@ -0,0 +1,153 @@
+//! Hierarchical view of bookmarks and folders
+
+use std::collections::HashMap;
+
+use chrono::{DateTime, Utc};
+
+use crate::folder::special_folders;
+use crate::{Bookmark, BookmarkFolder, BookmarkId};
+
+/// A node in a bookmark tree
+#[derive(Debug, Clone)]
+pub enum BookmarkNode {
+    /// A bookmark
+    Bookmark(Bookmark),
+    /// A folder with its children
+    Folder(FolderNode),
+}
+
+/// A folder and its children, ordered by position
+#[derive(Debug, Clone)]
+pub struct FolderNode {
+    /// The folder itself
+    pub folder: BookmarkFolder,
+    /// Child bookmarks and folders
+    pub children: Vec<BookmarkNode>,
+}
+
+impl BookmarkNode {
+    /// Create a folder node
+    pub fn folder(folder: BookmarkFolder, children: Vec<BookmarkNode>) -> Self {
+        Self::Folder(FolderNode { folder, children })
+    }
+
+    /// Get the node ID
+    pub fn id(&self) -> &str {
+        match self {
+            Self::Bookmark(b) => &b.id,
+            Self::Folder(f) => &f.folder.id,
+        }
+    }
+
+    /// Get the position in the parent folder
+    pub fn position(&self) -> u32 {
+        match self {
+            Self::Bookmark(b) => b.position,
+            Self::Folder(f) => f.folder.position,
+        }
+    }
+
+    /// Get the creation time
+    pub fn created_at(&self) -> DateTime<Utc> {
+        match self {
+            Self::Bookmark(b) => b.created_at,
+            Self::Folder(f) => f.folder.created_at,
+        }
+    }
+
+    /// Count bookmarks in this node and its descendants
+    pub fn bookmark_count(&self) -> usize {
+        match self {
+            Self::Bookmark(_) => 1,
+            Self::Folder(f) => f.children.iter().map(Self::bookmark_count).sum(),
+        }
+    }
+}
+
+impl FolderNode {
+    /// Check if this is the bookmarks bar
+    pub fn is_bookmarks_bar(&self) -> bool {
+        self.folder.id == special_folders::BOOKMARKS_BAR
+    }
+
+    /// Check if this is the "other bookmarks" folder
+    pub fn is_other_bookmarks(&self) -> bool {
+        self.folder.id == special_folders::OTHER_BOOKMARKS
+    }
+}
+
+/// Build the tree of root nodes from flat maps
+///
+/// Special folders come first; items whose parent is missing are treated as
+/// root items.
+pub(crate) fn build(
+    bookmarks: &HashMap<BookmarkId, Bookmark>,
+    folders: &HashMap<BookmarkId, BookmarkFolder>,
+) -> Vec<BookmarkNode> {
+    let parent_of = |parent: &Option<BookmarkId>| {
+        parent
+            .as_deref()
+            .filter(|p| folders.contains_key(*p))
+            .map(String::from)
+    };
+
+    let mut index = ChildIndex::default();
+    for bookmark in bookmarks.values() {
+        index
+            .bookmarks
+            .entry(parent_of(&bookmark.parent_id))
+            .or_default()
+            .push(bookmark);
+    }
+    for folder in folders.values() {
+        index
+            .folders
+            .entry(parent_of(&folder.parent_id))
+            .or_default()
+            .push(folder);
+    }
+
+    let mut roots = index.assemble(None);
+    roots.sort_by_key(|n| match n.id() {
+        special_folders::BOOKMARKS_BAR => 0,
+        special_folders::OTHER_BOOKMARKS => 1,
+        _ => 2,
+    });
+    roots
+}
+
+/// Children of each folder, keyed by parent ID
+#[derive(Default)]
+struct ChildIndex<'a> {
+    bookmarks: HashMap<Option<BookmarkId>, Vec<&'a Bookmark>>,
+    folders: HashMap<Option<BookmarkId>, Vec<&'a BookmarkFolder>>,
+}
+
+impl ChildIndex<'_> {
+    // Folders in a parent cycle are unreachable from the root, so recursion
+    // always terminates
+    fn assemble(&self, parent: Option<BookmarkId>) -> Vec<BookmarkNode> {
+        let mut nodes: Vec<BookmarkNode> = self
+            .bookmarks
+            .get(&parent)
+            .into_iter()
+            .flatten()
+            .map(|b| BookmarkNode::Bookmark((*b).clone()))
+            .collect();
+        for folder in self.folders.get(&parent).into_iter().flatten() {
+            let children = self.assemble(Some(folder.id.clone()));
+            nodes.push(BookmarkNode::folder((*folder).clone(), children));
+        }
+        sort(&mut nodes);
+        nodes
+    }
+}
+
+/// Order nodes by position, folders before bookmarks on ties
+fn sort(nodes: &mut [BookmarkNode]) {
+    nodes.sort_by(|a, b| {
+        let kind = |n: &BookmarkNode| matches!(n, BookmarkNode::Bookmark(_));
+        let key = |n: &BookmarkNode| (n.position(), kind(n), n.created_at());
+        key(a).cmp(&key(b)).then_with(|| a.id().cmp(b.id()))
+    });
+}
@@ -0,0 +1,653 @@
+use auroraview_bookmarks::{
+    BookmarkError, BookmarkFormat, BookmarkManager, BookmarkNode, ImportSummary,
+};
+use rstest::*;
+use tempfile::TempDir;
+
+const CHROME_HTML: &str = r#"<!DOCTYPE NETSCAPE-Bookmark-file-1>
+<!-- This is an automatically generated file. -->
+<META HTTP-EQUIV="Content-Type" CONTENT="text/html; charset=UTF-8">
+<TITLE>Bookmarks</TITLE>
+<H1>Bookmarks</H1>
+<DL><p>
+    <DT><H3 ADD_DATE="1700000000" LAST_MODIFIED="1700000100" PERSONAL_TOOLBAR_FOLDER="true">Bookmarks bar</H3>
+    <DL><p>
+        <DT><A HREF="https://github.com/" ADD_DATE="1700000001" ICON="data:image/png;base64,AAA">GitHub</A>
+        <DT><H3 ADD_DATE="1700000002">Rust &amp; Friends</H3>
+        <DL><p>
+            <DT><A HREF="https://www.rust-lang.org/" ADD_DATE="1700000003">Rust</A>
+            <DT><A HREF="https://docs.rs/?q=a&amp;b" ADD_DATE="1700000004">Docs.rs</A>
+        </DL><p>
+    </DL><p>
+    <DT><A HREF="https://example.com/" ADD_DATE="1700000005">Example</A>
+</DL><p>
+"#;
+
+const FIREFOX_HTML: &str = r#"<!DOCTYPE NETSCAPE-Bookmark-file-1>
+<META HTTP-EQUIV="Content-Type" CONTENT="text/html; charset=UTF-8">
+<meta http-equiv="Content-Security-Policy" content="default-src 'self'">
+<TITLE>Bookmarks</TITLE>
+<H1>Bookmarks Menu</H1>
+
+<DL><p>
+    <DT><A HREF="place:sort=8&maxResults=10" ADD_DATE="1700000000">Recently Bookmarked</A>
+    <DT><A HREF="https://mozilla.org/" ADD_DATE="1700000010" LAST_MODIFIED="1700000020" TAGS="browser,oss">Mozilla</A>
+    <HR>
+    <DT><H3 ADD_DATE="1700000030" LAST_MODIFIED="1700000040" PERSONAL_TOOLBAR_FOLDER="true">Bookmarks Toolbar</H3>
+    <DL><p>
+        <DT><A HREF="https://developer.mozilla.org/" ADD_DATE="1700000050" ICON_URI="https://developer.mozilla.org/favicon.ico">MDN</A>
+    </DL><p>
+    <DT><H3 ADD_DATE="1700000060" UNFILED_BOOKMARKS_FOLDER="true">Other Bookmarks</H3>
+    <DL><p>
+        <DT><A HREF="https://news.ycombinator.com/" ADD_DATE="1700000070">HN</A>
+    </DL><p>
+</DL>
+"#;
+
+const CHROME_JSON: &str = r#"{
+   "checksum": "00000000000000000000000000000000",
+   "roots": {
+      "bookmark_bar": {
+         "children": [ {
+            "date_added": "13345000000000000",
+            "guid": "6a0e6ad4-0b5f-4bde-9f31-7c7f8b6b7a01",
+            "id": "5",
+            "name": "GitHub",
+            "type": "url",
+            "url": "https://github.com/"
+         }, {
+            "children": [ {
+               "date_added": "13345000001000000",
+               "id": "7",
+               "name": "Rust",
+               "type": "url",
+               "url": "https://www.rust-lang.org/"
+            } ],
+            "date_added": "13345000000500000",
+            "date_modified": "0",
+            "id": "6",
+            "name": "Dev",
+            "type": "folder"
+         } ],
+         "date_added": "13344000000000000",
+         "id": "1",
+         "name": "Bookmarks bar",
+         "type": "folder"
+      },
+      "other": {
+         "children": [ {
+            "date_added": "13345000002000000",
+            "id": "8",
+            "name": "Example",
+            "type": "url",
+            "url": "https://example.com/"
+         } ],
+         "id": "2",
+         "name": "Other bookmarks",
+         "type": "folder"
+      },
+      "synced": {
+         "children": [ ],
+         "id": "3",
+         "name": "Mobile bookmarks",
+         "type": "folder"
+      }
+   },
+   "version": 1
+}"#;
+
+const FIREFOX_JSON: &str = r#"{
+  "guid": "root________", "title": "", "index": 0, "id": 1,
+  "typeCode": 2, "type": "text/x-moz-place-container", "root": "placesRoot",
+  "children": [
+    {
+      "guid": "menu________", "title": "menu", "index": 0, "id": 2,
+      "typeCode": 2, "type": "text/x-moz-place-container", "root": "bookmarksMenuFolder",
+      "children": [
+        {
+          "guid": "aaaaaaaaaaaa", "title": "Mozilla", "index": 0, "id": 10,
+          "dateAdded": 1700000010000000, "lastModified": 1700000020000000,
+          "typeCode": 1, "type": "text/x-moz-place", "uri": "https://mozilla.org/",
+          "tags": "browser,oss"
+        },
+        { "guid": "sep_________", "index": 1, "id": 11, "typeCode": 3, "type": "text/x-moz-place-separator" }
+      ]
+    },
+    {
+      "guid": "toolbar_____", "title": "toolbar", "index": 1, "id": 3,
+      "typeCode": 2, "type": "text/x-moz-place-container", "root": "toolbarFolder",
+      "children": [
+        {
+          "guid": "bbbbbbbbbbbb", "title": "Work", "index": 0, "id": 12,
+          "dateAdded": 1700000030000000, "typeCode": 2, "type": "text/x-moz-place-container",
+          "children": [
+            {
+              "guid": "cccccccccccc", "title": "MDN", "index": 0, "id": 13,
+              "dateAdded": 1700000040000000, "typeCode": 1, "type": "text/x-moz-place",
+              "uri": "https://developer.mozilla.org/", "iconUri": "https://developer.mozilla.org/favicon.ico"
+            },
+            {
+              "guid": "dddddddddddd", "title": "Most Visited", "index": 1, "id": 14,
+              "typeCode": 1, "type": "text/x-moz-place", "uri": "place:sort=8&maxResults=10"
+            }
+          ]
+        }
+      ]
+    },
+    {
+      "guid": "tags________", "title": "tags", "index": 2, "id": 4,
+      "typeCode": 2, "type": "text/x-moz-place-container", "root": "tagsFolder",
+      "children": [
+        { "title": "oss", "typeCode": 2, "children": [
+          { "typeCode": 1, "uri": "https://mozilla.org/", "title": "Mozilla" }
+        ] }
+      ]
+    },
+    {
+      "guid": "unfiled_____", "title": "unfiled", "index": 3, "id": 5,
+      "typeCode": 2, "type": "text/x-moz-place-container", "root": "unfiledBookmarksFolder",
+      "children": [
+        {
+          "guid": "eeeeeeeeeeee", "title": "HN", "index": 0, "id": 15,
+          "dateAdded": 1700000050000000, "typeCode": 1, "type": "text/x-moz-place",
+          "uri": "https://news.ycombinator.com/"
+        }
+      ]
+    },
+    {
+      "guid": "mobile______", "title": "mobile", "index": 4, "id": 6,
+      "typeCode": 2, "type": "text/x-moz-place-container", "root": "mobileFolder"
+    }
+  ]
+}"#;
+
+const BOOKMARKS_BAR: &str = "bookmarks_bar";
+const OTHER_BOOKMARKS: &str = "other_bookmarks";
+
+fn folder_named(manager: &BookmarkManager, name: &str) -> String {
+    manager
+        .all_folders()
+        .into_iter()
+        .find(|f| f.name == name)
+        .unwrap_or_else(|| panic!("folder {name:?} not found"))
+        .id
+}
+
+fn titles_in(manager: &BookmarkManager, folder_id: &str) -> Vec<String> {
+    let mut bookmarks = manager.in_folder(folder_id);
+    bookmarks.sort_by_key(|b| b.position);
+    bookmarks.into_iter().map(|b| b.title).collect()
+}
+
+/// Manager with a bar bookmark, a nested folder and an untagged root item
+fn populated() -> BookmarkManager {
+    let manager = BookmarkManager::new(None);
+    manager
+        .add_to_folder("https://github.com/", "GitHub", BOOKMARKS_BAR)
+        .unwrap();
+    let dev = manager.create_subfolder("Dev", BOOKMARKS_BAR).unwrap();
+    let rust = manager
+        .add_to_folder("https://www.rust-lang.org/", "Rust <3 & co", &dev)
+        .unwrap();
+    manager
+        .add_to_folder("https://example.com/", "Example", OTHER_BOOKMARKS)
+        .unwrap();
+    manager.add("https://loose.example/", "Loose");
+
+    // Tags and favicons survive formats that support them
+    let store = manager.export().unwrap();
+    let mut data: serde_json::Value = serde_json::from_str(&store).unwrap();
+    data["bookmarks"][&rust]["tags"] = serde_json::json!(["lang", "systems"]);
+    data["bookmarks"][&rust]["favicon"] = serde_json::json!("https://www.rust-lang.org/icon.png");
+    let manager = BookmarkManager::new(None);
+    manager.import(&data.to_string()).unwrap();
+    manager
+}
+
+// ========== Format Detection ==========
+
+#[rstest]
+#[case(CHROME_HTML, Some(BookmarkFormat::NetscapeHtml))]
+#[case(FIREFOX_HTML, Some(BookmarkFormat::NetscapeHtml))]
+#[case(CHROME_JSON, Some(BookmarkFormat::ChromeJson))]
+#[case(FIREFOX_JSON, Some(BookmarkFormat::FirefoxJson))]
+#[case(r#"{"bookmarks": {}, "folders": {}}"#, Some(BookmarkFormat::Native))]
+#[case(
+    "\u{feff}<!DOCTYPE NETSCAPE-Bookmark-file-1>",
+    Some(BookmarkFormat::NetscapeHtml)
+)]
+#[case("<html><body>hello</body></html>", None)]
+#[case("{\"foo\": 1}", None)]
+#[case("not bookmarks", None)]
+fn detect_format(#[case] content: &str, #[case] expected: Option<BookmarkFormat>) {
+    assert_eq!(BookmarkFormat::detect(content), expected);
+}
+
+#[rstest]
+#[case(0)]
+#[case(1)]
+#[case(2)]
+fn detect_format_with_non_ascii_head(#[case] padding: usize) {
+    // Multi-byte characters straddle the 512th byte for one of the paddings
+    let content = format!(
+        "<!DOCTYPE NETSCAPE-Bookmark-file-1>{}\n<DL><p>\n<DT><H3>{}</H3>\n</DL>",
+        " ".repeat(padding),
+        "书签文件夹".repeat(60)
+    );
+    assert_eq!(
+        BookmarkFormat::detect(&content),
+        Some(BookmarkFormat::NetscapeHtml)
+    );
+}
+
+#[rstest]
+fn format_extension() {
+    assert_eq!(BookmarkFormat::NetscapeHtml.extension(), "html");
+    assert_eq!(BookmarkFormat::ChromeJson.extension(), "json");
+}
+
+#[rstest]
+fn format_serde() {
+    let json = serde_json::to_string(&BookmarkFormat::NetscapeHtml).unwrap();
+    assert_eq!(json, "\"netscapeHtml\"");
+}
+
+// ========== Netscape HTML Import ==========
+
+#[rstest]
+fn import_chrome_html() {
+    let manager = BookmarkManager::new(None);
+    let summary = manager
+        .import_from(BookmarkFormat::NetscapeHtml, CHROME_HTML)
+        .unwrap();
+
+    assert_eq!(
+        summary,
+        ImportSummary {
+            added: 4,
+            duplicates: 0,
+            folders_created: 1,
+            folders_merged: 0,
+        }
+    );
+
+    assert_eq!(titles_in(&manager, BOOKMARKS_BAR), vec!["GitHub"]);
+    let rust = folder_named(&manager, "Rust & Friends");
+    assert_eq!(
+        manager.get_folder(&rust).unwrap().parent_id.as_deref(),
+        Some(BOOKMARKS_BAR)
+    );
+    assert_eq!(titles_in(&manager, &rust), vec!["Rust", "Docs.rs"]);
+
+    let docs = manager.find_by_url("https://docs.rs/?q=a&b").unwrap();
+    assert_eq!(docs.created_at.timestamp(), 1_700_000_004);
+
+    let github = manager.find_by_url("https://github.com/").unwrap();
+    assert_eq!(github.favicon.as_deref(), Some("data:image/png;base64,AAA"));
+
+    // Items outside any folder stay at the root
+    assert_eq!(
+        manager
+            .root_bookmarks()
+            .into_iter()
+            .map(|b| b.title)
+            .collect::<Vec<_>>(),
+        vec!["Example"]
+    );
+}
+
+#[rstest]
+fn import_firefox_html() {
+    let manager = BookmarkManager::new(None);
+    let summary = manager
+        .import_from(BookmarkFormat::NetscapeHtml, FIREFOX_HTML)
+        .unwrap();
+
+    // The place: query is skipped
+    assert_eq!(summary.added, 3);
+    assert_eq!(summary.folders_created, 0);
+
+    let mozilla = manager.find_by_url("https://mozilla.org/").unwrap();
+    assert_eq!(mozilla.tags, vec!["browser", "oss"]);
+    assert_eq!(mozilla.created_at.timestamp(), 1_700_000_010);
+    assert_eq!(mozilla.modified_at.timestamp(), 1_700_000_020);
+    assert!(mozilla.parent_id.is_none());
+
+    let mdn = manager
+        .find_by_url("https://developer.mozilla.org/")
+        .unwrap();
+    assert_eq!(mdn.parent_id.as_deref(), Some(BOOKMARKS_BAR));
+    assert_eq!(
+        mdn.favicon.as_deref(),
+        Some("https://developer.mozilla.org/favicon.ico")
+    );
+
+    assert_eq!(titles_in(&manager, OTHER_BOOKMARKS), vec!["HN"]);
+    assert!(!manager.is_bookmarked("place:sort=8&maxResults=10"));
+}
+
+#[rstest]
+fn import_html_tolerates_unclosed_lists() {
+    let html = "<DL><p><DT><H3>Unclosed</H3><DL><p><DT><A HREF=\"https://a.com\">A</A>";
+    let manager = BookmarkManager::new(None);
+    let summary = manager
+        .import_from(BookmarkFormat::NetscapeHtml, html)
+        .unwrap();
+
+    assert_eq!(summary.added, 1);
+    let folder = folder_named(&manager, "Unclosed");
+    assert_eq!(titles_in(&manager, &folder), vec!["A"]);
+}
+
+#[rstest]
+fn import_html_empty_folder() {
+    let html = "<DL><p><DT><H3>Empty</H3><DT><A HREF=\"https://a.com\">A</A></DL>";
+    let manager = BookmarkManager::new(None);
+    let summary = manager
+        .import_from(BookmarkFormat::NetscapeHtml, html)
+        .unwrap();
+
+    assert_eq!(summary.folders_created, 1);
+    let folder = folder_named(&manager, "Empty");
+    assert!(manager.in_folder(&folder).is_empty());
+    assert_eq!(manager.root_bookmarks().len(), 1);
+}
+
+// ========== Chrome JSON Import ==========
+
+#[rstest]
+fn import_chrome_json() {
+    let manager = BookmarkManager::new(None);
+    let summary = manager
+        .import_from(BookmarkFormat::ChromeJson, CHROME_JSON)
+        .unwrap();
+
+    assert_eq!(summary.added, 3);
+    assert_eq!(summary.folders_created, 1);
+    // The empty mobile folder is not imported
+    assert_eq!(manager.folder_count(), 3);
+
+    assert_eq!(titles_in(&manager, BOOKMARKS_BAR), vec!["GitHub"]);
+    assert_eq!(titles_in(&manager, OTHER_BOOKMARKS), vec!["Example"]);
+    let dev = folder_named(&manager, "Dev");
+    assert_eq!(titles_in(&manager, &dev), vec!["Rust"]);
+
+    let github = manager.find_by_url("https://github.com/").unwrap();
+    assert_eq!(github.created_at.timestamp(), 1_700_526_400);
+}
+
+#[rstest]
+#[case("{}")]
+#[case("[]")]
+#[case(r#"{"roots": 1}"#)]
+fn import_chrome_json_invalid(#[case] content: &str) {
+    let manager = BookmarkManager::new(None);
+    let result = manager.import_from(BookmarkFormat::ChromeJson, content);
+    assert!(matches!(result, Err(BookmarkError::InvalidFormat(_))));
+}
+
+#[rstest]
+fn import_malformed_json() {
+    let manager = BookmarkManager::new(None);
+    let result = manager.import_from(BookmarkFormat::ChromeJson, "{ nope");
+    assert!(matches!(result, Err(BookmarkError::Serialization(_))));
+    assert_eq!(manager.count(), 0);
+}
+
+// ========== Firefox JSON Import ==========
+
+#[rstest]
+fn import_firefox_json() {
+    let manager = BookmarkManager::new(None);
+    let summary = manager
+        .import_from(BookmarkFormat::FirefoxJson, FIREFOX_JSON)
+        .unwrap();
+
+    // Separator, place: query and tag folder entries are skipped
+    assert_eq!(summary.added, 3);
+    // "Bookmarks Menu" and "Work"
+    assert_eq!(summary.folders_created, 2);
+
+    let menu = folder_named(&manager, "Bookmarks Menu");
+    assert_eq!(titles_in(&manager, &menu), vec!["Mozilla"]);
+    let mozilla = manager.find_by_url("https://mozilla.org/").unwrap();
+    assert_eq!(mozilla.tags, vec!["browser", "oss"]);
+    assert_eq!(mozilla.created_at.timestamp(), 1_700_000_010);
+    assert_eq!(mozilla.modified_at.timestamp(), 1_700_000_020);
+
+    let work = folder_named(&manager, "Work");
+    assert_eq!(
+        manager.get_folder(&work).unwrap().parent_id.as_deref(),
+        Some(BOOKMARKS_BAR)
+    );
+    assert_eq!(titles_in(&manager, &work), vec!["MDN"]);
+    assert_eq!(titles_in(&manager, OTHER_BOOKMARKS), vec!["HN"]);
+    assert!(manager.all_folders().iter().all(|f| f.name != "oss"));
+}
+
+#[rstest]
+fn import_firefox_json_invalid() {
+    let manager = BookmarkManager::new(None);
+    let result = manager.import_from(BookmarkFormat::FirefoxJson, CHROME_JSON);
+    assert!(matches!(result, Err(BookmarkError::InvalidFormat(_))));
+}
+
+// ========== Merging ==========
+
+#[rstest]
+#[case(BookmarkFormat::NetscapeHtml, CHROME_HTML)]
+#[case(BookmarkFormat::ChromeJson, CHROME_JSON)]
+#[case(BookmarkFormat::FirefoxJson, FIREFOX_JSON)]
+fn reimport_skips_duplicates(#[case] format: BookmarkFormat, #[case] content: &str) {
+    let manager = BookmarkManager::new(None);
+    let first = manager.import_from(format, content).unwrap();
+    let count = manager.count();
+    let folders = manager.folder_count();
+
+    let second = manager.import_from(format, content).unwrap();
+    assert_eq!(second.added, 0);
+    assert_eq!(second.duplicates, first.added);
+    assert_eq!(second.folders_created, 0);
+    assert_eq!(second.folders_merged, first.folders_created);
+    assert_eq!(manager.count(), count);
+    assert_eq!(manager.folder_count(), folders);
+}
+
+#[rstest]
+fn import_merges_into_existing_tree() {
+    let manager = BookmarkManager::new(None);
+    manager
+        .add_to_folder("https://existing.com/", "Existing", BOOKMARKS_BAR)
+        .unwrap();
+    let dev = manager.create_subfolder("Dev", BOOKMARKS_BAR).unwrap();
+    manager
+        .add_to_folder("https://www.rust-lang.org/", "My Rust", &dev)
+        .unwrap();
+
+    let summary = manager
+        .import_from(BookmarkFormat::ChromeJson, CHROME_JSON)
+        .unwrap();
+    assert_eq!(summary.added, 2);
+    assert_eq!(summary.duplicates, 1);
+    assert_eq!(summary.folders_merged, 1);
+    assert_eq!(summary.folders_created, 0);
+
+    // Imported items are appended after the existing ones
+    let bar: Vec<_> = manager
+        .tree()
+        .into_iter()
+        .find_map(|n| match n {
+            BookmarkNode::Folder(f) if f.is_bookmarks_bar() => Some(f.children),
+            _ => None,
+        })
+        .unwrap()
+        .into_iter()
+        .map(|n| match n {
+            BookmarkNode::Bookmark(b) => b.title,
+            BookmarkNode::Folder(f) => f.folder.name,
+        })
+        .collect();
+    assert_eq!(bar, vec!["Dev", "Existing", "GitHub"]);
+
+    // The existing bookmark keeps its title
+    assert_eq!(titles_in(&manager, &dev), vec!["My Rust"]);
+}
+
+#[rstest]
+fn duplicate_merges_tags() {
+    let manager = BookmarkManager::new(None);
+    manager.add("https://mozilla.org/", "Mozilla");
+
+    let summary = manager
+        .import_from(BookmarkFormat::NetscapeHtml, FIREFOX_HTML)
+        .unwrap();
+    assert_eq!(summary.duplicates, 1);
+
+    let mozilla = manager.find_by_url("https://mozilla.org/").unwrap();
+    assert_eq!(mozilla.tags, vec!["browser", "oss"]);
+    assert!(mozilla.parent_id.is_none());
+}
+
+#[rstest]
+fn duplicates_within_import() {
+    let html = r#"<DL><p>
+        <DT><A HREF="https://a.com/" TAGS="x">A</A>
+        <DT><H3>Folder</H3>
+        <DL><p><DT><A HREF="https://a.com/" TAGS="y">A again</A></DL><p>
+    </DL>"#;
+    let manager = BookmarkManager::new(None);
+    let summary = manager
+        .import_from(BookmarkFormat::NetscapeHtml, html)
+        .unwrap();
+
+    assert_eq!(summary.added, 1);
+    assert_eq!(summary.duplicates, 1);
+    let a = manager.find_by_url("https://a.com/").unwrap();
+    assert_eq!(a.title, "A");
+    assert_eq!(a.tags, vec!["x", "y"]);
+}
+
+// ========== Export Round Trips ==========
+
+#[rstest]
+#[case(BookmarkFormat::NetscapeHtml)]
+#[case(BookmarkFormat::ChromeJson)]
+#[case(BookmarkFormat::FirefoxJson)]
+#[case(BookmarkFormat::Native)]
+fn export_round_trip(#[case] format: BookmarkFormat) {
+    let source = populated();
+    let exported = source.export_to(format).unwrap();
+    assert_eq!(BookmarkFormat::detect(&exported), Some(format));
+
+    let target = BookmarkManager::new(None);
+    let summary = target.import_from(format, &exported).unwrap();
+    assert_eq!(summary.added, 4);
+    assert_eq!(summary.duplicates, 0);
+
+    assert_eq!(titles_in(&target, BOOKMARKS_BAR), vec!["GitHub"]);
+    let dev = folder_named(&target, "Dev");
+    assert_eq!(
+        target.get_folder(&dev).unwrap().parent_id.as_deref(),
+        Some(BOOKMARKS_BAR)
+    );
+    assert_eq!(titles_in(&target, &dev), vec!["Rust <3 & co"]);
+    assert_eq!(titles_in(&target, OTHER_BOOKMARKS)[0], "Example");
+
+    for original in source.all() {
+        let imported = target.find_by_url(&original.url).unwrap();
+        assert_eq!(imported.title, original.title);
+        // HTML dates have second precision
+        assert_eq!(
+            imported.created_at.timestamp(),
+            original.created_at.timestamp()
+        );
+    }
+}
+
+#[rstest]
+#[case(BookmarkFormat::NetscapeHtml)]
+#[case(BookmarkFormat::FirefoxJson)]
+#[case(BookmarkFormat::Native)]
+fn export_round_trip_tags(#[case] format: BookmarkFormat) {
+    let source = populated();
+    let exported = source.export_to(format).unwrap();
+
+    let target = BookmarkManager::new(None);
+    target.import_from(format, &exported).unwrap();
+
+    let rust = target.find_by_url("https://www.rust-lang.org/").unwrap();
+    assert_eq!(rust.tags, vec!["lang", "systems"]);
+    assert_eq!(
+        rust.favicon.as_deref(),
+        Some("https://www.rust-lang.org/icon.png")
+    );
+}
+
+#[rstest]
+fn export_html_escapes() {
+    let html = populated().export_to(BookmarkFormat::NetscapeHtml).unwrap();
+    assert!(html.starts_with("<!DOCTYPE NETSCAPE-Bookmark-file-1>"));
+    assert!(html.contains("PERSONAL_TOOLBAR_FOLDER=\"true\""));
+    assert!(html.contains("Rust &lt;3 &amp; co"));
+    assert!(html.contains("TAGS=\"lang,systems\""));
+}
+
+#[rstest]
+fn export_chrome_places_loose_items_in_other() {
+    let json = populated().export_to(BookmarkFormat::ChromeJson).unwrap();
+    let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+    let other: Vec<_> = value["roots"]["other"]["children"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|c| c["name"].as_str().unwrap())
+        .collect();
+    assert_eq!(other, vec!["Example", "Loose"]);
+}
+
+#[rstest]
+fn tree_orders_special_folders_first() {
+    let manager = populated();
+    let tree = manager.tree();
+    assert_eq!(tree[0].id(), BOOKMARKS_BAR);
+    assert_eq!(tree[1].id(), OTHER_BOOKMARKS);
+    assert_eq!(
+        tree.iter().map(BookmarkNode::bookmark_count).sum::<usize>(),
+        4
+    );
+}
+
+// ========== Files ==========
+
+#[rstest]
+fn import_and_export_files() {
+    let dir = TempDir::new().unwrap();
+    let path = dir.path().join("bookmarks.html");
+    std::fs::write(&path, CHROME_HTML).unwrap();
+
+    let manager = BookmarkManager::new(Some(dir.path()));
+    let summary = manager.import_file(&path).unwrap();
+    assert_eq!(summary.added, 4);
+
+    // Imports are persisted
+    let reloaded = BookmarkManager::new(Some(dir.path()));
+    assert_eq!(reloaded.count(), 4);
+
+    let out = dir.path().join("export.json");
+    reloaded
+        .export_file(&out, BookmarkFormat::FirefoxJson)
+        .unwrap();
+    let target = BookmarkManager::new(None);
+    assert_eq!(target.import_file(&out).unwrap().added, 4);
+}
+
+#[rstest]
+fn import_file_unknown_format() {
+    let dir = TempDir::new().unwrap();
+    let path = dir.path().join("notes.txt");
+    std::fs::write(&path, "just some notes").unwrap();
+
+    let manager = BookmarkManager::new(None);
+    let result = manager.import_file(&path);
+    assert!(matches!(result, Err(BookmarkError::InvalidFormat(_))));
+}
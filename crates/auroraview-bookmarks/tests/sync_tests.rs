@@ -0,0 +1,591 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::thread;
+use std::time::Duration;
+
+use auroraview_bookmarks::{
+    BookmarkError, BookmarkManager, BookmarkNode, ChangeKind, FileSystemBackend, ItemKind,
+    RemoteState, SyncBackend,
+};
+use rstest::*;
+use tempfile::TempDir;
+
+const BOOKMARKS_BAR: &str = "bookmarks_bar";
+
+#[fixture]
+fn remote() -> TempDir {
+    TempDir::new().unwrap()
+}
+
+fn device(name: &str) -> BookmarkManager {
+    BookmarkManager::new(None).with_sync(name)
+}
+
+/// Make sure the next change gets a later timestamp
+fn tick() {
+    thread::sleep(Duration::from_millis(5));
+}
+
+fn title_of(manager: &BookmarkManager, url: &str) -> Option<String> {
+    manager.find_by_url(url).map(|b| b.title)
+}
+
+// ========== Change Log Tests ==========
+
+#[rstest]
+fn change_log_disabled_by_default() {
+    let manager = BookmarkManager::new(None);
+    manager.add("https://a.com", "A");
+    assert!(manager.pending_changes().is_empty());
+    assert!(manager.device_id().is_none());
+    assert!(manager.sync_revision().is_none());
+}
+
+#[rstest]
+fn change_log_records_operations() {
+    let manager = device("workstation-1");
+    assert_eq!(manager.device_id().as_deref(), Some("workstation-1"));
+
+    let id = manager.add("https://a.com", "A");
+    let folder = manager.create_folder("Refs");
+    manager.update(&id, Some("A2"), None).unwrap();
+    manager.move_to_folder(&id, Some(&folder)).unwrap();
+    manager.rename_folder(&folder, "References").unwrap();
+    manager.remove(&id);
+
+    let changes = manager.pending_changes();
+    let ops: Vec<_> = changes
+        .iter()
+        .map(|c| (c.item_id.as_str(), c.item, c.kind))
+        .collect();
+    assert_eq!(
+        ops,
+        vec![
+            (id.as_str(), ItemKind::Bookmark, ChangeKind::Add),
+            (folder.as_str(), ItemKind::Folder, ChangeKind::Add),
+            (id.as_str(), ItemKind::Bookmark, ChangeKind::Rename),
+            (id.as_str(), ItemKind::Bookmark, ChangeKind::Move),
+            (folder.as_str(), ItemKind::Folder, ChangeKind::Rename),
+            (id.as_str(), ItemKind::Bookmark, ChangeKind::Delete),
+        ]
+    );
+    assert!(changes.iter().all(|c| c.device_id == "workstation-1"));
+    assert!(changes.iter().all(|c| c.revision.is_none()));
+    assert!(changes.windows(2).all(|w| w[0].timestamp <= w[1].timestamp));
+}
+
+#[rstest]
+fn change_log_url_edit() {
+    let manager = device("d");
+    let id = manager.add("https://a.com", "A");
+    manager.update(&id, None, Some("https://b.com")).unwrap();
+    assert_eq!(manager.pending_changes()[1].kind, ChangeKind::Edit);
+}
+
+#[rstest]
+fn change_log_delete_folder_contents() {
+    let manager = device("d");
+    let folder = manager.create_folder("F");
+    let a = manager
+        .add_to_folder("https://a.com", "A", &folder)
+        .unwrap();
+    manager.delete_folder(&folder, true).unwrap();
+
+    let changes = manager.pending_changes();
+    let deleted: Vec<_> = changes
+        .iter()
+        .filter(|c| c.kind == ChangeKind::Delete)
+        .map(|c| c.item_id.clone())
+        .collect();
+    assert_eq!(deleted, vec![a, folder]);
+}
+
+#[rstest]
+fn change_log_change_serde() {
+    let manager = device("d");
+    manager.add("https://a.com", "A");
+    let json = serde_json::to_value(&manager.pending_changes()[0]).unwrap();
+    assert_eq!(json["kind"], "add");
+    assert_eq!(json["item"], "bookmark");
+    assert_eq!(json["deviceId"], "d");
+    assert!(json.get("revision").is_none());
+}
+
+// ========== Folder Move Tests ==========
+
+#[rstest]
+fn move_folder() {
+    let manager = device("d");
+    let parent = manager.create_folder("Parent");
+    let child = manager.create_folder("Child");
+
+    manager.move_folder(&child, Some(&parent)).unwrap();
+    assert_eq!(manager.subfolders(&parent).len(), 1);
+    assert_eq!(
+        manager.pending_changes().last().unwrap().kind,
+        ChangeKind::Move
+    );
+
+    manager.move_folder(&child, None).unwrap();
+    assert!(manager.get_folder(&child).unwrap().parent_id.is_none());
+}
+
+#[rstest]
+fn move_folder_into_descendant_fails() {
+    let manager = BookmarkManager::new(None);
+    let parent = manager.create_folder("Parent");
+    let child = manager.create_subfolder("Child", &parent).unwrap();
+
+    let result = manager.move_folder(&parent, Some(&child));
+    assert!(matches!(result, Err(BookmarkError::InvalidMove(_))));
+    let result = manager.move_folder(&parent, Some(&parent));
+    assert!(matches!(result, Err(BookmarkError::InvalidMove(_))));
+    let result = manager.move_folder(&parent, Some("missing"));
+    assert!(matches!(result, Err(BookmarkError::FolderNotFound(_))));
+}
+
+// ========== Sync Tests ==========
+
+#[rstest]
+fn sync_requires_enabling() {
+    let dir = TempDir::new().unwrap();
+    let manager = BookmarkManager::new(None);
+    let result = manager.sync(&FileSystemBackend::new(dir.path()));
+    assert!(matches!(result, Err(BookmarkError::Sync(_))));
+}
+
+#[rstest]
+fn first_sync_pushes(remote: TempDir) {
+    let backend = FileSystemBackend::new(remote.path());
+    let a = device("a");
+    a.add("https://a.com", "A");
+
+    let report = a.sync(&backend).unwrap();
+    assert_eq!(report.revision, 1);
+    assert_eq!(report.pushed, 1);
+    assert_eq!(report.pulled, 0);
+    assert!(a.pending_changes().is_empty());
+    assert_eq!(a.sync_revision(), Some(1));
+
+    let state = backend.pull().unwrap().unwrap();
+    assert_eq!(state.revision, 1);
+    assert_eq!(state.snapshot.bookmarks.len(), 1);
+    assert_eq!(state.changes.len(), 1);
+    assert_eq!(state.changes[0].revision, Some(1));
+    assert!(backend.state_path().exists());
+}
+
+#[rstest]
+fn sync_without_changes_is_noop(remote: TempDir) {
+    let backend = FileSystemBackend::new(remote.path());
+    let a = device("a");
+    a.add("https://a.com", "A");
+    a.sync(&backend).unwrap();
+
+    let report = a.sync(&backend).unwrap();
+    assert_eq!(report.revision, 1);
+    assert_eq!(report.pushed, 0);
+    assert_eq!(backend.pull().unwrap().unwrap().revision, 1);
+}
+
+#[rstest]
+fn sync_between_devices(remote: TempDir) {
+    let backend = FileSystemBackend::new(remote.path());
+    let a = device("a");
+    let b = device("b");
+
+    let folder = a.create_subfolder("Shots", BOOKMARKS_BAR).unwrap();
+    a.add_to_folder("https://a.com", "A", &folder).unwrap();
+    a.sync(&backend).unwrap();
+
+    b.add("https://b.com", "B");
+    let report = b.sync(&backend).unwrap();
+    assert_eq!(report.revision, 2);
+    assert_eq!(report.pulled, 2);
+    assert_eq!(report.pushed, 1);
+    assert_eq!(report.conflicts, 0);
+    assert_eq!(b.in_folder(&folder).len(), 1);
+    assert!(b.is_bookmarked("https://b.com"));
+
+    let report = a.sync(&backend).unwrap();
+    assert_eq!(report.pulled, 1);
+    assert_eq!(report.pushed, 0);
+    assert!(a.is_bookmarked("https://b.com"));
+    assert_eq!(a.count(), 2);
+    assert_eq!(a.folder_count(), 3);
+}
+
+#[rstest]
+fn sync_merges_independent_edits(remote: TempDir) {
+    let backend = FileSystemBackend::new(remote.path());
+    let a = device("a");
+    let b = device("b");
+    let id = a.add("https://a.com", "A");
+    let folder = a.create_folder("Refs");
+    a.sync(&backend).unwrap();
+    b.sync(&backend).unwrap();
+
+    a.update(&id, Some("Renamed"), None).unwrap();
+    b.move_to_folder(&id, Some(&folder)).unwrap();
+    a.sync(&backend).unwrap();
+    let report = b.sync(&backend).unwrap();
+    assert_eq!(report.conflicts, 0);
+
+    let merged = b.get(&id).unwrap();
+    assert_eq!(merged.title, "Renamed");
+    assert_eq!(merged.parent_id.as_deref(), Some(folder.as_str()));
+
+    a.sync(&backend).unwrap();
+    assert_eq!(a.get(&id).unwrap(), merged);
+}
+
+#[rstest]
+#[case::remote_later(false)]
+#[case::local_later(true)]
+fn sync_conflicting_rename_latest_wins(remote: TempDir, #[case] local_later: bool) {
+    let backend = FileSystemBackend::new(remote.path());
+    let a = device("a");
+    let b = device("b");
+    let id = a.add("https://a.com", "A");
+    a.sync(&backend).unwrap();
+    b.sync(&backend).unwrap();
+
+    if local_later {
+        a.update(&id, Some("From A"), None).unwrap();
+        tick();
+        b.update(&id, Some("From B"), None).unwrap();
+    } else {
+        b.update(&id, Some("From B"), None).unwrap();
+        tick();
+        a.update(&id, Some("From A"), None).unwrap();
+    }
+    a.sync(&backend).unwrap();
+    let report = b.sync(&backend).unwrap();
+    assert_eq!(report.conflicts, 1);
+
+    let expected = if local_later { "From B" } else { "From A" };
+    assert_eq!(b.get(&id).unwrap().title, expected);
+    a.sync(&backend).unwrap();
+    assert_eq!(a.get(&id).unwrap().title, expected);
+}
+
+#[rstest]
+fn sync_folder_rename_conflict(remote: TempDir) {
+    let backend = FileSystemBackend::new(remote.path());
+    let a = device("a");
+    let b = device("b");
+    let folder = a.create_folder("Refs");
+    a.sync(&backend).unwrap();
+    b.sync(&backend).unwrap();
+
+    b.rename_folder(&folder, "Old idea").unwrap();
+    tick();
+    a.rename_folder(&folder, "Reference").unwrap();
+    b.sync(&backend).unwrap();
+    a.sync(&backend).unwrap();
+    b.sync(&backend).unwrap();
+
+    assert_eq!(a.get_folder(&folder).unwrap().name, "Reference");
+    assert_eq!(b.get_folder(&folder).unwrap().name, "Reference");
+}
+
+#[rstest]
+fn sync_delete_propagates(remote: TempDir) {
+    let backend = FileSystemBackend::new(remote.path());
+    let a = device("a");
+    let b = device("b");
+    let id = a.add("https://a.com", "A");
+    a.sync(&backend).unwrap();
+    b.sync(&backend).unwrap();
+
+    a.remove(&id);
+    a.sync(&backend).unwrap();
+    b.sync(&backend).unwrap();
+    assert!(b.get(&id).is_none());
+}
+
+#[rstest]
+fn sync_edit_wins_over_delete(remote: TempDir) {
+    let backend = FileSystemBackend::new(remote.path());
+    let a = device("a");
+    let b = device("b");
+    let id = a.add("https://a.com", "A");
+    a.sync(&backend).unwrap();
+    b.sync(&backend).unwrap();
+
+    a.remove(&id);
+    b.update(&id, Some("Still needed"), None).unwrap();
+    a.sync(&backend).unwrap();
+    let report = b.sync(&backend).unwrap();
+    assert_eq!(report.conflicts, 1);
+    assert_eq!(
+        title_of(&b, "https://a.com").as_deref(),
+        Some("Still needed")
+    );
+
+    a.sync(&backend).unwrap();
+    assert_eq!(
+        title_of(&a, "https://a.com").as_deref(),
+        Some("Still needed")
+    );
+}
+
+#[rstest]
+fn sync_restores_deleted_folder_with_new_content(remote: TempDir) {
+    let backend = FileSystemBackend::new(remote.path());
+    let a = device("a");
+    let b = device("b");
+    let folder = a.create_folder("Refs");
+    a.sync(&backend).unwrap();
+    b.sync(&backend).unwrap();
+
+    a.delete_folder(&folder, true).unwrap();
+    b.add_to_folder("https://new.com", "New", &folder).unwrap();
+    a.sync(&backend).unwrap();
+    b.sync(&backend).unwrap();
+    a.sync(&backend).unwrap();
+
+    for manager in [&a, &b] {
+        assert!(manager.get_folder(&folder).is_some());
+        assert_eq!(manager.in_folder(&folder).len(), 1);
+    }
+}
+
+#[rstest]
+fn sync_breaks_move_cycles(remote: TempDir) {
+    let backend = FileSystemBackend::new(remote.path());
+    let a = device("a");
+    let b = device("b");
+    let x = a.create_folder("X");
+    let y = a.create_folder("Y");
+    a.sync(&backend).unwrap();
+    b.sync(&backend).unwrap();
+
+    a.move_folder(&x, Some(&y)).unwrap();
+    b.move_folder(&y, Some(&x)).unwrap();
+    a.sync(&backend).unwrap();
+    let report = b.sync(&backend).unwrap();
+    assert!(report.conflicts >= 1);
+
+    // One of the moves is undone, so every folder is reachable again
+    let px = b.get_folder(&x).unwrap().parent_id;
+    let py = b.get_folder(&y).unwrap().parent_id;
+    assert!(px.is_none() != py.is_none());
+    assert_eq!(
+        b.tree().iter().map(count_folders).sum::<usize>(),
+        b.folder_count()
+    );
+}
+
+fn count_folders(node: &BookmarkNode) -> usize {
+    match node {
+        BookmarkNode::Bookmark(_) => 0,
+        BookmarkNode::Folder(f) => 1 + f.children.iter().map(count_folders).sum::<usize>(),
+    }
+}
+
+#[rstest]
+fn sync_dedups_urls_added_on_both_devices(remote: TempDir) {
+    let backend = FileSystemBackend::new(remote.path());
+    let a = device("a");
+    let b = device("b");
+    a.sync(&backend).unwrap();
+    b.sync(&backend).unwrap();
+
+    let from_a = a.add("https://same.com", "Same");
+    b.add("https://same.com", "Same");
+    a.sync(&backend).unwrap();
+    b.sync(&backend).unwrap();
+
+    assert_eq!(b.count(), 1);
+    assert_eq!(b.all()[0].id, from_a);
+}
+
+#[rstest]
+fn sync_merges_tags(remote: TempDir) {
+    let backend = FileSystemBackend::new(remote.path());
+    let a = device("a");
+    let b = device("b");
+    let json = r#"{"bookmarks": {"x": {"id": "x", "title": "X", "url": "https://x.com",
+        "created_at": "2024-01-01T00:00:00Z", "modified_at": "2024-01-01T00:00:00Z",
+        "tags": ["keep", "drop"]}}, "folders": {}}"#;
+    a.import(json).unwrap();
+    a.sync(&backend).unwrap();
+    b.sync(&backend).unwrap();
+
+    // Each device edits the tags through a native re-import
+    let edit = |manager: &BookmarkManager, tags: &str| {
+        let mut data: serde_json::Value = serde_json::from_str(&manager.export().unwrap()).unwrap();
+        data["bookmarks"]["x"]["tags"] = serde_json::from_str(tags).unwrap();
+        manager.import(&data.to_string()).unwrap();
+    };
+    edit(&a, r#"["keep", "from-a"]"#);
+    edit(&b, r#"["keep", "drop", "from-b"]"#);
+    a.sync(&backend).unwrap();
+    b.sync(&backend).unwrap();
+
+    assert_eq!(b.get("x").unwrap().tags, vec!["keep", "from-b", "from-a"]);
+}
+
+#[rstest]
+fn sync_state_persists(remote: TempDir) {
+    let data = TempDir::new().unwrap();
+    let backend = FileSystemBackend::new(remote.path());
+
+    let a = BookmarkManager::new(Some(data.path())).with_sync("a");
+    a.add("https://a.com", "A");
+    drop(a);
+    assert!(data.path().join("bookmarks-sync-state.json").exists());
+
+    // Pending changes survive a restart
+    let a = BookmarkManager::new(Some(data.path())).with_sync("a");
+    assert_eq!(a.pending_changes().len(), 1);
+    a.sync(&backend).unwrap();
+
+    let a = BookmarkManager::new(Some(data.path())).with_sync("a");
+    assert!(a.pending_changes().is_empty());
+    assert_eq!(a.sync_revision(), Some(1));
+}
+
+#[rstest]
+fn sync_after_remote_reset(remote: TempDir) {
+    let backend = FileSystemBackend::new(remote.path());
+    let a = device("a");
+    a.add("https://a.com", "A");
+    a.sync(&backend).unwrap();
+
+    std::fs::remove_file(backend.state_path()).unwrap();
+    let report = a.sync(&backend).unwrap();
+    assert_eq!(report.revision, 1);
+    assert_eq!(a.count(), 1);
+    assert_eq!(backend.pull().unwrap().unwrap().snapshot.bookmarks.len(), 1);
+}
+
+/// Lets another device sync right before the first push
+struct Racing<'a> {
+    inner: FileSystemBackend,
+    other: &'a BookmarkManager,
+    raced: AtomicBool,
+}
+
+impl SyncBackend for Racing<'_> {
+    fn pull(&self) -> auroraview_bookmarks::Result<Option<RemoteState>> {
+        self.inner.pull()
+    }
+
+    fn push(&self, state: &RemoteState, expected: u64) -> auroraview_bookmarks::Result<()> {
+        if !self.raced.swap(true, Ordering::SeqCst) {
+            self.other.sync(&self.inner)?;
+        }
+        self.inner.push(state, expected)
+    }
+}
+
+#[rstest]
+fn sync_retries_after_concurrent_push(remote: TempDir) {
+    let a = device("a");
+    let b = device("b");
+    a.add("https://a.com", "A");
+    b.add("https://b.com", "B");
+
+    let racing = Racing {
+        inner: FileSystemBackend::new(remote.path()),
+        other: &b,
+        raced: AtomicBool::new(false),
+    };
+    let report = a.sync(&racing).unwrap();
+    assert_eq!(report.revision, 2);
+    assert!(a.is_bookmarked("https://b.com"));
+    assert_eq!(a.count(), 2);
+}
+
+#[rstest]
+fn sync_keeps_edits_made_during_sync(remote: TempDir) {
+    struct Editing<'a> {
+        inner: FileSystemBackend,
+        manager: &'a BookmarkManager,
+    }
+
+    impl SyncBackend for Editing<'_> {
+        fn pull(&self) -> auroraview_bookmarks::Result<Option<RemoteState>> {
+            self.inner.pull()
+        }
+
+        fn push(&self, state: &RemoteState, expected: u64) -> auroraview_bookmarks::Result<()> {
+            self.manager.add("https://late.com", "Late");
+            self.inner.push(state, expected)
+        }
+    }
+
+    let a = device("a");
+    a.add("https://a.com", "A");
+    let backend = Editing {
+        inner: FileSystemBackend::new(remote.path()),
+        manager: &a,
+    };
+    a.sync(&backend).unwrap();
+
+    assert!(a.is_bookmarked("https://late.com"));
+    assert_eq!(a.pending_changes().len(), 1);
+    assert_eq!(
+        backend
+            .inner
+            .pull()
+            .unwrap()
+            .unwrap()
+            .snapshot
+            .bookmarks
+            .len(),
+        1
+    );
+}
+
+// ========== File System Backend Tests ==========
+
+#[rstest]
+fn fs_backend_empty(remote: TempDir) {
+    let backend = FileSystemBackend::new(remote.path().join("nested"));
+    assert!(backend.pull().unwrap().is_none());
+}
+
+#[rstest]
+fn fs_backend_rejects_stale_revision(remote: TempDir) {
+    let backend = FileSystemBackend::new(remote.path());
+    let state = RemoteState {
+        revision: 1,
+        ..RemoteState::default()
+    };
+    backend.push(&state, 0).unwrap();
+
+    let result = backend.push(&state, 0);
+    assert!(matches!(
+        result,
+        Err(BookmarkError::SyncConflict {
+            expected: 0,
+            found: 1
+        })
+    ));
+}
+
+#[rstest]
+fn fs_backend_lock_timeout(remote: TempDir) {
+    let backend =
+        FileSystemBackend::new(remote.path()).with_lock_timeout(Duration::from_millis(100));
+    std::fs::write(remote.path().join("bookmarks-sync.lock"), "123").unwrap();
+
+    let result = backend.push(&RemoteState::default(), 0);
+    assert!(matches!(result, Err(BookmarkError::Sync(_))));
+    assert!(backend.pull().unwrap().is_none());
+}
+
+#[rstest]
+fn fs_backend_releases_lock(remote: TempDir) {
+    let backend = FileSystemBackend::new(remote.path());
+    backend.push(&RemoteState::default(), 0).unwrap();
+    assert!(!remote.path().join("bookmarks-sync.lock").exists());
+
+    let files: Vec<_> = std::fs::read_dir(remote.path())
+        .unwrap()
+        .map(|e| e.unwrap().file_name().into_string().unwrap())
+        .collect();
+    assert_eq!(files, vec!["bookmarks-sync.json"]);
+}
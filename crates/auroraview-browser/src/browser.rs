@@ -17,7 +17,8 @@ use wry::WebViewExtWindows;
 use crate::config::BrowserConfig;
 use crate::devtools::DevToolsManager;
 use crate::extensions::ExtensionRegistry;
-use crate::navigation::{BookmarkManager, HistoryManager};
+use crate::navigation::omnibox::{OpenTab, OpenTabProvider};
+use crate::navigation::{BookmarkManager, HistoryManager, OmniboxEngine, OmniboxResult};
 use crate::tab::{TabEvent, TabId, TabManager, TabState};
 use crate::{BrowserError, Extension, Result};
 
@@ -99,6 +100,7 @@ pub struct Browser {
     tabs: Rc<TabManager>,
     bookmarks: BookmarkManager,
     history: HistoryManager,
+    omnibox: OmniboxEngine,
     extensions: ExtensionRegistry,
     devtools: DevToolsManager,
     controller_webview: Option<WryWebView>,
@@ -112,12 +114,21 @@ impl Browser {
 
         Self {
             tabs: Rc::new(TabManager::new(config.clone())),
-            bookmarks: BookmarkManager::new(config.user_data_dir.as_deref()),
-            history: HistoryManager::new(
-                config.user_data_dir.as_deref(),
-                10000,
-                config.features.history,
-            ),
+            bookmarks: if config.incognito {
+                BookmarkManager::in_memory()
+            } else {
+                BookmarkManager::new(config.user_data_dir.as_deref())
+            },
+            history: if config.incognito {
+                HistoryManager::in_memory(10000, config.features.history)
+            } else {
+                HistoryManager::new(
+                    config.user_data_dir.as_deref(),
+                    10000,
+                    config.features.history,
+                )
+            },
+            omnibox: OmniboxEngine::new(),
             extensions: ExtensionRegistry::new(config.features.extensions),
             devtools: DevToolsManager::new(config.devtools.clone()),
             config,
@@ -141,6 +152,30 @@ impl Browser {
         &self.history
     }
 
+    /// Get omnibox engine
+    pub fn omnibox(&self) -> &OmniboxEngine {
+        &self.omnibox
+    }
+
+    /// Get mutable omnibox engine (to add providers and search engines)
+    pub fn omnibox_mut(&mut self) -> &mut OmniboxEngine {
+        &mut self.omnibox
+    }
+
+    /// Suggest address bar completions from history, bookmarks and open tabs
+    pub fn suggest(&self, input: &str) -> OmniboxResult {
+        let active = self.tabs.active_tab_id();
+        let open_tabs = OpenTabProvider::new(
+            self.tabs
+                .tab_states()
+                .into_iter()
+                .filter(|tab| Some(&tab.id) != active.as_ref())
+                .map(|tab| OpenTab::new(tab.id, tab.url, tab.title).with_favicon(tab.favicon)),
+        );
+        self.omnibox
+            .suggest_with(input, &[&self.history, &self.bookmarks, &open_tabs])
+    }
+
     /// Get extension registry
     pub fn extensions(&self) -> &ExtensionRegistry {
         &self.extensions
@@ -299,6 +334,24 @@ impl Browser {
         }
     }
 
+    /// Send omnibox suggestions to controller WebView
+    fn sync_suggestions(&self, input: &str) {
+        if let Some(controller) = &self.controller_webview {
+            let data = serde_json::to_value(self.suggest(input)).unwrap_or_default();
+
+            let script = format!(
+                r#"(function() {{
+                    if (window.auroraview && window.auroraview.trigger) {{
+                        window.auroraview.trigger('omnibox:suggestions', {});
+                    }}
+                }})();"#,
+                data
+            );
+
+            let _ = controller.evaluate_script(&script);
+        }
+    }
+
     /// Bring controller WebView to top of z-order
     #[cfg(target_os = "windows")]
     fn bring_controller_to_top(&self) {
@@ -467,6 +520,14 @@ impl Browser {
                                 }
                             }
                         }
+                        "browser.suggest" => {
+                            let input = params.get("input").and_then(|v| v.as_str()).unwrap_or("");
+                            if let Some(proxy) = tabs_clone.event_proxy() {
+                                let _ = proxy.send_event(TabEvent::Suggest {
+                                    input: input.to_string(),
+                                });
+                            }
+                        }
                         "browser.go_back" => {
                             if let Some(proxy) = tabs_clone.event_proxy() {
                                 let _ = proxy.send_event(TabEvent::GoBack);
@@ -681,6 +742,9 @@ impl Browser {
                 let _ = self.tabs.home();
                 self.sync_tabs();
             }
+            TabEvent::Suggest { input } => {
+                self.sync_suggestions(&input);
+            }
             TabEvent::TitleChanged { tab_id, title } => {
                 self.tabs.update_title(&tab_id, title);
                 self.sync_tabs();
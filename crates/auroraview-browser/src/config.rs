@@ -1,8 +1,10 @@
 //! Browser configuration
 
 use crate::devtools::{DevToolsConfig, DockSide};
+use crate::profile::Profile;
 use crate::ui::Theme;
 use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
 
 /// Browser features toggle
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -58,6 +60,11 @@ pub struct BrowserConfig {
     pub initial_urls: Vec<String>,
     /// User data directory for persistence
     pub user_data_dir: Option<String>,
+    /// Keep bookmarks and history in memory only, and open tabs in
+    /// incognito WebViews (incognito profile)
+    pub incognito: bool,
+    /// WebView engine data directory shared by all tabs (engine default if None)
+    pub webview_data_dir: Option<PathBuf>,
     /// DevTools configuration
     pub devtools: DevToolsConfig,
     /// CDP remote debugging port (0 = disabled, typically 9222)
@@ -78,6 +85,8 @@ impl Default for BrowserConfig {
             debug: false,
             initial_urls: vec![],
             user_data_dir: None,
+            incognito: false,
+            webview_data_dir: None,
             devtools: DevToolsConfig::default(),
             remote_debugging_port: 0,
             frameless: true,
@@ -201,6 +210,16 @@ impl BrowserConfigBuilder {
         self
     }
 
+    /// Use a profile's data directory (or incognito mode)
+    pub fn profile(mut self, profile: &Profile) -> Self {
+        self.config.user_data_dir = profile
+            .data_dir()
+            .map(|dir| dir.to_string_lossy().into_owned());
+        self.config.incognito = profile.is_incognito();
+        self.config.webview_data_dir = profile.webview_data_dir();
+        self
+    }
+
     /// Enable/disable frameless window (no native title bar)
     pub fn frameless(mut self, frameless: bool) -> Self {
         self.config.frameless = frameless;
@@ -44,6 +44,18 @@ pub enum BrowserError {
     /// Extension error
     #[error("Extension error: {0}")]
     Extension(String),
+
+    /// Profile not found
+    #[error("Profile not found: {0}")]
+    ProfileNotFound(String),
+
+    /// Profile already exists
+    #[error("Profile already exists: {0}")]
+    ProfileExists(String),
+
+    /// Invalid profile name or operation
+    #[error("Invalid profile: {0}")]
+    InvalidProfile(String),
 }
 
 /// Result type alias for browser operations
@@ -35,6 +35,12 @@ impl ChromeExtensionBridge {
         }
     }
 
+    /// Create a bridge whose `chrome.cookies` uses the profile's cookie jar
+    pub fn for_profile(profile: &crate::Profile) -> crate::Result<Self> {
+        let plugin = ExtensionsPlugin::new().with_cookie_store(profile.cookie_jar()?);
+        Ok(Self::from_plugin(Arc::new(plugin)))
+    }
+
     /// Create from existing plugin instance
     pub fn from_plugin(plugin: Arc<ExtensionsPlugin>) -> Self {
         Self { plugin }
@@ -9,6 +9,8 @@
 //! - Navigation controls (back, forward, reload, home)
 //! - Bookmarks management
 //! - Browsing history
+//! - Omnibox suggestions ranked by frecency, with inline completion
+//! - Named profiles with separate state, plus incognito
 //! - Extension system
 //! - Theme customization (Light/Dark/System)
 //! - DevTools integration (F12)
@@ -69,6 +71,8 @@ pub mod error;
 pub mod extensions;
 /// Navigation controls, bookmarks, and history (built-in).
 pub mod navigation;
+/// Named profiles owning the data directories of all browser state.
+pub mod profile;
 /// Tab state, ordering, and lifecycle management.
 pub mod tab;
 /// Theme, toolbar, and UI customization.
@@ -126,6 +130,10 @@ pub use extensions::{
 };
 /// Built-in navigation types: bookmarks and history entries.
 pub use navigation::{Bookmark, BookmarkId, BookmarkManager, HistoryEntry, HistoryManager};
+/// Omnibox types: suggestion engine, search engines, and provider trait.
+pub use navigation::{OmniboxEngine, OmniboxResult, SearchEngine, Suggestion, SuggestionProvider};
+/// Profile types: profile handles, metadata, and manager.
+pub use profile::{Profile, ProfileInfo, ProfileManager};
 /// Tab management types: tab state, identifiers, and manager.
 pub use tab::{Tab, TabId, TabManager, TabState};
 
@@ -146,6 +146,14 @@ impl BookmarkManager {
         }
     }
 
+    /// Create a bookmark manager that is never saved to disk
+    pub fn in_memory() -> Self {
+        Self {
+            storage: RwLock::new(BookmarkStorage::default()),
+            data_path: None,
+        }
+    }
+
     /// Add a bookmark
     pub fn add(&self, bookmark: Bookmark) -> BookmarkId {
         let id = bookmark.id.clone();
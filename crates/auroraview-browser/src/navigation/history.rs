@@ -89,6 +89,15 @@ impl HistoryManager {
         }
     }
 
+    /// Create a history manager that is never saved to disk
+    pub fn in_memory(max_entries: usize, enabled: bool) -> Self {
+        Self {
+            storage: RwLock::new(HistoryStorage::new(max_entries)),
+            data_path: None,
+            enabled,
+        }
+    }
+
     /// Add a history entry
     pub fn add(&self, url: &str, title: &str) {
         if !self.enabled {
@@ -1,7 +1,12 @@
-//! Navigation module - bookmarks and history
+//! Navigation module - bookmarks, history and omnibox suggestions
 
 mod bookmarks;
 mod history;
+pub mod omnibox;
 
 pub use bookmarks::{Bookmark, BookmarkFolder, BookmarkId, BookmarkManager};
 pub use history::{HistoryEntry, HistoryManager};
+pub use omnibox::{
+    OmniboxEngine, OmniboxInput, OmniboxResult, SearchEngine, Suggestion, SuggestionKind,
+    SuggestionProvider,
+};
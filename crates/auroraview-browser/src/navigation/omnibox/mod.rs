@@ -0,0 +1,689 @@
+//! Omnibox (address bar) suggestion engine
+//!
+//! Ranks suggestions for the text typed into the address bar from several
+//! sources:
+//!
+//! - History, by frecency (visit count, typed count and recency)
+//! - Bookmarks
+//! - Open tabs ("switch to tab")
+//! - Search engines, including keyword searches (`w rust` searches the
+//!   engine registered with keyword `w`)
+//! - Any [`SuggestionProvider`] added by the application, such as extensions
+//!   using `chrome.omnibox`
+//!
+//! Matches for the same page are merged by normalized URL, and the best
+//! URL-prefix match is offered as an inline completion.
+//!
+//! ```rust,ignore
+//! let engine = OmniboxEngine::new()
+//!     .with_provider(my_provider)
+//!     .with_search_engine(SearchEngine::new("Wikipedia", WIKI_URL).with_keyword("w"));
+//!
+//! let result = engine.suggest_with("git", &[&history, &bookmarks]);
+//! assert_eq!(result.inline_completion.as_deref(), Some("hub.com"));
+//! ```
+
+mod providers;
+
+pub use providers::{OpenTab, OpenTabProvider};
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Highest score a history, bookmark or open tab match can reach
+pub const MAX_MATCH_SCORE: u32 = 1000;
+
+/// Score of a keyword search or extension keyword suggestion
+const KEYWORD_SCORE: u32 = 1500;
+/// Score of the typed text as a URL, when it looks like one
+const VERBATIM_URL_SCORE: u32 = 1150;
+/// Score of searching the typed text, when it does not look like a URL
+const DEFAULT_SEARCH_SCORE: u32 = 1100;
+/// Score of searching the typed text, when it looks like a URL
+const FALLBACK_SEARCH_SCORE: u32 = 300;
+
+/// Frecency a bookmark match is ranked as
+const BOOKMARK_FRECENCY: f64 = 140.0;
+/// Frecency an open tab match is ranked as
+const OPEN_TAB_FRECENCY: f64 = 200.0;
+
+/// Placeholder replaced by the query in search engine URLs
+pub const SEARCH_TERMS: &str = "{searchTerms}";
+
+/// Kind of suggestion
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum SuggestionKind {
+    /// Previously visited page
+    History,
+    /// Bookmarked page
+    Bookmark,
+    /// Page already open in a tab
+    OpenTab,
+    /// The typed text as a URL
+    Url,
+    /// Search with the default search engine
+    Search,
+    /// Keyword search or extension keyword suggestion
+    Keyword,
+    /// Suggestion from an application provider
+    Custom,
+}
+
+/// An omnibox suggestion
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Suggestion {
+    /// Kind of suggestion
+    pub kind: SuggestionKind,
+    /// Name of the provider that produced the suggestion
+    pub provider: String,
+    /// URL to open
+    ///
+    /// For extension keyword suggestions this is the content passed to
+    /// `chrome.omnibox.onInputEntered`.
+    pub url: String,
+    /// Title to display
+    pub title: String,
+    /// Favicon URL
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub favicon: Option<String>,
+    /// Relevance score (higher is better)
+    pub score: u32,
+    /// Tab that already shows this page
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub open_tab_id: Option<String>,
+    /// Whether the page is bookmarked
+    #[serde(default)]
+    pub bookmarked: bool,
+    /// Extension that produced the suggestion
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub extension_id: Option<String>,
+    /// Whether the URL may be offered as inline completion
+    #[serde(skip)]
+    pub allow_inline: bool,
+}
+
+impl Suggestion {
+    /// Create a new suggestion
+    pub fn new(
+        kind: SuggestionKind,
+        url: impl Into<String>,
+        title: impl Into<String>,
+        score: u32,
+    ) -> Self {
+        Self {
+            kind,
+            provider: String::new(),
+            url: url.into(),
+            title: title.into(),
+            favicon: None,
+            score,
+            open_tab_id: None,
+            bookmarked: matches!(kind, SuggestionKind::Bookmark),
+            extension_id: None,
+            allow_inline: false,
+        }
+    }
+
+    /// Set the provider name
+    pub fn with_provider(mut self, provider: impl Into<String>) -> Self {
+        self.provider = provider.into();
+        self
+    }
+
+    /// Set the favicon
+    pub fn with_favicon(mut self, favicon: Option<String>) -> Self {
+        self.favicon = favicon;
+        self
+    }
+
+    /// Mark the page as open in a tab
+    pub fn with_open_tab(mut self, tab_id: impl Into<String>) -> Self {
+        self.open_tab_id = Some(tab_id.into());
+        self
+    }
+
+    /// Set the extension that produced the suggestion
+    pub fn with_extension(mut self, extension_id: impl Into<String>) -> Self {
+        self.extension_id = Some(extension_id.into());
+        self
+    }
+
+    /// Allow or forbid inline completion
+    pub fn with_inline(mut self, allow: bool) -> Self {
+        self.allow_inline = allow;
+        self
+    }
+
+    /// Key identifying the page for deduplication
+    fn dedup_key(&self) -> String {
+        match &self.extension_id {
+            Some(extension_id) => format!("{}:{}", extension_id, self.url),
+            None => normalize_url(&self.url),
+        }
+    }
+
+    /// Fold a duplicate match of the same page into this one
+    fn absorb(&mut self, other: Suggestion) {
+        if other.score > self.score {
+            let weaker = std::mem::replace(self, other);
+            return self.absorb(weaker);
+        }
+        // Several sources agreeing on a page make it more relevant
+        self.score = (self.score + other.score / 4).min(self.score.max(MAX_MATCH_SCORE));
+        if self.title.is_empty() {
+            self.title = other.title;
+        }
+        if self.favicon.is_none() {
+            self.favicon = other.favicon;
+        }
+        if self.open_tab_id.is_none() {
+            self.open_tab_id = other.open_tab_id;
+        }
+        self.bookmarked |= other.bookmarked;
+        self.allow_inline |= other.allow_inline;
+    }
+}
+
+/// Text typed into the omnibox
+#[derive(Debug, Clone, PartialEq)]
+pub struct OmniboxInput {
+    text: String,
+    terms: Vec<String>,
+}
+
+impl OmniboxInput {
+    /// Parse input text
+    pub fn new(text: &str) -> Self {
+        let text = text.trim_start().to_string();
+        let terms = text.split_whitespace().map(str::to_lowercase).collect();
+        Self { text, terms }
+    }
+
+    /// Input text, without leading whitespace
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    /// Lowercased whitespace-separated terms
+    pub fn terms(&self) -> &[String] {
+        &self.terms
+    }
+
+    /// Check if nothing was typed
+    pub fn is_empty(&self) -> bool {
+        self.terms.is_empty()
+    }
+
+    /// Check if the input can be completed inline (a single term being typed)
+    pub fn allows_inline(&self) -> bool {
+        !self.text.is_empty() && !self.text.contains(char::is_whitespace)
+    }
+
+    /// Check if the input looks like a URL rather than a search
+    pub fn looks_like_url(&self) -> bool {
+        let text = self.text.trim_end();
+        if text.is_empty() || text.contains(char::is_whitespace) {
+            return false;
+        }
+        if text.contains("://") || text.starts_with("about:") || text.starts_with("file:") {
+            return true;
+        }
+        let host = host_of(text);
+        host.eq_ignore_ascii_case("localhost")
+            || host.starts_with("localhost:")
+            || (host.contains('.') && !host.starts_with('.') && !host.ends_with('.'))
+    }
+
+    /// The typed text as a URL
+    pub fn as_url(&self) -> String {
+        let text = self.text.trim_end();
+        if text.contains("://") || text.starts_with("about:") || text.starts_with("file:") {
+            text.to_string()
+        } else {
+            format!("https://{}", text)
+        }
+    }
+
+    /// Rate how well a page matches the input, from 0.0 to 1.0
+    ///
+    /// Every term must match the title or URL; `None` means no match.
+    /// Terms matching the start of the host rate highest, then terms matching
+    /// the start of a word, then anywhere.
+    pub fn match_quality(&self, title: &str, url: &str) -> Option<f64> {
+        if self.terms.is_empty() {
+            return None;
+        }
+        let title = title.to_lowercase();
+        let url = url.to_lowercase();
+        let address = strip_www(strip_scheme(&url));
+
+        let mut total = 0.0;
+        for term in &self.terms {
+            let term = term.as_str();
+            total += if [address, strip_scheme(&url), url.as_str()]
+                .iter()
+                .any(|a| a.starts_with(term))
+            {
+                1.0
+            } else if starts_word(&title, term) || starts_word(address, term) {
+                0.8
+            } else if title.contains(term) || url.contains(term) {
+                0.5
+            } else {
+                return None;
+            };
+        }
+        Some(total / self.terms.len() as f64)
+    }
+
+    /// Split off a leading keyword, returning the rest of the input
+    ///
+    /// `"w rust"` with keyword `"w"` gives `Some("rust")`.
+    pub fn strip_keyword(&self, keyword: &str) -> Option<&str> {
+        let (first, rest) = self.text.split_once(char::is_whitespace)?;
+        let rest = rest.trim();
+        (first.eq_ignore_ascii_case(keyword) && !rest.is_empty()).then_some(rest)
+    }
+}
+
+/// Source of omnibox suggestions
+///
+/// Implemented for the built-in history and bookmark managers and for open
+/// tabs; applications implement it to add their own sources. Providers score
+/// their suggestions with [`history_score`] so sources stay comparable.
+pub trait SuggestionProvider {
+    /// Provider name, recorded on suggestions that do not set one
+    fn name(&self) -> &str;
+
+    /// Return up to `limit` suggestions for the input
+    fn suggest(&self, input: &OmniboxInput, limit: usize) -> Vec<Suggestion>;
+}
+
+/// Frecency of a page: visits weighted by how recently the page was visited
+///
+/// Typed visits count one and a half times.
+pub fn frecency(
+    visit_count: u32,
+    typed_count: u32,
+    last_visit: DateTime<Utc>,
+    now: DateTime<Utc>,
+) -> f64 {
+    let recency = match (now - last_visit).num_days() {
+        ..=4 => 100.0,
+        5..=14 => 70.0,
+        15..=31 => 50.0,
+        32..=90 => 30.0,
+        _ => 10.0,
+    };
+    recency * (visit_count as f64 + 1.5 * typed_count as f64)
+}
+
+/// Score of a page match from its match quality and frecency
+pub fn history_score(quality: f64, frecency: f64) -> u32 {
+    let score = quality * (1.0 + frecency.max(0.0)).ln() * 100.0;
+    (score.round() as u32).min(MAX_MATCH_SCORE)
+}
+
+/// Normalize a URL for deduplication
+///
+/// Drops the scheme, a leading `www.`, the fragment and trailing slashes, and
+/// lowercases the host.
+pub fn normalize_url(url: &str) -> String {
+    let url = url.trim();
+    let url = url.split('#').next().unwrap_or(url);
+    let rest = strip_scheme(url);
+    let host = host_of(rest);
+    let path = &rest[host.len()..];
+    let host = host.to_ascii_lowercase();
+    format!("{}{}", strip_www(&host), path.trim_end_matches('/'))
+}
+
+/// A search engine
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SearchEngine {
+    /// Display name
+    pub name: String,
+    /// Keyword that selects this engine (`w rust`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub keyword: Option<String>,
+    /// URL template containing `{searchTerms}`
+    pub url: String,
+}
+
+impl SearchEngine {
+    /// Create a new search engine
+    pub fn new(name: impl Into<String>, url: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            keyword: None,
+            url: url.into(),
+        }
+    }
+
+    /// Google search
+    pub fn google() -> Self {
+        Self::new("Google", "https://www.google.com/search?q={searchTerms}")
+    }
+
+    /// Set the keyword
+    pub fn with_keyword(mut self, keyword: impl Into<String>) -> Self {
+        self.keyword = Some(keyword.into());
+        self
+    }
+
+    /// URL searching for `query`
+    pub fn search_url(&self, query: &str) -> String {
+        self.url.replace(SEARCH_TERMS, &encode_query(query))
+    }
+}
+
+/// Suggestions for an input
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OmniboxResult {
+    /// The input the suggestions are for
+    pub input: String,
+    /// Text to append after the cursor, selected, so typing continues over it
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub inline_completion: Option<String>,
+    /// Suggestions, best first
+    pub suggestions: Vec<Suggestion>,
+}
+
+impl OmniboxResult {
+    /// The suggestion opened when Enter is pressed
+    pub fn default_match(&self) -> Option<&Suggestion> {
+        self.suggestions.first()
+    }
+}
+
+/// Omnibox suggestion engine
+pub struct OmniboxEngine {
+    providers: Vec<Box<dyn SuggestionProvider>>,
+    /// Search engines; the first one is the default
+    search_engines: Vec<SearchEngine>,
+    max_results: usize,
+}
+
+impl Default for OmniboxEngine {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl OmniboxEngine {
+    /// Create an engine searching with Google
+    pub fn new() -> Self {
+        Self {
+            providers: Vec::new(),
+            search_engines: vec![SearchEngine::google()],
+            max_results: 8,
+        }
+    }
+
+    /// Add a suggestion provider
+    pub fn with_provider(mut self, provider: impl SuggestionProvider + 'static) -> Self {
+        self.add_provider(provider);
+        self
+    }
+
+    /// Add a suggestion provider
+    pub fn add_provider(&mut self, provider: impl SuggestionProvider + 'static) {
+        self.providers.push(Box::new(provider));
+    }
+
+    /// Add a search engine
+    pub fn with_search_engine(mut self, engine: SearchEngine) -> Self {
+        self.search_engines.push(engine);
+        self
+    }
+
+    /// Replace the default search engine
+    pub fn with_default_search_engine(mut self, engine: SearchEngine) -> Self {
+        self.set_default_search_engine(engine);
+        self
+    }
+
+    /// Replace the default search engine
+    pub fn set_default_search_engine(&mut self, engine: SearchEngine) {
+        if self.search_engines.is_empty() {
+            self.search_engines.push(engine);
+        } else {
+            self.search_engines[0] = engine;
+        }
+    }
+
+    /// Set the maximum number of suggestions
+    pub fn with_max_results(mut self, max_results: usize) -> Self {
+        self.max_results = max_results;
+        self
+    }
+
+    /// Get the search engines
+    pub fn search_engines(&self) -> &[SearchEngine] {
+        &self.search_engines
+    }
+
+    /// Get the default search engine
+    pub fn default_search_engine(&self) -> Option<&SearchEngine> {
+        self.search_engines.first()
+    }
+
+    /// Suggest completions for the input
+    pub fn suggest(&self, text: &str) -> OmniboxResult {
+        self.suggest_with(text, &[])
+    }
+
+    /// Suggest completions, also asking providers borrowed for this call
+    pub fn suggest_with(&self, text: &str, extra: &[&dyn SuggestionProvider]) -> OmniboxResult {
+        let input = OmniboxInput::new(text);
+        let mut result = OmniboxResult {
+            input: text.to_string(),
+            ..Default::default()
+        };
+        if input.is_empty() {
+            return result;
+        }
+
+        let mut candidates = Vec::new();
+
+        for engine in &self.search_engines {
+            let Some(query) = engine
+                .keyword
+                .as_deref()
+                .and_then(|k| input.strip_keyword(k))
+            else {
+                continue;
+            };
+            candidates.push(
+                Suggestion::new(
+                    SuggestionKind::Keyword,
+                    engine.search_url(query),
+                    format!("Search {} for \"{}\"", engine.name, query),
+                    KEYWORD_SCORE,
+                )
+                .with_provider("search"),
+            );
+        }
+
+        let providers = self.providers.iter().map(|p| p.as_ref());
+        for provider in providers.chain(extra.iter().copied()) {
+            for mut suggestion in provider.suggest(&input, self.max_results) {
+                if suggestion.provider.is_empty() {
+                    suggestion.provider = provider.name().to_string();
+                }
+                candidates.push(suggestion);
+            }
+        }
+
+        let looks_like_url = input.looks_like_url();
+        if looks_like_url {
+            candidates.push(
+                Suggestion::new(SuggestionKind::Url, input.as_url(), "", VERBATIM_URL_SCORE)
+                    .with_provider("url"),
+            );
+        }
+        if let Some(engine) = self.default_search_engine() {
+            let query = input.text().trim_end();
+            let score = if looks_like_url {
+                FALLBACK_SEARCH_SCORE
+            } else {
+                DEFAULT_SEARCH_SCORE
+            };
+            candidates.push(
+                Suggestion::new(
+                    SuggestionKind::Search,
+                    engine.search_url(query),
+                    format!("{} - {} Search", query, engine.name),
+                    score,
+                )
+                .with_provider("search"),
+            );
+        }
+
+        let mut suggestions = merge(candidates);
+        // Stable: ties keep the order in which sources were asked
+        suggestions.sort_by_key(|s| std::cmp::Reverse(s.score));
+
+        if input.allows_inline() {
+            let inline = suggestions.iter().enumerate().find_map(|(i, s)| {
+                s.allow_inline
+                    .then(|| inline_completion(input.text(), &s.url))
+                    .flatten()
+                    .map(|completion| (i, completion))
+            });
+            if let Some((index, completion)) = inline {
+                let suggestion = suggestions.remove(index);
+                suggestions.insert(0, suggestion);
+                result.inline_completion = Some(completion).filter(|c| !c.is_empty());
+            }
+        }
+
+        suggestions.truncate(self.max_results);
+        result.suggestions = suggestions;
+        result
+    }
+}
+
+/// Merge suggestions for the same page, keeping first-seen order
+fn merge(candidates: Vec<Suggestion>) -> Vec<Suggestion> {
+    let mut index: HashMap<String, usize> = HashMap::new();
+    let mut merged: Vec<Suggestion> = Vec::new();
+    for suggestion in candidates {
+        match index.get(&suggestion.dedup_key()) {
+            Some(&i) => merged[i].absorb(suggestion),
+            None => {
+                index.insert(suggestion.dedup_key(), merged.len());
+                merged.push(suggestion);
+            }
+        }
+    }
+    merged
+}
+
+/// Rest of `url` after the typed text, if the URL starts with it
+///
+/// The scheme and `www.` may be left out of the typed text.
+fn inline_completion(typed: &str, url: &str) -> Option<String> {
+    let without_scheme = strip_scheme(url);
+    [strip_www(without_scheme), without_scheme, url]
+        .into_iter()
+        .find_map(|candidate| {
+            let candidate = candidate.trim_end_matches('/');
+            let prefix = candidate.get(..typed.len())?;
+            prefix
+                .eq_ignore_ascii_case(typed)
+                .then(|| candidate[typed.len()..].to_string())
+        })
+}
+
+fn strip_scheme(url: &str) -> &str {
+    match url.find("://") {
+        Some(i)
+            if url[..i]
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || matches!(c, '+' | '-' | '.')) =>
+        {
+            &url[i + 3..]
+        }
+        _ => url,
+    }
+}
+
+fn strip_www(address: &str) -> &str {
+    match address.get(..4) {
+        Some(prefix) if prefix.eq_ignore_ascii_case("www.") => &address[4..],
+        _ => address,
+    }
+}
+
+/// Host (and port) of a URL without scheme
+fn host_of(address: &str) -> &str {
+    let end = address.find(['/', '?', '#']).unwrap_or(address.len());
+    &address[..end]
+}
+
+/// Check if a word of `text` starts with `term`
+fn starts_word(text: &str, term: &str) -> bool {
+    text.split(|c: char| !c.is_alphanumeric())
+        .any(|word| word.starts_with(term))
+}
+
+/// Percent-encode a search query, with `+` for spaces
+pub(crate) fn encode_query(query: &str) -> String {
+    let mut result = String::new();
+    for c in query.chars() {
+        match c {
+            'a'..='z' | 'A'..='Z' | '0'..='9' | '-' | '_' | '.' | '~' => result.push(c),
+            ' ' => result.push('+'),
+            _ => {
+                let mut buf = [0; 4];
+                for b in c.encode_utf8(&mut buf).as_bytes() {
+                    result.push_str(&format!("%{:02X}", b));
+                }
+            }
+        }
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize_url() {
+        assert_eq!(normalize_url("https://www.GitHub.com/"), "github.com");
+        assert_eq!(
+            normalize_url("http://github.com/rust-lang/rust#readme"),
+            "github.com/rust-lang/rust"
+        );
+        assert_eq!(normalize_url("github.com/Path"), "github.com/Path");
+    }
+
+    #[test]
+    fn test_inline_completion() {
+        let url = "https://www.github.com/";
+        assert_eq!(inline_completion("git", url).as_deref(), Some("hub.com"));
+        assert_eq!(
+            inline_completion("WWW.G", url).as_deref(),
+            Some("ithub.com")
+        );
+        assert_eq!(
+            inline_completion("https://www.g", url).as_deref(),
+            Some("ithub.com")
+        );
+        assert_eq!(inline_completion("gitlab", url), None);
+    }
+
+    #[test]
+    fn test_encode_query() {
+        assert_eq!(encode_query("rust lang"), "rust+lang");
+        assert_eq!(encode_query("a&b=c"), "a%26b%3Dc");
+        assert_eq!(encode_query("é"), "%C3%A9");
+    }
+}
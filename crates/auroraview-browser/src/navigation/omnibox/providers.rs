@@ -0,0 +1,310 @@
+//! Suggestion providers for the built-in and modular managers
+
+use super::{
+    frecency, history_score, OmniboxInput, Suggestion, SuggestionKind, SuggestionProvider,
+    BOOKMARK_FRECENCY, OPEN_TAB_FRECENCY,
+};
+use crate::navigation::{BookmarkManager, HistoryManager};
+use chrono::Utc;
+
+/// Keep the `limit` best suggestions
+fn best(mut suggestions: Vec<Suggestion>, limit: usize) -> Vec<Suggestion> {
+    suggestions.sort_by_key(|s| std::cmp::Reverse(s.score));
+    suggestions.truncate(limit);
+    suggestions
+}
+
+impl SuggestionProvider for HistoryManager {
+    fn name(&self) -> &str {
+        "history"
+    }
+
+    fn suggest(&self, input: &OmniboxInput, limit: usize) -> Vec<Suggestion> {
+        let now = Utc::now();
+        let suggestions = self
+            .all()
+            .into_iter()
+            .filter_map(|entry| {
+                let quality = input.match_quality(&entry.title, &entry.url)?;
+                let frecency = frecency(entry.visit_count, 0, entry.visited_at, now);
+                Some(
+                    Suggestion::new(
+                        SuggestionKind::History,
+                        entry.url,
+                        entry.title,
+                        history_score(quality, frecency),
+                    )
+                    .with_favicon(entry.favicon)
+                    // A single visit is not enough to complete inline
+                    .with_inline(entry.visit_count >= 2),
+                )
+            })
+            .collect();
+        best(suggestions, limit)
+    }
+}
+
+impl SuggestionProvider for BookmarkManager {
+    fn name(&self) -> &str {
+        "bookmarks"
+    }
+
+    fn suggest(&self, input: &OmniboxInput, limit: usize) -> Vec<Suggestion> {
+        let suggestions = self
+            .all()
+            .into_iter()
+            .filter_map(|bookmark| {
+                let quality = input.match_quality(&bookmark.title, &bookmark.url)?;
+                Some(
+                    Suggestion::new(
+                        SuggestionKind::Bookmark,
+                        bookmark.url,
+                        bookmark.title,
+                        history_score(quality, BOOKMARK_FRECENCY),
+                    )
+                    .with_favicon(bookmark.favicon)
+                    .with_inline(true),
+                )
+            })
+            .collect();
+        best(suggestions, limit)
+    }
+}
+
+/// An open tab offered as "switch to tab"
+#[derive(Debug, Clone, PartialEq)]
+pub struct OpenTab {
+    /// Tab ID
+    pub tab_id: String,
+    /// Current URL
+    pub url: String,
+    /// Current title
+    pub title: String,
+    /// Favicon URL
+    pub favicon: Option<String>,
+}
+
+impl OpenTab {
+    /// Create a new open tab
+    pub fn new(
+        tab_id: impl Into<String>,
+        url: impl Into<String>,
+        title: impl Into<String>,
+    ) -> Self {
+        Self {
+            tab_id: tab_id.into(),
+            url: url.into(),
+            title: title.into(),
+            favicon: None,
+        }
+    }
+
+    /// Set favicon
+    pub fn with_favicon(mut self, favicon: Option<String>) -> Self {
+        self.favicon = favicon;
+        self
+    }
+}
+
+/// Suggests open tabs
+#[derive(Debug, Clone, Default)]
+pub struct OpenTabProvider {
+    tabs: Vec<OpenTab>,
+}
+
+impl OpenTabProvider {
+    /// Create a provider for a set of tabs
+    pub fn new(tabs: impl IntoIterator<Item = OpenTab>) -> Self {
+        Self {
+            tabs: tabs.into_iter().collect(),
+        }
+    }
+}
+
+impl SuggestionProvider for OpenTabProvider {
+    fn name(&self) -> &str {
+        "tabs"
+    }
+
+    fn suggest(&self, input: &OmniboxInput, limit: usize) -> Vec<Suggestion> {
+        let suggestions = self
+            .tabs
+            .iter()
+            .filter_map(|tab| {
+                let quality = input.match_quality(&tab.title, &tab.url)?;
+                Some(
+                    Suggestion::new(
+                        SuggestionKind::OpenTab,
+                        tab.url.clone(),
+                        tab.title.clone(),
+                        history_score(quality, OPEN_TAB_FRECENCY),
+                    )
+                    .with_favicon(tab.favicon.clone())
+                    .with_open_tab(tab.tab_id.clone()),
+                )
+            })
+            .collect();
+        best(suggestions, limit)
+    }
+}
+
+#[cfg(feature = "modular-history")]
+impl SuggestionProvider for auroraview_history::HistoryManager {
+    fn name(&self) -> &str {
+        "history"
+    }
+
+    fn suggest(&self, input: &OmniboxInput, limit: usize) -> Vec<Suggestion> {
+        let Some(first) = input.terms().first() else {
+            return Vec::new();
+        };
+        let now = Utc::now();
+        let suggestions = self
+            .search(first)
+            .into_iter()
+            .filter_map(|result| {
+                let entry = result.entry;
+                let quality = input.match_quality(&entry.title, &entry.url)?;
+                let frecency =
+                    frecency(entry.visit_count, entry.typed_count, entry.last_visit, now);
+                Some(
+                    Suggestion::new(
+                        SuggestionKind::History,
+                        entry.url,
+                        entry.title,
+                        history_score(quality, frecency),
+                    )
+                    .with_favicon(entry.favicon)
+                    .with_inline(entry.typed_count > 0 || entry.visit_count >= 2),
+                )
+            })
+            .collect();
+        best(suggestions, limit)
+    }
+}
+
+#[cfg(feature = "modular-bookmarks")]
+impl SuggestionProvider for auroraview_bookmarks::BookmarkManager {
+    fn name(&self) -> &str {
+        "bookmarks"
+    }
+
+    fn suggest(&self, input: &OmniboxInput, limit: usize) -> Vec<Suggestion> {
+        let Some(first) = input.terms().first() else {
+            return Vec::new();
+        };
+        let suggestions = self
+            .search(first)
+            .into_iter()
+            .filter_map(|bookmark| {
+                let quality = input
+                    .match_quality(&bookmark.title, &bookmark.url)
+                    .or_else(|| {
+                        // Every term matches a tag
+                        input
+                            .terms()
+                            .iter()
+                            .all(|term| {
+                                bookmark
+                                    .tags
+                                    .iter()
+                                    .any(|tag| tag.to_lowercase().contains(term.as_str()))
+                            })
+                            .then_some(0.6)
+                    })?;
+                Some(
+                    Suggestion::new(
+                        SuggestionKind::Bookmark,
+                        bookmark.url,
+                        bookmark.title,
+                        history_score(quality, BOOKMARK_FRECENCY),
+                    )
+                    .with_favicon(bookmark.favicon)
+                    .with_inline(true),
+                )
+            })
+            .collect();
+        best(suggestions, limit)
+    }
+}
+
+#[cfg(feature = "modular-tabs")]
+impl SuggestionProvider for auroraview_tabs::TabManager {
+    fn name(&self) -> &str {
+        "tabs"
+    }
+
+    /// Suggests every tab except the active one
+    fn suggest(&self, input: &OmniboxInput, limit: usize) -> Vec<Suggestion> {
+        let active = self.active_id();
+        let tabs = self
+            .all()
+            .into_iter()
+            .filter(|tab| Some(&tab.id) != active.as_ref())
+            .map(|tab| OpenTab::new(tab.id, tab.url, tab.title).with_favicon(tab.favicon));
+        OpenTabProvider::new(tabs).suggest(input, limit)
+    }
+}
+
+/// Routes keyword input to the extension that registered the keyword
+///
+/// Offers the extension's default suggestion (with `%s` replaced by the text
+/// after the keyword) and the suggestions it last sent from
+/// `chrome.omnibox.onInputChanged`. Register the extension host's instance
+/// (`ExtensionHost::omnibox`), which holds the keywords of loaded extensions.
+#[cfg(feature = "modular-extensions")]
+impl SuggestionProvider for auroraview_extensions::apis::omnibox::OmniboxApi {
+    fn name(&self) -> &str {
+        "extensions"
+    }
+
+    fn suggest(&self, input: &OmniboxInput, limit: usize) -> Vec<Suggestion> {
+        let Some((extension_id, text)) = self.parse_input(input.text()) else {
+            return Vec::new();
+        };
+
+        let mut suggestions = Vec::new();
+        if let Some(default) = self.get_default_suggestion(&extension_id) {
+            let title = strip_markup(&default.description.replace("%s", &text));
+            suggestions.push(
+                Suggestion::new(
+                    SuggestionKind::Keyword,
+                    text.clone(),
+                    title,
+                    super::KEYWORD_SCORE,
+                )
+                .with_extension(extension_id.clone()),
+            );
+        }
+        for (i, result) in self.get_suggestions(&extension_id).into_iter().enumerate() {
+            let score = super::KEYWORD_SCORE.saturating_sub(1 + i as u32);
+            suggestions.push(
+                Suggestion::new(
+                    SuggestionKind::Keyword,
+                    result.content,
+                    strip_markup(&result.description),
+                    score,
+                )
+                .with_extension(extension_id.clone()),
+            );
+        }
+        suggestions.truncate(limit);
+        suggestions
+    }
+}
+
+/// Remove the `<match>`, `<url>` and `<dim>` markup of extension descriptions
+#[cfg(feature = "modular-extensions")]
+fn strip_markup(description: &str) -> String {
+    let mut text = String::with_capacity(description.len());
+    let mut in_tag = false;
+    for c in description.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' if in_tag => in_tag = false,
+            _ if !in_tag => text.push(c),
+            _ => {}
+        }
+    }
+    text
+}
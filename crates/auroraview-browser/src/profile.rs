@@ -0,0 +1,387 @@
+//! Browser profiles
+//!
+//! A profile is a named directory that owns all persistent browser state:
+//! bookmarks, history, downloads, settings, the tab session and cookies.
+//! Several users (or shows) sharing a workstation each get their own profile.
+//!
+//! ```text
+//! <root>/
+//! ├── Default/
+//! │   ├── profile.json     # ProfileInfo
+//! │   ├── bookmarks.json
+//! │   ├── history.json
+//! │   ├── downloads.json
+//! │   ├── settings.json
+//! │   ├── session.json
+//! │   ├── cookies.json
+//! │   └── webview/         # engine user data (cache, local storage)
+//! └── Show A/
+//!     └── ...
+//! ```
+//!
+//! The incognito profile ([`Profile::incognito`]) has no directory; every
+//! manager created from it keeps its state in memory only.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, OnceLock};
+
+use auroraview_core::cookies::CookieJar;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::error::{BrowserError, Result};
+use crate::navigation::{BookmarkManager, HistoryManager};
+
+/// Name of the profile created when none is specified
+pub const DEFAULT_PROFILE: &str = "Default";
+
+/// Name reported by the incognito profile (reserved)
+pub const INCOGNITO_PROFILE: &str = "Incognito";
+
+/// Metadata file inside each profile directory
+pub const PROFILE_METADATA_FILE: &str = "profile.json";
+
+/// Maximum profile name length
+const MAX_NAME_LEN: usize = 64;
+
+/// Profile metadata
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ProfileInfo {
+    /// Profile name (also the directory name)
+    pub name: String,
+    /// Creation time
+    pub created_at: DateTime<Utc>,
+    /// Last time the profile was opened
+    pub last_used_at: DateTime<Utc>,
+}
+
+impl ProfileInfo {
+    fn new(name: &str) -> Self {
+        let now = Utc::now();
+        Self {
+            name: name.to_string(),
+            created_at: now,
+            last_used_at: now,
+        }
+    }
+}
+
+/// An opened profile
+///
+/// Hands out the managers for its state, each pointed at the profile
+/// directory (or kept in memory for incognito). Clones share one cookie jar.
+#[derive(Debug, Clone)]
+pub struct Profile {
+    info: ProfileInfo,
+    data_dir: Option<PathBuf>,
+    cookie_jar: Arc<OnceLock<Arc<CookieJar>>>,
+}
+
+impl Profile {
+    /// Create an ephemeral profile that never touches disk
+    pub fn incognito() -> Self {
+        Self::with_dir(ProfileInfo::new(INCOGNITO_PROFILE), None)
+    }
+
+    fn with_dir(info: ProfileInfo, data_dir: Option<PathBuf>) -> Self {
+        Self {
+            info,
+            data_dir,
+            cookie_jar: Arc::default(),
+        }
+    }
+
+    /// Profile name
+    pub fn name(&self) -> &str {
+        &self.info.name
+    }
+
+    /// Profile metadata
+    pub fn info(&self) -> &ProfileInfo {
+        &self.info
+    }
+
+    /// Whether this is the ephemeral incognito profile
+    pub fn is_incognito(&self) -> bool {
+        self.data_dir.is_none()
+    }
+
+    /// Profile directory (`None` for incognito)
+    pub fn data_dir(&self) -> Option<&Path> {
+        self.data_dir.as_deref()
+    }
+
+    /// Directory for the WebView engine's own user data (`None` for incognito)
+    pub fn webview_data_dir(&self) -> Option<PathBuf> {
+        self.data_dir.as_ref().map(|dir| dir.join("webview"))
+    }
+
+    /// The profile's cookie jar, opened on first use and shared afterwards
+    ///
+    /// Session cookies are persisted too, so logins survive restarts.
+    pub fn cookie_jar(&self) -> Result<Arc<CookieJar>> {
+        if let Some(jar) = self.cookie_jar.get() {
+            return Ok(jar.clone());
+        }
+        let jar = match &self.data_dir {
+            Some(dir) => CookieJar::for_profile(dir)
+                .map_err(|e| BrowserError::Io(std::io::Error::other(e.to_string())))?
+                .persist_session_cookies(true),
+            None => CookieJar::new(),
+        };
+        Ok(self.cookie_jar.get_or_init(|| Arc::new(jar)).clone())
+    }
+
+    /// Built-in bookmark manager for this profile
+    pub fn bookmarks(&self) -> BookmarkManager {
+        match &self.data_dir {
+            Some(dir) => BookmarkManager::new(Some(&dir.to_string_lossy())),
+            None => BookmarkManager::in_memory(),
+        }
+    }
+
+    /// Built-in history manager for this profile
+    pub fn history(&self, max_entries: usize, enabled: bool) -> HistoryManager {
+        match &self.data_dir {
+            Some(dir) => HistoryManager::new(Some(&dir.to_string_lossy()), max_entries, enabled),
+            None => HistoryManager::in_memory(max_entries, enabled),
+        }
+    }
+
+    /// History manager from `auroraview-history`
+    #[cfg(feature = "modular-history")]
+    pub fn history_manager(&self) -> auroraview_history::HistoryManager {
+        auroraview_history::HistoryManager::new(self.data_dir())
+    }
+
+    /// Bookmark manager from `auroraview-bookmarks`
+    #[cfg(feature = "modular-bookmarks")]
+    pub fn bookmark_manager(&self) -> auroraview_bookmarks::BookmarkManager {
+        auroraview_bookmarks::BookmarkManager::new(self.data_dir())
+    }
+
+    /// Download manager from `auroraview-downloads`
+    ///
+    /// Downloaded files still go to `download_dir` (the system downloads
+    /// folder when `None`); only the download list is kept in the profile.
+    #[cfg(feature = "downloads")]
+    pub fn download_manager(
+        &self,
+        download_dir: Option<&Path>,
+    ) -> auroraview_downloads::DownloadManager {
+        match &self.data_dir {
+            Some(dir) => auroraview_downloads::DownloadManager::with_persistence(download_dir, dir),
+            None => auroraview_downloads::DownloadManager::new(download_dir),
+        }
+    }
+
+    /// Settings manager from `auroraview-settings`
+    #[cfg(feature = "settings")]
+    pub fn settings_manager(&self) -> auroraview_settings::SettingsManager {
+        match &self.data_dir {
+            Some(dir) => {
+                auroraview_settings::SettingsManager::with_storage(dir.join("settings.json"))
+            }
+            None => auroraview_settings::SettingsManager::new(),
+        }
+    }
+
+    /// Tab session manager from `auroraview-tabs` (`None` for incognito)
+    #[cfg(feature = "modular-tabs")]
+    pub fn session_manager(&self) -> Option<auroraview_tabs::SessionManager> {
+        self.data_dir
+            .as_deref()
+            .map(auroraview_tabs::SessionManager::new)
+    }
+}
+
+/// Creates, lists, clones and deletes profiles under a root directory
+#[derive(Debug, Clone)]
+pub struct ProfileManager {
+    root: PathBuf,
+}
+
+impl ProfileManager {
+    /// Create a manager for profiles under `root`
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+
+    /// Default profiles root (`<data_dir>/auroraview/profiles`)
+    pub fn default_root() -> Option<PathBuf> {
+        dirs::data_dir().map(|d| d.join("auroraview").join("profiles"))
+    }
+
+    /// Root directory
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// Whether a profile exists
+    pub fn exists(&self, name: &str) -> bool {
+        validate_name(name).is_ok() && self.metadata_path(name).is_file()
+    }
+
+    /// Create a new profile
+    ///
+    /// A directory without metadata (left behind by an interrupted create)
+    /// is adopted instead of blocking the name.
+    pub fn create(&self, name: &str) -> Result<Profile> {
+        validate_name(name)?;
+        if self.metadata_path(name).exists() {
+            return Err(BrowserError::ProfileExists(name.to_string()));
+        }
+
+        let dir = self.root.join(name);
+        fs::create_dir_all(&dir)?;
+
+        let info = ProfileInfo::new(name);
+        write_info(&dir, &info)?;
+        tracing::info!("[Profile] Created profile '{}'", name);
+        Ok(Profile::with_dir(info, Some(dir)))
+    }
+
+    /// Open an existing profile and mark it as used
+    pub fn open(&self, name: &str) -> Result<Profile> {
+        let dir = self.profile_dir(name)?;
+        let mut info = read_info(&dir)?;
+        info.last_used_at = Utc::now();
+        write_info(&dir, &info)?;
+        Ok(Profile::with_dir(info, Some(dir)))
+    }
+
+    /// Open a profile, creating it first if needed
+    pub fn open_or_create(&self, name: &str) -> Result<Profile> {
+        if self.exists(name) {
+            self.open(name)
+        } else {
+            self.create(name)
+        }
+    }
+
+    /// All profiles, sorted by name
+    ///
+    /// Directories without readable metadata are skipped.
+    pub fn list(&self) -> Result<Vec<ProfileInfo>> {
+        if !self.root.exists() {
+            return Ok(Vec::new());
+        }
+
+        let mut profiles = Vec::new();
+        for entry in fs::read_dir(&self.root)? {
+            let path = entry?.path();
+            if !path.is_dir() {
+                continue;
+            }
+            match read_info(&path) {
+                Ok(info) => profiles.push(info),
+                Err(e) => tracing::debug!("[Profile] Skipping {:?}: {}", path, e),
+            }
+        }
+        profiles.sort_by_key(|info| info.name.to_lowercase());
+        Ok(profiles)
+    }
+
+    /// Delete a profile and all its data
+    pub fn delete(&self, name: &str) -> Result<()> {
+        let dir = self.profile_dir(name)?;
+        fs::remove_dir_all(&dir)?;
+        tracing::info!("[Profile] Deleted profile '{}'", name);
+        Ok(())
+    }
+
+    /// Copy a profile's data into a new profile
+    ///
+    /// The source should not be in use, otherwise files written while
+    /// copying may be inconsistent.
+    pub fn clone_profile(&self, source: &str, target: &str) -> Result<Profile> {
+        let source_dir = self.profile_dir(source)?;
+        let profile = self.create(target)?;
+        let target_dir = self.root.join(target);
+
+        // Copying overwrites the fresh metadata, so write it back afterwards
+        if let Err(e) =
+            copy_dir(&source_dir, &target_dir).and_then(|_| write_info(&target_dir, profile.info()))
+        {
+            let _ = fs::remove_dir_all(&target_dir);
+            return Err(e);
+        }
+        tracing::info!("[Profile] Cloned profile '{}' to '{}'", source, target);
+        Ok(profile)
+    }
+
+    fn metadata_path(&self, name: &str) -> PathBuf {
+        self.root.join(name).join(PROFILE_METADATA_FILE)
+    }
+
+    /// Directory of an existing profile
+    fn profile_dir(&self, name: &str) -> Result<PathBuf> {
+        validate_name(name)?;
+        if !self.metadata_path(name).is_file() {
+            return Err(BrowserError::ProfileNotFound(name.to_string()));
+        }
+        Ok(self.root.join(name))
+    }
+}
+
+/// Profile names double as directory names, so keep them portable
+fn validate_name(name: &str) -> Result<()> {
+    let invalid = |reason: &str| Err(BrowserError::InvalidProfile(format!("'{name}' {reason}")));
+
+    if name.trim().is_empty() {
+        return invalid("is empty");
+    }
+    if name != name.trim() {
+        return invalid("has leading or trailing whitespace");
+    }
+    if name.chars().count() > MAX_NAME_LEN {
+        return invalid("is too long");
+    }
+    if name.starts_with('.') {
+        return invalid("starts with '.'");
+    }
+    if name.eq_ignore_ascii_case(INCOGNITO_PROFILE) {
+        return invalid("is reserved");
+    }
+    if !name
+        .chars()
+        .all(|c| c.is_alphanumeric() || matches!(c, ' ' | '-' | '_' | '.'))
+    {
+        return invalid("contains unsupported characters");
+    }
+    Ok(())
+}
+
+fn read_info(dir: &Path) -> Result<ProfileInfo> {
+    let json = fs::read_to_string(dir.join(PROFILE_METADATA_FILE))?;
+    Ok(serde_json::from_str(&json)?)
+}
+
+/// Write the metadata through a temporary file, so it is never left half written
+fn write_info(dir: &Path, info: &ProfileInfo) -> Result<()> {
+    let json = serde_json::to_string_pretty(info)?;
+    let staged = dir.join(format!("{PROFILE_METADATA_FILE}.tmp"));
+    fs::write(&staged, json)?;
+    if let Err(e) = fs::rename(&staged, dir.join(PROFILE_METADATA_FILE)) {
+        let _ = fs::remove_file(&staged);
+        return Err(e.into());
+    }
+    Ok(())
+}
+
+/// Recursively copy `from` into `to`
+fn copy_dir(from: &Path, to: &Path) -> Result<()> {
+    fs::create_dir_all(to)?;
+    for entry in fs::read_dir(from)? {
+        let entry = entry?;
+        let source = entry.path();
+        let target = to.join(entry.file_name());
+        if entry.file_type()?.is_dir() {
+            copy_dir(&source, &target)?;
+        } else {
+            fs::copy(&source, &target)?;
+        }
+    }
+    Ok(())
+}
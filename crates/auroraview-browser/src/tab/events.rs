@@ -36,6 +36,8 @@ pub enum TabEvent {
     Stop,
     /// Navigate to home page
     Home,
+    /// Request omnibox suggestions for the address bar text
+    Suggest { input: String },
 
     // === Tab State Update Events (from Tab WebViews) ===
     /// Tab title changed
@@ -1,5 +1,6 @@
 //! Tab Manager implementation
 
+use std::cell::RefCell;
 use std::collections::HashMap;
 use std::rc::Rc;
 use std::sync::atomic::{AtomicU32, Ordering};
@@ -7,10 +8,11 @@ use std::sync::atomic::{AtomicU32, Ordering};
 use parking_lot::RwLock;
 use tao::event_loop::EventLoopProxy;
 use tao::window::Window;
-use wry::WebViewBuilder;
+use wry::{WebContext, WebViewBuilder};
 
 use super::{Tab, TabEvent, TabId, TabState};
 use crate::config::BrowserConfig;
+use crate::navigation::omnibox::encode_query;
 use crate::BrowserError;
 
 /// Tab Manager - manages multiple WebViews in a single window
@@ -19,7 +21,7 @@ use crate::BrowserError;
 /// It follows the same architecture patterns:
 ///
 /// 1. **Single Window with Multiple WebViews**
-/// 2. **Shared Environment** (automatic with wry)
+/// 2. **Shared Environment** (one `WebContext` rooted at the profile's WebView data directory)
 /// 3. **Tab Visibility Management**
 pub struct TabManager {
     /// All tabs indexed by ID
@@ -32,6 +34,8 @@ pub struct TabManager {
     tab_counter: AtomicU32,
     /// Browser configuration
     config: Rc<BrowserConfig>,
+    /// Engine context shared by all tabs
+    web_context: RefCell<WebContext>,
     /// Event loop proxy for sending events
     event_proxy: RwLock<Option<EventLoopProxy<TabEvent>>>,
 }
@@ -44,6 +48,7 @@ impl TabManager {
             active_tab_id: RwLock::new(None),
             tab_order: RwLock::new(Vec::new()),
             tab_counter: AtomicU32::new(0),
+            web_context: RefCell::new(WebContext::new(config.webview_data_dir.clone())),
             config,
             event_proxy: RwLock::new(None),
         }
@@ -118,8 +123,10 @@ impl TabManager {
         #[cfg(target_os = "windows")]
         let content_height = size.height.saturating_sub(header_height);
 
-        // Build WebView
-        let builder = WebViewBuilder::new()
+        // Build WebView in the profile's context
+        let mut web_context = self.web_context.borrow_mut();
+        let builder = WebViewBuilder::new_with_web_context(&mut web_context)
+            .with_incognito(self.config.incognito)
             .with_url(&actual_url)
             .with_devtools(self.config.debug || self.config.features.dev_tools)
             .with_visible(false);
@@ -255,10 +262,7 @@ impl TabManager {
         } else if url.contains('.') && !url.contains(' ') {
             format!("https://{}", url)
         } else {
-            format!(
-                "https://www.google.com/search?q={}",
-                urlencoding_encode(url)
-            )
+            format!("https://www.google.com/search?q={}", encode_query(url))
         }
     }
 
@@ -469,20 +473,3 @@ impl TabManager {
             .unwrap_or(false)
     }
 }
-
-/// Simple URL encoding function
-fn urlencoding_encode(s: &str) -> String {
-    let mut result = String::new();
-    for c in s.chars() {
-        match c {
-            'a'..='z' | 'A'..='Z' | '0'..='9' | '-' | '_' | '.' | '~' => result.push(c),
-            ' ' => result.push('+'),
-            _ => {
-                for b in c.to_string().as_bytes() {
-                    result.push_str(&format!("%{:02X}", b));
-                }
-            }
-        }
-    }
-    result
-}
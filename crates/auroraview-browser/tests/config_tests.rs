@@ -1,7 +1,7 @@
 //! Tests for BrowserConfig
 
 use auroraview_browser::devtools::DockSide;
-use auroraview_browser::{BrowserConfig, BrowserFeatures, Theme};
+use auroraview_browser::{BrowserConfig, BrowserFeatures, Profile, ProfileManager, Theme};
 use rstest::rstest;
 
 // -------------------------------------------------------------------------
@@ -92,6 +92,32 @@ fn browser_config_builder_user_data_dir() {
     assert_eq!(config.user_data_dir, Some("/path/to/data".to_string()));
 }
 
+#[test]
+fn browser_config_builder_incognito_profile() {
+    let config = BrowserConfig::builder()
+        .user_data_dir("/path/to/data")
+        .profile(&Profile::incognito())
+        .build();
+
+    assert!(config.incognito);
+    assert!(config.user_data_dir.is_none());
+    assert!(config.webview_data_dir.is_none());
+}
+
+#[test]
+fn browser_config_builder_named_profile() {
+    let temp = tempfile::TempDir::new().unwrap();
+    let profile = ProfileManager::new(temp.path()).create("Show A").unwrap();
+    let config = BrowserConfig::builder().profile(&profile).build();
+
+    assert!(!config.incognito);
+    assert_eq!(config.webview_data_dir, profile.webview_data_dir());
+    assert_eq!(
+        config.user_data_dir.as_deref(),
+        profile.data_dir().and_then(|dir| dir.to_str())
+    );
+}
+
 #[test]
 fn browser_features_default() {
     let config = BrowserConfig::default();
@@ -57,6 +57,24 @@ fn extension_error_display() {
     assert_eq!(err.to_string(), "Extension error: disabled");
 }
 
+#[rstest]
+fn profile_not_found_display() {
+    let err = BrowserError::ProfileNotFound("Show A".to_string());
+    assert_eq!(err.to_string(), "Profile not found: Show A");
+}
+
+#[rstest]
+fn profile_exists_display() {
+    let err = BrowserError::ProfileExists("Default".to_string());
+    assert_eq!(err.to_string(), "Profile already exists: Default");
+}
+
+#[rstest]
+fn invalid_profile_display() {
+    let err = BrowserError::InvalidProfile("empty name".to_string());
+    assert_eq!(err.to_string(), "Invalid profile: empty name");
+}
+
 // ---------------------------------------------------------------------------
 // Debug output contains variant name
 // ---------------------------------------------------------------------------
@@ -78,6 +96,9 @@ fn debug_contains_variant_names() {
         ("Navigation", BrowserError::Navigation("x".into())),
         ("InvalidUrl", BrowserError::InvalidUrl("x".into())),
         ("Extension", BrowserError::Extension("x".into())),
+        ("ProfileNotFound", BrowserError::ProfileNotFound("x".into())),
+        ("ProfileExists", BrowserError::ProfileExists("x".into())),
+        ("InvalidProfile", BrowserError::InvalidProfile("x".into())),
     ];
     for (name, err) in cases {
         let debug = format!("{err:?}");
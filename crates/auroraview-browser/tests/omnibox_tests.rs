@@ -0,0 +1,408 @@
+//! Omnibox coverage: frecency ranking, inline completion, deduplication
+//! and suggestion providers
+
+use auroraview_browser::navigation::omnibox::{
+    frecency, history_score, normalize_url, OpenTab, OpenTabProvider, MAX_MATCH_SCORE,
+};
+use auroraview_browser::navigation::{
+    BookmarkManager, HistoryManager, OmniboxEngine, OmniboxInput, SearchEngine, Suggestion,
+    SuggestionKind, SuggestionProvider,
+};
+use chrono::{Duration, Utc};
+use rstest::*;
+
+// ---------------------------------------------------------------------------
+// Helpers
+// ---------------------------------------------------------------------------
+
+fn history(visits: &[(&str, &str, u32)]) -> HistoryManager {
+    let mgr = HistoryManager::in_memory(100, true);
+    for (url, title, count) in visits {
+        for _ in 0..*count {
+            mgr.add(url, title);
+        }
+    }
+    mgr
+}
+
+fn urls(engine: &OmniboxEngine, input: &str, providers: &[&dyn SuggestionProvider]) -> Vec<String> {
+    engine
+        .suggest_with(input, providers)
+        .suggestions
+        .into_iter()
+        .map(|s| s.url)
+        .collect()
+}
+
+/// Provider returning fixed suggestions
+struct Fixed(Vec<Suggestion>);
+
+impl SuggestionProvider for Fixed {
+    fn name(&self) -> &str {
+        "fixed"
+    }
+
+    fn suggest(&self, _input: &OmniboxInput, limit: usize) -> Vec<Suggestion> {
+        self.0.iter().take(limit).cloned().collect()
+    }
+}
+
+// ===========================================================================
+// Scoring
+// ===========================================================================
+
+#[rstest]
+#[case(1, 100.0)]
+#[case(10, 70.0)]
+#[case(20, 50.0)]
+#[case(60, 30.0)]
+#[case(365, 10.0)]
+fn frecency_decays_with_age(#[case] days: i64, #[case] expected: f64) {
+    let now = Utc::now();
+    assert_eq!(frecency(1, 0, now - Duration::days(days), now), expected);
+}
+
+#[test]
+fn frecency_weighs_typed_visits_more() {
+    let now = Utc::now();
+    assert!(frecency(2, 2, now, now) > frecency(4, 0, now, now));
+}
+
+#[test]
+fn history_score_is_capped() {
+    assert!(history_score(1.0, 100.0) > history_score(0.5, 100.0));
+    assert!(history_score(1.0, 1000.0) > history_score(1.0, 100.0));
+    assert_eq!(history_score(1.0, f64::MAX), MAX_MATCH_SCORE);
+    assert_eq!(history_score(0.0, 1000.0), 0);
+}
+
+#[rstest]
+#[case("https://www.example.com/", "example.com")]
+#[case("http://example.com", "example.com")]
+#[case("https://EXAMPLE.com/Docs/#intro", "example.com/Docs")]
+#[case("example.com/a?b=1", "example.com/a?b=1")]
+fn normalize_url_cases(#[case] url: &str, #[case] expected: &str) {
+    assert_eq!(normalize_url(url), expected);
+}
+
+// ===========================================================================
+// Input parsing
+// ===========================================================================
+
+#[rstest]
+#[case("github.com", true)]
+#[case("localhost:3000/api", true)]
+#[case("https://example.com", true)]
+#[case("about:blank", true)]
+#[case("rust lang", false)]
+#[case("rust", false)]
+#[case("end.", false)]
+fn input_looks_like_url(#[case] text: &str, #[case] expected: bool) {
+    assert_eq!(OmniboxInput::new(text).looks_like_url(), expected);
+}
+
+#[test]
+fn match_quality_requires_every_term() {
+    let input = OmniboxInput::new("rust book");
+    assert!(input
+        .match_quality(
+            "The Rust Programming Language",
+            "https://doc.rust-lang.org/book/"
+        )
+        .is_some());
+    assert!(input
+        .match_quality("The Rust Reference", "https://doc.rust-lang.org/reference/")
+        .is_none());
+}
+
+#[test]
+fn match_quality_prefers_host_prefix_then_word_start() {
+    let input = OmniboxInput::new("git");
+    let host = input.match_quality("", "https://github.com").unwrap();
+    let word = input
+        .match_quality("Learn Git", "https://learn.example")
+        .unwrap();
+    let inner = input
+        .match_quality("Digital", "https://example.com")
+        .unwrap();
+    assert!(host > word);
+    assert!(word > inner);
+}
+
+#[test]
+fn strip_keyword_requires_query() {
+    let input = OmniboxInput::new("W rust lang ");
+    assert_eq!(input.strip_keyword("w"), Some("rust lang"));
+    assert_eq!(input.strip_keyword("g"), None);
+    assert_eq!(OmniboxInput::new("w").strip_keyword("w"), None);
+}
+
+// ===========================================================================
+// Engine
+// ===========================================================================
+
+#[test]
+fn empty_input_has_no_suggestions() {
+    let result = OmniboxEngine::new().suggest("   ");
+    assert!(result.suggestions.is_empty());
+    assert!(result.inline_completion.is_none());
+}
+
+#[test]
+fn query_defaults_to_search() {
+    let result = OmniboxEngine::new().suggest("rust lang");
+    let first = result.default_match().unwrap();
+    assert_eq!(first.kind, SuggestionKind::Search);
+    assert_eq!(first.url, "https://www.google.com/search?q=rust+lang");
+    assert!(result.inline_completion.is_none());
+}
+
+#[test]
+fn url_input_defaults_to_verbatim_url() {
+    let result = OmniboxEngine::new().suggest("example.com/docs");
+    let kinds: Vec<_> = result.suggestions.iter().map(|s| s.kind).collect();
+    assert_eq!(kinds, vec![SuggestionKind::Url, SuggestionKind::Search]);
+    assert_eq!(result.suggestions[0].url, "https://example.com/docs");
+}
+
+#[test]
+fn history_ranked_by_frecency() {
+    let history = history(&[
+        ("https://rarely.example/rust", "Rust once", 1),
+        ("https://often.example/rust", "Rust often", 5),
+    ]);
+    let engine = OmniboxEngine::new();
+    let urls = urls(&engine, "rust", &[&history]);
+    // Search for the query stays on top, then history by frecency
+    assert_eq!(
+        urls[1..],
+        ["https://often.example/rust", "https://rarely.example/rust"]
+    );
+}
+
+#[test]
+fn inline_completion_from_repeated_visits() {
+    let history = history(&[
+        ("https://github.com/", "GitHub", 3),
+        ("https://gitlab.com/", "GitLab", 1),
+    ]);
+    let engine = OmniboxEngine::new();
+
+    let result = engine.suggest_with("gi", &[&history]);
+    assert_eq!(result.inline_completion.as_deref(), Some("thub.com"));
+    assert_eq!(result.default_match().unwrap().url, "https://github.com/");
+
+    // A single visit is not completed inline
+    let result = engine.suggest_with("gitl", &[&history]);
+    assert!(result.inline_completion.is_none());
+    assert_eq!(result.default_match().unwrap().kind, SuggestionKind::Search);
+}
+
+#[rstest]
+#[case("github.com")]
+#[case("github ")]
+#[case("hub")]
+fn no_inline_completion(#[case] input: &str) {
+    let history = history(&[("https://github.com/", "GitHub", 3)]);
+    let result = OmniboxEngine::new().suggest_with(input, &[&history]);
+    assert!(result.inline_completion.is_none());
+}
+
+#[test]
+fn duplicates_merged_by_normalized_url() {
+    let history = history(&[("https://www.rust-lang.org/", "Rust", 2)]);
+    let bookmarks = BookmarkManager::in_memory();
+    bookmarks.add_bookmark("http://rust-lang.org", "Rust Programming Language");
+    let tabs = OpenTabProvider::new([OpenTab::new("tab_1", "https://rust-lang.org/#top", "")]);
+
+    let result = OmniboxEngine::new().suggest_with("rust", &[&history, &bookmarks, &tabs]);
+    let matches: Vec<_> = result
+        .suggestions
+        .iter()
+        .filter(|s| s.kind != SuggestionKind::Search)
+        .collect();
+
+    assert_eq!(matches.len(), 1);
+    let merged = matches[0];
+    assert!(merged.bookmarked);
+    assert_eq!(merged.open_tab_id.as_deref(), Some("tab_1"));
+
+    let alone = OmniboxEngine::new().suggest_with("rust", &[&tabs]);
+    let tab_only = alone
+        .suggestions
+        .iter()
+        .find(|s| s.kind == SuggestionKind::OpenTab)
+        .unwrap();
+    assert!(merged.score > tab_only.score);
+}
+
+#[test]
+fn keyword_search_engine() {
+    let engine = OmniboxEngine::new().with_search_engine(
+        SearchEngine::new(
+            "Wikipedia",
+            "https://en.wikipedia.org/w/index.php?search={searchTerms}",
+        )
+        .with_keyword("w"),
+    );
+
+    let result = engine.suggest("w rust lang");
+    let first = result.default_match().unwrap();
+    assert_eq!(first.kind, SuggestionKind::Keyword);
+    assert_eq!(
+        first.url,
+        "https://en.wikipedia.org/w/index.php?search=rust+lang"
+    );
+
+    // Without a query the keyword is plain text
+    let result = engine.suggest("w");
+    assert_eq!(result.default_match().unwrap().kind, SuggestionKind::Search);
+}
+
+#[test]
+fn default_search_engine_can_be_replaced() {
+    let engine = OmniboxEngine::new().with_default_search_engine(SearchEngine::new(
+        "Ddg",
+        "https://ddg.example/?q={searchTerms}",
+    ));
+    assert_eq!(engine.search_engines().len(), 1);
+    assert_eq!(
+        engine.suggest("a b").default_match().unwrap().url,
+        "https://ddg.example/?q=a+b"
+    );
+}
+
+#[test]
+fn custom_provider_and_result_limit() {
+    let custom = Fixed(
+        (0..20)
+            .map(|i| {
+                Suggestion::new(
+                    SuggestionKind::Custom,
+                    format!("https://app.example/{}", i),
+                    "",
+                    500 - i,
+                )
+            })
+            .collect(),
+    );
+    let engine = OmniboxEngine::new()
+        .with_provider(custom)
+        .with_max_results(5);
+
+    let result = engine.suggest("anything");
+    assert_eq!(result.suggestions.len(), 5);
+    assert!(result.suggestions[1..]
+        .iter()
+        .all(|s| s.kind == SuggestionKind::Custom && s.provider == "fixed"));
+    assert_eq!(result.suggestions[1].url, "https://app.example/0");
+}
+
+#[test]
+fn result_serializes_camel_case() {
+    let tabs = OpenTabProvider::new([OpenTab::new("tab_3", "https://docs.rs", "Docs.rs")]);
+    let result = OmniboxEngine::new().suggest_with("docs", &[&tabs]);
+    let json = serde_json::to_value(&result).unwrap();
+
+    let tab = json["suggestions"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|s| s["kind"] == "openTab")
+        .unwrap();
+    assert_eq!(tab["openTabId"], "tab_3");
+    assert!(tab.get("allowInline").is_none());
+}
+
+// ===========================================================================
+// Modular providers
+// ===========================================================================
+
+#[cfg(feature = "modular-history")]
+#[test]
+fn modular_history_typed_visit_completes_inline() {
+    let history = auroraview_history::HistoryManager::new(None);
+    history.typed_visit("https://crates.io/", "crates.io");
+    history.visit("https://crates.example/", "Crates");
+
+    let result = OmniboxEngine::new().suggest_with("cra", &[&history]);
+    assert_eq!(result.inline_completion.as_deref(), Some("tes.io"));
+    assert_eq!(result.suggestions.len(), 3);
+}
+
+#[cfg(feature = "modular-bookmarks")]
+#[test]
+fn modular_bookmarks_are_suggested() {
+    let bookmarks = auroraview_bookmarks::BookmarkManager::new(None);
+    bookmarks.add("https://docs.rs/serde", "serde docs");
+
+    let result = OmniboxEngine::new().suggest_with("serde", &[&bookmarks]);
+    assert!(result
+        .suggestions
+        .iter()
+        .any(|s| s.kind == SuggestionKind::Bookmark && s.bookmarked));
+}
+
+#[cfg(feature = "modular-tabs")]
+#[test]
+fn modular_tabs_skip_active_tab() {
+    let tabs = auroraview_tabs::TabManager::new();
+    tabs.create("https://docs.rs/a");
+    let b = tabs.create("https://docs.rs/b");
+
+    let result = OmniboxEngine::new().suggest_with("docs", &[&tabs]);
+    let open: Vec<_> = result
+        .suggestions
+        .iter()
+        .filter_map(|s| s.open_tab_id.clone())
+        .collect();
+    assert_eq!(open, vec![b]);
+}
+
+#[cfg(feature = "modular-extensions")]
+#[test]
+fn extension_keyword_suggestions() {
+    use auroraview_extensions::apis::omnibox::{DefaultSuggestion, OmniboxApi, SuggestResult};
+
+    let api = OmniboxApi::new();
+    api.set_keyword("gh-ext", "gh");
+    api.set_default_suggestion(
+        "gh-ext",
+        DefaultSuggestion {
+            description: "Search GitHub for <match>%s</match>".to_string(),
+        },
+    )
+    .unwrap();
+    api.send_suggestions(
+        "gh-ext",
+        vec![SuggestResult {
+            content: "rust-lang/rust".to_string(),
+            description: "<url>rust-lang/rust</url>".to_string(),
+            deletable: None,
+        }],
+    )
+    .unwrap();
+
+    let result = OmniboxEngine::new().suggest_with("gh rust", &[&api]);
+    let keyword: Vec<_> = result
+        .suggestions
+        .iter()
+        .filter(|s| s.kind == SuggestionKind::Keyword)
+        .map(|s| (s.url.as_str(), s.title.as_str()))
+        .collect();
+    assert_eq!(
+        keyword,
+        vec![
+            ("rust", "Search GitHub for rust"),
+            ("rust-lang/rust", "rust-lang/rust")
+        ]
+    );
+    assert_eq!(
+        result.suggestions[0].extension_id.as_deref(),
+        Some("gh-ext")
+    );
+
+    // Without the keyword the extension is not asked
+    let result = OmniboxEngine::new().suggest_with("rust", &[&api]);
+    assert!(result.suggestions.iter().all(|s| s.extension_id.is_none()));
+}
@@ -0,0 +1,243 @@
+//! Tests for profile module
+
+use std::sync::Arc;
+
+use auroraview_browser::profile::{DEFAULT_PROFILE, PROFILE_METADATA_FILE};
+use auroraview_browser::{BrowserError, Profile, ProfileManager};
+use auroraview_core::cookies::Cookie;
+use rstest::rstest;
+use tempfile::TempDir;
+
+fn create_test_manager() -> (ProfileManager, TempDir) {
+    let temp_dir = TempDir::new().expect("Failed to create temp directory");
+    let manager = ProfileManager::new(temp_dir.path().join("profiles"));
+    (manager, temp_dir)
+}
+
+// ─── Create / open / list ────────────────────────────────────────────────────
+
+#[test]
+fn create_and_open() {
+    let (manager, _temp) = create_test_manager();
+
+    let profile = manager.create("Show A").unwrap();
+    assert_eq!(profile.name(), "Show A");
+    assert!(!profile.is_incognito());
+    let dir = profile.data_dir().unwrap();
+    assert!(dir.join(PROFILE_METADATA_FILE).is_file());
+
+    let opened = manager.open("Show A").unwrap();
+    assert_eq!(opened.info().created_at, profile.info().created_at);
+    assert!(opened.info().last_used_at >= profile.info().last_used_at);
+}
+
+#[test]
+fn create_duplicate_fails() {
+    let (manager, _temp) = create_test_manager();
+    manager.create(DEFAULT_PROFILE).unwrap();
+
+    let err = manager.create(DEFAULT_PROFILE).unwrap_err();
+    assert!(matches!(err, BrowserError::ProfileExists(_)));
+}
+
+#[test]
+fn create_adopts_directory_without_metadata() {
+    let (manager, _temp) = create_test_manager();
+    let dir = manager.root().join("Show A");
+    std::fs::create_dir_all(&dir).unwrap();
+    assert!(!manager.exists("Show A"));
+
+    let profile = manager.open_or_create("Show A").unwrap();
+    assert_eq!(profile.data_dir(), Some(dir.as_path()));
+    assert!(dir.join(PROFILE_METADATA_FILE).is_file());
+    assert!(!dir.join(format!("{PROFILE_METADATA_FILE}.tmp")).exists());
+    assert!(manager.exists("Show A"));
+}
+
+#[test]
+fn open_missing_fails() {
+    let (manager, _temp) = create_test_manager();
+    let err = manager.open("nobody").unwrap_err();
+    assert!(matches!(err, BrowserError::ProfileNotFound(_)));
+}
+
+#[test]
+fn open_or_create() {
+    let (manager, _temp) = create_test_manager();
+    assert!(!manager.exists(DEFAULT_PROFILE));
+    manager.open_or_create(DEFAULT_PROFILE).unwrap();
+    assert!(manager.exists(DEFAULT_PROFILE));
+    manager.open_or_create(DEFAULT_PROFILE).unwrap();
+    assert_eq!(manager.list().unwrap().len(), 1);
+}
+
+#[test]
+fn list_sorted_and_skips_foreign_dirs() {
+    let (manager, _temp) = create_test_manager();
+    assert!(manager.list().unwrap().is_empty());
+
+    manager.create("charlie").unwrap();
+    manager.create("Alice").unwrap();
+    manager.create("bob").unwrap();
+    std::fs::create_dir_all(manager.root().join("not-a-profile")).unwrap();
+
+    let names: Vec<String> = manager
+        .list()
+        .unwrap()
+        .into_iter()
+        .map(|info| info.name)
+        .collect();
+    assert_eq!(names, vec!["Alice", "bob", "charlie"]);
+}
+
+#[rstest]
+#[case("")]
+#[case("  ")]
+#[case(" padded")]
+#[case(".hidden")]
+#[case("a/b")]
+#[case("..")]
+#[case("C:\\x")]
+#[case("incognito")]
+fn invalid_names_rejected(#[case] name: &str) {
+    let (manager, _temp) = create_test_manager();
+    let err = manager.create(name).unwrap_err();
+    assert!(matches!(err, BrowserError::InvalidProfile(_)), "{name:?}");
+}
+
+// ─── Delete / clone ──────────────────────────────────────────────────────────
+
+#[test]
+fn delete_removes_data() {
+    let (manager, _temp) = create_test_manager();
+    let profile = manager.create("temp").unwrap();
+    let dir = profile.data_dir().unwrap().to_path_buf();
+    profile
+        .bookmarks()
+        .add_bookmark("https://example.com", "Example");
+
+    manager.delete("temp").unwrap();
+    assert!(!dir.exists());
+    assert!(matches!(
+        manager.delete("temp"),
+        Err(BrowserError::ProfileNotFound(_))
+    ));
+}
+
+#[test]
+fn clone_copies_state() {
+    let (manager, _temp) = create_test_manager();
+    let source = manager.create("template").unwrap();
+    source
+        .bookmarks()
+        .add_bookmark("https://example.com", "Example");
+    source
+        .cookie_jar()
+        .unwrap()
+        .set(Cookie::new("sid", "1", "example.com").expires_at(4_102_444_800))
+        .unwrap();
+
+    let copy = manager.clone_profile("template", "Show B").unwrap();
+    assert_eq!(copy.name(), "Show B");
+    assert_eq!(manager.open("Show B").unwrap().name(), "Show B");
+    assert_eq!(copy.bookmarks().all().len(), 1);
+    assert!(copy
+        .cookie_jar()
+        .unwrap()
+        .get("example.com", "sid")
+        .is_some());
+
+    // Profiles are independent after cloning
+    copy.bookmarks().clear();
+    assert_eq!(source.bookmarks().all().len(), 1);
+}
+
+#[test]
+fn clone_into_existing_fails() {
+    let (manager, _temp) = create_test_manager();
+    manager.create("a").unwrap();
+    manager.create("b").unwrap();
+    assert!(matches!(
+        manager.clone_profile("a", "b"),
+        Err(BrowserError::ProfileExists(_))
+    ));
+    assert!(matches!(
+        manager.clone_profile("missing", "c"),
+        Err(BrowserError::ProfileNotFound(_))
+    ));
+}
+
+// ─── Isolation / incognito ───────────────────────────────────────────────────
+
+#[test]
+fn profiles_are_isolated() {
+    let (manager, _temp) = create_test_manager();
+    let alice = manager.create("alice").unwrap();
+    let bob = manager.create("bob").unwrap();
+
+    alice
+        .history(100, true)
+        .add("https://alice.example", "Alice");
+    assert_eq!(alice.history(100, true).all().len(), 1);
+    assert!(bob.history(100, true).all().is_empty());
+}
+
+#[test]
+fn incognito_never_touches_disk() {
+    let profile = Profile::incognito();
+    assert!(profile.is_incognito());
+    assert!(profile.data_dir().is_none());
+    assert!(profile.webview_data_dir().is_none());
+
+    let bookmarks = profile.bookmarks();
+    bookmarks.add_bookmark("https://secret.example", "Secret");
+    assert_eq!(bookmarks.all().len(), 1);
+    // A fresh manager from the same profile starts empty
+    assert!(profile.bookmarks().all().is_empty());
+
+    let history = profile.history(100, true);
+    history.add("https://secret.example", "Secret");
+    assert_eq!(history.all().len(), 1);
+    assert!(profile.history(100, true).all().is_empty());
+
+    let jar = profile.cookie_jar().unwrap();
+    assert!(jar.path().is_none());
+}
+
+#[test]
+fn cookie_jar_is_shared() {
+    let (manager, _temp) = create_test_manager();
+    let profile = manager.create("Show A").unwrap();
+
+    let jar = profile.cookie_jar().unwrap();
+    assert!(Arc::ptr_eq(&jar, &profile.cookie_jar().unwrap()));
+    assert!(Arc::ptr_eq(&jar, &profile.clone().cookie_jar().unwrap()));
+
+    jar.set(Cookie::new("sid", "1", "example.com")).unwrap();
+    assert!(profile
+        .cookie_jar()
+        .unwrap()
+        .get("example.com", "sid")
+        .is_some());
+
+    let incognito = Profile::incognito();
+    assert!(Arc::ptr_eq(
+        &incognito.cookie_jar().unwrap(),
+        &incognito.cookie_jar().unwrap()
+    ));
+}
+
+#[test]
+fn session_cookies_survive_reopen() {
+    let (manager, _temp) = create_test_manager();
+    manager
+        .create("Show A")
+        .unwrap()
+        .cookie_jar()
+        .unwrap()
+        .set(Cookie::new("session", "1", "example.com"))
+        .unwrap();
+
+    let jar = manager.open("Show A").unwrap().cookie_jar().unwrap();
+    assert!(jar.get("example.com", "session").is_some());
+}
@@ -2,17 +2,24 @@
 //!
 //! This command reads the overlay data from a packed AuroraView executable
 //! and displays the assets and configuration for debugging purposes.
+//!
+//! It can also compare two packed executables (`--diff OLD NEW`) and dump an
+//! overlay to disk (`--extract DIR`). Every mode supports `--json` so CI can
+//! gate releases on the result.
 
 use anyhow::{Context, Result};
-use auroraview_pack::{OverlayReader, PackMode};
+use auroraview_pack::{OverlayData, OverlayReader, PackConfig, PackMode};
 use clap::Args;
-use std::path::PathBuf;
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::{BTreeSet, HashMap};
+use std::path::{Component, Path, PathBuf};
 
 #[derive(Args, Debug)]
 pub struct InspectArgs {
     /// Path to the packed executable to inspect
-    #[arg(value_name = "PACKED_EXE")]
-    pub exe_path: PathBuf,
+    #[arg(value_name = "PACKED_EXE", required_unless_present = "diff")]
+    pub exe_path: Option<PathBuf>,
 
     /// Show full asset content (for small assets only)
     #[arg(long, default_value = "false")]
@@ -21,24 +28,163 @@ pub struct InspectArgs {
     /// Filter assets by pattern (e.g., "*.html", "index*")
     #[arg(long)]
     pub filter: Option<String>,
+
+    /// Compare two packed executables (assets and configuration)
+    #[arg(
+        long,
+        num_args = 2,
+        value_names = ["OLD", "NEW"],
+        conflicts_with_all = ["exe_path", "extract", "show_content", "filter"]
+    )]
+    pub diff: Option<Vec<PathBuf>>,
+
+    /// Write the overlay's assets and configuration to DIR
+    #[arg(long, value_name = "DIR")]
+    pub extract: Option<PathBuf>,
+
+    /// Print machine-readable JSON instead of text
+    #[arg(long)]
+    pub json: bool,
+}
+
+/// Asset entry in `--json` output
+#[derive(Debug, Clone, Serialize)]
+struct AssetEntry {
+    path: String,
+    size: u64,
+}
+
+/// `inspect --json` output
+#[derive(Debug, Serialize)]
+struct InspectReport<'a> {
+    path: &'a Path,
+    content_hash: &'a str,
+    signed_by: Option<&'a str>,
+    config: &'a PackConfig,
+    assets: Vec<AssetEntry>,
+}
+
+/// How an asset differs between two overlays
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+enum AssetStatus {
+    Added,
+    Removed,
+    Changed,
+}
+
+/// An asset that differs between two overlays
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+struct AssetChange {
+    path: String,
+    status: AssetStatus,
+    old_size: Option<u64>,
+    new_size: Option<u64>,
+    size_delta: i64,
+}
+
+/// A configuration value that differs, addressed by JSON pointer
+#[derive(Debug, Clone, PartialEq, Serialize)]
+struct ConfigChange {
+    path: String,
+    old: Option<Value>,
+    new: Option<Value>,
+}
+
+/// Differences between two overlays (`inspect --diff`)
+#[derive(Debug, Clone, Serialize)]
+struct OverlayDiff {
+    old_content_hash: String,
+    new_content_hash: String,
+    /// Added, removed and changed assets, sorted by path
+    assets: Vec<AssetChange>,
+    /// Number of assets identical in both overlays
+    unchanged: usize,
+    /// Total asset size change in bytes
+    size_delta: i64,
+    /// `PackConfig` changes
+    config: Vec<ConfigChange>,
+}
+
+/// `inspect --diff --json` output
+#[derive(Debug, Serialize)]
+struct DiffReport<'a> {
+    old: &'a Path,
+    new: &'a Path,
+    #[serde(flatten)]
+    diff: &'a OverlayDiff,
+}
+
+/// Result of `inspect --extract`
+#[derive(Debug, Clone, Serialize)]
+struct ExtractReport {
+    output: PathBuf,
+    config: PathBuf,
+    assets: usize,
+    bytes: u64,
 }
 
 /// Run the inspect command
 pub fn run_inspect(args: InspectArgs) -> Result<()> {
-    let exe_path = &args.exe_path;
+    if let Some(paths) = &args.diff {
+        let [old, new] = paths.as_slice() else {
+            anyhow::bail!("--diff expects exactly two executables: OLD NEW");
+        };
+        return run_diff(old, new, args.json);
+    }
 
-    if !exe_path.exists() {
-        anyhow::bail!("File not found: {}", exe_path.display());
+    let exe_path = args
+        .exe_path
+        .as_deref()
+        .ok_or_else(|| anyhow::anyhow!("No packed executable given"))?;
+    let overlay = read_overlay(exe_path)?;
+
+    if let Some(dir) = &args.extract {
+        let report = extract_overlay(&overlay, dir)?;
+        if args.json {
+            println!("{}", serde_json::to_string_pretty(&report)?);
+        } else {
+            println!(
+                "Extracted {} assets ({}) to {}",
+                report.assets,
+                format_size(report.bytes),
+                report.output.display()
+            );
+            println!("Config written to {}", report.config.display());
+        }
+        return Ok(());
+    }
+
+    let filter_pattern = args.filter.as_ref().and_then(|f| {
+        let pattern = f.replace('*', ".*");
+        regex::Regex::new(&pattern).ok()
+    });
+
+    if args.json {
+        let mut assets: Vec<AssetEntry> = overlay
+            .assets
+            .iter()
+            .filter(|(path, _)| filter_pattern.as_ref().is_none_or(|p| p.is_match(path)))
+            .map(|(path, content)| AssetEntry {
+                path: path.clone(),
+                size: content.len() as u64,
+            })
+            .collect();
+        assets.sort_by(|a, b| a.path.cmp(&b.path));
+        let report = InspectReport {
+            path: exe_path,
+            content_hash: &overlay.content_hash,
+            signed_by: overlay.signature.as_ref().map(|s| s.key_id.as_str()),
+            config: &overlay.config,
+            assets,
+        };
+        println!("{}", serde_json::to_string_pretty(&report)?);
+        return Ok(());
     }
 
     println!("Inspecting: {}", exe_path.display());
     println!("{}", "=".repeat(60));
 
-    // Read overlay data
-    let overlay = OverlayReader::read(exe_path)
-        .with_context(|| format!("Failed to read overlay from: {}", exe_path.display()))?
-        .ok_or_else(|| anyhow::anyhow!("No overlay data found in: {}", exe_path.display()))?;
-
     // Display configuration
     println!("\n[Configuration]");
     println!("  Window Title: {}", overlay.config.window.title);
@@ -48,6 +194,10 @@ pub fn run_inspect(args: InspectArgs) -> Result<()> {
         "  Window Size: {}x{}",
         overlay.config.window.width, overlay.config.window.height
     );
+    match overlay.signature {
+        Some(ref signature) => println!("  Signed: yes (key {})", signature.key_id),
+        None => println!("  Signed: no"),
+    }
 
     // Display Python backend info if FullStack mode
     if let PackMode::FullStack { ref python, .. } = overlay.config.mode {
@@ -87,12 +237,6 @@ pub fn run_inspect(args: InspectArgs) -> Result<()> {
     let mut assets: Vec<_> = overlay.assets.iter().collect();
     assets.sort_by(|a, b| a.0.cmp(&b.0));
 
-    // Filter if pattern provided
-    let filter_pattern = args.filter.as_ref().and_then(|f| {
-        let pattern = f.replace('*', ".*");
-        regex::Regex::new(&pattern).ok()
-    });
-
     let mut shown_count = 0;
     for (path, content) in &assets {
         // Apply filter
@@ -103,13 +247,7 @@ pub fn run_inspect(args: InspectArgs) -> Result<()> {
         }
 
         let size = content.len();
-        let size_str = if size < 1024 {
-            format!("{} B", size)
-        } else if size < 1024 * 1024 {
-            format!("{:.2} KB", size as f64 / 1024.0)
-        } else {
-            format!("{:.2} MB", size as f64 / 1024.0 / 1024.0)
-        };
+        let size_str = format_size(size as u64);
 
         // Check if it's the main index.html
         let marker = if *path == "index.html" || *path == "frontend/index.html" {
@@ -199,6 +337,240 @@ pub fn run_inspect(args: InspectArgs) -> Result<()> {
     Ok(())
 }
 
+/// Compare two packed executables and print the result
+fn run_diff(old_path: &Path, new_path: &Path, json: bool) -> Result<()> {
+    let old = read_overlay(old_path)?;
+    let new = read_overlay(new_path)?;
+    let diff = diff_overlays(&old, &new)?;
+
+    if json {
+        let report = DiffReport {
+            old: old_path,
+            new: new_path,
+            diff: &diff,
+        };
+        println!("{}", serde_json::to_string_pretty(&report)?);
+        return Ok(());
+    }
+
+    println!("Comparing: {}", old_path.display());
+    println!("       to: {}", new_path.display());
+    println!("{}", "=".repeat(60));
+
+    let count = |status| diff.assets.iter().filter(|a| a.status == status).count();
+    println!(
+        "\n[Assets] {} added, {} removed, {} changed, {} unchanged",
+        count(AssetStatus::Added),
+        count(AssetStatus::Removed),
+        count(AssetStatus::Changed),
+        diff.unchanged
+    );
+    for change in &diff.assets {
+        match change.status {
+            AssetStatus::Added => println!(
+                "  + {} ({})",
+                change.path,
+                format_size(change.new_size.unwrap_or(0))
+            ),
+            AssetStatus::Removed => println!(
+                "  - {} ({})",
+                change.path,
+                format_size(change.old_size.unwrap_or(0))
+            ),
+            AssetStatus::Changed => println!(
+                "  ~ {} ({} -> {}, {})",
+                change.path,
+                format_size(change.old_size.unwrap_or(0)),
+                format_size(change.new_size.unwrap_or(0)),
+                format_delta(change.size_delta)
+            ),
+        }
+    }
+    println!("  Total size change: {}", format_delta(diff.size_delta));
+
+    println!("\n[Configuration] {} changed", diff.config.len());
+    let show = |value: &Option<Value>| {
+        value
+            .as_ref()
+            .map_or_else(|| "(absent)".to_string(), Value::to_string)
+    };
+    for change in &diff.config {
+        println!(
+            "  {}: {} -> {}",
+            change.path,
+            show(&change.old),
+            show(&change.new)
+        );
+    }
+
+    println!("\n{}", "=".repeat(60));
+    Ok(())
+}
+
+/// Compute asset and configuration differences between two overlays
+fn diff_overlays(old: &OverlayData, new: &OverlayData) -> Result<OverlayDiff> {
+    let old_assets: HashMap<&str, &[u8]> = old
+        .assets
+        .iter()
+        .map(|(p, c)| (p.as_str(), c.as_slice()))
+        .collect();
+    let new_assets: HashMap<&str, &[u8]> = new
+        .assets
+        .iter()
+        .map(|(p, c)| (p.as_str(), c.as_slice()))
+        .collect();
+    let paths: BTreeSet<&str> = old_assets
+        .keys()
+        .chain(new_assets.keys())
+        .copied()
+        .collect();
+
+    let mut assets = Vec::new();
+    let mut unchanged = 0;
+    for path in paths {
+        let (old_content, new_content) = (old_assets.get(path), new_assets.get(path));
+        let status = match (old_content, new_content) {
+            (Some(a), Some(b)) if a == b => {
+                unchanged += 1;
+                continue;
+            }
+            (Some(_), Some(_)) => AssetStatus::Changed,
+            (None, _) => AssetStatus::Added,
+            (_, None) => AssetStatus::Removed,
+        };
+        let old_size = old_content.map(|c| c.len() as u64);
+        let new_size = new_content.map(|c| c.len() as u64);
+        assets.push(AssetChange {
+            path: path.to_string(),
+            status,
+            old_size,
+            new_size,
+            size_delta: new_size.unwrap_or(0) as i64 - old_size.unwrap_or(0) as i64,
+        });
+    }
+
+    let mut config = Vec::new();
+    diff_json(
+        "",
+        &serde_json::to_value(&old.config)?,
+        &serde_json::to_value(&new.config)?,
+        &mut config,
+    );
+
+    Ok(OverlayDiff {
+        old_content_hash: old.content_hash.clone(),
+        new_content_hash: new.content_hash.clone(),
+        size_delta: assets.iter().map(|a| a.size_delta).sum(),
+        assets,
+        unchanged,
+        config,
+    })
+}
+
+/// Collect leaf differences between two JSON values as JSON pointers
+///
+/// Objects are compared key by key; arrays and scalars are compared whole.
+fn diff_json(path: &str, old: &Value, new: &Value, out: &mut Vec<ConfigChange>) {
+    match (old, new) {
+        (Value::Object(a), Value::Object(b)) => {
+            let keys: BTreeSet<&String> = a.keys().chain(b.keys()).collect();
+            for key in keys {
+                let child = format!("{}/{}", path, key.replace('~', "~0").replace('/', "~1"));
+                match (a.get(key), b.get(key)) {
+                    (Some(x), Some(y)) => diff_json(&child, x, y, out),
+                    (x, y) => out.push(ConfigChange {
+                        path: child,
+                        old: x.cloned(),
+                        new: y.cloned(),
+                    }),
+                }
+            }
+        }
+        _ if old != new => out.push(ConfigChange {
+            path: if path.is_empty() { "/" } else { path }.to_string(),
+            old: Some(old.clone()),
+            new: Some(new.clone()),
+        }),
+        _ => {}
+    }
+}
+
+/// Write an overlay's assets to `dir/assets/` and its configuration to
+/// `dir/config.json` (plus `dir/overlay.json` with hash, signature and asset list)
+fn extract_overlay(overlay: &OverlayData, dir: &Path) -> Result<ExtractReport> {
+    let assets_dir = dir.join("assets");
+    std::fs::create_dir_all(&assets_dir)
+        .with_context(|| format!("Failed to create {}", assets_dir.display()))?;
+
+    let mut bytes = 0u64;
+    let mut entries = Vec::with_capacity(overlay.assets.len());
+    for (path, content) in &overlay.assets {
+        let relative = Path::new(path);
+        if !relative
+            .components()
+            .all(|c| matches!(c, Component::Normal(_) | Component::CurDir))
+        {
+            anyhow::bail!("Refusing to extract asset with unsafe path: {}", path);
+        }
+        let dest = assets_dir.join(relative);
+        if let Some(parent) = dest.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(&dest, content)
+            .with_context(|| format!("Failed to write {}", dest.display()))?;
+        bytes += content.len() as u64;
+        entries.push(AssetEntry {
+            path: path.clone(),
+            size: content.len() as u64,
+        });
+    }
+
+    let config_path = dir.join("config.json");
+    std::fs::write(&config_path, serde_json::to_vec_pretty(&overlay.config)?)
+        .with_context(|| format!("Failed to write {}", config_path.display()))?;
+
+    let overlay_info = serde_json::json!({
+        "content_hash": overlay.content_hash,
+        "signature": overlay.signature,
+        "assets": entries,
+    });
+    std::fs::write(
+        dir.join("overlay.json"),
+        serde_json::to_vec_pretty(&overlay_info)?,
+    )?;
+
+    Ok(ExtractReport {
+        output: dir.to_path_buf(),
+        config: config_path,
+        assets: overlay.assets.len(),
+        bytes,
+    })
+}
+
+fn read_overlay(exe_path: &Path) -> Result<OverlayData> {
+    if !exe_path.exists() {
+        anyhow::bail!("File not found: {}", exe_path.display());
+    }
+    OverlayReader::read(exe_path)
+        .with_context(|| format!("Failed to read overlay from: {}", exe_path.display()))?
+        .ok_or_else(|| anyhow::anyhow!("No overlay data found in: {}", exe_path.display()))
+}
+
+fn format_size(size: u64) -> String {
+    if size < 1024 {
+        format!("{} B", size)
+    } else if size < 1024 * 1024 {
+        format!("{:.2} KB", size as f64 / 1024.0)
+    } else {
+        format!("{:.2} MB", size as f64 / 1024.0 / 1024.0)
+    }
+}
+
+fn format_delta(delta: i64) -> String {
+    let sign = if delta < 0 { "-" } else { "+" };
+    format!("{}{}", sign, format_size(delta.unsigned_abs()))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -237,9 +609,12 @@ mod tests {
     #[test]
     fn inspect_missing_file_errors() {
         let args = InspectArgs {
-            exe_path: PathBuf::from("/no/such/packed-exe-xyz"),
+            exe_path: Some(PathBuf::from("/no/such/packed-exe-xyz")),
             show_content: false,
             filter: None,
+            diff: None,
+            extract: None,
+            json: false,
         };
         let err = run_inspect(args).unwrap_err();
         assert!(err.to_string().contains("File not found"));
@@ -250,9 +625,12 @@ mod tests {
         let temp = NamedTempFile::new().unwrap();
         fs::write(temp.path(), b"not a packed exe").unwrap();
         let args = InspectArgs {
-            exe_path: temp.path().to_path_buf(),
+            exe_path: Some(temp.path().to_path_buf()),
             show_content: false,
             filter: None,
+            diff: None,
+            extract: None,
+            json: false,
         };
         let err = run_inspect(args).unwrap_err();
         assert!(err.to_string().contains("No overlay data"));
@@ -269,9 +647,12 @@ mod tests {
             ],
         );
         let args = InspectArgs {
-            exe_path: temp.path().to_path_buf(),
+            exe_path: Some(temp.path().to_path_buf()),
             show_content: true,
             filter: None,
+            diff: None,
+            extract: None,
+            json: false,
         };
         // run_inspect prints to stdout; we only assert it walks the whole path Ok.
         run_inspect(args).expect("inspect should succeed on a valid URL overlay");
@@ -293,9 +674,12 @@ mod tests {
             ],
         );
         let args = InspectArgs {
-            exe_path: temp.path().to_path_buf(),
+            exe_path: Some(temp.path().to_path_buf()),
             show_content: false,
             filter: Some("*.html".to_string()),
+            diff: None,
+            extract: None,
+            json: false,
         };
         run_inspect(args).expect("inspect should succeed on a FullStack overlay");
     }
@@ -306,10 +690,174 @@ mod tests {
         let config = PackConfig::url("https://example.com");
         let temp = packed_exe(config, &[("about.html", b"<html></html>")]);
         let args = InspectArgs {
-            exe_path: temp.path().to_path_buf(),
+            exe_path: Some(temp.path().to_path_buf()),
             show_content: false,
             filter: None,
+            diff: None,
+            extract: None,
+            json: false,
         };
         run_inspect(args).expect("inspect should still succeed (warning only)");
     }
+
+    fn overlay(title: &str, assets: &[(&str, &[u8])]) -> OverlayData {
+        let mut data = OverlayData::new(PackConfig::url("https://example.com").with_title(title));
+        for (path, content) in assets {
+            data.add_asset((*path).to_string(), content.to_vec());
+        }
+        data
+    }
+
+    #[test]
+    fn diff_reports_asset_changes_with_size_deltas() {
+        let old = overlay(
+            "V1",
+            &[
+                ("index.html", b"<html></html>"),
+                ("app.js", b"1"),
+                ("old.css", b"body{}"),
+            ],
+        );
+        let new = overlay(
+            "V2",
+            &[
+                ("index.html", b"<html></html>"),
+                ("app.js", b"12345"),
+                ("new.css", b"main{}!!"),
+            ],
+        );
+
+        let diff = diff_overlays(&old, &new).unwrap();
+        assert_eq!(diff.unchanged, 1);
+        assert_eq!(
+            diff.assets,
+            vec![
+                AssetChange {
+                    path: "app.js".to_string(),
+                    status: AssetStatus::Changed,
+                    old_size: Some(1),
+                    new_size: Some(5),
+                    size_delta: 4,
+                },
+                AssetChange {
+                    path: "new.css".to_string(),
+                    status: AssetStatus::Added,
+                    old_size: None,
+                    new_size: Some(8),
+                    size_delta: 8,
+                },
+                AssetChange {
+                    path: "old.css".to_string(),
+                    status: AssetStatus::Removed,
+                    old_size: Some(6),
+                    new_size: None,
+                    size_delta: -6,
+                },
+            ]
+        );
+        assert_eq!(diff.size_delta, 6);
+        assert_eq!(
+            diff.config,
+            vec![ConfigChange {
+                path: "/window/title".to_string(),
+                old: Some("V1".into()),
+                new: Some("V2".into()),
+            }]
+        );
+    }
+
+    #[test]
+    fn diff_json_uses_json_pointers() {
+        let old = serde_json::json!({"a": {"b/c": 1, "gone": true}, "list": [1, 2]});
+        let new = serde_json::json!({"a": {"b/c": 2, "added": "x"}, "list": [1, 2]});
+        let mut changes = Vec::new();
+        diff_json("", &old, &new, &mut changes);
+        let paths: Vec<&str> = changes.iter().map(|c| c.path.as_str()).collect();
+        assert_eq!(paths, vec!["/a/added", "/a/b~1c", "/a/gone"]);
+        assert_eq!(changes[0].old, None);
+        assert_eq!(changes[2].new, None);
+
+        let mut changes = Vec::new();
+        diff_json(
+            "",
+            &serde_json::json!(1),
+            &serde_json::json!(2),
+            &mut changes,
+        );
+        assert_eq!(changes[0].path, "/");
+    }
+
+    #[test]
+    fn extract_writes_assets_and_config() {
+        let dir = tempfile::tempdir().unwrap();
+        let data = overlay(
+            "Extract",
+            &[("index.html", b"<html></html>"), ("js/app.js", b"go()")],
+        );
+
+        let report = extract_overlay(&data, dir.path()).unwrap();
+        assert_eq!(report.assets, 2);
+        assert_eq!(report.bytes, 17);
+        assert_eq!(
+            fs::read(dir.path().join("assets/js/app.js")).unwrap(),
+            b"go()"
+        );
+        let config: PackConfig =
+            serde_json::from_slice(&fs::read(dir.path().join("config.json")).unwrap()).unwrap();
+        assert_eq!(config.window.title, "Extract");
+        let info: Value =
+            serde_json::from_slice(&fs::read(dir.path().join("overlay.json")).unwrap()).unwrap();
+        assert_eq!(info["assets"].as_array().unwrap().len(), 2);
+    }
+
+    #[test]
+    fn extract_rejects_escaping_paths() {
+        let dir = tempfile::tempdir().unwrap();
+        let data = overlay("Evil", &[("../escape.txt", b"x")]);
+        let err = extract_overlay(&data, &dir.path().join("out")).unwrap_err();
+        assert!(err.to_string().contains("unsafe path"));
+        assert!(!dir.path().join("escape.txt").exists());
+    }
+
+    #[test]
+    fn inspect_diff_and_extract_run_end_to_end() {
+        let old = packed_exe(PackConfig::url("https://example.com"), &[("a", b"1")]);
+        let new = packed_exe(PackConfig::url("https://example.org"), &[("b", b"2")]);
+        let out = tempfile::tempdir().unwrap();
+
+        #[derive(clap::Parser)]
+        struct Cli {
+            #[command(flatten)]
+            inspect: InspectArgs,
+        }
+        let parse = |args: &[&std::ffi::OsStr]| {
+            <Cli as clap::Parser>::try_parse_from(
+                std::iter::once("inspect".as_ref()).chain(args.iter().copied()),
+            )
+            .map(|cli| cli.inspect)
+        };
+
+        let diff = parse(&[
+            "--diff".as_ref(),
+            old.path().as_os_str(),
+            new.path().as_os_str(),
+            "--json".as_ref(),
+        ])
+        .unwrap();
+        assert!(diff.exe_path.is_none());
+        run_inspect(diff).unwrap();
+
+        let extract = parse(&[
+            old.path().as_os_str(),
+            "--extract".as_ref(),
+            out.path().as_os_str(),
+        ])
+        .unwrap();
+        run_inspect(extract).unwrap();
+        assert!(out.path().join("assets/a").exists());
+
+        // A single executable or a missing target are usage errors
+        assert!(parse(&["--diff".as_ref(), old.path().as_os_str()]).is_err());
+        assert!(parse(&[]).is_err());
+    }
 }
@@ -6,6 +6,7 @@ mod icon;
 mod info;
 mod inspect;
 mod pack;
+mod patch;
 mod run;
 mod self_update;
 mod skills;
@@ -14,6 +15,7 @@ pub use icon::{run_icon, IconArgs};
 pub use info::run_info;
 pub use inspect::{run_inspect, InspectArgs};
 pub use pack::{resolve_capture_file_drop, run_pack, PackArgs};
+pub use patch::{apply_patch_file, run_patch, PatchArgs};
 pub use run::{resolve_capture_file_drop as resolve_run_capture_file_drop, run_webview, RunArgs};
 pub use self_update::{run_self_update, SelfUpdateArgs};
 pub use skills::{run_skills, SkillsArgs};
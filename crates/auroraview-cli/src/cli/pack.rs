@@ -112,6 +112,15 @@ pub struct PackArgs {
         overrides_with = "capture_file_drop"
     )]
     pub no_capture_file_drop: bool,
+
+    /// Sign the overlay with this Ed25519 private key file
+    /// Overrides `[build].signing_key` in the manifest
+    #[arg(long, value_name = "FILE")]
+    pub signing_key: Option<PathBuf>,
+
+    /// Generate a new Ed25519 signing key at FILE and exit
+    #[arg(long, value_name = "FILE", exclusive = true)]
+    pub generate_signing_key: Option<PathBuf>,
 }
 
 /// Resolve the user's `--capture-file-drop` / `--no-capture-file-drop`
@@ -128,10 +137,40 @@ fn format_path(path: &Path) -> String {
     path.to_string_lossy().replace('\\', "/")
 }
 
+/// Write a new overlay signing key and print its public half
+fn generate_signing_key(path: &Path) -> Result<()> {
+    if path.exists() {
+        anyhow::bail!("Refusing to overwrite existing key: {}", format_path(path));
+    }
+    let key = auroraview_pack::OverlaySigningKey::generate();
+    key.write_to_file(path)
+        .with_context(|| format!("Failed to write signing key: {}", format_path(path)))?;
+
+    let public = key.verifying_key();
+    println!("Signing key written: {}", format_path(path));
+    println!("  Key id:     {}", public.key_id());
+    println!("  Public key: {}", public.to_hex());
+    println!();
+    println!(
+        "Pack with: auroraview pack --signing-key {}",
+        format_path(path)
+    );
+    println!("Signed apps are pinned to this key and refuse overlays signed by any other.");
+    println!(
+        "To trust it in every app from a runtime build, set AURORAVIEW_TRUSTED_OVERLAY_KEYS={}",
+        public.to_hex()
+    );
+    Ok(())
+}
+
 /// Run the pack command
 pub fn run_pack(args: PackArgs) -> Result<()> {
     use auroraview_pack::{Manifest, PackConfig, PackManager};
 
+    if let Some(key_path) = &args.generate_signing_key {
+        return generate_signing_key(key_path);
+    }
+
     // Resolve drag-drop tri-state up-front so we don't borrow `args` after
     // it has been partially moved during config construction.
     let capture_file_drop_override = resolve_capture_file_drop(&args);
@@ -360,6 +399,9 @@ pub fn run_pack(args: PackArgs) -> Result<()> {
     if let Some(output_dir) = args.output_dir {
         config.output_dir = output_dir;
     }
+    if let Some(signing_key) = args.signing_key {
+        config = config.with_signing_key(std::env::current_dir()?.join(signing_key));
+    }
 
     // Apply Windows resource overrides from CLI
     if let Some(icon) = args.icon {
@@ -0,0 +1,134 @@
+//! Patch command - Create and apply delta patches between packed executables
+//!
+//! A patch carries only the overlay assets that changed between two packed
+//! builds, so shipping a small frontend/Python change does not require
+//! re-downloading the whole executable.
+
+use anyhow::{Context, Result};
+use auroraview_pack::{ApplyOptions, Patch, PatchStats};
+use clap::{Parser, Subcommand};
+use std::path::{Path, PathBuf};
+
+/// Arguments for the 'patch' subcommand
+#[derive(Parser, Debug)]
+pub struct PatchArgs {
+    #[command(subcommand)]
+    pub command: PatchCommands,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum PatchCommands {
+    /// Create a patch that upgrades OLD to NEW
+    Create {
+        /// Currently deployed packed executable
+        #[arg(value_name = "OLD")]
+        old: PathBuf,
+
+        /// New packed executable
+        #[arg(value_name = "NEW")]
+        new: PathBuf,
+
+        /// Output patch file (defaults to <NEW>.avpt)
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+
+        /// zstd compression level (1-22)
+        #[arg(short, long, default_value = "19")]
+        level: i32,
+    },
+
+    /// Apply a patch to a packed executable (in place unless --output is given)
+    Apply {
+        /// Packed executable to patch
+        #[arg(value_name = "PACKED_EXE")]
+        exe: PathBuf,
+
+        /// Patch file
+        #[arg(value_name = "PATCH")]
+        patch: PathBuf,
+
+        /// Write the patched executable here instead of replacing PACKED_EXE
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+
+        /// Accept a new runtime that pins different signing keys
+        #[arg(long)]
+        allow_key_rotation: bool,
+    },
+}
+
+/// Run the patch command
+pub fn run_patch(args: PatchArgs) -> Result<()> {
+    match args.command {
+        PatchCommands::Create {
+            old,
+            new,
+            output,
+            level,
+        } => {
+            let output = output.unwrap_or_else(|| new.with_extension("avpt"));
+            let patch = Patch::create(&old, &new).with_context(|| {
+                format!(
+                    "Failed to create patch from {} to {}",
+                    old.display(),
+                    new.display()
+                )
+            })?;
+            let size = patch
+                .write(&output, level)
+                .with_context(|| format!("Failed to write patch: {}", output.display()))?;
+
+            print_stats(&patch.stats());
+            let full_size = std::fs::metadata(&new).map(|m| m.len()).unwrap_or(0);
+            println!(
+                "Patch written: {} ({:.2} MB, full executable {:.2} MB)",
+                output.display(),
+                size as f64 / 1_048_576.0,
+                full_size as f64 / 1_048_576.0
+            );
+            Ok(())
+        }
+        PatchCommands::Apply {
+            exe,
+            patch,
+            output,
+            allow_key_rotation,
+        } => {
+            let options = ApplyOptions::new().with_key_rotation(allow_key_rotation);
+            let stats = apply_patch_file(&exe, &patch, output.as_deref(), &options)?;
+            print_stats(&stats);
+            println!("Patched: {}", output.as_deref().unwrap_or(&exe).display());
+            Ok(())
+        }
+    }
+}
+
+/// Read `patch_path` and apply it to `exe`, in place or into `output`
+pub fn apply_patch_file(
+    exe: &Path,
+    patch_path: &Path,
+    output: Option<&Path>,
+    options: &ApplyOptions,
+) -> Result<PatchStats> {
+    let patch = Patch::read(patch_path)
+        .with_context(|| format!("Failed to read patch: {}", patch_path.display()))?;
+    let stats = patch
+        .apply_to_with_options(exe, output.unwrap_or(exe), options)
+        .with_context(|| format!("Failed to apply patch to {}", exe.display()))?;
+    Ok(stats)
+}
+
+fn print_stats(stats: &PatchStats) {
+    println!(
+        "Assets: {} kept, {} added, {} replaced, {} removed",
+        stats.kept, stats.added, stats.replaced, stats.removed
+    );
+    println!(
+        "Runtime: {}",
+        if stats.runtime_changed {
+            "replaced"
+        } else {
+            "unchanged"
+        }
+    );
+}
@@ -35,8 +35,8 @@ use clap::{Parser, Subcommand};
 use std::path::PathBuf;
 
 use auroraview_cli::cli::{
-    run_icon, run_info, run_inspect, run_pack, run_self_update, run_skills, run_webview, IconArgs,
-    InspectArgs, PackArgs, RunArgs, SelfUpdateArgs, SkillsArgs,
+    run_icon, run_info, run_inspect, run_pack, run_patch, run_self_update, run_skills, run_webview,
+    IconArgs, InspectArgs, PackArgs, PatchArgs, RunArgs, SelfUpdateArgs, SkillsArgs,
 };
 use auroraview_cli::packed;
 
@@ -114,6 +114,9 @@ enum Commands {
     /// Inspect a packed executable's overlay data (for debugging)
     Inspect(InspectArgs),
 
+    /// Create or apply delta patches between packed executables
+    Patch(PatchArgs),
+
     /// Self-update to the latest version
     #[command(name = "self-update")]
     SelfUpdate(SelfUpdateArgs),
@@ -145,6 +148,7 @@ fn main() -> Result<()> {
         Some(Commands::Icon(args)) => run_icon(args),
         Some(Commands::Info) => run_info(),
         Some(Commands::Inspect(args)) => run_inspect(args),
+        Some(Commands::Patch(args)) => run_patch(args),
         Some(Commands::SelfUpdate(args)) => run_self_update(args),
         Some(Commands::Skills(args)) => run_skills(args),
         None => {
@@ -13,6 +13,7 @@
 //! | `some/file.proj`               | GUI (open path later) |
 //! | `run <cmd> [--k v ...]`        | CLI: invoke a command |
 //! | `list [--json]`                | CLI: list commands    |
+//! | `apply-patch <file.avpt>`      | CLI: self-patch       |
 //! | `-h` / `--help`                | CLI: print help       |
 //! | `-V` / `--version`             | CLI: print version    |
 //!
@@ -50,7 +51,10 @@ pub enum PackedInvocation {
 }
 
 /// Reserved subcommand verbs that trigger the CLI path (§4.3, decision #1).
-const RESERVED_VERBS: &[&str] = &["run", "list"];
+///
+/// `apply-patch` applies a delta patch (see `auroraview patch create`) to the
+/// packed executable itself; the update takes effect on the next launch.
+const RESERVED_VERBS: &[&str] = &["run", "list", "apply-patch"];
 
 /// Classify a packed invocation from the full process argument list.
 ///
@@ -122,6 +126,27 @@ pub fn run_packed_cli(cli_args: Vec<String>) -> Result<()> {
             Ok(())
         }
         "run" => super::run::run_command(&cli_args[1..]),
+        "apply-patch" => {
+            let Some(patch_path) = cli_args.get(1) else {
+                anyhow::bail!(
+                    "usage: {} apply-patch <file.avpt> [--allow-key-rotation]",
+                    program_name()
+                );
+            };
+            let rotate = cli_args.iter().skip(2).any(|a| a == "--allow-key-rotation");
+            let options = auroraview_pack::ApplyOptions::new().with_key_rotation(rotate);
+            let exe_path = std::env::current_exe()?;
+            let stats =
+                crate::cli::apply_patch_file(&exe_path, Path::new(patch_path), None, &options)?;
+            println!(
+                "Patched {} ({} added, {} replaced, {} removed); restart to use the update",
+                exe_path.display(),
+                stats.added,
+                stats.replaced,
+                stats.removed
+            );
+            Ok(())
+        }
         _ => {
             eprintln!("auroraview: unknown CLI invocation: {first}");
             std::process::exit(2);
@@ -207,6 +232,19 @@ mod tests {
         );
     }
 
+    #[test]
+    fn apply_patch_verb_triggers_cli() {
+        assert_eq!(
+            classify(&["app.exe", "apply-patch", "update.avpt"]),
+            PackedInvocation::Cli(vec!["apply-patch".into(), "update.avpt".into()])
+        );
+    }
+
+    #[test]
+    fn apply_patch_without_file_is_an_error() {
+        assert!(run_packed_cli(vec!["apply-patch".to_string()]).is_err());
+    }
+
     #[test]
     fn help_and_version_flags_trigger_cli() {
         for flag in ["-h", "--help", "-V", "--version"] {
@@ -36,7 +36,7 @@ mod webview;
 use std::time::Instant;
 
 use anyhow::{Context, Result};
-use auroraview_pack::{OverlayReader, PackedMetrics};
+use auroraview_pack::{OverlayReader, PackedMetrics, SignaturePolicy};
 
 // Re-export public items
 pub use utils::{
@@ -178,8 +178,11 @@ pub fn run_packed_app() -> Result<()> {
 
     // Read overlay data from the executable with metrics
     // Note: WebView2 warmup is running in parallel during this I/O operation
+    // Refuses to start if the overlay signature is invalid, or missing when
+    // this runtime was built with pinned keys
     let exe_path = std::env::current_exe()?;
-    let overlay = OverlayReader::read_with_metrics(&exe_path, Some(&mut metrics))
+    let policy = SignaturePolicy::runtime().with_context(|| "Invalid trusted overlay keys")?;
+    let overlay = OverlayReader::read_with_policy(&exe_path, &policy, Some(&mut metrics))
         .with_context(|| "Failed to read overlay data")?
         .ok_or_else(|| anyhow::anyhow!("No overlay data found in packed executable"))?;
 
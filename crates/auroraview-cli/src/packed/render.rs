@@ -17,6 +17,7 @@ pub fn render_help(program: &str, commands: &[CliCommandMeta]) -> String {
     out.push_str("USAGE:\n");
     out.push_str(&format!("    {program} run <command> [--key value ...]\n"));
     out.push_str(&format!("    {program} list [--json]\n"));
+    out.push_str(&format!("    {program} apply-patch <file.avpt>\n"));
     out.push_str(&format!("    {program} -h | --help\n"));
     out.push_str(&format!("    {program} -V | --version\n"));
 
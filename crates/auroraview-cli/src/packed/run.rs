@@ -48,7 +48,9 @@ pub fn run_command(args: &[String]) -> Result<()> {
     };
 
     let exe_path = std::env::current_exe().context("locate current executable")?;
-    let overlay = match auroraview_pack::OverlayReader::read(&exe_path)? {
+    let policy = auroraview_pack::SignaturePolicy::runtime()?;
+    let overlay = match auroraview_pack::OverlayReader::read_with_policy(&exe_path, &policy, None)?
+    {
         Some(o) => o,
         None => {
             eprintln!("auroraview: no packed overlay found");
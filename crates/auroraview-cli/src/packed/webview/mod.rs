@@ -28,6 +28,8 @@ use anyhow::{Context, Result};
 use auroraview_core::assets::{
     build_error_page, build_packed_init_script_with_csp, get_loading_html,
 };
+use auroraview_core::cookies::CookieJar;
+use auroraview_core::plugins::extensions::ExtensionsPlugin;
 use auroraview_core::plugins::{PathScope, PluginRequest, ScopeConfig, ShellScope};
 use auroraview_core::protocol::MemoryAssets;
 use auroraview_pack::{OverlayData, PackMode, PackedMetrics};
@@ -119,6 +121,17 @@ pub fn run_packed_webview(overlay: OverlayData, mut metrics: PackedMetrics) -> R
 
     let proxy = event_loop.create_proxy();
 
+    // Cookie jar shared by the WebView and `chrome.cookies`
+    let data_dir = get_webview_data_dir();
+    // Keep session cookies too, so logins survive relaunching the app
+    let cookie_jar = Arc::new(match CookieJar::for_profile(&data_dir) {
+        Ok(jar) => jar.persist_session_cookies(true),
+        Err(e) => {
+            tracing::warn!("[packed] Failed to open cookie jar, using memory: {}", e);
+            CookieJar::new()
+        }
+    });
+
     // Create PluginRouter with all built-in plugins and secure default scope
     let default_scope = {
         let cwd = std::env::current_dir().unwrap_or_else(|_| std::path::PathBuf::from("."));
@@ -140,9 +153,11 @@ pub fn run_packed_webview(overlay: OverlayData, mut metrics: PackedMetrics) -> R
             .with_shell_scope(ShellScope::new())
     };
 
-    let plugin_router = Arc::new(RwLock::new(auroraview_plugins::create_router_with_scope(
-        default_scope,
-    )));
+    let mut router = auroraview_plugins::create_router_with_extensions(
+        ExtensionsPlugin::new().with_cookie_store(cookie_jar.clone()),
+    );
+    router.set_scope(default_scope);
+    let plugin_router = Arc::new(RwLock::new(router));
 
     // Set up event callback for plugins to emit events to WebView
     let proxy_for_events = proxy.clone();
@@ -249,7 +264,6 @@ pub fn run_packed_webview(overlay: OverlayData, mut metrics: PackedMetrics) -> R
     metrics.mark_window_created();
 
     // Create WebContext
-    let data_dir = get_webview_data_dir();
     let mut web_context = WebContext::new(Some(data_dir));
 
     // Build initialization script
@@ -472,6 +486,11 @@ pub fn run_packed_webview(overlay: OverlayData, mut metrics: PackedMetrics) -> R
         }
     };
 
+    match cookie_jar.seed_webview(&webview) {
+        Ok(count) => tracing::debug!("[packed] Restored {} cookies", count),
+        Err(e) => tracing::warn!("[packed] Failed to restore cookies: {}", e),
+    }
+
     // Start Python backend
     if let PackMode::FullStack { ref python, .. } = config.mode {
         let overlay_for_backend = overlay.clone();
@@ -613,6 +632,9 @@ pub fn run_packed_webview(overlay: OverlayData, mut metrics: PackedMetrics) -> R
                     tracing::info!("Stopping Python backend...");
                     backend.shutdown();
                 }
+                if let Ok(wv) = webview_for_event.try_borrow() {
+                    save_cookies(&cookie_jar, &wv);
+                }
                 *control_flow = ControlFlow::Exit;
             }
             tao::event::Event::UserEvent(user_event) => {
@@ -878,6 +900,7 @@ pub fn run_packed_webview(overlay: OverlayData, mut metrics: PackedMetrics) -> R
                                 tracing::info!("Stopping Python backend...");
                                 backend.shutdown();
                             }
+                            save_cookies(&cookie_jar, &wv);
                             *control_flow = ControlFlow::Exit;
                         }
                         UserEvent::PythonCrash {
@@ -1037,3 +1060,11 @@ pub fn run_packed_webview(overlay: OverlayData, mut metrics: PackedMetrics) -> R
     #[allow(unreachable_code)]
     Ok(())
 }
+
+/// Write the WebView's cookies back to the profile cookie jar before exit
+fn save_cookies(jar: &CookieJar, webview: &wry::WebView) {
+    match jar.save_webview(webview) {
+        Ok(count) => tracing::debug!("[packed] Saved {} cookies", count),
+        Err(e) => tracing::warn!("[packed] Failed to save cookies: {}", e),
+    }
+}
@@ -469,6 +469,16 @@ pub fn get_channel_bridge_js() -> String {
         .unwrap_or_default()
 }
 
+/// Get the blob bridge JavaScript code
+///
+/// Provides `auroraview.blob` for fetching and uploading binary payloads
+/// through the IPC blob store (see [`crate::ipc::BlobStore`]).
+pub fn get_blob_bridge_js() -> String {
+    Assets::get("js/core/blob_bridge.js")
+        .map(|f| String::from_utf8_lossy(&f.data).to_string())
+        .unwrap_or_default()
+}
+
 /// Get the event utilities JavaScript code
 ///
 /// This script provides utility functions for event handling:
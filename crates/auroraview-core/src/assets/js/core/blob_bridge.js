@@ -0,0 +1,129 @@
+/**
+ * AuroraView Blob Bridge
+ *
+ * Transfers large binary payloads through the Rust-side blob store instead
+ * of JSON/base64 IPC. Blobs are addressed by handles ({id, size, mime, url})
+ * and fetched or uploaded through the auroraview custom protocol.
+ *
+ * Usage in JavaScript:
+ *   // Python sends a handle, e.g. via emit("preview_ready", handle)
+ *   const buffer = await auroraview.blob.fetch(handle);   // ArrayBuffer
+ *   await auroraview.blob.release(handle);
+ *
+ *   // Upload to Rust and pass the handle to Python
+ *   const handle = await auroraview.blob.upload(canvasBlob, { lifetime: "one-shot" });
+ *   await auroraview.call("api.save_image", { blob: handle.id });
+ */
+
+(function() {
+    'use strict';
+
+    /**
+     * Base URL of the blob store for the current platform
+     */
+    function baseUrl() {
+        if (window.location.protocol === 'auroraview:') {
+            return 'auroraview://localhost/type:blob/';
+        }
+        if (navigator.userAgent.indexOf('Windows') !== -1) {
+            return 'https://auroraview.localhost/type:blob/';
+        }
+        return 'auroraview://localhost/type:blob/';
+    }
+
+    /**
+     * Resolve a handle or blob ID to its URL
+     * @param {object|string} handle - Handle object or blob ID
+     */
+    function url(handle) {
+        if (handle && typeof handle === 'object') {
+            return handle.url || (baseUrl() + handle.id);
+        }
+        return baseUrl() + handle;
+    }
+
+    function ensureOk(response, action) {
+        if (!response.ok) {
+            throw new Error('[AuroraView] Blob ' + action + ' failed: ' + response.status);
+        }
+        return response;
+    }
+
+    /**
+     * Fetch a blob as an ArrayBuffer
+     * @param {object|string} handle - Handle object or blob ID
+     * @returns {Promise<ArrayBuffer>}
+     */
+    async function fetchBlob(handle) {
+        const response = ensureOk(await fetch(url(handle)), 'fetch');
+        return response.arrayBuffer();
+    }
+
+    /**
+     * Fetch a blob as a Blob (e.g. for URL.createObjectURL)
+     * @param {object|string} handle - Handle object or blob ID
+     * @returns {Promise<Blob>}
+     */
+    async function fetchAsBlob(handle) {
+        const response = ensureOk(await fetch(url(handle)), 'fetch');
+        return response.blob();
+    }
+
+    /**
+     * Upload binary data to the Rust blob store
+     * @param {Blob|ArrayBuffer|ArrayBufferView} data - Payload
+     * @param {object} [options]
+     * @param {string} [options.mime] - MIME type (defaults to the Blob type)
+     * @param {string} [options.lifetime] - "manual", "one-shot" or "ttl:<seconds>"
+     * @returns {Promise<{id: string, size: number, mime: string, url: string}>}
+     */
+    async function upload(data, options) {
+        options = options || {};
+        const mime = options.mime || (data instanceof Blob && data.type) ||
+            'application/octet-stream';
+        const query = options.lifetime ? '?lifetime=' + options.lifetime : '';
+        const response = ensureOk(await fetch(baseUrl() + query, {
+            method: 'POST',
+            headers: { 'Content-Type': mime },
+            body: data
+        }), 'upload');
+        return response.json();
+    }
+
+    /**
+     * Release a blob
+     * @param {object|string} handle - Handle object or blob ID
+     * @returns {Promise<boolean>} Whether the blob existed
+     */
+    async function release(handle) {
+        const response = await fetch(url(handle), { method: 'DELETE' });
+        return response.ok;
+    }
+
+    // Attach to auroraview object
+    function attachToAuroraView() {
+        if (window.auroraview) {
+            window.auroraview.blob = {
+                url: url,
+                fetch: fetchBlob,
+                fetchBlob: fetchAsBlob,
+                upload: upload,
+                release: release
+            };
+            console.log('[AuroraView] Blob bridge initialized');
+        }
+    }
+
+    // Try to attach immediately or wait
+    if (window.auroraview) {
+        attachToAuroraView();
+    } else {
+        const check = setInterval(() => {
+            if (window.auroraview) {
+                clearInterval(check);
+                attachToAuroraView();
+            }
+        }, 10);
+        setTimeout(() => clearInterval(check), 5000);
+    }
+})();
@@ -13,6 +13,19 @@
  *   // Or use with invoke
  *   const result = await auroraview.invoke("stream_data", {});
  *   // result.channel_id contains the channel ID
+ *
+ * Flow control:
+ *   Channels opened with credits (Python: open_channel / create_channel(credits=...))
+ *   acknowledge every message once all handlers consumed it. Handlers returning
+ *   a Promise are awaited first, so a slow consumer throttles the producer.
+ *   Messages buffered before the first onMessage() are only acknowledged when
+ *   they are handled.
+ *
+ *   channel.onMessage(async (row) => { await table.append(row); });
+ *   channel.cancel("user aborted");   // stops the Python producer
+ *   channel.stats;                    // { received, dropped, acked }
+ *
+ *   Leaving or reloading the page cancels every open channel.
  */
 
 (function() {
@@ -31,6 +44,14 @@
             this._closeHandlers = [];
             this._closed = false;
             this._buffer = [];
+            // Flow control (set by a __channel_open__ carrying credits)
+            this._flow = null;
+            this._nextSeq = 0;
+            this._received = 0;
+            this._dropped = 0;
+            this._acked = 0;
+            this._pendingAcks = 0;
+            this._ackScheduled = false;
         }
         
         /**
@@ -42,10 +63,9 @@
             this._messageHandlers.push(handler);
             
             // Flush buffer
-            this._buffer.forEach(data => {
-                try { handler(data); } catch (e) { console.error(e); }
-            });
+            const buffered = this._buffer;
             this._buffer = [];
+            buffered.forEach(data => this._dispatch(data));
             
             return () => {
                 const idx = this._messageHandlers.indexOf(handler);
@@ -71,6 +91,21 @@
             };
         }
         
+        /**
+         * Cancel the channel, telling the Python producer to stop
+         * @param {string} [reason] - Passed to Python on_cancel handlers
+         */
+        cancel(reason) {
+            if (this._closed) return;
+            if (window.auroraview && window.auroraview.send_event) {
+                const payload = { channel_id: this.id };
+                if (reason !== undefined) payload.reason = String(reason);
+                window.auroraview.send_event('__channel_cancel__', payload);
+            }
+            this._handleClose();
+            _channels.delete(this.id);
+        }
+        
         /**
          * Check if channel is closed
          */
@@ -78,19 +113,93 @@
             return this._closed;
         }
         
+        /**
+         * Check if the channel uses credit-based flow control
+         */
+        get isFlowControlled() {
+            return this._flow !== null;
+        }
+        
+        /**
+         * Flow-control counters: received messages, messages dropped or
+         * coalesced by the producer (sequence gaps) and acknowledged messages
+         */
+        get stats() {
+            return { received: this._received, dropped: this._dropped, acked: this._acked };
+        }
+        
+        /**
+         * Internal: Handle channel open
+         */
+        _handleOpen(data) {
+            if (data.credits === undefined || data.credits === null) return;
+            this._flow = {
+                credits: data.credits,
+                capacity: data.capacity,
+                policy: data.policy
+            };
+            this._nextSeq = 0;
+        }
+        
         /**
          * Internal: Handle incoming message
          */
-        _handleMessage(data) {
+        _handleMessage(data, seq) {
+            if (typeof seq === 'number') {
+                // Gaps are messages the producer dropped or coalesced
+                if (seq > this._nextSeq) this._dropped += seq - this._nextSeq;
+                this._nextSeq = Math.max(this._nextSeq, seq + 1);
+            }
+            this._received++;
             if (this._messageHandlers.length === 0) {
                 this._buffer.push(data);
             } else {
-                this._messageHandlers.forEach(handler => {
-                    try { handler(data); } catch (e) { console.error(e); }
+                this._dispatch(data);
+            }
+        }
+        
+        /**
+         * Internal: Run handlers, then acknowledge once all of them finished
+         */
+        _dispatch(data) {
+            const pending = [];
+            this._messageHandlers.slice().forEach(handler => {
+                try {
+                    const result = handler(data);
+                    if (result && typeof result.then === 'function') pending.push(result);
+                } catch (e) { console.error(e); }
+            });
+            if (!this._flow) return;
+            if (pending.length === 0) {
+                this._ack();
+            } else {
+                Promise.allSettled(pending).then(results => {
+                    results.forEach(r => { if (r.status === 'rejected') console.error(r.reason); });
+                    this._ack();
                 });
             }
         }
         
+        /**
+         * Internal: Return a credit; acks within one task are batched
+         */
+        _ack() {
+            if (this._closed) return;
+            this._pendingAcks++;
+            if (this._ackScheduled) return;
+            this._ackScheduled = true;
+            queueMicrotask(() => {
+                const credits = this._pendingAcks;
+                this._pendingAcks = 0;
+                this._ackScheduled = false;
+                if (this._closed || credits === 0) return;
+                this._acked += credits;
+                if (window.auroraview && window.auroraview.send_event) {
+                    window.auroraview.send_event('__channel_ack__', { channel_id: this.id, credits: credits });
+                }
+            });
+        }
+        
         /**
          * Internal: Handle channel close
          */
@@ -120,6 +229,7 @@
     function handleChannelOpen(data) {
         if (!data || !data.channel_id) return;
         const channel = getChannel(data.channel_id);
+        channel._handleOpen(data);
         console.log('[AuroraView] Channel opened:', data.channel_id);
     }
     
@@ -129,7 +239,7 @@
     function handleChannelMessage(data) {
         if (!data || !data.channel_id) return;
         const channel = getChannel(data.channel_id);
-        channel._handleMessage(data.data);
+        channel._handleMessage(data.data, data.seq);
     }
     
     /**
@@ -152,6 +262,10 @@
             window.auroraview.on('__channel_open__', handleChannelOpen);
             window.auroraview.on('__channel_message__', handleChannelMessage);
             window.auroraview.on('__channel_close__', handleChannelClose);
+            // The producers stream to this page: stop them when it goes away
+            window.addEventListener('pagehide', () => {
+                Array.from(_channels.values()).forEach(channel => channel.cancel('page unloaded'));
+            });
             console.log('[AuroraView] Channel bridge initialized');
         }
     }
@@ -7,6 +7,7 @@
   (function() {
     const _stateData = {};
     const _changeHandlers = [];
+    let _version = null;
     function notifyHandlers(key, value, source) {
       _changeHandlers.forEach((handler) => {
         try {
@@ -16,11 +17,58 @@
         }
       });
     }
+    function escapeToken(token) {
+      return token.replace(/~/g, "~0").replace(/\//g, "~1");
+    }
+    function parsePointer(path) {
+      return path.split("/").slice(1).map((token) => token.replace(/~1/g, "/").replace(/~0/g, "~"));
+    }
     function sendToPython(key, value) {
-      if (window.auroraview && window.auroraview.send_event) {
+      if (!window.auroraview || !window.auroraview.send_event) return;
+      if (_version !== null) {
+        const path = "/" + escapeToken(key);
+        const op = value === void 0 ? { op: "remove", path } : { op: "add", path, value };
+        window.auroraview.send_event("__state_patch__", { patch: [op] });
+      } else {
         window.auroraview.send_event("__state_update__", { key, value });
       }
     }
+    function applyOp(op) {
+      const tokens = parsePointer(op.path);
+      const last = tokens.pop();
+      if (last === void 0) return void 0;
+      let parent = _stateData;
+      for (const token of tokens) {
+        parent = parent[token];
+        if (parent === null || typeof parent !== "object") {
+          throw new Error("path not found: " + op.path);
+        }
+      }
+      if (Array.isArray(parent)) {
+        const index = last === "-" ? parent.length : Number(last);
+        if (op.op === "add") parent.splice(index, 0, op.value);
+        else if (op.op === "remove") parent.splice(index, 1);
+        else parent[index] = op.value;
+      } else {
+        const container = parent;
+        if (op.op === "remove") delete container[last];
+        else container[last] = op.value;
+      }
+      return tokens.length > 0 ? tokens[0] : last;
+    }
+    function applyPatch(patch) {
+      const changed = [];
+      for (const op of patch) {
+        const key = applyOp(op);
+        if (key !== void 0 && changed.indexOf(key) === -1) changed.push(key);
+      }
+      changed.forEach((key) => notifyHandlers(key, _stateData[key], "python"));
+    }
+    function requestResync() {
+      if (window.auroraview && window.auroraview.send_event) {
+        window.auroraview.send_event("__state_patch__", { patch: [], resync: true });
+      }
+    }
     function createStateProxy() {
       return new Proxy(_stateData, {
         get: function(target, prop) {
@@ -74,6 +122,17 @@
     function handleStateSync(data) {
       if (!data || typeof data !== "object") return;
       switch (data.type) {
+        case "patch":
+          if (Array.isArray(data.patch)) {
+            try {
+              applyPatch(data.patch);
+              if (typeof data.version === "number") _version = data.version;
+            } catch (e) {
+              console.warn("[AuroraView State] Patch failed, resyncing:", e);
+              requestResync();
+            }
+          }
+          break;
         case "set":
           if (data.key) {
             _stateData[data.key] = data.value;
@@ -95,6 +154,7 @@
           }
           break;
         case "full":
+          if (typeof data.version === "number") _version = data.version;
           Object.keys(_stateData).forEach((key) => delete _stateData[key]);
           if (data.data && typeof data.data === "object") {
             Object.assign(_stateData, data.data);
@@ -115,6 +175,7 @@
     if (window.auroraview) {
       window.auroraview.state = stateProxy;
       window.auroraview.on("__state_sync__", handleStateSync);
+      requestResync();
     } else {
       Object.defineProperty(window, "auroraview", {
         configurable: true,
@@ -123,6 +184,7 @@
           window.auroraview = val;
           window.auroraview.state = stateProxy;
           window.auroraview.on("__state_sync__", handleStateSync);
+          requestResync();
         }
       });
     }
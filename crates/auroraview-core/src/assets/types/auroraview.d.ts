@@ -19,6 +19,16 @@ declare global {
   interface Window {
     auroraview: AuroraViewAPI;
   }
+
+  /**
+   * Typed command registry
+   *
+   * Empty by default. Declarations generated from the plugin command
+   * registry (`PluginManager.typescript_definitions()`) augment it so that
+   * `invoke()` checks argument names and infers result types per command.
+   */
+  // eslint-disable-next-line @typescript-eslint/no-empty-interface
+  interface AuroraViewCommands {}
 }
 
 /**
@@ -126,7 +136,14 @@ export interface AuroraViewAPI {
    * @example
    * const result = await window.auroraview.invoke("greet", { name: "Alice" });
    * console.log(result); // "Hello, Alice!"
+   *
+   * // Commands declared in AuroraViewCommands are fully typed:
+   * const text = await window.auroraview.invoke("plugin:fs|read_file", { path });
    */
+  invoke<K extends keyof AuroraViewCommands>(
+    command: K,
+    args?: AuroraViewCommands[K]["args"]
+  ): Promise<AuroraViewCommands[K]["result"]>;
   invoke<T = unknown>(command: string, args?: Record<string, unknown>): Promise<T>;
 
   // ============================================
@@ -146,6 +163,19 @@ export interface AuroraViewAPI {
    */
   channel(channelId: string): AuroraViewChannel;
 
+  // ============================================
+  // Blob System (binary payloads)
+  // ============================================
+
+  /**
+   * Binary payload transfer through the Rust-side blob store
+   *
+   * @example
+   * const buffer = await window.auroraview.blob.fetch(handle);
+   * const uploaded = await window.auroraview.blob.upload(file, { lifetime: "one-shot" });
+   */
+  blob: AuroraViewBlobAPI;
+
   // ============================================
   // API Mode (DCC/QtWebView)
   // ============================================
@@ -213,12 +243,25 @@ export interface AuroraViewChannel {
    */
   readonly isClosed: boolean;
 
+  /**
+   * Whether the channel was opened with credit-based flow control
+   */
+  readonly isFlowControlled: boolean;
+
+  /**
+   * Flow-control counters
+   */
+  readonly stats: ChannelStats;
+
   /**
    * Register a message handler
+   *
+   * On flow-controlled channels a message is acknowledged once every
+   * handler returned; returned promises are awaited first.
    * @param handler - Called with each message
    * @returns Unsubscribe function
    */
-  onMessage<T = unknown>(handler: (data: T) => void): () => void;
+  onMessage<T = unknown>(handler: (data: T) => void | Promise<void>): () => void;
 
   /**
    * Register a close handler
@@ -226,6 +269,77 @@ export interface AuroraViewChannel {
    * @returns Unsubscribe function
    */
   onClose(handler: () => void): () => void;
+
+  /**
+   * Cancel the channel and stop the Python producer
+   * @param reason - Passed to Python `on_cancel` handlers
+   */
+  cancel(reason?: string): void;
+}
+
+/**
+ * Flow-control counters of a channel
+ */
+export interface ChannelStats {
+  /** Messages received from Python */
+  received: number;
+  /** Messages the producer dropped or coalesced (sequence gaps) */
+  dropped: number;
+  /** Messages acknowledged back to Python */
+  acked: number;
+}
+
+/**
+ * Reference to a blob in the Rust-side blob store
+ */
+export interface BlobHandle {
+  /** Blob ID */
+  id: string;
+  /** Size in bytes */
+  size: number;
+  /** MIME type */
+  mime: string;
+  /** URL the bytes are fetched from */
+  url: string;
+}
+
+/**
+ * Blob lifetime: until released, removed after the first fetch, or a TTL
+ */
+export type BlobLifetime = "manual" | "one-shot" | `ttl:${number}`;
+
+/**
+ * Blob transfer interface
+ */
+export interface AuroraViewBlobAPI {
+  /**
+   * URL of a blob
+   */
+  url(handle: BlobHandle | string): string;
+
+  /**
+   * Fetch a blob as an ArrayBuffer
+   */
+  fetch(handle: BlobHandle | string): Promise<ArrayBuffer>;
+
+  /**
+   * Fetch a blob as a Blob
+   */
+  fetchBlob(handle: BlobHandle | string): Promise<Blob>;
+
+  /**
+   * Upload binary data; the returned handle can be passed to Python
+   */
+  upload(
+    data: Blob | ArrayBuffer | ArrayBufferView,
+    options?: { mime?: string; lifetime?: BlobLifetime }
+  ): Promise<BlobHandle>;
+
+  /**
+   * Release a blob
+   * @returns Whether the blob existed
+   */
+  release(handle: BlobHandle | string): Promise<boolean>;
 }
 
 /**
@@ -349,6 +463,8 @@ export interface StateSyncMessage {
  */
 export interface ChannelMessage<T = unknown> {
   channel_id: string;
+  /** Sequence number (flow-controlled channels only) */
+  seq?: number;
   data?: T;
 }
 
@@ -4,11 +4,16 @@
 //! inspired by Qt WebView's `QWebViewFactory`.
 
 use super::error::{WebViewError, WebViewResult};
+use super::headless_impl::HeadlessBackend;
 use super::settings::WebViewSettingsImpl;
 use super::traits::WebViewBackend;
+#[cfg(all(target_os = "linux", feature = "webkitgtk"))]
+use super::webkitgtk_impl::WebKitGtkBackend;
 use super::wry_impl::WryBackend;
+use crate::cookies::CookieJar;
 use std::path::PathBuf;
 use std::str::FromStr;
+use std::sync::Arc;
 
 /// Available backend types
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
@@ -25,6 +30,8 @@ pub enum BackendType {
     /// WebKitGTK backend (Linux native)
     #[cfg(target_os = "linux")]
     WebKitGTK,
+    /// In-memory backend without a window (testing/CI)
+    Headless,
 }
 
 impl FromStr for BackendType {
@@ -39,6 +46,7 @@ impl FromStr for BackendType {
             "wkwebview" | "wk" | "webkit" => Ok(Self::WKWebView),
             #[cfg(target_os = "linux")]
             "webkitgtk" | "gtk" => Ok(Self::WebKitGTK),
+            "headless" | "null" | "offscreen" => Ok(Self::Headless),
             other => Err(WebViewError::UnsupportedBackend(other.to_string())),
         }
     }
@@ -54,6 +62,7 @@ impl std::fmt::Display for BackendType {
             Self::WKWebView => write!(f, "wkwebview"),
             #[cfg(target_os = "linux")]
             Self::WebKitGTK => write!(f, "webkitgtk"),
+            Self::Headless => write!(f, "headless"),
         }
     }
 }
@@ -77,6 +86,14 @@ pub struct BackendConfig {
     pub parent_handle: Option<u64>,
     /// Asset root directory
     pub asset_root: Option<PathBuf>,
+    /// Profile data directory; the cookie jar is persisted there
+    pub data_dir: Option<PathBuf>,
+    /// Cookie jar shared with other backends and `chrome.cookies`
+    /// (opened from `data_dir` when unset)
+    pub cookie_jar: Option<Arc<CookieJar>>,
+    /// Keep session cookies in the `data_dir` jar across restarts
+    /// (default: true, so logins survive relaunching the app)
+    pub persist_session_cookies: bool,
     /// WebView settings
     pub settings: WebViewSettingsImpl,
 }
@@ -92,11 +109,29 @@ impl Default for BackendConfig {
             html: None,
             parent_handle: None,
             asset_root: None,
+            data_dir: None,
+            cookie_jar: None,
+            persist_session_cookies: true,
             settings: WebViewSettingsImpl::default(),
         }
     }
 }
 
+impl BackendConfig {
+    /// Cookie jar for the backend: the shared one, else the profile's, else in-memory
+    pub fn open_cookie_jar(&self) -> WebViewResult<Arc<CookieJar>> {
+        if let Some(jar) = &self.cookie_jar {
+            return Ok(jar.clone());
+        }
+        match &self.data_dir {
+            Some(dir) => CookieJar::for_profile(dir)
+                .map(|jar| Arc::new(jar.persist_session_cookies(self.persist_session_cookies)))
+                .map_err(|e| WebViewError::Cookie(e.to_string())),
+            None => Ok(Arc::new(CookieJar::new())),
+        }
+    }
+}
+
 /// Backend factory
 ///
 /// Creates WebView backend instances based on configuration.
@@ -116,14 +151,14 @@ impl BackendFactory {
         match backend_type {
             BackendType::Wry => {
                 // Create WryBackend with settings from config
-                let mut backend = WryBackend::new();
+                let mut backend = WryBackend::new().with_cookie_jar(config.open_cookie_jar()?);
                 backend.apply_settings(config.settings.clone());
                 Ok(Box::new(backend))
             }
             #[cfg(target_os = "windows")]
             BackendType::WebView2 => {
                 // WebView2 is also handled via Wry on Windows (wry uses WebView2)
-                let mut backend = WryBackend::new();
+                let mut backend = WryBackend::new().with_cookie_jar(config.open_cookie_jar()?);
                 backend.apply_settings(config.settings.clone());
                 Ok(Box::new(backend))
             }
@@ -131,10 +166,13 @@ impl BackendFactory {
             BackendType::WKWebView => Err(WebViewError::Internal(
                 "WKWebView backend not yet implemented".into(),
             )),
-            #[cfg(target_os = "linux")]
-            BackendType::WebKitGTK => Err(WebViewError::Internal(
-                "WebKitGTK backend not yet implemented".into(),
+            #[cfg(all(target_os = "linux", feature = "webkitgtk"))]
+            BackendType::WebKitGTK => Ok(Box::new(WebKitGtkBackend::new(config)?)),
+            #[cfg(all(target_os = "linux", not(feature = "webkitgtk")))]
+            BackendType::WebKitGTK => Err(WebViewError::UnsupportedBackend(
+                "webkitgtk (enable the `webkitgtk` feature of auroraview-core)".into(),
             )),
+            BackendType::Headless => Ok(Box::new(HeadlessBackend::from_config(config)?)),
         }
     }
 
@@ -167,6 +205,8 @@ impl BackendFactory {
         #[cfg(target_os = "linux")]
         backends.push(BackendType::WebKitGTK);
 
+        backends.push(BackendType::Headless);
+
         backends
     }
 }
@@ -0,0 +1,639 @@
+//! Headless backend implementation
+//!
+//! Implements the WebViewBackend trait entirely in memory, without a window
+//! or a browser engine. Intended for unit tests and CI machines without a
+//! display.
+//!
+//! ## What is simulated
+//!
+//! - Navigation history (back/forward stack) and load progress
+//! - Cookies, stored in a [`CookieJar`] (in-memory unless one is shared with
+//!   `with_cookie_jar`)
+//! - Lifecycle transitions via `AtomicLifecycle`
+//! - JavaScript: every `eval_js` call is recorded and results can be scripted
+//!
+//! By default navigations complete immediately. Call `set_auto_complete(false)`
+//! to drive loading manually with `set_load_progress`/`finish_loading`.
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, AtomicU8, Ordering};
+use std::sync::Arc;
+
+use parking_lot::RwLock;
+use serde_json::Value;
+
+use super::error::{WebViewError, WebViewResult};
+use super::factory::BackendConfig;
+use super::lifecycle::{AtomicLifecycle, LifecycleState};
+use super::message_processor::ProcessResult;
+use super::settings::{WebViewSettings, WebViewSettingsImpl};
+use crate::cookies::{Cookie, CookieJar};
+
+use super::traits::{
+    CookieInfo, EmbeddableBackend, JavaScriptCallback, LoadProgress, NavigationEvent,
+    NavigationState, WebViewBackend,
+};
+
+/// URL reported after `load_html`
+pub const HEADLESS_HTML_URL: &str = "about:blank";
+
+/// Scripted JavaScript handler, consulted when no exact script result matches
+type ScriptHandler = Box<dyn Fn(&str) -> Option<WebViewResult<Value>> + Send + Sync>;
+
+/// Back/forward history stack
+#[derive(Debug, Default)]
+struct History {
+    entries: Vec<String>,
+    index: usize,
+}
+
+impl History {
+    fn current(&self) -> Option<&String> {
+        self.entries.get(self.index)
+    }
+
+    fn push(&mut self, url: String) {
+        if !self.entries.is_empty() {
+            self.entries.truncate(self.index + 1);
+        }
+        self.entries.push(url);
+        self.index = self.entries.len() - 1;
+    }
+
+    fn can_go_back(&self) -> bool {
+        self.index > 0
+    }
+
+    fn can_go_forward(&self) -> bool {
+        self.index + 1 < self.entries.len()
+    }
+}
+
+/// Window state tracked by the headless backend
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HeadlessWindowState {
+    /// X position
+    pub x: i32,
+    /// Y position
+    pub y: i32,
+    /// Width
+    pub width: u32,
+    /// Height
+    pub height: u32,
+    /// Visibility
+    pub visible: bool,
+    /// Whether `focus` was called last
+    pub focused: bool,
+}
+
+/// Headless (in-memory) backend implementation
+///
+/// ## Thread Safety
+///
+/// This struct is `Send + Sync`. State is kept in atomics and
+/// `parking_lot::RwLock`s, mirroring `WryBackend`.
+///
+/// ## Example
+///
+/// ```rust
+/// use auroraview_core::backend::{HeadlessBackend, WebViewBackend};
+///
+/// let backend = HeadlessBackend::new();
+/// backend.script_result("document.title", serde_json::json!("Home"));
+///
+/// backend.navigate("https://example.com/a").unwrap();
+/// backend.navigate("https://example.com/b").unwrap();
+/// assert!(backend.can_go_back());
+///
+/// backend.go_back().unwrap();
+/// assert_eq!(backend.url().as_deref(), Some("https://example.com/a"));
+///
+/// backend.eval_js("document.title").unwrap();
+/// assert_eq!(backend.eval_calls(), vec!["document.title".to_string()]);
+/// ```
+pub struct HeadlessBackend {
+    /// Lifecycle state machine (lock-free)
+    lifecycle: AtomicLifecycle,
+    /// Navigation history
+    history: RwLock<History>,
+    /// Current title
+    current_title: RwLock<Option<String>>,
+    /// Last HTML passed to `load_html`
+    current_html: RwLock<Option<String>>,
+    /// Whether the WebView is loading
+    is_loading: AtomicBool,
+    /// Load progress percentage (0-100)
+    load_progress: AtomicU8,
+    /// Whether navigations complete immediately
+    auto_complete: AtomicBool,
+    /// Navigation events in order
+    navigation_events: RwLock<Vec<NavigationEvent>>,
+    /// Cookie jar (possibly shared with other backends and `chrome.cookies`)
+    cookie_jar: Arc<CookieJar>,
+    /// Recorded `eval_js` scripts
+    eval_calls: RwLock<Vec<String>>,
+    /// Exact-match scripted results
+    script_results: RwLock<HashMap<String, WebViewResult<Value>>>,
+    /// Fallback scripted handler
+    script_handler: RwLock<Option<ScriptHandler>>,
+    /// Events passed to `emit_event`
+    emitted_events: RwLock<Vec<(String, Value)>>,
+    /// Window state
+    window: RwLock<HeadlessWindowState>,
+    /// Settings (using Box for stable address)
+    settings: Box<WebViewSettingsImpl>,
+    /// User agent string
+    user_agent: String,
+}
+
+impl Default for HeadlessBackend {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl HeadlessBackend {
+    /// Create a new headless backend in Active state
+    pub fn new() -> Self {
+        Self {
+            lifecycle: AtomicLifecycle::new_active(),
+            history: RwLock::new(History::default()),
+            current_title: RwLock::new(None),
+            current_html: RwLock::new(None),
+            is_loading: AtomicBool::new(false),
+            load_progress: AtomicU8::new(0),
+            auto_complete: AtomicBool::new(true),
+            navigation_events: RwLock::new(Vec::new()),
+            cookie_jar: Arc::new(CookieJar::new()),
+            eval_calls: RwLock::new(Vec::new()),
+            script_results: RwLock::new(HashMap::new()),
+            script_handler: RwLock::new(None),
+            emitted_events: RwLock::new(Vec::new()),
+            window: RwLock::new(HeadlessWindowState {
+                x: 0,
+                y: 0,
+                width: 800,
+                height: 600,
+                visible: true,
+                focused: false,
+            }),
+            settings: Box::new(WebViewSettingsImpl::default()),
+            user_agent: format!("AuroraView/{} (Headless)", env!("CARGO_PKG_VERSION")),
+        }
+    }
+
+    /// Create a headless backend from configuration
+    ///
+    /// Loads `config.html` (or navigates to `config.url`) immediately.
+    pub fn from_config(config: &BackendConfig) -> WebViewResult<Self> {
+        let mut backend = Self::new().with_cookie_jar(config.open_cookie_jar()?);
+        backend.apply_settings(config.settings.clone());
+        {
+            let mut window = backend.window.write();
+            window.width = config.width;
+            window.height = config.height;
+        }
+        *backend.current_title.write() = Some(config.title.clone());
+
+        if let Some(html) = &config.html {
+            backend.load_html(html)?;
+        } else if let Some(url) = &config.url {
+            backend.navigate(url)?;
+        }
+        Ok(backend)
+    }
+
+    /// Apply settings from a WebViewSettingsImpl
+    pub fn apply_settings(&mut self, settings: WebViewSettingsImpl) {
+        *self.settings = settings;
+    }
+
+    /// Get the lifecycle state machine
+    pub fn lifecycle(&self) -> &AtomicLifecycle {
+        &self.lifecycle
+    }
+
+    /// Simulate the user closing the window (Active -> CloseRequested)
+    pub fn request_close(&self) -> bool {
+        self.lifecycle.request_close().is_success()
+    }
+
+    // ========== Loading control ==========
+
+    /// Control whether navigations complete immediately (default: true)
+    pub fn set_auto_complete(&self, auto_complete: bool) {
+        self.auto_complete.store(auto_complete, Ordering::Release);
+    }
+
+    /// Set load progress of the current navigation
+    pub fn set_load_progress(&self, progress: u8) {
+        self.load_progress
+            .store(progress.min(100), Ordering::Release);
+    }
+
+    /// Complete the current navigation
+    pub fn finish_loading(&self) {
+        if !self.is_loading.swap(false, Ordering::AcqRel) {
+            return;
+        }
+        self.load_progress.store(100, Ordering::Release);
+        self.record_navigation(NavigationState::Completed, None);
+    }
+
+    /// Fail the current navigation
+    pub fn fail_loading(&self, error: impl Into<String>) {
+        if !self.is_loading.swap(false, Ordering::AcqRel) {
+            return;
+        }
+        self.record_navigation(NavigationState::Failed, Some(error.into()));
+    }
+
+    /// Set the page title (real backends read it from the document)
+    pub fn set_title(&self, title: Option<String>) {
+        *self.current_title.write() = title;
+    }
+
+    /// Last HTML passed to `load_html`
+    pub fn html(&self) -> Option<String> {
+        self.current_html.read().clone()
+    }
+
+    /// All history entries and the index of the current one
+    pub fn history(&self) -> (Vec<String>, usize) {
+        let history = self.history.read();
+        (history.entries.clone(), history.index)
+    }
+
+    /// Navigation events recorded so far
+    pub fn navigation_events(&self) -> Vec<NavigationEvent> {
+        self.navigation_events.read().clone()
+    }
+
+    // ========== JavaScript scripting ==========
+
+    /// Script the result of an exact JavaScript source
+    pub fn script_result(&self, script: impl Into<String>, result: Value) {
+        self.script_results
+            .write()
+            .insert(script.into(), Ok(result));
+    }
+
+    /// Script a JavaScript error for an exact JavaScript source
+    pub fn script_error(&self, script: impl Into<String>, message: impl Into<String>) {
+        self.script_results
+            .write()
+            .insert(script.into(), Err(WebViewError::javascript(message)));
+    }
+
+    /// Install a fallback handler for scripts without an exact result
+    ///
+    /// Returning `None` falls back to `Value::Null`.
+    pub fn set_script_handler<F>(&self, handler: F)
+    where
+        F: Fn(&str) -> Option<WebViewResult<Value>> + Send + Sync + 'static,
+    {
+        *self.script_handler.write() = Some(Box::new(handler));
+    }
+
+    /// Scripts passed to `eval_js`/`eval_js_with_callback`, in order
+    pub fn eval_calls(&self) -> Vec<String> {
+        self.eval_calls.read().clone()
+    }
+
+    /// Forget recorded scripts
+    pub fn clear_eval_calls(&self) {
+        self.eval_calls.write().clear();
+    }
+
+    /// Events passed to `emit_event`, in order
+    pub fn emitted_events(&self) -> Vec<(String, Value)> {
+        self.emitted_events.read().clone()
+    }
+
+    // ========== Cookies and window ==========
+
+    /// Use a shared cookie jar instead of a private in-memory one
+    pub fn with_cookie_jar(mut self, jar: Arc<CookieJar>) -> Self {
+        self.cookie_jar = jar;
+        self
+    }
+
+    /// Cookie jar backing the cookie methods
+    pub fn cookie_jar(&self) -> &Arc<CookieJar> {
+        &self.cookie_jar
+    }
+
+    /// All unexpired cookies in the jar
+    pub fn cookies(&self) -> Vec<CookieInfo> {
+        self.cookie_jar.all().iter().map(CookieInfo::from).collect()
+    }
+
+    /// Current window state
+    pub fn window_state(&self) -> HeadlessWindowState {
+        *self.window.read()
+    }
+
+    // ========== Internal ==========
+
+    fn ensure_open(&self) -> WebViewResult<()> {
+        if self.lifecycle.is_closing() {
+            return Err(WebViewError::Closed);
+        }
+        Ok(())
+    }
+
+    fn record_navigation(&self, state: NavigationState, error: Option<String>) {
+        let url = self.url().unwrap_or_default();
+        self.navigation_events
+            .write()
+            .push(NavigationEvent { url, state, error });
+    }
+
+    /// Start loading the current history entry
+    fn begin_load(&self) {
+        self.is_loading.store(true, Ordering::Release);
+        self.load_progress.store(0, Ordering::Release);
+        self.record_navigation(NavigationState::Started, None);
+        if self.auto_complete.load(Ordering::Acquire) {
+            self.finish_loading();
+        }
+    }
+
+    fn run_script(&self, script: &str) -> WebViewResult<Value> {
+        self.eval_calls.write().push(script.to_string());
+        if let Some(result) = self.script_results.read().get(script) {
+            return result.clone();
+        }
+        if let Some(handler) = self.script_handler.read().as_ref() {
+            if let Some(result) = handler(script) {
+                return result;
+            }
+        }
+        Ok(Value::Null)
+    }
+}
+
+/// Extract the `<title>` of an HTML document
+fn html_title(html: &str) -> Option<String> {
+    let lower = html.to_ascii_lowercase();
+    let open = lower.find("<title")?;
+    let start = open + lower[open..].find('>')? + 1;
+    let end = start + lower[start..].find("</title")?;
+    Some(html[start..end].trim().to_string()).filter(|t| !t.is_empty())
+}
+
+impl WebViewBackend for HeadlessBackend {
+    // ========== Navigation ==========
+
+    fn navigate(&self, url: &str) -> WebViewResult<()> {
+        self.ensure_open()?;
+        url::Url::parse(url)
+            .map_err(|e| WebViewError::navigation(format!("Invalid URL '{}': {}", url, e)))?;
+        self.history.write().push(url.to_string());
+        *self.current_html.write() = None;
+        self.begin_load();
+        Ok(())
+    }
+
+    fn url(&self) -> Option<String> {
+        self.history.read().current().cloned()
+    }
+
+    fn can_go_back(&self) -> bool {
+        self.history.read().can_go_back()
+    }
+
+    fn can_go_forward(&self) -> bool {
+        self.history.read().can_go_forward()
+    }
+
+    fn go_back(&self) -> WebViewResult<()> {
+        self.ensure_open()?;
+        {
+            let mut history = self.history.write();
+            if !history.can_go_back() {
+                return Err(WebViewError::navigation("No previous history entry"));
+            }
+            history.index -= 1;
+        }
+        self.begin_load();
+        Ok(())
+    }
+
+    fn go_forward(&self) -> WebViewResult<()> {
+        self.ensure_open()?;
+        {
+            let mut history = self.history.write();
+            if !history.can_go_forward() {
+                return Err(WebViewError::navigation("No next history entry"));
+            }
+            history.index += 1;
+        }
+        self.begin_load();
+        Ok(())
+    }
+
+    fn reload(&self) -> WebViewResult<()> {
+        self.ensure_open()?;
+        if self.url().is_none() {
+            return Err(WebViewError::navigation("Nothing to reload"));
+        }
+        self.begin_load();
+        Ok(())
+    }
+
+    fn stop(&self) -> WebViewResult<()> {
+        self.fail_loading("Stopped");
+        Ok(())
+    }
+
+    // ========== Content Loading ==========
+
+    fn load_html(&self, html: &str) -> WebViewResult<()> {
+        self.ensure_open()?;
+        self.history.write().push(HEADLESS_HTML_URL.to_string());
+        *self.current_html.write() = Some(html.to_string());
+        if let Some(title) = html_title(html) {
+            self.set_title(Some(title));
+        }
+        self.begin_load();
+        Ok(())
+    }
+
+    fn title(&self) -> Option<String> {
+        self.current_title.read().clone()
+    }
+
+    fn load_progress(&self) -> LoadProgress {
+        LoadProgress {
+            percent: self.load_progress.load(Ordering::Acquire),
+            is_complete: !self.is_loading.load(Ordering::Acquire),
+        }
+    }
+
+    fn is_loading(&self) -> bool {
+        self.is_loading.load(Ordering::Acquire)
+    }
+
+    // ========== JavaScript ==========
+
+    fn eval_js(&self, script: &str) -> WebViewResult<()> {
+        self.ensure_open()?;
+        self.run_script(script).map(|_| ())
+    }
+
+    fn eval_js_with_callback(
+        &self,
+        script: &str,
+        callback: JavaScriptCallback,
+    ) -> WebViewResult<()> {
+        self.ensure_open()?;
+        callback(self.run_script(script));
+        Ok(())
+    }
+
+    // ========== Cookie Management ==========
+
+    fn set_cookie(&self, cookie: &CookieInfo) -> WebViewResult<()> {
+        self.ensure_open()?;
+        if cookie.domain.is_empty() || cookie.name.is_empty() {
+            return Err(WebViewError::invalid_arg(
+                "Cookie domain and name are required",
+            ));
+        }
+        // The jar deletes the cookie when it is already expired, like a browser does
+        self.cookie_jar
+            .set(Cookie::from(cookie))
+            .map_err(|e| WebViewError::Cookie(e.to_string()))
+    }
+
+    fn get_cookie(&self, domain: &str, name: &str) -> WebViewResult<Option<CookieInfo>> {
+        self.ensure_open()?;
+        Ok(self
+            .cookie_jar
+            .get(domain, name)
+            .map(|c| CookieInfo::from(&c)))
+    }
+
+    fn delete_cookie(&self, domain: &str, name: &str) -> WebViewResult<()> {
+        self.ensure_open()?;
+        self.cookie_jar
+            .remove_named(domain, name)
+            .map(|_| ())
+            .map_err(|e| WebViewError::Cookie(e.to_string()))
+    }
+
+    fn clear_cookies(&self) -> WebViewResult<()> {
+        self.ensure_open()?;
+        self.cookie_jar
+            .clear()
+            .map_err(|e| WebViewError::Cookie(e.to_string()))
+    }
+
+    // ========== Settings ==========
+
+    fn settings(&self) -> &dyn WebViewSettings {
+        self.settings.as_ref()
+    }
+
+    fn settings_mut(&mut self) -> &mut dyn WebViewSettings {
+        self.settings.as_mut()
+    }
+
+    fn http_user_agent(&self) -> String {
+        self.settings
+            .user_agent()
+            .unwrap_or_else(|| self.user_agent.clone())
+    }
+
+    // ========== Lifecycle ==========
+
+    fn lifecycle_state(&self) -> LifecycleState {
+        self.lifecycle.state()
+    }
+
+    fn close(&self) -> WebViewResult<()> {
+        let _ = self.lifecycle.request_close();
+        let _ = self.lifecycle.begin_destroy();
+        let _ = self.lifecycle.finish_destroy();
+        Ok(())
+    }
+
+    // ========== Window Control ==========
+
+    fn set_bounds(&self, x: i32, y: i32, width: u32, height: u32) -> WebViewResult<()> {
+        self.ensure_open()?;
+        let mut window = self.window.write();
+        window.x = x;
+        window.y = y;
+        window.width = width;
+        window.height = height;
+        Ok(())
+    }
+
+    fn set_visible(&self, visible: bool) -> WebViewResult<()> {
+        self.ensure_open()?;
+        self.window.write().visible = visible;
+        Ok(())
+    }
+
+    fn focus(&self) -> WebViewResult<()> {
+        self.ensure_open()?;
+        self.window.write().focused = true;
+        Ok(())
+    }
+}
+
+impl EmbeddableBackend for HeadlessBackend {
+    fn native_handle(&self) -> Option<u64> {
+        None
+    }
+
+    fn process_events(&self) -> ProcessResult {
+        self.process_ipc_only()
+    }
+
+    fn process_ipc_only(&self) -> ProcessResult {
+        if self.lifecycle.is_closing() {
+            ProcessResult::CloseRequested
+        } else {
+            ProcessResult::Continue
+        }
+    }
+
+    fn emit_event(&self, event_name: &str, data: Value) -> WebViewResult<()> {
+        self.ensure_open()?;
+        self.emitted_events
+            .write()
+            .push((event_name.to_string(), data));
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_history_truncates_forward_entries() {
+        let mut history = History::default();
+        history.push("a".into());
+        history.push("b".into());
+        history.push("c".into());
+        history.index = 0;
+        history.push("d".into());
+        assert_eq!(history.entries, vec!["a", "d"]);
+        assert_eq!(history.index, 1);
+        assert!(!history.can_go_forward());
+    }
+
+    #[test]
+    fn test_html_title() {
+        assert_eq!(
+            html_title("<html><TITLE> Hello </TITLE></html>"),
+            Some("Hello".to_string())
+        );
+        assert_eq!(html_title("<title></title>"), None);
+        assert_eq!(html_title("<p>no title</p>"), None);
+    }
+}
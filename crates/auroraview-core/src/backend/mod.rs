@@ -11,6 +11,8 @@
 //! - `BackendType`: Enum representing available backend types
 //! - `AtomicLifecycle`: Lock-free lifecycle state machine
 //! - `MessageProcessor`: Unified message processing
+//! - `HeadlessBackend`: In-memory backend for tests and CI without a display
+//! - `WebKitGtkBackend`: Native WebKitGTK backend (Linux, `webkitgtk` feature)
 //!
 //! ## Key Design Decisions
 //!
@@ -37,14 +39,18 @@
 
 mod error;
 mod factory;
+mod headless_impl;
 pub mod lifecycle;
 pub mod message_processor;
 mod settings;
 mod traits;
+#[cfg(all(target_os = "linux", feature = "webkitgtk"))]
+mod webkitgtk_impl;
 mod wry_impl;
 
 pub use error::{WebViewError, WebViewResult};
 pub use factory::{BackendConfig, BackendFactory, BackendType};
+pub use headless_impl::{HeadlessBackend, HeadlessWindowState, HEADLESS_HTML_URL};
 pub use lifecycle::{
     AtomicLifecycle, LifecycleEvent, LifecycleObserver, LifecycleState, ObservableLifecycle,
     TransitionResult,
@@ -58,4 +64,6 @@ pub use traits::{
     CookieInfo, EmbeddableBackend, EventLoopBackend, JavaScriptCallback, LoadProgress,
     NavigationEvent, NavigationState, WebViewBackend,
 };
+#[cfg(all(target_os = "linux", feature = "webkitgtk"))]
+pub use webkitgtk_impl::{WebKitGtkBackend, ENV_SOFTWARE_RENDERING};
 pub use wry_impl::WryBackend;
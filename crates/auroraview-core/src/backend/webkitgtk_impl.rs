@@ -0,0 +1,944 @@
+//! WebKitGTK backend implementation
+//!
+//! Implements the WebViewBackend trait directly on top of WebKitGTK (Linux).
+//!
+//! ## Architecture
+//!
+//! GTK objects are bound to the thread that initialized GTK, while
+//! `WebViewBackend` must be `Send + Sync`. This implementation therefore:
+//! - Runs GTK on a dedicated thread (spawned lazily), unless the host
+//!   application already initialized GTK, in which case the host's main
+//!   context is used
+//! - Keeps the native window/webview in a thread-local registry on the GTK
+//!   thread, keyed by backend id
+//! - Marshals every operation to the GTK thread via `MainContext::invoke`
+//! - Mirrors URL/title/loading state into atomics and `RwLock`s, updated by
+//!   WebKit signals, so queries never block on the GTK thread
+//! - Seeds WebKit's cookie store from the profile's [`CookieJar`] before the
+//!   first load, mirrors cookie reads and writes into it, and writes WebKit's
+//!   cookies back to the jar on close
+//!
+//! ## Headless Testing
+//!
+//! Under Xvfb, set `GDK_BACKEND=x11` and `AURORAVIEW_SOFTWARE_RENDERING=1`
+//! to disable GPU compositing.
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicU8, Ordering};
+use std::sync::{mpsc, Arc, OnceLock};
+use std::time::{Duration, Instant};
+
+use gtk::glib;
+use gtk::prelude::*;
+use javascriptcore::ValueExt;
+use parking_lot::RwLock;
+use webkit2gtk::{
+    CookieManagerExt, HardwareAccelerationPolicy, LoadEvent, SettingsExt, WebContext,
+    WebContextExt, WebViewExt, WebsiteDataManagerExtManual, WebsiteDataTypes,
+};
+
+use super::error::{WebViewError, WebViewResult};
+use super::factory::BackendConfig;
+use super::lifecycle::{AtomicLifecycle, LifecycleState};
+use super::message_processor::ProcessResult;
+use super::settings::{WebViewSettings, WebViewSettingsImpl};
+use super::traits::{
+    CookieInfo, EmbeddableBackend, JavaScriptCallback, LoadProgress, WebViewBackend,
+};
+use crate::cookies::{Cookie, CookieJar};
+use crate::templates::EmitEventTemplate;
+
+/// Environment variable that forces software rendering (e.g. under Xvfb)
+pub const ENV_SOFTWARE_RENDERING: &str = "AURORAVIEW_SOFTWARE_RENDERING";
+
+/// Maximum time to wait for a synchronous GTK-thread operation
+const CALL_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Next backend id
+static NEXT_ID: AtomicU64 = AtomicU64::new(1);
+
+/// Native widgets owned by the GTK thread
+struct NativeView {
+    window: gtk::Window,
+    webview: webkit2gtk::WebView,
+}
+
+thread_local! {
+    /// Registry of live webviews (only populated on the GTK thread)
+    static VIEWS: RefCell<HashMap<u64, NativeView>> = RefCell::new(HashMap::new());
+}
+
+/// Make sure GTK is initialized and its main loop is running somewhere
+fn ensure_gtk() -> WebViewResult<()> {
+    static INIT: OnceLock<Result<(), String>> = OnceLock::new();
+
+    INIT.get_or_init(|| {
+        // The host (e.g. tao/wry) already runs GTK - share its main context
+        if gtk::is_initialized() {
+            return Ok(());
+        }
+
+        let (tx, rx) = mpsc::channel();
+        std::thread::Builder::new()
+            .name("auroraview-gtk".to_string())
+            .spawn(move || match gtk::init() {
+                Ok(()) => {
+                    let _ = tx.send(Ok(()));
+                    gtk::main();
+                }
+                Err(e) => {
+                    let _ = tx.send(Err(e.to_string()));
+                }
+            })
+            .map_err(|e| format!("Failed to spawn GTK thread: {}", e))?;
+
+        rx.recv()
+            .map_err(|_| "GTK thread exited during initialization".to_string())?
+    })
+    .clone()
+    .map_err(WebViewError::Initialization)
+}
+
+/// Run an asynchronous GTK operation and wait for its result
+///
+/// `start` runs on the GTK thread and must eventually send exactly one value.
+/// When called from the GTK thread itself, the main context is pumped instead
+/// of blocking so that the operation can complete.
+fn call_on_gtk<R, F>(start: F) -> WebViewResult<R>
+where
+    R: Send + 'static,
+    F: FnOnce(mpsc::Sender<R>) + Send + 'static,
+{
+    let (tx, rx) = mpsc::channel();
+
+    if gtk::is_initialized_main_thread() {
+        start(tx);
+        let context = glib::MainContext::default();
+        let deadline = Instant::now() + CALL_TIMEOUT;
+        loop {
+            match rx.try_recv() {
+                Ok(value) => return Ok(value),
+                Err(mpsc::TryRecvError::Disconnected) => {
+                    return Err(WebViewError::Internal(
+                        "GTK operation dropped its result".to_string(),
+                    ))
+                }
+                Err(mpsc::TryRecvError::Empty) => {}
+            }
+            if Instant::now() >= deadline {
+                return Err(WebViewError::Timeout("GTK operation".to_string()));
+            }
+            if !context.iteration(false) {
+                std::thread::sleep(Duration::from_millis(1));
+            }
+        }
+    }
+
+    glib::MainContext::default().invoke(move || start(tx));
+    rx.recv_timeout(CALL_TIMEOUT).map_err(|e| match e {
+        mpsc::RecvTimeoutError::Timeout => WebViewError::Timeout("GTK operation".to_string()),
+        mpsc::RecvTimeoutError::Disconnected => {
+            WebViewError::Internal("GTK operation dropped its result".to_string())
+        }
+    })
+}
+
+/// Run a closure against a registered webview on the GTK thread
+fn with_view<R, F>(id: u64, f: F) -> WebViewResult<R>
+where
+    R: Send + 'static,
+    F: FnOnce(&NativeView) -> R + Send + 'static,
+{
+    call_on_gtk(move |tx| {
+        let result = VIEWS.with(|views| views.borrow().get(&id).map(f).ok_or(WebViewError::Closed));
+        let _ = tx.send(result);
+    })?
+}
+
+/// Convert a WebKit cookie to `CookieInfo`
+fn cookie_info(cookie: &mut soup::Cookie) -> CookieInfo {
+    CookieInfo {
+        domain: cookie.domain().map(|s| s.to_string()).unwrap_or_default(),
+        name: cookie.name().map(|s| s.to_string()).unwrap_or_default(),
+        value: cookie.value().map(|s| s.to_string()).unwrap_or_default(),
+        path: cookie.path().map(|s| s.to_string()),
+        expires: cookie.expires().map(|t| t.to_unix()),
+        http_only: cookie.is_http_only(),
+        secure: cookie.is_secure(),
+    }
+}
+
+/// Convert `CookieInfo` to a WebKit cookie (GTK thread only)
+fn soup_cookie(info: &CookieInfo) -> soup::Cookie {
+    let mut cookie = soup::Cookie::new(
+        &info.name,
+        &info.value,
+        &info.domain,
+        info.path.as_deref().unwrap_or("/"),
+        -1,
+    );
+    cookie.set_http_only(info.http_only);
+    cookie.set_secure(info.secure);
+    if let Some(expires) = info
+        .expires
+        .and_then(|ts| glib::DateTime::from_unix_utc(ts).ok())
+    {
+        cookie.set_expires(&expires);
+    }
+    cookie
+}
+
+/// URI used to look up the cookies of a domain
+///
+/// WebKit only returns cookies that would be sent to a URI, so cookies are
+/// looked up at the domain root over https (which includes non-secure ones).
+fn cookie_lookup_uri(domain: &str) -> String {
+    format!("https://{}/", domain.trim_start_matches('.'))
+}
+
+/// Cookie manager of a registered webview (GTK thread only)
+fn cookie_manager(id: u64) -> WebViewResult<webkit2gtk::CookieManager> {
+    VIEWS.with(|views| {
+        let views = views.borrow();
+        let view = views.get(&id).ok_or(WebViewError::Closed)?;
+        view.webview
+            .context()
+            .and_then(|c| c.cookie_manager())
+            .ok_or_else(|| WebViewError::Cookie("No cookie manager".to_string()))
+    })
+}
+
+/// Find a cookie by domain and name, then hand it to `then` (GTK thread only)
+fn find_cookie<F>(manager: webkit2gtk::CookieManager, domain: &str, name: &str, then: F)
+where
+    F: FnOnce(&webkit2gtk::CookieManager, WebViewResult<Option<soup::Cookie>>) + 'static,
+{
+    let name = name.to_string();
+    let domain = domain.trim_start_matches('.').to_string();
+    let lookup = manager.clone();
+    manager.cookies(
+        &cookie_lookup_uri(&domain),
+        None::<&gtk::gio::Cancellable>,
+        move |result| {
+            let result = result
+                .map(|cookies| {
+                    cookies.into_iter().find(|c| {
+                        let mut c = c.clone();
+                        c.name().as_deref() == Some(name.as_str())
+                            && c.domain()
+                                .is_some_and(|d| d.trim_start_matches('.') == domain)
+                    })
+                })
+                .map_err(|e| WebViewError::Cookie(e.to_string()));
+            then(&lookup, result);
+        },
+    );
+}
+
+/// State shared between the backend handle and GTK signal handlers
+struct SharedState {
+    lifecycle: AtomicLifecycle,
+    current_url: RwLock<Option<String>>,
+    current_title: RwLock<Option<String>>,
+    is_loading: AtomicBool,
+    load_progress: AtomicU8,
+    can_go_back: AtomicBool,
+    can_go_forward: AtomicBool,
+}
+
+impl SharedState {
+    fn new() -> Self {
+        Self {
+            lifecycle: AtomicLifecycle::new(),
+            current_url: RwLock::new(None),
+            current_title: RwLock::new(None),
+            is_loading: AtomicBool::new(false),
+            load_progress: AtomicU8::new(0),
+            can_go_back: AtomicBool::new(false),
+            can_go_forward: AtomicBool::new(false),
+        }
+    }
+
+    fn set_loading(&self, loading: bool) {
+        self.is_loading.store(loading, Ordering::Release);
+        if loading {
+            self.load_progress.store(0, Ordering::Release);
+        } else {
+            self.load_progress.store(100, Ordering::Release);
+        }
+    }
+
+    fn sync_history(&self, webview: &webkit2gtk::WebView) {
+        self.can_go_back
+            .store(webview.can_go_back(), Ordering::Release);
+        self.can_go_forward
+            .store(webview.can_go_forward(), Ordering::Release);
+    }
+}
+
+/// Apply unified settings to WebKit settings
+fn apply_webkit_settings(webview: &webkit2gtk::WebView, settings: &WebViewSettingsImpl) {
+    let Some(webkit_settings) = WebViewExt::settings(webview) else {
+        return;
+    };
+    webkit_settings.set_enable_javascript(settings.javascript_enabled());
+    webkit_settings.set_enable_html5_local_storage(settings.local_storage_enabled());
+    webkit_settings.set_enable_developer_extras(settings.dev_tools_enabled());
+    webkit_settings.set_allow_file_access_from_file_urls(settings.allow_file_access());
+    if let Some(user_agent) = settings.user_agent() {
+        webkit_settings.set_user_agent(Some(&user_agent));
+    }
+    if std::env::var_os(ENV_SOFTWARE_RENDERING).is_some() {
+        webkit_settings.set_hardware_acceleration_policy(HardwareAccelerationPolicy::Never);
+    }
+    if let Some(color) = settings
+        .background_color()
+        .and_then(|c| c.parse::<gtk::gdk::RGBA>().ok())
+    {
+        webview.set_background_color(&color);
+    }
+}
+
+/// Parameters for creating the native view on the GTK thread
+struct CreateParams {
+    id: u64,
+    title: String,
+    width: u32,
+    height: u32,
+    url: Option<String>,
+    html: Option<String>,
+    base_uri: Option<String>,
+    parent_handle: Option<u64>,
+    settings: WebViewSettingsImpl,
+    state: Arc<SharedState>,
+    /// Cookies from the jar, added before the first load
+    cookies: Vec<CookieInfo>,
+}
+
+/// Result of native view creation: (X11 window id, effective user agent)
+type Created = Result<(Option<u64>, String), String>;
+
+/// Create the window and webview (GTK thread only)
+fn create_native(params: CreateParams) -> Created {
+    let context = WebContext::default().ok_or("No default WebKit context")?;
+    let webview = webkit2gtk::WebView::with_context(&context);
+    apply_webkit_settings(&webview, &params.settings);
+
+    if let Some(manager) = context.cookie_manager() {
+        for info in &params.cookies {
+            manager.add_cookie(
+                &mut soup_cookie(info),
+                None::<&gtk::gio::Cancellable>,
+                |r| {
+                    if let Err(e) = r {
+                        tracing::warn!("[WebKitGTK] Failed to restore cookie: {}", e);
+                    }
+                },
+            );
+        }
+    }
+
+    let context_menu = params.settings.context_menu_enabled();
+    webview.connect_context_menu(move |_, _, _, _| !context_menu);
+
+    let state = params.state.clone();
+    webview.connect_load_changed(move |webview, event| match event {
+        LoadEvent::Started => state.set_loading(true),
+        LoadEvent::Committed => {
+            *state.current_url.write() = webview.uri().map(|s| s.to_string());
+            state.sync_history(webview);
+        }
+        LoadEvent::Finished => {
+            state.set_loading(false);
+            state.sync_history(webview);
+        }
+        _ => {}
+    });
+
+    let state = params.state.clone();
+    webview.connect_load_failed(move |_, _, uri, error| {
+        tracing::warn!("[WebKitGTK] Failed to load {}: {}", uri, error);
+        state.set_loading(false);
+        false
+    });
+
+    let state = params.state.clone();
+    webview.connect_title_notify(move |webview| {
+        *state.current_title.write() = webview
+            .title()
+            .map(|s| s.to_string())
+            .filter(|s| !s.is_empty());
+    });
+
+    let state = params.state.clone();
+    webview.connect_uri_notify(move |webview| {
+        *state.current_url.write() = webview.uri().map(|s| s.to_string());
+    });
+
+    let state = params.state.clone();
+    webview.connect_estimated_load_progress_notify(move |webview| {
+        let percent = (webview.estimated_load_progress() * 100.0).round() as u8;
+        state
+            .load_progress
+            .store(percent.min(100), Ordering::Release);
+    });
+
+    let window = gtk::Window::new(gtk::WindowType::Toplevel);
+    window.set_title(&params.title);
+    window.set_default_size(params.width as i32, params.height as i32);
+    window.add(&webview);
+
+    // Closing the native window only requests close; the owner decides
+    let state = params.state.clone();
+    window.connect_delete_event(move |_, _| {
+        let _ = state.lifecycle.request_close();
+        glib::Propagation::Stop
+    });
+
+    window.show_all();
+
+    let gdk_window = window.window();
+    let xid = gdk_window
+        .as_ref()
+        .and_then(|w| w.downcast_ref::<gdkx11::X11Window>())
+        // xlib::Window is a c_ulong, which is narrower than u64 on 32-bit targets
+        .map(|w| {
+            #[allow(clippy::unnecessary_cast)]
+            let xid = w.xid() as u64;
+            xid
+        });
+
+    if let (Some(parent), Some(child)) = (params.parent_handle, gdk_window.as_ref()) {
+        let display = child
+            .display()
+            .downcast::<gdkx11::X11Display>()
+            .map_err(|_| "Embedding requires an X11 display")?;
+        let parent = gdkx11::X11Window::foreign_new_for_display(&display, parent as _);
+        child.reparent(parent.upcast_ref(), 0, 0);
+    }
+
+    if let Some(html) = params.html {
+        params.state.set_loading(true);
+        webview.load_html(&html, params.base_uri.as_deref());
+    } else if let Some(url) = params.url {
+        params.state.set_loading(true);
+        webview.load_uri(&url);
+    }
+
+    let user_agent = WebViewExt::settings(&webview)
+        .and_then(|s| s.user_agent())
+        .map(|s| s.to_string())
+        .unwrap_or_default();
+
+    VIEWS.with(|views| {
+        views
+            .borrow_mut()
+            .insert(params.id, NativeView { window, webview })
+    });
+
+    Ok((xid, user_agent))
+}
+
+/// WebKitGTK backend implementation
+///
+/// Owns a native GTK window hosting a `WebKitWebView`.
+///
+/// ## Thread Safety
+///
+/// The handle is `Send + Sync`; all widget access happens on the GTK thread.
+/// Synchronous operations (cookies, creation) wait at most 10 seconds and
+/// return `WebViewError::Timeout` if the GTK thread does not respond.
+pub struct WebKitGtkBackend {
+    /// Registry key of the native view
+    id: u64,
+    /// State mirrored from WebKit signals
+    state: Arc<SharedState>,
+    /// X11 window id (None on Wayland)
+    native_handle: Option<u64>,
+    /// Settings (using Box for stable address)
+    settings: Box<WebViewSettingsImpl>,
+    /// Effective user agent string
+    user_agent: String,
+    /// Base URI used for `load_html`
+    base_uri: Option<String>,
+    /// Cookie jar mirrored from WebKit's cookie store
+    cookie_jar: Arc<CookieJar>,
+}
+
+impl WebKitGtkBackend {
+    /// Create a new WebKitGTK backend from configuration
+    ///
+    /// Spawns the GTK thread on first use and blocks until the native window exists.
+    pub fn new(config: &BackendConfig) -> WebViewResult<Self> {
+        ensure_gtk()?;
+
+        let cookie_jar = config.open_cookie_jar()?;
+        let id = NEXT_ID.fetch_add(1, Ordering::Relaxed);
+        let state = Arc::new(SharedState::new());
+        let base_uri = config
+            .asset_root
+            .as_ref()
+            .and_then(|root| url::Url::from_directory_path(root).ok())
+            .map(|u| u.to_string());
+
+        let params = CreateParams {
+            id,
+            title: config.title.clone(),
+            width: config.width,
+            height: config.height,
+            url: config.url.clone(),
+            html: config.html.clone(),
+            base_uri: base_uri.clone(),
+            parent_handle: config.parent_handle,
+            settings: config.settings.clone(),
+            state: state.clone(),
+            cookies: cookie_jar
+                .all()
+                .iter()
+                .filter(|c| c.partition_key.is_none())
+                .map(CookieInfo::from)
+                .collect(),
+        };
+        let (native_handle, user_agent) = call_on_gtk(move |tx| {
+            let _ = tx.send(create_native(params));
+        })?
+        .map_err(WebViewError::Initialization)?;
+
+        state.lifecycle.activate();
+
+        Ok(Self {
+            id,
+            state,
+            native_handle,
+            settings: Box::new(config.settings.clone()),
+            user_agent,
+            base_uri,
+            cookie_jar,
+        })
+    }
+
+    /// Cookie jar mirrored from WebKit's cookie store
+    pub fn cookie_jar(&self) -> &Arc<CookieJar> {
+        &self.cookie_jar
+    }
+
+    /// Write WebKit's cookies for the jar's domains and the current page back to the jar
+    fn save_cookies(&self) -> WebViewResult<()> {
+        let mut hosts: Vec<String> = self
+            .cookie_jar
+            .all()
+            .into_iter()
+            .filter(|c| c.partition_key.is_none())
+            .map(|c| c.domain)
+            .collect();
+        if let Some(host) = self
+            .url()
+            .and_then(|u| url::Url::parse(&u).ok())
+            .and_then(|u| u.host_str().map(str::to_string))
+        {
+            hosts.push(host);
+        }
+        hosts.sort();
+        hosts.dedup();
+
+        for host in hosts {
+            let (id, uri) = (self.id, cookie_lookup_uri(&host));
+            let snapshot = call_on_gtk(move |tx| match cookie_manager(id) {
+                Ok(manager) => manager.cookies(&uri, None::<&gtk::gio::Cancellable>, move |r| {
+                    let _ = tx.send(
+                        r.map(|cookies| {
+                            cookies
+                                .into_iter()
+                                .map(|mut c| Cookie::from(&cookie_info(&mut c)))
+                                .collect::<Vec<_>>()
+                        })
+                        .map_err(|e| WebViewError::Cookie(e.to_string())),
+                    );
+                }),
+                Err(e) => {
+                    let _ = tx.send(Err(e));
+                }
+            })??;
+            // The lookup only returns cookies sent to the domain root
+            self.cookie_jar
+                .replace_from_engine(|c| c.domain == host && c.path == "/", snapshot)
+                .map_err(|e| WebViewError::Cookie(e.to_string()))?;
+        }
+        Ok(())
+    }
+
+    /// Apply settings from a WebViewSettingsImpl
+    pub fn apply_settings(&mut self, settings: WebViewSettingsImpl) -> WebViewResult<()> {
+        *self.settings = settings.clone();
+        with_view(self.id, move |view| {
+            apply_webkit_settings(&view.webview, &settings)
+        })
+    }
+
+    /// Get the lifecycle state machine
+    pub fn lifecycle(&self) -> &AtomicLifecycle {
+        &self.state.lifecycle
+    }
+
+    /// Fail fast once close has been requested
+    fn ensure_open(&self) -> WebViewResult<()> {
+        if self.state.lifecycle.is_closing() {
+            return Err(WebViewError::Closed);
+        }
+        Ok(())
+    }
+
+    /// Run a fire-and-forget operation on the webview
+    fn dispatch<F>(&self, f: F) -> WebViewResult<()>
+    where
+        F: FnOnce(&NativeView) + Send + 'static,
+    {
+        self.ensure_open()?;
+        with_view(self.id, f)
+    }
+}
+
+impl Drop for WebKitGtkBackend {
+    fn drop(&mut self) {
+        if !self.state.lifecycle.is_destroyed() {
+            let _ = self.close();
+        }
+    }
+}
+
+impl WebViewBackend for WebKitGtkBackend {
+    // ========== Navigation ==========
+
+    fn navigate(&self, url: &str) -> WebViewResult<()> {
+        let url = url.to_string();
+        self.state.set_loading(true);
+        self.dispatch(move |view| view.webview.load_uri(&url))
+    }
+
+    fn url(&self) -> Option<String> {
+        self.state.current_url.read().clone()
+    }
+
+    fn can_go_back(&self) -> bool {
+        self.state.can_go_back.load(Ordering::Acquire)
+    }
+
+    fn can_go_forward(&self) -> bool {
+        self.state.can_go_forward.load(Ordering::Acquire)
+    }
+
+    fn go_back(&self) -> WebViewResult<()> {
+        self.dispatch(|view| view.webview.go_back())
+    }
+
+    fn go_forward(&self) -> WebViewResult<()> {
+        self.dispatch(|view| view.webview.go_forward())
+    }
+
+    fn reload(&self) -> WebViewResult<()> {
+        self.dispatch(|view| view.webview.reload())
+    }
+
+    fn stop(&self) -> WebViewResult<()> {
+        self.dispatch(|view| view.webview.stop_loading())
+    }
+
+    // ========== Content Loading ==========
+
+    fn load_html(&self, html: &str) -> WebViewResult<()> {
+        let html = html.to_string();
+        let base_uri = self.base_uri.clone();
+        self.state.set_loading(true);
+        self.dispatch(move |view| view.webview.load_html(&html, base_uri.as_deref()))
+    }
+
+    fn title(&self) -> Option<String> {
+        self.state.current_title.read().clone()
+    }
+
+    fn load_progress(&self) -> LoadProgress {
+        LoadProgress {
+            percent: self.state.load_progress.load(Ordering::Acquire),
+            is_complete: !self.is_loading(),
+        }
+    }
+
+    fn is_loading(&self) -> bool {
+        self.state.is_loading.load(Ordering::Acquire)
+    }
+
+    // ========== JavaScript ==========
+
+    fn eval_js(&self, script: &str) -> WebViewResult<()> {
+        let script = script.to_string();
+        self.dispatch(move |view| {
+            view.webview.evaluate_javascript(
+                &script,
+                None,
+                None,
+                None::<&gtk::gio::Cancellable>,
+                |result| {
+                    if let Err(e) = result {
+                        tracing::warn!("[WebKitGTK] JavaScript error: {}", e);
+                    }
+                },
+            )
+        })
+    }
+
+    fn eval_js_with_callback(
+        &self,
+        script: &str,
+        callback: JavaScriptCallback,
+    ) -> WebViewResult<()> {
+        let script = script.to_string();
+        self.dispatch(move |view| {
+            view.webview.evaluate_javascript(
+                &script,
+                None,
+                None,
+                None::<&gtk::gio::Cancellable>,
+                move |result| {
+                    let value = result
+                        .map_err(|e| WebViewError::JavaScript(e.to_string()))
+                        .and_then(|value| {
+                            if value.is_undefined() || value.is_null() {
+                                return Ok(serde_json::Value::Null);
+                            }
+                            let json = value.to_json(0).ok_or_else(|| {
+                                WebViewError::JavaScript(
+                                    "Result is not JSON-serializable".to_string(),
+                                )
+                            })?;
+                            serde_json::from_str(&json)
+                                .map_err(|e| WebViewError::JavaScript(e.to_string()))
+                        });
+                    callback(value);
+                },
+            )
+        })
+    }
+
+    // ========== Cookie Management ==========
+
+    fn set_cookie(&self, cookie: &CookieInfo) -> WebViewResult<()> {
+        self.ensure_open()?;
+        if cookie.domain.is_empty() || cookie.name.is_empty() {
+            return Err(WebViewError::InvalidArgument(
+                "Cookie domain and name are required".to_string(),
+            ));
+        }
+        if let Some(ts) = cookie.expires {
+            glib::DateTime::from_unix_utc(ts).map_err(|e| {
+                WebViewError::InvalidArgument(format!("Invalid cookie expiry: {}", e))
+            })?;
+        }
+        self.cookie_jar
+            .set(Cookie::from(cookie))
+            .map_err(|e| WebViewError::Cookie(e.to_string()))?;
+
+        let info = cookie.clone();
+        let id = self.id;
+        call_on_gtk(move |tx| {
+            let manager = match cookie_manager(id) {
+                Ok(manager) => manager,
+                Err(e) => {
+                    let _ = tx.send(Err(e));
+                    return;
+                }
+            };
+            manager.add_cookie(
+                &mut soup_cookie(&info),
+                None::<&gtk::gio::Cancellable>,
+                move |r| {
+                    let _ = tx.send(r.map_err(|e| WebViewError::Cookie(e.to_string())));
+                },
+            );
+        })?
+    }
+
+    fn get_cookie(&self, domain: &str, name: &str) -> WebViewResult<Option<CookieInfo>> {
+        self.ensure_open()?;
+        let (id, lookup_domain, lookup_name) = (self.id, domain.to_string(), name.to_string());
+        let found = call_on_gtk(move |tx| match cookie_manager(id) {
+            Ok(manager) => find_cookie(manager, &lookup_domain, &lookup_name, move |_, result| {
+                let _ = tx.send(result.map(|c| c.map(|mut c| cookie_info(&mut c))));
+            }),
+            Err(e) => {
+                let _ = tx.send(Err(e));
+            }
+        })??;
+
+        // WebKit is authoritative while open (pages set cookies too); mirror it
+        let mirrored = match &found {
+            Some(info) => self.cookie_jar.set(Cookie::from(info)),
+            None => self.cookie_jar.remove_named(domain, name).map(|_| ()),
+        };
+        if let Err(e) = mirrored {
+            tracing::warn!("[WebKitGTK] Failed to update cookie jar: {}", e);
+        }
+        Ok(found)
+    }
+
+    fn delete_cookie(&self, domain: &str, name: &str) -> WebViewResult<()> {
+        self.ensure_open()?;
+        self.cookie_jar
+            .remove_named(domain, name)
+            .map_err(|e| WebViewError::Cookie(e.to_string()))?;
+        let (id, domain, name) = (self.id, domain.to_string(), name.to_string());
+        call_on_gtk(move |tx| match cookie_manager(id) {
+            Ok(manager) => find_cookie(
+                manager,
+                &domain,
+                &name,
+                move |manager, result| match result {
+                    Ok(Some(mut cookie)) => manager.delete_cookie(
+                        &mut cookie,
+                        None::<&gtk::gio::Cancellable>,
+                        move |r| {
+                            let _ = tx.send(r.map_err(|e| WebViewError::Cookie(e.to_string())));
+                        },
+                    ),
+                    other => {
+                        let _ = tx.send(other.map(|_| ()));
+                    }
+                },
+            ),
+            Err(e) => {
+                let _ = tx.send(Err(e));
+            }
+        })?
+    }
+
+    fn clear_cookies(&self) -> WebViewResult<()> {
+        self.ensure_open()?;
+        self.cookie_jar
+            .clear()
+            .map_err(|e| WebViewError::Cookie(e.to_string()))?;
+        let id = self.id;
+        call_on_gtk(move |tx| {
+            let manager = VIEWS.with(|views| {
+                views
+                    .borrow()
+                    .get(&id)
+                    .and_then(|view| view.webview.website_data_manager())
+            });
+            match manager {
+                Some(manager) => manager.clear(
+                    WebsiteDataTypes::COOKIES,
+                    glib::TimeSpan(0),
+                    None::<&gtk::gio::Cancellable>,
+                    move |r| {
+                        let _ = tx.send(r.map_err(|e| WebViewError::Cookie(e.to_string())));
+                    },
+                ),
+                None => {
+                    let _ = tx.send(Err(WebViewError::Closed));
+                }
+            }
+        })?
+    }
+
+    // ========== Settings ==========
+
+    fn settings(&self) -> &dyn WebViewSettings {
+        self.settings.as_ref()
+    }
+
+    fn settings_mut(&mut self) -> &mut dyn WebViewSettings {
+        self.settings.as_mut()
+    }
+
+    fn http_user_agent(&self) -> String {
+        self.settings
+            .user_agent()
+            .unwrap_or_else(|| self.user_agent.clone())
+    }
+
+    // ========== Lifecycle ==========
+
+    fn lifecycle_state(&self) -> LifecycleState {
+        self.state.lifecycle.state()
+    }
+
+    fn close(&self) -> WebViewResult<()> {
+        let lifecycle = &self.state.lifecycle;
+        if !lifecycle.is_destroyed() {
+            if let Err(e) = self.save_cookies() {
+                tracing::warn!("[WebKitGTK] Failed to save cookies: {}", e);
+            }
+        }
+        let _ = lifecycle.request_close();
+        let _ = lifecycle.begin_destroy();
+
+        let id = self.id;
+        let destroyed = call_on_gtk(move |tx| {
+            let view = VIEWS.with(|views| views.borrow_mut().remove(&id));
+            if let Some(view) = view {
+                // SAFETY: the window is removed from the registry and never used again
+                unsafe { view.window.destroy() };
+            }
+            let _ = tx.send(());
+        });
+
+        let _ = lifecycle.finish_destroy();
+        destroyed
+    }
+
+    // ========== Window Control ==========
+
+    fn set_bounds(&self, x: i32, y: i32, width: u32, height: u32) -> WebViewResult<()> {
+        self.dispatch(move |view| {
+            view.window.move_(x, y);
+            view.window.resize(width as i32, height as i32);
+        })
+    }
+
+    fn set_visible(&self, visible: bool) -> WebViewResult<()> {
+        self.dispatch(move |view| view.window.set_visible(visible))
+    }
+
+    fn focus(&self) -> WebViewResult<()> {
+        self.dispatch(|view| {
+            view.window.present();
+            view.webview.grab_focus();
+        })
+    }
+}
+
+impl EmbeddableBackend for WebKitGtkBackend {
+    fn native_handle(&self) -> Option<u64> {
+        self.native_handle
+    }
+
+    fn process_events(&self) -> ProcessResult {
+        // Pump GTK when the caller owns the GTK main context
+        if gtk::is_initialized_main_thread() {
+            let context = glib::MainContext::default();
+            while context.iteration(false) {}
+        }
+        self.process_ipc_only()
+    }
+
+    fn process_ipc_only(&self) -> ProcessResult {
+        if self.state.lifecycle.is_closing() {
+            ProcessResult::CloseRequested
+        } else {
+            ProcessResult::Continue
+        }
+    }
+
+    fn emit_event(&self, event_name: &str, data: serde_json::Value) -> WebViewResult<()> {
+        use askama::Template;
+
+        let event_data = data.to_string();
+        let script = EmitEventTemplate {
+            event_name,
+            event_data: &event_data,
+        }
+        .render()
+        .map_err(|e| WebViewError::Internal(e.to_string()))?;
+        self.eval_js(&script)
+    }
+}
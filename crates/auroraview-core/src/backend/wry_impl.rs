@@ -12,8 +12,13 @@
 //!
 //! The actual WebView operations are delegated to the main WebView instance.
 //! This backend primarily tracks state for the trait interface.
+//!
+//! Cookies go through a [`CookieJar`]; the owner of the wry WebView seeds
+//! the engine from the same jar on startup and writes the engine's cookies
+//! back on shutdown (see `CookieJar::seed_webview`).
 
 use std::sync::atomic::{AtomicBool, AtomicU8, Ordering};
+use std::sync::Arc;
 
 use parking_lot::RwLock;
 
@@ -21,6 +26,7 @@ use super::error::{WebViewError, WebViewResult};
 use super::lifecycle::{AtomicLifecycle, LifecycleState};
 use super::settings::{WebViewSettings, WebViewSettingsImpl};
 use super::traits::{CookieInfo, JavaScriptCallback, LoadProgress, WebViewBackend};
+use crate::cookies::{Cookie, CookieJar};
 
 /// Wry backend implementation
 ///
@@ -47,6 +53,8 @@ pub struct WryBackend {
     settings: Box<WebViewSettingsImpl>,
     /// User agent string
     user_agent: String,
+    /// Cookie jar (possibly shared with the engine owner and `chrome.cookies`)
+    cookie_jar: Arc<CookieJar>,
 }
 
 impl Default for WryBackend {
@@ -66,6 +74,7 @@ impl WryBackend {
             load_progress: AtomicU8::new(0),
             settings: Box::new(WebViewSettingsImpl::default()),
             user_agent: format!("AuroraView/{}", env!("CARGO_PKG_VERSION")),
+            cookie_jar: Arc::new(CookieJar::new()),
         }
     }
 
@@ -79,9 +88,21 @@ impl WryBackend {
             load_progress: AtomicU8::new(0),
             settings: Box::new(WebViewSettingsImpl::default()),
             user_agent: format!("AuroraView/{}", env!("CARGO_PKG_VERSION")),
+            cookie_jar: Arc::new(CookieJar::new()),
         }
     }
 
+    /// Use a shared cookie jar instead of a private in-memory one
+    pub fn with_cookie_jar(mut self, jar: Arc<CookieJar>) -> Self {
+        self.cookie_jar = jar;
+        self
+    }
+
+    /// Cookie jar backing the cookie methods
+    pub fn cookie_jar(&self) -> &Arc<CookieJar> {
+        &self.cookie_jar
+    }
+
     /// Activate the backend (transition from Creating to Active)
     pub fn activate(&self) -> bool {
         self.lifecycle.activate().is_success()
@@ -242,21 +263,38 @@ impl WebViewBackend for WryBackend {
 
     // ========== Cookie Management ==========
 
-    fn set_cookie(&self, _cookie: &CookieInfo) -> WebViewResult<()> {
-        // Note: Cookie management requires platform-specific implementation
-        Err(WebViewError::Unsupported("set_cookie".to_string()))
+    fn set_cookie(&self, cookie: &CookieInfo) -> WebViewResult<()> {
+        if self.lifecycle.is_closing() {
+            return Err(WebViewError::Closed);
+        }
+        if cookie.domain.is_empty() || cookie.name.is_empty() {
+            return Err(WebViewError::invalid_arg(
+                "Cookie domain and name are required",
+            ));
+        }
+        self.cookie_jar
+            .set(Cookie::from(cookie))
+            .map_err(|e| WebViewError::Cookie(e.to_string()))
     }
 
-    fn get_cookie(&self, _domain: &str, _name: &str) -> WebViewResult<Option<CookieInfo>> {
-        Err(WebViewError::Unsupported("get_cookie".to_string()))
+    fn get_cookie(&self, domain: &str, name: &str) -> WebViewResult<Option<CookieInfo>> {
+        Ok(self
+            .cookie_jar
+            .get(domain, name)
+            .map(|c| CookieInfo::from(&c)))
     }
 
-    fn delete_cookie(&self, _domain: &str, _name: &str) -> WebViewResult<()> {
-        Err(WebViewError::Unsupported("delete_cookie".to_string()))
+    fn delete_cookie(&self, domain: &str, name: &str) -> WebViewResult<()> {
+        self.cookie_jar
+            .remove_named(domain, name)
+            .map(|_| ())
+            .map_err(|e| WebViewError::Cookie(e.to_string()))
     }
 
     fn clear_cookies(&self) -> WebViewResult<()> {
-        Err(WebViewError::Unsupported("clear_cookies".to_string()))
+        self.cookie_jar
+            .clear()
+            .map_err(|e| WebViewError::Cookie(e.to_string()))
     }
 
     // ========== Settings ==========
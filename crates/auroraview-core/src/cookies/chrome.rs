@@ -0,0 +1,98 @@
+//! `chrome.cookies` storage adapter
+//!
+//! Lets [`CookiesApi::with_store`](auroraview_extensions::apis::cookies::CookiesApi::with_store)
+//! read and write the same jar the WebView backends use. Only the default
+//! store (`"0"`) is backed by the jar; partitioned cookies are hidden, as
+//! in Chrome when no `partitionKey` is given.
+
+use auroraview_extensions::apis::cookies::{
+    Cookie as ExtensionCookie, CookieStoreBackend, SameSiteStatus,
+};
+use auroraview_extensions::{ExtensionError, ExtensionResult};
+
+use super::cookie::normalize_domain;
+use super::{Cookie, CookieJar, SameSite};
+
+/// ID of the default cookie store
+const DEFAULT_STORE_ID: &str = "0";
+
+impl From<&Cookie> for ExtensionCookie {
+    fn from(cookie: &Cookie) -> Self {
+        Self {
+            name: cookie.name.clone(),
+            value: cookie.value.clone(),
+            domain: cookie.display_domain(),
+            host_only: cookie.host_only,
+            path: cookie.path.clone(),
+            secure: cookie.secure,
+            http_only: cookie.http_only,
+            same_site: match cookie.same_site {
+                SameSite::Strict => SameSiteStatus::Strict,
+                SameSite::Lax => SameSiteStatus::Lax,
+                SameSite::None => SameSiteStatus::NoRestriction,
+                SameSite::Unspecified => SameSiteStatus::Unspecified,
+            },
+            session: cookie.is_session(),
+            expiration_date: cookie.expires.map(|e| e as f64),
+            store_id: DEFAULT_STORE_ID.to_string(),
+        }
+    }
+}
+
+impl From<&ExtensionCookie> for Cookie {
+    fn from(cookie: &ExtensionCookie) -> Self {
+        let mut converted = Cookie::new(cookie.name.clone(), cookie.value.clone(), &cookie.domain)
+            .with_path(cookie.path.clone())
+            .secure(cookie.secure)
+            .http_only(cookie.http_only)
+            .with_same_site(match cookie.same_site {
+                SameSiteStatus::Strict => SameSite::Strict,
+                SameSiteStatus::Lax => SameSite::Lax,
+                SameSiteStatus::NoRestriction => SameSite::None,
+                SameSiteStatus::Unspecified => SameSite::Unspecified,
+            });
+        converted.host_only = cookie.host_only && !cookie.domain.starts_with('.');
+        converted.expires = cookie
+            .expiration_date
+            .filter(|_| !cookie.session)
+            .map(|e| e as i64);
+        converted
+    }
+}
+
+impl CookieStoreBackend for CookieJar {
+    fn cookies(&self, store_id: &str) -> Vec<ExtensionCookie> {
+        if store_id != DEFAULT_STORE_ID {
+            return Vec::new();
+        }
+        self.all()
+            .iter()
+            .filter(|c| c.partition_key.is_none())
+            .map(ExtensionCookie::from)
+            .collect()
+    }
+
+    fn set_cookie(&self, cookie: ExtensionCookie) -> ExtensionResult<()> {
+        if cookie.store_id != DEFAULT_STORE_ID {
+            return Err(ExtensionError::NotFound(format!(
+                "Cookie store '{}'",
+                cookie.store_id
+            )));
+        }
+        self.set(Cookie::from(&cookie))
+            .map_err(|e| ExtensionError::InvalidParams(e.to_string()))
+    }
+
+    fn remove_cookie(&self, cookie: &ExtensionCookie) -> ExtensionResult<bool> {
+        if cookie.store_id != DEFAULT_STORE_ID {
+            return Ok(false);
+        }
+        self.remove(
+            &normalize_domain(&cookie.domain),
+            &cookie.path,
+            &cookie.name,
+            None,
+        )
+        .map_err(|e| ExtensionError::Storage(e.to_string()))
+    }
+}
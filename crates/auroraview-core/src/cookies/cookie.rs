@@ -0,0 +1,304 @@
+//! Cookie model and RFC 6265 matching rules
+
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+
+use super::{CookieError, CookieResult};
+use crate::backend::CookieInfo;
+
+/// SameSite attribute
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SameSite {
+    /// Never sent on cross-site requests
+    Strict,
+    /// Sent on cross-site top-level navigations only
+    Lax,
+    /// Always sent (requires `Secure`)
+    None,
+    /// Not specified; treated as `Lax`
+    #[default]
+    Unspecified,
+}
+
+/// A stored cookie
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Cookie {
+    /// Cookie name
+    pub name: String,
+    /// Cookie value
+    pub value: String,
+    /// Domain (lowercase, without leading dot)
+    pub domain: String,
+    /// Only sent to `domain` itself, not its subdomains
+    pub host_only: bool,
+    /// Path
+    pub path: String,
+    /// Only sent over secure connections
+    pub secure: bool,
+    /// Hidden from JavaScript
+    pub http_only: bool,
+    /// SameSite attribute
+    #[serde(default)]
+    pub same_site: SameSite,
+    /// Expiration timestamp (Unix epoch seconds), `None` for session cookies
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expires: Option<i64>,
+    /// Top-level site of a partitioned cookie (e.g. `https://example.com`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub partition_key: Option<String>,
+    /// Creation timestamp (Unix epoch seconds)
+    #[serde(default)]
+    pub created: i64,
+}
+
+impl Cookie {
+    /// Create a session cookie for `/`
+    ///
+    /// A leading dot in `domain` makes it a domain cookie (sent to subdomains);
+    /// otherwise the cookie is host-only.
+    pub fn new(name: impl Into<String>, value: impl Into<String>, domain: &str) -> Self {
+        Self {
+            name: name.into(),
+            value: value.into(),
+            domain: normalize_domain(domain),
+            host_only: !domain.starts_with('.'),
+            path: "/".to_string(),
+            secure: false,
+            http_only: false,
+            same_site: SameSite::Unspecified,
+            expires: None,
+            partition_key: None,
+            created: unix_now(),
+        }
+    }
+
+    /// Set the path
+    pub fn with_path(mut self, path: impl Into<String>) -> Self {
+        self.path = path.into();
+        self
+    }
+
+    /// Set the Secure flag
+    pub fn secure(mut self, secure: bool) -> Self {
+        self.secure = secure;
+        self
+    }
+
+    /// Set the HttpOnly flag
+    pub fn http_only(mut self, http_only: bool) -> Self {
+        self.http_only = http_only;
+        self
+    }
+
+    /// Set the SameSite attribute
+    pub fn with_same_site(mut self, same_site: SameSite) -> Self {
+        self.same_site = same_site;
+        self
+    }
+
+    /// Set the expiration timestamp (Unix epoch seconds)
+    pub fn expires_at(mut self, expires: i64) -> Self {
+        self.expires = Some(expires);
+        self
+    }
+
+    /// Partition the cookie by top-level site (CHIPS)
+    pub fn partitioned(mut self, top_level_site: impl Into<String>) -> Self {
+        self.partition_key = Some(top_level_site.into());
+        self
+    }
+
+    /// Whether the cookie lives only for the session
+    pub fn is_session(&self) -> bool {
+        self.expires.is_none()
+    }
+
+    /// Whether the cookie has expired at `now` (Unix epoch seconds)
+    pub fn is_expired_at(&self, now: i64) -> bool {
+        self.expires.is_some_and(|expires| expires <= now)
+    }
+
+    /// Whether the cookie has expired
+    pub fn is_expired(&self) -> bool {
+        self.is_expired_at(unix_now())
+    }
+
+    /// Domain matching (RFC 6265 section 5.1.3)
+    pub fn domain_matches(&self, host: &str) -> bool {
+        let host = normalize_domain(host);
+        if self.host_only {
+            return host == self.domain;
+        }
+        host == self.domain
+            || (host.ends_with(&self.domain)
+                && host[..host.len() - self.domain.len()].ends_with('.'))
+    }
+
+    /// Path matching (RFC 6265 section 5.1.4)
+    pub fn path_matches(&self, request_path: &str) -> bool {
+        let request_path = if request_path.is_empty() {
+            "/"
+        } else {
+            request_path
+        };
+        if request_path == self.path {
+            return true;
+        }
+        request_path.starts_with(&self.path)
+            && (self.path.ends_with('/') || request_path[self.path.len()..].starts_with('/'))
+    }
+
+    /// Check the cookie can be stored
+    pub fn validate(&self) -> CookieResult<()> {
+        if self.name.is_empty() {
+            return Err(CookieError::InvalidCookie("name is empty".to_string()));
+        }
+        if self.domain.is_empty() {
+            return Err(CookieError::InvalidCookie(format!(
+                "'{}' has no domain",
+                self.name
+            )));
+        }
+        if !self.path.starts_with('/') {
+            return Err(CookieError::InvalidCookie(format!(
+                "'{}' path must start with '/'",
+                self.name
+            )));
+        }
+        if self.same_site == SameSite::None && !self.secure {
+            return Err(CookieError::InvalidCookie(format!(
+                "'{}' has SameSite=None without Secure",
+                self.name
+            )));
+        }
+        if self.partition_key.is_some() && !self.secure {
+            return Err(CookieError::InvalidCookie(format!(
+                "'{}' is partitioned without Secure",
+                self.name
+            )));
+        }
+        Ok(())
+    }
+
+    /// Domain in `CookieInfo` form (leading dot for domain cookies)
+    pub fn display_domain(&self) -> String {
+        if self.host_only {
+            self.domain.clone()
+        } else {
+            format!(".{}", self.domain)
+        }
+    }
+}
+
+impl From<&CookieInfo> for Cookie {
+    fn from(info: &CookieInfo) -> Self {
+        let mut cookie = Cookie::new(info.name.clone(), info.value.clone(), &info.domain)
+            .secure(info.secure)
+            .http_only(info.http_only)
+            .with_path(info.path.clone().unwrap_or_else(|| "/".to_string()));
+        cookie.expires = info.expires;
+        cookie
+    }
+}
+
+impl From<&Cookie> for CookieInfo {
+    fn from(cookie: &Cookie) -> Self {
+        Self {
+            domain: cookie.display_domain(),
+            name: cookie.name.clone(),
+            value: cookie.value.clone(),
+            path: Some(cookie.path.clone()),
+            expires: cookie.expires,
+            http_only: cookie.http_only,
+            secure: cookie.secure,
+        }
+    }
+}
+
+/// Request context used to decide which cookies are sent
+#[derive(Debug, Clone, Copy, Default)]
+pub struct CookieContext<'a> {
+    /// URL of the top-level document; `None` when the request is the top-level document
+    pub top_level_url: Option<&'a str>,
+    /// Whether the request is a top-level navigation (allows `Lax` cookies cross-site)
+    pub top_level_navigation: bool,
+}
+
+impl<'a> CookieContext<'a> {
+    /// Context for a subresource request embedded in `top_level_url`
+    pub fn embedded_in(top_level_url: &'a str) -> Self {
+        Self {
+            top_level_url: Some(top_level_url),
+            top_level_navigation: false,
+        }
+    }
+}
+
+/// Site of a URL: scheme and the last two host labels
+///
+/// This approximates the registrable domain without a public suffix list,
+/// which is sufficient for SameSite and partitioning decisions between
+/// distinct organisations (e.g. `https://example.com`).
+pub fn site_for_url(url: &url::Url) -> Option<String> {
+    let host = url.host_str()?.to_ascii_lowercase();
+    let site = if url
+        .host()
+        .is_some_and(|h| matches!(h, url::Host::Domain(_)))
+    {
+        let labels: Vec<&str> = host.split('.').collect();
+        labels[labels.len().saturating_sub(2)..].join(".")
+    } else {
+        host
+    };
+    Some(format!("{}://{}", url.scheme(), site))
+}
+
+/// Lowercase a domain and strip its leading dot
+pub(crate) fn normalize_domain(domain: &str) -> String {
+    domain.trim().trim_start_matches('.').to_ascii_lowercase()
+}
+
+/// Current time in Unix epoch seconds
+pub(crate) fn unix_now() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs() as i64)
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_domain_matching() {
+        let host_only = Cookie::new("a", "1", "example.com");
+        assert!(host_only.domain_matches("EXAMPLE.com"));
+        assert!(!host_only.domain_matches("www.example.com"));
+
+        let domain = Cookie::new("a", "1", ".example.com");
+        assert!(domain.domain_matches("example.com"));
+        assert!(domain.domain_matches("a.b.example.com"));
+        assert!(!domain.domain_matches("badexample.com"));
+    }
+
+    #[test]
+    fn test_path_matching() {
+        let cookie = Cookie::new("a", "1", "example.com").with_path("/docs");
+        assert!(cookie.path_matches("/docs"));
+        assert!(cookie.path_matches("/docs/api"));
+        assert!(!cookie.path_matches("/docsearch"));
+        assert!(!cookie.path_matches("/"));
+        assert!(Cookie::new("a", "1", "example.com").path_matches(""));
+    }
+
+    #[test]
+    fn test_site_for_url() {
+        let url = url::Url::parse("https://a.b.example.com:8443/x").unwrap();
+        assert_eq!(site_for_url(&url).as_deref(), Some("https://example.com"));
+        let url = url::Url::parse("http://127.0.0.1/").unwrap();
+        assert_eq!(site_for_url(&url).as_deref(), Some("http://127.0.0.1"));
+    }
+}
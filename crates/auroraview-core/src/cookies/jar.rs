@@ -0,0 +1,423 @@
+//! Cookie jar with optional on-disk persistence
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use parking_lot::RwLock;
+use serde::{Deserialize, Serialize};
+
+use super::cookie::{normalize_domain, site_for_url, unix_now};
+use super::netscape::{parse_netscape, to_netscape};
+use super::{Cookie, CookieContext, CookieError, CookieResult, SameSite};
+
+/// File name of the jar inside a profile data directory
+pub const COOKIE_JAR_FILE: &str = "cookies.json";
+
+/// Current on-disk format version
+const JAR_VERSION: u32 = 1;
+
+/// Unique identity of a cookie: (partition, domain, path, name)
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+struct CookieKey {
+    partition: String,
+    domain: String,
+    path: String,
+    name: String,
+}
+
+impl CookieKey {
+    fn of(cookie: &Cookie) -> Self {
+        Self {
+            partition: cookie.partition_key.clone().unwrap_or_default(),
+            domain: cookie.domain.clone(),
+            path: cookie.path.clone(),
+            name: cookie.name.clone(),
+        }
+    }
+}
+
+/// On-disk representation
+#[derive(Serialize, Deserialize)]
+struct JarFile {
+    version: u32,
+    cookies: Vec<Cookie>,
+}
+
+/// Thread-safe cookie jar
+///
+/// Share it with `Arc<CookieJar>` between backends and `chrome.cookies`.
+/// When opened from a file, every mutation is written back immediately.
+/// Session cookies are kept in memory only unless
+/// [`persist_session_cookies`](Self::persist_session_cookies) is enabled.
+#[derive(Debug, Default)]
+pub struct CookieJar {
+    cookies: RwLock<BTreeMap<CookieKey, Cookie>>,
+    path: Option<PathBuf>,
+    persist_session: bool,
+}
+
+impl CookieJar {
+    /// Create an in-memory jar
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Open a jar persisted at `path`, loading existing cookies
+    pub fn open(path: impl Into<PathBuf>) -> CookieResult<Self> {
+        let path = path.into();
+        let jar = Self {
+            path: Some(path.clone()),
+            ..Self::default()
+        };
+        if path.exists() {
+            let file: JarFile = serde_json::from_slice(&std::fs::read(&path)?)?;
+            if file.version > JAR_VERSION {
+                return Err(CookieError::Storage(format!(
+                    "Unsupported cookie jar version {}",
+                    file.version
+                )));
+            }
+            let now = unix_now();
+            let mut cookies = jar.cookies.write();
+            for cookie in file.cookies.into_iter().filter(|c| !c.is_expired_at(now)) {
+                cookies.insert(CookieKey::of(&cookie), cookie);
+            }
+        }
+        Ok(jar)
+    }
+
+    /// Open the jar of a profile data directory (`<data_dir>/cookies.json`)
+    pub fn for_profile(data_dir: impl AsRef<Path>) -> CookieResult<Self> {
+        Self::open(data_dir.as_ref().join(COOKIE_JAR_FILE))
+    }
+
+    /// Also persist session cookies, so logins survive restarts
+    pub fn persist_session_cookies(mut self, persist: bool) -> Self {
+        self.persist_session = persist;
+        self
+    }
+
+    /// Path of the backing file, if persistent
+    pub fn path(&self) -> Option<&Path> {
+        self.path.as_deref()
+    }
+
+    /// Store a cookie, replacing one with the same identity
+    ///
+    /// Setting an already expired cookie deletes it, like a browser does.
+    pub fn set(&self, mut cookie: Cookie) -> CookieResult<()> {
+        cookie.validate()?;
+        let key = CookieKey::of(&cookie);
+        {
+            let mut cookies = self.cookies.write();
+            if cookie.is_expired() {
+                cookies.remove(&key);
+            } else {
+                // Keep the original creation time when overwriting
+                if let Some(existing) = cookies.get(&key) {
+                    cookie.created = existing.created;
+                }
+                cookies.insert(key, cookie);
+            }
+        }
+        self.autosave()
+    }
+
+    /// Find an unexpired cookie by domain and name (any path, unpartitioned first)
+    pub fn get(&self, domain: &str, name: &str) -> Option<Cookie> {
+        let domain = normalize_domain(domain);
+        let now = unix_now();
+        self.cookies
+            .read()
+            .values()
+            .find(|c| c.domain == domain && c.name == name && !c.is_expired_at(now))
+            .cloned()
+    }
+
+    /// Find an unexpired cookie by its full identity
+    pub fn find(
+        &self,
+        domain: &str,
+        path: &str,
+        name: &str,
+        partition_key: Option<&str>,
+    ) -> Option<Cookie> {
+        let key = CookieKey {
+            partition: partition_key.unwrap_or_default().to_string(),
+            domain: normalize_domain(domain),
+            path: path.to_string(),
+            name: name.to_string(),
+        };
+        self.cookies
+            .read()
+            .get(&key)
+            .filter(|c| !c.is_expired())
+            .cloned()
+    }
+
+    /// Remove a cookie by its full identity, returning whether it existed
+    pub fn remove(
+        &self,
+        domain: &str,
+        path: &str,
+        name: &str,
+        partition_key: Option<&str>,
+    ) -> CookieResult<bool> {
+        let key = CookieKey {
+            partition: partition_key.unwrap_or_default().to_string(),
+            domain: normalize_domain(domain),
+            path: path.to_string(),
+            name: name.to_string(),
+        };
+        let removed = self.cookies.write().remove(&key).is_some();
+        if removed {
+            self.autosave()?;
+        }
+        Ok(removed)
+    }
+
+    /// Remove every cookie with this domain and name (all paths and partitions)
+    pub fn remove_named(&self, domain: &str, name: &str) -> CookieResult<usize> {
+        let domain = normalize_domain(domain);
+        self.remove_where(|c| c.domain == domain && c.name == name)
+    }
+
+    /// Cookies to send with a request to `url`, in RFC 6265 order
+    pub fn cookies_for_url(&self, url: &str, context: &CookieContext) -> CookieResult<Vec<Cookie>> {
+        let request = url::Url::parse(url).map_err(|e| CookieError::InvalidUrl(e.to_string()))?;
+        let host = request
+            .host_str()
+            .ok_or_else(|| CookieError::InvalidUrl(format!("'{}' has no host", url)))?;
+        let secure_request = matches!(request.scheme(), "https" | "wss")
+            || matches!(host, "localhost" | "127.0.0.1" | "[::1]");
+
+        let top_level = match context.top_level_url {
+            Some(top) => {
+                url::Url::parse(top).map_err(|e| CookieError::InvalidUrl(e.to_string()))?
+            }
+            None => request.clone(),
+        };
+        let top_level_site = site_for_url(&top_level);
+        let cross_site = top_level_site != site_for_url(&request);
+
+        let now = unix_now();
+        let mut matched: Vec<Cookie> = self
+            .cookies
+            .read()
+            .values()
+            .filter(|c| !c.is_expired_at(now))
+            .filter(|c| c.domain_matches(host) && c.path_matches(request.path()))
+            .filter(|c| secure_request || !c.secure)
+            .filter(|c| {
+                c.partition_key.is_none() || c.partition_key.as_deref() == top_level_site.as_deref()
+            })
+            .filter(|c| {
+                !cross_site
+                    || match c.same_site {
+                        SameSite::None => true,
+                        SameSite::Strict => false,
+                        SameSite::Lax | SameSite::Unspecified => context.top_level_navigation,
+                    }
+            })
+            .cloned()
+            .collect();
+
+        matched.sort_by(|a, b| {
+            b.path
+                .len()
+                .cmp(&a.path.len())
+                .then(a.created.cmp(&b.created))
+        });
+        Ok(matched)
+    }
+
+    /// `Cookie` request header value for `url`, if any cookie applies
+    pub fn cookie_header(
+        &self,
+        url: &str,
+        context: &CookieContext,
+    ) -> CookieResult<Option<String>> {
+        let cookies = self.cookies_for_url(url, context)?;
+        if cookies.is_empty() {
+            return Ok(None);
+        }
+        Ok(Some(
+            cookies
+                .iter()
+                .map(|c| format!("{}={}", c.name, c.value))
+                .collect::<Vec<_>>()
+                .join("; "),
+        ))
+    }
+
+    /// All unexpired cookies
+    pub fn all(&self) -> Vec<Cookie> {
+        let now = unix_now();
+        self.cookies
+            .read()
+            .values()
+            .filter(|c| !c.is_expired_at(now))
+            .cloned()
+            .collect()
+    }
+
+    /// Number of stored cookies (including not yet purged expired ones)
+    pub fn len(&self) -> usize {
+        self.cookies.read().len()
+    }
+
+    /// Whether the jar is empty
+    pub fn is_empty(&self) -> bool {
+        self.cookies.read().is_empty()
+    }
+
+    /// Remove all cookies
+    pub fn clear(&self) -> CookieResult<()> {
+        self.cookies.write().clear();
+        self.autosave()
+    }
+
+    /// Remove session cookies (what a browser does on exit)
+    pub fn clear_session_cookies(&self) -> CookieResult<usize> {
+        self.remove_where(Cookie::is_session)
+    }
+
+    /// Drop expired cookies
+    pub fn purge_expired(&self) -> CookieResult<usize> {
+        let now = unix_now();
+        self.remove_where(|c| c.is_expired_at(now))
+    }
+
+    /// Replace the unpartitioned cookies selected by `scope` with a snapshot
+    /// read from a browser engine
+    ///
+    /// Engines do not report whether a cookie is host-only, so a cookie the
+    /// jar already knows keeps its flag and creation time. Invalid or expired
+    /// snapshot entries are skipped.
+    pub fn replace_from_engine(
+        &self,
+        scope: impl Fn(&Cookie) -> bool,
+        snapshot: Vec<Cookie>,
+    ) -> CookieResult<()> {
+        let now = unix_now();
+        {
+            let mut cookies = self.cookies.write();
+            let previous: BTreeMap<CookieKey, Cookie> = cookies
+                .iter()
+                .filter(|(_, c)| c.partition_key.is_none() && scope(c))
+                .map(|(k, c)| (k.clone(), c.clone()))
+                .collect();
+            cookies.retain(|k, _| !previous.contains_key(k));
+
+            for mut cookie in snapshot {
+                if cookie.partition_key.is_some()
+                    || cookie.is_expired_at(now)
+                    || cookie.validate().is_err()
+                {
+                    continue;
+                }
+                let key = CookieKey::of(&cookie);
+                if let Some(known) = previous.get(&key) {
+                    cookie.host_only = known.host_only;
+                    cookie.created = known.created;
+                }
+                cookies.insert(key, cookie);
+            }
+        }
+        self.autosave()
+    }
+
+    /// Write the jar to its backing file (no-op for in-memory jars)
+    pub fn save(&self) -> CookieResult<()> {
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+        let now = unix_now();
+        let cookies = self
+            .cookies
+            .read()
+            .values()
+            .filter(|c| !c.is_expired_at(now) && (self.persist_session || !c.is_session()))
+            .cloned()
+            .collect();
+        let data = serde_json::to_vec_pretty(&JarFile {
+            version: JAR_VERSION,
+            cookies,
+        })?;
+
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        // Write to a temporary file first so a crash never truncates the jar
+        let tmp = path.with_extension("json.tmp");
+        std::fs::write(&tmp, data)?;
+        std::fs::rename(&tmp, path)?;
+        Ok(())
+    }
+
+    // ========== Netscape cookies.txt ==========
+
+    /// Import cookies from Netscape `cookies.txt` content, returning how many were stored
+    pub fn import_netscape(&self, content: &str) -> CookieResult<usize> {
+        let now = unix_now();
+        let imported = parse_netscape(content)?;
+        let count = {
+            let mut cookies = self.cookies.write();
+            let mut count = 0;
+            for cookie in imported.into_iter().filter(|c| !c.is_expired_at(now)) {
+                cookies.insert(CookieKey::of(&cookie), cookie);
+                count += 1;
+            }
+            count
+        };
+        self.autosave()?;
+        Ok(count)
+    }
+
+    /// Export unexpired, unpartitioned cookies as Netscape `cookies.txt`
+    ///
+    /// Partitioned cookies are skipped because the format cannot express
+    /// their partition, and importing them unpartitioned would leak them
+    /// across top-level sites.
+    pub fn export_netscape(&self) -> String {
+        let cookies: Vec<Cookie> = self
+            .all()
+            .into_iter()
+            .filter(|c| c.partition_key.is_none())
+            .collect();
+        to_netscape(&cookies)
+    }
+
+    /// Import a Netscape `cookies.txt` file
+    pub fn import_netscape_file(&self, path: impl AsRef<Path>) -> CookieResult<usize> {
+        self.import_netscape(&std::fs::read_to_string(path)?)
+    }
+
+    /// Export to a Netscape `cookies.txt` file
+    pub fn export_netscape_file(&self, path: impl AsRef<Path>) -> CookieResult<()> {
+        std::fs::write(path, self.export_netscape())?;
+        Ok(())
+    }
+
+    // ========== Internal ==========
+
+    fn remove_where(&self, predicate: impl Fn(&Cookie) -> bool) -> CookieResult<usize> {
+        let removed = {
+            let mut cookies = self.cookies.write();
+            let before = cookies.len();
+            cookies.retain(|_, c| !predicate(c));
+            before - cookies.len()
+        };
+        if removed > 0 {
+            self.autosave()?;
+        }
+        Ok(removed)
+    }
+
+    fn autosave(&self) -> CookieResult<()> {
+        if self.path.is_some() {
+            self.save()?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,71 @@
+//! Persistent Cookie Jar
+//!
+//! A cookie store shared by WebView backends and the `chrome.cookies`
+//! extension API, persisted per profile data directory.
+//!
+//! ## Features
+//! - Expiry, domain and path matching (RFC 6265)
+//! - SameSite enforcement and partitioned (CHIPS) cookies
+//! - JSON persistence with atomic writes
+//! - Netscape `cookies.txt` import/export
+//! - Seeding and saving a wry WebView's cookie store (`wry-builder` feature)
+//!
+//! ## Usage
+//!
+//! ```rust,ignore
+//! use std::sync::Arc;
+//! use auroraview_core::cookies::{Cookie, CookieContext, CookieJar};
+//!
+//! let jar = Arc::new(CookieJar::for_profile(&data_dir)?);
+//! jar.set(Cookie::new("sid", "abc", "tools.example.com").expires_at(expiry))?;
+//!
+//! let header = jar.cookie_header("https://tools.example.com/", &CookieContext::default())?;
+//!
+//! // chrome.cookies sees the same cookies
+//! let router = plugins::create_router_with_extensions(
+//!     ExtensionsPlugin::new().with_cookie_store(jar.clone()),
+//! );
+//! ```
+
+mod chrome;
+mod cookie;
+mod jar;
+mod netscape;
+#[cfg(feature = "wry-builder")]
+mod webview;
+
+pub use cookie::{site_for_url, Cookie, CookieContext, SameSite};
+pub use jar::{CookieJar, COOKIE_JAR_FILE};
+pub use netscape::{parse_netscape, to_netscape};
+
+/// Result type for cookie operations
+pub type CookieResult<T> = Result<T, CookieError>;
+
+/// Error type for cookie operations
+#[derive(Debug, Clone, thiserror::Error)]
+pub enum CookieError {
+    /// Cookie attributes are invalid
+    #[error("Invalid cookie: {0}")]
+    InvalidCookie(String),
+    /// URL could not be parsed
+    #[error("Invalid URL: {0}")]
+    InvalidUrl(String),
+    /// A cookies.txt line could not be parsed
+    #[error("Invalid cookies.txt line {line}: {message}")]
+    Parse { line: usize, message: String },
+    /// Reading or writing the jar failed
+    #[error("Cookie storage error: {0}")]
+    Storage(String),
+}
+
+impl From<std::io::Error> for CookieError {
+    fn from(e: std::io::Error) -> Self {
+        Self::Storage(e.to_string())
+    }
+}
+
+impl From<serde_json::Error> for CookieError {
+    fn from(e: serde_json::Error) -> Self {
+        Self::Storage(e.to_string())
+    }
+}
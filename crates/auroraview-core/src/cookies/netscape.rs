@@ -0,0 +1,109 @@
+//! Netscape `cookies.txt` format
+//!
+//! One cookie per line, seven tab-separated fields:
+//!
+//! ```text
+//! domain  include_subdomains  path  secure  expiry  name  value
+//! ```
+//!
+//! `#HttpOnly_` before the domain marks HttpOnly cookies; other lines
+//! starting with `#` are comments. An expiry of `0` denotes a session cookie.
+
+use super::cookie::{normalize_domain, unix_now};
+use super::{Cookie, CookieError, CookieResult, SameSite};
+
+/// Header written at the top of exported files
+const HEADER: &str = "# Netscape HTTP Cookie File\n\
+# This file was generated by AuroraView. Edit at your own risk.\n\n";
+
+/// Prefix marking HttpOnly cookies
+const HTTP_ONLY_PREFIX: &str = "#HttpOnly_";
+
+/// Parse Netscape `cookies.txt` content
+pub fn parse_netscape(content: &str) -> CookieResult<Vec<Cookie>> {
+    let now = unix_now();
+    let mut cookies = Vec::new();
+
+    for (index, raw) in content.lines().enumerate() {
+        let line_no = index + 1;
+        let line = raw.trim_end_matches('\r');
+        let (line, http_only) = match line.strip_prefix(HTTP_ONLY_PREFIX) {
+            Some(rest) => (rest, true),
+            None => (line, false),
+        };
+        if line.trim().is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let error = |message: &str| CookieError::Parse {
+            line: line_no,
+            message: message.to_string(),
+        };
+        let fields: Vec<&str> = line.split('\t').collect();
+        if !(6..=7).contains(&fields.len()) {
+            return Err(error(&format!("expected 7 fields, found {}", fields.len())));
+        }
+
+        let include_subdomains = parse_bool(fields[1]).ok_or_else(|| error("invalid flag"))?;
+        let secure = parse_bool(fields[3]).ok_or_else(|| error("invalid secure flag"))?;
+        let expiry: i64 = fields[4]
+            .trim()
+            .parse()
+            .map_err(|_| error("invalid expiry"))?;
+
+        let cookie = Cookie {
+            name: fields[5].to_string(),
+            value: fields.get(6).copied().unwrap_or_default().to_string(),
+            domain: normalize_domain(fields[0]),
+            host_only: !include_subdomains,
+            path: fields[2].to_string(),
+            secure,
+            http_only,
+            same_site: SameSite::Unspecified,
+            expires: (expiry != 0).then_some(expiry),
+            partition_key: None,
+            created: now,
+        };
+        cookie.validate().map_err(|e| error(&e.to_string()))?;
+        cookies.push(cookie);
+    }
+
+    Ok(cookies)
+}
+
+/// Serialize cookies to Netscape `cookies.txt` content
+pub fn to_netscape(cookies: &[Cookie]) -> String {
+    let mut out = String::from(HEADER);
+    for cookie in cookies {
+        if cookie.http_only {
+            out.push_str(HTTP_ONLY_PREFIX);
+        }
+        out.push_str(&format!(
+            "{}\t{}\t{}\t{}\t{}\t{}\t{}\n",
+            cookie.display_domain(),
+            format_bool(!cookie.host_only),
+            cookie.path,
+            format_bool(cookie.secure),
+            cookie.expires.unwrap_or(0),
+            cookie.name,
+            cookie.value
+        ));
+    }
+    out
+}
+
+fn parse_bool(field: &str) -> Option<bool> {
+    match field.trim().to_ascii_uppercase().as_str() {
+        "TRUE" => Some(true),
+        "FALSE" => Some(false),
+        _ => None,
+    }
+}
+
+fn format_bool(value: bool) -> &'static str {
+    if value {
+        "TRUE"
+    } else {
+        "FALSE"
+    }
+}
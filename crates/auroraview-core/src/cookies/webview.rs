@@ -0,0 +1,165 @@
+//! Sync between the cookie jar and a wry WebView's cookie store
+//!
+//! The jar is the persistent copy; the engine store is seeded from it when a
+//! WebView starts and read back into it before the WebView is destroyed.
+
+use wry::cookie::time::OffsetDateTime;
+use wry::cookie::{Cookie as EngineCookie, SameSite as EngineSameSite};
+
+use super::{Cookie, CookieError, CookieJar, CookieResult, SameSite};
+
+impl CookieJar {
+    /// Copy the jar's unpartitioned cookies into the WebView's cookie store
+    ///
+    /// Cookies the engine rejects are logged and skipped. Returns the number
+    /// of cookies written.
+    pub fn seed_webview(&self, webview: &wry::WebView) -> CookieResult<usize> {
+        let mut seeded = 0;
+        for cookie in self.all() {
+            if cookie.partition_key.is_some() {
+                continue;
+            }
+            match webview.set_cookie(&to_engine(&cookie)) {
+                Ok(()) => seeded += 1,
+                Err(e) => tracing::warn!(
+                    "[CookieJar] Failed to seed cookie {} for {}: {}",
+                    cookie.name,
+                    cookie.domain,
+                    e
+                ),
+            }
+        }
+        Ok(seeded)
+    }
+
+    /// Replace the jar's unpartitioned cookies with the WebView's cookie store
+    ///
+    /// Call before the WebView is destroyed. Returns the number of cookies read.
+    pub fn save_webview(&self, webview: &wry::WebView) -> CookieResult<usize> {
+        let snapshot: Vec<Cookie> = webview
+            .cookies()
+            .map_err(|e| CookieError::Storage(e.to_string()))?
+            .iter()
+            .filter_map(from_engine)
+            .collect();
+        let count = snapshot.len();
+        self.replace_from_engine(|_| true, snapshot)?;
+        Ok(count)
+    }
+
+    /// Store a cookie in the jar and the WebView's cookie store
+    pub fn set_webview_cookie(&self, webview: &wry::WebView, cookie: Cookie) -> CookieResult<()> {
+        let engine = to_engine(&cookie);
+        self.set(cookie)?;
+        webview
+            .set_cookie(&engine)
+            .map_err(|e| CookieError::Storage(e.to_string()))
+    }
+
+    /// Read the cookie `name` the WebView sends to `url`, updating the jar with it
+    pub fn webview_cookie(
+        &self,
+        webview: &wry::WebView,
+        url: &str,
+        name: &str,
+    ) -> CookieResult<Option<Cookie>> {
+        let found = webview
+            .cookies_for_url(url)
+            .map_err(|e| CookieError::Storage(e.to_string()))?
+            .iter()
+            .filter(|c| c.name() == name)
+            .find_map(from_engine);
+        if let Some(cookie) = &found {
+            let (domain, path) = (cookie.domain.clone(), cookie.path.clone());
+            self.replace_from_engine(
+                |c| c.name == name && c.domain == domain && c.path == path,
+                vec![cookie.clone()],
+            )?;
+        }
+        Ok(found)
+    }
+
+    /// Delete the cookies named `name` for `domain` (and `path`, when given)
+    /// from the jar and the WebView
+    pub fn delete_webview_cookie(
+        &self,
+        webview: &wry::WebView,
+        domain: &str,
+        path: Option<&str>,
+        name: &str,
+    ) -> CookieResult<()> {
+        let domain = domain.trim_start_matches('.').to_ascii_lowercase();
+        let cookies = webview
+            .cookies()
+            .map_err(|e| CookieError::Storage(e.to_string()))?;
+        for cookie in cookies.iter().filter(|c| {
+            c.name() == name
+                && path.is_none_or(|p| c.path().unwrap_or("/") == p)
+                && c.domain()
+                    .is_some_and(|d| d.trim_start_matches('.').eq_ignore_ascii_case(&domain))
+        }) {
+            webview
+                .delete_cookie(cookie)
+                .map_err(|e| CookieError::Storage(e.to_string()))?;
+        }
+        match path {
+            Some(path) => self.remove(&domain, path, name, None).map(|_| ()),
+            None => self.remove_named(&domain, name).map(|_| ()),
+        }
+    }
+
+    /// Remove every cookie from the jar and the WebView
+    pub fn clear_webview(&self, webview: &wry::WebView) -> CookieResult<()> {
+        let cookies = webview
+            .cookies()
+            .map_err(|e| CookieError::Storage(e.to_string()))?;
+        for cookie in &cookies {
+            webview
+                .delete_cookie(cookie)
+                .map_err(|e| CookieError::Storage(e.to_string()))?;
+        }
+        self.clear()
+    }
+}
+
+/// Convert a jar cookie to the engine representation
+fn to_engine(cookie: &Cookie) -> EngineCookie<'static> {
+    let mut builder = EngineCookie::build((cookie.name.clone(), cookie.value.clone()))
+        .domain(cookie.display_domain())
+        .path(cookie.path.clone())
+        .secure(cookie.secure)
+        .http_only(cookie.http_only);
+    builder = match cookie.same_site {
+        SameSite::Strict => builder.same_site(EngineSameSite::Strict),
+        SameSite::Lax => builder.same_site(EngineSameSite::Lax),
+        SameSite::None => builder.same_site(EngineSameSite::None),
+        SameSite::Unspecified => builder,
+    };
+    if let Some(expires) = cookie
+        .expires
+        .and_then(|ts| OffsetDateTime::from_unix_timestamp(ts).ok())
+    {
+        builder = builder.expires(expires);
+    }
+    builder.build()
+}
+
+/// Convert an engine cookie to a jar cookie (`None` for partitioned or domainless cookies)
+fn from_engine(cookie: &EngineCookie<'_>) -> Option<Cookie> {
+    if cookie.partitioned() == Some(true) {
+        return None;
+    }
+    let domain = cookie.domain().filter(|d| !d.is_empty())?;
+    let mut converted = Cookie::new(cookie.name(), cookie.value(), domain)
+        .with_path(cookie.path().unwrap_or("/"))
+        .secure(cookie.secure().unwrap_or(false))
+        .http_only(cookie.http_only().unwrap_or(false))
+        .with_same_site(match cookie.same_site() {
+            Some(EngineSameSite::Strict) => SameSite::Strict,
+            Some(EngineSameSite::Lax) => SameSite::Lax,
+            Some(EngineSameSite::None) => SameSite::None,
+            None => SameSite::Unspecified,
+        });
+    converted.expires = cookie.expires_datetime().map(|t| t.unix_timestamp());
+    Some(converted)
+}
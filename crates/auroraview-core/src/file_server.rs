@@ -0,0 +1,496 @@
+//! File serving for custom protocol handlers
+//!
+//! Builds HTTP responses for local files with:
+//!
+//! - Byte ranges (`Range` → `206 Partial Content`), reading only the requested
+//!   bytes so multi-GB media can be seeked without loading the whole file;
+//!   files too large to answer in full are refused (`413`) unless a range is
+//!   requested
+//! - Validators (`ETag`, `Last-Modified`) and conditional requests
+//!   (`If-None-Match`, `If-Modified-Since`, `If-Range` → `304 Not Modified`)
+//! - A `Cache-Control` policy
+//! - Pre-compressed `.br` / `.gz` siblings selected by `Accept-Encoding`
+//!
+//! ```rust,ignore
+//! let options = ServeOptions::default().with_cache_policy(CachePolicy::Immutable(ONE_YEAR));
+//! match serve_file(&path, &request, &options) {
+//!     Ok(response) => response,
+//!     Err(_) => not_found(),
+//! }
+//! ```
+
+use http::{header, Method, Request, Response, StatusCode};
+use std::borrow::Cow;
+use std::ffi::OsString;
+use std::fs::File;
+use std::io::{self, Read, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::protocol::guess_mime_type;
+
+/// Default maximum number of bytes returned for one range request
+///
+/// Media elements request open-ended ranges (`bytes=0-`) and continue with
+/// the next range when a shorter `206` arrives, so capping the length bounds
+/// memory use per request.
+pub const DEFAULT_MAX_RANGE_LENGTH: u64 = 4 * 1024 * 1024;
+
+/// Default size of the largest file answered in full without a `Range` header
+pub const DEFAULT_MAX_BODY_LENGTH: u64 = 64 * 1024 * 1024;
+
+/// Response type produced by protocol handlers
+pub type FileServerResponse = Response<Cow<'static, [u8]>>;
+
+/// `Cache-Control` policy for served files
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CachePolicy {
+    /// Cache but revalidate every time (`no-cache`); cheap with `ETag`
+    #[default]
+    Revalidate,
+    /// Never store (`no-store`)
+    NoStore,
+    /// Fresh for a duration (`max-age=N`)
+    MaxAge(Duration),
+    /// Fresh for a duration and never revalidated (`max-age=N, immutable`),
+    /// for content-hashed build output
+    Immutable(Duration),
+}
+
+impl CachePolicy {
+    /// Value of the `Cache-Control` header
+    pub fn header_value(&self) -> String {
+        match self {
+            Self::Revalidate => "no-cache".to_string(),
+            Self::NoStore => "no-store".to_string(),
+            Self::MaxAge(age) => format!("max-age={}", age.as_secs()),
+            Self::Immutable(age) => format!("max-age={}, immutable", age.as_secs()),
+        }
+    }
+}
+
+/// Options for [`serve_file`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ServeOptions {
+    /// `Cache-Control` policy
+    pub cache_policy: CachePolicy,
+    /// Serve `<file>.br` / `<file>.gz` when present and accepted
+    pub precompressed: bool,
+    /// Maximum number of bytes returned for one range request
+    pub max_range_length: u64,
+    /// Largest file answered with `200` to a request without `Range`;
+    /// larger files are only served in ranges and otherwise get a `413`
+    pub max_body_length: u64,
+    /// Add `Access-Control-Allow-Origin: *`
+    pub cors: bool,
+}
+
+impl Default for ServeOptions {
+    fn default() -> Self {
+        Self {
+            cache_policy: CachePolicy::default(),
+            precompressed: false,
+            max_range_length: DEFAULT_MAX_RANGE_LENGTH,
+            max_body_length: DEFAULT_MAX_BODY_LENGTH,
+            cors: false,
+        }
+    }
+}
+
+impl ServeOptions {
+    /// Set the cache policy
+    pub fn with_cache_policy(mut self, policy: CachePolicy) -> Self {
+        self.cache_policy = policy;
+        self
+    }
+
+    /// Enable or disable pre-compressed sibling serving
+    pub fn with_precompressed(mut self, precompressed: bool) -> Self {
+        self.precompressed = precompressed;
+        self
+    }
+
+    /// Set the maximum range length (at least one byte)
+    pub fn with_max_range_length(mut self, length: u64) -> Self {
+        self.max_range_length = length.max(1);
+        self
+    }
+
+    /// Set the largest file answered in full without a `Range` header
+    pub fn with_max_body_length(mut self, length: u64) -> Self {
+        self.max_body_length = length;
+        self
+    }
+
+    /// Enable or disable `Access-Control-Allow-Origin: *`
+    pub fn with_cors(mut self, cors: bool) -> Self {
+        self.cors = cors;
+        self
+    }
+}
+
+/// Requested byte range, resolved against the file length
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ByteRange {
+    /// Bytes `start..=end`
+    Partial { start: u64, end: u64 },
+    /// The range lies outside the file (`416`)
+    Unsatisfiable,
+}
+
+/// Parse a single-range `Range` header against a file of `len` bytes
+///
+/// Returns `None` when the header should be ignored (malformed, another unit,
+/// or several ranges), in which case the whole file is served.
+pub fn parse_range(value: &str, len: u64) -> Option<ByteRange> {
+    let (unit, spec) = value.trim().split_once('=')?;
+    if !unit.trim().eq_ignore_ascii_case("bytes") || spec.contains(',') {
+        return None;
+    }
+    let (first, last) = spec.trim().split_once('-')?;
+    let (first, last) = (first.trim(), last.trim());
+
+    if first.is_empty() {
+        // Suffix range: the last N bytes
+        let suffix: u64 = last.parse().ok()?;
+        if suffix == 0 || len == 0 {
+            return Some(ByteRange::Unsatisfiable);
+        }
+        return Some(ByteRange::Partial {
+            start: len.saturating_sub(suffix),
+            end: len - 1,
+        });
+    }
+
+    let start: u64 = first.parse().ok()?;
+    let end = match last {
+        "" => None,
+        last => Some(last.parse::<u64>().ok()?),
+    };
+    if end.is_some_and(|end| end < start) {
+        return None;
+    }
+    if start >= len {
+        return Some(ByteRange::Unsatisfiable);
+    }
+    Some(ByteRange::Partial {
+        start,
+        end: end.map_or(len - 1, |end| end.min(len - 1)),
+    })
+}
+
+/// Serve a file for a `GET` or `HEAD` request
+///
+/// Returns an error when the file cannot be opened or is not a regular file,
+/// leaving the error response (plain text, styled page) to the caller.
+pub fn serve_file<B>(
+    path: &Path,
+    request: &Request<B>,
+    options: &ServeOptions,
+) -> io::Result<FileServerResponse> {
+    let (file_path, encoding) = select_encoding(path, request, options);
+    let mut file = File::open(&file_path)?;
+    let metadata = file.metadata()?;
+    if !metadata.is_file() {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("not a file: {}", file_path.display()),
+        ));
+    }
+
+    let len = metadata.len();
+    let modified = metadata.modified().ok();
+    let etag = entity_tag(len, modified, encoding);
+    let last_modified = modified.map(http_date);
+
+    let mut headers: Vec<(header::HeaderName, String)> = vec![
+        (header::ETAG, etag.clone()),
+        (header::CACHE_CONTROL, options.cache_policy.header_value()),
+    ];
+    if let Some(last_modified) = &last_modified {
+        headers.push((header::LAST_MODIFIED, last_modified.clone()));
+    }
+    if options.precompressed {
+        headers.push((header::VARY, "Accept-Encoding".to_string()));
+    }
+    if options.cors {
+        headers.push((header::ACCESS_CONTROL_ALLOW_ORIGIN, "*".to_string()));
+    }
+
+    if is_not_modified(request, &etag, modified) {
+        return Ok(build(StatusCode::NOT_MODIFIED, headers, Vec::new()));
+    }
+
+    headers.push((header::CONTENT_TYPE, guess_mime_type(path)));
+    match encoding {
+        Some(encoding) => headers.push((header::CONTENT_ENCODING, encoding.to_string())),
+        None => headers.push((header::ACCEPT_RANGES, "bytes".to_string())),
+    }
+
+    let range = match encoding {
+        // Ranges of an encoded representation are not meaningful to media players
+        Some(_) => None,
+        None => requested_range(request, len, &etag, last_modified.as_deref()),
+    };
+    // Never read more than `max_body_length` into memory for one response.
+    // A truncated `200` (or an unrequested `206`) would look like a complete
+    // download, so refuse instead; clients can still fetch ranges.
+    if range.is_none() && len > options.max_body_length {
+        return Ok(build(StatusCode::PAYLOAD_TOO_LARGE, headers, Vec::new()));
+    }
+    let head = request.method() == Method::HEAD;
+
+    match range {
+        Some(ByteRange::Unsatisfiable) => {
+            headers.push((header::CONTENT_RANGE, format!("bytes */{}", len)));
+            Ok(build(
+                StatusCode::RANGE_NOT_SATISFIABLE,
+                headers,
+                Vec::new(),
+            ))
+        }
+        Some(ByteRange::Partial { start, end }) => {
+            let end = end.min(start + options.max_range_length.max(1) - 1);
+            let length = end - start + 1;
+            headers.push((
+                header::CONTENT_RANGE,
+                format!("bytes {}-{}/{}", start, end, len),
+            ));
+            headers.push((header::CONTENT_LENGTH, length.to_string()));
+            let body = if head {
+                Vec::new()
+            } else {
+                read_range(&mut file, start, length)?
+            };
+            Ok(build(StatusCode::PARTIAL_CONTENT, headers, body))
+        }
+        None => {
+            headers.push((header::CONTENT_LENGTH, len.to_string()));
+            let body = if head {
+                Vec::new()
+            } else {
+                let mut body = Vec::with_capacity(len as usize);
+                file.read_to_end(&mut body)?;
+                body
+            };
+            Ok(build(StatusCode::OK, headers, body))
+        }
+    }
+}
+
+/// Pick the pre-compressed sibling to serve, if any
+fn select_encoding<B>(
+    path: &Path,
+    request: &Request<B>,
+    options: &ServeOptions,
+) -> (PathBuf, Option<&'static str>) {
+    if !options.precompressed || request.headers().contains_key(header::RANGE) {
+        return (path.to_path_buf(), None);
+    }
+    let accept = header_str(request, header::ACCEPT_ENCODING).unwrap_or("");
+    for (encoding, extension) in [("br", "br"), ("gzip", "gz")] {
+        if !accepts_encoding(accept, encoding) {
+            continue;
+        }
+        let mut sibling = OsString::from(path.as_os_str());
+        sibling.push(".");
+        sibling.push(extension);
+        let sibling = PathBuf::from(sibling);
+        // Encoded files cannot be split into ranges, so large ones are skipped
+        let fits = sibling
+            .metadata()
+            .is_ok_and(|m| m.is_file() && m.len() <= options.max_body_length);
+        if fits {
+            return (sibling, Some(encoding));
+        }
+    }
+    (path.to_path_buf(), None)
+}
+
+/// Check if an `Accept-Encoding` header accepts a content coding
+fn accepts_encoding(accept: &str, encoding: &str) -> bool {
+    accept.split(',').any(|item| {
+        let mut params = item.split(';');
+        let name = params.next().unwrap_or("").trim();
+        let quality = params
+            .filter_map(|p| p.trim().strip_prefix("q="))
+            .find_map(|q| q.trim().parse::<f32>().ok())
+            .unwrap_or(1.0);
+        (name.eq_ignore_ascii_case(encoding) || name == "*") && quality > 0.0
+    })
+}
+
+/// Strong entity tag from the file size and modification time
+fn entity_tag(len: u64, modified: Option<SystemTime>, encoding: Option<&str>) -> String {
+    let nanos = modified
+        .and_then(|m| m.duration_since(UNIX_EPOCH).ok())
+        .map_or(0, |d| d.as_nanos());
+    match encoding {
+        Some(encoding) => format!("\"{:x}-{:x}-{}\"", len, nanos, encoding),
+        None => format!("\"{:x}-{:x}\"", len, nanos),
+    }
+}
+
+/// Evaluate `If-None-Match` / `If-Modified-Since`
+fn is_not_modified<B>(request: &Request<B>, etag: &str, modified: Option<SystemTime>) -> bool {
+    if !matches!(*request.method(), Method::GET | Method::HEAD) {
+        return false;
+    }
+    // If-None-Match takes precedence over If-Modified-Since
+    if let Some(tags) = header_str(request, header::IF_NONE_MATCH) {
+        return tags
+            .split(',')
+            .map(str::trim)
+            .any(|tag| tag == "*" || weak_tag(tag) == weak_tag(etag));
+    }
+    match (header_str(request, header::IF_MODIFIED_SINCE), modified) {
+        (Some(since), Some(modified)) => parse_http_date(since).is_some_and(|since| {
+            let modified = modified
+                .duration_since(UNIX_EPOCH)
+                .map_or(0, |d| d.as_secs());
+            modified <= since
+        }),
+        _ => false,
+    }
+}
+
+/// The range to serve, honoring `If-Range`
+fn requested_range<B>(
+    request: &Request<B>,
+    len: u64,
+    etag: &str,
+    last_modified: Option<&str>,
+) -> Option<ByteRange> {
+    let range = header_str(request, header::RANGE)?;
+    if let Some(validator) = header_str(request, header::IF_RANGE) {
+        let validator = validator.trim();
+        // Strong comparison only: a weak tag never matches
+        let current = validator == etag || Some(validator) == last_modified;
+        if !current {
+            return None;
+        }
+    }
+    parse_range(range, len)
+}
+
+fn weak_tag(tag: &str) -> &str {
+    tag.strip_prefix("W/").unwrap_or(tag)
+}
+
+fn header_str<B>(request: &Request<B>, name: header::HeaderName) -> Option<&str> {
+    request.headers().get(name).and_then(|v| v.to_str().ok())
+}
+
+fn read_range(file: &mut File, start: u64, length: u64) -> io::Result<Vec<u8>> {
+    file.seek(SeekFrom::Start(start))?;
+    let mut buffer = vec![0; length as usize];
+    file.read_exact(&mut buffer)?;
+    Ok(buffer)
+}
+
+fn build(
+    status: StatusCode,
+    headers: Vec<(header::HeaderName, String)>,
+    body: Vec<u8>,
+) -> FileServerResponse {
+    let mut builder = Response::builder().status(status);
+    for (name, value) in headers {
+        builder = builder.header(name, value);
+    }
+    builder
+        .body(Cow::Owned(body))
+        .unwrap_or_else(|_| Response::new(Cow::Borrowed(b"Internal Server Error" as &[u8])))
+}
+
+const WEEKDAYS: [&str; 7] = ["Thu", "Fri", "Sat", "Sun", "Mon", "Tue", "Wed"];
+const MONTHS: [&str; 12] = [
+    "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+];
+
+/// Format a time as an HTTP date (`Sun, 06 Nov 1994 08:49:37 GMT`)
+pub fn http_date(time: SystemTime) -> String {
+    let secs = time.duration_since(UNIX_EPOCH).map_or(0, |d| d.as_secs());
+    let days = secs / 86_400;
+    let (year, month, day) = civil_from_days(days as i64);
+    format!(
+        "{}, {:02} {} {} {:02}:{:02}:{:02} GMT",
+        // The epoch was a Thursday
+        WEEKDAYS[(days % 7) as usize],
+        day,
+        MONTHS[month as usize - 1],
+        year,
+        secs % 86_400 / 3600,
+        secs % 3600 / 60,
+        secs % 60
+    )
+}
+
+/// Parse an HTTP date (IMF-fixdate) into seconds since the epoch
+pub fn parse_http_date(value: &str) -> Option<u64> {
+    let (_, rest) = value.trim().split_once(", ")?;
+    let parts: Vec<&str> = rest.split(' ').collect();
+    let [day, month, year, time, "GMT"] = parts.as_slice() else {
+        return None;
+    };
+    let day: u32 = day.parse().ok()?;
+    let month = MONTHS.iter().position(|m| m == month)? as u32 + 1;
+    let year: i64 = year.parse().ok()?;
+    let mut hms = time.split(':').map(|p| p.parse::<u64>().ok());
+    let (hours, minutes, seconds) = (hms.next()??, hms.next()??, hms.next()??);
+    if !(1..=31).contains(&day) || hours > 23 || minutes > 59 || seconds > 60 {
+        return None;
+    }
+    let days = u64::try_from(days_from_civil(year, month, day)).ok()?;
+    Some(days * 86_400 + hours * 3600 + minutes * 60 + seconds)
+}
+
+/// Days since the epoch to (year, month, day), proleptic Gregorian
+fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+    let month = (if mp < 10 { mp + 3 } else { mp - 9 }) as u32;
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    (year, month, day)
+}
+
+/// (year, month, day) to days since the epoch, proleptic Gregorian
+fn days_from_civil(year: i64, month: u32, day: u32) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let yoe = year.rem_euclid(400);
+    let month = i64::from(month);
+    let mp = if month > 2 { month - 3 } else { month + 9 };
+    let doy = (153 * mp + 2) / 5 + i64::from(day) - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * 146_097 + doe - 719_468
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_http_date_round_trip() {
+        let time = UNIX_EPOCH + Duration::from_secs(784_111_777);
+        assert_eq!(http_date(time), "Sun, 06 Nov 1994 08:49:37 GMT");
+        assert_eq!(
+            parse_http_date("Sun, 06 Nov 1994 08:49:37 GMT"),
+            Some(784_111_777)
+        );
+        assert_eq!(http_date(UNIX_EPOCH), "Thu, 01 Jan 1970 00:00:00 GMT");
+        assert_eq!(parse_http_date("Sunday, 06-Nov-94 08:49:37 GMT"), None);
+    }
+
+    #[test]
+    fn test_accepts_encoding() {
+        assert!(accepts_encoding("gzip, deflate, br", "br"));
+        assert!(accepts_encoding("*", "gzip"));
+        assert!(!accepts_encoding("gzip, br;q=0", "br"));
+        assert!(!accepts_encoding("", "gzip"));
+    }
+}
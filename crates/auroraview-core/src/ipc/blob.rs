@@ -0,0 +1,543 @@
+//! Binary blob store for large IPC payloads
+//!
+//! JSON IPC is a poor fit for image previews, mesh buffers and other large
+//! binary payloads: they have to be base64 encoded, copied into a JSON string
+//! and parsed again on the other side. The blob store keeps such payloads on
+//! the Rust side and exposes them through the `auroraview` custom protocol:
+//!
+//! - Rust → JS: [`BlobStore::insert`] returns a [`BlobHandle`] whose `url`
+//!   JavaScript fetches as an `ArrayBuffer`
+//! - JS → Rust: JavaScript `POST`s a `Blob` to the blob URL and receives the
+//!   handle as JSON; Rust reads it with [`BlobStore::get`] or [`BlobStore::take`]
+//!
+//! Blobs live until they are released, until their [`BlobLifetime`] ends, or
+//! until the store is cleared when the owning WebView is destroyed.
+//!
+//! ## URL format
+//!
+//! | Method   | URL                      | Effect                               |
+//! |----------|--------------------------|--------------------------------------|
+//! | `GET`    | `.../type:blob/{id}`     | Fetch the bytes                      |
+//! | `POST`   | `.../type:blob/`         | Store the body, returns a handle     |
+//! | `DELETE` | `.../type:blob/{id}`     | Release the blob                     |
+
+use http::{header, Method, Request, Response, StatusCode};
+use parking_lot::RwLock;
+use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
+use std::collections::hash_map::RandomState;
+use std::collections::HashMap;
+use std::hash::BuildHasher;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use thiserror::Error;
+
+use crate::protocol::{extract_protocol_path, PROTOCOL_TYPE_BLOB};
+
+/// Default limit for the total size of all blobs in one store (1 GiB)
+pub const DEFAULT_MAX_BLOB_BYTES: u64 = 1024 * 1024 * 1024;
+
+/// Default MIME type of blobs
+pub const DEFAULT_BLOB_MIME: &str = "application/octet-stream";
+
+/// Response type produced by the blob protocol handler
+pub type BlobResponse = Response<Cow<'static, [u8]>>;
+
+/// Blob store errors
+#[derive(Debug, Error, Clone, PartialEq, Eq)]
+pub enum BlobError {
+    /// Storing the blob would exceed the store limit
+    #[error(
+        "blob of {size} bytes exceeds the store limit ({available} of {limit} bytes available)"
+    )]
+    TooLarge {
+        /// Size of the rejected blob
+        size: u64,
+        /// Bytes still available
+        available: u64,
+        /// Total store limit
+        limit: u64,
+    },
+}
+
+/// How long a blob stays in the store
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum BlobLifetime {
+    /// Until released or the store is cleared
+    #[default]
+    Manual,
+    /// Removed after the first fetch
+    OneShot,
+    /// Removed after a duration
+    Ttl(Duration),
+}
+
+impl BlobLifetime {
+    /// Parse the `lifetime` query parameter of an upload
+    ///
+    /// Accepts `manual`, `one-shot` and `ttl:<seconds>` (or `ttl=<seconds>`).
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "manual" => Some(Self::Manual),
+            "one-shot" | "oneshot" => Some(Self::OneShot),
+            _ => value
+                .strip_prefix("ttl:")
+                .or_else(|| value.strip_prefix("ttl="))
+                .and_then(|secs| secs.parse().ok())
+                .map(|secs| Self::Ttl(Duration::from_secs(secs))),
+        }
+    }
+}
+
+/// Reference to a stored blob, sent over JSON IPC instead of the bytes
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BlobHandle {
+    /// Blob ID
+    pub id: String,
+    /// Size in bytes
+    pub size: u64,
+    /// MIME type
+    pub mime: String,
+    /// URL JavaScript fetches the bytes from
+    pub url: String,
+}
+
+/// A stored blob
+#[derive(Debug, Clone)]
+pub struct Blob {
+    /// Blob bytes
+    pub data: Arc<Vec<u8>>,
+    /// MIME type
+    pub mime: String,
+}
+
+impl Blob {
+    /// Take the bytes, copying only if the blob is still shared
+    pub fn into_bytes(self) -> Vec<u8> {
+        Arc::try_unwrap(self.data).unwrap_or_else(|shared| shared.as_ref().clone())
+    }
+}
+
+#[derive(Debug)]
+struct Entry {
+    blob: Blob,
+    lifetime: BlobLifetime,
+    expires_at: Option<Instant>,
+}
+
+impl Entry {
+    fn is_expired(&self, now: Instant) -> bool {
+        self.expires_at.is_some_and(|at| now >= at)
+    }
+}
+
+#[derive(Debug, Default)]
+struct Inner {
+    blobs: HashMap<String, Entry>,
+    total_bytes: u64,
+}
+
+impl Inner {
+    fn remove(&mut self, id: &str) -> Option<Entry> {
+        let entry = self.blobs.remove(id)?;
+        self.total_bytes -= entry.blob.data.len() as u64;
+        Some(entry)
+    }
+
+    fn purge_expired(&mut self, now: Instant) -> usize {
+        let expired: Vec<String> = self
+            .blobs
+            .iter()
+            .filter(|(_, entry)| entry.is_expired(now))
+            .map(|(id, _)| id.clone())
+            .collect();
+        for id in &expired {
+            self.remove(id);
+        }
+        expired.len()
+    }
+}
+
+/// Thread-safe store of binary payloads addressed by unguessable IDs
+///
+/// Cloning is cheap and shares the same store, so one clone can live in the
+/// protocol handler while another is used from Python or plugin threads.
+#[derive(Debug, Clone)]
+pub struct BlobStore {
+    inner: Arc<RwLock<Inner>>,
+    counter: Arc<AtomicU64>,
+    hasher: RandomState,
+    max_bytes: u64,
+}
+
+impl Default for BlobStore {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl BlobStore {
+    /// Create an empty store with [`DEFAULT_MAX_BLOB_BYTES`]
+    pub fn new() -> Self {
+        Self {
+            inner: Arc::new(RwLock::new(Inner::default())),
+            counter: Arc::new(AtomicU64::new(0)),
+            hasher: RandomState::new(),
+            max_bytes: DEFAULT_MAX_BLOB_BYTES,
+        }
+    }
+
+    /// Set the limit for the total size of all blobs
+    pub fn with_max_bytes(mut self, max_bytes: u64) -> Self {
+        self.max_bytes = max_bytes;
+        self
+    }
+
+    /// Limit for the total size of all blobs
+    pub fn max_bytes(&self) -> u64 {
+        self.max_bytes
+    }
+
+    /// Store a blob that lives until released
+    pub fn insert(
+        &self,
+        data: impl Into<Vec<u8>>,
+        mime: impl Into<String>,
+    ) -> Result<BlobHandle, BlobError> {
+        self.insert_with_lifetime(data, mime, BlobLifetime::Manual)
+    }
+
+    /// Store a blob with a lifetime
+    pub fn insert_with_lifetime(
+        &self,
+        data: impl Into<Vec<u8>>,
+        mime: impl Into<String>,
+        lifetime: BlobLifetime,
+    ) -> Result<BlobHandle, BlobError> {
+        let data = data.into();
+        let size = data.len() as u64;
+        let mut mime = mime.into();
+        if mime.is_empty() {
+            mime = DEFAULT_BLOB_MIME.to_string();
+        }
+
+        let now = Instant::now();
+        let mut inner = self.inner.write();
+        inner.purge_expired(now);
+
+        let available = self.max_bytes.saturating_sub(inner.total_bytes);
+        if size > available {
+            return Err(BlobError::TooLarge {
+                size,
+                available,
+                limit: self.max_bytes,
+            });
+        }
+
+        let id = self.next_id();
+        let expires_at = match lifetime {
+            BlobLifetime::Ttl(ttl) => Some(now + ttl),
+            _ => None,
+        };
+        inner.total_bytes += size;
+        inner.blobs.insert(
+            id.clone(),
+            Entry {
+                blob: Blob {
+                    data: Arc::new(data),
+                    mime: mime.clone(),
+                },
+                lifetime,
+                expires_at,
+            },
+        );
+
+        tracing::debug!("[BlobStore] Stored blob {} ({} bytes, {})", id, size, mime);
+        Ok(BlobHandle {
+            url: blob_url(&id),
+            id,
+            size,
+            mime,
+        })
+    }
+
+    /// Get a blob without consuming it
+    ///
+    /// One-shot blobs stay in the store; use [`take`](Self::take) to consume them.
+    pub fn get(&self, id: &str) -> Option<Blob> {
+        let inner = self.inner.read();
+        let entry = inner.blobs.get(id)?;
+        (!entry.is_expired(Instant::now())).then(|| entry.blob.clone())
+    }
+
+    /// Remove a blob and return it
+    pub fn take(&self, id: &str) -> Option<Blob> {
+        let entry = self.inner.write().remove(id)?;
+        (!entry.is_expired(Instant::now())).then_some(entry.blob)
+    }
+
+    /// Fetch a blob for delivery, removing it if it is one-shot
+    pub fn fetch(&self, id: &str) -> Option<Blob> {
+        let mut inner = self.inner.write();
+        let now = Instant::now();
+        let entry = inner.blobs.get(id)?;
+        if entry.is_expired(now) {
+            inner.remove(id);
+            return None;
+        }
+        if entry.lifetime == BlobLifetime::OneShot {
+            return inner.remove(id).map(|entry| entry.blob);
+        }
+        Some(entry.blob.clone())
+    }
+
+    /// Handle for a stored blob
+    pub fn handle(&self, id: &str) -> Option<BlobHandle> {
+        self.get(id).map(|blob| BlobHandle {
+            id: id.to_string(),
+            size: blob.data.len() as u64,
+            mime: blob.mime,
+            url: blob_url(id),
+        })
+    }
+
+    /// Release a blob; returns whether it existed
+    pub fn release(&self, id: &str) -> bool {
+        let released = self.inner.write().remove(id).is_some();
+        if released {
+            tracing::debug!("[BlobStore] Released blob {}", id);
+        }
+        released
+    }
+
+    /// Check whether a blob is stored
+    pub fn contains(&self, id: &str) -> bool {
+        self.get(id).is_some()
+    }
+
+    /// Remove expired blobs; returns how many were removed
+    pub fn purge_expired(&self) -> usize {
+        self.inner.write().purge_expired(Instant::now())
+    }
+
+    /// Remove all blobs (called when the owning WebView is destroyed)
+    pub fn clear(&self) {
+        let mut inner = self.inner.write();
+        if !inner.blobs.is_empty() {
+            tracing::debug!("[BlobStore] Clearing {} blobs", inner.blobs.len());
+        }
+        inner.blobs.clear();
+        inner.total_bytes = 0;
+    }
+
+    /// Number of stored blobs
+    pub fn len(&self) -> usize {
+        self.inner.read().blobs.len()
+    }
+
+    /// Check if the store is empty
+    pub fn is_empty(&self) -> bool {
+        self.inner.read().blobs.is_empty()
+    }
+
+    /// Total size of all stored blobs
+    pub fn total_bytes(&self) -> u64 {
+        self.inner.read().total_bytes
+    }
+
+    /// Handle a blob protocol request
+    ///
+    /// `GET` fetches a blob (consuming a one-shot blob), `HEAD` only reports
+    /// its size and type, `POST` stores the request body (MIME type
+    /// from `Content-Type`, lifetime from the `lifetime` query parameter) and
+    /// answers `201` with the [`BlobHandle`] as JSON, `DELETE` releases a blob
+    /// and `OPTIONS` answers CORS preflights.
+    pub fn handle_request(&self, request: Request<Vec<u8>>) -> BlobResponse {
+        let uri = request.uri().to_string();
+        let Some(id) = blob_id_from_uri(&uri) else {
+            return plain_response(StatusCode::NOT_FOUND, "Not Found");
+        };
+
+        match *request.method() {
+            Method::GET | Method::HEAD => {
+                let head = request.method() == Method::HEAD;
+                // HEAD must not consume a one-shot blob
+                let blob = if head { self.get(&id) } else { self.fetch(&id) };
+                let Some(blob) = blob else {
+                    return plain_response(StatusCode::NOT_FOUND, "Blob not found");
+                };
+                let size = blob.data.len();
+                let mime = blob.mime.clone();
+                let body = if head {
+                    Cow::Borrowed(&[][..])
+                } else {
+                    Cow::Owned(blob.into_bytes())
+                };
+                cors(Response::builder())
+                    .status(StatusCode::OK)
+                    .header(header::CONTENT_TYPE, mime)
+                    .header(header::CONTENT_LENGTH, size)
+                    .header(header::CACHE_CONTROL, "no-store")
+                    .body(body)
+                    .unwrap_or_else(|_| internal_error())
+            }
+            Method::POST => {
+                if !id.is_empty() {
+                    return plain_response(StatusCode::METHOD_NOT_ALLOWED, "Method Not Allowed");
+                }
+                let mime = request
+                    .headers()
+                    .get(header::CONTENT_TYPE)
+                    .and_then(|value| value.to_str().ok())
+                    .unwrap_or(DEFAULT_BLOB_MIME)
+                    .to_string();
+                let lifetime = match query_param(&uri, "lifetime") {
+                    Some(value) => match BlobLifetime::parse(&value) {
+                        Some(lifetime) => lifetime,
+                        None => return plain_response(StatusCode::BAD_REQUEST, "Invalid lifetime"),
+                    },
+                    None => BlobLifetime::Manual,
+                };
+                match self.insert_with_lifetime(request.into_body(), mime, lifetime) {
+                    Ok(handle) => json_response(StatusCode::CREATED, &handle),
+                    Err(e) => {
+                        tracing::warn!("[BlobStore] Rejected upload: {}", e);
+                        plain_response(StatusCode::PAYLOAD_TOO_LARGE, &e.to_string())
+                    }
+                }
+            }
+            Method::DELETE => {
+                if self.release(&id) {
+                    cors(Response::builder())
+                        .status(StatusCode::NO_CONTENT)
+                        .body(Cow::Borrowed(&[][..]))
+                        .unwrap_or_else(|_| internal_error())
+                } else {
+                    plain_response(StatusCode::NOT_FOUND, "Blob not found")
+                }
+            }
+            Method::OPTIONS => cors(Response::builder())
+                .status(StatusCode::NO_CONTENT)
+                .header(
+                    header::ACCESS_CONTROL_ALLOW_METHODS,
+                    "GET, HEAD, POST, DELETE, OPTIONS",
+                )
+                .header(header::ACCESS_CONTROL_ALLOW_HEADERS, "Content-Type")
+                .body(Cow::Borrowed(&[][..]))
+                .unwrap_or_else(|_| internal_error()),
+            _ => plain_response(StatusCode::METHOD_NOT_ALLOWED, "Method Not Allowed"),
+        }
+    }
+
+    fn next_id(&self) -> String {
+        let n = self.counter.fetch_add(1, Ordering::Relaxed);
+        let random = self.hasher.hash_one((n, Instant::now()));
+        format!("{:016x}{:x}", random, n)
+    }
+}
+
+/// URL a blob is fetched from
+///
+/// Uses `https://auroraview.localhost/type:blob/{id}` on Windows, where wry
+/// maps custom protocols to HTTPS, and `auroraview://localhost/type:blob/{id}`
+/// elsewhere.
+pub fn blob_url(id: &str) -> String {
+    #[cfg(target_os = "windows")]
+    {
+        format!(
+            "https://{}/{}/{}",
+            crate::protocol::AURORAVIEW_HOST,
+            PROTOCOL_TYPE_BLOB,
+            id
+        )
+    }
+    #[cfg(not(target_os = "windows"))]
+    {
+        format!("auroraview://localhost/{}/{}", PROTOCOL_TYPE_BLOB, id)
+    }
+}
+
+/// Check whether a protocol URI addresses the blob store
+pub fn is_blob_uri(uri: &str) -> bool {
+    blob_id_from_uri(uri).is_some()
+}
+
+/// Extract the blob ID from a protocol URI
+///
+/// Returns an empty ID for the upload URL (`.../type:blob/`).
+pub fn blob_id_from_uri(uri: &str) -> Option<String> {
+    let path = extract_protocol_path(uri, "auroraview")?;
+    let rest = path.strip_prefix(PROTOCOL_TYPE_BLOB)?;
+    let rest = match rest.strip_prefix('/') {
+        Some(rest) => rest,
+        None if rest.is_empty() || rest.starts_with('?') => rest,
+        None => return None,
+    };
+    let end = rest.find(['?', '#', '/']).unwrap_or(rest.len());
+    Some(rest[..end].to_string())
+}
+
+fn query_param(uri: &str, name: &str) -> Option<String> {
+    let query = uri.split_once('?')?.1;
+    let query = query.split('#').next().unwrap_or(query);
+    query.split('&').find_map(|pair| {
+        let (key, value) = pair.split_once('=').unwrap_or((pair, ""));
+        (key == name).then(|| value.to_string())
+    })
+}
+
+fn cors(builder: http::response::Builder) -> http::response::Builder {
+    builder.header(header::ACCESS_CONTROL_ALLOW_ORIGIN, "*")
+}
+
+fn plain_response(status: StatusCode, message: &str) -> BlobResponse {
+    cors(Response::builder())
+        .status(status)
+        .header(header::CONTENT_TYPE, "text/plain")
+        .body(Cow::Owned(message.as_bytes().to_vec()))
+        .unwrap_or_else(|_| internal_error())
+}
+
+fn json_response<T: Serialize>(status: StatusCode, value: &T) -> BlobResponse {
+    match serde_json::to_vec(value) {
+        Ok(body) => cors(Response::builder())
+            .status(status)
+            .header(header::CONTENT_TYPE, "application/json")
+            .body(Cow::Owned(body))
+            .unwrap_or_else(|_| internal_error()),
+        Err(_) => internal_error(),
+    }
+}
+
+fn internal_error() -> BlobResponse {
+    let mut response = Response::new(Cow::Borrowed(b"Internal Server Error" as &[u8]));
+    *response.status_mut() = StatusCode::INTERNAL_SERVER_ERROR;
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ids_are_unique() {
+        let store = BlobStore::new();
+        let a = store.insert(vec![1], "").unwrap();
+        let b = store.insert(vec![2], "").unwrap();
+        assert_ne!(a.id, b.id);
+        assert_eq!(a.mime, DEFAULT_BLOB_MIME);
+    }
+
+    #[test]
+    fn test_query_param() {
+        assert_eq!(
+            query_param(
+                "auroraview://localhost/type:blob/?lifetime=ttl:5",
+                "lifetime"
+            ),
+            Some("ttl:5".to_string())
+        );
+        assert_eq!(query_param("auroraview://localhost/type:blob/", "x"), None);
+    }
+}
@@ -0,0 +1,729 @@
+//! Flow-controlled streaming channels
+//!
+//! Plain channel messages are pushed into the WebView message queue as fast
+//! as the producer emits them, so a page that falls behind (or a queue that
+//! fills up) silently loses data. A [`FlowChannel`] adds credit-based flow
+//! control on top of the `__channel_*__` events of `channel_bridge.js`:
+//!
+//! - The open frame grants JavaScript an initial window of credits
+//! - Every delivered message consumes one credit
+//! - JavaScript returns credits with `__channel_ack__` once its handlers
+//!   have consumed messages
+//! - Without credits, messages wait in a bounded per-channel buffer; when the
+//!   buffer is full the [`OverflowPolicy`] decides what happens
+//! - `__channel_cancel__` from JavaScript cancels the channel and wakes the
+//!   producer, whose next `send` fails with [`ChannelError::Cancelled`]
+//! - Navigating or reloading the page cancels every channel (JavaScript
+//!   sends `__channel_cancel__` on `pagehide`, and the backends call
+//!   [`ChannelRegistry::handle_page_load`])
+//!
+//! ## Protocol
+//!
+//! | Event                 | Direction | Payload                                        |
+//! |-----------------------|-----------|------------------------------------------------|
+//! | `__channel_open__`    | → JS      | `{channel_id, credits, capacity, policy}`      |
+//! | `__channel_message__` | → JS      | `{channel_id, seq, data}`                      |
+//! | `__channel_close__`   | → JS      | `{channel_id}`                                 |
+//! | `__channel_ack__`     | → Rust    | `{channel_id, credits}`                        |
+//! | `__channel_cancel__`  | → Rust    | `{channel_id, reason?}`                        |
+//!
+//! Sequence numbers are assigned on `send`, so messages dropped or coalesced
+//! while buffered show up as gaps on the JavaScript side.
+
+use parking_lot::{Condvar, Mutex, RwLock};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::collections::{HashMap, VecDeque};
+use std::fmt;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use thiserror::Error;
+
+/// Event announcing a channel to JavaScript
+pub const CHANNEL_OPEN_EVENT: &str = "__channel_open__";
+
+/// Event carrying one channel message to JavaScript
+pub const CHANNEL_MESSAGE_EVENT: &str = "__channel_message__";
+
+/// Event telling JavaScript that no more messages follow
+pub const CHANNEL_CLOSE_EVENT: &str = "__channel_close__";
+
+/// Event returning consumed credits from JavaScript
+pub const CHANNEL_ACK_EVENT: &str = "__channel_ack__";
+
+/// Event cancelling a channel from JavaScript
+pub const CHANNEL_CANCEL_EVENT: &str = "__channel_cancel__";
+
+/// Default number of messages JavaScript may have in flight
+pub const DEFAULT_CHANNEL_CREDITS: u32 = 16;
+
+/// Default number of messages buffered while JavaScript has no credits
+pub const DEFAULT_CHANNEL_CAPACITY: usize = 256;
+
+/// Channel errors
+#[derive(Debug, Error, Clone, PartialEq, Eq)]
+pub enum ChannelError {
+    /// The producer closed the channel
+    #[error("channel '{0}' is closed")]
+    Closed(String),
+    /// JavaScript cancelled the channel
+    #[error("channel '{id}' was cancelled{}", reason_suffix(.reason))]
+    Cancelled {
+        /// Channel ID
+        id: String,
+        /// Reason given by JavaScript
+        reason: Option<String>,
+    },
+    /// A blocking send did not get buffer space in time
+    #[error("timed out waiting for channel '{0}' to drain")]
+    Timeout(String),
+}
+
+fn reason_suffix(reason: &Option<String>) -> String {
+    reason
+        .as_ref()
+        .map(|reason| format!(": {}", reason))
+        .unwrap_or_default()
+}
+
+/// What happens to a send when the channel buffer is full
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OverflowPolicy {
+    /// Block the producer until JavaScript acknowledges messages
+    ///
+    /// Credits are returned through IPC handled on the UI thread, so only
+    /// block from worker threads.
+    #[default]
+    Block,
+    /// Discard the oldest buffered message (log tails)
+    DropOldest,
+    /// Replace the newest buffered message (progress, latest value wins)
+    Coalesce,
+}
+
+impl OverflowPolicy {
+    /// Parse a policy name (`block`, `drop_oldest`/`drop-oldest`, `coalesce`)
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "block" => Some(Self::Block),
+            "drop_oldest" | "drop-oldest" => Some(Self::DropOldest),
+            "coalesce" => Some(Self::Coalesce),
+            _ => None,
+        }
+    }
+
+    /// Policy name as sent to JavaScript
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Block => "block",
+            Self::DropOldest => "drop_oldest",
+            Self::Coalesce => "coalesce",
+        }
+    }
+}
+
+/// Flow control settings of a channel
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChannelConfig {
+    /// Initial credit window granted to JavaScript
+    pub credits: u32,
+    /// Messages buffered while JavaScript has no credits
+    pub capacity: usize,
+    /// Behavior when the buffer is full
+    pub policy: OverflowPolicy,
+    /// Maximum time a blocking send waits (None = until drained or cancelled)
+    pub block_timeout: Option<Duration>,
+}
+
+impl Default for ChannelConfig {
+    fn default() -> Self {
+        Self {
+            credits: DEFAULT_CHANNEL_CREDITS,
+            capacity: DEFAULT_CHANNEL_CAPACITY,
+            policy: OverflowPolicy::default(),
+            block_timeout: None,
+        }
+    }
+}
+
+impl ChannelConfig {
+    /// Set the initial credit window (at least 1)
+    pub fn with_credits(mut self, credits: u32) -> Self {
+        self.credits = credits.max(1);
+        self
+    }
+
+    /// Set the buffer capacity (at least 1)
+    pub fn with_capacity(mut self, capacity: usize) -> Self {
+        self.capacity = capacity.max(1);
+        self
+    }
+
+    /// Set the overflow policy
+    pub fn with_policy(mut self, policy: OverflowPolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+
+    /// Limit how long a blocking send waits
+    pub fn with_block_timeout(mut self, timeout: Duration) -> Self {
+        self.block_timeout = Some(timeout);
+        self
+    }
+}
+
+/// A frame sent to JavaScript
+#[derive(Debug, Clone, PartialEq)]
+pub enum ChannelFrame {
+    /// Channel announcement with the initial credit window
+    Open {
+        /// Channel ID
+        channel_id: String,
+        /// Initial credits
+        credits: u32,
+        /// Buffer capacity
+        capacity: usize,
+        /// Overflow policy
+        policy: OverflowPolicy,
+    },
+    /// One message
+    Message {
+        /// Channel ID
+        channel_id: String,
+        /// Sequence number (gaps mean dropped or coalesced messages)
+        seq: u64,
+        /// Payload
+        data: Value,
+    },
+    /// End of stream
+    Close {
+        /// Channel ID
+        channel_id: String,
+    },
+}
+
+impl ChannelFrame {
+    /// Name of the event carrying this frame
+    pub fn event_name(&self) -> &'static str {
+        match self {
+            Self::Open { .. } => CHANNEL_OPEN_EVENT,
+            Self::Message { .. } => CHANNEL_MESSAGE_EVENT,
+            Self::Close { .. } => CHANNEL_CLOSE_EVENT,
+        }
+    }
+
+    /// Event payload
+    pub fn payload(&self) -> Value {
+        match self {
+            Self::Open {
+                channel_id,
+                credits,
+                capacity,
+                policy,
+            } => json!({
+                "channel_id": channel_id,
+                "credits": credits,
+                "capacity": capacity,
+                "policy": policy.as_str(),
+            }),
+            Self::Message {
+                channel_id,
+                seq,
+                data,
+            } => json!({"channel_id": channel_id, "seq": seq, "data": data}),
+            Self::Close { channel_id } => json!({"channel_id": channel_id}),
+        }
+    }
+}
+
+/// Result of a successful send
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SendOutcome {
+    /// Sent to JavaScript immediately
+    Delivered,
+    /// Buffered until JavaScript returns credits
+    Queued,
+    /// Buffered after discarding the oldest buffered message
+    DroppedOldest,
+    /// Replaced the newest buffered message
+    Coalesced,
+}
+
+/// Channel counters
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct ChannelStats {
+    /// Messages accepted by `send`
+    pub sent: u64,
+    /// Messages delivered to JavaScript
+    pub delivered: u64,
+    /// Messages discarded by [`OverflowPolicy::DropOldest`] or cancellation
+    pub dropped: u64,
+    /// Messages replaced by [`OverflowPolicy::Coalesce`]
+    pub coalesced: u64,
+    /// Messages currently buffered
+    pub buffered: usize,
+    /// Credits currently available to JavaScript
+    pub credits: u64,
+}
+
+/// Receives frames for JavaScript (e.g. pushes them into the message queue)
+///
+/// Called while the channel is locked to keep frames in order, so it must
+/// not block or call back into the channel.
+pub type ChannelSink = Arc<dyn Fn(ChannelFrame) + Send + Sync>;
+
+type CancelCallback = Box<dyn FnOnce(Option<&str>) + Send>;
+
+#[derive(Default)]
+struct State {
+    credits: u64,
+    buffer: VecDeque<(u64, Value)>,
+    next_seq: u64,
+    /// Producer called `close` (close frame may still wait for the buffer)
+    closing: bool,
+    /// Close frame sent or channel cancelled
+    finished: bool,
+    cancelled: Option<Option<String>>,
+    stats: ChannelStats,
+    on_cancel: Vec<CancelCallback>,
+}
+
+struct Inner {
+    id: String,
+    config: ChannelConfig,
+    sink: ChannelSink,
+    state: Mutex<State>,
+    changed: Condvar,
+}
+
+impl Inner {
+    fn emit_message(&self, state: &mut State, seq: u64, data: Value) {
+        state.credits -= 1;
+        state.stats.delivered += 1;
+        (self.sink)(ChannelFrame::Message {
+            channel_id: self.id.clone(),
+            seq,
+            data,
+        });
+    }
+
+    fn emit_close(&self, state: &mut State) {
+        state.finished = true;
+        (self.sink)(ChannelFrame::Close {
+            channel_id: self.id.clone(),
+        });
+    }
+
+    fn error(&self, state: &State) -> ChannelError {
+        match &state.cancelled {
+            Some(reason) => ChannelError::Cancelled {
+                id: self.id.clone(),
+                reason: reason.clone(),
+            },
+            None => ChannelError::Closed(self.id.clone()),
+        }
+    }
+}
+
+/// Producer side of a flow-controlled channel
+///
+/// Cloning is cheap and shares the channel, so the clone kept by a
+/// [`ChannelRegistry`] receives acknowledgements while the producer sends
+/// from another thread.
+#[derive(Clone)]
+pub struct FlowChannel {
+    inner: Arc<Inner>,
+}
+
+impl fmt::Debug for FlowChannel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FlowChannel")
+            .field("id", &self.inner.id)
+            .field("config", &self.inner.config)
+            .field("stats", &self.stats())
+            .finish()
+    }
+}
+
+impl FlowChannel {
+    /// Open a channel and send its open frame
+    pub fn open(id: impl Into<String>, config: ChannelConfig, sink: ChannelSink) -> Self {
+        let config = ChannelConfig {
+            credits: config.credits.max(1),
+            capacity: config.capacity.max(1),
+            ..config
+        };
+        let inner = Inner {
+            id: id.into(),
+            state: Mutex::new(State {
+                credits: u64::from(config.credits),
+                ..State::default()
+            }),
+            config,
+            sink,
+            changed: Condvar::new(),
+        };
+        (inner.sink)(ChannelFrame::Open {
+            channel_id: inner.id.clone(),
+            credits: inner.config.credits,
+            capacity: inner.config.capacity,
+            policy: inner.config.policy,
+        });
+        Self {
+            inner: Arc::new(inner),
+        }
+    }
+
+    /// Channel ID
+    pub fn id(&self) -> &str {
+        &self.inner.id
+    }
+
+    /// Flow control settings
+    pub fn config(&self) -> &ChannelConfig {
+        &self.inner.config
+    }
+
+    /// Send a message
+    ///
+    /// Delivered immediately while JavaScript has credits, buffered
+    /// otherwise. With a full buffer the overflow policy applies;
+    /// [`OverflowPolicy::Block`] waits until an acknowledgement frees space.
+    pub fn send(&self, data: Value) -> Result<SendOutcome, ChannelError> {
+        let inner = &self.inner;
+        let deadline = inner.config.block_timeout.map(|t| Instant::now() + t);
+        let mut state = inner.state.lock();
+
+        loop {
+            if state.closing || state.finished {
+                return Err(inner.error(&state));
+            }
+            if state.buffer.len() < inner.config.capacity {
+                break;
+            }
+            match inner.config.policy {
+                OverflowPolicy::Block => {
+                    let timed_out = match deadline {
+                        Some(deadline) => {
+                            inner.changed.wait_until(&mut state, deadline).timed_out()
+                        }
+                        None => {
+                            inner.changed.wait(&mut state);
+                            false
+                        }
+                    };
+                    if timed_out && state.buffer.len() >= inner.config.capacity {
+                        return Err(ChannelError::Timeout(inner.id.clone()));
+                    }
+                }
+                OverflowPolicy::DropOldest | OverflowPolicy::Coalesce => break,
+            }
+        }
+
+        let seq = state.next_seq;
+        state.next_seq += 1;
+        state.stats.sent += 1;
+
+        if state.credits > 0 && state.buffer.is_empty() {
+            inner.emit_message(&mut state, seq, data);
+            return Ok(SendOutcome::Delivered);
+        }
+
+        let outcome = if state.buffer.len() < inner.config.capacity {
+            state.buffer.push_back((seq, data));
+            SendOutcome::Queued
+        } else if inner.config.policy == OverflowPolicy::Coalesce {
+            state.stats.coalesced += 1;
+            if let Some(newest) = state.buffer.back_mut() {
+                *newest = (seq, data);
+            }
+            SendOutcome::Coalesced
+        } else {
+            state.stats.dropped += 1;
+            state.buffer.pop_front();
+            state.buffer.push_back((seq, data));
+            SendOutcome::DroppedOldest
+        };
+        Ok(outcome)
+    }
+
+    /// Return credits from JavaScript and deliver buffered messages
+    pub fn grant(&self, credits: u64) {
+        let inner = &self.inner;
+        let mut state = inner.state.lock();
+        if state.finished {
+            return;
+        }
+        state.credits = state.credits.saturating_add(credits);
+        while state.credits > 0 {
+            let Some((seq, data)) = state.buffer.pop_front() else {
+                break;
+            };
+            inner.emit_message(&mut state, seq, data);
+        }
+        if state.closing && state.buffer.is_empty() {
+            inner.emit_close(&mut state);
+        }
+        drop(state);
+        inner.changed.notify_all();
+    }
+
+    /// Close the channel
+    ///
+    /// Further sends fail. The close frame follows the buffered messages, so
+    /// it is sent once JavaScript has acknowledged enough of them.
+    pub fn close(&self) {
+        let inner = &self.inner;
+        let mut state = inner.state.lock();
+        if state.closing || state.finished {
+            return;
+        }
+        state.closing = true;
+        if state.buffer.is_empty() {
+            inner.emit_close(&mut state);
+        }
+        drop(state);
+        inner.changed.notify_all();
+    }
+
+    /// Cancel the channel on behalf of JavaScript
+    ///
+    /// Discards buffered messages, wakes blocked producers and runs the
+    /// cancel callbacks. Returns false if the channel had already finished.
+    pub fn cancel(&self, reason: Option<String>) -> bool {
+        let inner = &self.inner;
+        let mut state = inner.state.lock();
+        if state.finished {
+            return false;
+        }
+        state.finished = true;
+        state.stats.dropped += state.buffer.len() as u64;
+        state.buffer.clear();
+        state.cancelled = Some(reason.clone());
+        let callbacks = std::mem::take(&mut state.on_cancel);
+        drop(state);
+        inner.changed.notify_all();
+
+        tracing::debug!("[FlowChannel] '{}' cancelled: {:?}", inner.id, reason);
+        for callback in callbacks {
+            callback(reason.as_deref());
+        }
+        true
+    }
+
+    /// Call `callback(reason)` when JavaScript cancels the channel
+    ///
+    /// Runs immediately if the channel is already cancelled.
+    pub fn on_cancel(&self, callback: impl FnOnce(Option<&str>) + Send + 'static) {
+        let mut state = self.inner.state.lock();
+        match &state.cancelled {
+            Some(reason) => {
+                let reason = reason.clone();
+                drop(state);
+                callback(reason.as_deref());
+            }
+            None if state.finished => {}
+            None => state.on_cancel.push(Box::new(callback)),
+        }
+    }
+
+    /// Whether JavaScript cancelled the channel
+    pub fn is_cancelled(&self) -> bool {
+        self.inner.state.lock().cancelled.is_some()
+    }
+
+    /// Reason given by JavaScript when cancelling
+    pub fn cancel_reason(&self) -> Option<String> {
+        self.inner.state.lock().cancelled.clone().flatten()
+    }
+
+    /// Whether the producer closed the channel (or it was cancelled)
+    pub fn is_closed(&self) -> bool {
+        let state = self.inner.state.lock();
+        state.closing || state.finished
+    }
+
+    /// Whether the channel needs no further acknowledgements
+    pub fn is_finished(&self) -> bool {
+        self.inner.state.lock().finished
+    }
+
+    /// Block until the close frame is sent or the channel is cancelled
+    ///
+    /// Returns false if `timeout` elapsed first.
+    pub fn wait_finished(&self, timeout: Option<Duration>) -> bool {
+        let deadline = timeout.map(|t| Instant::now() + t);
+        let mut state = self.inner.state.lock();
+        while !state.finished {
+            match deadline {
+                Some(deadline) => {
+                    if self
+                        .inner
+                        .changed
+                        .wait_until(&mut state, deadline)
+                        .timed_out()
+                    {
+                        return state.finished;
+                    }
+                }
+                None => self.inner.changed.wait(&mut state),
+            }
+        }
+        true
+    }
+
+    /// Current counters
+    pub fn stats(&self) -> ChannelStats {
+        let state = self.inner.state.lock();
+        ChannelStats {
+            buffered: state.buffer.len(),
+            credits: state.credits,
+            ..state.stats
+        }
+    }
+}
+
+/// Channels of one WebView, addressed by ID
+///
+/// Routes `__channel_ack__` and `__channel_cancel__` from JavaScript to the
+/// producing channel. Finished channels are removed automatically.
+#[derive(Clone, Default)]
+pub struct ChannelRegistry {
+    channels: Arc<RwLock<HashMap<String, FlowChannel>>>,
+    counter: Arc<AtomicU64>,
+    page_loaded: Arc<AtomicBool>,
+}
+
+impl fmt::Debug for ChannelRegistry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ChannelRegistry")
+            .field("channels", &self.len())
+            .finish()
+    }
+}
+
+impl ChannelRegistry {
+    /// Create an empty registry
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Open and register a channel
+    ///
+    /// Without an ID a unique `stream_<n>` ID is generated. An existing
+    /// channel with the same ID is cancelled and replaced.
+    pub fn open(
+        &self,
+        id: Option<String>,
+        config: ChannelConfig,
+        sink: ChannelSink,
+    ) -> FlowChannel {
+        self.prune();
+        let id = id.unwrap_or_else(|| {
+            format!(
+                "stream_{}",
+                self.counter.fetch_add(1, Ordering::Relaxed) + 1
+            )
+        });
+        if let Some(previous) = self.channels.write().remove(&id) {
+            previous.cancel(Some("replaced".to_string()));
+        }
+        let channel = FlowChannel::open(id.clone(), config, sink);
+        self.channels.write().insert(id, channel.clone());
+        channel
+    }
+
+    /// Get a registered channel
+    pub fn get(&self, id: &str) -> Option<FlowChannel> {
+        self.channels.read().get(id).cloned()
+    }
+
+    /// Return credits to a channel
+    ///
+    /// Returns false if no such channel is registered.
+    pub fn grant(&self, id: &str, credits: u64) -> bool {
+        let Some(channel) = self.get(id) else {
+            return false;
+        };
+        channel.grant(credits);
+        if channel.is_finished() {
+            self.channels.write().remove(id);
+        }
+        true
+    }
+
+    /// Cancel a channel and unregister it
+    pub fn cancel(&self, id: &str, reason: Option<String>) -> bool {
+        match self.channels.write().remove(id) {
+            Some(channel) => channel.cancel(reason),
+            None => false,
+        }
+    }
+
+    /// Handle an `__channel_ack__` payload (`{channel_id, credits}`)
+    pub fn handle_ack(&self, payload: &Value) -> bool {
+        let Some(id) = payload.get("channel_id").and_then(Value::as_str) else {
+            return false;
+        };
+        let credits = payload.get("credits").and_then(Value::as_u64).unwrap_or(1);
+        self.grant(id, credits)
+    }
+
+    /// Handle an `__channel_cancel__` payload (`{channel_id, reason?}`)
+    pub fn handle_cancel(&self, payload: &Value) -> bool {
+        let Some(id) = payload.get("channel_id").and_then(Value::as_str) else {
+            return false;
+        };
+        let reason = payload
+            .get("reason")
+            .and_then(Value::as_str)
+            .map(str::to_string);
+        self.cancel(id, reason)
+    }
+
+    /// Cancel every channel, e.g. when the WebView is destroyed
+    pub fn cancel_all(&self, reason: &str) -> usize {
+        let channels: Vec<FlowChannel> = self.channels.write().drain().map(|(_, c)| c).collect();
+        channels
+            .iter()
+            .filter(|channel| channel.cancel(Some(reason.to_string())))
+            .count()
+    }
+
+    /// Cancel every channel when the WebView starts loading a new page
+    ///
+    /// The page consuming the channels is gone and will never acknowledge
+    /// them again. The first load is ignored so channels opened before the
+    /// page existed survive it.
+    pub fn handle_page_load(&self) -> usize {
+        if !self.page_loaded.swap(true, Ordering::AcqRel) {
+            return 0;
+        }
+        self.cancel_all("page reloaded")
+    }
+
+    /// Remove finished channels
+    pub fn prune(&self) -> usize {
+        let mut channels = self.channels.write();
+        let before = channels.len();
+        channels.retain(|_, channel| !channel.is_finished());
+        before - channels.len()
+    }
+
+    /// IDs of registered channels
+    pub fn ids(&self) -> Vec<String> {
+        self.channels.read().keys().cloned().collect()
+    }
+
+    /// Number of registered channels
+    pub fn len(&self) -> usize {
+        self.channels.read().len()
+    }
+
+    /// Whether no channels are registered
+    pub fn is_empty(&self) -> bool {
+        self.channels.read().is_empty()
+    }
+}
@@ -13,6 +13,8 @@
 //! │  - IpcMetrics: Performance tracking                          │
 //! │  - WebViewMessage: WebView operations                        │
 //! │  - WindowEventType: Window lifecycle events                  │
+//! │  - BlobStore: Binary payloads served over the protocol       │
+//! │  - FlowChannel: Credit-based streaming with backpressure     │
 //! └─────────────────────────────────────────────────────────────┘
 //!                              ↑
 //!                              │ uses
@@ -23,8 +25,20 @@
 //! └─────────────────────────────────────────────────────────────┘
 //! ```
 
+mod blob;
+mod channel;
 mod message;
 mod metrics;
 
+pub use blob::{
+    blob_id_from_uri, blob_url, is_blob_uri, Blob, BlobError, BlobHandle, BlobLifetime,
+    BlobResponse, BlobStore, DEFAULT_BLOB_MIME, DEFAULT_MAX_BLOB_BYTES,
+};
+pub use channel::{
+    ChannelConfig, ChannelError, ChannelFrame, ChannelRegistry, ChannelSink, ChannelStats,
+    FlowChannel, OverflowPolicy, SendOutcome, CHANNEL_ACK_EVENT, CHANNEL_CANCEL_EVENT,
+    CHANNEL_CLOSE_EVENT, CHANNEL_MESSAGE_EVENT, CHANNEL_OPEN_EVENT, DEFAULT_CHANNEL_CAPACITY,
+    DEFAULT_CHANNEL_CREDITS,
+};
 pub use message::{IpcMessage, IpcMode, WebViewMessage, WindowEventType};
 pub use metrics::{IpcMetrics, IpcMetricsSnapshot};
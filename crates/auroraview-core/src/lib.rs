@@ -38,10 +38,14 @@ pub mod cli;
 pub mod config;
 /// Workspace-level shared constants (env var names, well-known literals).
 pub mod constants;
+/// Persistent cookie jar shared by backends and extensions.
+pub mod cookies;
 /// DOM manipulation primitives (DomOp, DomBatch).
 pub mod dom;
 /// Unified user event types (CoreUserEvent, ExtendedUserEvent).
 pub mod events;
+/// File serving with byte ranges, conditional requests and caching.
+pub mod file_server;
 /// Icon utilities (PNG loading, ICO conversion, compression).
 pub mod icon;
 /// Unique ID generation utilities.
@@ -62,6 +66,8 @@ pub mod protocol;
 pub mod service_discovery;
 /// Qt-inspired signal-slot event system.
 pub mod signals;
+/// Shared application state with JSON-patch sync and persistence.
+pub mod state;
 /// JavaScript templates (Askama).
 pub mod templates;
 /// Thread safety utilities (lock ordering, deadlock prevention).
@@ -34,6 +34,9 @@ pub const PROTOCOL_TYPE_FILE: &str = "type:file";
 /// Protocol type prefix for local path conversions
 pub const PROTOCOL_TYPE_LOCAL: &str = "type:local";
 
+/// Protocol type prefix for blobs in the IPC blob store
+pub const PROTOCOL_TYPE_BLOB: &str = "type:blob";
+
 // ============================================================================
 // URL Conversion Functions
 // ============================================================================
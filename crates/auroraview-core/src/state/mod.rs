@@ -0,0 +1,72 @@
+//! Shared Application State
+//!
+//! The authoritative store behind the JavaScript `auroraview.state` bridge.
+//! Every attached WebView holds a replica; changes from any window are
+//! applied here first and then broadcast to the other windows as minimal
+//! JSON patches, so multi-panel tools stay in sync.
+//!
+//! ## Features
+//! - JSON Patch (RFC 6902) diffs in both directions
+//! - Monotonic versions and full resyncs for late or failed subscribers
+//! - Key watchers for Rust/Python code
+//! - Opt-in persistence per key with atomic writes
+//!
+//! ## Usage
+//!
+//! ```rust,ignore
+//! use auroraview_core::state::{StateStore, RUST_ORIGIN, STATE_FILE};
+//!
+//! let store = StateStore::open(data_dir.join(STATE_FILE))?;
+//! store.set_persistent("layout", true)?;
+//!
+//! store.subscribe("panel-1", move |message| send_to_webview(message));
+//! store.watch("selection", |change| println!("{:?}", change.value));
+//!
+//! store.set("selection", json!(["pCube1"]), RUST_ORIGIN)?;
+//! store.apply_patch(&patch_from_js, "panel-1")?;
+//! ```
+
+mod patch;
+mod store;
+
+pub use patch::{
+    apply_patch, diff, diff_at, escape_token, parse_pointer, pointer, top_level_key, PatchOp,
+};
+pub use store::{
+    StateChange, StateMessage, StateStore, WatchId, RUST_ORIGIN, STATE_FILE, WATCH_ALL,
+};
+
+/// Result type for state operations
+pub type StateResult<T> = Result<T, StateError>;
+
+/// Error type for state operations
+#[derive(Debug, Clone, PartialEq, thiserror::Error)]
+pub enum StateError {
+    /// JSON Pointer is malformed
+    #[error("Invalid JSON pointer: {0}")]
+    InvalidPointer(String),
+    /// Patch refers to a location that does not exist
+    #[error("Path not found: {0}")]
+    PathNotFound(String),
+    /// A `test` operation did not match
+    #[error("Test failed at: {0}")]
+    TestFailed(String),
+    /// Patch is structurally invalid
+    #[error("Invalid patch: {0}")]
+    InvalidPatch(String),
+    /// Reading or writing persisted state failed
+    #[error("State storage error: {0}")]
+    Storage(String),
+}
+
+impl From<std::io::Error> for StateError {
+    fn from(e: std::io::Error) -> Self {
+        Self::Storage(e.to_string())
+    }
+}
+
+impl From<serde_json::Error> for StateError {
+    fn from(e: serde_json::Error) -> Self {
+        Self::Storage(e.to_string())
+    }
+}
@@ -0,0 +1,308 @@
+//! JSON Patch (RFC 6902) and JSON Pointer (RFC 6901) support
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+use super::{StateError, StateResult};
+
+/// A JSON Patch operation
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "op", rename_all = "lowercase")]
+pub enum PatchOp {
+    /// Add a value (inserts into arrays, sets object members)
+    Add { path: String, value: Value },
+    /// Remove a value
+    Remove { path: String },
+    /// Replace an existing value
+    Replace { path: String, value: Value },
+    /// Move a value
+    Move { from: String, path: String },
+    /// Copy a value
+    Copy { from: String, path: String },
+    /// Fail the patch unless the value equals `value`
+    Test { path: String, value: Value },
+}
+
+impl PatchOp {
+    /// Create an `add` operation
+    pub fn add(path: impl Into<String>, value: Value) -> Self {
+        Self::Add {
+            path: path.into(),
+            value,
+        }
+    }
+
+    /// Create a `remove` operation
+    pub fn remove(path: impl Into<String>) -> Self {
+        Self::Remove { path: path.into() }
+    }
+
+    /// Create a `replace` operation
+    pub fn replace(path: impl Into<String>, value: Value) -> Self {
+        Self::Replace {
+            path: path.into(),
+            value,
+        }
+    }
+
+    /// Target path of the operation
+    pub fn path(&self) -> &str {
+        match self {
+            Self::Add { path, .. }
+            | Self::Remove { path }
+            | Self::Replace { path, .. }
+            | Self::Move { path, .. }
+            | Self::Copy { path, .. }
+            | Self::Test { path, .. } => path,
+        }
+    }
+
+    /// Source path of `move` and `copy`
+    pub fn from(&self) -> Option<&str> {
+        match self {
+            Self::Move { from, .. } | Self::Copy { from, .. } => Some(from),
+            _ => None,
+        }
+    }
+
+    /// Whether the operation changes the document
+    pub fn is_mutation(&self) -> bool {
+        !matches!(self, Self::Test { .. })
+    }
+}
+
+/// Escape a key for use as a JSON Pointer token
+pub fn escape_token(token: &str) -> String {
+    token.replace('~', "~0").replace('/', "~1")
+}
+
+/// Build a JSON Pointer from unescaped tokens
+pub fn pointer<'a>(tokens: impl IntoIterator<Item = &'a str>) -> String {
+    tokens
+        .into_iter()
+        .map(|token| format!("/{}", escape_token(token)))
+        .collect()
+}
+
+/// Split a JSON Pointer into unescaped tokens
+pub fn parse_pointer(pointer: &str) -> StateResult<Vec<String>> {
+    if pointer.is_empty() {
+        return Ok(Vec::new());
+    }
+    let Some(rest) = pointer.strip_prefix('/') else {
+        return Err(StateError::InvalidPointer(pointer.to_string()));
+    };
+    Ok(rest
+        .split('/')
+        .map(|token| token.replace("~1", "/").replace("~0", "~"))
+        .collect())
+}
+
+/// Top-level key addressed by a pointer (`None` for the root)
+pub fn top_level_key(pointer: &str) -> StateResult<Option<String>> {
+    Ok(parse_pointer(pointer)?.into_iter().next())
+}
+
+/// Apply a patch atomically: either every operation applies or `doc` is unchanged
+pub fn apply_patch(doc: &mut Value, patch: &[PatchOp]) -> StateResult<()> {
+    let mut working = doc.clone();
+    for op in patch {
+        apply_op(&mut working, op)?;
+    }
+    *doc = working;
+    Ok(())
+}
+
+fn apply_op(doc: &mut Value, op: &PatchOp) -> StateResult<()> {
+    match op {
+        PatchOp::Add { path, value } => add(doc, path, value.clone()),
+        PatchOp::Remove { path } => remove(doc, path).map(|_| ()),
+        PatchOp::Replace { path, value } => {
+            let target = doc
+                .pointer_mut(path)
+                .ok_or_else(|| StateError::PathNotFound(path.clone()))?;
+            *target = value.clone();
+            Ok(())
+        }
+        PatchOp::Move { from, path } => {
+            if path != from && path.starts_with(&format!("{}/", from)) {
+                return Err(StateError::InvalidPatch(format!(
+                    "cannot move '{}' into its own child '{}'",
+                    from, path
+                )));
+            }
+            let value = remove(doc, from)?;
+            add(doc, path, value)
+        }
+        PatchOp::Copy { from, path } => {
+            let value = doc
+                .pointer(from)
+                .cloned()
+                .ok_or_else(|| StateError::PathNotFound(from.clone()))?;
+            add(doc, path, value)
+        }
+        PatchOp::Test { path, value } => match doc.pointer(path) {
+            Some(actual) if actual == value => Ok(()),
+            Some(_) => Err(StateError::TestFailed(path.clone())),
+            None => Err(StateError::PathNotFound(path.clone())),
+        },
+    }
+}
+
+/// Resolve the parent container of `path` and the last token
+fn parent_mut<'a>(doc: &'a mut Value, path: &str) -> StateResult<(&'a mut Value, String)> {
+    let mut tokens = parse_pointer(path)?;
+    let Some(last) = tokens.pop() else {
+        return Err(StateError::InvalidPatch(
+            "operation needs a parent container".to_string(),
+        ));
+    };
+    let parent_path = pointer(tokens.iter().map(String::as_str));
+    let parent = doc
+        .pointer_mut(&parent_path)
+        .ok_or_else(|| StateError::PathNotFound(parent_path.clone()))?;
+    Ok((parent, last))
+}
+
+fn array_index(token: &str, len: usize, path: &str) -> StateResult<usize> {
+    // Leading zeros are not allowed (RFC 6901)
+    if token.is_empty() || (token.len() > 1 && token.starts_with('0')) {
+        return Err(StateError::InvalidPointer(path.to_string()));
+    }
+    let index: usize = token
+        .parse()
+        .map_err(|_| StateError::InvalidPointer(path.to_string()))?;
+    if index > len {
+        return Err(StateError::PathNotFound(path.to_string()));
+    }
+    Ok(index)
+}
+
+fn add(doc: &mut Value, path: &str, value: Value) -> StateResult<()> {
+    if path.is_empty() {
+        *doc = value;
+        return Ok(());
+    }
+    let (parent, token) = parent_mut(doc, path)?;
+    match parent {
+        Value::Object(map) => {
+            map.insert(token, value);
+            Ok(())
+        }
+        Value::Array(items) => {
+            let index = if token == "-" {
+                items.len()
+            } else {
+                array_index(&token, items.len(), path)?
+            };
+            items.insert(index, value);
+            Ok(())
+        }
+        _ => Err(StateError::PathNotFound(path.to_string())),
+    }
+}
+
+fn remove(doc: &mut Value, path: &str) -> StateResult<Value> {
+    if path.is_empty() {
+        return Ok(std::mem::replace(doc, Value::Null));
+    }
+    let (parent, token) = parent_mut(doc, path)?;
+    match parent {
+        Value::Object(map) => map
+            .remove(&token)
+            .ok_or_else(|| StateError::PathNotFound(path.to_string())),
+        Value::Array(items) => {
+            let index = array_index(&token, items.len(), path)?;
+            if index == items.len() {
+                return Err(StateError::PathNotFound(path.to_string()));
+            }
+            Ok(items.remove(index))
+        }
+        _ => Err(StateError::PathNotFound(path.to_string())),
+    }
+}
+
+/// Compute a minimal patch turning `old` into `new`
+///
+/// Objects are compared member by member and arrays element by element
+/// (appending or truncating at the end), so changing one field of a large
+/// value produces a single `replace`.
+pub fn diff(old: &Value, new: &Value) -> Vec<PatchOp> {
+    let mut ops = Vec::new();
+    diff_into(String::new(), old, new, &mut ops);
+    ops
+}
+
+/// Compute a minimal patch for the value at `path`
+pub fn diff_at(path: &str, old: Option<&Value>, new: Option<&Value>) -> Vec<PatchOp> {
+    match (old, new) {
+        (None, None) => Vec::new(),
+        (None, Some(new)) => vec![PatchOp::add(path, new.clone())],
+        (Some(_), None) => vec![PatchOp::remove(path)],
+        (Some(old), Some(new)) => {
+            let mut ops = Vec::new();
+            diff_into(path.to_string(), old, new, &mut ops);
+            ops
+        }
+    }
+}
+
+fn diff_into(path: String, old: &Value, new: &Value, ops: &mut Vec<PatchOp>) {
+    if old == new {
+        return;
+    }
+    match (old, new) {
+        (Value::Object(old), Value::Object(new)) => diff_objects(&path, old, new, ops),
+        (Value::Array(old), Value::Array(new)) => {
+            let common = old.len().min(new.len());
+            for i in 0..common {
+                diff_into(format!("{}/{}", path, i), &old[i], &new[i], ops);
+            }
+            // Remove from the end so earlier indices stay valid
+            for i in (common..old.len()).rev() {
+                ops.push(PatchOp::remove(format!("{}/{}", path, i)));
+            }
+            for (i, value) in new.iter().enumerate().skip(common) {
+                ops.push(PatchOp::add(format!("{}/{}", path, i), value.clone()));
+            }
+        }
+        _ => ops.push(PatchOp::replace(path, new.clone())),
+    }
+}
+
+fn diff_objects(
+    path: &str,
+    old: &Map<String, Value>,
+    new: &Map<String, Value>,
+    ops: &mut Vec<PatchOp>,
+) {
+    for key in old.keys().filter(|key| !new.contains_key(*key)) {
+        ops.push(PatchOp::remove(format!("{}/{}", path, escape_token(key))));
+    }
+    for (key, value) in new {
+        let child = format!("{}/{}", path, escape_token(key));
+        match old.get(key) {
+            Some(old_value) => diff_into(child, old_value, value, ops),
+            None => ops.push(PatchOp::add(child, value.clone())),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_pointer_escaping() {
+        assert_eq!(pointer(["a/b", "c~d"]), "/a~1b/c~0d");
+        assert_eq!(parse_pointer("/a~1b/c~0d").unwrap(), vec!["a/b", "c~d"]);
+        assert!(parse_pointer("no-slash").is_err());
+    }
+
+    #[test]
+    fn test_leading_zero_index_is_invalid() {
+        let mut doc = json!({"list": [1, 2]});
+        assert!(apply_patch(&mut doc, &[PatchOp::remove("/list/01")]).is_err());
+    }
+}
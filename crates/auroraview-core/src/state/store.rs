@@ -0,0 +1,496 @@
+//! Authoritative state store with subscribers, watchers and persistence
+
+use std::collections::{BTreeSet, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+
+use parking_lot::{ReentrantMutex, RwLock};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+use super::patch::{self, escape_token, PatchOp};
+use super::{StateError, StateResult};
+
+/// Origin used for changes made from Rust or Python
+pub const RUST_ORIGIN: &str = "rust";
+
+/// Key that watches every top-level key
+pub const WATCH_ALL: &str = "*";
+
+/// Default file name of persisted state inside a data directory
+pub const STATE_FILE: &str = "state.json";
+
+/// Current on-disk format version
+const STATE_FILE_VERSION: u32 = 1;
+
+/// Identifier returned by [`StateStore::watch`]
+pub type WatchId = u64;
+
+type Sink = Arc<dyn Fn(&StateMessage) + Send + Sync>;
+type Watcher = Arc<dyn Fn(&StateChange) + Send + Sync>;
+
+/// Message sent to subscribed WebViews (the `__state_sync__` payload)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum StateMessage {
+    /// Incremental change
+    Patch {
+        patch: Vec<PatchOp>,
+        version: u64,
+        origin: String,
+    },
+    /// Complete state, sent on subscribe and after a rejected patch
+    Full { data: Value, version: u64 },
+}
+
+/// Change of one top-level key, delivered to watchers
+#[derive(Debug, Clone, PartialEq)]
+pub struct StateChange {
+    /// Top-level key that changed
+    pub key: String,
+    /// New value (`None` when the key was deleted)
+    pub value: Option<Value>,
+    /// Operations applied to this key
+    pub patch: Vec<PatchOp>,
+    /// Subscriber ID (or [`RUST_ORIGIN`]) that made the change
+    pub origin: String,
+    /// Store version after the change
+    pub version: u64,
+}
+
+/// On-disk representation
+#[derive(Serialize, Deserialize)]
+struct StateFile {
+    version: u32,
+    values: Map<String, Value>,
+}
+
+struct Data {
+    root: Value,
+    version: u64,
+}
+
+struct Inner {
+    data: RwLock<Data>,
+    subscribers: RwLock<Vec<(String, Sink)>>,
+    watchers: RwLock<Vec<(WatchId, String, Watcher)>>,
+    next_watch_id: AtomicU64,
+    persistent: RwLock<HashSet<String>>,
+    path: Option<PathBuf>,
+    // Serializes commit + dispatch so every subscriber sees versions in order.
+    // Reentrant so watchers may change the store from their callback.
+    dispatch: ReentrantMutex<()>,
+}
+
+/// Thread-safe shared state store
+///
+/// Cloning is cheap and every clone refers to the same state. The root is
+/// always a JSON object whose members are the state keys.
+#[derive(Clone)]
+pub struct StateStore {
+    inner: Arc<Inner>,
+}
+
+impl Default for StateStore {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl std::fmt::Debug for StateStore {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("StateStore")
+            .field("version", &self.version())
+            .field("keys", &self.keys())
+            .field("subscribers", &self.subscriber_count())
+            .field("path", &self.inner.path)
+            .finish()
+    }
+}
+
+impl StateStore {
+    /// Create an in-memory store
+    pub fn new() -> Self {
+        Self::with_path(None)
+    }
+
+    /// Open a store persisted at `path`, loading previously persisted keys
+    ///
+    /// Keys found in the file are marked persistent.
+    pub fn open(path: impl Into<PathBuf>) -> StateResult<Self> {
+        let path = path.into();
+        let store = Self::with_path(Some(path.clone()));
+        if path.exists() {
+            let file: StateFile = serde_json::from_slice(&std::fs::read(&path)?)?;
+            if file.version > STATE_FILE_VERSION {
+                return Err(StateError::Storage(format!(
+                    "Unsupported state file version {}",
+                    file.version
+                )));
+            }
+            store
+                .inner
+                .persistent
+                .write()
+                .extend(file.values.keys().cloned());
+            store.inner.data.write().root = Value::Object(file.values);
+        }
+        Ok(store)
+    }
+
+    fn with_path(path: Option<PathBuf>) -> Self {
+        Self {
+            inner: Arc::new(Inner {
+                data: RwLock::new(Data {
+                    root: Value::Object(Map::new()),
+                    version: 0,
+                }),
+                subscribers: RwLock::new(Vec::new()),
+                watchers: RwLock::new(Vec::new()),
+                next_watch_id: AtomicU64::new(1),
+                persistent: RwLock::new(HashSet::new()),
+                path,
+                dispatch: ReentrantMutex::new(()),
+            }),
+        }
+    }
+
+    /// Path of the backing file, if persistent
+    pub fn path(&self) -> Option<&Path> {
+        self.inner.path.as_deref()
+    }
+
+    // ========== Reading ==========
+
+    /// Current version (incremented by every effective change)
+    pub fn version(&self) -> u64 {
+        self.inner.data.read().version
+    }
+
+    /// Value of a top-level key
+    pub fn get(&self, key: &str) -> Option<Value> {
+        self.inner.data.read().root.get(key).cloned()
+    }
+
+    /// Value at a JSON Pointer (e.g. `/layout/panels/0`)
+    pub fn get_path(&self, pointer: &str) -> Option<Value> {
+        self.inner.data.read().root.pointer(pointer).cloned()
+    }
+
+    /// Whether a top-level key exists
+    pub fn contains(&self, key: &str) -> bool {
+        self.inner.data.read().root.get(key).is_some()
+    }
+
+    /// Top-level keys
+    pub fn keys(&self) -> Vec<String> {
+        match &self.inner.data.read().root {
+            Value::Object(map) => map.keys().cloned().collect(),
+            _ => Vec::new(),
+        }
+    }
+
+    /// Copy of the whole state
+    pub fn snapshot(&self) -> Value {
+        self.inner.data.read().root.clone()
+    }
+
+    // ========== Writing ==========
+
+    /// Set a top-level key
+    ///
+    /// Returns the minimal patch that was broadcast (empty if unchanged).
+    pub fn set(&self, key: &str, value: Value, origin: &str) -> StateResult<Vec<PatchOp>> {
+        self.update(
+            |root| {
+                root.insert(key.to_string(), value);
+            },
+            origin,
+        )
+    }
+
+    /// Delete a top-level key
+    pub fn delete(&self, key: &str, origin: &str) -> StateResult<Vec<PatchOp>> {
+        self.update(
+            |root| {
+                root.remove(key);
+            },
+            origin,
+        )
+    }
+
+    /// Set several top-level keys in one change
+    pub fn set_many(
+        &self,
+        values: impl IntoIterator<Item = (String, Value)>,
+        origin: &str,
+    ) -> StateResult<Vec<PatchOp>> {
+        self.update(|root| root.extend(values), origin)
+    }
+
+    /// Remove every key
+    pub fn clear(&self, origin: &str) -> StateResult<Vec<PatchOp>> {
+        self.update(Map::clear, origin)
+    }
+
+    /// Apply a JSON patch atomically
+    ///
+    /// Used for patches coming from WebViews, with the subscriber ID as
+    /// `origin` so the change is not echoed back. On error nothing changes;
+    /// call [`resync`](Self::resync) to reset the sender's replica.
+    pub fn apply_patch(&self, ops: &[PatchOp], origin: &str) -> StateResult<Vec<PatchOp>> {
+        let _dispatch = self.inner.dispatch.lock();
+        let data = self.inner.data.write();
+        let mut next = data.root.clone();
+        patch::apply_patch(&mut next, ops)?;
+        if !next.is_object() {
+            return Err(StateError::InvalidPatch(
+                "state root must remain an object".to_string(),
+            ));
+        }
+        self.commit(data, next, origin)
+    }
+
+    fn update(
+        &self,
+        mutate: impl FnOnce(&mut Map<String, Value>),
+        origin: &str,
+    ) -> StateResult<Vec<PatchOp>> {
+        let _dispatch = self.inner.dispatch.lock();
+        let data = self.inner.data.write();
+        let mut next = data.root.clone();
+        if let Value::Object(map) = &mut next {
+            mutate(map);
+        }
+        self.commit(data, next, origin)
+    }
+
+    /// Swap in the new root, then persist and notify outside the data lock
+    fn commit(
+        &self,
+        mut data: parking_lot::RwLockWriteGuard<'_, Data>,
+        next: Value,
+        origin: &str,
+    ) -> StateResult<Vec<PatchOp>> {
+        let empty = Map::new();
+        let old = data.root.as_object().unwrap_or(&empty);
+        let new = next.as_object().unwrap_or(&empty);
+        let keys: BTreeSet<&String> = old.keys().chain(new.keys()).collect();
+
+        let mut ops = Vec::new();
+        let mut changed = Vec::new();
+        for key in keys {
+            let path = format!("/{}", escape_token(key));
+            let key_ops = patch::diff_at(&path, old.get(key), new.get(key));
+            if !key_ops.is_empty() {
+                ops.extend(key_ops.iter().cloned());
+                changed.push((key.clone(), new.get(key).cloned(), key_ops));
+            }
+        }
+        if ops.is_empty() {
+            return Ok(ops);
+        }
+
+        data.root = next;
+        data.version += 1;
+        let version = data.version;
+        drop(data);
+
+        let needs_save = {
+            let persistent = self.inner.persistent.read();
+            changed.iter().any(|(key, _, _)| persistent.contains(key))
+        };
+        if needs_save {
+            if let Err(e) = self.save() {
+                tracing::warn!("[StateStore] Failed to persist state: {}", e);
+            }
+        }
+
+        let message = StateMessage::Patch {
+            patch: ops.clone(),
+            version,
+            origin: origin.to_string(),
+        };
+        let sinks: Vec<Sink> = self
+            .inner
+            .subscribers
+            .read()
+            .iter()
+            .filter(|(id, _)| id != origin)
+            .map(|(_, sink)| sink.clone())
+            .collect();
+        for sink in sinks {
+            sink(&message);
+        }
+
+        for (key, value, patch) in changed {
+            let watchers: Vec<Watcher> = self
+                .inner
+                .watchers
+                .read()
+                .iter()
+                .filter(|(_, watched, _)| watched == &key || watched == WATCH_ALL)
+                .map(|(_, _, watcher)| watcher.clone())
+                .collect();
+            if watchers.is_empty() {
+                continue;
+            }
+            let change = StateChange {
+                key,
+                value,
+                patch,
+                origin: origin.to_string(),
+                version,
+            };
+            for watcher in watchers {
+                watcher(&change);
+            }
+        }
+
+        Ok(ops)
+    }
+
+    // ========== Subscribers ==========
+
+    /// Subscribe a WebView replica
+    ///
+    /// The sink immediately receives a [`StateMessage::Full`], then a
+    /// [`StateMessage::Patch`] for every change not made by `id` itself.
+    /// Subscribing an existing ID replaces its sink.
+    pub fn subscribe<F>(&self, id: impl Into<String>, sink: F)
+    where
+        F: Fn(&StateMessage) + Send + Sync + 'static,
+    {
+        let id = id.into();
+        let sink: Sink = Arc::new(sink);
+        let _dispatch = self.inner.dispatch.lock();
+        {
+            let mut subscribers = self.inner.subscribers.write();
+            subscribers.retain(|(existing, _)| existing != &id);
+            subscribers.push((id, sink.clone()));
+        }
+        sink(&self.full_message());
+    }
+
+    /// Remove a subscriber
+    pub fn unsubscribe(&self, id: &str) -> bool {
+        let mut subscribers = self.inner.subscribers.write();
+        let before = subscribers.len();
+        subscribers.retain(|(existing, _)| existing != id);
+        subscribers.len() != before
+    }
+
+    /// Send the full state to one subscriber (e.g. after a rejected patch)
+    pub fn resync(&self, id: &str) -> bool {
+        let _dispatch = self.inner.dispatch.lock();
+        let sink = self
+            .inner
+            .subscribers
+            .read()
+            .iter()
+            .find(|(existing, _)| existing == id)
+            .map(|(_, sink)| sink.clone());
+        match sink {
+            Some(sink) => {
+                sink(&self.full_message());
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Number of subscribed WebViews
+    pub fn subscriber_count(&self) -> usize {
+        self.inner.subscribers.read().len()
+    }
+
+    fn full_message(&self) -> StateMessage {
+        let data = self.inner.data.read();
+        StateMessage::Full {
+            data: data.root.clone(),
+            version: data.version,
+        }
+    }
+
+    // ========== Watchers ==========
+
+    /// Call `callback` whenever `key` changes ([`WATCH_ALL`] for every key)
+    pub fn watch<F>(&self, key: impl Into<String>, callback: F) -> WatchId
+    where
+        F: Fn(&StateChange) + Send + Sync + 'static,
+    {
+        let id = self.inner.next_watch_id.fetch_add(1, Ordering::Relaxed);
+        self.inner
+            .watchers
+            .write()
+            .push((id, key.into(), Arc::new(callback)));
+        id
+    }
+
+    /// Remove a watcher
+    pub fn unwatch(&self, id: WatchId) -> bool {
+        let mut watchers = self.inner.watchers.write();
+        let before = watchers.len();
+        watchers.retain(|(existing, _, _)| *existing != id);
+        watchers.len() != before
+    }
+
+    // ========== Persistence ==========
+
+    /// Enable or disable persistence of a key
+    ///
+    /// Has no on-disk effect for in-memory stores.
+    pub fn set_persistent(&self, key: &str, persistent: bool) -> StateResult<()> {
+        let changed = {
+            let mut keys = self.inner.persistent.write();
+            if persistent {
+                keys.insert(key.to_string())
+            } else {
+                keys.remove(key)
+            }
+        };
+        if changed {
+            self.save()?;
+        }
+        Ok(())
+    }
+
+    /// Whether a key is persisted
+    pub fn is_persistent(&self, key: &str) -> bool {
+        self.inner.persistent.read().contains(key)
+    }
+
+    /// Write persistent keys to the backing file (no-op for in-memory stores)
+    pub fn save(&self) -> StateResult<()> {
+        let Some(path) = &self.inner.path else {
+            return Ok(());
+        };
+        let values: Map<String, Value> = {
+            let persistent = self.inner.persistent.read();
+            let data = self.inner.data.read();
+            data.root
+                .as_object()
+                .map(|root| {
+                    root.iter()
+                        .filter(|(key, _)| persistent.contains(*key))
+                        .map(|(key, value)| (key.clone(), value.clone()))
+                        .collect()
+                })
+                .unwrap_or_default()
+        };
+        let data = serde_json::to_vec_pretty(&StateFile {
+            version: STATE_FILE_VERSION,
+            values,
+        })?;
+
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        // Write to a temporary file first so a crash never truncates the state
+        let tmp = path.with_extension("json.tmp");
+        std::fs::write(&tmp, data)?;
+        std::fs::rename(&tmp, path)?;
+        Ok(())
+    }
+}
@@ -88,6 +88,8 @@ fn test_backend_config_clone() {
         html: None,
         parent_handle: Some(12345),
         asset_root: Some(PathBuf::from("/assets")),
+        data_dir: None,
+        cookie_jar: None,
         settings: WebViewSettingsImpl::default(),
     };
 
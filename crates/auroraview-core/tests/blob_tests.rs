@@ -0,0 +1,314 @@
+//! Blob store tests
+
+use std::thread;
+use std::time::Duration;
+
+use auroraview_core::ipc::{
+    blob_id_from_uri, blob_url, is_blob_uri, BlobError, BlobHandle, BlobLifetime, BlobStore,
+    DEFAULT_BLOB_MIME,
+};
+use http::{header, Request};
+use rstest::rstest;
+
+fn request(method: &str, uri: &str, body: Vec<u8>) -> Request<Vec<u8>> {
+    Request::builder()
+        .method(method)
+        .uri(uri)
+        .body(body)
+        .unwrap()
+}
+
+fn upload_url(query: &str) -> String {
+    format!("auroraview://localhost/type:blob/{}", query)
+}
+
+// ─── URLs ────────────────────────────────────────────────────────────────────
+
+#[rstest]
+#[case("auroraview://localhost/type:blob/abc123", Some("abc123"))]
+#[case("https://auroraview.localhost/type:blob/abc123", Some("abc123"))]
+#[case("https://auroraview.localhost/type:blob/abc123?x=1", Some("abc123"))]
+#[case("auroraview://localhost/type:blob/", Some(""))]
+#[case("auroraview://localhost/type:blob", Some(""))]
+#[case("auroraview://localhost/type:blob?lifetime=one-shot", Some(""))]
+#[case("auroraview://localhost/type:blobs/abc", None)]
+#[case("auroraview://localhost/assets/type:blob/abc", None)]
+#[case("auroraview://localhost/index.html", None)]
+fn blob_id_parsing(#[case] uri: &str, #[case] expected: Option<&str>) {
+    assert_eq!(blob_id_from_uri(uri).as_deref(), expected);
+    assert_eq!(is_blob_uri(uri), expected.is_some());
+}
+
+#[test]
+fn blob_url_round_trips() {
+    let url = blob_url("deadbeef");
+    assert!(url.ends_with("/type:blob/deadbeef"));
+    assert_eq!(blob_id_from_uri(&url).as_deref(), Some("deadbeef"));
+}
+
+#[rstest]
+#[case("manual", Some(BlobLifetime::Manual))]
+#[case("one-shot", Some(BlobLifetime::OneShot))]
+#[case("ttl:30", Some(BlobLifetime::Ttl(Duration::from_secs(30))))]
+#[case("ttl=5", Some(BlobLifetime::Ttl(Duration::from_secs(5))))]
+#[case("forever", None)]
+#[case("ttl:abc", None)]
+fn lifetime_parsing(#[case] value: &str, #[case] expected: Option<BlobLifetime>) {
+    assert_eq!(BlobLifetime::parse(value), expected);
+}
+
+// ─── Store ───────────────────────────────────────────────────────────────────
+
+#[test]
+fn insert_and_get() {
+    let store = BlobStore::new();
+    let handle = store.insert(vec![1, 2, 3], "image/x-exr").unwrap();
+
+    assert_eq!(handle.size, 3);
+    assert_eq!(handle.mime, "image/x-exr");
+    assert_eq!(handle.url, blob_url(&handle.id));
+    assert_eq!(store.len(), 1);
+    assert_eq!(store.total_bytes(), 3);
+
+    let blob = store.get(&handle.id).unwrap();
+    assert_eq!(blob.data.as_slice(), &[1, 2, 3]);
+    assert_eq!(store.handle(&handle.id), Some(handle));
+}
+
+#[test]
+fn release_frees_bytes() {
+    let store = BlobStore::new();
+    let handle = store.insert(vec![0; 10], "").unwrap();
+
+    assert!(store.release(&handle.id));
+    assert!(!store.release(&handle.id));
+    assert!(store.is_empty());
+    assert_eq!(store.total_bytes(), 0);
+}
+
+#[test]
+fn take_removes_without_copy() {
+    let store = BlobStore::new();
+    let handle = store.insert(vec![7; 4], "").unwrap();
+
+    let blob = store.take(&handle.id).unwrap();
+    assert_eq!(blob.into_bytes(), vec![7; 4]);
+    assert!(!store.contains(&handle.id));
+}
+
+#[test]
+fn one_shot_is_removed_after_fetch() {
+    let store = BlobStore::new();
+    let handle = store
+        .insert_with_lifetime(vec![1], "", BlobLifetime::OneShot)
+        .unwrap();
+
+    // get() does not consume
+    assert!(store.get(&handle.id).is_some());
+    assert!(store.fetch(&handle.id).is_some());
+    assert!(store.fetch(&handle.id).is_none());
+    assert_eq!(store.total_bytes(), 0);
+}
+
+#[test]
+fn ttl_expires() {
+    let store = BlobStore::new();
+    let handle = store
+        .insert_with_lifetime(vec![1], "", BlobLifetime::Ttl(Duration::from_millis(20)))
+        .unwrap();
+    assert!(store.contains(&handle.id));
+
+    thread::sleep(Duration::from_millis(40));
+    assert!(store.get(&handle.id).is_none());
+    assert!(store.fetch(&handle.id).is_none());
+    assert_eq!(store.purge_expired(), 0);
+    assert_eq!(store.total_bytes(), 0);
+}
+
+#[test]
+fn max_bytes_is_enforced() {
+    let store = BlobStore::new().with_max_bytes(10);
+    store.insert(vec![0; 6], "").unwrap();
+
+    let err = store.insert(vec![0; 6], "").unwrap_err();
+    assert_eq!(
+        err,
+        BlobError::TooLarge {
+            size: 6,
+            available: 4,
+            limit: 10
+        }
+    );
+    assert!(store.insert(vec![0; 4], "").is_ok());
+}
+
+#[test]
+fn clear_frees_everything_for_all_clones() {
+    let store = BlobStore::new();
+    let handler_side = store.clone();
+    store.insert(vec![0; 8], "").unwrap();
+    store.insert(vec![0; 8], "").unwrap();
+    assert_eq!(handler_side.len(), 2);
+
+    store.clear();
+    assert!(handler_side.is_empty());
+    assert_eq!(handler_side.total_bytes(), 0);
+}
+
+#[test]
+fn store_is_shared_across_threads() {
+    let store = BlobStore::new();
+    let handles: Vec<_> = (0..4)
+        .map(|i| {
+            let store = store.clone();
+            thread::spawn(move || store.insert(vec![i; 16], "").unwrap())
+        })
+        .collect();
+    let ids: Vec<String> = handles.into_iter().map(|h| h.join().unwrap().id).collect();
+
+    assert_eq!(store.len(), 4);
+    assert_eq!(store.total_bytes(), 64);
+    for id in ids {
+        assert!(store.contains(&id));
+    }
+}
+
+// ─── Protocol ────────────────────────────────────────────────────────────────
+
+#[test]
+fn get_serves_bytes_with_mime() {
+    let store = BlobStore::new();
+    let handle = store.insert(vec![9; 5], "model/gltf-binary").unwrap();
+
+    let response = store.handle_request(request("GET", &handle.url, Vec::new()));
+    assert_eq!(response.status(), 200);
+    assert_eq!(response.body().as_ref(), &[9; 5]);
+    assert_eq!(
+        response.headers()[header::CONTENT_TYPE],
+        "model/gltf-binary"
+    );
+    assert_eq!(response.headers()[header::CONTENT_LENGTH], "5");
+    assert_eq!(response.headers()[header::ACCESS_CONTROL_ALLOW_ORIGIN], "*");
+    // Manual blobs stay available
+    assert!(store.contains(&handle.id));
+}
+
+#[test]
+fn head_has_no_body() {
+    let store = BlobStore::new();
+    let handle = store.insert(vec![1; 5], "").unwrap();
+
+    let response = store.handle_request(request("HEAD", &handle.url, Vec::new()));
+    assert_eq!(response.status(), 200);
+    assert!(response.body().is_empty());
+    assert_eq!(response.headers()[header::CONTENT_LENGTH], "5");
+}
+
+#[test]
+fn head_keeps_one_shot_blob() {
+    let store = BlobStore::new();
+    let handle = store
+        .insert_with_lifetime(vec![1; 3], "", BlobLifetime::OneShot)
+        .unwrap();
+
+    let response = store.handle_request(request("HEAD", &handle.url, Vec::new()));
+    assert_eq!(response.status(), 200);
+    assert_eq!(response.headers()[header::CONTENT_LENGTH], "3");
+    assert!(store.contains(&handle.id));
+
+    let response = store.handle_request(request("GET", &handle.url, Vec::new()));
+    assert_eq!(response.body().as_ref(), [1; 3]);
+    assert!(!store.contains(&handle.id));
+}
+
+#[test]
+fn unknown_blob_is_not_found() {
+    let store = BlobStore::new();
+    let response = store.handle_request(request("GET", &upload_url("missing"), Vec::new()));
+    assert_eq!(response.status(), 404);
+}
+
+#[test]
+fn post_stores_body_and_returns_handle() {
+    let store = BlobStore::new();
+    let request = Request::builder()
+        .method("POST")
+        .uri(upload_url(""))
+        .header(header::CONTENT_TYPE, "image/png")
+        .body(vec![1, 2, 3, 4])
+        .unwrap();
+
+    let response = store.handle_request(request);
+    assert_eq!(response.status(), 201);
+    let handle: BlobHandle = serde_json::from_slice(response.body()).unwrap();
+    assert_eq!(handle.size, 4);
+    assert_eq!(handle.mime, "image/png");
+    assert_eq!(
+        store.take(&handle.id).unwrap().into_bytes(),
+        vec![1, 2, 3, 4]
+    );
+}
+
+#[test]
+fn post_with_lifetime() {
+    let store = BlobStore::new();
+    let response =
+        store.handle_request(request("POST", &upload_url("?lifetime=one-shot"), vec![1]));
+    let handle: BlobHandle = serde_json::from_slice(response.body()).unwrap();
+    assert_eq!(handle.mime, DEFAULT_BLOB_MIME);
+
+    assert_eq!(
+        store
+            .handle_request(request("GET", &handle.url, Vec::new()))
+            .status(),
+        200
+    );
+    assert_eq!(
+        store
+            .handle_request(request("GET", &handle.url, Vec::new()))
+            .status(),
+        404
+    );
+
+    let response = store.handle_request(request("POST", &upload_url("?lifetime=bogus"), vec![1]));
+    assert_eq!(response.status(), 400);
+}
+
+#[test]
+fn post_over_limit_is_rejected() {
+    let store = BlobStore::new().with_max_bytes(2);
+    let response = store.handle_request(request("POST", &upload_url(""), vec![0; 3]));
+    assert_eq!(response.status(), 413);
+    assert!(store.is_empty());
+}
+
+#[test]
+fn post_to_existing_id_is_not_allowed() {
+    let store = BlobStore::new();
+    let response = store.handle_request(request("POST", &upload_url("abc"), vec![0]));
+    assert_eq!(response.status(), 405);
+}
+
+#[test]
+fn delete_releases() {
+    let store = BlobStore::new();
+    let handle = store.insert(vec![1], "").unwrap();
+
+    let response = store.handle_request(request("DELETE", &handle.url, Vec::new()));
+    assert_eq!(response.status(), 204);
+    assert!(store.is_empty());
+
+    let response = store.handle_request(request("DELETE", &handle.url, Vec::new()));
+    assert_eq!(response.status(), 404);
+}
+
+#[test]
+fn options_answers_preflight() {
+    let store = BlobStore::new();
+    let response = store.handle_request(request("OPTIONS", &upload_url(""), Vec::new()));
+    assert_eq!(response.status(), 204);
+    assert!(response.headers()[header::ACCESS_CONTROL_ALLOW_METHODS]
+        .to_str()
+        .unwrap()
+        .contains("POST"));
+}
@@ -0,0 +1,560 @@
+//! Tests for flow-controlled streaming channels
+
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+
+use auroraview_core::ipc::{
+    ChannelConfig, ChannelError, ChannelFrame, ChannelRegistry, ChannelSink, FlowChannel,
+    OverflowPolicy, SendOutcome, CHANNEL_CLOSE_EVENT, CHANNEL_MESSAGE_EVENT, CHANNEL_OPEN_EVENT,
+    DEFAULT_CHANNEL_CAPACITY, DEFAULT_CHANNEL_CREDITS,
+};
+use rstest::rstest;
+use serde_json::{json, Value};
+
+type Frames = Arc<Mutex<Vec<ChannelFrame>>>;
+
+fn recorder() -> (ChannelSink, Frames) {
+    let frames: Frames = Arc::new(Mutex::new(Vec::new()));
+    let sink_frames = frames.clone();
+    let sink: ChannelSink = Arc::new(move |frame| sink_frames.lock().unwrap().push(frame));
+    (sink, frames)
+}
+
+fn open(config: ChannelConfig) -> (FlowChannel, Frames) {
+    let (sink, frames) = recorder();
+    (FlowChannel::open("ch", config, sink), frames)
+}
+
+fn config(credits: u32, capacity: usize, policy: OverflowPolicy) -> ChannelConfig {
+    ChannelConfig::default()
+        .with_credits(credits)
+        .with_capacity(capacity)
+        .with_policy(policy)
+}
+
+/// Payloads of delivered messages
+fn delivered(frames: &Frames) -> Vec<Value> {
+    frames
+        .lock()
+        .unwrap()
+        .iter()
+        .filter_map(|frame| match frame {
+            ChannelFrame::Message { data, .. } => Some(data.clone()),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Sequence numbers of delivered messages
+fn seqs(frames: &Frames) -> Vec<u64> {
+    frames
+        .lock()
+        .unwrap()
+        .iter()
+        .filter_map(|frame| match frame {
+            ChannelFrame::Message { seq, .. } => Some(*seq),
+            _ => None,
+        })
+        .collect()
+}
+
+fn close_frames(frames: &Frames) -> usize {
+    frames
+        .lock()
+        .unwrap()
+        .iter()
+        .filter(|frame| matches!(frame, ChannelFrame::Close { .. }))
+        .count()
+}
+
+// ========== Config and policy ==========
+
+#[test]
+fn config_defaults() {
+    let config = ChannelConfig::default();
+    assert_eq!(config.credits, DEFAULT_CHANNEL_CREDITS);
+    assert_eq!(config.capacity, DEFAULT_CHANNEL_CAPACITY);
+    assert_eq!(config.policy, OverflowPolicy::Block);
+    assert!(config.block_timeout.is_none());
+}
+
+#[test]
+fn config_clamps_zero() {
+    let config = ChannelConfig::default().with_credits(0).with_capacity(0);
+    assert_eq!(config.credits, 1);
+    assert_eq!(config.capacity, 1);
+}
+
+#[rstest]
+#[case("block", Some(OverflowPolicy::Block))]
+#[case("drop_oldest", Some(OverflowPolicy::DropOldest))]
+#[case("drop-oldest", Some(OverflowPolicy::DropOldest))]
+#[case("coalesce", Some(OverflowPolicy::Coalesce))]
+#[case("latest", None)]
+fn policy_parse(#[case] input: &str, #[case] expected: Option<OverflowPolicy>) {
+    assert_eq!(OverflowPolicy::parse(input), expected);
+}
+
+#[rstest]
+#[case(OverflowPolicy::Block)]
+#[case(OverflowPolicy::DropOldest)]
+#[case(OverflowPolicy::Coalesce)]
+fn policy_roundtrip(#[case] policy: OverflowPolicy) {
+    assert_eq!(OverflowPolicy::parse(policy.as_str()), Some(policy));
+    let json = serde_json::to_value(policy).unwrap();
+    assert_eq!(json, json!(policy.as_str()));
+}
+
+// ========== Frames ==========
+
+#[test]
+fn open_sends_open_frame() {
+    let (_channel, frames) = open(config(4, 8, OverflowPolicy::DropOldest));
+    let frames = frames.lock().unwrap();
+    assert_eq!(frames.len(), 1);
+    assert_eq!(frames[0].event_name(), CHANNEL_OPEN_EVENT);
+    assert_eq!(
+        frames[0].payload(),
+        json!({"channel_id": "ch", "credits": 4, "capacity": 8, "policy": "drop_oldest"})
+    );
+}
+
+#[test]
+fn frame_payloads() {
+    let message = ChannelFrame::Message {
+        channel_id: "a".to_string(),
+        seq: 3,
+        data: json!({"line": "x"}),
+    };
+    assert_eq!(message.event_name(), CHANNEL_MESSAGE_EVENT);
+    assert_eq!(
+        message.payload(),
+        json!({"channel_id": "a", "seq": 3, "data": {"line": "x"}})
+    );
+
+    let close = ChannelFrame::Close {
+        channel_id: "a".to_string(),
+    };
+    assert_eq!(close.event_name(), CHANNEL_CLOSE_EVENT);
+    assert_eq!(close.payload(), json!({"channel_id": "a"}));
+}
+
+// ========== Credits ==========
+
+#[test]
+fn send_delivers_while_credits_last() {
+    let (channel, frames) = open(config(2, 8, OverflowPolicy::Block));
+    assert_eq!(channel.send(json!(1)).unwrap(), SendOutcome::Delivered);
+    assert_eq!(channel.send(json!(2)).unwrap(), SendOutcome::Delivered);
+    assert_eq!(channel.send(json!(3)).unwrap(), SendOutcome::Queued);
+
+    assert_eq!(delivered(&frames), vec![json!(1), json!(2)]);
+    let stats = channel.stats();
+    assert_eq!(stats.sent, 3);
+    assert_eq!(stats.delivered, 2);
+    assert_eq!(stats.buffered, 1);
+    assert_eq!(stats.credits, 0);
+}
+
+#[test]
+fn grant_flushes_buffer_in_order() {
+    let (channel, frames) = open(config(1, 8, OverflowPolicy::Block));
+    for i in 0..5 {
+        channel.send(json!(i)).unwrap();
+    }
+    assert_eq!(delivered(&frames), vec![json!(0)]);
+
+    channel.grant(2);
+    assert_eq!(delivered(&frames), vec![json!(0), json!(1), json!(2)]);
+    assert_eq!(channel.stats().buffered, 2);
+
+    channel.grant(10);
+    assert_eq!(seqs(&frames), vec![0, 1, 2, 3, 4]);
+    assert_eq!(channel.stats().credits, 8);
+}
+
+#[test]
+fn send_after_grant_keeps_order() {
+    let (channel, frames) = open(config(1, 8, OverflowPolicy::Block));
+    channel.send(json!("a")).unwrap();
+    channel.send(json!("b")).unwrap();
+    channel.grant(1);
+    channel.send(json!("c")).unwrap();
+    channel.grant(1);
+    assert_eq!(delivered(&frames), vec![json!("a"), json!("b"), json!("c")]);
+}
+
+// ========== Overflow policies ==========
+
+#[test]
+fn drop_oldest_discards_front() {
+    let (channel, frames) = open(config(1, 2, OverflowPolicy::DropOldest));
+    channel.send(json!(0)).unwrap();
+    channel.send(json!(1)).unwrap();
+    channel.send(json!(2)).unwrap();
+    assert_eq!(channel.send(json!(3)).unwrap(), SendOutcome::DroppedOldest);
+
+    channel.grant(5);
+    assert_eq!(delivered(&frames), vec![json!(0), json!(2), json!(3)]);
+    assert_eq!(seqs(&frames), vec![0, 2, 3]);
+    assert_eq!(channel.stats().dropped, 1);
+}
+
+#[test]
+fn coalesce_replaces_newest() {
+    let (channel, frames) = open(config(1, 1, OverflowPolicy::Coalesce));
+    channel.send(json!({"progress": 0})).unwrap();
+    channel.send(json!({"progress": 10})).unwrap();
+    assert_eq!(
+        channel.send(json!({"progress": 20})).unwrap(),
+        SendOutcome::Coalesced
+    );
+    assert_eq!(
+        channel.send(json!({"progress": 30})).unwrap(),
+        SendOutcome::Coalesced
+    );
+
+    channel.grant(1);
+    assert_eq!(
+        delivered(&frames),
+        vec![json!({"progress": 0}), json!({"progress": 30})]
+    );
+    assert_eq!(channel.stats().coalesced, 2);
+}
+
+#[test]
+fn block_waits_for_ack() {
+    let (channel, frames) = open(config(1, 1, OverflowPolicy::Block));
+    channel.send(json!(0)).unwrap();
+    channel.send(json!(1)).unwrap();
+
+    let producer = channel.clone();
+    let handle = thread::spawn(move || producer.send(json!(2)));
+    thread::sleep(Duration::from_millis(50));
+    assert!(!handle.is_finished());
+
+    channel.grant(1);
+    assert_eq!(handle.join().unwrap().unwrap(), SendOutcome::Queued);
+    channel.grant(1);
+    assert_eq!(delivered(&frames), vec![json!(0), json!(1), json!(2)]);
+}
+
+#[test]
+fn block_times_out() {
+    let (channel, _frames) =
+        open(config(1, 1, OverflowPolicy::Block).with_block_timeout(Duration::from_millis(20)));
+    channel.send(json!(0)).unwrap();
+    channel.send(json!(1)).unwrap();
+    assert_eq!(
+        channel.send(json!(2)),
+        Err(ChannelError::Timeout("ch".to_string()))
+    );
+    assert_eq!(channel.stats().sent, 2);
+}
+
+// ========== Close ==========
+
+#[test]
+fn close_without_buffer_sends_close_frame() {
+    let (channel, frames) = open(ChannelConfig::default());
+    channel.send(json!(1)).unwrap();
+    channel.close();
+    assert!(channel.is_closed());
+    assert!(channel.is_finished());
+    assert_eq!(close_frames(&frames), 1);
+    assert_eq!(
+        channel.send(json!(2)),
+        Err(ChannelError::Closed("ch".to_string()))
+    );
+}
+
+#[test]
+fn close_waits_for_buffered_messages() {
+    let (channel, frames) = open(config(1, 8, OverflowPolicy::Block));
+    channel.send(json!(0)).unwrap();
+    channel.send(json!(1)).unwrap();
+    channel.close();
+    assert!(channel.is_closed());
+    assert!(!channel.is_finished());
+    assert_eq!(close_frames(&frames), 0);
+
+    channel.grant(1);
+    assert!(channel.is_finished());
+    let frames = frames.lock().unwrap();
+    assert!(matches!(frames.last(), Some(ChannelFrame::Close { .. })));
+}
+
+#[test]
+fn close_is_idempotent() {
+    let (channel, frames) = open(ChannelConfig::default());
+    channel.close();
+    channel.close();
+    assert_eq!(close_frames(&frames), 1);
+}
+
+#[test]
+fn close_wakes_blocked_producer() {
+    let (channel, _frames) = open(config(1, 1, OverflowPolicy::Block));
+    channel.send(json!(0)).unwrap();
+    channel.send(json!(1)).unwrap();
+
+    let producer = channel.clone();
+    let handle = thread::spawn(move || producer.send(json!(2)));
+    thread::sleep(Duration::from_millis(20));
+    channel.close();
+    assert_eq!(
+        handle.join().unwrap(),
+        Err(ChannelError::Closed("ch".to_string()))
+    );
+}
+
+#[test]
+fn wait_finished_after_drain() {
+    let (channel, _frames) = open(config(1, 8, OverflowPolicy::Block));
+    channel.send(json!(0)).unwrap();
+    channel.send(json!(1)).unwrap();
+    channel.close();
+    assert!(!channel.wait_finished(Some(Duration::from_millis(10))));
+
+    let consumer = channel.clone();
+    let handle = thread::spawn(move || consumer.grant(1));
+    assert!(channel.wait_finished(Some(Duration::from_secs(5))));
+    handle.join().unwrap();
+}
+
+// ========== Cancellation ==========
+
+#[test]
+fn cancel_fails_sends_and_drops_buffer() {
+    let (channel, frames) = open(config(1, 8, OverflowPolicy::Block));
+    channel.send(json!(0)).unwrap();
+    channel.send(json!(1)).unwrap();
+    channel.send(json!(2)).unwrap();
+
+    assert!(channel.cancel(Some("user abort".to_string())));
+    assert!(channel.is_cancelled());
+    assert_eq!(channel.cancel_reason().as_deref(), Some("user abort"));
+    assert_eq!(channel.stats().dropped, 2);
+    assert_eq!(
+        channel.send(json!(3)),
+        Err(ChannelError::Cancelled {
+            id: "ch".to_string(),
+            reason: Some("user abort".to_string()),
+        })
+    );
+
+    // No close frame for a channel JavaScript cancelled
+    channel.grant(5);
+    assert_eq!(close_frames(&frames), 0);
+    assert_eq!(delivered(&frames), vec![json!(0)]);
+}
+
+#[test]
+fn cancel_error_message() {
+    let err = ChannelError::Cancelled {
+        id: "ch".to_string(),
+        reason: Some("tab closed".to_string()),
+    };
+    assert_eq!(err.to_string(), "channel 'ch' was cancelled: tab closed");
+    let err = ChannelError::Cancelled {
+        id: "ch".to_string(),
+        reason: None,
+    };
+    assert_eq!(err.to_string(), "channel 'ch' was cancelled");
+}
+
+#[test]
+fn cancel_wakes_blocked_producer() {
+    let (channel, _frames) = open(config(1, 1, OverflowPolicy::Block));
+    channel.send(json!(0)).unwrap();
+    channel.send(json!(1)).unwrap();
+
+    let producer = channel.clone();
+    let handle = thread::spawn(move || producer.send(json!(2)));
+    thread::sleep(Duration::from_millis(20));
+    channel.cancel(None);
+    assert!(matches!(
+        handle.join().unwrap(),
+        Err(ChannelError::Cancelled { reason: None, .. })
+    ));
+}
+
+#[test]
+fn cancel_runs_callbacks_once() {
+    let (channel, _frames) = open(ChannelConfig::default());
+    let reasons = Arc::new(Mutex::new(Vec::new()));
+    let sink = reasons.clone();
+    channel.on_cancel(move |reason| sink.lock().unwrap().push(reason.map(str::to_string)));
+
+    assert!(channel.cancel(Some("stop".to_string())));
+    assert!(!channel.cancel(Some("again".to_string())));
+    assert_eq!(*reasons.lock().unwrap(), vec![Some("stop".to_string())]);
+
+    // Registered after cancellation: runs immediately
+    let late = reasons.clone();
+    channel.on_cancel(move |reason| late.lock().unwrap().push(reason.map(str::to_string)));
+    assert_eq!(reasons.lock().unwrap().len(), 2);
+}
+
+#[test]
+fn cancel_after_close_is_noop() {
+    let (channel, _frames) = open(ChannelConfig::default());
+    channel.close();
+    assert!(!channel.cancel(None));
+    assert!(!channel.is_cancelled());
+}
+
+// ========== Registry ==========
+
+#[test]
+fn registry_generates_ids() {
+    let registry = ChannelRegistry::new();
+    let (sink, _) = recorder();
+    let a = registry.open(None, ChannelConfig::default(), sink.clone());
+    let b = registry.open(None, ChannelConfig::default(), sink);
+    assert_ne!(a.id(), b.id());
+    assert!(a.id().starts_with("stream_"));
+    assert_eq!(registry.len(), 2);
+}
+
+#[test]
+fn registry_routes_acks() {
+    let registry = ChannelRegistry::new();
+    let (sink, frames) = recorder();
+    let channel = registry.open(
+        Some("logs".to_string()),
+        config(1, 8, OverflowPolicy::Block),
+        sink,
+    );
+    channel.send(json!(0)).unwrap();
+    channel.send(json!(1)).unwrap();
+
+    assert!(registry.handle_ack(&json!({"channel_id": "logs", "credits": 1})));
+    assert_eq!(delivered(&frames), vec![json!(0), json!(1)]);
+    assert!(!registry.handle_ack(&json!({"channel_id": "missing", "credits": 1})));
+    assert!(!registry.handle_ack(&json!({"credits": 1})));
+}
+
+#[test]
+fn registry_ack_defaults_to_one_credit() {
+    let registry = ChannelRegistry::new();
+    let (sink, _frames) = recorder();
+    let channel = registry.open(
+        Some("a".to_string()),
+        config(1, 8, OverflowPolicy::Block),
+        sink,
+    );
+    channel.send(json!(0)).unwrap();
+    registry.handle_ack(&json!({"channel_id": "a"}));
+    assert_eq!(channel.stats().credits, 1);
+}
+
+#[test]
+fn registry_routes_cancel() {
+    let registry = ChannelRegistry::new();
+    let (sink, _frames) = recorder();
+    let channel = registry.open(Some("render".to_string()), ChannelConfig::default(), sink);
+
+    assert!(registry.handle_cancel(&json!({"channel_id": "render", "reason": "closed"})));
+    assert!(channel.is_cancelled());
+    assert_eq!(channel.cancel_reason().as_deref(), Some("closed"));
+    assert!(registry.is_empty());
+    assert!(!registry.handle_cancel(&json!({"channel_id": "render"})));
+}
+
+#[test]
+fn registry_removes_finished_channels() {
+    let registry = ChannelRegistry::new();
+    let (sink, _frames) = recorder();
+    let channel = registry.open(
+        Some("a".to_string()),
+        config(1, 8, OverflowPolicy::Block),
+        sink.clone(),
+    );
+    channel.send(json!(0)).unwrap();
+    channel.send(json!(1)).unwrap();
+    channel.close();
+    assert_eq!(registry.len(), 1);
+    registry.grant("a", 1);
+    assert!(registry.get("a").is_none());
+
+    let done = registry.open(Some("b".to_string()), ChannelConfig::default(), sink);
+    done.close();
+    assert_eq!(registry.prune(), 1);
+    assert!(registry.is_empty());
+}
+
+#[test]
+fn registry_replaces_duplicate_id() {
+    let registry = ChannelRegistry::new();
+    let (sink, _frames) = recorder();
+    let first = registry.open(
+        Some("x".to_string()),
+        ChannelConfig::default(),
+        sink.clone(),
+    );
+    let second = registry.open(Some("x".to_string()), ChannelConfig::default(), sink);
+    assert!(first.is_cancelled());
+    assert!(!second.is_cancelled());
+    assert_eq!(registry.ids(), vec!["x".to_string()]);
+}
+
+#[test]
+fn registry_cancel_all() {
+    let registry = ChannelRegistry::new();
+    let (sink, _frames) = recorder();
+    let a = registry.open(None, ChannelConfig::default(), sink.clone());
+    let b = registry.open(None, ChannelConfig::default(), sink);
+    b.close();
+    assert_eq!(registry.cancel_all("webview destroyed"), 1);
+    assert!(a.is_cancelled());
+    assert!(registry.is_empty());
+}
+
+#[test]
+fn registry_cancels_on_reload() {
+    let registry = ChannelRegistry::new();
+    let (sink, _frames) = recorder();
+    let early = registry.open(None, ChannelConfig::default(), sink.clone());
+
+    // Initial load: channels opened for the page survive
+    assert_eq!(registry.handle_page_load(), 0);
+    assert!(!early.is_cancelled());
+
+    let late = registry.open(None, ChannelConfig::default(), sink);
+    assert_eq!(registry.handle_page_load(), 2);
+    assert!(early.is_cancelled());
+    assert_eq!(late.cancel_reason().as_deref(), Some("page reloaded"));
+    assert!(registry.is_empty());
+}
+
+// ========== Concurrency ==========
+
+#[test]
+fn blocking_producer_with_consumer_delivers_everything() {
+    let (sink, frames) = recorder();
+    let channel = FlowChannel::open("bulk", config(4, 4, OverflowPolicy::Block), sink);
+
+    let producer = channel.clone();
+    let handle = thread::spawn(move || {
+        for i in 0..200 {
+            producer.send(json!(i)).unwrap();
+        }
+        producer.close();
+    });
+
+    // Consumer: acknowledge whatever was delivered since the last round
+    let mut acked = 0;
+    while !channel.wait_finished(Some(Duration::from_millis(1))) {
+        let count = seqs(&frames).len();
+        if count > acked {
+            channel.grant((count - acked) as u64);
+            acked = count;
+        }
+    }
+    handle.join().unwrap();
+
+    assert_eq!(seqs(&frames), (0..200).collect::<Vec<u64>>());
+    assert_eq!(close_frames(&frames), 1);
+}
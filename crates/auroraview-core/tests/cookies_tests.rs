@@ -0,0 +1,373 @@
+//! Cookie jar tests
+
+use std::sync::Arc;
+
+use auroraview_core::backend::{
+    BackendConfig, CookieInfo, HeadlessBackend, WebViewBackend, WryBackend,
+};
+use auroraview_core::cookies::{
+    parse_netscape, Cookie, CookieContext, CookieError, CookieJar, SameSite, COOKIE_JAR_FILE,
+};
+use auroraview_extensions::apis::cookies::{CookieDetails, CookiesApi, SetDetails};
+use rstest::rstest;
+use serde_json::json;
+
+const FUTURE: i64 = 4_102_444_800; // 2100-01-01
+const PAST: i64 = 946_684_800; // 2000-01-01
+
+fn names(cookies: &[Cookie]) -> Vec<&str> {
+    cookies.iter().map(|c| c.name.as_str()).collect()
+}
+
+#[test]
+fn test_persistence_roundtrip() {
+    let dir = tempfile::tempdir().unwrap();
+    {
+        let jar = CookieJar::for_profile(dir.path()).unwrap();
+        jar.set(Cookie::new("sid", "abc", "example.com").expires_at(FUTURE))
+            .unwrap();
+        jar.set(Cookie::new("session", "tmp", "example.com"))
+            .unwrap();
+    }
+    assert!(dir.path().join(COOKIE_JAR_FILE).exists());
+
+    let jar = CookieJar::for_profile(dir.path()).unwrap();
+    assert_eq!(jar.get("example.com", "sid").unwrap().value, "abc");
+    // Session cookies are not persisted by default
+    assert!(jar.get("example.com", "session").is_none());
+}
+
+#[test]
+fn test_persist_session_cookies() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("jar.json");
+    {
+        let jar = CookieJar::open(&path)
+            .unwrap()
+            .persist_session_cookies(true);
+        jar.set(Cookie::new("session", "tmp", "example.com"))
+            .unwrap();
+    }
+    let jar = CookieJar::open(&path).unwrap();
+    assert!(jar.get("example.com", "session").is_some());
+}
+
+#[test]
+fn test_expired_cookies() {
+    let jar = CookieJar::new();
+    jar.set(Cookie::new("a", "1", "example.com").expires_at(FUTURE))
+        .unwrap();
+    jar.set(Cookie::new("a", "2", "example.com").expires_at(PAST))
+        .unwrap();
+    assert!(jar.is_empty());
+}
+
+#[test]
+fn test_domain_and_path_matching() {
+    let jar = CookieJar::new();
+    jar.set(Cookie::new("root", "1", ".example.com")).unwrap();
+    jar.set(Cookie::new("host", "1", "example.com")).unwrap();
+    jar.set(Cookie::new("docs", "1", "example.com").with_path("/docs"))
+        .unwrap();
+
+    let ctx = CookieContext::default();
+    let cookies = jar
+        .cookies_for_url("https://example.com/docs/api", &ctx)
+        .unwrap();
+    // Longest path first
+    assert_eq!(names(&cookies)[0], "docs");
+    assert_eq!(cookies.len(), 3);
+
+    let cookies = jar
+        .cookies_for_url("https://www.example.com/", &ctx)
+        .unwrap();
+    assert_eq!(names(&cookies), vec!["root"]);
+}
+
+#[test]
+fn test_secure_cookies_need_secure_transport() {
+    let jar = CookieJar::new();
+    jar.set(Cookie::new("token", "1", "example.com").secure(true))
+        .unwrap();
+    let ctx = CookieContext::default();
+    assert!(jar
+        .cookies_for_url("http://example.com/", &ctx)
+        .unwrap()
+        .is_empty());
+    assert_eq!(
+        jar.cookie_header("https://example.com/", &ctx).unwrap(),
+        Some("token=1".to_string())
+    );
+}
+
+#[rstest]
+#[case(SameSite::Strict, false, false)]
+#[case(SameSite::Strict, true, false)]
+#[case(SameSite::Lax, false, false)]
+#[case(SameSite::Lax, true, true)]
+#[case(SameSite::Unspecified, true, true)]
+#[case(SameSite::None, false, true)]
+fn test_same_site_cross_site(
+    #[case] same_site: SameSite,
+    #[case] top_level_navigation: bool,
+    #[case] sent: bool,
+) {
+    let jar = CookieJar::new();
+    jar.set(
+        Cookie::new("c", "1", "api.example.com")
+            .secure(true)
+            .with_same_site(same_site),
+    )
+    .unwrap();
+    let ctx = CookieContext {
+        top_level_url: Some("https://other.org/page"),
+        top_level_navigation,
+    };
+    let cookies = jar
+        .cookies_for_url("https://api.example.com/", &ctx)
+        .unwrap();
+    assert_eq!(!cookies.is_empty(), sent);
+
+    // Same-site requests always carry the cookie
+    let same_site_ctx = CookieContext::embedded_in("https://www.example.com/");
+    assert_eq!(
+        jar.cookies_for_url("https://api.example.com/", &same_site_ctx)
+            .unwrap()
+            .len(),
+        1
+    );
+}
+
+#[test]
+fn test_partitioned_cookies() {
+    let jar = CookieJar::new();
+    jar.set(
+        Cookie::new("embed", "a", "widget.io")
+            .secure(true)
+            .with_same_site(SameSite::None)
+            .partitioned("https://site-a.com"),
+    )
+    .unwrap();
+    jar.set(
+        Cookie::new("embed", "b", "widget.io")
+            .secure(true)
+            .with_same_site(SameSite::None)
+            .partitioned("https://site-b.com"),
+    )
+    .unwrap();
+    assert_eq!(jar.len(), 2);
+
+    let in_a = jar
+        .cookies_for_url(
+            "https://widget.io/frame",
+            &CookieContext::embedded_in("https://www.site-a.com/"),
+        )
+        .unwrap();
+    assert_eq!(in_a.len(), 1);
+    assert_eq!(in_a[0].value, "a");
+
+    let standalone = jar
+        .cookies_for_url("https://widget.io/", &CookieContext::default())
+        .unwrap();
+    assert!(standalone.is_empty());
+}
+
+#[rstest]
+#[case(Cookie::new("", "v", "example.com"))]
+#[case(Cookie::new("n", "v", ""))]
+#[case(Cookie::new("n", "v", "example.com").with_path("docs"))]
+#[case(Cookie::new("n", "v", "example.com").with_same_site(SameSite::None))]
+#[case(Cookie::new("n", "v", "example.com").partitioned("https://a.com"))]
+fn test_invalid_cookies(#[case] cookie: Cookie) {
+    let jar = CookieJar::new();
+    assert!(matches!(
+        jar.set(cookie),
+        Err(CookieError::InvalidCookie(_))
+    ));
+}
+
+#[test]
+fn test_netscape_import_export() {
+    let content = "# Netscape HTTP Cookie File\n\
+        .example.com\tTRUE\t/\tFALSE\t4102444800\tsid\tabc\n\
+        #HttpOnly_example.com\tFALSE\t/app\tTRUE\t0\ttoken\txyz\n\
+        example.com\tFALSE\t/\tFALSE\t946684800\told\tgone\n\
+        example.com\tFALSE\t/\tFALSE\t0\tempty\n";
+
+    let jar = CookieJar::new();
+    assert_eq!(jar.import_netscape(content).unwrap(), 3);
+
+    let sid = jar.get("example.com", "sid").unwrap();
+    assert!(!sid.host_only);
+    assert_eq!(sid.expires, Some(FUTURE));
+
+    let token = jar.get("example.com", "token").unwrap();
+    assert!(token.http_only && token.secure && token.host_only);
+    assert!(token.is_session());
+    assert_eq!(jar.get("example.com", "empty").unwrap().value, "");
+
+    let exported = jar.export_netscape();
+    let reparsed = parse_netscape(&exported).unwrap();
+    assert_eq!(reparsed.len(), 3);
+    assert!(exported.contains("#HttpOnly_example.com\tFALSE\t/app\tTRUE\t0\ttoken\txyz"));
+}
+
+#[test]
+fn test_netscape_parse_error_reports_line() {
+    let err = parse_netscape("# header\nexample.com\tFALSE\t/\n").unwrap_err();
+    assert!(matches!(err, CookieError::Parse { line: 2, .. }));
+}
+
+#[test]
+fn test_shared_with_headless_backend() {
+    let jar = Arc::new(CookieJar::new());
+    let first = HeadlessBackend::new().with_cookie_jar(jar.clone());
+    let second = HeadlessBackend::new().with_cookie_jar(jar.clone());
+
+    first
+        .set_cookie(&CookieInfo::session(".example.com", "sid", "1"))
+        .unwrap();
+    let cookie = second.get_cookie("example.com", "sid").unwrap().unwrap();
+    assert_eq!(cookie.domain, ".example.com");
+    assert_eq!(jar.len(), 1);
+}
+
+#[test]
+fn test_shared_with_wry_backend() {
+    let jar = Arc::new(CookieJar::new());
+    let backend = WryBackend::new().with_cookie_jar(jar.clone());
+
+    backend
+        .set_cookie(&CookieInfo::session("example.com", "sid", "1"))
+        .unwrap();
+    assert_eq!(jar.get("example.com", "sid").unwrap().value, "1");
+    assert_eq!(
+        backend
+            .get_cookie("example.com", "sid")
+            .unwrap()
+            .unwrap()
+            .value,
+        "1"
+    );
+
+    backend.delete_cookie("example.com", "sid").unwrap();
+    assert!(jar.is_empty());
+}
+
+#[test]
+fn test_backend_config_opens_profile_jar() {
+    let dir = tempfile::tempdir().unwrap();
+    let config = BackendConfig {
+        data_dir: Some(dir.path().to_path_buf()),
+        ..Default::default()
+    };
+
+    let jar = config.open_cookie_jar().unwrap();
+    jar.set(Cookie::new("sid", "1", "example.com").expires_at(FUTURE))
+        .unwrap();
+    jar.set(Cookie::new("session", "1", "example.com")).unwrap();
+    assert!(dir.path().join(COOKIE_JAR_FILE).exists());
+
+    // Session cookies survive reopening the profile
+    let reopened = config.open_cookie_jar().unwrap();
+    assert!(reopened.get("example.com", "session").is_some());
+
+    let shared = BackendConfig {
+        cookie_jar: Some(jar.clone()),
+        ..config
+    };
+    assert!(Arc::ptr_eq(&shared.open_cookie_jar().unwrap(), &jar));
+}
+
+#[test]
+fn test_backend_config_without_session_persistence() {
+    let dir = tempfile::tempdir().unwrap();
+    let config = BackendConfig {
+        data_dir: Some(dir.path().to_path_buf()),
+        persist_session_cookies: false,
+        ..Default::default()
+    };
+
+    config
+        .open_cookie_jar()
+        .unwrap()
+        .set(Cookie::new("session", "1", "example.com"))
+        .unwrap();
+    let reopened = config.open_cookie_jar().unwrap();
+    assert!(reopened.get("example.com", "session").is_none());
+}
+
+#[test]
+fn test_replace_from_engine() {
+    let jar = CookieJar::new();
+    jar.set(Cookie::new("kept", "old", "example.com")).unwrap();
+    jar.set(Cookie::new("gone", "1", "example.com")).unwrap();
+    jar.set(Cookie::new("other", "1", "other.com")).unwrap();
+    jar.set(
+        Cookie::new("chip", "1", "example.com")
+            .partitioned("https://a.com")
+            .secure(true),
+    )
+    .unwrap();
+    let created = jar.get("example.com", "kept").unwrap().created;
+
+    // Engines report domains without the leading dot
+    let mut kept = Cookie::new("kept", "new", "example.com");
+    kept.host_only = false;
+    jar.replace_from_engine(
+        |c| c.domain == "example.com",
+        vec![
+            kept,
+            Cookie::new("fresh", "1", ".example.com"),
+            Cookie::new("stale", "1", "example.com").expires_at(PAST),
+        ],
+    )
+    .unwrap();
+
+    let kept = jar.get("example.com", "kept").unwrap();
+    assert_eq!(kept.value, "new");
+    assert!(kept.host_only);
+    assert_eq!(kept.created, created);
+    assert!(jar.get("example.com", "gone").is_none());
+    assert!(jar.get("example.com", "stale").is_none());
+    assert!(jar.get("example.com", "fresh").is_some());
+    assert!(jar.get("other.com", "other").is_some());
+    assert_eq!(jar.len(), 4);
+}
+
+#[test]
+fn test_shared_with_chrome_cookies() {
+    let jar = Arc::new(CookieJar::new());
+    let api = CookiesApi::with_store(jar.clone());
+
+    api.set(SetDetails {
+        url: "https://example.com/".to_string(),
+        name: "ext".to_string(),
+        value: Some("1".to_string()),
+        domain: None,
+        path: None,
+        secure: Some(true),
+        http_only: None,
+        same_site: None,
+        expiration_date: Some(FUTURE as f64),
+        store_id: None,
+    })
+    .unwrap();
+    let stored = jar.get("example.com", "ext").unwrap();
+    assert!(stored.secure);
+    assert_eq!(stored.expires, Some(FUTURE));
+
+    jar.set(Cookie::new("backend", "2", "example.com")).unwrap();
+    let all = api
+        .handle("getAll", json!({"domain": "example.com"}))
+        .unwrap();
+    assert_eq!(all.as_array().unwrap().len(), 2);
+
+    api.remove(CookieDetails {
+        url: "https://example.com/".to_string(),
+        name: "backend".to_string(),
+        store_id: None,
+    })
+    .unwrap();
+    assert!(jar.get("example.com", "backend").is_none());
+}
@@ -0,0 +1,375 @@
+//! File server tests: byte ranges, conditional requests and encodings
+
+use std::path::PathBuf;
+use std::time::Duration;
+
+use auroraview_core::file_server::{
+    http_date, parse_http_date, parse_range, serve_file, ByteRange, CachePolicy,
+    FileServerResponse, ServeOptions,
+};
+use http::{header, Request};
+use rstest::*;
+use tempfile::TempDir;
+
+const CONTENT: &[u8] = b"0123456789abcdefghij";
+
+struct Fixture {
+    _dir: TempDir,
+    path: PathBuf,
+}
+
+#[fixture]
+fn video() -> Fixture {
+    let dir = TempDir::new().unwrap();
+    let path = dir.path().join("clip.mp4");
+    std::fs::write(&path, CONTENT).unwrap();
+    Fixture { _dir: dir, path }
+}
+
+fn get(headers: &[(header::HeaderName, &str)]) -> Request<Vec<u8>> {
+    let mut builder = Request::builder()
+        .method("GET")
+        .uri("auroraview://clip.mp4");
+    for (name, value) in headers {
+        builder = builder.header(name, *value);
+    }
+    builder.body(Vec::new()).unwrap()
+}
+
+fn header_of(response: &FileServerResponse, name: header::HeaderName) -> &str {
+    response.headers()[name].to_str().unwrap()
+}
+
+// ============================================================================
+// Range Parsing Tests
+// ============================================================================
+
+#[rstest]
+#[case("bytes=0-4", Some(ByteRange::Partial { start: 0, end: 4 }))]
+#[case("bytes=5-", Some(ByteRange::Partial { start: 5, end: 19 }))]
+#[case("bytes=-3", Some(ByteRange::Partial { start: 17, end: 19 }))]
+#[case("bytes=-50", Some(ByteRange::Partial { start: 0, end: 19 }))]
+#[case("bytes=10-100", Some(ByteRange::Partial { start: 10, end: 19 }))]
+#[case("bytes=20-", Some(ByteRange::Unsatisfiable))]
+#[case("bytes=-0", Some(ByteRange::Unsatisfiable))]
+#[case("bytes=5-2", None)]
+#[case("bytes=0-1,4-5", None)]
+#[case("items=0-4", None)]
+#[case("bytes=a-b", None)]
+fn test_parse_range(#[case] value: &str, #[case] expected: Option<ByteRange>) {
+    assert_eq!(parse_range(value, CONTENT.len() as u64), expected);
+}
+
+#[test]
+fn test_http_date_parsing() {
+    let secs = parse_http_date("Thu, 01 Jan 2026 12:00:00 GMT").unwrap();
+    let time = std::time::UNIX_EPOCH + Duration::from_secs(secs);
+    assert_eq!(http_date(time), "Thu, 01 Jan 2026 12:00:00 GMT");
+    assert_eq!(parse_http_date("not a date"), None);
+}
+
+#[rstest]
+#[case(CachePolicy::Revalidate, "no-cache")]
+#[case(CachePolicy::NoStore, "no-store")]
+#[case(CachePolicy::MaxAge(Duration::from_secs(60)), "max-age=60")]
+#[case(
+    CachePolicy::Immutable(Duration::from_secs(31_536_000)),
+    "max-age=31536000, immutable"
+)]
+fn test_cache_policy_header(#[case] policy: CachePolicy, #[case] expected: &str) {
+    assert_eq!(policy.header_value(), expected);
+}
+
+// ============================================================================
+// Full Response Tests
+// ============================================================================
+
+#[rstest]
+fn test_full_response_has_validators(video: Fixture) {
+    let response = serve_file(&video.path, &get(&[]), &ServeOptions::default()).unwrap();
+
+    assert_eq!(response.status(), 200);
+    assert_eq!(response.body().as_ref(), CONTENT);
+    assert_eq!(header_of(&response, header::CONTENT_TYPE), "video/mp4");
+    assert_eq!(header_of(&response, header::CONTENT_LENGTH), "20");
+    assert_eq!(header_of(&response, header::ACCEPT_RANGES), "bytes");
+    assert_eq!(header_of(&response, header::CACHE_CONTROL), "no-cache");
+    assert!(header_of(&response, header::ETAG).starts_with('"'));
+    assert!(parse_http_date(header_of(&response, header::LAST_MODIFIED)).is_some());
+    assert!(response.headers().get(header::VARY).is_none());
+}
+
+#[rstest]
+fn test_head_request_has_no_body(video: Fixture) {
+    let request = Request::builder()
+        .method("HEAD")
+        .uri("auroraview://clip.mp4")
+        .body(Vec::<u8>::new())
+        .unwrap();
+    let response = serve_file(&video.path, &request, &ServeOptions::default()).unwrap();
+
+    assert_eq!(response.status(), 200);
+    assert!(response.body().is_empty());
+    assert_eq!(header_of(&response, header::CONTENT_LENGTH), "20");
+}
+
+#[rstest]
+fn test_missing_file_and_directory_are_errors(video: Fixture) {
+    let options = ServeOptions::default();
+    assert!(serve_file(&video.path.with_extension("mov"), &get(&[]), &options).is_err());
+    assert!(serve_file(video.path.parent().unwrap(), &get(&[]), &options).is_err());
+}
+
+#[rstest]
+fn test_cors_and_cache_policy(video: Fixture) {
+    let options = ServeOptions::default()
+        .with_cors(true)
+        .with_cache_policy(CachePolicy::MaxAge(Duration::from_secs(3600)));
+    let response = serve_file(&video.path, &get(&[]), &options).unwrap();
+
+    assert_eq!(
+        header_of(&response, header::ACCESS_CONTROL_ALLOW_ORIGIN),
+        "*"
+    );
+    assert_eq!(header_of(&response, header::CACHE_CONTROL), "max-age=3600");
+}
+
+// ============================================================================
+// Range Response Tests
+// ============================================================================
+
+#[rstest]
+fn test_range_returns_partial_content(video: Fixture) {
+    let request = get(&[(header::RANGE, "bytes=2-5")]);
+    let response = serve_file(&video.path, &request, &ServeOptions::default()).unwrap();
+
+    assert_eq!(response.status(), 206);
+    assert_eq!(response.body().as_ref(), b"2345");
+    assert_eq!(header_of(&response, header::CONTENT_RANGE), "bytes 2-5/20");
+    assert_eq!(header_of(&response, header::CONTENT_LENGTH), "4");
+}
+
+#[rstest]
+fn test_open_ended_range_is_capped(video: Fixture) {
+    let options = ServeOptions::default().with_max_range_length(8);
+    let request = get(&[(header::RANGE, "bytes=4-")]);
+    let response = serve_file(&video.path, &request, &options).unwrap();
+
+    assert_eq!(response.status(), 206);
+    assert_eq!(response.body().as_ref(), b"456789ab");
+    assert_eq!(header_of(&response, header::CONTENT_RANGE), "bytes 4-11/20");
+}
+
+#[rstest]
+fn test_large_file_without_range_is_refused(video: Fixture) {
+    let options = ServeOptions::default()
+        .with_max_body_length(10)
+        .with_max_range_length(8);
+    let response = serve_file(&video.path, &get(&[]), &options).unwrap();
+
+    assert_eq!(response.status(), 413);
+    assert!(response.body().is_empty());
+    assert!(response.headers().get(header::CONTENT_RANGE).is_none());
+    assert_eq!(header_of(&response, header::ACCEPT_RANGES), "bytes");
+
+    // Ranges are still served, capped at `max_range_length`
+    let request = get(&[(header::RANGE, "bytes=0-")]);
+    let response = serve_file(&video.path, &request, &options).unwrap();
+    assert_eq!(response.status(), 206);
+    assert_eq!(response.body().as_ref(), b"01234567");
+    assert_eq!(header_of(&response, header::CONTENT_RANGE), "bytes 0-7/20");
+
+    // Files up to the limit are still served in full
+    let options = options.with_max_body_length(20);
+    let response = serve_file(&video.path, &get(&[]), &options).unwrap();
+    assert_eq!(response.status(), 200);
+    assert_eq!(response.body().as_ref(), CONTENT);
+}
+
+#[rstest]
+fn test_unsatisfiable_range(video: Fixture) {
+    let request = get(&[(header::RANGE, "bytes=100-")]);
+    let response = serve_file(&video.path, &request, &ServeOptions::default()).unwrap();
+
+    assert_eq!(response.status(), 416);
+    assert!(response.body().is_empty());
+    assert_eq!(header_of(&response, header::CONTENT_RANGE), "bytes */20");
+}
+
+#[rstest]
+fn test_if_range_with_stale_validator_serves_full_file(video: Fixture) {
+    let options = ServeOptions::default();
+    let etag = header_of(
+        &serve_file(&video.path, &get(&[]), &options).unwrap(),
+        header::ETAG,
+    )
+    .to_string();
+
+    let current = get(&[(header::RANGE, "bytes=0-1"), (header::IF_RANGE, &etag)]);
+    assert_eq!(
+        serve_file(&video.path, &current, &options)
+            .unwrap()
+            .status(),
+        206
+    );
+
+    let stale = get(&[(header::RANGE, "bytes=0-1"), (header::IF_RANGE, "\"old\"")]);
+    let response = serve_file(&video.path, &stale, &options).unwrap();
+    assert_eq!(response.status(), 200);
+    assert_eq!(response.body().len(), CONTENT.len());
+}
+
+// ============================================================================
+// Conditional Request Tests
+// ============================================================================
+
+#[rstest]
+fn test_if_none_match_returns_not_modified(video: Fixture) {
+    let options = ServeOptions::default();
+    let first = serve_file(&video.path, &get(&[]), &options).unwrap();
+    let etag = header_of(&first, header::ETAG).to_string();
+
+    for value in [
+        etag.clone(),
+        format!("W/{}", etag),
+        "\"other\", *".to_string(),
+    ] {
+        let request = get(&[(header::IF_NONE_MATCH, &value)]);
+        let response = serve_file(&video.path, &request, &options).unwrap();
+        assert_eq!(response.status(), 304, "If-None-Match: {}", value);
+        assert!(response.body().is_empty());
+        assert_eq!(header_of(&response, header::ETAG), etag);
+    }
+
+    let request = get(&[(header::IF_NONE_MATCH, "\"other\"")]);
+    assert_eq!(
+        serve_file(&video.path, &request, &options)
+            .unwrap()
+            .status(),
+        200
+    );
+}
+
+#[rstest]
+fn test_if_modified_since(video: Fixture) {
+    let options = ServeOptions::default();
+    let first = serve_file(&video.path, &get(&[]), &options).unwrap();
+    let last_modified = header_of(&first, header::LAST_MODIFIED).to_string();
+
+    let request = get(&[(header::IF_MODIFIED_SINCE, &last_modified)]);
+    assert_eq!(
+        serve_file(&video.path, &request, &options)
+            .unwrap()
+            .status(),
+        304
+    );
+
+    let request = get(&[(header::IF_MODIFIED_SINCE, "Thu, 01 Jan 1970 00:00:00 GMT")]);
+    assert_eq!(
+        serve_file(&video.path, &request, &options)
+            .unwrap()
+            .status(),
+        200
+    );
+}
+
+#[rstest]
+fn test_etag_changes_with_content(video: Fixture) {
+    let options = ServeOptions::default();
+    let before = serve_file(&video.path, &get(&[]), &options).unwrap();
+    std::fs::write(&video.path, b"changed").unwrap();
+    let after = serve_file(&video.path, &get(&[]), &options).unwrap();
+
+    assert_ne!(
+        header_of(&before, header::ETAG),
+        header_of(&after, header::ETAG)
+    );
+}
+
+// ============================================================================
+// Pre-compressed Sibling Tests
+// ============================================================================
+
+#[fixture]
+fn script() -> Fixture {
+    let dir = TempDir::new().unwrap();
+    let path = dir.path().join("app.js");
+    std::fs::write(&path, b"console.log('plain')").unwrap();
+    std::fs::write(dir.path().join("app.js.br"), b"BR").unwrap();
+    std::fs::write(dir.path().join("app.js.gz"), b"GZ").unwrap();
+    Fixture { _dir: dir, path }
+}
+
+#[rstest]
+#[case("gzip, deflate, br", Some("br"), b"BR".as_slice())]
+#[case("gzip", Some("gzip"), b"GZ".as_slice())]
+#[case("br;q=0, gzip", Some("gzip"), b"GZ".as_slice())]
+#[case("identity", None, b"console.log('plain')".as_slice())]
+fn test_precompressed_sibling(
+    script: Fixture,
+    #[case] accept: &str,
+    #[case] encoding: Option<&str>,
+    #[case] body: &[u8],
+) {
+    let options = ServeOptions::default().with_precompressed(true);
+    let request = get(&[(header::ACCEPT_ENCODING, accept)]);
+    let response = serve_file(&script.path, &request, &options).unwrap();
+
+    assert_eq!(response.status(), 200);
+    assert_eq!(response.body().as_ref(), body);
+    assert_eq!(
+        response
+            .headers()
+            .get(header::CONTENT_ENCODING)
+            .map(|v| v.to_str().unwrap()),
+        encoding
+    );
+    assert!(header_of(&response, header::CONTENT_TYPE).contains("javascript"));
+    assert_eq!(header_of(&response, header::VARY), "Accept-Encoding");
+}
+
+#[rstest]
+fn test_precompressed_disabled_or_ranged(script: Fixture) {
+    let request = get(&[(header::ACCEPT_ENCODING, "br")]);
+    let response = serve_file(&script.path, &request, &ServeOptions::default()).unwrap();
+    assert!(response.headers().get(header::CONTENT_ENCODING).is_none());
+
+    // Ranges always address the identity representation
+    let options = ServeOptions::default().with_precompressed(true);
+    let request = get(&[
+        (header::ACCEPT_ENCODING, "br"),
+        (header::RANGE, "bytes=0-6"),
+    ]);
+    let response = serve_file(&script.path, &request, &options).unwrap();
+    assert_eq!(response.status(), 206);
+    assert_eq!(response.body().as_ref(), b"console");
+}
+
+#[rstest]
+fn test_large_precompressed_sibling_is_skipped(script: Fixture) {
+    let options = ServeOptions::default()
+        .with_precompressed(true)
+        .with_max_body_length(1);
+    let request = get(&[(header::ACCEPT_ENCODING, "br")]);
+    let response = serve_file(&script.path, &request, &options).unwrap();
+
+    assert!(response.headers().get(header::CONTENT_ENCODING).is_none());
+    assert_eq!(response.status(), 413);
+}
+
+#[rstest]
+fn test_encoded_variants_have_distinct_etags(script: Fixture) {
+    let options = ServeOptions::default().with_precompressed(true);
+    let br = serve_file(
+        &script.path,
+        &get(&[(header::ACCEPT_ENCODING, "br")]),
+        &options,
+    )
+    .unwrap();
+    let plain = serve_file(&script.path, &get(&[]), &options).unwrap();
+
+    assert_ne!(
+        header_of(&br, header::ETAG),
+        header_of(&plain, header::ETAG)
+    );
+}
@@ -0,0 +1,234 @@
+//! Headless backend tests
+
+use std::sync::{Arc, Mutex};
+
+use auroraview_core::backend::{
+    BackendConfig, BackendFactory, BackendType, CookieInfo, EmbeddableBackend, HeadlessBackend,
+    LifecycleState, NavigationState, ProcessResult, WebViewBackend, WebViewError,
+    HEADLESS_HTML_URL,
+};
+use rstest::rstest;
+use serde_json::json;
+
+fn eval(backend: &dyn WebViewBackend, script: &str) -> Result<serde_json::Value, WebViewError> {
+    let result = Arc::new(Mutex::new(None));
+    let slot = result.clone();
+    backend
+        .eval_js_with_callback(
+            script,
+            Box::new(move |r| {
+                *slot.lock().unwrap() = Some(r);
+            }),
+        )
+        .unwrap();
+    let value = result.lock().unwrap().take();
+    value.expect("callback should run synchronously")
+}
+
+#[rstest]
+#[case("headless")]
+#[case("NULL")]
+#[case("offscreen")]
+fn test_backend_type_from_str_headless(#[case] input: &str) {
+    assert_eq!(input.parse::<BackendType>().unwrap(), BackendType::Headless);
+    assert_eq!(BackendType::Headless.to_string(), "headless");
+}
+
+#[test]
+fn test_headless_always_available() {
+    assert!(BackendFactory::available_backends().contains(&BackendType::Headless));
+}
+
+#[test]
+fn test_factory_creates_headless_backend() {
+    let config = BackendConfig {
+        backend_type: BackendType::Headless,
+        html: Some("<title>Tool</title><p>hi</p>".to_string()),
+        ..Default::default()
+    };
+    let backend = BackendFactory::create(&config).unwrap();
+
+    assert_eq!(backend.lifecycle_state(), LifecycleState::Active);
+    assert_eq!(backend.url().as_deref(), Some(HEADLESS_HTML_URL));
+    assert_eq!(backend.title().as_deref(), Some("Tool"));
+    assert!(!backend.is_loading());
+}
+
+#[test]
+fn test_navigation_history() {
+    let backend = HeadlessBackend::new();
+    assert!(!backend.can_go_back());
+    assert!(matches!(
+        backend.go_back(),
+        Err(WebViewError::Navigation(_))
+    ));
+
+    backend.navigate("https://example.com/a").unwrap();
+    backend.navigate("https://example.com/b").unwrap();
+    backend.navigate("https://example.com/c").unwrap();
+    assert!(backend.can_go_back());
+    assert!(!backend.can_go_forward());
+
+    backend.go_back().unwrap();
+    backend.go_back().unwrap();
+    assert_eq!(backend.url().as_deref(), Some("https://example.com/a"));
+    assert!(backend.can_go_forward());
+
+    backend.go_forward().unwrap();
+    assert_eq!(backend.url().as_deref(), Some("https://example.com/b"));
+
+    // Navigating drops the forward entries
+    backend.navigate("https://example.com/d").unwrap();
+    assert!(!backend.can_go_forward());
+    let (entries, index) = backend.history();
+    assert_eq!(
+        entries,
+        vec![
+            "https://example.com/a",
+            "https://example.com/b",
+            "https://example.com/d"
+        ]
+    );
+    assert_eq!(index, 2);
+}
+
+#[test]
+fn test_invalid_url_is_navigation_error() {
+    let backend = HeadlessBackend::new();
+    assert!(matches!(
+        backend.navigate("not a url"),
+        Err(WebViewError::Navigation(_))
+    ));
+    assert!(backend.url().is_none());
+}
+
+#[test]
+fn test_manual_load_progress() {
+    let backend = HeadlessBackend::new();
+    backend.set_auto_complete(false);
+
+    backend.navigate("https://example.com").unwrap();
+    assert!(backend.is_loading());
+    assert_eq!(backend.load_progress().percent, 0);
+
+    backend.set_load_progress(60);
+    assert_eq!(backend.load_progress().percent, 60);
+    assert!(!backend.load_progress().is_complete);
+
+    backend.finish_loading();
+    assert!(backend.load_progress().is_complete);
+    assert_eq!(backend.load_progress().percent, 100);
+
+    backend.reload().unwrap();
+    backend.stop().unwrap();
+    let states: Vec<_> = backend
+        .navigation_events()
+        .into_iter()
+        .map(|e| e.state)
+        .collect();
+    assert_eq!(
+        states,
+        vec![
+            NavigationState::Started,
+            NavigationState::Completed,
+            NavigationState::Started,
+            NavigationState::Failed,
+        ]
+    );
+}
+
+#[test]
+fn test_cookie_jar() {
+    let backend = HeadlessBackend::new();
+    backend
+        .set_cookie(&CookieInfo::session(".example.com", "sid", "1"))
+        .unwrap();
+    backend
+        .set_cookie(&CookieInfo::session("example.com", "theme", "dark"))
+        .unwrap();
+
+    let cookie = backend.get_cookie("example.com", "sid").unwrap().unwrap();
+    assert_eq!(cookie.value, "1");
+    assert_eq!(backend.cookies().len(), 2);
+
+    let mut expired = CookieInfo::session("example.com", "theme", "light");
+    expired.expires = Some(1);
+    backend.set_cookie(&expired).unwrap();
+    assert!(backend
+        .get_cookie("example.com", "theme")
+        .unwrap()
+        .is_none());
+
+    backend.delete_cookie("example.com", "sid").unwrap();
+    assert!(backend.cookies().is_empty());
+
+    assert!(matches!(
+        backend.set_cookie(&CookieInfo::session("", "a", "b")),
+        Err(WebViewError::InvalidArgument(_))
+    ));
+}
+
+#[test]
+fn test_scripted_eval() {
+    let backend = HeadlessBackend::new();
+    backend.script_result("document.title", json!("Scripted"));
+    backend.script_error("boom()", "ReferenceError: boom is not defined");
+    backend.set_script_handler(|script| script.strip_prefix("echo:").map(|rest| Ok(json!(rest))));
+
+    assert_eq!(eval(&backend, "document.title").unwrap(), json!("Scripted"));
+    assert!(matches!(
+        eval(&backend, "boom()"),
+        Err(WebViewError::JavaScript(_))
+    ));
+    assert_eq!(eval(&backend, "echo:hi").unwrap(), json!("hi"));
+    assert_eq!(eval(&backend, "1 + 1").unwrap(), serde_json::Value::Null);
+    assert!(backend.eval_js("boom()").is_err());
+
+    assert_eq!(
+        backend.eval_calls(),
+        vec!["document.title", "boom()", "echo:hi", "1 + 1", "boom()"]
+    );
+    backend.clear_eval_calls();
+    assert!(backend.eval_calls().is_empty());
+}
+
+#[test]
+fn test_emit_event_and_window_state() {
+    let backend = HeadlessBackend::new();
+    backend.emit_event("ready", json!({"ok": true})).unwrap();
+    assert_eq!(
+        backend.emitted_events(),
+        vec![("ready".to_string(), json!({"ok": true}))]
+    );
+
+    backend.set_bounds(10, 20, 300, 200).unwrap();
+    backend.set_visible(false).unwrap();
+    backend.focus().unwrap();
+    let window = backend.window_state();
+    assert_eq!(
+        (window.x, window.y, window.width, window.height),
+        (10, 20, 300, 200)
+    );
+    assert!(!window.visible);
+    assert!(window.focused);
+    assert!(backend.native_handle().is_none());
+}
+
+#[test]
+fn test_lifecycle() {
+    let backend = HeadlessBackend::new();
+    assert_eq!(backend.process_events(), ProcessResult::Continue);
+
+    assert!(backend.request_close());
+    assert_eq!(backend.lifecycle_state(), LifecycleState::CloseRequested);
+    assert_eq!(backend.process_events(), ProcessResult::CloseRequested);
+    assert!(matches!(
+        backend.navigate("https://example.com"),
+        Err(WebViewError::Closed)
+    ));
+
+    backend.close().unwrap();
+    assert!(backend.is_closed());
+    assert!(backend.eval_js("1").is_err());
+    assert!(backend.get_cookie("example.com", "a").is_err());
+}
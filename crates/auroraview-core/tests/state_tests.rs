@@ -0,0 +1,470 @@
+//! Shared state store tests
+
+use std::sync::Arc;
+use std::thread;
+
+use auroraview_core::state::{
+    apply_patch, diff, PatchOp, StateChange, StateError, StateMessage, StateStore, RUST_ORIGIN,
+    STATE_FILE, WATCH_ALL,
+};
+use parking_lot::Mutex;
+use rstest::rstest;
+use serde_json::{json, Value};
+
+/// Subscribe a recording sink
+fn record(store: &StateStore, id: &str) -> Arc<Mutex<Vec<StateMessage>>> {
+    let messages = Arc::new(Mutex::new(Vec::new()));
+    let sink = messages.clone();
+    store.subscribe(id, move |message| sink.lock().push(message.clone()));
+    messages
+}
+
+fn ops(value: Value) -> Vec<PatchOp> {
+    serde_json::from_value(value).unwrap()
+}
+
+// ─── JSON Patch ──────────────────────────────────────────────────────────────
+
+#[rstest]
+#[case(json!([{"op": "add", "path": "/b", "value": 2}]), json!({"a": 1, "b": 2, "list": [1, 2]}))]
+#[case(json!([{"op": "add", "path": "/list/-", "value": 3}]), json!({"a": 1, "list": [1, 2, 3]}))]
+#[case(json!([{"op": "add", "path": "/list/0", "value": 0}]), json!({"a": 1, "list": [0, 1, 2]}))]
+#[case(json!([{"op": "remove", "path": "/list/0"}]), json!({"a": 1, "list": [2]}))]
+#[case(json!([{"op": "replace", "path": "/a", "value": "x"}]), json!({"a": "x", "list": [1, 2]}))]
+#[case(json!([{"op": "move", "from": "/a", "path": "/b"}]), json!({"b": 1, "list": [1, 2]}))]
+#[case(json!([{"op": "copy", "from": "/list", "path": "/c"}]), json!({"a": 1, "c": [1, 2], "list": [1, 2]}))]
+#[case(json!([{"op": "test", "path": "/a", "value": 1}]), json!({"a": 1, "list": [1, 2]}))]
+fn patch_operations(#[case] patch: Value, #[case] expected: Value) {
+    let mut doc = json!({"a": 1, "list": [1, 2]});
+    apply_patch(&mut doc, &ops(patch)).unwrap();
+    assert_eq!(doc, expected);
+}
+
+#[rstest]
+#[case(json!([{"op": "replace", "path": "/missing", "value": 1}]))]
+#[case(json!([{"op": "remove", "path": "/list/5"}]))]
+#[case(json!([{"op": "add", "path": "/missing/child", "value": 1}]))]
+#[case(json!([{"op": "test", "path": "/a", "value": 2}]))]
+#[case(json!([{"op": "move", "from": "/list", "path": "/list/0"}]))]
+#[case(json!([{"op": "add", "path": "a", "value": 1}]))]
+fn invalid_patches_are_rejected(#[case] patch: Value) {
+    let mut doc = json!({"a": 1, "list": [1, 2]});
+    assert!(apply_patch(&mut doc, &ops(patch)).is_err());
+}
+
+#[test]
+fn failed_patch_leaves_document_unchanged() {
+    let mut doc = json!({"a": 1});
+    let patch = ops(json!([
+        {"op": "replace", "path": "/a", "value": 2},
+        {"op": "test", "path": "/a", "value": 3}
+    ]));
+    assert_eq!(
+        apply_patch(&mut doc, &patch),
+        Err(StateError::TestFailed("/a".into()))
+    );
+    assert_eq!(doc, json!({"a": 1}));
+}
+
+#[rstest]
+#[case(json!({"a": 1}), json!({"a": 1}))]
+#[case(json!({"a": 1}), json!({"a": 2}))]
+#[case(json!({"a": 1, "b": 2}), json!({"b": 2, "c": 3}))]
+#[case(json!({"list": [1, 2, 3]}), json!({"list": [1, 5]}))]
+#[case(json!({"list": [1]}), json!({"list": [1, {"x": true}, 3]}))]
+#[case(json!({"a": {"b": {"c": 1}}}), json!({"a": {"b": {"c": 2, "d": null}}}))]
+#[case(json!({"a/b": 1, "t~": 1}), json!({"a/b": 2}))]
+#[case(json!({"a": [1]}), json!({"a": "scalar"}))]
+fn diff_round_trips(#[case] old: Value, #[case] new: Value) {
+    let patch = diff(&old, &new);
+    let mut doc = old.clone();
+    apply_patch(&mut doc, &patch).unwrap();
+    assert_eq!(doc, new);
+}
+
+#[test]
+fn diff_is_minimal_for_nested_change() {
+    let old = json!({"layout": {"panels": [{"w": 100}, {"w": 200}], "theme": "dark"}});
+    let mut new = old.clone();
+    new["layout"]["panels"][1]["w"] = json!(250);
+
+    assert_eq!(
+        diff(&old, &new),
+        vec![PatchOp::replace("/layout/panels/1/w", json!(250))]
+    );
+}
+
+#[test]
+fn patch_serializes_as_rfc6902() {
+    let json = serde_json::to_value(PatchOp::add("/a", json!(1))).unwrap();
+    assert_eq!(json, json!({"op": "add", "path": "/a", "value": 1}));
+}
+
+// ─── Store ───────────────────────────────────────────────────────────────────
+
+#[test]
+fn set_get_delete() {
+    let store = StateStore::new();
+    store.set("count", json!(1), RUST_ORIGIN).unwrap();
+
+    assert_eq!(store.get("count"), Some(json!(1)));
+    assert!(store.contains("count"));
+    assert_eq!(store.version(), 1);
+
+    store.delete("count", RUST_ORIGIN).unwrap();
+    assert_eq!(store.get("count"), None);
+    assert_eq!(store.version(), 2);
+}
+
+#[test]
+fn unchanged_set_is_a_no_op() {
+    let store = StateStore::new();
+    store.set("a", json!({"x": 1}), RUST_ORIGIN).unwrap();
+    let patch = store.set("a", json!({"x": 1}), RUST_ORIGIN).unwrap();
+
+    assert!(patch.is_empty());
+    assert_eq!(store.version(), 1);
+}
+
+#[test]
+fn set_returns_minimal_patch() {
+    let store = StateStore::new();
+    store
+        .set(
+            "selection",
+            json!({"items": ["a", "b"], "mode": "add"}),
+            RUST_ORIGIN,
+        )
+        .unwrap();
+    let patch = store
+        .set(
+            "selection",
+            json!({"items": ["a", "b"], "mode": "replace"}),
+            RUST_ORIGIN,
+        )
+        .unwrap();
+
+    assert_eq!(
+        patch,
+        vec![PatchOp::replace("/selection/mode", json!("replace"))]
+    );
+}
+
+#[test]
+fn get_path_reads_nested_values() {
+    let store = StateStore::new();
+    store
+        .set(
+            "layout",
+            json!({"panels": [{"id": "outliner"}]}),
+            RUST_ORIGIN,
+        )
+        .unwrap();
+    assert_eq!(
+        store.get_path("/layout/panels/0/id"),
+        Some(json!("outliner"))
+    );
+}
+
+#[test]
+fn set_many_and_clear() {
+    let store = StateStore::new();
+    store
+        .set_many(
+            [("a".to_string(), json!(1)), ("b".to_string(), json!(2))],
+            RUST_ORIGIN,
+        )
+        .unwrap();
+    assert_eq!(store.version(), 1);
+    assert_eq!(store.keys(), vec!["a", "b"]);
+
+    store.clear(RUST_ORIGIN).unwrap();
+    assert_eq!(store.snapshot(), json!({}));
+}
+
+#[test]
+fn patch_must_keep_object_root() {
+    let store = StateStore::new();
+    let result = store.apply_patch(&[PatchOp::replace("", json!([1]))], "panel");
+
+    assert!(matches!(result, Err(StateError::InvalidPatch(_))));
+    assert_eq!(store.snapshot(), json!({}));
+}
+
+// ─── Subscribers ─────────────────────────────────────────────────────────────
+
+#[test]
+fn subscribe_sends_full_state() {
+    let store = StateStore::new();
+    store.set("a", json!(1), RUST_ORIGIN).unwrap();
+
+    let messages = record(&store, "panel-1");
+    assert_eq!(
+        messages.lock().as_slice(),
+        &[StateMessage::Full {
+            data: json!({"a": 1}),
+            version: 1
+        }]
+    );
+}
+
+#[test]
+fn patches_are_broadcast_to_other_subscribers() {
+    let store = StateStore::new();
+    let first = record(&store, "panel-1");
+    let second = record(&store, "panel-2");
+
+    let patch = ops(json!([{"op": "add", "path": "/selection", "value": ["pCube1"]}]));
+    store.apply_patch(&patch, "panel-1").unwrap();
+
+    // The origin is not echoed
+    assert_eq!(first.lock().len(), 1);
+    assert_eq!(
+        second.lock().last(),
+        Some(&StateMessage::Patch {
+            patch,
+            version: 1,
+            origin: "panel-1".into()
+        })
+    );
+}
+
+#[test]
+fn broadcast_patch_is_normalized() {
+    let store = StateStore::new();
+    store
+        .set("a", json!({"x": 1, "y": 2}), RUST_ORIGIN)
+        .unwrap();
+    let messages = record(&store, "panel-2");
+
+    // A whole-value replace from one window reaches others as a field change
+    store
+        .apply_patch(
+            &[PatchOp::replace("/a", json!({"x": 1, "y": 3}))],
+            "panel-1",
+        )
+        .unwrap();
+    match messages.lock().last() {
+        Some(StateMessage::Patch { patch, .. }) => {
+            assert_eq!(patch, &vec![PatchOp::replace("/a/y", json!(3))]);
+        }
+        other => panic!("unexpected message: {:?}", other),
+    };
+}
+
+#[test]
+fn rust_changes_reach_every_subscriber() {
+    let store = StateStore::new();
+    let first = record(&store, "panel-1");
+    let second = record(&store, "panel-2");
+
+    store.set("a", json!(1), RUST_ORIGIN).unwrap();
+    assert_eq!(first.lock().len(), 2);
+    assert_eq!(second.lock().len(), 2);
+}
+
+#[test]
+fn resync_and_unsubscribe() {
+    let store = StateStore::new();
+    let messages = record(&store, "panel-1");
+    store.set("a", json!(1), RUST_ORIGIN).unwrap();
+
+    assert!(store.resync("panel-1"));
+    assert!(matches!(
+        messages.lock().last(),
+        Some(StateMessage::Full { version: 1, .. })
+    ));
+
+    assert!(store.unsubscribe("panel-1"));
+    assert!(!store.resync("panel-1"));
+    store.set("b", json!(2), RUST_ORIGIN).unwrap();
+    assert_eq!(messages.lock().len(), 3);
+    assert_eq!(store.subscriber_count(), 0);
+}
+
+#[test]
+fn message_serializes_for_state_bridge() {
+    let message = StateMessage::Patch {
+        patch: vec![PatchOp::remove("/a")],
+        version: 3,
+        origin: "rust".into(),
+    };
+    assert_eq!(
+        serde_json::to_value(&message).unwrap(),
+        json!({
+            "type": "patch",
+            "patch": [{"op": "remove", "path": "/a"}],
+            "version": 3,
+            "origin": "rust"
+        })
+    );
+}
+
+// ─── Watchers ────────────────────────────────────────────────────────────────
+
+#[test]
+fn watchers_see_their_key() {
+    let store = StateStore::new();
+    let changes: Arc<Mutex<Vec<StateChange>>> = Arc::default();
+    let sink = changes.clone();
+    let id = store.watch("selection", move |change| sink.lock().push(change.clone()));
+
+    store.set("other", json!(1), RUST_ORIGIN).unwrap();
+    store
+        .apply_patch(
+            &[PatchOp::add("/selection", json!(["pSphere1"]))],
+            "panel-1",
+        )
+        .unwrap();
+    store.delete("selection", RUST_ORIGIN).unwrap();
+
+    {
+        let changes = changes.lock();
+        assert_eq!(changes.len(), 2);
+        assert_eq!(changes[0].value, Some(json!(["pSphere1"])));
+        assert_eq!(changes[0].origin, "panel-1");
+        assert_eq!(changes[0].version, 2);
+        assert_eq!(changes[1].value, None);
+    }
+
+    assert!(store.unwatch(id));
+    store.set("selection", json!([]), RUST_ORIGIN).unwrap();
+    assert_eq!(changes.lock().len(), 2);
+}
+
+#[test]
+fn watch_all_sees_every_key() {
+    let store = StateStore::new();
+    let keys: Arc<Mutex<Vec<String>>> = Arc::default();
+    let sink = keys.clone();
+    store.watch(WATCH_ALL, move |change| {
+        sink.lock().push(change.key.clone())
+    });
+
+    store
+        .set_many(
+            [("a".to_string(), json!(1)), ("b".to_string(), json!(2))],
+            RUST_ORIGIN,
+        )
+        .unwrap();
+    assert_eq!(*keys.lock(), vec!["a", "b"]);
+}
+
+#[test]
+fn watcher_may_write_to_store() {
+    let store = StateStore::new();
+    let inner = store.clone();
+    store.watch("count", move |change| {
+        let doubled = change.value.as_ref().and_then(Value::as_i64).unwrap_or(0) * 2;
+        inner.set("doubled", json!(doubled), RUST_ORIGIN).unwrap();
+    });
+
+    store.set("count", json!(21), RUST_ORIGIN).unwrap();
+    assert_eq!(store.get("doubled"), Some(json!(42)));
+}
+
+// ─── Persistence ─────────────────────────────────────────────────────────────
+
+#[test]
+fn only_persistent_keys_survive_reopen() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join(STATE_FILE);
+    {
+        let store = StateStore::open(&path).unwrap();
+        store.set_persistent("layout", true).unwrap();
+        store
+            .set("layout", json!({"split": 0.3}), RUST_ORIGIN)
+            .unwrap();
+        store.set("selection", json!(["a"]), RUST_ORIGIN).unwrap();
+    }
+
+    let store = StateStore::open(&path).unwrap();
+    assert_eq!(store.get("layout"), Some(json!({"split": 0.3})));
+    assert_eq!(store.get("selection"), None);
+    assert!(store.is_persistent("layout"));
+}
+
+#[test]
+fn patches_from_webviews_are_persisted() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("nested").join(STATE_FILE);
+    let store = StateStore::open(&path).unwrap();
+    store.set_persistent("prefs", true).unwrap();
+
+    store
+        .apply_patch(&[PatchOp::add("/prefs", json!({"units": "cm"}))], "panel")
+        .unwrap();
+    assert_eq!(
+        StateStore::open(&path).unwrap().get("prefs"),
+        Some(json!({"units": "cm"}))
+    );
+}
+
+#[test]
+fn unpersisting_removes_key_from_file() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join(STATE_FILE);
+    let store = StateStore::open(&path).unwrap();
+    store.set("a", json!(1), RUST_ORIGIN).unwrap();
+    store.set_persistent("a", true).unwrap();
+    assert_eq!(StateStore::open(&path).unwrap().get("a"), Some(json!(1)));
+
+    store.set_persistent("a", false).unwrap();
+    assert_eq!(StateStore::open(&path).unwrap().get("a"), None);
+}
+
+#[test]
+fn newer_file_version_is_rejected() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join(STATE_FILE);
+    std::fs::write(&path, r#"{"version": 99, "values": {}}"#).unwrap();
+
+    assert!(matches!(
+        StateStore::open(&path),
+        Err(StateError::Storage(_))
+    ));
+}
+
+// ─── Concurrency ─────────────────────────────────────────────────────────────
+
+#[test]
+fn concurrent_windows_converge() {
+    let store = StateStore::new();
+    let messages = record(&store, "observer");
+
+    let handles: Vec<_> = (0..4)
+        .map(|i| {
+            let store = store.clone();
+            thread::spawn(move || {
+                for n in 0..25 {
+                    let key = format!("panel{}", i);
+                    store
+                        .apply_patch(&[PatchOp::add(format!("/{}", key), json!(n))], &key)
+                        .unwrap();
+                }
+            })
+        })
+        .collect();
+    for handle in handles {
+        handle.join().unwrap();
+    }
+
+    // Replaying the broadcast reproduces the authoritative state, in order
+    let mut replica = json!({});
+    let mut last_version = 0;
+    for message in messages.lock().iter() {
+        match message {
+            StateMessage::Full { data, version } => {
+                replica = data.clone();
+                last_version = *version;
+            }
+            StateMessage::Patch { patch, version, .. } => {
+                assert_eq!(*version, last_version + 1);
+                apply_patch(&mut replica, patch).unwrap();
+                last_version = *version;
+            }
+        }
+    }
+    assert_eq!(replica, store.snapshot());
+    assert_eq!(store.version(), 100);
+}
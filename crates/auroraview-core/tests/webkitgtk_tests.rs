@@ -0,0 +1,160 @@
+//! WebKitGTK backend tests
+//!
+//! These tests need an X server. Run them headless with:
+//!
+//! ```bash
+//! GDK_BACKEND=x11 AURORAVIEW_SOFTWARE_RENDERING=1 \
+//!     xvfb-run -a cargo test -p auroraview-core --features webkitgtk --test webkitgtk_tests
+//! ```
+//!
+//! Without `DISPLAY` every test is skipped.
+
+#![cfg(target_os = "linux")]
+
+use std::sync::mpsc;
+use std::time::{Duration, Instant};
+
+use auroraview_core::backend::{
+    BackendConfig, BackendFactory, BackendType, CookieInfo, EmbeddableBackend, LifecycleState,
+    ProcessResult, WebKitGtkBackend, WebViewBackend, WebViewError,
+};
+
+fn has_display() -> bool {
+    std::env::var_os("DISPLAY").is_some()
+}
+
+fn config(html: &str) -> BackendConfig {
+    BackendConfig {
+        backend_type: BackendType::WebKitGTK,
+        title: "webkitgtk test".to_string(),
+        html: Some(html.to_string()),
+        ..Default::default()
+    }
+}
+
+fn wait_until(mut done: impl FnMut() -> bool) {
+    let deadline = Instant::now() + Duration::from_secs(15);
+    while !done() {
+        assert!(Instant::now() < deadline, "timed out waiting for WebKitGTK");
+        std::thread::sleep(Duration::from_millis(20));
+    }
+}
+
+fn loaded(html: &str) -> WebKitGtkBackend {
+    let backend = WebKitGtkBackend::new(&config(html)).unwrap();
+    wait_until(|| !backend.is_loading() && backend.load_progress().is_complete);
+    backend
+}
+
+fn eval(backend: &dyn WebViewBackend, script: &str) -> Result<serde_json::Value, WebViewError> {
+    let (tx, rx) = mpsc::channel();
+    backend
+        .eval_js_with_callback(
+            script,
+            Box::new(move |result| {
+                let _ = tx.send(result);
+            }),
+        )
+        .unwrap();
+    rx.recv_timeout(Duration::from_secs(15)).unwrap()
+}
+
+#[test]
+fn test_factory_creates_webkitgtk_backend() {
+    if !has_display() {
+        return;
+    }
+    let backend = BackendFactory::create(&config("<title>Factory</title>")).unwrap();
+    assert_eq!(backend.lifecycle_state(), LifecycleState::Active);
+    wait_until(|| backend.title().as_deref() == Some("Factory"));
+    backend.close().unwrap();
+    assert!(backend.is_closed());
+}
+
+#[test]
+fn test_eval_js_with_callback_returns_json() {
+    if !has_display() {
+        return;
+    }
+    let backend = loaded("<p>eval</p>");
+
+    assert_eq!(eval(&backend, "1 + 2").unwrap(), serde_json::json!(3));
+    assert_eq!(
+        eval(&backend, "({a: [1, 'x'], b: null})").unwrap(),
+        serde_json::json!({"a": [1, "x"], "b": null})
+    );
+    assert_eq!(
+        eval(&backend, "undefined").unwrap(),
+        serde_json::Value::Null
+    );
+    assert!(matches!(
+        eval(&backend, "throw new Error('boom')"),
+        Err(WebViewError::JavaScript(_))
+    ));
+}
+
+#[test]
+fn test_cookie_roundtrip() {
+    if !has_display() {
+        return;
+    }
+    let backend = loaded("<p>cookies</p>");
+
+    let mut cookie = CookieInfo::session("cookies.example.com", "token", "abc");
+    cookie.http_only = true;
+    cookie.expires = Some(4_102_444_800);
+    backend.set_cookie(&cookie).unwrap();
+
+    let stored = backend
+        .get_cookie("cookies.example.com", "token")
+        .unwrap()
+        .expect("cookie should be stored");
+    assert_eq!(stored.value, "abc");
+    assert!(stored.http_only);
+    assert_eq!(stored.expires, Some(4_102_444_800));
+
+    backend
+        .delete_cookie("cookies.example.com", "token")
+        .unwrap();
+    assert!(backend
+        .get_cookie("cookies.example.com", "token")
+        .unwrap()
+        .is_none());
+
+    backend
+        .set_cookie(&CookieInfo::session("cookies.example.com", "other", "1"))
+        .unwrap();
+    backend.clear_cookies().unwrap();
+    assert!(backend
+        .get_cookie("cookies.example.com", "other")
+        .unwrap()
+        .is_none());
+}
+
+#[test]
+fn test_native_handle_is_x11_window() {
+    if !has_display() {
+        return;
+    }
+    let backend = loaded("<p>handle</p>");
+    let handle = backend.native_handle().expect("X11 window id");
+    assert_ne!(handle, 0);
+    assert_eq!(backend.process_ipc_only(), ProcessResult::Continue);
+}
+
+#[test]
+fn test_closed_backend_rejects_operations() {
+    if !has_display() {
+        return;
+    }
+    let backend = loaded("<p>close</p>");
+    backend.close().unwrap();
+
+    assert!(matches!(
+        backend.navigate("about:blank"),
+        Err(WebViewError::Closed)
+    ));
+    assert!(backend.eval_js("1").is_err());
+    assert!(backend.get_cookie("example.com", "a").is_err());
+    assert_eq!(backend.process_events(), ProcessResult::CloseRequested);
+}
@@ -0,0 +1,796 @@
+//! HTTP download engine
+//!
+//! [`DownloadEngine`] drives a [`DownloadManager`]: it takes downloads off the
+//! queue whenever a slot is free and streams each one on its own thread.
+//!
+//! - Bytes are written to `<save path>.part`, which is renamed once complete.
+//! - An existing `.part` file is continued with a `Range` request, so
+//!   transfers survive pause/resume and application restarts.
+//! - Speed is sampled periodically; [`DownloadItem::eta`] is derived from it.
+//! - Connection errors, `429` and `5xx` responses are retried with
+//!   exponential backoff.
+//! - When [`DownloadItem::sha256`] is set the file is verified before rename.
+//! - The manager's [`QueuePolicy`](crate::QueuePolicy) bandwidth cap is shared
+//!   by all transfers, [`DownloadItem::max_bytes_per_sec`] caps a single one,
+//!   and transfers stop reading while the queue is held or outside its
+//!   scheduling windows.
+//!
+//! Transfers react to the manager's state: [`DownloadManager::pause`],
+//! [`DownloadManager::cancel`] and [`DownloadManager::remove`] stop a running
+//! transfer at the next chunk.
+
+use std::collections::HashMap;
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Read, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+use parking_lot::{Condvar, Mutex};
+use sha2::{Digest, Sha256};
+
+use crate::{
+    DownloadError, DownloadId, DownloadItem, DownloadManager, DownloadState, RateLimiter, Result,
+};
+
+/// Extension appended to the save path while a download is in progress
+pub const PART_EXTENSION: &str = "part";
+
+/// Path of the partial file for a download saved to `path`
+pub fn part_path(path: &Path) -> PathBuf {
+    let mut name = path.file_name().unwrap_or_default().to_os_string();
+    name.push(".");
+    name.push(PART_EXTENSION);
+    path.with_file_name(name)
+}
+
+/// Download engine configuration
+#[derive(Debug, Clone)]
+pub struct EngineConfig {
+    /// Read buffer size in bytes
+    pub chunk_size: usize,
+    /// Retries after a failed attempt before the download is marked failed
+    ///
+    /// The counter resets whenever an attempt makes progress.
+    pub max_retries: u32,
+    /// Delay before the first retry; doubled for every further retry
+    pub retry_backoff: Duration,
+    /// Upper bound for the retry delay
+    pub max_backoff: Duration,
+    /// How often the transfer speed is sampled
+    pub speed_interval: Duration,
+    /// Timeout for establishing a connection
+    pub connect_timeout: Duration,
+    /// Timeout for receiving the response headers
+    pub response_timeout: Duration,
+    /// Value of the `User-Agent` header
+    pub user_agent: String,
+}
+
+impl Default for EngineConfig {
+    fn default() -> Self {
+        Self {
+            chunk_size: 64 * 1024,
+            max_retries: 5,
+            retry_backoff: Duration::from_millis(500),
+            max_backoff: Duration::from_secs(30),
+            speed_interval: Duration::from_millis(500),
+            connect_timeout: Duration::from_secs(30),
+            response_timeout: Duration::from_secs(60),
+            user_agent: concat!("AuroraView/", env!("CARGO_PKG_VERSION")).to_string(),
+        }
+    }
+}
+
+impl EngineConfig {
+    /// Set read buffer size
+    pub fn with_chunk_size(mut self, bytes: usize) -> Self {
+        self.chunk_size = bytes.max(1);
+        self
+    }
+
+    /// Set number of retries
+    pub fn with_max_retries(mut self, retries: u32) -> Self {
+        self.max_retries = retries;
+        self
+    }
+
+    /// Set initial and maximum retry delay
+    pub fn with_retry_backoff(mut self, initial: Duration, max: Duration) -> Self {
+        self.retry_backoff = initial;
+        self.max_backoff = max.max(initial);
+        self
+    }
+
+    /// Set speed sampling interval
+    pub fn with_speed_interval(mut self, interval: Duration) -> Self {
+        self.speed_interval = interval;
+        self
+    }
+
+    /// Set connect and response header timeouts
+    pub fn with_timeouts(mut self, connect: Duration, response: Duration) -> Self {
+        self.connect_timeout = connect;
+        self.response_timeout = response;
+        self
+    }
+
+    /// Set `User-Agent` header
+    pub fn with_user_agent(mut self, user_agent: impl Into<String>) -> Self {
+        self.user_agent = user_agent.into();
+        self
+    }
+
+    /// Delay before retry number `attempt` (1-based)
+    fn backoff(&self, attempt: u32) -> Duration {
+        let factor = 1u32 << attempt.saturating_sub(1).min(16);
+        self.retry_backoff
+            .saturating_mul(factor)
+            .min(self.max_backoff)
+    }
+}
+
+/// HTTP(S) download engine
+///
+/// Cloning is cheap; clones share the same workers.
+///
+/// ```rust,ignore
+/// use auroraview_downloads::{DownloadEngine, DownloadManager};
+///
+/// let manager = DownloadManager::new(None);
+/// manager.add("https://example.com/textures.zip", "textures.zip");
+///
+/// let engine = DownloadEngine::new(manager.clone());
+/// engine.pump();
+/// engine.wait_idle();
+/// ```
+#[derive(Clone)]
+pub struct DownloadEngine {
+    inner: Arc<EngineInner>,
+}
+
+struct EngineInner {
+    manager: DownloadManager,
+    config: EngineConfig,
+    agent: ureq::Agent,
+    workers: Mutex<Workers>,
+    idle: Condvar,
+}
+
+#[derive(Default)]
+struct Workers {
+    running: HashMap<DownloadId, Worker>,
+    next_token: u64,
+}
+
+struct Worker {
+    token: u64,
+    handle: JoinHandle<()>,
+}
+
+/// How a transfer ended without error
+enum Outcome {
+    /// All bytes received (and verified)
+    Completed,
+    /// Paused; the `.part` file is kept
+    Paused,
+    /// Cancelled or removed; the `.part` file is deleted
+    Stopped,
+}
+
+/// Failed transfer attempt
+enum Failure {
+    /// Transient; try again after a backoff
+    Retry(DownloadError),
+    /// Permanent; fail the download
+    Fatal(DownloadError),
+}
+
+impl From<io::Error> for Failure {
+    // Local file errors (disk full, permissions) won't go away by retrying
+    fn from(e: io::Error) -> Self {
+        Failure::Fatal(e.into())
+    }
+}
+
+impl From<ureq::Error> for Failure {
+    fn from(e: ureq::Error) -> Self {
+        let retry = matches!(
+            e,
+            ureq::Error::Io(_)
+                | ureq::Error::Timeout(_)
+                | ureq::Error::HostNotFound
+                | ureq::Error::ConnectionFailed
+                | ureq::Error::Protocol(_)
+        );
+        let error = DownloadError::Http(e.to_string());
+        if retry {
+            Failure::Retry(error)
+        } else {
+            Failure::Fatal(error)
+        }
+    }
+}
+
+impl DownloadEngine {
+    /// Create an engine with the default configuration
+    pub fn new(manager: DownloadManager) -> Self {
+        Self::with_config(manager, EngineConfig::default())
+    }
+
+    /// Create an engine with a custom configuration
+    pub fn with_config(manager: DownloadManager, config: EngineConfig) -> Self {
+        let agent = ureq::Agent::config_builder()
+            .http_status_as_error(false)
+            // Byte offsets must refer to the stored representation
+            .accept_encoding("identity")
+            .user_agent(config.user_agent.as_str())
+            .timeout_connect(Some(config.connect_timeout))
+            .timeout_recv_response(Some(config.response_timeout))
+            .build()
+            .into();
+
+        Self {
+            inner: Arc::new(EngineInner {
+                manager,
+                config,
+                agent,
+                workers: Mutex::new(Workers::default()),
+                idle: Condvar::new(),
+            }),
+        }
+    }
+
+    /// Get the driven manager
+    pub fn manager(&self) -> &DownloadManager {
+        &self.inner.manager
+    }
+
+    /// Get the configuration
+    pub fn config(&self) -> &EngineConfig {
+        &self.inner.config
+    }
+
+    /// Start queued downloads while slots are free
+    ///
+    /// Returns the IDs of the downloads that were started. Finishing
+    /// transfers pump the queue themselves, so this only needs to be called
+    /// after adding downloads.
+    pub fn pump(&self) -> Vec<DownloadId> {
+        self.inner.pump()
+    }
+
+    /// Pause a running download and wait for its transfer to stop
+    pub fn pause(&self, id: &DownloadId) -> Result<()> {
+        self.inner.manager.pause(id)?;
+        self.join(id);
+        Ok(())
+    }
+
+    /// Queue a paused download again, ahead of other pending downloads
+    pub fn resume(&self, id: &DownloadId) -> Result<()> {
+        match self.inner.manager.state(id) {
+            Some(DownloadState::Paused) => {}
+            Some(state) => {
+                return Err(DownloadError::InvalidState(format!(
+                    "Cannot resume download in {:?} state",
+                    state
+                )))
+            }
+            None => return Err(DownloadError::NotFound(id.clone())),
+        }
+
+        // A transfer paused through the manager may still be stopping; it
+        // holds the slot until it exits
+        self.join(id);
+        self.inner
+            .manager
+            .with_queue(|queue| queue.enqueue_priority(id.clone()));
+        self.pump();
+        Ok(())
+    }
+
+    /// Cancel a download, wait for its transfer to stop and delete the
+    /// partial file
+    pub fn cancel(&self, id: &DownloadId) -> Result<()> {
+        let item = self
+            .inner
+            .manager
+            .get(id)
+            .ok_or_else(|| DownloadError::NotFound(id.clone()))?;
+        self.inner.manager.cancel(id)?;
+        self.join(id);
+
+        let _ = fs::remove_file(part_path(&self.inner.destination(&item)));
+        Ok(())
+    }
+
+    /// Put the queue on hold; see [`DownloadManager::hold`]
+    pub fn hold(&self) {
+        self.inner.manager.hold();
+    }
+
+    /// Release one hold and start downloads that were waiting for it
+    pub fn release(&self) {
+        self.inner.manager.release();
+        self.pump();
+    }
+
+    /// Pump the queue every `interval` on a background thread
+    ///
+    /// Needed for time-window scheduling, where nothing else wakes the
+    /// queue when a window opens. The thread exits once every clone of the
+    /// engine has been dropped.
+    pub fn spawn_scheduler(&self, interval: Duration) -> JoinHandle<()> {
+        let engine = Arc::downgrade(&self.inner);
+        thread::spawn(move || loop {
+            thread::sleep(interval);
+            match engine.upgrade() {
+                Some(engine) => {
+                    engine.pump();
+                }
+                None => break,
+            }
+        })
+    }
+
+    /// Check if a transfer is running for a download
+    pub fn is_running(&self, id: &DownloadId) -> bool {
+        self.inner.workers.lock().running.contains_key(id)
+    }
+
+    /// Number of running transfers
+    pub fn running_count(&self) -> usize {
+        self.inner.workers.lock().running.len()
+    }
+
+    /// Block until no transfer is running
+    ///
+    /// Downloads still pending when this returns could not be started
+    /// (for example because they were paused).
+    pub fn wait_idle(&self) {
+        let mut workers = self.inner.workers.lock();
+        while !workers.running.is_empty() {
+            self.inner.idle.wait(&mut workers);
+        }
+    }
+
+    /// Wait for the transfer of `id` to stop, if one is running
+    fn join(&self, id: &DownloadId) {
+        let worker = self.inner.workers.lock().running.remove(id);
+        if let Some(worker) = worker {
+            let _ = worker.handle.join();
+            self.inner.idle.notify_all();
+        }
+    }
+}
+
+impl std::fmt::Debug for DownloadEngine {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("DownloadEngine")
+            .field("config", &self.inner.config)
+            .field("running", &self.running_count())
+            .finish()
+    }
+}
+
+impl EngineInner {
+    fn pump(self: &Arc<Self>) -> Vec<DownloadId> {
+        // Held while spawning so a worker can't finish before it's registered
+        let mut workers = self.workers.lock();
+        self.start_pending(&mut workers)
+    }
+
+    fn start_pending(self: &Arc<Self>, workers: &mut Workers) -> Vec<DownloadId> {
+        let mut started = Vec::new();
+        // Still winding down from an earlier transfer; started once it exits
+        let mut busy = Vec::new();
+
+        while let Some(id) = self.manager.next_to_start() {
+            if workers.running.contains_key(&id) {
+                busy.push(id);
+                continue;
+            }
+            match self.manager.state(&id) {
+                // Interrupted by a crash; continue from the .part file
+                Some(DownloadState::Downloading) => {}
+                Some(_) => {
+                    if self.manager.start(&id).is_err() {
+                        self.manager.with_queue(|queue| queue.mark_finished(&id));
+                        continue;
+                    }
+                }
+                None => {
+                    self.manager.with_queue(|queue| queue.mark_finished(&id));
+                    continue;
+                }
+            }
+
+            workers.next_token += 1;
+            let token = workers.next_token;
+            let engine = Arc::clone(self);
+            let worker_id = id.clone();
+            let spawned = thread::Builder::new()
+                .name(format!("download-{}", id))
+                .spawn(move || engine.work(worker_id, token));
+
+            match spawned {
+                Ok(handle) => {
+                    workers.running.insert(id.clone(), Worker { token, handle });
+                    started.push(id);
+                }
+                Err(e) => {
+                    let _ = self.manager.fail(&id, e.to_string());
+                }
+            }
+        }
+
+        if !busy.is_empty() {
+            self.manager.with_queue(|queue| {
+                for id in busy.into_iter().rev() {
+                    queue.mark_inactive(&id);
+                    queue.enqueue_priority(id);
+                }
+            });
+        }
+
+        started
+    }
+
+    /// Worker thread body
+    fn work(self: Arc<Self>, id: DownloadId, token: u64) {
+        let paused = match self.transfer(&id) {
+            Ok(Outcome::Completed) => {
+                let _ = self.manager.complete(&id);
+                false
+            }
+            Ok(Outcome::Paused) => true,
+            Ok(Outcome::Stopped) => false,
+            Err(e) => {
+                let _ = self.manager.fail(&id, e.to_string());
+                false
+            }
+        };
+
+        // Free the slot and unregister together, so `pump` never finds the
+        // download queued again while this worker still looks alive
+        let mut workers = self.workers.lock();
+        if paused {
+            // `DownloadEngine::resume` queues it again
+            self.manager.with_queue(|queue| queue.mark_inactive(&id));
+        }
+        if workers.running.get(&id).is_some_and(|w| w.token == token) {
+            workers.running.remove(&id);
+        }
+        self.start_pending(&mut workers);
+        drop(workers);
+        self.idle.notify_all();
+    }
+
+    fn destination(&self, item: &DownloadItem) -> PathBuf {
+        item.save_path
+            .clone()
+            .unwrap_or_else(|| self.manager.download_dir().join(&item.filename))
+    }
+
+    /// Whether the manager asked the transfer to stop
+    fn interrupted(&self, id: &DownloadId) -> Option<Outcome> {
+        match self.manager.state(id) {
+            Some(DownloadState::Downloading) => None,
+            Some(DownloadState::Paused) => Some(Outcome::Paused),
+            _ => Some(Outcome::Stopped),
+        }
+    }
+
+    fn transfer(&self, id: &DownloadId) -> Result<Outcome> {
+        let Some(item) = self.manager.get(id) else {
+            return Ok(Outcome::Stopped);
+        };
+        let dest = self.destination(&item);
+        let part = part_path(&dest);
+        if let Some(parent) = dest.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        let limiter = RateLimiter::new(item.max_bytes_per_sec);
+        let mut failures = 0;
+        loop {
+            let before = file_len(&part);
+            let error = match self.attempt(&item, &part, &limiter) {
+                Ok(Outcome::Completed) => break,
+                Ok(outcome) => return Ok(self.stop(outcome, &part)),
+                Err(Failure::Fatal(e)) => return Err(e),
+                Err(Failure::Retry(e)) => e,
+            };
+
+            if file_len(&part) > before {
+                failures = 0;
+            }
+            failures += 1;
+            if failures > self.config.max_retries {
+                return Err(error);
+            }
+            if let Some(outcome) = self.sleep(id, self.config.backoff(failures)) {
+                return Ok(self.stop(outcome, &part));
+            }
+        }
+
+        if let Some(expected) = item.sha256.as_deref() {
+            let actual = sha256_file(&part)?;
+            if !actual.eq_ignore_ascii_case(expected.trim()) {
+                let _ = fs::remove_file(&part);
+                return Err(DownloadError::ChecksumMismatch {
+                    expected: expected.to_string(),
+                    actual,
+                });
+            }
+        }
+
+        fs::rename(&part, &dest)?;
+        Ok(Outcome::Completed)
+    }
+
+    /// Clean up after an interrupted transfer
+    fn stop(&self, outcome: Outcome, part: &Path) -> Outcome {
+        if let Outcome::Stopped = outcome {
+            let _ = fs::remove_file(part);
+        }
+        outcome
+    }
+
+    /// Sleep for `duration` unless the transfer is interrupted first
+    fn sleep(&self, id: &DownloadId, duration: Duration) -> Option<Outcome> {
+        let deadline = Instant::now() + duration;
+        loop {
+            if let Some(outcome) = self.interrupted(id) {
+                return Some(outcome);
+            }
+            let now = Instant::now();
+            if now >= deadline {
+                return None;
+            }
+            thread::sleep((deadline - now).min(Duration::from_millis(50)));
+        }
+    }
+
+    /// Wait while the queue is on hold or outside its time windows
+    fn wait_suspended(&self, id: &DownloadId) -> Option<Outcome> {
+        while self.manager.suspended().is_some() {
+            if let Some(outcome) = self.sleep(id, Duration::from_millis(100)) {
+                return Some(outcome);
+            }
+        }
+        None
+    }
+
+    /// One request, continuing from the current `.part` length
+    fn attempt(
+        &self,
+        item: &DownloadItem,
+        part: &Path,
+        limiter: &RateLimiter,
+    ) -> std::result::Result<Outcome, Failure> {
+        let mut offset = file_len(part);
+        let mut request = self.agent.get(&item.url);
+        if offset > 0 {
+            request = request.header("Range", format!("bytes={}-", offset));
+        }
+        let mut response = request.call()?;
+
+        let status = response.status().as_u16();
+        let content_length = header(&response, "content-length").and_then(|v| v.parse().ok());
+        let content_range = header(&response, "content-range").and_then(parse_content_range);
+
+        let total = match status {
+            200 => {
+                // Full body; the server ignored or doesn't support ranges
+                if offset > 0 {
+                    File::create(part)?;
+                    offset = 0;
+                }
+                content_length
+            }
+            206 => match content_range {
+                Some((Some(start), total)) if start == offset => {
+                    total.or(content_length.map(|len| offset + len))
+                }
+                _ => {
+                    File::create(part)?;
+                    return Err(Failure::Retry(DownloadError::Http(
+                        "Unexpected Content-Range in partial response".to_string(),
+                    )));
+                }
+            },
+            416 if offset > 0 => {
+                // Nothing left to fetch if the .part file is already whole
+                if content_range.and_then(|(_, total)| total) == Some(offset) {
+                    self.manager.update_progress(&item.id, offset, Some(offset));
+                    return Ok(Outcome::Completed);
+                }
+                File::create(part)?;
+                return Err(Failure::Retry(DownloadError::Http(
+                    "Range not satisfiable".to_string(),
+                )));
+            }
+            429 | 500..=599 => {
+                return Err(Failure::Retry(DownloadError::Http(format!(
+                    "Server responded with HTTP {}",
+                    status
+                ))))
+            }
+            _ => {
+                return Err(Failure::Fatal(DownloadError::Http(format!(
+                    "Server responded with HTTP {}",
+                    status
+                ))))
+            }
+        };
+
+        self.manager.update_progress(&item.id, offset, total);
+
+        let mut file = OpenOptions::new().create(true).append(true).open(part)?;
+        let mut reader = response.body_mut().as_reader();
+        let mut buffer = vec![0; self.config.chunk_size];
+        let mut received = offset;
+        let mut sample = (Instant::now(), received);
+
+        loop {
+            if let Some(outcome) = self.interrupted(&item.id) {
+                file.flush()?;
+                return Ok(outcome);
+            }
+            if self.manager.suspended().is_some() {
+                file.flush()?;
+                if let Some(outcome) = self.wait_suspended(&item.id) {
+                    return Ok(outcome);
+                }
+                sample = (Instant::now(), received);
+            }
+
+            limiter.set_rate(self.manager.speed_limit(&item.id));
+            let len = read_size(
+                self.config.chunk_size,
+                [self.manager.bandwidth().rate(), limiter.rate()],
+            );
+            let n = match reader.read(&mut buffer[..len]) {
+                Ok(0) => break,
+                Ok(n) => n,
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+                Err(e) => {
+                    file.flush()?;
+                    return Err(Failure::Retry(e.into()));
+                }
+            };
+            file.write_all(&buffer[..n])?;
+            received += n as u64;
+            self.manager.update_progress(&item.id, received, None);
+
+            let elapsed = sample.0.elapsed();
+            if elapsed >= self.config.speed_interval {
+                let speed = (received - sample.1) as f64 / elapsed.as_secs_f64();
+                self.manager.update_speed(&item.id, speed as u64);
+                sample = (Instant::now(), received);
+            }
+
+            let wait = self
+                .manager
+                .bandwidth()
+                .reserve(n as u64)
+                .max(limiter.reserve(n as u64));
+            if let Some(outcome) = self.sleep(&item.id, wait) {
+                file.flush()?;
+                return Ok(outcome);
+            }
+        }
+        file.sync_all()?;
+
+        match total {
+            Some(total) if received < total => Err(Failure::Retry(DownloadError::Http(format!(
+                "Connection closed after {} of {} bytes",
+                received, total
+            )))),
+            _ => Ok(Outcome::Completed),
+        }
+    }
+}
+
+/// Bytes to read at once: small enough that throttled transfers stay smooth
+fn read_size(chunk_size: usize, rates: [Option<u64>; 2]) -> usize {
+    match rates.into_iter().flatten().min() {
+        Some(rate) => usize::try_from(rate / 4)
+            .unwrap_or(usize::MAX)
+            .clamp(1024, chunk_size.max(1024))
+            .min(chunk_size),
+        None => chunk_size,
+    }
+}
+
+fn file_len(path: &Path) -> u64 {
+    fs::metadata(path).map(|m| m.len()).unwrap_or(0)
+}
+
+fn header<'a>(response: &'a ureq::http::Response<ureq::Body>, name: &str) -> Option<&'a str> {
+    response.headers().get(name)?.to_str().ok()
+}
+
+/// Parse `bytes <start>-<end>/<total>` or `bytes */<total>`
+///
+/// Returns the start offset and the total length, either of which may be
+/// unknown.
+fn parse_content_range(value: &str) -> Option<(Option<u64>, Option<u64>)> {
+    let (range, total) = value.trim().strip_prefix("bytes ")?.split_once('/')?;
+    let total = total.trim().parse().ok();
+    let start = match range.trim() {
+        "*" => None,
+        range => Some(range.split_once('-')?.0.trim().parse().ok()?),
+    };
+    Some((start, total))
+}
+
+/// Hex-encoded SHA-256 of a file
+fn sha256_file(path: &Path) -> io::Result<String> {
+    let mut file = File::open(path)?;
+    let mut hasher = Sha256::new();
+    let mut buffer = vec![0; 64 * 1024];
+    loop {
+        let n = file.read(&mut buffer)?;
+        if n == 0 {
+            break;
+        }
+        hasher.update(&buffer[..n]);
+    }
+    Ok(hasher
+        .finalize()
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_path() {
+        assert_eq!(
+            part_path(Path::new("/tmp/file.zip")),
+            PathBuf::from("/tmp/file.zip.part")
+        );
+    }
+
+    #[test]
+    fn test_parse_content_range() {
+        assert_eq!(
+            parse_content_range("bytes 100-199/1000"),
+            Some((Some(100), Some(1000)))
+        );
+        assert_eq!(
+            parse_content_range("bytes 100-199/*"),
+            Some((Some(100), None))
+        );
+        assert_eq!(
+            parse_content_range("bytes */1000"),
+            Some((None, Some(1000)))
+        );
+        assert_eq!(parse_content_range("items 0-1/2"), None);
+    }
+
+    #[test]
+    fn test_read_size() {
+        assert_eq!(read_size(65536, [None, None]), 65536);
+        assert_eq!(read_size(65536, [Some(40_000), None]), 10_000);
+        assert_eq!(read_size(65536, [Some(40_000), Some(100)]), 1024);
+        assert_eq!(read_size(512, [Some(100), None]), 512);
+    }
+
+    #[test]
+    fn test_backoff() {
+        let config = EngineConfig::default()
+            .with_retry_backoff(Duration::from_millis(100), Duration::from_millis(350));
+        assert_eq!(config.backoff(1), Duration::from_millis(100));
+        assert_eq!(config.backoff(2), Duration::from_millis(200));
+        assert_eq!(config.backoff(3), Duration::from_millis(350));
+        assert_eq!(config.backoff(40), Duration::from_millis(350));
+    }
+}
@@ -21,6 +21,19 @@ pub enum DownloadError {
     #[error("Storage error: {0}")]
     Storage(String),
 
+    /// HTTP transfer error
+    #[error("HTTP error: {0}")]
+    Http(String),
+
+    /// Downloaded file does not match the expected checksum
+    #[error("Checksum mismatch: expected {expected}, got {actual}")]
+    ChecksumMismatch {
+        /// Expected SHA-256 (hex)
+        expected: String,
+        /// Actual SHA-256 (hex)
+        actual: String,
+    },
+
     /// IO error
     #[error("IO error: {0}")]
     Io(#[from] std::io::Error),
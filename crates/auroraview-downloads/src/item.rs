@@ -61,6 +61,12 @@ pub struct DownloadItem {
     /// Download speed in bytes per second
     #[serde(skip_serializing_if = "Option::is_none")]
     pub speed: Option<u64>,
+    /// Expected SHA-256 of the file (hex), verified on completion
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sha256: Option<String>,
+    /// Bandwidth cap for this download in bytes per second
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_bytes_per_sec: Option<u64>,
 }
 
 impl DownloadItem {
@@ -79,6 +85,8 @@ impl DownloadItem {
             created_at: Utc::now(),
             completed_at: None,
             speed: None,
+            sha256: None,
+            max_bytes_per_sec: None,
         }
     }
 
@@ -101,6 +109,8 @@ impl DownloadItem {
             created_at: Utc::now(),
             completed_at: None,
             speed: None,
+            sha256: None,
+            max_bytes_per_sec: None,
         }
     }
 
@@ -122,6 +132,18 @@ impl DownloadItem {
         self
     }
 
+    /// Set expected SHA-256 checksum (hex)
+    pub fn with_sha256(mut self, sha256: impl Into<String>) -> Self {
+        self.sha256 = Some(sha256.into());
+        self
+    }
+
+    /// Set bandwidth cap in bytes per second
+    pub fn with_max_bytes_per_sec(mut self, bytes_per_sec: u64) -> Self {
+        self.max_bytes_per_sec = Some(bytes_per_sec.max(1));
+        self
+    }
+
     /// Start downloading
     pub fn start(&mut self) {
         if self.state == DownloadState::Pending || self.state == DownloadState::Paused {
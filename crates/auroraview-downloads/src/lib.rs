@@ -6,9 +6,11 @@
 //! # Features
 //!
 //! - Download queue management
+//! - Bandwidth caps, per-host limits, holds and time-window scheduling
 //! - Progress tracking
 //! - Download history
 //! - Persistence
+//! - HTTP(S) engine with resume, retries and checksums (feature `http`)
 //!
 //! # Example
 //!
@@ -27,16 +29,24 @@
 //! let downloads = manager.all();
 //! ```
 
+#[cfg(feature = "http")]
+pub mod engine;
 mod error;
 mod item;
 mod manager;
+mod policy;
 mod queue;
 
+/// HTTP engine that performs queued downloads.
+#[cfg(feature = "http")]
+pub use engine::{DownloadEngine, EngineConfig};
 /// Error and result types for download operations.
 pub use error::{DownloadError, Result};
 /// Download item types: identifier, state, and metadata.
 pub use item::{DownloadId, DownloadItem, DownloadState};
 /// High-level download manager for tracking and controlling downloads.
 pub use manager::DownloadManager;
+/// Scheduling policies and bandwidth limiting.
+pub use policy::{QueueLimit, QueuePolicy, RateLimiter, TimeWindow};
 /// Download queue for concurrent download scheduling.
-pub use queue::DownloadQueue;
+pub use queue::{DownloadQueue, PendingStatus, QueueStats};
@@ -1,6 +1,9 @@
 //! Download manager implementation
 
-use crate::{DownloadError, DownloadId, DownloadItem, DownloadQueue, DownloadState, Result};
+use crate::{
+    DownloadError, DownloadId, DownloadItem, DownloadQueue, DownloadState, QueueLimit, QueuePolicy,
+    QueueStats, RateLimiter, Result,
+};
 use parking_lot::RwLock;
 use std::collections::HashMap;
 use std::path::{Path, PathBuf};
@@ -12,6 +15,7 @@ use std::sync::Arc;
 #[derive(Debug)]
 pub struct DownloadManager {
     inner: Arc<RwLock<DownloadStore>>,
+    bandwidth: Arc<RateLimiter>,
     download_dir: PathBuf,
     storage_path: Option<PathBuf>,
 }
@@ -22,6 +26,17 @@ struct DownloadStore {
     queue: DownloadQueue,
 }
 
+impl DownloadStore {
+    /// Insert a download and append it to the queue
+    fn enqueue(&mut self, item: DownloadItem) {
+        if let Some(host) = item.domain() {
+            self.queue.set_host(&item.id, host);
+        }
+        self.queue.enqueue(item.id.clone());
+        self.downloads.insert(item.id.clone(), item);
+    }
+}
+
 impl DownloadManager {
     /// Create a new download manager
     ///
@@ -33,6 +48,7 @@ impl DownloadManager {
 
         Self {
             inner: Arc::new(RwLock::new(DownloadStore::default())),
+            bandwidth: Arc::new(RateLimiter::default()),
             download_dir,
             storage_path: None,
         }
@@ -59,6 +75,19 @@ impl DownloadManager {
         store.queue.set_max_concurrent(max);
     }
 
+    /// Set scheduling and bandwidth policy
+    pub fn set_policy(&self, policy: QueuePolicy) {
+        self.bandwidth.set_rate(policy.max_bytes_per_sec);
+        let mut store = self.inner.write();
+        store.queue.set_policy(policy);
+    }
+
+    /// Get scheduling and bandwidth policy
+    pub fn policy(&self) -> QueuePolicy {
+        let store = self.inner.read();
+        store.queue.policy().clone()
+    }
+
     /// Get download directory
     pub fn download_dir(&self) -> &Path {
         &self.download_dir
@@ -81,8 +110,7 @@ impl DownloadManager {
         let id = item.id.clone();
 
         let mut store = self.inner.write();
-        store.downloads.insert(id.clone(), item);
-        store.queue.enqueue(id.clone());
+        store.enqueue(item);
         drop(store);
 
         let _ = self.save();
@@ -94,8 +122,7 @@ impl DownloadManager {
         let id = item.id.clone();
 
         let mut store = self.inner.write();
-        store.downloads.insert(id.clone(), item);
-        store.queue.enqueue(id.clone());
+        store.enqueue(item);
         drop(store);
 
         let _ = self.save();
@@ -311,6 +338,22 @@ impl DownloadManager {
         }
     }
 
+    /// Set the bandwidth cap of a single download
+    ///
+    /// Applies on top of the global cap in [`QueuePolicy::max_bytes_per_sec`].
+    pub fn set_speed_limit(&self, id: &DownloadId, bytes_per_sec: Option<u64>) -> Result<()> {
+        let mut store = self.inner.write();
+        let download = store
+            .downloads
+            .get_mut(id)
+            .ok_or_else(|| DownloadError::NotFound(id.clone()))?;
+        download.max_bytes_per_sec = bytes_per_sec.map(|b| b.max(1));
+        drop(store);
+
+        let _ = self.save();
+        Ok(())
+    }
+
     /// Update download speed
     pub fn update_speed(&self, id: &DownloadId, bytes_per_second: u64) {
         let mut store = self.inner.write();
@@ -319,8 +362,21 @@ impl DownloadManager {
         }
     }
 
+    /// Get the state of a download
+    pub fn state(&self, id: &DownloadId) -> Option<DownloadState> {
+        let store = self.inner.read();
+        store.downloads.get(id).map(|d| d.state)
+    }
+
     // ========== Queue Operations ==========
 
+    /// Run `f` with exclusive access to the queue
+    #[cfg(feature = "http")]
+    pub(crate) fn with_queue<R>(&self, f: impl FnOnce(&mut DownloadQueue) -> R) -> R {
+        let mut store = self.inner.write();
+        f(&mut store.queue)
+    }
+
     /// Get next download to start (from queue)
     pub fn next_to_start(&self) -> Option<DownloadId> {
         let mut store = self.inner.write();
@@ -333,14 +389,56 @@ impl DownloadManager {
         store.queue.can_start()
     }
 
-    /// Get queue statistics
-    pub fn queue_stats(&self) -> (usize, usize, usize) {
+    /// Get queue statistics, including the limit holding each pending download
+    pub fn queue_stats(&self) -> QueueStats {
+        let store = self.inner.read();
+        QueueStats {
+            pending: store.queue.pending_count(),
+            active: store.queue.active_count(),
+            total: store.downloads.len(),
+            held: store.queue.is_held(),
+            items: store.queue.pending_status(),
+        }
+    }
+
+    /// Put the queue on hold, e.g. while a tab is loading in the foreground
+    ///
+    /// No downloads start and running transfers stop reading until every
+    /// hold is released.
+    pub fn hold(&self) {
+        let mut store = self.inner.write();
+        store.queue.hold();
+    }
+
+    /// Release one hold
+    pub fn release(&self) {
+        let mut store = self.inner.write();
+        store.queue.release();
+    }
+
+    /// Check if the queue is on hold
+    pub fn is_held(&self) -> bool {
+        let store = self.inner.read();
+        store.queue.is_held()
+    }
+
+    /// Queue-wide limit suspending all downloads right now, if any
+    pub fn suspended(&self) -> Option<QueueLimit> {
+        let store = self.inner.read();
+        store.queue.suspended()
+    }
+
+    /// Global bandwidth limiter shared by all transfers
+    #[cfg(feature = "http")]
+    pub(crate) fn bandwidth(&self) -> &RateLimiter {
+        &self.bandwidth
+    }
+
+    /// Bandwidth cap of a single download
+    #[cfg(feature = "http")]
+    pub(crate) fn speed_limit(&self, id: &DownloadId) -> Option<u64> {
         let store = self.inner.read();
-        (
-            store.queue.pending_count(),
-            store.queue.active_count(),
-            store.downloads.len(),
-        )
+        store.downloads.get(id).and_then(|d| d.max_bytes_per_sec)
     }
 
     // ========== Statistics ==========
@@ -403,9 +501,10 @@ impl DownloadManager {
             if download.state == DownloadState::Pending
                 || download.state == DownloadState::Downloading
             {
-                store.queue.enqueue(download.id.clone());
+                store.enqueue(download);
+            } else {
+                store.downloads.insert(download.id.clone(), download);
             }
-            store.downloads.insert(download.id.clone(), download);
         }
 
         Ok(())
@@ -423,6 +522,7 @@ impl Clone for DownloadManager {
     fn clone(&self) -> Self {
         Self {
             inner: Arc::clone(&self.inner),
+            bandwidth: Arc::clone(&self.bandwidth),
             download_dir: self.download_dir.clone(),
             storage_path: self.storage_path.clone(),
         }
@@ -0,0 +1,230 @@
+//! Queue scheduling policies and bandwidth limiting
+
+use chrono::NaiveTime;
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use std::time::{Duration, Instant};
+
+/// Limit that keeps a pending download from starting
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum QueueLimit {
+    /// The queue is on hold (e.g. while a tab is loading in the foreground)
+    Held,
+    /// The current time is outside every scheduling window
+    OutsideWindow,
+    /// The global `max_concurrent` limit is reached
+    MaxConcurrent,
+    /// The per-host concurrency limit is reached
+    PerHost,
+}
+
+/// Daily time window in local time
+///
+/// A window whose end is before its start wraps around midnight, so
+/// `22:00-06:00` means "overnight". Equal start and end cover the whole day.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TimeWindow {
+    /// Start of the window (inclusive)
+    pub start: NaiveTime,
+    /// End of the window (exclusive)
+    pub end: NaiveTime,
+}
+
+impl TimeWindow {
+    /// Create a window
+    pub fn new(start: NaiveTime, end: NaiveTime) -> Self {
+        Self { start, end }
+    }
+
+    /// Create a window from hours and minutes, e.g. `TimeWindow::hm((22, 0), (6, 0))`
+    ///
+    /// Returns `None` for invalid times.
+    pub fn hm(start: (u32, u32), end: (u32, u32)) -> Option<Self> {
+        Some(Self::new(
+            NaiveTime::from_hms_opt(start.0, start.1, 0)?,
+            NaiveTime::from_hms_opt(end.0, end.1, 0)?,
+        ))
+    }
+
+    /// Check if `time` falls into the window
+    pub fn contains(&self, time: NaiveTime) -> bool {
+        if self.start == self.end {
+            true
+        } else if self.start < self.end {
+            time >= self.start && time < self.end
+        } else {
+            time >= self.start || time < self.end
+        }
+    }
+}
+
+/// Scheduling and throttling policy of a download queue
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QueuePolicy {
+    /// Combined bandwidth cap for all downloads in bytes per second
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_bytes_per_sec: Option<u64>,
+    /// Maximum concurrent downloads from the same host
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_per_host: Option<usize>,
+    /// Windows in which downloads may run; empty means always
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub windows: Vec<TimeWindow>,
+}
+
+impl QueuePolicy {
+    /// Create a policy without limits
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set global bandwidth cap
+    pub fn with_max_bytes_per_sec(mut self, bytes_per_sec: u64) -> Self {
+        self.max_bytes_per_sec = Some(bytes_per_sec.max(1));
+        self
+    }
+
+    /// Set per-host concurrency limit
+    pub fn with_max_per_host(mut self, max: usize) -> Self {
+        self.max_per_host = Some(max.max(1));
+        self
+    }
+
+    /// Add a scheduling window
+    pub fn with_window(mut self, window: TimeWindow) -> Self {
+        self.windows.push(window);
+        self
+    }
+
+    /// Check if downloads may run at `time`
+    pub fn allows(&self, time: NaiveTime) -> bool {
+        self.windows.is_empty() || self.windows.iter().any(|w| w.contains(time))
+    }
+}
+
+/// Token bucket rate limiter shared between transfers
+///
+/// Callers report the bytes they are about to consume and wait for the
+/// returned duration. The bucket holds at most one second of tokens, so short
+/// bursts are smoothed while the long-term rate stays at the limit.
+#[derive(Debug)]
+pub struct RateLimiter {
+    bucket: Mutex<Bucket>,
+}
+
+#[derive(Debug)]
+struct Bucket {
+    rate: Option<u64>,
+    tokens: f64,
+    last: Instant,
+}
+
+impl RateLimiter {
+    /// Create a limiter; `None` means unlimited
+    pub fn new(bytes_per_sec: Option<u64>) -> Self {
+        Self {
+            bucket: Mutex::new(Bucket {
+                rate: bytes_per_sec,
+                tokens: bytes_per_sec.unwrap_or(0) as f64,
+                last: Instant::now(),
+            }),
+        }
+    }
+
+    /// Get the rate in bytes per second
+    pub fn rate(&self) -> Option<u64> {
+        self.bucket.lock().rate
+    }
+
+    /// Change the rate
+    pub fn set_rate(&self, bytes_per_sec: Option<u64>) {
+        let mut bucket = self.bucket.lock();
+        if bucket.rate != bytes_per_sec {
+            bucket.rate = bytes_per_sec;
+            bucket.tokens = bucket.tokens.min(bytes_per_sec.unwrap_or(0) as f64);
+            bucket.last = Instant::now();
+        }
+    }
+
+    /// Consume `bytes` and return how long to wait before using them
+    pub fn reserve(&self, bytes: u64) -> Duration {
+        let mut bucket = self.bucket.lock();
+        let Some(rate) = bucket.rate.filter(|r| *r > 0) else {
+            return Duration::ZERO;
+        };
+        let rate = rate as f64;
+
+        let now = Instant::now();
+        let elapsed = now.duration_since(bucket.last).as_secs_f64();
+        bucket.last = now;
+        bucket.tokens = (bucket.tokens + elapsed * rate).min(rate) - bytes as f64;
+
+        if bucket.tokens >= 0.0 {
+            Duration::ZERO
+        } else {
+            Duration::from_secs_f64(-bucket.tokens / rate)
+        }
+    }
+}
+
+impl Default for RateLimiter {
+    fn default() -> Self {
+        Self::new(None)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn t(h: u32, m: u32) -> NaiveTime {
+        NaiveTime::from_hms_opt(h, m, 0).unwrap()
+    }
+
+    #[test]
+    fn test_window_contains() {
+        let day = TimeWindow::hm((9, 0), (17, 0)).unwrap();
+        assert!(day.contains(t(9, 0)));
+        assert!(day.contains(t(12, 30)));
+        assert!(!day.contains(t(17, 0)));
+        assert!(!day.contains(t(3, 0)));
+
+        let night = TimeWindow::hm((22, 0), (6, 0)).unwrap();
+        assert!(night.contains(t(23, 0)));
+        assert!(night.contains(t(2, 0)));
+        assert!(!night.contains(t(6, 0)));
+        assert!(!night.contains(t(12, 0)));
+
+        assert!(TimeWindow::hm((0, 0), (0, 0)).unwrap().contains(t(13, 0)));
+        assert!(TimeWindow::hm((25, 0), (1, 0)).is_none());
+    }
+
+    #[test]
+    fn test_policy_allows() {
+        assert!(QueuePolicy::new().allows(t(12, 0)));
+
+        let policy = QueuePolicy::new()
+            .with_window(TimeWindow::hm((1, 0), (2, 0)).unwrap())
+            .with_window(TimeWindow::hm((22, 0), (23, 0)).unwrap());
+        assert!(policy.allows(t(1, 30)));
+        assert!(policy.allows(t(22, 30)));
+        assert!(!policy.allows(t(12, 0)));
+    }
+
+    #[test]
+    fn test_rate_limiter() {
+        let unlimited = RateLimiter::default();
+        assert_eq!(unlimited.reserve(u64::MAX), Duration::ZERO);
+
+        let limiter = RateLimiter::new(Some(1000));
+        // One second of burst is available up front
+        assert_eq!(limiter.reserve(1000), Duration::ZERO);
+        let wait = limiter.reserve(500);
+        assert!(wait > Duration::from_millis(400) && wait <= Duration::from_millis(500));
+
+        limiter.set_rate(None);
+        assert_eq!(limiter.reserve(10_000), Duration::ZERO);
+    }
+}
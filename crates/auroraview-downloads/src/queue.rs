@@ -1,9 +1,42 @@
 //! Download queue management
 
-use crate::DownloadId;
-use std::collections::VecDeque;
+use crate::{DownloadId, QueueLimit, QueuePolicy};
+use chrono::NaiveTime;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, VecDeque};
+
+/// Scheduling status of a pending download
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PendingStatus {
+    /// Download ID
+    pub id: DownloadId,
+    /// Limit holding the download back, or `None` if it can start now
+    pub blocked_by: Option<QueueLimit>,
+}
+
+/// Queue statistics
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QueueStats {
+    /// Number of pending downloads
+    pub pending: usize,
+    /// Number of active downloads
+    pub active: usize,
+    /// Number of known downloads
+    pub total: usize,
+    /// Whether the queue is on hold
+    pub held: bool,
+    /// Pending downloads in queue order
+    pub items: Vec<PendingStatus>,
+}
 
 /// Download queue - manages concurrent downloads
+///
+/// Besides the global `max_concurrent` limit, a [`QueuePolicy`] can restrict
+/// downloads per host and to time windows, and the whole queue can be put on
+/// hold. Pending downloads that are held back by a per-host limit don't block
+/// downloads from other hosts behind them.
 #[derive(Debug)]
 pub struct DownloadQueue {
     /// Pending downloads
@@ -12,6 +45,12 @@ pub struct DownloadQueue {
     active: Vec<DownloadId>,
     /// Maximum concurrent downloads
     max_concurrent: usize,
+    /// Scheduling policy
+    policy: QueuePolicy,
+    /// Host of each download, for per-host limits
+    hosts: HashMap<DownloadId, String>,
+    /// Number of outstanding holds
+    holds: usize,
 }
 
 impl DownloadQueue {
@@ -20,11 +59,7 @@ impl DownloadQueue {
 
     /// Create a new download queue
     pub fn new() -> Self {
-        Self {
-            pending: VecDeque::new(),
-            active: Vec::new(),
-            max_concurrent: Self::DEFAULT_MAX_CONCURRENT,
-        }
+        Self::with_max_concurrent(Self::DEFAULT_MAX_CONCURRENT)
     }
 
     /// Create a queue with custom max concurrent downloads
@@ -33,9 +68,69 @@ impl DownloadQueue {
             pending: VecDeque::new(),
             active: Vec::new(),
             max_concurrent: max.max(1),
+            policy: QueuePolicy::default(),
+            hosts: HashMap::new(),
+            holds: 0,
+        }
+    }
+
+    /// Set scheduling policy
+    pub fn with_policy(mut self, policy: QueuePolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+
+    /// Set scheduling policy
+    pub fn set_policy(&mut self, policy: QueuePolicy) {
+        self.policy = policy;
+    }
+
+    /// Get scheduling policy
+    pub fn policy(&self) -> &QueuePolicy {
+        &self.policy
+    }
+
+    /// Record the host of a download for per-host limits
+    pub fn set_host(&mut self, download_id: &DownloadId, host: impl Into<String>) {
+        self.hosts.insert(download_id.clone(), host.into());
+    }
+
+    /// Put the queue on hold
+    ///
+    /// Holds nest: the queue resumes after a matching number of
+    /// [`release`](Self::release) calls.
+    pub fn hold(&mut self) {
+        self.holds += 1;
+    }
+
+    /// Release one hold
+    pub fn release(&mut self) {
+        self.holds = self.holds.saturating_sub(1);
+    }
+
+    /// Check if the queue is on hold
+    pub fn is_held(&self) -> bool {
+        self.holds > 0
+    }
+
+    /// Limit that suspends all downloads at `time`, if any
+    ///
+    /// Only queue-wide limits (hold and time windows) apply here.
+    pub fn suspended_at(&self, time: NaiveTime) -> Option<QueueLimit> {
+        if self.is_held() {
+            Some(QueueLimit::Held)
+        } else if !self.policy.allows(time) {
+            Some(QueueLimit::OutsideWindow)
+        } else {
+            None
         }
     }
 
+    /// Limit that suspends all downloads now, if any
+    pub fn suspended(&self) -> Option<QueueLimit> {
+        self.suspended_at(now())
+    }
+
     /// Set maximum concurrent downloads
     pub fn set_max_concurrent(&mut self, max: usize) {
         self.max_concurrent = max.max(1);
@@ -88,6 +183,7 @@ impl DownloadQueue {
             }
         };
 
+        self.hosts.remove(download_id);
         pending_removed || active_removed
     }
 
@@ -110,37 +206,99 @@ impl DownloadQueue {
         self.pending.retain(|id| id != download_id);
     }
 
+    /// Free the slot of an active download
+    ///
+    /// Unlike [`mark_finished`](Self::mark_finished) this keeps the
+    /// download if it was queued again in the meantime.
+    pub fn mark_inactive(&mut self, download_id: &DownloadId) {
+        self.active.retain(|id| id != download_id);
+    }
+
     /// Get next download to start (if any slot available)
     pub fn next_pending(&mut self) -> Option<DownloadId> {
-        if self.active.len() >= self.max_concurrent {
-            return None;
-        }
+        self.next_pending_at(now())
+    }
 
-        self.pending.pop_front().inspect(|id| {
-            self.active.push(id.clone());
-        })
+    /// Get next download to start at `time`
+    pub fn next_pending_at(&mut self, time: NaiveTime) -> Option<DownloadId> {
+        let pos = self
+            .evaluate(time)
+            .iter()
+            .position(|limit| limit.is_none())?;
+        let id = self.pending.remove(pos)?;
+        self.active.push(id.clone());
+        Some(id)
     }
 
     /// Get all downloads that can be started
     pub fn next_batch(&mut self) -> Vec<DownloadId> {
-        let available_slots = self.max_concurrent.saturating_sub(self.active.len());
+        let time = now();
         let mut batch = Vec::new();
-
-        for _ in 0..available_slots {
-            if let Some(id) = self.pending.pop_front() {
-                self.active.push(id.clone());
-                batch.push(id);
-            } else {
-                break;
-            }
+        while let Some(id) = self.next_pending_at(time) {
+            batch.push(id);
         }
-
         batch
     }
 
     /// Check if can start new download
     pub fn can_start(&self) -> bool {
-        !self.pending.is_empty() && self.active.len() < self.max_concurrent
+        self.evaluate(now()).iter().any(|limit| limit.is_none())
+    }
+
+    /// Scheduling status of every pending download at `time`
+    pub fn pending_status_at(&self, time: NaiveTime) -> Vec<PendingStatus> {
+        self.pending
+            .iter()
+            .zip(self.evaluate(time))
+            .map(|(id, blocked_by)| PendingStatus {
+                id: id.clone(),
+                blocked_by,
+            })
+            .collect()
+    }
+
+    /// Scheduling status of every pending download
+    pub fn pending_status(&self) -> Vec<PendingStatus> {
+        self.pending_status_at(now())
+    }
+
+    /// Limit blocking each pending download, in queue order
+    ///
+    /// Downloads that could start take their slot, so the limits reported
+    /// for later downloads account for the earlier ones starting first.
+    fn evaluate(&self, time: NaiveTime) -> Vec<Option<QueueLimit>> {
+        if let Some(limit) = self.suspended_at(time) {
+            return vec![Some(limit); self.pending.len()];
+        }
+
+        let mut active = self.active.len();
+        let mut per_host: HashMap<&str, usize> = HashMap::new();
+        for id in &self.active {
+            if let Some(host) = self.hosts.get(id) {
+                *per_host.entry(host).or_default() += 1;
+            }
+        }
+
+        self.pending
+            .iter()
+            .map(|id| {
+                if active >= self.max_concurrent {
+                    return Some(QueueLimit::MaxConcurrent);
+                }
+                let host = self.hosts.get(id).map(String::as_str);
+                if let (Some(host), Some(max)) = (host, self.policy.max_per_host) {
+                    if per_host.get(host).copied().unwrap_or(0) >= max {
+                        return Some(QueueLimit::PerHost);
+                    }
+                }
+
+                active += 1;
+                if let Some(host) = host {
+                    *per_host.entry(host).or_default() += 1;
+                }
+                None
+            })
+            .collect()
     }
 
     /// Get number of pending downloads
@@ -182,6 +340,7 @@ impl DownloadQueue {
     pub fn clear(&mut self) {
         self.pending.clear();
         self.active.clear();
+        self.hosts.clear();
     }
 
     /// Move a download up in the queue
@@ -203,6 +362,11 @@ impl DownloadQueue {
     }
 }
 
+/// Current local time of day
+fn now() -> NaiveTime {
+    chrono::Local::now().time()
+}
+
 impl Default for DownloadQueue {
     fn default() -> Self {
         Self::new()
@@ -440,16 +440,16 @@ fn manager_queue_stats() {
     let id1 = manager.add("https://a.com/1", "1");
     manager.add("https://a.com/2", "2");
 
-    let (pending, active, total) = manager.queue_stats();
-    assert_eq!(total, 2);
-    assert_eq!(pending, 2);
-    assert_eq!(active, 0);
+    let stats = manager.queue_stats();
+    assert_eq!(stats.total, 2);
+    assert_eq!(stats.pending, 2);
+    assert_eq!(stats.active, 0);
 
     manager.start(&id1).unwrap();
-    let (pending, active, total) = manager.queue_stats();
-    assert_eq!(pending, 1);
-    assert_eq!(active, 1);
-    assert_eq!(total, 2);
+    let stats = manager.queue_stats();
+    assert_eq!(stats.pending, 1);
+    assert_eq!(stats.active, 1);
+    assert_eq!(stats.total, 2);
 }
 
 #[rstest]
@@ -1111,9 +1111,9 @@ fn manager_queue_stats_checks_active() {
     let id = manager.add("https://a.com/f.zip", "f.zip");
     manager.start(&id).unwrap();
 
-    let (pending, active, completed) = manager.queue_stats();
-    assert_eq!(active, 1);
-    let _ = (pending, completed); // values checked implicitly
+    let stats = manager.queue_stats();
+    assert_eq!(stats.active, 1);
+    assert_eq!(stats.pending, 0);
 }
 
 #[rstest]
@@ -0,0 +1,724 @@
+//! Tests for the HTTP download engine, against a local HTTP server
+
+use std::io::{BufRead, BufReader, Write};
+use std::net::{TcpListener, TcpStream};
+use std::path::Path;
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use auroraview_downloads::engine::part_path;
+use auroraview_downloads::{
+    DownloadEngine, DownloadItem, DownloadManager, DownloadState, EngineConfig, QueueLimit,
+    QueuePolicy,
+};
+use parking_lot::Mutex;
+use rstest::*;
+use sha2::{Digest, Sha256};
+use tempfile::TempDir;
+
+// ========== Test Server ==========
+
+#[derive(Clone, Default)]
+struct Behavior {
+    /// Honour `Range` requests
+    ranges: bool,
+    /// Respond 503 to the first N requests
+    fail_first: usize,
+    /// Always respond with this status and no body
+    status: Option<u16>,
+    /// Drop the first body response after N bytes
+    cut_after: Option<usize>,
+    /// Delay between 4 KiB body chunks
+    chunk_delay: Duration,
+}
+
+struct TestServer {
+    url: String,
+    /// `Range` header of every request received
+    ranges: Arc<Mutex<Vec<Option<String>>>>,
+}
+
+impl TestServer {
+    fn start(body: Vec<u8>, behavior: Behavior) -> Self {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/pack.bin", listener.local_addr().unwrap());
+        let ranges = Arc::new(Mutex::new(Vec::new()));
+        let body = Arc::new(body);
+
+        let seen = Arc::clone(&ranges);
+        thread::spawn(move || {
+            for stream in listener.incoming().flatten() {
+                let body = Arc::clone(&body);
+                let behavior = behavior.clone();
+                let seen = Arc::clone(&seen);
+                thread::spawn(move || serve(stream, &body, &behavior, &seen));
+            }
+        });
+
+        Self { url, ranges }
+    }
+
+    fn requests(&self) -> Vec<Option<String>> {
+        self.ranges.lock().clone()
+    }
+}
+
+fn serve(
+    mut stream: TcpStream,
+    body: &[u8],
+    behavior: &Behavior,
+    seen: &Mutex<Vec<Option<String>>>,
+) {
+    let mut reader = BufReader::new(stream.try_clone().unwrap());
+    let mut range = None;
+    let mut line = String::new();
+    loop {
+        line.clear();
+        if reader.read_line(&mut line).unwrap_or(0) == 0 || line == "\r\n" {
+            break;
+        }
+        if let Some((name, value)) = line.split_once(':') {
+            if name.eq_ignore_ascii_case("range") {
+                range = Some(value.trim().to_string());
+            }
+        }
+    }
+
+    let index = {
+        let mut seen = seen.lock();
+        seen.push(range.clone());
+        seen.len() - 1
+    };
+
+    let empty = |stream: &mut TcpStream, status: u16, extra: &str| {
+        let _ = write!(
+            stream,
+            "HTTP/1.1 {status} Test\r\nContent-Length: 0\r\n{extra}Connection: close\r\n\r\n"
+        );
+    };
+    if let Some(status) = behavior.status {
+        return empty(&mut stream, status, "");
+    }
+    if index < behavior.fail_first {
+        return empty(&mut stream, 503, "");
+    }
+
+    let start = match (&range, behavior.ranges) {
+        (Some(range), true) => range
+            .strip_prefix("bytes=")
+            .and_then(|r| r.strip_suffix('-'))
+            .and_then(|r| r.parse().ok())
+            .unwrap_or(0),
+        _ => 0,
+    };
+    if start >= body.len() && start > 0 {
+        let extra = format!("Content-Range: bytes */{}\r\n", body.len());
+        return empty(&mut stream, 416, &extra);
+    }
+
+    let mut head = if start > 0 {
+        format!(
+            "HTTP/1.1 206 Partial Content\r\nContent-Range: bytes {}-{}/{}\r\n",
+            start,
+            body.len() - 1,
+            body.len()
+        )
+    } else {
+        "HTTP/1.1 200 OK\r\n".to_string()
+    };
+    head.push_str(&format!(
+        "Content-Length: {}\r\nContent-Type: application/octet-stream\r\nConnection: close\r\n\r\n",
+        body.len() - start
+    ));
+    if stream.write_all(head.as_bytes()).is_err() {
+        return;
+    }
+
+    let cut = (index == behavior.fail_first)
+        .then_some(behavior.cut_after)
+        .flatten();
+    let mut written = 0;
+    for chunk in body[start..].chunks(4096) {
+        if cut.is_some_and(|cut| written >= cut) {
+            return;
+        }
+        if stream.write_all(chunk).is_err() {
+            return;
+        }
+        written += chunk.len();
+        thread::sleep(behavior.chunk_delay);
+    }
+}
+
+// ========== Helpers ==========
+
+fn payload(len: usize) -> Vec<u8> {
+    (0..len).map(|i| (i * 31 % 251) as u8).collect()
+}
+
+fn sha256_hex(data: &[u8]) -> String {
+    Sha256::digest(data)
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect()
+}
+
+fn fast_config() -> EngineConfig {
+    EngineConfig::default()
+        .with_chunk_size(4096)
+        .with_retry_backoff(Duration::from_millis(10), Duration::from_millis(50))
+        .with_timeouts(Duration::from_secs(5), Duration::from_secs(5))
+}
+
+fn wait_for(what: &str, condition: impl Fn() -> bool) {
+    let deadline = Instant::now() + Duration::from_secs(10);
+    while !condition() {
+        assert!(Instant::now() < deadline, "timed out waiting for {what}");
+        thread::sleep(Duration::from_millis(5));
+    }
+}
+
+fn setup(server: &TestServer) -> (TempDir, DownloadEngine, String) {
+    let dir = TempDir::new().unwrap();
+    let manager = DownloadManager::new(Some(dir.path()));
+    let id = manager.add(&server.url, "pack.bin");
+    let engine = DownloadEngine::with_config(manager, fast_config());
+    (dir, engine, id)
+}
+
+fn run(engine: &DownloadEngine) {
+    engine.pump();
+    engine.wait_idle();
+}
+
+// ========== Transfers ==========
+
+#[rstest]
+fn downloads_file() {
+    let body = payload(200_000);
+    let server = TestServer::start(body.clone(), Behavior::default());
+    let (dir, engine, id) = setup(&server);
+
+    run(&engine);
+
+    let item = engine.manager().get(&id).unwrap();
+    assert_eq!(item.state, DownloadState::Completed, "{:?}", item.error);
+    assert_eq!(item.total_bytes, Some(200_000));
+    assert_eq!(item.received_bytes, 200_000);
+    assert_eq!(std::fs::read(dir.path().join("pack.bin")).unwrap(), body);
+    assert!(!part_path(&dir.path().join("pack.bin")).exists());
+    assert!(!engine.is_running(&id));
+}
+
+#[rstest]
+fn verifies_checksum() {
+    let body = payload(50_000);
+    let server = TestServer::start(body.clone(), Behavior::default());
+    let dir = TempDir::new().unwrap();
+    let manager = DownloadManager::new(Some(dir.path()));
+    let good = manager.add_item(
+        DownloadItem::new(&server.url, "good.bin")
+            .with_save_path(dir.path().join("good.bin"))
+            .with_sha256(sha256_hex(&body).to_uppercase()),
+    );
+    let bad = manager.add_item(
+        DownloadItem::new(&server.url, "bad.bin")
+            .with_save_path(dir.path().join("bad.bin"))
+            .with_sha256("0".repeat(64)),
+    );
+    let engine = DownloadEngine::with_config(manager, fast_config());
+
+    run(&engine);
+
+    let manager = engine.manager();
+    assert_eq!(manager.get(&good).unwrap().state, DownloadState::Completed);
+    let failed = manager.get(&bad).unwrap();
+    assert_eq!(failed.state, DownloadState::Failed);
+    assert!(failed.error.unwrap().contains("Checksum mismatch"));
+    assert!(!dir.path().join("bad.bin").exists());
+    assert!(!part_path(&dir.path().join("bad.bin")).exists());
+}
+
+#[rstest]
+fn creates_missing_directories() {
+    let server = TestServer::start(payload(10), Behavior::default());
+    let dir = TempDir::new().unwrap();
+    let dest = dir.path().join("textures").join("pack.bin");
+    let manager = DownloadManager::new(Some(dir.path()));
+    let id = manager.add_item(DownloadItem::new(&server.url, "pack.bin").with_save_path(&dest));
+    let engine = DownloadEngine::with_config(manager, fast_config());
+
+    run(&engine);
+
+    assert_eq!(
+        engine.manager().get(&id).unwrap().state,
+        DownloadState::Completed
+    );
+    assert!(dest.exists());
+}
+
+// ========== Resume ==========
+
+#[rstest]
+fn resumes_after_dropped_connection() {
+    let body = payload(100_000);
+    let behavior = Behavior {
+        ranges: true,
+        cut_after: Some(40_960),
+        ..Default::default()
+    };
+    let server = TestServer::start(body.clone(), behavior);
+    let (dir, engine, id) = setup(&server);
+
+    run(&engine);
+
+    assert_eq!(
+        engine.manager().get(&id).unwrap().state,
+        DownloadState::Completed
+    );
+    assert_eq!(std::fs::read(dir.path().join("pack.bin")).unwrap(), body);
+    assert_eq!(
+        server.requests(),
+        vec![None, Some("bytes=40960-".to_string())]
+    );
+}
+
+#[rstest]
+fn resumes_part_file_after_restart() {
+    let body = payload(30_000);
+    let behavior = Behavior {
+        ranges: true,
+        ..Default::default()
+    };
+    let server = TestServer::start(body.clone(), behavior);
+    let downloads = TempDir::new().unwrap();
+    let data = TempDir::new().unwrap();
+
+    // Simulate a crash mid-transfer
+    let id = {
+        let manager = DownloadManager::with_persistence(Some(downloads.path()), data.path());
+        let id = manager.add(&server.url, "pack.bin");
+        manager.start(&id).unwrap();
+        id
+    };
+    let dest = downloads.path().join("pack.bin");
+    std::fs::write(part_path(&dest), &body[..12_000]).unwrap();
+
+    let manager = DownloadManager::with_persistence(Some(downloads.path()), data.path());
+    let engine = DownloadEngine::with_config(manager, fast_config());
+    run(&engine);
+
+    assert_eq!(
+        engine.manager().get(&id).unwrap().state,
+        DownloadState::Completed
+    );
+    assert_eq!(std::fs::read(&dest).unwrap(), body);
+    assert_eq!(server.requests(), vec![Some("bytes=12000-".to_string())]);
+}
+
+#[rstest]
+fn complete_part_file_is_finished() {
+    let body = payload(8_000);
+    let behavior = Behavior {
+        ranges: true,
+        ..Default::default()
+    };
+    let server = TestServer::start(body.clone(), behavior);
+    let (dir, engine, id) = setup(&server);
+    let dest = dir.path().join("pack.bin");
+    std::fs::write(part_path(&dest), &body).unwrap();
+
+    run(&engine);
+
+    assert_eq!(
+        engine.manager().get(&id).unwrap().state,
+        DownloadState::Completed
+    );
+    assert_eq!(std::fs::read(&dest).unwrap(), body);
+}
+
+#[rstest]
+fn restarts_when_ranges_unsupported() {
+    let body = payload(20_000);
+    let server = TestServer::start(body.clone(), Behavior::default());
+    let (dir, engine, id) = setup(&server);
+    let dest = dir.path().join("pack.bin");
+    std::fs::write(part_path(&dest), b"stale bytes").unwrap();
+
+    run(&engine);
+
+    assert_eq!(
+        engine.manager().get(&id).unwrap().state,
+        DownloadState::Completed
+    );
+    assert_eq!(std::fs::read(&dest).unwrap(), body);
+    assert_eq!(server.requests(), vec![Some("bytes=11-".to_string())]);
+}
+
+// ========== Retries ==========
+
+#[rstest]
+fn retries_server_errors() {
+    let behavior = Behavior {
+        fail_first: 2,
+        ..Default::default()
+    };
+    let server = TestServer::start(payload(1_000), behavior);
+    let (_dir, engine, id) = setup(&server);
+
+    run(&engine);
+
+    assert_eq!(
+        engine.manager().get(&id).unwrap().state,
+        DownloadState::Completed
+    );
+    assert_eq!(server.requests().len(), 3);
+}
+
+#[rstest]
+fn gives_up_after_max_retries() {
+    let behavior = Behavior {
+        fail_first: 10,
+        ..Default::default()
+    };
+    let server = TestServer::start(payload(1_000), behavior);
+    let dir = TempDir::new().unwrap();
+    let manager = DownloadManager::new(Some(dir.path()));
+    let id = manager.add(&server.url, "pack.bin");
+    let engine = DownloadEngine::with_config(manager, fast_config().with_max_retries(2));
+
+    run(&engine);
+
+    let item = engine.manager().get(&id).unwrap();
+    assert_eq!(item.state, DownloadState::Failed);
+    assert!(item.error.unwrap().contains("503"));
+    assert_eq!(server.requests().len(), 3);
+}
+
+#[rstest]
+#[case(404)]
+#[case(403)]
+fn client_errors_are_not_retried(#[case] status: u16) {
+    let behavior = Behavior {
+        status: Some(status),
+        ..Default::default()
+    };
+    let server = TestServer::start(Vec::new(), behavior);
+    let (_dir, engine, id) = setup(&server);
+
+    run(&engine);
+
+    let item = engine.manager().get(&id).unwrap();
+    assert_eq!(item.state, DownloadState::Failed);
+    assert!(item.error.unwrap().contains(&status.to_string()));
+    assert_eq!(server.requests().len(), 1);
+}
+
+#[rstest]
+fn connection_refused_fails() {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}/pack.bin", listener.local_addr().unwrap());
+    drop(listener);
+
+    let dir = TempDir::new().unwrap();
+    let manager = DownloadManager::new(Some(dir.path()));
+    let id = manager.add(url, "pack.bin");
+    let engine = DownloadEngine::with_config(manager, fast_config().with_max_retries(1));
+
+    run(&engine);
+
+    assert_eq!(
+        engine.manager().get(&id).unwrap().state,
+        DownloadState::Failed
+    );
+}
+
+// ========== Control ==========
+
+fn slow_server(body: Vec<u8>) -> TestServer {
+    TestServer::start(
+        body,
+        Behavior {
+            ranges: true,
+            chunk_delay: Duration::from_millis(5),
+            ..Default::default()
+        },
+    )
+}
+
+#[rstest]
+fn pause_and_resume() {
+    let body = payload(400_000);
+    let server = slow_server(body.clone());
+    let (dir, engine, id) = setup(&server);
+    let dest = dir.path().join("pack.bin");
+
+    engine.pump();
+    wait_for("progress", || {
+        engine.manager().get(&id).unwrap().received_bytes > 0
+    });
+    engine.pause(&id).unwrap();
+
+    let item = engine.manager().get(&id).unwrap();
+    assert_eq!(item.state, DownloadState::Paused);
+    assert!(!engine.is_running(&id));
+    let kept = std::fs::metadata(part_path(&dest)).unwrap().len();
+    assert!(kept > 0 && kept < body.len() as u64);
+    // The slot is free while paused
+    assert_eq!(engine.manager().queue_stats().active, 0);
+
+    engine.resume(&id).unwrap();
+    engine.wait_idle();
+
+    assert_eq!(
+        engine.manager().get(&id).unwrap().state,
+        DownloadState::Completed
+    );
+    assert_eq!(std::fs::read(&dest).unwrap(), body);
+    assert_eq!(
+        server.requests().last().unwrap().as_deref(),
+        Some(format!("bytes={}-", kept).as_str())
+    );
+}
+
+#[rstest]
+#[case::engine_pause(true)]
+#[case::manager_pause(false)]
+fn pause_resume_stress(#[case] through_engine: bool) {
+    let body = payload(400_000);
+    let server = TestServer::start(
+        body.clone(),
+        Behavior {
+            ranges: true,
+            chunk_delay: Duration::from_millis(1),
+            ..Default::default()
+        },
+    );
+    let (dir, engine, id) = setup(&server);
+
+    engine.pump();
+    for _ in 0..50 {
+        let paused = if through_engine {
+            engine.pause(&id)
+        } else {
+            // Doesn't wait for the transfer, so resume races its shutdown
+            engine.manager().pause(&id)
+        };
+        // Fails once the download has completed
+        if paused.is_err() {
+            break;
+        }
+        engine.resume(&id).unwrap();
+    }
+    engine.wait_idle();
+
+    assert_eq!(
+        engine.manager().get(&id).unwrap().state,
+        DownloadState::Completed
+    );
+    assert_eq!(std::fs::read(dir.path().join("pack.bin")).unwrap(), body);
+    assert_eq!(engine.manager().queue_stats().active, 0);
+}
+
+#[rstest]
+fn resume_requires_paused() {
+    let server = TestServer::start(payload(10), Behavior::default());
+    let (_dir, engine, id) = setup(&server);
+    assert!(engine.resume(&id).is_err());
+    assert!(engine.resume(&"missing".to_string()).is_err());
+}
+
+#[rstest]
+fn cancel_removes_part_file() {
+    let server = slow_server(payload(400_000));
+    let (dir, engine, id) = setup(&server);
+
+    engine.pump();
+    wait_for("progress", || {
+        engine.manager().get(&id).unwrap().received_bytes > 0
+    });
+    engine.cancel(&id).unwrap();
+
+    assert_eq!(
+        engine.manager().get(&id).unwrap().state,
+        DownloadState::Cancelled
+    );
+    assert!(!part_path(&dir.path().join("pack.bin")).exists());
+    assert!(!dir.path().join("pack.bin").exists());
+}
+
+#[rstest]
+fn manager_cancel_stops_transfer() {
+    let server = slow_server(payload(400_000));
+    let (dir, engine, id) = setup(&server);
+
+    engine.pump();
+    wait_for("progress", || {
+        engine.manager().get(&id).unwrap().received_bytes > 0
+    });
+    engine.manager().cancel(&id).unwrap();
+    engine.wait_idle();
+
+    assert!(!part_path(&dir.path().join("pack.bin")).exists());
+}
+
+#[rstest]
+fn samples_speed() {
+    let server = slow_server(payload(400_000));
+    let dir = TempDir::new().unwrap();
+    let manager = DownloadManager::new(Some(dir.path()));
+    let id = manager.add(&server.url, "pack.bin");
+    let config = fast_config().with_speed_interval(Duration::from_millis(20));
+    let engine = DownloadEngine::with_config(manager, config);
+
+    engine.pump();
+    wait_for("speed sample", || {
+        engine.manager().get(&id).unwrap().speed.is_some()
+    });
+    let item = engine.manager().get(&id).unwrap();
+    assert!(item.speed.unwrap() > 0);
+    assert!(item.eta().is_some());
+
+    engine.cancel(&id).unwrap();
+}
+
+// ========== Queue ==========
+
+#[rstest]
+fn drives_queue_within_concurrency_limit() {
+    let server = TestServer::start(payload(50_000), Behavior::default());
+    let dir = TempDir::new().unwrap();
+    let manager = DownloadManager::new(Some(dir.path()));
+    manager.set_max_concurrent(2);
+    let ids: Vec<_> = (0..5)
+        .map(|i| manager.add(&server.url, format!("pack{i}.bin")))
+        .collect();
+    let engine = DownloadEngine::with_config(manager, fast_config());
+
+    let started = engine.pump();
+    assert_eq!(started.len(), 2);
+    engine.wait_idle();
+
+    for (i, id) in ids.iter().enumerate() {
+        assert_eq!(
+            engine.manager().get(id).unwrap().state,
+            DownloadState::Completed
+        );
+        assert!(dir.path().join(format!("pack{i}.bin")).exists());
+    }
+    assert!(engine.pump().is_empty());
+}
+
+#[rstest]
+fn part_path_appends_extension() {
+    assert_eq!(
+        part_path(Path::new("/data/pack.zip")),
+        Path::new("/data/pack.zip.part")
+    );
+}
+
+// ========== Policies ==========
+
+#[rstest]
+fn global_bandwidth_cap() {
+    let body = payload(30_000);
+    let server = TestServer::start(body.clone(), Behavior::default());
+    let (dir, engine, id) = setup(&server);
+    engine
+        .manager()
+        .set_policy(QueuePolicy::new().with_max_bytes_per_sec(20_000));
+
+    let started = Instant::now();
+    run(&engine);
+
+    // 20 KB burst, then 10 KB at 20 KB/s
+    assert!(started.elapsed() >= Duration::from_millis(400));
+    assert_eq!(
+        engine.manager().get(&id).unwrap().state,
+        DownloadState::Completed
+    );
+    assert_eq!(std::fs::read(dir.path().join("pack.bin")).unwrap(), body);
+}
+
+#[rstest]
+fn per_download_bandwidth_cap() {
+    let server = TestServer::start(payload(30_000), Behavior::default());
+    let dir = TempDir::new().unwrap();
+    let manager = DownloadManager::new(Some(dir.path()));
+    let capped = manager
+        .add_item(DownloadItem::new(&server.url, "capped.bin").with_max_bytes_per_sec(20_000));
+    let engine = DownloadEngine::with_config(manager, fast_config());
+
+    let started = Instant::now();
+    run(&engine);
+
+    assert!(started.elapsed() >= Duration::from_millis(400));
+    assert_eq!(
+        engine.manager().get(&capped).unwrap().state,
+        DownloadState::Completed
+    );
+}
+
+#[rstest]
+fn hold_defers_start_until_release() {
+    let server = TestServer::start(payload(1_000), Behavior::default());
+    let (_dir, engine, id) = setup(&server);
+
+    engine.hold();
+    assert!(engine.pump().is_empty());
+    let stats = engine.manager().queue_stats();
+    assert!(stats.held);
+    assert_eq!(stats.items[0].blocked_by, Some(QueueLimit::Held));
+
+    engine.release();
+    engine.wait_idle();
+    assert_eq!(
+        engine.manager().get(&id).unwrap().state,
+        DownloadState::Completed
+    );
+}
+
+#[rstest]
+fn hold_suspends_running_transfer() {
+    let body = payload(400_000);
+    let server = slow_server(body.clone());
+    let (dir, engine, id) = setup(&server);
+
+    engine.pump();
+    wait_for("progress", || {
+        engine.manager().get(&id).unwrap().received_bytes > 0
+    });
+    engine.hold();
+    thread::sleep(Duration::from_millis(150));
+    let held_at = engine.manager().get(&id).unwrap().received_bytes;
+    thread::sleep(Duration::from_millis(200));
+    assert_eq!(engine.manager().get(&id).unwrap().received_bytes, held_at);
+    assert!(engine.is_running(&id));
+
+    engine.release();
+    engine.wait_idle();
+    assert_eq!(std::fs::read(dir.path().join("pack.bin")).unwrap(), body);
+}
+
+#[rstest]
+fn per_host_limit_in_engine() {
+    let server = slow_server(payload(100_000));
+    let dir = TempDir::new().unwrap();
+    let manager = DownloadManager::new(Some(dir.path()));
+    manager.set_policy(QueuePolicy::new().with_max_per_host(1));
+    let first = manager.add(&server.url, "a.bin");
+    let second = manager.add(&server.url, "b.bin");
+    let engine = DownloadEngine::with_config(manager, fast_config());
+
+    assert_eq!(engine.pump(), vec![first]);
+    let stats = engine.manager().queue_stats();
+    assert_eq!(stats.items[0].id, second);
+    assert_eq!(stats.items[0].blocked_by, Some(QueueLimit::PerHost));
+
+    engine.wait_idle();
+    assert_eq!(
+        engine.manager().get(&second).unwrap().state,
+        DownloadState::Completed
+    );
+}
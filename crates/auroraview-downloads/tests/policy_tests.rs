@@ -0,0 +1,264 @@
+//! Tests for queue scheduling policies
+
+use std::path::Path;
+
+use auroraview_downloads::{
+    DownloadError, DownloadItem, DownloadManager, DownloadQueue, QueueLimit, QueuePolicy,
+    TimeWindow,
+};
+use chrono::{Duration, Local, NaiveTime};
+use rstest::*;
+
+fn t(h: u32, m: u32) -> NaiveTime {
+    NaiveTime::from_hms_opt(h, m, 0).unwrap()
+}
+
+fn queue_with_hosts(max: usize, policy: QueuePolicy, items: &[(&str, &str)]) -> DownloadQueue {
+    let mut queue = DownloadQueue::with_max_concurrent(max).with_policy(policy);
+    for (id, host) in items {
+        queue.set_host(&id.to_string(), *host);
+        queue.enqueue(id.to_string());
+    }
+    queue
+}
+
+fn blocked(queue: &DownloadQueue, time: NaiveTime) -> Vec<(String, Option<QueueLimit>)> {
+    queue
+        .pending_status_at(time)
+        .into_iter()
+        .map(|s| (s.id, s.blocked_by))
+        .collect()
+}
+
+/// A window that is closed right now
+fn closed_window() -> TimeWindow {
+    let now = Local::now().time();
+    TimeWindow::new(now + Duration::hours(2), now + Duration::hours(3))
+}
+
+// ========== Per-host Limits ==========
+
+#[rstest]
+fn per_host_limit_skips_blocked_hosts() {
+    let policy = QueuePolicy::new().with_max_per_host(1);
+    let mut queue = queue_with_hosts(
+        3,
+        policy,
+        &[("a1", "a.com"), ("a2", "a.com"), ("b1", "b.com")],
+    );
+
+    assert_eq!(queue.next_batch(), vec!["a1", "b1"]);
+    assert_eq!(queue.pending(), vec!["a2"]);
+    assert!(!queue.can_start());
+
+    queue.mark_finished(&"a1".to_string());
+    assert_eq!(queue.next_pending(), Some("a2".to_string()));
+}
+
+#[rstest]
+fn per_host_limit_counts_active() {
+    let policy = QueuePolicy::new().with_max_per_host(2);
+    let mut queue = queue_with_hosts(
+        10,
+        policy,
+        &[("a1", "a.com"), ("a2", "a.com"), ("a3", "a.com")],
+    );
+    queue.mark_active(&"a1".to_string());
+
+    assert_eq!(
+        blocked(&queue, t(12, 0)),
+        vec![
+            ("a2".to_string(), None),
+            ("a3".to_string(), Some(QueueLimit::PerHost)),
+        ]
+    );
+}
+
+#[rstest]
+fn unknown_host_is_not_limited() {
+    let mut queue = DownloadQueue::new().with_policy(QueuePolicy::new().with_max_per_host(1));
+    queue.enqueue("x".to_string());
+    queue.enqueue("y".to_string());
+    assert_eq!(queue.next_batch().len(), 2);
+}
+
+// ========== Global Limit ==========
+
+#[rstest]
+fn status_reports_max_concurrent() {
+    let queue = queue_with_hosts(1, QueuePolicy::new(), &[("a", "a.com"), ("b", "b.com")]);
+    assert_eq!(
+        blocked(&queue, t(12, 0)),
+        vec![
+            ("a".to_string(), None),
+            ("b".to_string(), Some(QueueLimit::MaxConcurrent)),
+        ]
+    );
+}
+
+// ========== Holds ==========
+
+#[rstest]
+fn holds_nest() {
+    let mut queue = queue_with_hosts(3, QueuePolicy::new(), &[("a", "a.com")]);
+    queue.hold();
+    queue.hold();
+    assert!(queue.is_held());
+    assert_eq!(queue.next_pending(), None);
+    assert_eq!(
+        blocked(&queue, t(12, 0)),
+        vec![("a".to_string(), Some(QueueLimit::Held))]
+    );
+
+    queue.release();
+    assert!(queue.is_held());
+    queue.release();
+    queue.release();
+    assert!(!queue.is_held());
+    assert_eq!(queue.next_pending(), Some("a".to_string()));
+}
+
+// ========== Time Windows ==========
+
+#[rstest]
+fn overnight_window() {
+    let policy = QueuePolicy::new().with_window(TimeWindow::hm((22, 0), (6, 0)).unwrap());
+    let mut queue = queue_with_hosts(3, policy, &[("a", "a.com")]);
+
+    assert_eq!(
+        blocked(&queue, t(14, 0)),
+        vec![("a".to_string(), Some(QueueLimit::OutsideWindow))]
+    );
+    assert_eq!(
+        queue.suspended_at(t(14, 0)),
+        Some(QueueLimit::OutsideWindow)
+    );
+    assert_eq!(queue.next_pending_at(t(14, 0)), None);
+    assert_eq!(queue.suspended_at(t(23, 30)), None);
+    assert_eq!(queue.next_pending_at(t(3, 0)), Some("a".to_string()));
+}
+
+#[rstest]
+fn hold_takes_precedence_over_window() {
+    let policy = QueuePolicy::new().with_window(TimeWindow::hm((1, 0), (2, 0)).unwrap());
+    let mut queue = DownloadQueue::new().with_policy(policy);
+    queue.hold();
+    assert_eq!(queue.suspended_at(t(12, 0)), Some(QueueLimit::Held));
+}
+
+// ========== Manager Integration ==========
+
+#[rstest]
+fn manager_queue_stats_reports_limits() {
+    let manager = DownloadManager::new(Some(Path::new(".")));
+    manager.set_max_concurrent(2);
+    manager.set_policy(QueuePolicy::new().with_max_per_host(1));
+    let a1 = manager.add("https://a.com/1", "1");
+    let a2 = manager.add("https://a.com/2", "2");
+    let b1 = manager.add("https://b.com/1", "1");
+    let c1 = manager.add("https://c.com/1", "1");
+
+    let stats = manager.queue_stats();
+    assert_eq!(stats.pending, 4);
+    assert!(!stats.held);
+    let limits: Vec<_> = stats.items.iter().map(|s| (&s.id, s.blocked_by)).collect();
+    assert_eq!(
+        limits,
+        vec![
+            (&a1, None),
+            (&a2, Some(QueueLimit::PerHost)),
+            (&b1, None),
+            (&c1, Some(QueueLimit::MaxConcurrent)),
+        ]
+    );
+
+    assert_eq!(manager.next_to_start(), Some(a1));
+    assert_eq!(manager.next_to_start(), Some(b1));
+    assert_eq!(manager.next_to_start(), None);
+}
+
+#[rstest]
+fn manager_window_suspends() {
+    let manager = DownloadManager::new(Some(Path::new(".")));
+    manager.add("https://a.com/1", "1");
+    manager.set_policy(QueuePolicy::new().with_window(closed_window()));
+
+    assert_eq!(manager.suspended(), Some(QueueLimit::OutsideWindow));
+    assert!(!manager.can_start_new());
+    assert_eq!(
+        manager.queue_stats().items[0].blocked_by,
+        Some(QueueLimit::OutsideWindow)
+    );
+
+    manager.set_policy(QueuePolicy::new());
+    assert!(manager.can_start_new());
+}
+
+#[rstest]
+fn manager_hold_release() {
+    let manager = DownloadManager::new(Some(Path::new(".")));
+    manager.add("https://a.com/1", "1");
+
+    manager.hold();
+    assert!(manager.is_held());
+    assert_eq!(manager.suspended(), Some(QueueLimit::Held));
+    assert!(manager.next_to_start().is_none());
+
+    manager.release();
+    assert!(!manager.is_held());
+    assert!(manager.next_to_start().is_some());
+}
+
+#[rstest]
+fn manager_policy_round_trip() {
+    let manager = DownloadManager::new(Some(Path::new(".")));
+    let policy = QueuePolicy::new()
+        .with_max_bytes_per_sec(1_000_000)
+        .with_max_per_host(2);
+    manager.set_policy(policy.clone());
+    assert_eq!(manager.policy(), policy);
+}
+
+#[rstest]
+fn manager_speed_limit() {
+    let manager = DownloadManager::new(Some(Path::new(".")));
+    let id = manager.add("https://a.com/1", "1");
+
+    manager.set_speed_limit(&id, Some(4096)).unwrap();
+    assert_eq!(manager.get(&id).unwrap().max_bytes_per_sec, Some(4096));
+    manager.set_speed_limit(&id, None).unwrap();
+    assert_eq!(manager.get(&id).unwrap().max_bytes_per_sec, None);
+
+    let err = manager
+        .set_speed_limit(&"missing".to_string(), Some(1))
+        .unwrap_err();
+    assert!(matches!(err, DownloadError::NotFound(_)));
+}
+
+// ========== Serialization ==========
+
+#[rstest]
+fn policy_serde() {
+    let policy = QueuePolicy::new()
+        .with_max_bytes_per_sec(2048)
+        .with_window(TimeWindow::hm((22, 0), (6, 0)).unwrap());
+    let json = serde_json::to_value(&policy).unwrap();
+    assert_eq!(json["maxBytesPerSec"], 2048);
+    assert_eq!(json["windows"][0]["start"], "22:00:00");
+    assert!(json.get("maxPerHost").is_none());
+
+    let back: QueuePolicy = serde_json::from_value(json).unwrap();
+    assert_eq!(back, policy);
+    let empty: QueuePolicy = serde_json::from_str("{}").unwrap();
+    assert_eq!(empty, QueuePolicy::default());
+}
+
+#[rstest]
+fn item_speed_limit_serde() {
+    let item = DownloadItem::new("https://a.com/f", "f").with_max_bytes_per_sec(100);
+    let json = serde_json::to_value(&item).unwrap();
+    assert_eq!(json["maxBytesPerSec"], 100);
+
+    let plain = serde_json::to_value(DownloadItem::new("https://a.com/f", "f")).unwrap();
+    assert!(plain.get("maxBytesPerSec").is_none());
+}
@@ -6,6 +6,7 @@
 //! - Get, set, remove cookies
 //! - Query cookies by domain, name, path
 //! - Cookie store management
+//! - Pluggable storage ([`CookieStoreBackend`]) shared with the WebView cookie jar
 //! - Event notifications for changes
 
 use std::sync::Arc;
@@ -143,6 +144,22 @@ pub struct CookieStore {
     pub incognito: bool,
 }
 
+/// Storage behind `chrome.cookies`
+///
+/// The default [`MemoryCookieStore`] keeps cookies for the lifetime of the
+/// API instance. Hosts plug in a persistent store (such as the cookie jar
+/// shared with the WebView backends) through [`CookiesApi::with_store`].
+pub trait CookieStoreBackend: Send + Sync {
+    /// All unexpired cookies of a store
+    fn cookies(&self, store_id: &str) -> Vec<Cookie>;
+
+    /// Insert or replace a cookie (identified by domain, path, name and store)
+    fn set_cookie(&self, cookie: Cookie) -> ExtensionResult<()>;
+
+    /// Remove a cookie, returning whether it existed
+    fn remove_cookie(&self, cookie: &Cookie) -> ExtensionResult<bool>;
+}
+
 /// Cookie key for storage
 #[derive(Debug, Clone, Hash, PartialEq, Eq)]
 struct CookieKey {
@@ -152,10 +169,53 @@ struct CookieKey {
     store_id: String,
 }
 
+impl CookieKey {
+    fn of(cookie: &Cookie) -> Self {
+        Self {
+            domain: cookie.domain.clone(),
+            path: cookie.path.clone(),
+            name: cookie.name.clone(),
+            store_id: cookie.store_id.clone(),
+        }
+    }
+}
+
+/// In-memory cookie store
+#[derive(Debug, Default)]
+pub struct MemoryCookieStore {
+    cookies: DashMap<CookieKey, Cookie>,
+}
+
+impl MemoryCookieStore {
+    /// Create an empty store
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl CookieStoreBackend for MemoryCookieStore {
+    fn cookies(&self, store_id: &str) -> Vec<Cookie> {
+        self.cookies
+            .iter()
+            .filter(|entry| entry.key().store_id == store_id)
+            .map(|entry| entry.value().clone())
+            .collect()
+    }
+
+    fn set_cookie(&self, cookie: Cookie) -> ExtensionResult<()> {
+        self.cookies.insert(CookieKey::of(&cookie), cookie);
+        Ok(())
+    }
+
+    fn remove_cookie(&self, cookie: &Cookie) -> ExtensionResult<bool> {
+        Ok(self.cookies.remove(&CookieKey::of(cookie)).is_some())
+    }
+}
+
 /// Cookies API handler
 pub struct CookiesApi {
-    /// In-memory cookie storage
-    cookies: Arc<DashMap<CookieKey, Cookie>>,
+    /// Cookie storage
+    store: Arc<dyn CookieStoreBackend>,
 }
 
 impl Default for CookiesApi {
@@ -165,11 +225,14 @@ impl Default for CookiesApi {
 }
 
 impl CookiesApi {
-    /// Create a new CookiesApi instance
+    /// Create a new CookiesApi instance with in-memory storage
     pub fn new() -> Self {
-        Self {
-            cookies: Arc::new(DashMap::new()),
-        }
+        Self::with_store(Arc::new(MemoryCookieStore::new()))
+    }
+
+    /// Create a CookiesApi backed by a shared cookie store
+    pub fn with_store(store: Arc<dyn CookieStoreBackend>) -> Self {
+        Self { store }
     }
 
     /// Extract domain from URL
@@ -187,38 +250,34 @@ impl CookiesApi {
             .unwrap_or_else(|| "/".to_string())
     }
 
-    /// Get a cookie
-    pub fn get(&self, details: CookieDetails) -> ExtensionResult<Value> {
-        let store_id = details.store_id.unwrap_or_else(|| "0".to_string());
+    /// Whether a cookie domain applies to a host (exact or parent domain)
+    fn domain_matches(cookie_domain: &str, host: &str) -> bool {
+        let cookie_domain = cookie_domain.trim_start_matches('.');
+        cookie_domain == host || host.ends_with(&format!(".{}", cookie_domain))
+    }
 
-        let domain = Self::domain_from_url(&details.url)
+    /// Find the cookie that `get`/`remove` refer to, preferring the root path
+    fn find(&self, url: &str, name: &str, store_id: &str) -> ExtensionResult<Option<Cookie>> {
+        let domain = Self::domain_from_url(url)
             .ok_or_else(|| ExtensionError::InvalidParams("Invalid URL".into()))?;
 
-        let key = CookieKey {
-            domain: domain.clone(),
-            path: "/".to_string(),
-            name: details.name.clone(),
-            store_id: store_id.clone(),
-        };
-
-        // Try exact match first
-        if let Some(cookie) = self.cookies.get(&key) {
-            return Ok(serde_json::to_value(cookie.value())?);
-        }
+        let mut matches: Vec<Cookie> = self
+            .store
+            .cookies(store_id)
+            .into_iter()
+            .filter(|c| c.name == name && Self::domain_matches(&c.domain, &domain))
+            .collect();
+        matches.sort_by_key(|c| (c.domain.trim_start_matches('.') != domain, c.path != "/"));
+        Ok(matches.into_iter().next())
+    }
 
-        // Try with domain prefix (for subdomain cookies)
-        for entry in self.cookies.iter() {
-            let k = entry.key();
-            let cookie = entry.value();
-            if k.name == details.name
-                && k.store_id == store_id
-                && (k.domain == domain || domain.ends_with(&format!(".{}", k.domain)))
-            {
-                return Ok(serde_json::to_value(cookie)?);
-            }
+    /// Get a cookie
+    pub fn get(&self, details: CookieDetails) -> ExtensionResult<Value> {
+        let store_id = details.store_id.unwrap_or_else(|| "0".to_string());
+        match self.find(&details.url, &details.name, &store_id)? {
+            Some(cookie) => Ok(serde_json::to_value(cookie)?),
+            None => Ok(json!(null)),
         }
-
-        Ok(json!(null))
     }
 
     /// Get all cookies matching query
@@ -228,15 +287,10 @@ impl CookiesApi {
         let url_domain = query.url.as_ref().and_then(|u| Self::domain_from_url(u));
 
         let results: Vec<Cookie> = self
-            .cookies
-            .iter()
-            .map(|entry| entry.value().clone())
+            .store
+            .cookies(&store_id)
+            .into_iter()
             .filter(|cookie| {
-                // Store ID filter
-                if cookie.store_id != store_id {
-                    return false;
-                }
-
                 // Name filter
                 if let Some(ref name) = query.name {
                     if &cookie.name != name {
@@ -246,7 +300,9 @@ impl CookiesApi {
 
                 // Domain filter
                 if let Some(ref domain) = query.domain {
-                    if &cookie.domain != domain && !cookie.domain.ends_with(&format!(".{}", domain))
+                    let cookie_domain = cookie.domain.trim_start_matches('.');
+                    let domain = domain.trim_start_matches('.');
+                    if cookie_domain != domain && !cookie_domain.ends_with(&format!(".{}", domain))
                     {
                         return false;
                     }
@@ -254,9 +310,7 @@ impl CookiesApi {
 
                 // URL domain filter
                 if let Some(ref url_domain) = url_domain {
-                    if &cookie.domain != url_domain
-                        && !url_domain.ends_with(&format!(".{}", cookie.domain))
-                    {
+                    if !Self::domain_matches(&cookie.domain, url_domain) {
                         return false;
                     }
                 }
@@ -305,27 +359,20 @@ impl CookiesApi {
             .unwrap_or_else(|| Self::path_from_url(&details.url));
 
         let cookie = Cookie {
-            name: details.name.clone(),
+            name: details.name,
             value: details.value.unwrap_or_default(),
             domain: domain.clone(),
             host_only: !domain.starts_with('.'),
-            path: path.clone(),
+            path,
             secure: details.secure.unwrap_or(false),
             http_only: details.http_only.unwrap_or(false),
             same_site: details.same_site.unwrap_or_default(),
             session: details.expiration_date.is_none(),
             expiration_date: details.expiration_date,
-            store_id: store_id.clone(),
-        };
-
-        let key = CookieKey {
-            domain,
-            path,
-            name: details.name,
             store_id,
         };
 
-        self.cookies.insert(key, cookie.clone());
+        self.store.set_cookie(cookie.clone())?;
         Ok(serde_json::to_value(cookie)?)
     }
 
@@ -333,30 +380,13 @@ impl CookiesApi {
     pub fn remove(&self, details: CookieDetails) -> ExtensionResult<Value> {
         let store_id = details.store_id.unwrap_or_else(|| "0".to_string());
 
-        let domain = Self::domain_from_url(&details.url)
-            .ok_or_else(|| ExtensionError::InvalidParams("Invalid URL".into()))?;
-
-        // Find and remove matching cookie
-        let key_to_remove: Option<CookieKey> = self
-            .cookies
-            .iter()
-            .find(|entry| {
-                let k = entry.key();
-                k.name == details.name
-                    && k.store_id == store_id
-                    && (k.domain == domain || domain.ends_with(&format!(".{}", k.domain)))
-            })
-            .map(|entry| entry.key().clone());
-
-        if let Some(key) = key_to_remove {
-            self.cookies.remove(&key);
-            Ok(json!({
+        match self.find(&details.url, &details.name, &store_id)? {
+            Some(cookie) if self.store.remove_cookie(&cookie)? => Ok(json!({
                 "url": details.url,
                 "name": details.name,
                 "storeId": store_id
-            }))
-        } else {
-            Ok(json!(null))
+            })),
+            _ => Ok(json!(null)),
         }
     }
 
@@ -468,4 +498,37 @@ mod tests {
 
         assert!(result.is_null());
     }
+
+    #[test]
+    fn test_shared_store() {
+        let store: Arc<dyn CookieStoreBackend> = Arc::new(MemoryCookieStore::new());
+        let writer = CookiesApi::with_store(store.clone());
+        let reader = CookiesApi::with_store(store);
+
+        writer
+            .set(SetDetails {
+                url: "https://example.com/app".to_string(),
+                name: "sid".to_string(),
+                value: Some("abc".to_string()),
+                domain: Some(".example.com".to_string()),
+                path: Some("/".to_string()),
+                secure: None,
+                http_only: None,
+                same_site: None,
+                expiration_date: None,
+                store_id: None,
+            })
+            .unwrap();
+
+        let result = reader
+            .get(CookieDetails {
+                url: "https://www.example.com".to_string(),
+                name: "sid".to_string(),
+                store_id: None,
+            })
+            .unwrap();
+        let cookie: Cookie = serde_json::from_value(result).unwrap();
+        assert_eq!(cookie.value, "abc");
+        assert!(!cookie.host_only);
+    }
 }
@@ -3,7 +3,7 @@
 //! Provides address bar (omnibox) integration for extensions.
 //!
 //! ## Features
-//! - Register keyword for omnibox
+//! - Register keyword for omnibox (from the manifest `omnibox.keyword` key)
 //! - Provide suggestions as user types
 //! - Handle user selection
 
@@ -50,6 +50,8 @@ pub enum OnInputEnteredDisposition {
 /// Omnibox state for an extension
 #[derive(Debug, Clone, Default)]
 struct OmniboxState {
+    /// Registered keyword
+    keyword: Option<String>,
     /// Default suggestion
     default_suggestion: Option<DefaultSuggestion>,
     /// Current suggestions
@@ -57,6 +59,9 @@ struct OmniboxState {
 }
 
 /// Omnibox API handler
+///
+/// Clones share the same state.
+#[derive(Clone)]
 pub struct OmniboxApi {
     /// Per-extension omnibox state
     states: Arc<DashMap<String, OmniboxState>>,
@@ -76,6 +81,50 @@ impl OmniboxApi {
         }
     }
 
+    /// Register the keyword that routes address bar input to an extension
+    pub fn set_keyword(&self, extension_id: &str, keyword: impl Into<String>) {
+        self.states
+            .entry(extension_id.to_string())
+            .or_default()
+            .keyword = Some(keyword.into());
+    }
+
+    /// Get the keyword registered by an extension
+    pub fn keyword(&self, extension_id: &str) -> Option<String> {
+        self.states
+            .get(extension_id)
+            .and_then(|s| s.keyword.clone())
+    }
+
+    /// Find the extension that registered a keyword (case-insensitive)
+    pub fn extension_for_keyword(&self, keyword: &str) -> Option<String> {
+        self.states
+            .iter()
+            .find(|entry| {
+                entry
+                    .keyword
+                    .as_deref()
+                    .is_some_and(|k| k.eq_ignore_ascii_case(keyword))
+            })
+            .map(|entry| entry.key().clone())
+    }
+
+    /// Split address bar input into the target extension and the text after its keyword
+    ///
+    /// Returns `None` unless the input starts with a registered keyword
+    /// followed by whitespace.
+    pub fn parse_input(&self, input: &str) -> Option<(String, String)> {
+        let input = input.trim_start();
+        let (keyword, rest) = input.split_once(char::is_whitespace)?;
+        let extension_id = self.extension_for_keyword(keyword)?;
+        Some((extension_id, rest.trim_start().to_string()))
+    }
+
+    /// Remove all omnibox state of an extension
+    pub fn remove_extension(&self, extension_id: &str) {
+        self.states.remove(extension_id);
+    }
+
     /// Set default suggestion
     pub fn set_default_suggestion(
         &self,
@@ -185,4 +234,24 @@ mod tests {
         let suggestions = api.get_suggestions("test-ext");
         assert_eq!(suggestions.len(), 2);
     }
+
+    #[test]
+    fn test_keyword_routing() {
+        let api = OmniboxApi::new();
+        api.set_keyword("test-ext", "gh");
+        api.set_keyword("other-ext", "npm");
+
+        assert_eq!(api.keyword("test-ext").as_deref(), Some("gh"));
+        assert_eq!(api.extension_for_keyword("GH").as_deref(), Some("test-ext"));
+        assert_eq!(
+            api.parse_input("gh  rust lang"),
+            Some(("test-ext".to_string(), "rust lang".to_string()))
+        );
+        // Keyword alone or unknown keyword is not routed
+        assert_eq!(api.parse_input("gh"), None);
+        assert_eq!(api.parse_input("ghx rust"), None);
+
+        api.remove_extension("test-ext");
+        assert_eq!(api.parse_input("gh rust"), None);
+    }
 }
@@ -12,6 +12,7 @@ use std::sync::Arc;
 use dashmap::DashMap;
 use walkdir::WalkDir;
 
+use crate::apis::omnibox::OmniboxApi;
 use crate::error::{ExtensionError, ExtensionResult};
 use crate::manifest::Manifest;
 use crate::runtime::ExtensionRuntime;
@@ -115,6 +116,8 @@ pub struct ExtensionHost {
     storage: Arc<StorageBackend>,
     /// Extension runtimes (for background scripts)
     runtimes: Arc<DashMap<ExtensionId, ExtensionRuntime>>,
+    /// Omnibox keywords and suggestions of loaded extensions
+    omnibox: OmniboxApi,
 }
 
 impl ExtensionHost {
@@ -127,6 +130,7 @@ impl ExtensionHost {
             extensions: Arc::new(DashMap::new()),
             storage,
             runtimes: Arc::new(DashMap::new()),
+            omnibox: OmniboxApi::new(),
         }
     }
 
@@ -135,6 +139,11 @@ impl ExtensionHost {
         &self.storage
     }
 
+    /// Get the omnibox API holding the keywords of loaded extensions
+    pub fn omnibox(&self) -> &OmniboxApi {
+        &self.omnibox
+    }
+
     /// Load all extensions from the extensions directory
     pub async fn load_extensions(&self) -> ExtensionResult<Vec<ExtensionId>> {
         let mut loaded_ids = Vec::new();
@@ -202,6 +211,11 @@ impl ExtensionHost {
             enabled: true,
         };
 
+        // Route address bar input starting with the keyword to the extension
+        if let Some(keyword) = extension.manifest.omnibox_keyword() {
+            self.omnibox.set_keyword(&id, keyword);
+        }
+
         // Store extension
         self.extensions.insert(id.clone(), extension);
 
@@ -219,6 +233,7 @@ impl ExtensionHost {
 
         // Clean up runtime
         self.runtimes.remove(id);
+        self.omnibox.remove_extension(id);
 
         Ok(())
     }
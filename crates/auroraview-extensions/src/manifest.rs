@@ -88,6 +88,10 @@ pub struct Manifest {
     #[serde(default)]
     pub chrome_url_overrides: Option<ChromeUrlOverrides>,
 
+    /// Omnibox keyword
+    #[serde(default)]
+    pub omnibox: Option<OmniboxConfig>,
+
     /// Minimum Chrome version
     #[serde(default)]
     pub minimum_chrome_version: Option<String>,
@@ -356,6 +360,13 @@ pub struct OptionsUiConfig {
     pub open_in_tab: bool,
 }
 
+/// Omnibox configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OmniboxConfig {
+    /// Keyword that routes address bar input to the extension
+    pub keyword: String,
+}
+
 /// Chrome URL overrides
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct ChromeUrlOverrides {
@@ -407,6 +418,15 @@ impl Manifest {
             ));
         }
 
+        if let Some(omnibox) = &self.omnibox {
+            if omnibox.keyword.is_empty() || omnibox.keyword.contains(char::is_whitespace) {
+                return Err(ExtensionError::ManifestInvalid(format!(
+                    "Invalid omnibox keyword: {:?}",
+                    omnibox.keyword
+                )));
+            }
+        }
+
         Ok(())
     }
 
@@ -440,6 +460,11 @@ impl Manifest {
             .and_then(|sp| sp.default_path.as_deref())
     }
 
+    /// Get the omnibox keyword
+    pub fn omnibox_keyword(&self) -> Option<&str> {
+        self.omnibox.as_ref().map(|o| o.keyword.as_str())
+    }
+
     /// Get the action popup path
     pub fn get_popup_path(&self) -> Option<&str> {
         self.action
@@ -516,6 +541,23 @@ mod tests {
         assert!(manifest.has_permission("tabs"));
     }
 
+    #[test]
+    fn test_omnibox_keyword() {
+        let json = r#"{
+            "manifest_version": 3,
+            "name": "Test Extension",
+            "version": "1.0.0",
+            "omnibox": { "keyword": "gh" }
+        }"#;
+
+        let manifest = Manifest::from_json(json).unwrap();
+        assert_eq!(manifest.omnibox_keyword(), Some("gh"));
+        assert!(manifest.validate().is_ok());
+
+        let invalid = json.replace("\"gh\"", "\"g h\"");
+        assert!(Manifest::from_json(&invalid).unwrap().validate().is_err());
+    }
+
     #[test]
     fn test_url_pattern_matching() {
         // *.example.com matches subdomains like sub.example.com, not example.com itself
@@ -659,6 +659,36 @@ async fn host_storage_returns_arc() {
     let _storage = host.storage();
 }
 
+// ---------------------------------------------------------------------------
+// omnibox keyword
+// ---------------------------------------------------------------------------
+
+#[tokio::test]
+async fn omnibox_keyword_registered_on_load_and_removed_on_unload() {
+    let tmp = TempDir::new().unwrap();
+    let manifest = r#"{
+        "manifest_version": 3,
+        "name": "GitHub Search",
+        "version": "1.0.0",
+        "omnibox": {"keyword": "gh"}
+    }"#;
+    create_extension_dir(&tmp, "gh-ext", manifest);
+
+    let host = ExtensionHost::new(default_config(&tmp));
+    host.load_extension(&tmp.path().join("gh-ext"))
+        .await
+        .unwrap();
+    assert_eq!(host.omnibox().keyword("gh-ext").as_deref(), Some("gh"));
+    assert_eq!(
+        host.omnibox().parse_input("gh rust"),
+        Some(("gh-ext".to_string(), "rust".to_string()))
+    );
+
+    host.unload_extension("gh-ext").unwrap();
+    assert!(host.omnibox().keyword("gh-ext").is_none());
+    assert!(host.omnibox().parse_input("gh rust").is_none());
+}
+
 // ---------------------------------------------------------------------------
 // rstest parametric: permission polyfill inclusion
 // ---------------------------------------------------------------------------
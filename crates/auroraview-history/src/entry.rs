@@ -101,41 +101,63 @@ impl HistoryEntry {
 
     /// Calculate relevance score for search
     pub fn relevance_score(&self, query: &str) -> u32 {
-        let query = query.to_lowercase();
-        let mut score = 0u32;
-
-        // Title exact match
-        if self.title.to_lowercase() == query {
-            score += 100;
-        } else if self.title.to_lowercase().starts_with(&query) {
-            score += 50;
-        } else if self.title.to_lowercase().contains(&query) {
-            score += 20;
-        }
+        relevance_score(
+            &self.title,
+            &self.url,
+            self.visit_count,
+            self.typed_count,
+            self.last_visit,
+            query,
+        )
+    }
+}
 
-        // URL match
-        if self.url.to_lowercase().contains(&query) {
-            score += 15;
-        }
+/// Relevance score of an entry's fields for `query`
+///
+/// Shared with the SQLite backend, which ranks search results in SQL.
+pub(crate) fn relevance_score(
+    title: &str,
+    url: &str,
+    visit_count: u32,
+    typed_count: u32,
+    last_visit: DateTime<Utc>,
+    query: &str,
+) -> u32 {
+    let query = query.to_lowercase();
+    let title = title.to_lowercase();
+    let mut score = 0u32;
+
+    // Title exact match
+    if title == query {
+        score += 100;
+    } else if title.starts_with(&query) {
+        score += 50;
+    } else if title.contains(&query) {
+        score += 20;
+    }
 
-        // Boost by visit count (log scale)
-        score += (self.visit_count as f64).log2() as u32 * 5;
+    // URL match
+    if url.to_lowercase().contains(&query) {
+        score += 15;
+    }
 
-        // Boost by typed count
-        score += self.typed_count * 10;
+    // Boost by visit count (log scale)
+    score += (visit_count as f64).log2() as u32 * 5;
 
-        // Boost recent visits
-        let days_ago = (Utc::now() - self.last_visit).num_days();
-        if days_ago < 1 {
-            score += 20;
-        } else if days_ago < 7 {
-            score += 10;
-        } else if days_ago < 30 {
-            score += 5;
-        }
+    // Boost by typed count
+    score += typed_count * 10;
 
-        score
+    // Boost recent visits
+    let days_ago = (Utc::now() - last_visit).num_days();
+    if days_ago < 1 {
+        score += 20;
+    } else if days_ago < 7 {
+        score += 10;
+    } else if days_ago < 30 {
+        score += 5;
     }
+
+    score
 }
 
 #[cfg(test)]
@@ -6,7 +6,7 @@
 //! # Features
 //!
 //! - History entry storage
-//! - Persistent storage (JSON file)
+//! - Pluggable persistence: JSON file, or SQLite with FTS5 (`sqlite` feature)
 //! - Full-text search
 //! - Visit count tracking
 //! - Date-based filtering
@@ -26,12 +26,17 @@
 //!
 //! // Get recent history
 //! let recent = manager.recent(10);
+//!
+//! // Large histories: SQLite storage, migrating an existing history.json
+//! let manager = HistoryManager::sqlite(&data_dir)?.with_max_entries(500_000);
 //! ```
 
 mod entry;
 mod error;
 mod manager;
 mod search;
+/// Storage backends for history entries.
+pub mod storage;
 
 /// Single history entry with URL, title, and visit metadata.
 pub use entry::HistoryEntry;
@@ -41,6 +46,11 @@ pub use error::{HistoryError, Result};
 pub use manager::HistoryManager;
 /// Search configuration and result types for history queries.
 pub use search::{SearchOptions, SearchResult};
+/// SQLite storage backend (when the `sqlite` feature is enabled).
+#[cfg(feature = "sqlite")]
+pub use storage::SqliteStorage;
+/// Storage trait and the built-in JSON backend.
+pub use storage::{HistoryStorage, JsonStorage};
 
 /// Unique identifier for history entries
 pub type HistoryId = String;
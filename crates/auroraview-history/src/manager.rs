@@ -1,53 +1,59 @@
 //! History manager implementation
 
 use std::collections::HashMap;
-use std::path::{Path, PathBuf};
+use std::path::Path;
 use std::sync::Arc;
 
 use chrono::{DateTime, Duration, Utc};
-use parking_lot::RwLock;
 
+use crate::storage::{HistoryStorage, JsonStorage, JSON_FILE};
 use crate::{HistoryEntry, HistoryId, Result, SearchOptions, SearchResult};
 
 /// History manager
 ///
-/// Manages browsing history with optional persistence.
+/// Manages browsing history on top of a [`HistoryStorage`] backend.
+/// Storage errors are swallowed by the query methods (which then return
+/// empty results), matching the behavior of the original JSON store.
 #[derive(Debug)]
 pub struct HistoryManager {
-    inner: Arc<RwLock<HistoryStore>>,
-    storage_path: Option<PathBuf>,
+    storage: Arc<dyn HistoryStorage>,
     max_entries: usize,
 }
 
-#[derive(Debug, Default)]
-struct HistoryStore {
-    entries: HashMap<HistoryId, HistoryEntry>,
-}
-
 impl HistoryManager {
     /// Default maximum entries
     const DEFAULT_MAX_ENTRIES: usize = 10000;
 
     /// Create a new history manager
     ///
-    /// If `data_dir` is provided, history will be persisted to disk.
+    /// If `data_dir` is provided, history will be persisted to a JSON file.
     pub fn new(data_dir: Option<&Path>) -> Self {
-        let storage_path = data_dir.map(|p| p.join("history.json"));
-
-        let mut manager = Self {
-            inner: Arc::new(RwLock::new(HistoryStore::default())),
-            storage_path,
-            max_entries: Self::DEFAULT_MAX_ENTRIES,
+        let storage = match data_dir {
+            Some(dir) => JsonStorage::open(dir.join(JSON_FILE)),
+            None => JsonStorage::in_memory(),
         };
+        Self::with_storage(Arc::new(storage))
+    }
 
-        // Try to load existing history
-        if let Some(ref path) = manager.storage_path {
-            if path.exists() {
-                let _ = manager.load();
-            }
+    /// Create a history manager on top of a storage backend
+    pub fn with_storage(storage: Arc<dyn HistoryStorage>) -> Self {
+        Self {
+            storage,
+            max_entries: Self::DEFAULT_MAX_ENTRIES,
         }
+    }
+
+    /// Create a history manager backed by SQLite in `data_dir`
+    ///
+    /// An existing `history.json` in the same directory is imported on
+    /// first use and renamed to `history.json.migrated`.
+    #[cfg(feature = "sqlite")]
+    pub fn sqlite(data_dir: &Path) -> Result<Self> {
+        use crate::storage::{migrate_json, SqliteStorage, SQLITE_FILE};
 
-        manager
+        let storage = SqliteStorage::open(data_dir.join(SQLITE_FILE))?;
+        migrate_json(data_dir.join(JSON_FILE), &storage)?;
+        Ok(Self::with_storage(Arc::new(storage)))
     }
 
     /// Set maximum entries
@@ -56,143 +62,62 @@ impl HistoryManager {
         self
     }
 
+    /// Storage backend
+    pub fn storage(&self) -> &Arc<dyn HistoryStorage> {
+        &self.storage
+    }
+
     /// Record a visit
     pub fn visit(&self, url: impl Into<String>, title: impl Into<String>) -> HistoryId {
-        let url = url.into();
-        let title = title.into();
-
-        let mut store = self.inner.write();
-
-        // Check if entry exists
-        if let Some(entry) = store.entries.values_mut().find(|e| e.url == url) {
-            entry.record_visit();
-            if !title.is_empty() {
-                entry.set_title(&title);
-            }
-            let id = entry.id.clone();
-            drop(store);
-            let _ = self.save();
-            return id;
-        }
-
-        // Create new entry
-        let entry = HistoryEntry::new(&url, title);
-        let id = entry.id.clone();
-        store.entries.insert(id.clone(), entry);
-
-        // Enforce max entries
-        self.enforce_max_entries(&mut store);
-
-        drop(store);
-        let _ = self.save();
-        id
+        self.record(url.into(), title.into(), false)
     }
 
     /// Record a typed visit (user typed URL directly)
     pub fn typed_visit(&self, url: impl Into<String>, title: impl Into<String>) -> HistoryId {
-        let url = url.into();
-        let title = title.into();
-
-        let mut store = self.inner.write();
-
-        // Check if entry exists
-        if let Some(entry) = store.entries.values_mut().find(|e| e.url == url) {
-            entry.record_typed_visit();
-            if !title.is_empty() {
-                entry.set_title(&title);
-            }
-            let id = entry.id.clone();
-            drop(store);
-            let _ = self.save();
-            return id;
-        }
-
-        // Create new entry with typed visit
-        let mut entry = HistoryEntry::new(&url, title);
-        entry.typed_count = 1;
-        let id = entry.id.clone();
-        store.entries.insert(id.clone(), entry);
-
-        self.enforce_max_entries(&mut store);
-
-        drop(store);
-        let _ = self.save();
-        id
+        self.record(url.into(), title.into(), true)
     }
 
-    /// Enforce maximum entries limit
-    fn enforce_max_entries(&self, store: &mut HistoryStore) {
-        if store.entries.len() <= self.max_entries {
-            return;
-        }
-
-        // Remove oldest entries
-        let mut entries: Vec<_> = store.entries.values().collect();
-        entries.sort_by_key(|e| e.last_visit);
-
-        let to_remove = store.entries.len() - self.max_entries;
-        let ids_to_remove: Vec<_> = entries
-            .iter()
-            .take(to_remove)
-            .map(|e| e.id.clone())
-            .collect();
-
-        for id in ids_to_remove {
-            store.entries.remove(&id);
+    fn record(&self, url: String, title: String, typed: bool) -> HistoryId {
+        match self.storage.record_visit(&url, &title, typed) {
+            Ok(entry) => {
+                // Only a new entry can push the history over its limit
+                if entry.visit_count == 1 {
+                    let _ = self.storage.evict_oldest(self.max_entries);
+                }
+                entry.id
+            }
+            Err(_) => HistoryEntry::new(url, title).id,
         }
     }
 
     /// Get a history entry by ID
     pub fn get(&self, id: &str) -> Option<HistoryEntry> {
-        let store = self.inner.read();
-        store.entries.get(id).cloned()
+        self.storage.get(id).ok().flatten()
     }
 
     /// Get entry by URL
     pub fn get_by_url(&self, url: &str) -> Option<HistoryEntry> {
-        let store = self.inner.read();
-        store.entries.values().find(|e| e.url == url).cloned()
+        self.storage.get_by_url(url).ok().flatten()
     }
 
     /// Delete a history entry
     pub fn delete(&self, id: &str) -> bool {
-        let mut store = self.inner.write();
-        let removed = store.entries.remove(id).is_some();
-        drop(store);
-
-        if removed {
-            let _ = self.save();
-        }
-        removed
+        self.storage.delete(id).unwrap_or(false)
     }
 
     /// Delete entries by URL
     pub fn delete_url(&self, url: &str) -> bool {
-        let mut store = self.inner.write();
-        let initial_len = store.entries.len();
-        store.entries.retain(|_, e| e.url != url);
-        let removed = store.entries.len() < initial_len;
-        drop(store);
-
-        if removed {
-            let _ = self.save();
-        }
-        removed
+        self.storage.delete_url(url).unwrap_or(false)
     }
 
     /// Get recent history entries
     pub fn recent(&self, limit: usize) -> Vec<HistoryEntry> {
-        let store = self.inner.read();
-        let mut entries: Vec<_> = store.entries.values().cloned().collect();
-        entries.sort_by_key(|e| std::cmp::Reverse(e.last_visit));
-        entries.truncate(limit);
-        entries
+        self.storage.recent(limit).unwrap_or_default()
     }
 
     /// Get all history entries
     pub fn all(&self) -> Vec<HistoryEntry> {
-        let store = self.inner.read();
-        store.entries.values().cloned().collect()
+        self.storage.all().unwrap_or_default()
     }
 
     /// Search history
@@ -202,55 +127,22 @@ impl HistoryManager {
 
     /// Search history with options
     pub fn search_with_options(&self, query: &str, options: SearchOptions) -> Vec<SearchResult> {
-        let store = self.inner.read();
-
-        let mut results: Vec<_> = store
-            .entries
-            .values()
-            .filter(|e| e.matches(query) && options.matches(e))
-            .map(|e| SearchResult::new(e.clone(), query))
-            .collect();
-
-        // Sort by relevance
-        results.sort_by_key(|r| std::cmp::Reverse(r.score));
-
-        // Apply limit
-        if let Some(limit) = options.limit {
-            results.truncate(limit);
-        }
-
-        results
+        self.storage.search(query, &options).unwrap_or_default()
     }
 
     /// Get frequently visited sites
     pub fn frequent(&self, limit: usize) -> Vec<HistoryEntry> {
-        let store = self.inner.read();
-        let mut entries: Vec<_> = store.entries.values().cloned().collect();
-        entries.sort_by_key(|e| std::cmp::Reverse(e.visit_count));
-        entries.truncate(limit);
-        entries
+        self.storage.frequent(limit).unwrap_or_default()
     }
 
     /// Get entries by domain
     pub fn by_domain(&self, domain: &str) -> Vec<HistoryEntry> {
-        let store = self.inner.read();
-        store
-            .entries
-            .values()
-            .filter(|e| e.domain() == Some(domain))
-            .cloned()
-            .collect()
+        self.storage.by_domain(domain).unwrap_or_default()
     }
 
     /// Get entries in date range
     pub fn in_range(&self, start: DateTime<Utc>, end: DateTime<Utc>) -> Vec<HistoryEntry> {
-        let store = self.inner.read();
-        store
-            .entries
-            .values()
-            .filter(|e| e.last_visit >= start && e.last_visit <= end)
-            .cloned()
-            .collect()
+        self.storage.in_range(start, end).unwrap_or_default()
     }
 
     /// Get entries from today
@@ -277,111 +169,59 @@ impl HistoryManager {
     /// Delete entries older than specified days
     pub fn delete_older_than(&self, days: i64) -> usize {
         let cutoff = Utc::now() - Duration::days(days);
-
-        let mut store = self.inner.write();
-        let initial_len = store.entries.len();
-        store.entries.retain(|_, e| e.last_visit >= cutoff);
-        let removed = initial_len - store.entries.len();
-        drop(store);
-
-        if removed > 0 {
-            let _ = self.save();
-        }
-        removed
+        self.storage.delete_before(cutoff).unwrap_or(0)
     }
 
     /// Delete all history for a domain
     pub fn delete_domain(&self, domain: &str) -> usize {
-        let mut store = self.inner.write();
-        let initial_len = store.entries.len();
-        store.entries.retain(|_, e| e.domain() != Some(domain));
-        let removed = initial_len - store.entries.len();
-        drop(store);
-
-        if removed > 0 {
-            let _ = self.save();
-        }
-        removed
+        self.storage.delete_domain(domain).unwrap_or(0)
     }
 
     /// Clear all history
     pub fn clear(&self) {
-        let mut store = self.inner.write();
-        store.entries.clear();
-        drop(store);
-        let _ = self.save();
+        let _ = self.storage.clear();
     }
 
     /// Get entry count
     pub fn count(&self) -> usize {
-        let store = self.inner.read();
-        store.entries.len()
+        self.storage.count().unwrap_or(0)
     }
 
     // ========== Persistence ==========
 
     /// Save history to disk
     pub fn save(&self) -> Result<()> {
-        let Some(ref path) = self.storage_path else {
-            return Ok(());
-        };
-
-        let store = self.inner.read();
-
-        // Ensure parent directory exists
-        if let Some(parent) = path.parent() {
-            std::fs::create_dir_all(parent)?;
-        }
-
-        let json = serde_json::to_string(&store.entries)?;
-        std::fs::write(path, json)?;
-
-        Ok(())
+        self.storage.flush()
     }
 
     /// Load history from disk
     pub fn load(&mut self) -> Result<()> {
-        let Some(ref path) = self.storage_path else {
-            return Ok(());
-        };
-
-        if !path.exists() {
-            return Ok(());
-        }
-
-        let json = std::fs::read_to_string(path)?;
-        let entries: HashMap<HistoryId, HistoryEntry> = serde_json::from_str(&json)?;
-
-        let mut store = self.inner.write();
-        store.entries = entries;
-
-        Ok(())
+        self.storage.reload()
     }
 
     /// Export history to JSON string
     pub fn export(&self) -> Result<String> {
-        let store = self.inner.read();
-        Ok(serde_json::to_string_pretty(&store.entries)?)
+        let entries: HashMap<HistoryId, HistoryEntry> = self
+            .storage
+            .all()?
+            .into_iter()
+            .map(|e| (e.id.clone(), e))
+            .collect();
+        Ok(serde_json::to_string_pretty(&entries)?)
     }
 
     /// Import history from JSON string
     pub fn import(&self, json: &str) -> Result<()> {
         let entries: HashMap<HistoryId, HistoryEntry> = serde_json::from_str(json)?;
-
-        let mut store = self.inner.write();
-        store.entries.extend(entries);
-        drop(store);
-
-        let _ = self.save();
-        Ok(())
+        let entries: Vec<HistoryEntry> = entries.into_values().collect();
+        self.storage.put_many(&entries)
     }
 }
 
 impl Clone for HistoryManager {
     fn clone(&self) -> Self {
         Self {
-            inner: Arc::clone(&self.inner),
-            storage_path: self.storage_path.clone(),
+            storage: Arc::clone(&self.storage),
             max_entries: self.max_entries,
         }
     }
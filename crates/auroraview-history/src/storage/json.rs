@@ -0,0 +1,240 @@
+//! In-memory storage with optional JSON file persistence
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, Utc};
+use parking_lot::RwLock;
+
+use super::{rank, HistoryStorage};
+use crate::{HistoryEntry, HistoryId, Result, SearchOptions, SearchResult};
+
+/// JSON file storage
+///
+/// Keeps every entry in a `HashMap` and rewrites the whole file after each
+/// change. Without a path it is a purely in-memory store.
+#[derive(Debug, Default)]
+pub struct JsonStorage {
+    entries: RwLock<HashMap<HistoryId, HistoryEntry>>,
+    path: Option<PathBuf>,
+}
+
+impl JsonStorage {
+    /// Create an in-memory store
+    pub fn in_memory() -> Self {
+        Self::default()
+    }
+
+    /// Open a store backed by `path`, loading it if it exists
+    ///
+    /// A missing or unreadable file yields an empty store; the file is
+    /// (re)written on the next change.
+    pub fn open(path: impl Into<PathBuf>) -> Self {
+        let storage = Self {
+            entries: RwLock::new(HashMap::new()),
+            path: Some(path.into()),
+        };
+        let _ = storage.reload();
+        storage
+    }
+
+    /// Backing file, if any
+    pub fn path(&self) -> Option<&Path> {
+        self.path.as_deref()
+    }
+
+    fn save(&self, entries: &HashMap<HistoryId, HistoryEntry>) -> Result<()> {
+        let Some(ref path) = self.path else {
+            return Ok(());
+        };
+
+        // Ensure parent directory exists
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+
+        let json = serde_json::to_string(entries)?;
+        std::fs::write(path, json)?;
+        Ok(())
+    }
+
+    /// Remove entries matching `predicate` and save if anything changed
+    fn remove_where(&self, predicate: impl Fn(&HistoryEntry) -> bool) -> Result<usize> {
+        let mut entries = self.entries.write();
+        let initial_len = entries.len();
+        entries.retain(|_, e| !predicate(e));
+        let removed = initial_len - entries.len();
+        if removed > 0 {
+            self.save(&entries)?;
+        }
+        Ok(removed)
+    }
+
+    fn sorted_by_key<K: Ord>(
+        &self,
+        limit: usize,
+        key: impl Fn(&HistoryEntry) -> K,
+    ) -> Vec<HistoryEntry> {
+        let mut entries: Vec<_> = self.entries.read().values().cloned().collect();
+        entries.sort_by_key(|e| std::cmp::Reverse(key(e)));
+        entries.truncate(limit);
+        entries
+    }
+}
+
+impl HistoryStorage for JsonStorage {
+    fn record_visit(&self, url: &str, title: &str, typed: bool) -> Result<HistoryEntry> {
+        let mut entries = self.entries.write();
+
+        let entry = match entries.values_mut().find(|e| e.url == url) {
+            Some(entry) => {
+                if typed {
+                    entry.record_typed_visit();
+                } else {
+                    entry.record_visit();
+                }
+                if !title.is_empty() {
+                    entry.set_title(title);
+                }
+                entry.clone()
+            }
+            None => {
+                let mut entry = HistoryEntry::new(url, title);
+                if typed {
+                    entry.typed_count = 1;
+                }
+                entries.insert(entry.id.clone(), entry.clone());
+                entry
+            }
+        };
+
+        self.save(&entries)?;
+        Ok(entry)
+    }
+
+    fn put_many(&self, new_entries: &[HistoryEntry]) -> Result<()> {
+        let mut entries = self.entries.write();
+        entries.extend(new_entries.iter().map(|e| (e.id.clone(), e.clone())));
+        self.save(&entries)
+    }
+
+    fn get(&self, id: &str) -> Result<Option<HistoryEntry>> {
+        Ok(self.entries.read().get(id).cloned())
+    }
+
+    fn get_by_url(&self, url: &str) -> Result<Option<HistoryEntry>> {
+        Ok(self.entries.read().values().find(|e| e.url == url).cloned())
+    }
+
+    fn delete(&self, id: &str) -> Result<bool> {
+        let mut entries = self.entries.write();
+        let removed = entries.remove(id).is_some();
+        if removed {
+            self.save(&entries)?;
+        }
+        Ok(removed)
+    }
+
+    fn delete_url(&self, url: &str) -> Result<bool> {
+        Ok(self.remove_where(|e| e.url == url)? > 0)
+    }
+
+    fn delete_before(&self, cutoff: DateTime<Utc>) -> Result<usize> {
+        self.remove_where(|e| e.last_visit < cutoff)
+    }
+
+    fn delete_domain(&self, domain: &str) -> Result<usize> {
+        self.remove_where(|e| e.domain() == Some(domain))
+    }
+
+    fn evict_oldest(&self, max_entries: usize) -> Result<usize> {
+        let mut entries = self.entries.write();
+        if entries.len() <= max_entries {
+            return Ok(0);
+        }
+
+        let mut by_age: Vec<_> = entries
+            .values()
+            .map(|e| (e.last_visit, e.id.clone()))
+            .collect();
+        by_age.sort();
+        let to_remove = entries.len() - max_entries;
+        for (_, id) in by_age.into_iter().take(to_remove) {
+            entries.remove(&id);
+        }
+
+        self.save(&entries)?;
+        Ok(to_remove)
+    }
+
+    fn clear(&self) -> Result<()> {
+        let mut entries = self.entries.write();
+        entries.clear();
+        self.save(&entries)
+    }
+
+    fn count(&self) -> Result<usize> {
+        Ok(self.entries.read().len())
+    }
+
+    fn all(&self) -> Result<Vec<HistoryEntry>> {
+        Ok(self.entries.read().values().cloned().collect())
+    }
+
+    fn recent(&self, limit: usize) -> Result<Vec<HistoryEntry>> {
+        Ok(self.sorted_by_key(limit, |e| e.last_visit))
+    }
+
+    fn frequent(&self, limit: usize) -> Result<Vec<HistoryEntry>> {
+        Ok(self.sorted_by_key(limit, |e| e.visit_count))
+    }
+
+    fn by_domain(&self, domain: &str) -> Result<Vec<HistoryEntry>> {
+        Ok(self
+            .entries
+            .read()
+            .values()
+            .filter(|e| e.domain() == Some(domain))
+            .cloned()
+            .collect())
+    }
+
+    fn in_range(&self, start: DateTime<Utc>, end: DateTime<Utc>) -> Result<Vec<HistoryEntry>> {
+        Ok(self
+            .entries
+            .read()
+            .values()
+            .filter(|e| e.last_visit >= start && e.last_visit <= end)
+            .cloned()
+            .collect())
+    }
+
+    fn search(&self, query: &str, options: &SearchOptions) -> Result<Vec<SearchResult>> {
+        let results = self
+            .entries
+            .read()
+            .values()
+            .filter(|e| e.matches(query) && options.matches(e))
+            .map(|e| SearchResult::new(e.clone(), query))
+            .collect();
+        Ok(rank(results, options))
+    }
+
+    fn flush(&self) -> Result<()> {
+        self.save(&self.entries.read())
+    }
+
+    fn reload(&self) -> Result<()> {
+        let Some(ref path) = self.path else {
+            return Ok(());
+        };
+        if !path.exists() {
+            return Ok(());
+        }
+
+        let json = std::fs::read_to_string(path)?;
+        let loaded: HashMap<HistoryId, HistoryEntry> = serde_json::from_str(&json)?;
+        *self.entries.write() = loaded;
+        Ok(())
+    }
+}
@@ -0,0 +1,107 @@
+//! Pluggable history storage
+//!
+//! [`HistoryManager`](crate::HistoryManager) delegates all reads and writes
+//! to a [`HistoryStorage`] implementation:
+//!
+//! - [`JsonStorage`]: entries in memory, optionally mirrored to a JSON file
+//!   that is rewritten on every change. Fine for small histories.
+//! - `SqliteStorage` (feature `sqlite`): a SQLite database with indexed
+//!   domain/date queries, incremental writes and FTS5 full-text search.
+//!   Existing `history.json` files are migrated on first open.
+
+mod json;
+#[cfg(feature = "sqlite")]
+mod sqlite;
+
+pub use json::JsonStorage;
+#[cfg(feature = "sqlite")]
+pub use sqlite::{migrate_json, SqliteStorage};
+
+use chrono::{DateTime, Utc};
+
+use crate::{HistoryEntry, Result, SearchOptions, SearchResult};
+
+/// File name of the JSON history store inside a data directory
+pub const JSON_FILE: &str = "history.json";
+
+/// File name of the SQLite history database inside a data directory
+pub const SQLITE_FILE: &str = "history.db";
+
+/// Storage backend for history entries
+///
+/// Implementations must be safe to share between threads; every method
+/// takes `&self`.
+pub trait HistoryStorage: Send + Sync + std::fmt::Debug {
+    /// Record a visit to `url`, creating the entry if needed
+    ///
+    /// A non-empty `title` replaces the stored one. Returns the updated
+    /// entry; a `visit_count` of 1 means the entry was just created.
+    fn record_visit(&self, url: &str, title: &str, typed: bool) -> Result<HistoryEntry>;
+
+    /// Insert or replace entries (used by import and migration)
+    fn put_many(&self, entries: &[HistoryEntry]) -> Result<()>;
+
+    /// Get an entry by ID
+    fn get(&self, id: &str) -> Result<Option<HistoryEntry>>;
+
+    /// Get an entry by URL
+    fn get_by_url(&self, url: &str) -> Result<Option<HistoryEntry>>;
+
+    /// Delete an entry by ID
+    fn delete(&self, id: &str) -> Result<bool>;
+
+    /// Delete the entries for a URL
+    fn delete_url(&self, url: &str) -> Result<bool>;
+
+    /// Delete entries last visited before `cutoff`
+    fn delete_before(&self, cutoff: DateTime<Utc>) -> Result<usize>;
+
+    /// Delete all entries of a domain
+    fn delete_domain(&self, domain: &str) -> Result<usize>;
+
+    /// Delete the least recently visited entries beyond `max_entries`
+    fn evict_oldest(&self, max_entries: usize) -> Result<usize>;
+
+    /// Delete everything
+    fn clear(&self) -> Result<()>;
+
+    /// Number of entries
+    fn count(&self) -> Result<usize>;
+
+    /// All entries (unordered)
+    fn all(&self) -> Result<Vec<HistoryEntry>>;
+
+    /// Most recently visited entries first
+    fn recent(&self, limit: usize) -> Result<Vec<HistoryEntry>>;
+
+    /// Most visited entries first
+    fn frequent(&self, limit: usize) -> Result<Vec<HistoryEntry>>;
+
+    /// Entries of a domain
+    fn by_domain(&self, domain: &str) -> Result<Vec<HistoryEntry>>;
+
+    /// Entries last visited within `[start, end]`
+    fn in_range(&self, start: DateTime<Utc>, end: DateTime<Utc>) -> Result<Vec<HistoryEntry>>;
+
+    /// Entries matching `query` in title or URL, most relevant first
+    fn search(&self, query: &str, options: &SearchOptions) -> Result<Vec<SearchResult>>;
+
+    /// Write pending changes to disk
+    fn flush(&self) -> Result<()> {
+        Ok(())
+    }
+
+    /// Discard in-memory state and re-read it from disk
+    fn reload(&self) -> Result<()> {
+        Ok(())
+    }
+}
+
+/// Sort search results by relevance and apply the result limit
+pub(crate) fn rank(mut results: Vec<SearchResult>, options: &SearchOptions) -> Vec<SearchResult> {
+    results.sort_by_key(|r| std::cmp::Reverse(r.score));
+    if let Some(limit) = options.limit {
+        results.truncate(limit);
+    }
+    results
+}
@@ -0,0 +1,436 @@
+//! SQLite storage with FTS5 full-text search
+
+use std::path::Path;
+
+use chrono::{DateTime, Utc};
+use parking_lot::Mutex;
+use rusqlite::functions::FunctionFlags;
+use rusqlite::types::Value;
+use rusqlite::{params, params_from_iter, Connection, OptionalExtension, Row};
+
+use super::HistoryStorage;
+use crate::entry::relevance_score;
+use crate::{HistoryEntry, HistoryError, HistoryId, Result, SearchOptions, SearchResult};
+
+/// Current schema version (stored in `PRAGMA user_version`)
+const SCHEMA_VERSION: i64 = 1;
+
+/// Entry columns, in the order `entry_from_row` reads them
+const COLUMNS: &str =
+    "h.id, h.url, h.title, h.favicon, h.visit_count, h.typed_count, h.first_visit, h.last_visit";
+
+/// Trigram FTS needs at least three characters; shorter queries use LIKE
+const MIN_FTS_QUERY_CHARS: usize = 3;
+
+const SCHEMA: &str = "
+CREATE TABLE IF NOT EXISTS history (
+    id          TEXT PRIMARY KEY,
+    url         TEXT NOT NULL,
+    title       TEXT NOT NULL DEFAULT '',
+    favicon     TEXT,
+    domain      TEXT,
+    visit_count INTEGER NOT NULL DEFAULT 1,
+    typed_count INTEGER NOT NULL DEFAULT 0,
+    first_visit INTEGER NOT NULL,
+    last_visit  INTEGER NOT NULL
+);
+CREATE INDEX IF NOT EXISTS idx_history_url ON history(url);
+CREATE INDEX IF NOT EXISTS idx_history_domain ON history(domain);
+CREATE INDEX IF NOT EXISTS idx_history_last_visit ON history(last_visit);
+CREATE INDEX IF NOT EXISTS idx_history_visit_count ON history(visit_count);
+
+CREATE VIRTUAL TABLE IF NOT EXISTS history_fts USING fts5(
+    title, url, content='history', content_rowid='rowid', tokenize='trigram'
+);
+CREATE TRIGGER IF NOT EXISTS history_ai AFTER INSERT ON history BEGIN
+    INSERT INTO history_fts(rowid, title, url) VALUES (new.rowid, new.title, new.url);
+END;
+CREATE TRIGGER IF NOT EXISTS history_ad AFTER DELETE ON history BEGIN
+    INSERT INTO history_fts(history_fts, rowid, title, url)
+        VALUES ('delete', old.rowid, old.title, old.url);
+END;
+CREATE TRIGGER IF NOT EXISTS history_au AFTER UPDATE OF title, url ON history BEGIN
+    INSERT INTO history_fts(history_fts, rowid, title, url)
+        VALUES ('delete', old.rowid, old.title, old.url);
+    INSERT INTO history_fts(rowid, title, url) VALUES (new.rowid, new.title, new.url);
+END;
+";
+
+impl From<rusqlite::Error> for HistoryError {
+    fn from(e: rusqlite::Error) -> Self {
+        HistoryError::Storage(e.to_string())
+    }
+}
+
+/// SQLite history storage
+///
+/// Every change is a single indexed write instead of a file rewrite.
+/// `by_domain`, `in_range`, `recent` and `frequent` use indexes, and
+/// `search` uses an FTS5 trigram index over title and URL, so substring
+/// queries behave like [`HistoryEntry::matches`].
+#[derive(Debug)]
+pub struct SqliteStorage {
+    conn: Mutex<Connection>,
+}
+
+impl SqliteStorage {
+    /// Open (or create) a database file
+    pub fn open(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let conn = Connection::open(path)?;
+        conn.pragma_update(None, "journal_mode", "WAL")?;
+        conn.pragma_update(None, "synchronous", "NORMAL")?;
+        Self::init(conn)
+    }
+
+    /// Create a database that lives in memory only
+    pub fn in_memory() -> Result<Self> {
+        Self::init(Connection::open_in_memory()?)
+    }
+
+    fn init(conn: Connection) -> Result<Self> {
+        let version: i64 = conn.pragma_query_value(None, "user_version", |row| row.get(0))?;
+        if version > SCHEMA_VERSION {
+            return Err(HistoryError::Storage(format!(
+                "History database schema {version} is newer than supported ({SCHEMA_VERSION})"
+            )));
+        }
+        conn.execute_batch(SCHEMA)?;
+        conn.pragma_update(None, "user_version", SCHEMA_VERSION)?;
+        // `relevance(query, title, url, visit_count, typed_count, last_visit)`
+        // is `HistoryEntry::relevance_score`, so search ranks and limits in SQL
+        conn.create_scalar_function("relevance", 6, FunctionFlags::SQLITE_UTF8, |ctx| {
+            Ok(relevance_score(
+                &ctx.get::<String>(1)?,
+                &ctx.get::<String>(2)?,
+                ctx.get(3)?,
+                ctx.get(4)?,
+                DateTime::from_timestamp_nanos(ctx.get(5)?),
+                &ctx.get::<String>(0)?,
+            ))
+        })?;
+        Ok(Self {
+            conn: Mutex::new(conn),
+        })
+    }
+
+    fn query(&self, sql: &str, params: Vec<Value>) -> Result<Vec<HistoryEntry>> {
+        let conn = self.conn.lock();
+        let mut stmt = conn.prepare_cached(sql)?;
+        let entries = stmt
+            .query_map(params_from_iter(params), entry_from_row)?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+        Ok(entries)
+    }
+
+    fn query_one(&self, sql: &str, param: &str) -> Result<Option<HistoryEntry>> {
+        let conn = self.conn.lock();
+        let entry = conn
+            .prepare_cached(sql)?
+            .query_row([param], entry_from_row)
+            .optional()?;
+        Ok(entry)
+    }
+
+    fn execute(&self, sql: &str, params: Vec<Value>) -> Result<usize> {
+        let conn = self.conn.lock();
+        let changed = conn
+            .prepare_cached(sql)?
+            .execute(params_from_iter(params))?;
+        Ok(changed)
+    }
+}
+
+impl HistoryStorage for SqliteStorage {
+    fn record_visit(&self, url: &str, title: &str, typed: bool) -> Result<HistoryEntry> {
+        let mut conn = self.conn.lock();
+        let tx = conn.transaction()?;
+
+        let existing = tx
+            .prepare_cached(&format!(
+                "SELECT {COLUMNS} FROM history h WHERE h.url = ?1 LIMIT 1"
+            ))?
+            .query_row([url], entry_from_row)
+            .optional()?;
+
+        let entry = match existing {
+            Some(mut entry) => {
+                if typed {
+                    entry.record_typed_visit();
+                } else {
+                    entry.record_visit();
+                }
+                if !title.is_empty() {
+                    entry.set_title(title);
+                }
+                entry
+            }
+            None => {
+                let mut entry = HistoryEntry::new(url, title);
+                if typed {
+                    entry.typed_count = 1;
+                }
+                entry
+            }
+        };
+
+        upsert(&tx, &entry)?;
+        tx.commit()?;
+        Ok(entry)
+    }
+
+    fn put_many(&self, entries: &[HistoryEntry]) -> Result<()> {
+        let mut conn = self.conn.lock();
+        let tx = conn.transaction()?;
+        for entry in entries {
+            upsert(&tx, entry)?;
+        }
+        tx.commit()?;
+        Ok(())
+    }
+
+    fn get(&self, id: &str) -> Result<Option<HistoryEntry>> {
+        self.query_one(
+            &format!("SELECT {COLUMNS} FROM history h WHERE h.id = ?1"),
+            id,
+        )
+    }
+
+    fn get_by_url(&self, url: &str) -> Result<Option<HistoryEntry>> {
+        self.query_one(
+            &format!("SELECT {COLUMNS} FROM history h WHERE h.url = ?1 LIMIT 1"),
+            url,
+        )
+    }
+
+    fn delete(&self, id: &str) -> Result<bool> {
+        Ok(self.execute(
+            "DELETE FROM history WHERE id = ?1",
+            vec![id.to_owned().into()],
+        )? > 0)
+    }
+
+    fn delete_url(&self, url: &str) -> Result<bool> {
+        Ok(self.execute(
+            "DELETE FROM history WHERE url = ?1",
+            vec![url.to_owned().into()],
+        )? > 0)
+    }
+
+    fn delete_before(&self, cutoff: DateTime<Utc>) -> Result<usize> {
+        self.execute(
+            "DELETE FROM history WHERE last_visit < ?1",
+            vec![to_nanos(cutoff).into()],
+        )
+    }
+
+    fn delete_domain(&self, domain: &str) -> Result<usize> {
+        self.execute(
+            "DELETE FROM history WHERE domain = ?1",
+            vec![domain.to_owned().into()],
+        )
+    }
+
+    fn evict_oldest(&self, max_entries: usize) -> Result<usize> {
+        self.execute(
+            "DELETE FROM history WHERE rowid IN (
+                SELECT rowid FROM history ORDER BY last_visit ASC
+                LIMIT max(0, (SELECT COUNT(*) FROM history) - ?1)
+            )",
+            vec![(max_entries as i64).into()],
+        )
+    }
+
+    fn clear(&self) -> Result<()> {
+        // The delete trigger keeps the FTS index in sync
+        self.conn.lock().execute("DELETE FROM history", [])?;
+        Ok(())
+    }
+
+    fn count(&self) -> Result<usize> {
+        let count: i64 = self
+            .conn
+            .lock()
+            .query_row("SELECT COUNT(*) FROM history", [], |row| row.get(0))?;
+        Ok(count as usize)
+    }
+
+    fn all(&self) -> Result<Vec<HistoryEntry>> {
+        self.query(&format!("SELECT {COLUMNS} FROM history h"), vec![])
+    }
+
+    fn recent(&self, limit: usize) -> Result<Vec<HistoryEntry>> {
+        self.query(
+            &format!("SELECT {COLUMNS} FROM history h ORDER BY h.last_visit DESC LIMIT ?1"),
+            vec![(limit as i64).into()],
+        )
+    }
+
+    fn frequent(&self, limit: usize) -> Result<Vec<HistoryEntry>> {
+        self.query(
+            &format!("SELECT {COLUMNS} FROM history h ORDER BY h.visit_count DESC LIMIT ?1"),
+            vec![(limit as i64).into()],
+        )
+    }
+
+    fn by_domain(&self, domain: &str) -> Result<Vec<HistoryEntry>> {
+        self.query(
+            &format!("SELECT {COLUMNS} FROM history h WHERE h.domain = ?1"),
+            vec![domain.to_owned().into()],
+        )
+    }
+
+    fn in_range(&self, start: DateTime<Utc>, end: DateTime<Utc>) -> Result<Vec<HistoryEntry>> {
+        self.query(
+            &format!("SELECT {COLUMNS} FROM history h WHERE h.last_visit BETWEEN ?1 AND ?2"),
+            vec![to_nanos(start).into(), to_nanos(end).into()],
+        )
+    }
+
+    fn search(&self, query: &str, options: &SearchOptions) -> Result<Vec<SearchResult>> {
+        let mut conditions = Vec::new();
+        let mut params: Vec<Value> = vec![query.to_owned().into()];
+
+        let from = if query.is_empty() {
+            "history h"
+        } else if query.chars().count() >= MIN_FTS_QUERY_CHARS {
+            // Quote as a phrase: with the trigram tokenizer this is a
+            // case-insensitive substring match on title or URL
+            params.push(format!("\"{}\"", query.replace('"', "\"\"")).into());
+            conditions.push(format!("history_fts MATCH ?{}", params.len()));
+            "history_fts JOIN history h ON h.rowid = history_fts.rowid"
+        } else {
+            params.push(format!("%{}%", escape_like(query)).into());
+            let n = params.len();
+            conditions.push(format!(
+                "(h.title LIKE ?{n} ESCAPE '\\' OR h.url LIKE ?{n} ESCAPE '\\')"
+            ));
+            "history h"
+        };
+
+        if let Some(start) = options.start_date {
+            params.push(to_nanos(start).into());
+            conditions.push(format!("h.last_visit >= ?{}", params.len()));
+        }
+        if let Some(end) = options.end_date {
+            params.push(to_nanos(end).into());
+            conditions.push(format!("h.last_visit <= ?{}", params.len()));
+        }
+        if let Some(ref domain) = options.domain {
+            params.push(domain.clone().into());
+            conditions.push(format!("h.domain = ?{}", params.len()));
+        }
+        if let Some(min) = options.min_visits {
+            params.push(i64::from(min).into());
+            conditions.push(format!("h.visit_count >= ?{}", params.len()));
+        }
+
+        let mut sql = format!("SELECT {COLUMNS} FROM {from}");
+        if !conditions.is_empty() {
+            sql.push_str(" WHERE ");
+            sql.push_str(&conditions.join(" AND "));
+        }
+        // FTS or LIKE narrows the candidates; the shared relevance score
+        // orders them, most recent first on ties
+        sql.push_str(
+            " ORDER BY relevance(?1, h.title, h.url, h.visit_count, h.typed_count, h.last_visit) \
+             DESC, h.last_visit DESC",
+        );
+        if let Some(limit) = options.limit {
+            params.push((limit as i64).into());
+            sql.push_str(&format!(" LIMIT ?{}", params.len()));
+        }
+
+        Ok(self
+            .query(&sql, params)?
+            .into_iter()
+            .map(|e| SearchResult::new(e, query))
+            .collect())
+    }
+}
+
+/// Import a `history.json` file written by [`JsonStorage`](super::JsonStorage)
+///
+/// On success the file is renamed to `history.json.migrated` so it is not
+/// imported again. Returns the number of imported entries (0 if the file
+/// does not exist).
+pub fn migrate_json(json_path: impl AsRef<Path>, storage: &SqliteStorage) -> Result<usize> {
+    let json_path = json_path.as_ref();
+    if !json_path.exists() {
+        return Ok(0);
+    }
+
+    let json = std::fs::read_to_string(json_path)?;
+    let entries: std::collections::HashMap<HistoryId, HistoryEntry> = serde_json::from_str(&json)?;
+    let entries: Vec<HistoryEntry> = entries.into_values().collect();
+    storage.put_many(&entries)?;
+
+    let mut migrated = json_path.as_os_str().to_owned();
+    migrated.push(".migrated");
+    std::fs::rename(json_path, migrated)?;
+    Ok(entries.len())
+}
+
+fn upsert(conn: &Connection, entry: &HistoryEntry) -> Result<()> {
+    conn.prepare_cached(
+        "INSERT INTO history
+            (id, url, title, favicon, domain, visit_count, typed_count, first_visit, last_visit)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)
+         ON CONFLICT(id) DO UPDATE SET
+            url = excluded.url,
+            title = excluded.title,
+            favicon = excluded.favicon,
+            domain = excluded.domain,
+            visit_count = excluded.visit_count,
+            typed_count = excluded.typed_count,
+            first_visit = excluded.first_visit,
+            last_visit = excluded.last_visit",
+    )?
+    .execute(params![
+        entry.id,
+        entry.url,
+        entry.title,
+        entry.favicon,
+        entry.domain(),
+        entry.visit_count,
+        entry.typed_count,
+        to_nanos(entry.first_visit),
+        to_nanos(entry.last_visit),
+    ])?;
+    Ok(())
+}
+
+fn entry_from_row(row: &Row<'_>) -> rusqlite::Result<HistoryEntry> {
+    Ok(HistoryEntry {
+        id: row.get(0)?,
+        url: row.get(1)?,
+        title: row.get(2)?,
+        favicon: row.get(3)?,
+        visit_count: row.get(4)?,
+        typed_count: row.get(5)?,
+        first_visit: DateTime::from_timestamp_nanos(row.get(6)?),
+        last_visit: DateTime::from_timestamp_nanos(row.get(7)?),
+    })
+}
+
+/// Timestamps are stored as nanoseconds so entries round-trip exactly
+///
+/// Times outside the representable range (years 1677..2262) saturate.
+fn to_nanos(time: DateTime<Utc>) -> i64 {
+    time.timestamp_nanos_opt()
+        .unwrap_or(if time.timestamp() < 0 {
+            i64::MIN
+        } else {
+            i64::MAX
+        })
+}
+
+fn escape_like(query: &str) -> String {
+    query
+        .replace('\\', "\\\\")
+        .replace('%', "\\%")
+        .replace('_', "\\_")
+}
@@ -0,0 +1,277 @@
+//! Tests for the SQLite history storage backend
+
+use std::sync::Arc;
+
+use auroraview_history::storage::{migrate_json, JSON_FILE, SQLITE_FILE};
+use auroraview_history::{
+    HistoryEntry, HistoryManager, HistoryStorage, JsonStorage, SearchOptions, SqliteStorage,
+};
+use chrono::{Duration, Utc};
+use rstest::rstest;
+use tempfile::TempDir;
+
+fn sqlite_manager() -> HistoryManager {
+    HistoryManager::with_storage(Arc::new(SqliteStorage::in_memory().unwrap()))
+}
+
+fn json_manager() -> HistoryManager {
+    HistoryManager::with_storage(Arc::new(JsonStorage::in_memory()))
+}
+
+fn populate(manager: &HistoryManager) {
+    manager.visit("https://github.com/rust-lang/rust", "Rust Repository");
+    manager.visit("https://github.com", "GitHub");
+    manager.visit("https://github.com", "GitHub");
+    manager.typed_visit("https://gitlab.com", "GitLab");
+    manager.visit("https://docs.rs/serde", "serde - Rust docs");
+    manager.visit("http://example.com/100%_done", "Progress");
+}
+
+// ─── Parity with the JSON backend ────────────────────────────────────────────
+
+#[rstest]
+#[case::json(json_manager())]
+#[case::sqlite(sqlite_manager())]
+fn visits_and_lookups(#[case] manager: HistoryManager) {
+    populate(&manager);
+
+    assert_eq!(manager.count(), 5);
+    let github = manager.get_by_url("https://github.com").unwrap();
+    assert_eq!(github.visit_count, 2);
+    assert_eq!(manager.get(&github.id).unwrap().url, github.url);
+    assert_eq!(
+        manager
+            .get_by_url("https://gitlab.com")
+            .unwrap()
+            .typed_count,
+        1
+    );
+
+    assert_eq!(manager.frequent(1)[0].url, "https://github.com");
+    assert_eq!(manager.by_domain("github.com").len(), 2);
+    assert_eq!(manager.by_domain("example.com").len(), 1);
+    assert_eq!(manager.today().len(), 5);
+    assert!(manager
+        .in_range(
+            Utc::now() - Duration::days(10),
+            Utc::now() - Duration::days(5)
+        )
+        .is_empty());
+}
+
+#[rstest]
+#[case::json(json_manager())]
+#[case::sqlite(sqlite_manager())]
+fn search_matches_substrings(#[case] manager: HistoryManager) {
+    populate(&manager);
+
+    let mut urls: Vec<String> = manager
+        .search("git")
+        .into_iter()
+        .map(|r| r.entry.url)
+        .collect();
+    urls.sort();
+    assert_eq!(
+        urls,
+        vec![
+            "https://github.com",
+            "https://github.com/rust-lang/rust",
+            "https://gitlab.com"
+        ]
+    );
+
+    // Case-insensitive, inside words, in title or URL
+    assert_eq!(manager.search("HUB").len(), 2);
+    assert_eq!(manager.search("rust").len(), 2);
+    // Short queries and LIKE metacharacters
+    assert_eq!(manager.search("rs").len(), 1);
+    assert_eq!(manager.search("%_").len(), 1);
+    assert_eq!(manager.search("\"quoted\"").len(), 0);
+    assert_eq!(manager.search("").len(), 5);
+}
+
+#[rstest]
+#[case::json(json_manager())]
+#[case::sqlite(sqlite_manager())]
+fn search_ranking_and_options(#[case] manager: HistoryManager) {
+    populate(&manager);
+
+    // Exact title match ranks first
+    let results = manager.search("github");
+    assert_eq!(results[0].entry.title, "GitHub");
+    assert!(results[0].score >= results[1].score);
+
+    let options = SearchOptions::new().domain("github.com").min_visits(2);
+    let results = manager.search_with_options("git", options);
+    assert_eq!(results.len(), 1);
+    assert_eq!(results[0].entry.url, "https://github.com");
+
+    let results = manager.search_with_options("git", SearchOptions::new().limit(1));
+    assert_eq!(results.len(), 1);
+
+    let future = Utc::now() + Duration::days(1);
+    let results = manager.search_with_options("git", SearchOptions::new().start_date(future));
+    assert!(results.is_empty());
+}
+
+#[rstest]
+#[case::json_like(json_manager(), "go")]
+#[case::json_fts(json_manager(), "golang")]
+#[case::sqlite_like(sqlite_manager(), "go")]
+#[case::sqlite_fts(sqlite_manager(), "golang")]
+fn search_limit_keeps_best_match(#[case] manager: HistoryManager, #[case] query: &str) {
+    for i in 0..20 {
+        manager.visit(format!("https://example.com/golang/{i}"), "Notes");
+    }
+    manager.typed_visit("https://go.dev", "golang");
+
+    let results = manager.search_with_options(query, SearchOptions::new().limit(3));
+    assert_eq!(results.len(), 3);
+    assert_eq!(results[0].entry.url, "https://go.dev");
+    assert!(results.windows(2).all(|w| w[0].score >= w[1].score));
+}
+
+#[rstest]
+#[case::json(json_manager())]
+#[case::sqlite(sqlite_manager())]
+fn deletes(#[case] manager: HistoryManager) {
+    populate(&manager);
+
+    assert_eq!(manager.delete_domain("github.com"), 2);
+    assert!(manager.search("github").is_empty());
+    assert!(manager.delete_url("https://gitlab.com"));
+    assert!(!manager.delete_url("https://gitlab.com"));
+    assert_eq!(manager.delete_older_than(1), 0);
+
+    let id = manager.get_by_url("https://docs.rs/serde").unwrap().id;
+    assert!(manager.delete(&id));
+    assert_eq!(manager.count(), 1);
+
+    manager.clear();
+    assert_eq!(manager.count(), 0);
+    assert!(manager.search("progress").is_empty());
+}
+
+#[rstest]
+#[case::json(json_manager())]
+#[case::sqlite(sqlite_manager())]
+fn max_entries_evicts_oldest(#[case] manager: HistoryManager) {
+    let manager = manager.with_max_entries(3);
+    for i in 0..5 {
+        manager.visit(format!("https://site{i}.com"), format!("Site {i}"));
+        std::thread::sleep(std::time::Duration::from_millis(2));
+    }
+
+    assert_eq!(manager.count(), 3);
+    assert!(manager.get_by_url("https://site0.com").is_none());
+    assert!(manager.get_by_url("https://site4.com").is_some());
+}
+
+#[rstest]
+#[case::json(json_manager())]
+#[case::sqlite(sqlite_manager())]
+fn export_import(#[case] manager: HistoryManager) {
+    populate(&manager);
+    let json = manager.export().unwrap();
+
+    let target = sqlite_manager();
+    target.import(&json).unwrap();
+    assert_eq!(target.count(), 5);
+    assert_eq!(target.search("serde").len(), 1);
+}
+
+// ─── SQLite specifics ────────────────────────────────────────────────────────
+
+#[test]
+fn entries_round_trip_exactly() {
+    let storage = SqliteStorage::in_memory().unwrap();
+    let mut entry = HistoryEntry::new("https://example.com/a", "Example");
+    entry.set_favicon(Some("https://example.com/favicon.ico".to_string()));
+    entry.typed_count = 3;
+    storage.put_many(std::slice::from_ref(&entry)).unwrap();
+
+    let loaded = storage.get(&entry.id).unwrap().unwrap();
+    assert_eq!(loaded.favicon, entry.favicon);
+    assert_eq!(loaded.first_visit, entry.first_visit);
+    assert_eq!(loaded.last_visit, entry.last_visit);
+    assert_eq!(loaded.typed_count, 3);
+}
+
+#[test]
+fn title_updates_reindex_search() {
+    let manager = sqlite_manager();
+    manager.visit("https://example.com", "Old name");
+    manager.visit("https://example.com", "Brand new");
+
+    assert!(manager.search("old name").is_empty());
+    assert_eq!(manager.search("brand").len(), 1);
+}
+
+#[test]
+fn persists_across_reopen() {
+    let dir = TempDir::new().unwrap();
+    {
+        let manager = HistoryManager::sqlite(dir.path()).unwrap();
+        populate(&manager);
+    }
+    assert!(dir.path().join(SQLITE_FILE).exists());
+
+    let manager = HistoryManager::sqlite(dir.path()).unwrap();
+    assert_eq!(manager.count(), 5);
+    assert_eq!(
+        manager
+            .get_by_url("https://github.com")
+            .unwrap()
+            .visit_count,
+        2
+    );
+    assert_eq!(manager.search("gitlab").len(), 1);
+}
+
+#[test]
+fn migrates_json_history() {
+    let dir = TempDir::new().unwrap();
+    {
+        let manager = HistoryManager::new(Some(dir.path()));
+        populate(&manager);
+    }
+    let json_path = dir.path().join(JSON_FILE);
+    assert!(json_path.exists());
+
+    let manager = HistoryManager::sqlite(dir.path()).unwrap();
+    assert_eq!(manager.count(), 5);
+    assert_eq!(
+        manager
+            .get_by_url("https://github.com")
+            .unwrap()
+            .visit_count,
+        2
+    );
+    assert!(!json_path.exists());
+    assert!(dir.path().join("history.json.migrated").exists());
+
+    // Reopening does not import twice
+    drop(manager);
+    let manager = HistoryManager::sqlite(dir.path()).unwrap();
+    assert_eq!(manager.count(), 5);
+}
+
+#[test]
+fn migrate_missing_json_is_noop() {
+    let dir = TempDir::new().unwrap();
+    let storage = SqliteStorage::in_memory().unwrap();
+    assert_eq!(
+        migrate_json(dir.path().join(JSON_FILE), &storage).unwrap(),
+        0
+    );
+}
+
+#[test]
+fn migrate_invalid_json_keeps_file() {
+    let dir = TempDir::new().unwrap();
+    let json_path = dir.path().join(JSON_FILE);
+    std::fs::write(&json_path, "{ not json }").unwrap();
+
+    assert!(HistoryManager::sqlite(dir.path()).is_err());
+    assert!(json_path.exists());
+}
@@ -0,0 +1,99 @@
+//! AppImage AppDir writer
+//!
+//! Produces the directory layout `appimagetool` expects:
+//!
+//! ```text
+//! <name>.AppDir/
+//!   AppRun                      launcher script
+//!   <name>.desktop              desktop entry (root copy)
+//!   <name>.png, .DirIcon        application icon
+//!   usr/bin/<name>              packed executable
+//!   usr/share/applications/     desktop entry
+//!   usr/share/icons/hicolor/    themed icons
+//! ```
+
+use std::fs;
+use std::path::Path;
+
+use super::PackageSpec;
+use crate::builder::common::BuildResult;
+
+/// Write the AppDir for `spec` into `dir`, returning the total size
+pub(super) fn write_appdir(spec: &PackageSpec, dir: &Path) -> BuildResult<u64> {
+    if dir.exists() {
+        fs::remove_dir_all(dir)?;
+    }
+    fs::create_dir_all(dir)?;
+
+    let mut total = 0u64;
+
+    for file in &spec.files {
+        let dest = dir.join(&file.path);
+        if let Some(parent) = dest.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(&dest, &file.content)?;
+        set_mode(&dest, file.mode)?;
+        total += file.content.len() as u64;
+
+        // The desktop entry must also live at the AppDir root
+        if file.path.ends_with(".desktop") {
+            fs::write(dir.join(format!("{}.desktop", spec.name)), &file.content)?;
+            total += file.content.len() as u64;
+        }
+    }
+
+    let app_run = app_run(&spec.name);
+    let app_run_path = dir.join("AppRun");
+    fs::write(&app_run_path, &app_run)?;
+    set_mode(&app_run_path, 0o755)?;
+    total += app_run.len() as u64;
+
+    match spec.icon_png {
+        Some(ref png) => {
+            fs::write(dir.join(format!("{}.png", spec.name)), png)?;
+            fs::write(dir.join(".DirIcon"), png)?;
+            total += 2 * png.len() as u64;
+        }
+        None => tracing::warn!(
+            "No icon configured; appimagetool requires {}.png in the AppDir root",
+            spec.name
+        ),
+    }
+
+    Ok(total)
+}
+
+/// AppRun launcher that resolves the AppDir from its own location
+fn app_run(name: &str) -> String {
+    format!(
+        "#!/bin/sh\n\
+         HERE=\"$(dirname \"$(readlink -f \"$0\")\")\"\n\
+         export PATH=\"$HERE/usr/bin:$PATH\"\n\
+         exec \"$HERE/usr/bin/{name}\" \"$@\"\n"
+    )
+}
+
+#[cfg(unix)]
+fn set_mode(path: &Path, mode: u32) -> BuildResult<()> {
+    use std::os::unix::fs::PermissionsExt;
+    fs::set_permissions(path, fs::Permissions::from_mode(mode))?;
+    Ok(())
+}
+
+#[cfg(not(unix))]
+fn set_mode(_path: &Path, _mode: u32) -> BuildResult<()> {
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn app_run_execs_packed_binary() {
+        let script = app_run("viewer");
+        assert!(script.starts_with("#!/bin/sh\n"));
+        assert!(script.contains("exec \"$HERE/usr/bin/viewer\" \"$@\""));
+    }
+}
@@ -0,0 +1,235 @@
+//! Debian package (.deb) writer
+//!
+//! A `.deb` is an `ar` archive with three members, in this order:
+//!
+//! ```text
+//! debian-binary    "2.0\n"
+//! control.tar.gz   ./control, ./md5sums
+//! data.tar.gz      ./usr/bin/<name>, ./usr/share/...
+//! ```
+
+use std::path::Path;
+
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use md5::{Digest, Md5};
+
+use super::{to_hex, PackageSpec, DEB_DEPENDS};
+use crate::builder::common::BuildResult;
+use crate::PackError;
+
+/// Debian architecture name for the current build host
+pub(super) fn arch() -> &'static str {
+    match std::env::consts::ARCH {
+        "x86_64" => "amd64",
+        "x86" => "i386",
+        "aarch64" => "arm64",
+        "arm" => "armhf",
+        "powerpc64" => "ppc64el",
+        "riscv64" => "riscv64",
+        other => other,
+    }
+}
+
+/// Debian-compatible upstream version (must start with a digit)
+pub(super) fn version(raw: &str) -> String {
+    let cleaned: String = raw
+        .chars()
+        .filter_map(|c| match c {
+            '-' => Some('~'),
+            c if c.is_ascii_alphanumeric() || matches!(c, '.' | '+' | '~') => Some(c),
+            _ => None,
+        })
+        .collect();
+    match cleaned.chars().next() {
+        Some(c) if c.is_ascii_digit() => cleaned,
+        Some(_) => format!("0~{}", cleaned),
+        None => "0.0.0".to_string(),
+    }
+}
+
+/// Write a `.deb` for `spec` to `path`, returning its size
+pub(super) fn write_deb(spec: &PackageSpec, path: &Path) -> BuildResult<u64> {
+    let control = control_file(spec);
+    let md5sums = md5sums(spec);
+    let control_tar = tar_gz(
+        &[
+            ("control", control.as_bytes(), 0o644),
+            ("md5sums", md5sums.as_bytes(), 0o644),
+        ],
+        &[],
+        spec.mtime,
+    )?;
+
+    let data_files: Vec<(&str, &[u8], u32)> = spec
+        .files
+        .iter()
+        .map(|f| (f.path.as_str(), f.content.as_slice(), f.mode))
+        .collect();
+    let data_tar = tar_gz(&data_files, &spec.directories(), spec.mtime)?;
+
+    let deb = ar_archive(
+        &[
+            ("debian-binary", b"2.0\n"),
+            ("control.tar.gz", &control_tar),
+            ("data.tar.gz", &data_tar),
+        ],
+        spec.mtime,
+    );
+
+    std::fs::write(path, &deb)?;
+    Ok(deb.len() as u64)
+}
+
+/// Generate the `DEBIAN/control` file
+pub(super) fn control_file(spec: &PackageSpec) -> String {
+    let mut control = format!(
+        "Package: {}\n\
+         Version: {}-{}\n\
+         Architecture: {}\n\
+         Maintainer: {}\n\
+         Installed-Size: {}\n\
+         Depends: {}\n\
+         Section: utils\n\
+         Priority: optional\n\
+         Description: {}\n",
+        spec.name,
+        version(&spec.version),
+        spec.release,
+        arch(),
+        spec.maintainer,
+        spec.installed_size().div_ceil(1024),
+        DEB_DEPENDS.join(", "),
+        spec.summary
+    );
+
+    // Extended description: continuation lines start with a space, blank
+    // lines are written as " ."
+    let extended: Vec<&str> = spec.description.lines().collect();
+    if extended.len() > 1 || extended.first().is_some_and(|l| *l != spec.summary) {
+        for line in extended {
+            if line.trim().is_empty() {
+                control.push_str(" .\n");
+            } else {
+                control.push_str(&format!(" {}\n", line));
+            }
+        }
+    }
+
+    control
+}
+
+/// Generate the `md5sums` file (`<hex>  <path>` per regular file)
+pub(super) fn md5sums(spec: &PackageSpec) -> String {
+    spec.files
+        .iter()
+        .map(|f| format!("{}  {}\n", to_hex(&Md5::digest(&f.content)), f.path))
+        .collect()
+}
+
+/// Build a gzip-compressed tarball (root `./` entry, then directories, then files)
+fn tar_gz(files: &[(&str, &[u8], u32)], dirs: &[String], mtime: u64) -> BuildResult<Vec<u8>> {
+    let encoder = GzEncoder::new(Vec::new(), Compression::best());
+    let mut archive = tar::Builder::new(encoder);
+
+    let mut root = tar::Header::new_gnu();
+    root.set_entry_type(tar::EntryType::Directory);
+    root.set_mode(0o755);
+    root.set_size(0);
+    root.set_mtime(mtime);
+    root.set_cksum();
+    archive.append_data(&mut root, "./", std::io::empty())?;
+
+    for dir in dirs {
+        let mut header = tar::Header::new_gnu();
+        header.set_entry_type(tar::EntryType::Directory);
+        header.set_mode(0o755);
+        header.set_size(0);
+        header.set_mtime(mtime);
+        header.set_cksum();
+        archive.append_data(&mut header, format!("./{}/", dir), std::io::empty())?;
+    }
+
+    for (path, content, mode) in files {
+        let mut header = tar::Header::new_gnu();
+        header.set_entry_type(tar::EntryType::Regular);
+        header.set_mode(*mode);
+        header.set_size(content.len() as u64);
+        header.set_mtime(mtime);
+        header.set_cksum();
+        archive.append_data(&mut header, format!("./{}", path), *content)?;
+    }
+
+    let encoder = archive
+        .into_inner()
+        .map_err(|e| PackError::Bundle(e.to_string()))?;
+    encoder
+        .finish()
+        .map_err(|e| PackError::Compression(e.to_string()))
+}
+
+/// Build a common-format `ar` archive
+fn ar_archive(members: &[(&str, &[u8])], mtime: u64) -> Vec<u8> {
+    let mut out = Vec::new();
+    out.extend_from_slice(b"!<arch>\n");
+
+    for (name, data) in members {
+        // name(16) mtime(12) uid(6) gid(6) mode(8) size(10) magic(2)
+        let header = format!(
+            "{:<16}{:<12}{:<6}{:<6}{:<8}{:<10}`\n",
+            name,
+            mtime,
+            0,
+            0,
+            "100644",
+            data.len()
+        );
+        debug_assert_eq!(header.len(), 60);
+        out.extend_from_slice(header.as_bytes());
+        out.extend_from_slice(data);
+        if data.len() % 2 == 1 {
+            out.push(b'\n');
+        }
+    }
+
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn version_is_debian_compatible() {
+        assert_eq!(version("1.2.3"), "1.2.3");
+        assert_eq!(version("1.2.3-beta"), "1.2.3~beta");
+        assert_eq!(version("v2.0"), "0~v2.0");
+        assert_eq!(version(""), "0.0.0");
+    }
+
+    #[test]
+    fn ar_archive_pads_odd_members() {
+        let ar = ar_archive(&[("a", b"xyz"), ("b", b"12")], 0);
+        assert!(ar.starts_with(b"!<arch>\n"));
+        // magic + 2 headers + 3 bytes + pad + 2 bytes
+        assert_eq!(ar.len(), 8 + 60 + 4 + 60 + 2);
+        assert_eq!(&ar[8 + 58..8 + 60], b"`\n");
+    }
+
+    #[test]
+    fn tar_gz_writes_directories_before_files() {
+        let data = tar_gz(
+            &[("usr/bin/app", b"bin".as_slice(), 0o755)],
+            &["usr".to_string(), "usr/bin".to_string()],
+            0,
+        )
+        .unwrap();
+        let mut archive = tar::Archive::new(flate2::read::GzDecoder::new(&data[..]));
+        let paths: Vec<String> = archive
+            .entries()
+            .unwrap()
+            .map(|e| String::from_utf8_lossy(&e.unwrap().path_bytes()).to_string())
+            .collect();
+        assert_eq!(paths, vec!["./", "usr/", "usr/bin/", "usr/bin/app"]);
+    }
+}
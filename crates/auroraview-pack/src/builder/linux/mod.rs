@@ -0,0 +1,564 @@
+//! Linux Builder
+//!
+//! Produces Linux distributables from the same packed executable the Windows
+//! builder emits (the `auroraview` binary with an AVPK overlay appended):
+//! - `appimage` - AppDir layout (AppRun, `.desktop`, icons) ready for `appimagetool`
+//! - `deb` - Debian package (`ar` archive with control/data tarballs and md5sums)
+//! - `rpm` - RPM package (lead, signature, header and gzip'd cpio payload)
+//!
+//! All formats are written directly, so no packaging tools are required.
+
+mod appimage;
+mod deb;
+mod rpm;
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use super::common::{BuildContext, BuildOutput, BuildResult, FrontendConfig};
+use super::traits::{Builder, BuilderCapability};
+use super::win::WinBuilder;
+use crate::icon::{load_icon, resize_png};
+use crate::overlay::OverlayWriter;
+use crate::PackError;
+
+/// Icon sizes installed into the freedesktop `hicolor` theme
+const HICOLOR_SIZES: &[u32] = &[16, 32, 48, 64, 128, 256];
+
+/// Runtime dependencies of the WebKitGTK-based shell (Debian package names)
+const DEB_DEPENDS: &[&str] = &["libwebkit2gtk-4.1-0", "libgtk-3-0"];
+
+/// Runtime dependencies of the WebKitGTK-based shell (RPM package names)
+const RPM_REQUIRES: &[&str] = &["webkit2gtk4.1", "gtk3"];
+
+/// Linux package output format
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LinuxFormat {
+    /// AppImage (AppDir layout)
+    AppImage,
+    /// Debian package (.deb)
+    Deb,
+    /// RPM package (.rpm)
+    Rpm,
+}
+
+impl LinuxFormat {
+    /// All formats, in build order
+    pub const ALL: [LinuxFormat; 3] = [Self::AppImage, Self::Deb, Self::Rpm];
+
+    /// Parse from a target/format identifier
+    pub fn parse(s: &str) -> Option<Self> {
+        match s.to_lowercase().as_str() {
+            "appimage" | "appdir" => Some(Self::AppImage),
+            "deb" | "debian" => Some(Self::Deb),
+            "rpm" => Some(Self::Rpm),
+            _ => None,
+        }
+    }
+
+    /// Format identifier used in build output
+    pub fn id(&self) -> &'static str {
+        match self {
+            Self::AppImage => "appimage",
+            Self::Deb => "deb",
+            Self::Rpm => "rpm",
+        }
+    }
+}
+
+/// Linux platform builder
+pub struct LinuxBuilder {
+    /// Executable the overlay is appended to (defaults to the current exe)
+    base_executable: Option<PathBuf>,
+    /// Package release number (deb revision / rpm release)
+    release: String,
+}
+
+impl LinuxBuilder {
+    pub fn new() -> Self {
+        Self {
+            base_executable: None,
+            release: "1".to_string(),
+        }
+    }
+
+    pub fn base_executable(mut self, path: PathBuf) -> Self {
+        self.base_executable = Some(path);
+        self
+    }
+
+    pub fn release(mut self, release: &str) -> Self {
+        self.release = release.to_string();
+        self
+    }
+
+    /// Formats requested by the build target.
+    ///
+    /// `target.format` wins over `target.platform`; a generic `linux` target
+    /// builds every format.
+    pub fn requested_formats(ctx: &BuildContext) -> Vec<LinuxFormat> {
+        let target = &ctx.config.target;
+        target
+            .format
+            .as_deref()
+            .and_then(LinuxFormat::parse)
+            .or_else(|| LinuxFormat::parse(&target.platform))
+            .map(|f| vec![f])
+            .unwrap_or_else(|| LinuxFormat::ALL.to_vec())
+    }
+
+    /// Sanitized package name, also used for the binary and desktop file
+    pub fn package_name(ctx: &BuildContext) -> String {
+        let raw = ctx
+            .config
+            .target
+            .output_name
+            .clone()
+            .unwrap_or_else(|| ctx.config.app.name.clone());
+        let raw = raw.strip_suffix(".exe").unwrap_or(&raw);
+
+        let mut name: String = raw
+            .to_lowercase()
+            .chars()
+            .map(|c| {
+                if c.is_ascii_alphanumeric() || matches!(c, '+' | '-' | '.') {
+                    c
+                } else {
+                    '-'
+                }
+            })
+            .collect();
+        name = name
+            .trim_matches(|c: char| !c.is_ascii_alphanumeric())
+            .to_string();
+
+        if name.is_empty() {
+            "auroraview-app".to_string()
+        } else {
+            name
+        }
+    }
+
+    fn stage_executable(&self, ctx: &BuildContext, name: &str) -> BuildResult<PathBuf> {
+        let base = match &self.base_executable {
+            Some(path) => path.clone(),
+            None => std::env::current_exe()?,
+        };
+        let staged = ctx.temp_dir.join(name);
+        fs::copy(&base, &staged)?;
+
+        let overlay = WinBuilder::new().assemble_overlay(ctx)?;
+        OverlayWriter::write(&staged, &overlay)?;
+        Ok(staged)
+    }
+
+    fn build_spec(
+        &self,
+        ctx: &BuildContext,
+        name: &str,
+        binary: Vec<u8>,
+    ) -> BuildResult<PackageSpec> {
+        let app = &ctx.config.app;
+        let display_name = if app.name.is_empty() {
+            name.to_string()
+        } else {
+            app.name.clone()
+        };
+        let summary = app
+            .description
+            .as_deref()
+            .and_then(|d| d.lines().next())
+            .filter(|s| !s.trim().is_empty())
+            .map(str::to_string)
+            .unwrap_or_else(|| display_name.clone());
+
+        let mut spec = PackageSpec {
+            name: name.to_string(),
+            display_name,
+            version: app.version.clone(),
+            release: self.release.clone(),
+            summary,
+            description: app.description.clone().unwrap_or_default(),
+            maintainer: app.author.clone().unwrap_or_else(|| "Unknown".to_string()),
+            license: "Proprietary".to_string(),
+            mtime: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|d| d.as_secs())
+                .unwrap_or(0),
+            files: Vec::new(),
+            icon_png: None,
+        };
+
+        spec.files
+            .push(PackageFile::executable(format!("usr/bin/{}", name), binary));
+        spec.files.push(PackageFile::regular(
+            format!("usr/share/applications/{}.desktop", name),
+            desktop_entry(ctx, &spec).into_bytes(),
+        ));
+
+        if let Some(ref icon_path) = app.icon {
+            let icon = load_icon(icon_path)?;
+            for &size in HICOLOR_SIZES {
+                let png = resize_png(&icon.png_data, size)?;
+                spec.files.push(PackageFile::regular(
+                    format!("usr/share/icons/hicolor/{size}x{size}/apps/{}.png", name),
+                    png,
+                ));
+            }
+            spec.icon_png = Some(icon.png_data);
+        }
+
+        Ok(spec)
+    }
+}
+
+impl Default for LinuxBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Builder for LinuxBuilder {
+    fn id(&self) -> &'static str {
+        "linux"
+    }
+    fn name(&self) -> &'static str {
+        "Linux"
+    }
+    fn targets(&self) -> &'static [&'static str] {
+        &["linux", "appimage", "deb", "rpm"]
+    }
+
+    fn capabilities(&self) -> Vec<BuilderCapability> {
+        vec![
+            BuilderCapability::Standalone,
+            BuilderCapability::Installer,
+            BuilderCapability::Portable,
+            BuilderCapability::PythonEmbed,
+            BuilderCapability::Extensions,
+        ]
+    }
+
+    fn is_available(&self) -> bool {
+        cfg!(target_os = "linux")
+    }
+
+    fn validate(&self, ctx: &BuildContext) -> BuildResult<()> {
+        match &ctx.config.frontend {
+            Some(FrontendConfig::Path { path }) if !path.exists() => {
+                return Err(PackError::FrontendNotFound(path.clone()));
+            }
+            Some(FrontendConfig::Url { url }) if url.is_empty() => {
+                return Err(PackError::InvalidUrl("URL cannot be empty".into()));
+            }
+            _ => {}
+        }
+        if let Some(ref icon) = ctx.config.app.icon {
+            if !icon.exists() {
+                return Err(PackError::AssetNotFound(icon.clone()));
+            }
+        }
+        if let Some(ref base) = self.base_executable {
+            if !base.is_file() {
+                return Err(PackError::Build(format!(
+                    "Base executable not found: {}",
+                    base.display()
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    fn build(&self, ctx: &mut BuildContext) -> BuildResult<BuildOutput> {
+        self.validate(ctx)?;
+        ctx.ensure_temp_dir()?;
+        fs::create_dir_all(&ctx.output_dir)?;
+
+        let name = Self::package_name(ctx);
+        let formats = Self::requested_formats(ctx);
+        tracing::info!(
+            "Building Linux packages for '{}': {:?}",
+            name,
+            formats.iter().map(|f| f.id()).collect::<Vec<_>>()
+        );
+
+        let staged = self.stage_executable(ctx, &name)?;
+        let spec = self.build_spec(ctx, &name, fs::read(&staged)?)?;
+
+        let mut artifacts: Vec<(LinuxFormat, PathBuf, u64)> = Vec::new();
+        for format in &formats {
+            let (path, size) = match format {
+                LinuxFormat::AppImage => {
+                    let dir = ctx.output_dir.join(format!("{}.AppDir", name));
+                    let size = appimage::write_appdir(&spec, &dir)?;
+                    (dir, size)
+                }
+                LinuxFormat::Deb => {
+                    let path = ctx.output_dir.join(format!(
+                        "{}_{}-{}_{}.deb",
+                        name,
+                        deb::version(&spec.version),
+                        spec.release,
+                        deb::arch()
+                    ));
+                    (path.clone(), deb::write_deb(&spec, &path)?)
+                }
+                LinuxFormat::Rpm => {
+                    let path = ctx.output_dir.join(format!(
+                        "{}-{}-{}.{}.rpm",
+                        name,
+                        rpm::version(&spec.version),
+                        spec.release,
+                        rpm::arch()
+                    ));
+                    (path.clone(), rpm::write_rpm(&spec, &path)?)
+                }
+            };
+            tracing::info!(
+                "Built {}: {} ({:.2} MB)",
+                format.id(),
+                path.display(),
+                size as f64 / 1_048_576.0
+            );
+            artifacts.push((*format, path, size));
+        }
+
+        let total_size = artifacts.iter().map(|(_, _, size)| size).sum();
+        let mut output = match artifacts.as_slice() {
+            [(format, path, _)] => BuildOutput::new(path.clone(), format.id()),
+            _ => BuildOutput::new(ctx.output_dir.clone(), "linux"),
+        };
+        for (format, path, _) in &artifacts {
+            output = output.with_info(format.id(), &path.to_string_lossy());
+        }
+        if formats.contains(&LinuxFormat::AppImage) {
+            output = output.with_info(
+                "appimage_hint",
+                "Run `appimagetool <name>.AppDir` to produce a single-file AppImage",
+            );
+        }
+
+        Ok(output
+            .with_size(total_size)
+            .with_assets(ctx.assets.len())
+            .with_duration(ctx.elapsed()))
+    }
+
+    fn cleanup(&self, ctx: &BuildContext) -> BuildResult<()> {
+        ctx.cleanup()
+    }
+}
+
+/// Package metadata and file list shared by all Linux formats
+pub(crate) struct PackageSpec {
+    /// Sanitized package/binary name
+    pub name: String,
+    /// Human-readable application name
+    pub display_name: String,
+    /// Upstream version (unsanitized)
+    pub version: String,
+    /// Package release/revision
+    pub release: String,
+    /// One-line summary
+    pub summary: String,
+    /// Long description
+    pub description: String,
+    /// Maintainer / packager
+    pub maintainer: String,
+    /// License string
+    pub license: String,
+    /// Modification time for all entries (seconds since epoch)
+    pub mtime: u64,
+    /// Installed files, relative to `/`
+    pub files: Vec<PackageFile>,
+    /// Source icon PNG (full resolution), used for the AppDir root icon
+    pub icon_png: Option<Vec<u8>>,
+}
+
+impl PackageSpec {
+    /// Total installed size in bytes
+    pub fn installed_size(&self) -> u64 {
+        self.files.iter().map(|f| f.content.len() as u64).sum()
+    }
+
+    /// All parent directories of packaged files, sorted (without leading `/`)
+    pub fn directories(&self) -> Vec<String> {
+        let mut dirs = std::collections::BTreeSet::new();
+        for file in &self.files {
+            let mut current = Path::new(&file.path).parent();
+            while let Some(dir) = current {
+                let s = dir.to_string_lossy().replace('\\', "/");
+                if s.is_empty() {
+                    break;
+                }
+                dirs.insert(s);
+                current = dir.parent();
+            }
+        }
+        dirs.into_iter().collect()
+    }
+}
+
+/// A file installed by a Linux package
+pub(crate) struct PackageFile {
+    /// Path relative to `/` (e.g. `usr/bin/my-app`)
+    pub path: String,
+    /// File content
+    pub content: Vec<u8>,
+    /// Permission bits (e.g. `0o755`)
+    pub mode: u32,
+}
+
+impl PackageFile {
+    fn executable(path: String, content: Vec<u8>) -> Self {
+        Self {
+            path,
+            content,
+            mode: 0o755,
+        }
+    }
+
+    fn regular(path: String, content: Vec<u8>) -> Self {
+        Self {
+            path,
+            content,
+            mode: 0o644,
+        }
+    }
+}
+
+/// Generate the freedesktop `.desktop` entry for the application
+fn desktop_entry(ctx: &BuildContext, spec: &PackageSpec) -> String {
+    let linux = ctx.config.platform.linux.as_ref();
+    let mut categories = linux
+        .and_then(|l| l.category.clone())
+        .unwrap_or_else(|| "Utility".to_string());
+    if !categories.ends_with(';') {
+        categories.push(';');
+    }
+
+    let mut entry = format!(
+        "[Desktop Entry]\n\
+         Type=Application\n\
+         Name={}\n\
+         Comment={}\n\
+         Exec={}\n\
+         Icon={}\n\
+         Terminal=false\n\
+         Categories={}\n",
+        spec.display_name, spec.summary, spec.name, spec.name, categories
+    );
+
+    if let Some(linux) = linux {
+        let mut extras: Vec<_> = linux.desktop_extras.iter().collect();
+        extras.sort();
+        for (key, value) in extras {
+            entry.push_str(&format!("{}={}\n", key, value));
+        }
+    }
+
+    entry
+}
+
+/// Lowercase hex encoding of a digest
+pub(crate) fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::builder::common::{
+        AppConfig, BuildConfig, LinuxPlatform, PlatformConfig, TargetConfig,
+    };
+
+    fn ctx(name: &str, format: Option<&str>) -> BuildContext {
+        let config = BuildConfig {
+            version: 1,
+            app: AppConfig {
+                name: name.to_string(),
+                version: "1.2.3".to_string(),
+                ..Default::default()
+            },
+            target: TargetConfig {
+                platform: "linux".to_string(),
+                format: format.map(str::to_string),
+                output_dir: PathBuf::from("out"),
+                output_name: None,
+            },
+            window: Default::default(),
+            frontend: None,
+            backend: None,
+            extensions: Default::default(),
+            platform: PlatformConfig::default(),
+            debug: Default::default(),
+        };
+        BuildContext::new(config, PathBuf::from("out"))
+    }
+
+    #[test]
+    fn package_name_is_sanitized() {
+        assert_eq!(
+            LinuxBuilder::package_name(&ctx("My Cool App!", None)),
+            "my-cool-app"
+        );
+        assert_eq!(LinuxBuilder::package_name(&ctx("tool.exe", None)), "tool");
+        assert_eq!(
+            LinuxBuilder::package_name(&ctx("   ", None)),
+            "auroraview-app"
+        );
+    }
+
+    #[test]
+    fn requested_formats_follow_target() {
+        assert_eq!(
+            LinuxBuilder::requested_formats(&ctx("a", None)),
+            LinuxFormat::ALL.to_vec()
+        );
+        assert_eq!(
+            LinuxBuilder::requested_formats(&ctx("a", Some("deb"))),
+            vec![LinuxFormat::Deb]
+        );
+
+        let mut rpm_ctx = ctx("a", None);
+        rpm_ctx.config.target.platform = "rpm".to_string();
+        assert_eq!(
+            LinuxBuilder::requested_formats(&rpm_ctx),
+            vec![LinuxFormat::Rpm]
+        );
+    }
+
+    #[test]
+    fn desktop_entry_uses_category_and_extras() {
+        let mut c = ctx("Viewer", None);
+        let mut extras = std::collections::HashMap::new();
+        extras.insert("StartupWMClass".to_string(), "viewer".to_string());
+        c.config.platform.linux = Some(LinuxPlatform {
+            category: Some("Graphics".to_string()),
+            desktop_extras: extras,
+        });
+
+        let spec = LinuxBuilder::new()
+            .build_spec(&c, "viewer", b"bin".to_vec())
+            .unwrap();
+        let entry = desktop_entry(&c, &spec);
+
+        assert!(entry.starts_with("[Desktop Entry]\n"));
+        assert!(entry.contains("Name=Viewer\n"));
+        assert!(entry.contains("Exec=viewer\n"));
+        assert!(entry.contains("Categories=Graphics;\n"));
+        assert!(entry.contains("StartupWMClass=viewer\n"));
+    }
+
+    #[test]
+    fn spec_directories_include_all_parents() {
+        let spec = LinuxBuilder::new()
+            .build_spec(&ctx("app", None), "app", b"bin".to_vec())
+            .unwrap();
+        let dirs = spec.directories();
+        assert!(dirs.contains(&"usr".to_string()));
+        assert!(dirs.contains(&"usr/bin".to_string()));
+        assert!(dirs.contains(&"usr/share/applications".to_string()));
+    }
+}
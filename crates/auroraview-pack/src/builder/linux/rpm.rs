@@ -0,0 +1,566 @@
+//! RPM package (.rpm) writer
+//!
+//! An RPM v3 file (as read by rpm 4.x) is laid out as:
+//!
+//! ```text
+//! [Lead]              96 bytes, mostly legacy
+//! [Signature header]  size, MD5 and SHA-256 digests (padded to 8 bytes)
+//! [Header]            package metadata and file list
+//! [Payload]           gzip-compressed cpio ("newc") archive
+//! ```
+//!
+//! Both headers share the same structure: an 16-byte intro, a table of
+//! 16-byte index entries and a data store. Each header starts with a region
+//! tag whose trailer marks the entries as immutable.
+
+use std::io::Write;
+use std::path::Path;
+
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use md5::Md5;
+use sha2::{Digest, Sha256};
+
+use super::{to_hex, PackageSpec, RPM_REQUIRES};
+use crate::builder::common::BuildResult;
+use crate::PackError;
+
+/// RPM lead magic
+const LEAD_MAGIC: [u8; 4] = [0xed, 0xab, 0xee, 0xdb];
+
+/// Header structure magic (including version byte)
+const HEADER_MAGIC: [u8; 4] = [0x8e, 0xad, 0xe8, 0x01];
+
+// Region tags
+const RPMTAG_HEADERSIGNATURES: u32 = 62;
+const RPMTAG_HEADERIMMUTABLE: u32 = 63;
+const RPMTAG_HEADERI18NTABLE: u32 = 100;
+
+// Signature tags
+const RPMSIGTAG_SHA256: u32 = 273;
+const RPMSIGTAG_SIZE: u32 = 1000;
+const RPMSIGTAG_MD5: u32 = 1004;
+const RPMSIGTAG_PAYLOADSIZE: u32 = 1007;
+
+// Header tags
+const RPMTAG_NAME: u32 = 1000;
+const RPMTAG_VERSION: u32 = 1001;
+const RPMTAG_RELEASE: u32 = 1002;
+const RPMTAG_SUMMARY: u32 = 1004;
+const RPMTAG_DESCRIPTION: u32 = 1005;
+const RPMTAG_BUILDTIME: u32 = 1006;
+const RPMTAG_BUILDHOST: u32 = 1007;
+const RPMTAG_SIZE: u32 = 1009;
+const RPMTAG_LICENSE: u32 = 1014;
+const RPMTAG_GROUP: u32 = 1016;
+const RPMTAG_OS: u32 = 1021;
+const RPMTAG_ARCH: u32 = 1022;
+const RPMTAG_FILESIZES: u32 = 1028;
+const RPMTAG_FILEMODES: u32 = 1030;
+const RPMTAG_FILERDEVS: u32 = 1033;
+const RPMTAG_FILEMTIMES: u32 = 1034;
+const RPMTAG_FILEDIGESTS: u32 = 1035;
+const RPMTAG_FILELINKTOS: u32 = 1036;
+const RPMTAG_FILEFLAGS: u32 = 1037;
+const RPMTAG_FILEUSERNAME: u32 = 1039;
+const RPMTAG_FILEGROUPNAME: u32 = 1040;
+const RPMTAG_SOURCERPM: u32 = 1044;
+const RPMTAG_FILEVERIFYFLAGS: u32 = 1045;
+const RPMTAG_PROVIDENAME: u32 = 1047;
+const RPMTAG_REQUIREFLAGS: u32 = 1048;
+const RPMTAG_REQUIRENAME: u32 = 1049;
+const RPMTAG_REQUIREVERSION: u32 = 1050;
+const RPMTAG_FILEDEVICES: u32 = 1095;
+const RPMTAG_FILEINODES: u32 = 1096;
+const RPMTAG_FILELANGS: u32 = 1097;
+const RPMTAG_PROVIDEFLAGS: u32 = 1112;
+const RPMTAG_PROVIDEVERSION: u32 = 1113;
+const RPMTAG_DIRINDEXES: u32 = 1116;
+const RPMTAG_BASENAMES: u32 = 1117;
+const RPMTAG_DIRNAMES: u32 = 1118;
+const RPMTAG_PAYLOADFORMAT: u32 = 1124;
+const RPMTAG_PAYLOADCOMPRESSOR: u32 = 1125;
+const RPMTAG_PAYLOADFLAGS: u32 = 1126;
+const RPMTAG_FILEDIGESTALGO: u32 = 5011;
+const RPMTAG_PAYLOADDIGEST: u32 = 5092;
+const RPMTAG_PAYLOADDIGESTALGO: u32 = 5093;
+
+// Dependency sense flags
+const RPMSENSE_LESS: u32 = 1 << 1;
+const RPMSENSE_EQUAL: u32 = 1 << 3;
+const RPMSENSE_RPMLIB: u32 = 1 << 24;
+
+/// `PGPHASHALGO_SHA256`
+const DIGEST_ALGO_SHA256: u32 = 8;
+
+/// rpmlib features required by the header/payload we write
+const RPMLIB_REQUIRES: &[(&str, &str)] = &[
+    ("rpmlib(CompressedFileNames)", "3.0.4-1"),
+    ("rpmlib(FileDigests)", "4.6.0-1"),
+    ("rpmlib(PayloadFilesHavePrefix)", "4.0-1"),
+];
+
+/// RPM architecture name for the current build host
+pub(super) fn arch() -> &'static str {
+    match std::env::consts::ARCH {
+        "x86" => "i686",
+        "arm" => "armv7hl",
+        "powerpc64" => "ppc64le",
+        other => other,
+    }
+}
+
+/// RPM-compatible version (`-` is reserved as the version/release separator)
+pub(super) fn version(raw: &str) -> String {
+    let cleaned: String = raw
+        .chars()
+        .filter_map(|c| match c {
+            '-' => Some('~'),
+            c if c.is_ascii_alphanumeric() || matches!(c, '.' | '+' | '~' | '_') => Some(c),
+            _ => None,
+        })
+        .collect();
+    if cleaned.is_empty() {
+        "0.0.0".to_string()
+    } else {
+        cleaned
+    }
+}
+
+/// Write an `.rpm` for `spec` to `path`, returning its size
+pub(super) fn write_rpm(spec: &PackageSpec, path: &Path) -> BuildResult<u64> {
+    let version = version(&spec.version);
+
+    let cpio = cpio_archive(spec);
+    let mut encoder = GzEncoder::new(Vec::new(), Compression::best());
+    encoder.write_all(&cpio)?;
+    let payload = encoder
+        .finish()
+        .map_err(|e| PackError::Compression(e.to_string()))?;
+
+    let header = main_header(spec, &version, &payload).to_bytes(RPMTAG_HEADERIMMUTABLE);
+
+    let mut md5 = Md5::new();
+    md5.update(&header);
+    md5.update(&payload);
+
+    let mut signature = Header::default();
+    signature.push(
+        RPMSIGTAG_SHA256,
+        Value::String(to_hex(&Sha256::digest(&header))),
+    );
+    signature.push(
+        RPMSIGTAG_SIZE,
+        Value::Int32(vec![(header.len() + payload.len()) as u32]),
+    );
+    signature.push(RPMSIGTAG_MD5, Value::Bin(md5.finalize().to_vec()));
+    signature.push(RPMSIGTAG_PAYLOADSIZE, Value::Int32(vec![cpio.len() as u32]));
+    let mut signature = signature.to_bytes(RPMTAG_HEADERSIGNATURES);
+    // The signature header is padded so the main header is 8-byte aligned
+    signature.resize(signature.len().next_multiple_of(8), 0);
+
+    let lead = lead(&format!("{}-{}-{}", spec.name, version, spec.release));
+
+    let mut file = std::fs::File::create(path)?;
+    file.write_all(&lead)?;
+    file.write_all(&signature)?;
+    file.write_all(&header)?;
+    file.write_all(&payload)?;
+    file.sync_all()?;
+
+    Ok((lead.len() + signature.len() + header.len() + payload.len()) as u64)
+}
+
+/// Build the 96-byte lead
+fn lead(nevr: &str) -> Vec<u8> {
+    let mut lead = Vec::with_capacity(96);
+    lead.extend_from_slice(&LEAD_MAGIC);
+    lead.extend_from_slice(&[3, 0]); // format 3.0
+    lead.extend_from_slice(&0u16.to_be_bytes()); // binary package
+    let archnum: u16 = match std::env::consts::ARCH {
+        "x86" | "x86_64" => 1,
+        "arm" | "aarch64" => 12,
+        _ => 0,
+    };
+    lead.extend_from_slice(&archnum.to_be_bytes());
+    let mut name = [0u8; 66];
+    let bytes = nevr.as_bytes();
+    let len = bytes.len().min(65);
+    name[..len].copy_from_slice(&bytes[..len]);
+    lead.extend_from_slice(&name);
+    lead.extend_from_slice(&1u16.to_be_bytes()); // osnum: Linux
+    lead.extend_from_slice(&5u16.to_be_bytes()); // signature type: header-style
+    lead.extend_from_slice(&[0u8; 16]);
+    lead
+}
+
+/// Build the main metadata header
+fn main_header(spec: &PackageSpec, version: &str, payload: &[u8]) -> Header {
+    let count = spec.files.len();
+
+    // Compressed file names: each file is (dirindex, basename)
+    let mut dirnames: Vec<String> = Vec::new();
+    let mut dirindexes = Vec::with_capacity(count);
+    let mut basenames = Vec::with_capacity(count);
+    for file in &spec.files {
+        let (dir, base) = match file.path.rsplit_once('/') {
+            Some((dir, base)) => (format!("/{}/", dir), base.to_string()),
+            None => ("/".to_string(), file.path.clone()),
+        };
+        let index = match dirnames.iter().position(|d| *d == dir) {
+            Some(i) => i,
+            None => {
+                dirnames.push(dir);
+                dirnames.len() - 1
+            }
+        };
+        dirindexes.push(index as u32);
+        basenames.push(base);
+    }
+
+    let mut require_names: Vec<String> = Vec::new();
+    let mut require_versions: Vec<String> = Vec::new();
+    let mut require_flags: Vec<u32> = Vec::new();
+    for (name, ver) in RPMLIB_REQUIRES {
+        require_names.push(name.to_string());
+        require_versions.push(ver.to_string());
+        require_flags.push(RPMSENSE_LESS | RPMSENSE_EQUAL | RPMSENSE_RPMLIB);
+    }
+    for name in RPM_REQUIRES {
+        require_names.push(name.to_string());
+        require_versions.push(String::new());
+        require_flags.push(0);
+    }
+
+    let buildhost = hostname::get()
+        .ok()
+        .and_then(|h| h.into_string().ok())
+        .unwrap_or_else(|| "localhost".to_string());
+    let description = if spec.description.is_empty() {
+        spec.summary.clone()
+    } else {
+        spec.description.clone()
+    };
+    let evr = format!("{}-{}", version, spec.release);
+
+    let mut h = Header::default();
+    h.push(RPMTAG_HEADERI18NTABLE, Value::StringArray(vec!["C".into()]));
+    h.push(RPMTAG_NAME, Value::String(spec.name.clone()));
+    h.push(RPMTAG_VERSION, Value::String(version.to_string()));
+    h.push(RPMTAG_RELEASE, Value::String(spec.release.clone()));
+    h.push(RPMTAG_SUMMARY, Value::I18nString(spec.summary.clone()));
+    h.push(RPMTAG_DESCRIPTION, Value::I18nString(description));
+    h.push(RPMTAG_BUILDTIME, Value::Int32(vec![spec.mtime as u32]));
+    h.push(RPMTAG_BUILDHOST, Value::String(buildhost));
+    h.push(
+        RPMTAG_SIZE,
+        Value::Int32(vec![spec.installed_size() as u32]),
+    );
+    h.push(RPMTAG_LICENSE, Value::String(spec.license.clone()));
+    h.push(
+        RPMTAG_GROUP,
+        Value::I18nString("Applications/Internet".into()),
+    );
+    h.push(RPMTAG_OS, Value::String("linux".into()));
+    h.push(RPMTAG_ARCH, Value::String(arch().into()));
+    h.push(
+        RPMTAG_FILESIZES,
+        Value::Int32(spec.files.iter().map(|f| f.content.len() as u32).collect()),
+    );
+    h.push(
+        RPMTAG_FILEMODES,
+        Value::Int16(
+            spec.files
+                .iter()
+                .map(|f| (0o100000 | f.mode) as u16)
+                .collect(),
+        ),
+    );
+    h.push(RPMTAG_FILERDEVS, Value::Int16(vec![0; count]));
+    h.push(
+        RPMTAG_FILEMTIMES,
+        Value::Int32(vec![spec.mtime as u32; count]),
+    );
+    h.push(
+        RPMTAG_FILEDIGESTS,
+        Value::StringArray(
+            spec.files
+                .iter()
+                .map(|f| to_hex(&Sha256::digest(&f.content)))
+                .collect(),
+        ),
+    );
+    h.push(
+        RPMTAG_FILELINKTOS,
+        Value::StringArray(vec![String::new(); count]),
+    );
+    h.push(RPMTAG_FILEFLAGS, Value::Int32(vec![0; count]));
+    h.push(
+        RPMTAG_FILEUSERNAME,
+        Value::StringArray(vec!["root".into(); count]),
+    );
+    h.push(
+        RPMTAG_FILEGROUPNAME,
+        Value::StringArray(vec!["root".into(); count]),
+    );
+    h.push(
+        RPMTAG_SOURCERPM,
+        Value::String(format!("{}-{}.src.rpm", spec.name, evr)),
+    );
+    h.push(RPMTAG_FILEVERIFYFLAGS, Value::Int32(vec![u32::MAX; count]));
+    h.push(
+        RPMTAG_PROVIDENAME,
+        Value::StringArray(vec![spec.name.clone()]),
+    );
+    h.push(RPMTAG_REQUIREFLAGS, Value::Int32(require_flags));
+    h.push(RPMTAG_REQUIRENAME, Value::StringArray(require_names));
+    h.push(RPMTAG_REQUIREVERSION, Value::StringArray(require_versions));
+    h.push(RPMTAG_FILEDEVICES, Value::Int32(vec![1; count]));
+    h.push(
+        RPMTAG_FILEINODES,
+        Value::Int32((1..=count as u32).collect()),
+    );
+    h.push(
+        RPMTAG_FILELANGS,
+        Value::StringArray(vec![String::new(); count]),
+    );
+    h.push(RPMTAG_PROVIDEFLAGS, Value::Int32(vec![RPMSENSE_EQUAL]));
+    h.push(RPMTAG_PROVIDEVERSION, Value::StringArray(vec![evr]));
+    h.push(RPMTAG_DIRINDEXES, Value::Int32(dirindexes));
+    h.push(RPMTAG_BASENAMES, Value::StringArray(basenames));
+    h.push(RPMTAG_DIRNAMES, Value::StringArray(dirnames));
+    h.push(RPMTAG_PAYLOADFORMAT, Value::String("cpio".into()));
+    h.push(RPMTAG_PAYLOADCOMPRESSOR, Value::String("gzip".into()));
+    h.push(RPMTAG_PAYLOADFLAGS, Value::String("9".into()));
+    h.push(
+        RPMTAG_FILEDIGESTALGO,
+        Value::Int32(vec![DIGEST_ALGO_SHA256]),
+    );
+    h.push(
+        RPMTAG_PAYLOADDIGEST,
+        Value::StringArray(vec![to_hex(&Sha256::digest(payload))]),
+    );
+    h.push(
+        RPMTAG_PAYLOADDIGESTALGO,
+        Value::Int32(vec![DIGEST_ALGO_SHA256]),
+    );
+    h
+}
+
+/// Build a `newc` cpio archive of the package files
+fn cpio_archive(spec: &PackageSpec) -> Vec<u8> {
+    let mut out = Vec::new();
+    for (index, file) in spec.files.iter().enumerate() {
+        cpio_entry(
+            &mut out,
+            index as u32 + 1,
+            0o100000 | file.mode,
+            spec.mtime as u32,
+            &format!("./{}", file.path),
+            &file.content,
+        );
+    }
+    cpio_entry(&mut out, 0, 0, 0, "TRAILER!!!", &[]);
+    out
+}
+
+fn cpio_entry(out: &mut Vec<u8>, ino: u32, mode: u32, mtime: u32, name: &str, data: &[u8]) {
+    let nlink = 1;
+    let header = format!(
+        "070701{:08x}{:08x}{:08x}{:08x}{:08x}{:08x}{:08x}{:08x}{:08x}{:08x}{:08x}{:08x}{:08x}",
+        ino,
+        mode,
+        0, // uid
+        0, // gid
+        nlink,
+        mtime,
+        data.len(),
+        0, // devmajor
+        0, // devminor
+        0, // rdevmajor
+        0, // rdevminor
+        name.len() + 1,
+        0, // check
+    );
+    out.extend_from_slice(header.as_bytes());
+    out.extend_from_slice(name.as_bytes());
+    out.push(0);
+    pad4(out);
+    out.extend_from_slice(data);
+    pad4(out);
+}
+
+fn pad4(out: &mut Vec<u8>) {
+    while !out.len().is_multiple_of(4) {
+        out.push(0);
+    }
+}
+
+/// Typed value of a header entry
+enum Value {
+    Int16(Vec<u16>),
+    Int32(Vec<u32>),
+    String(String),
+    Bin(Vec<u8>),
+    StringArray(Vec<String>),
+    I18nString(String),
+}
+
+impl Value {
+    fn type_id(&self) -> u32 {
+        match self {
+            Value::Int16(_) => 3,
+            Value::Int32(_) => 4,
+            Value::String(_) => 6,
+            Value::Bin(_) => 7,
+            Value::StringArray(_) => 8,
+            Value::I18nString(_) => 9,
+        }
+    }
+
+    fn count(&self) -> u32 {
+        match self {
+            Value::Int16(v) => v.len() as u32,
+            Value::Int32(v) => v.len() as u32,
+            Value::String(_) | Value::I18nString(_) => 1,
+            Value::Bin(v) => v.len() as u32,
+            Value::StringArray(v) => v.len() as u32,
+        }
+    }
+
+    fn alignment(&self) -> usize {
+        match self {
+            Value::Int16(_) => 2,
+            Value::Int32(_) => 4,
+            _ => 1,
+        }
+    }
+
+    fn write(&self, store: &mut Vec<u8>) {
+        match self {
+            Value::Int16(v) => v
+                .iter()
+                .for_each(|n| store.extend_from_slice(&n.to_be_bytes())),
+            Value::Int32(v) => v
+                .iter()
+                .for_each(|n| store.extend_from_slice(&n.to_be_bytes())),
+            Value::String(s) | Value::I18nString(s) => {
+                store.extend_from_slice(s.as_bytes());
+                store.push(0);
+            }
+            Value::Bin(b) => store.extend_from_slice(b),
+            Value::StringArray(v) => {
+                for s in v {
+                    store.extend_from_slice(s.as_bytes());
+                    store.push(0);
+                }
+            }
+        }
+    }
+}
+
+/// Header structure builder (used for both the signature and main header)
+#[derive(Default)]
+struct Header {
+    entries: Vec<(u32, Value)>,
+}
+
+impl Header {
+    fn push(&mut self, tag: u32, value: Value) {
+        self.entries.push((tag, value));
+    }
+
+    /// Serialize with a leading immutable region tag
+    fn to_bytes(&self, region_tag: u32) -> Vec<u8> {
+        let mut entries: Vec<&(u32, Value)> = self.entries.iter().collect();
+        entries.sort_by_key(|(tag, _)| *tag);
+
+        let mut index = Vec::new();
+        let mut store = Vec::new();
+        for (tag, value) in entries {
+            let align = value.alignment();
+            while !store.len().is_multiple_of(align) {
+                store.push(0);
+            }
+            index.push((*tag, value.type_id(), store.len() as u32, value.count()));
+            value.write(&mut store);
+        }
+
+        // Region trailer: a copy of the region entry whose (negative) offset
+        // covers every index entry, including the region entry itself
+        let total_entries = index.len() as u32 + 1;
+        let trailer_offset = store.len() as u32;
+        store.extend_from_slice(&region_tag.to_be_bytes());
+        store.extend_from_slice(&7u32.to_be_bytes());
+        store.extend_from_slice(&(-((total_entries * 16) as i32)).to_be_bytes());
+        store.extend_from_slice(&16u32.to_be_bytes());
+        index.insert(0, (region_tag, 7, trailer_offset, 16));
+
+        let mut out = Vec::with_capacity(16 + index.len() * 16 + store.len());
+        out.extend_from_slice(&HEADER_MAGIC);
+        out.extend_from_slice(&[0u8; 4]);
+        out.extend_from_slice(&total_entries.to_be_bytes());
+        out.extend_from_slice(&(store.len() as u32).to_be_bytes());
+        for (tag, type_id, offset, count) in index {
+            out.extend_from_slice(&tag.to_be_bytes());
+            out.extend_from_slice(&type_id.to_be_bytes());
+            out.extend_from_slice(&offset.to_be_bytes());
+            out.extend_from_slice(&count.to_be_bytes());
+        }
+        out.extend_from_slice(&store);
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn version_replaces_release_separator() {
+        assert_eq!(version("1.2.3"), "1.2.3");
+        assert_eq!(version("1.2.3-beta.1"), "1.2.3~beta.1");
+        assert_eq!(version(""), "0.0.0");
+    }
+
+    #[test]
+    fn lead_is_96_bytes() {
+        let lead = lead("app-1.0.0-1");
+        assert_eq!(lead.len(), 96);
+        assert_eq!(&lead[..4], &LEAD_MAGIC);
+        assert_eq!(&lead[10..21], b"app-1.0.0-1");
+    }
+
+    #[test]
+    fn header_region_trailer_covers_all_entries() {
+        let mut h = Header::default();
+        h.push(RPMTAG_NAME, Value::String("app".into()));
+        h.push(RPMTAG_FILEMODES, Value::Int16(vec![0o100755]));
+        let bytes = h.to_bytes(RPMTAG_HEADERIMMUTABLE);
+
+        assert_eq!(&bytes[..4], &HEADER_MAGIC);
+        let nindex = u32::from_be_bytes(bytes[8..12].try_into().unwrap());
+        let hsize = u32::from_be_bytes(bytes[12..16].try_into().unwrap()) as usize;
+        assert_eq!(nindex, 3);
+        assert_eq!(bytes.len(), 16 + 3 * 16 + hsize);
+
+        // First entry is the region tag
+        assert_eq!(
+            u32::from_be_bytes(bytes[16..20].try_into().unwrap()),
+            RPMTAG_HEADERIMMUTABLE
+        );
+        let trailer = &bytes[bytes.len() - 16..];
+        assert_eq!(
+            i32::from_be_bytes(trailer[8..12].try_into().unwrap()),
+            -(3 * 16)
+        );
+    }
+
+    #[test]
+    fn cpio_entries_are_4_byte_aligned() {
+        let mut out = Vec::new();
+        cpio_entry(&mut out, 1, 0o100644, 0, "./a", b"12345");
+        assert_eq!(out.len() % 4, 0);
+        assert!(out.starts_with(b"070701"));
+    }
+}
@@ -0,0 +1,446 @@
+//! Web Builder (PWA/Static)
+//!
+//! Exports the pack configuration as a deployable static directory:
+//! - the bundled frontend assets (plus any hook-collected assets)
+//! - `manifest.webmanifest` built from the app metadata, with resized icons
+//! - `sw.js`, a cache-first service worker precaching every emitted file (PWA)
+//! - `auroraview-shim.js`, so `window.auroraview.*` calls degrade gracefully
+//!   when the page runs in a plain browser instead of the native host
+//!
+//! Every HTML page gets the manifest link and shim injected into its `<head>`.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Component, Path};
+
+use super::common::{BuildContext, BuildOutput, BuildResult, FrontendConfig};
+use super::traits::{Builder, BuilderCapability, OutputFormat};
+use crate::bundle::BundleBuilder;
+use crate::icon::{load_icon, resize_png};
+use crate::PackError;
+
+/// Icon sizes referenced by the web app manifest
+const ICON_SIZES: &[u32] = &[192, 512];
+
+/// Web app manifest file name
+pub const MANIFEST_FILE: &str = "manifest.webmanifest";
+
+/// Service worker file name
+pub const SERVICE_WORKER_FILE: &str = "sw.js";
+
+/// Bridge shim file name
+pub const SHIM_FILE: &str = "auroraview-shim.js";
+
+const SHIM_JS: &str = include_str!("shim.js");
+const SERVICE_WORKER_JS: &str = include_str!("sw.js");
+
+/// Manifest background/theme color when none is configured
+const DEFAULT_COLOR: &str = "#ffffff";
+
+pub struct WebBuilder {
+    /// Force the service worker on/off (defaults to on for PWA targets only)
+    service_worker: Option<bool>,
+}
+
+impl WebBuilder {
+    pub fn new() -> Self {
+        Self {
+            service_worker: None,
+        }
+    }
+
+    pub fn service_worker(mut self, enabled: bool) -> Self {
+        self.service_worker = Some(enabled);
+        self
+    }
+
+    /// Output format requested by the build target.
+    ///
+    /// A `pwa` platform or format selects [`OutputFormat::WebPwa`]; `web` and
+    /// `static` produce [`OutputFormat::WebStatic`].
+    pub fn output_format(ctx: &BuildContext) -> OutputFormat {
+        let target = &ctx.config.target;
+        let is_pwa = target.platform.eq_ignore_ascii_case("pwa")
+            || target
+                .format
+                .as_deref()
+                .is_some_and(|f| f.eq_ignore_ascii_case("pwa"));
+        if is_pwa {
+            OutputFormat::WebPwa
+        } else {
+            OutputFormat::WebStatic
+        }
+    }
+
+    fn wants_service_worker(&self, format: OutputFormat) -> bool {
+        self.service_worker
+            .unwrap_or(format == OutputFormat::WebPwa)
+    }
+
+    /// Directory name of the exported site
+    ///
+    /// The site directory is wiped before each build, so the name must be a
+    /// single normal path component that stays inside the output directory.
+    fn site_name(ctx: &BuildContext) -> BuildResult<String> {
+        let name = ctx
+            .config
+            .target
+            .output_name
+            .clone()
+            .unwrap_or_else(|| ctx.config.app.name.clone());
+        if name.trim().is_empty() {
+            return Ok("web".to_string());
+        }
+        let mut components = Path::new(&name).components();
+        match (components.next(), components.next()) {
+            (Some(Component::Normal(part)), None) if part == name.as_str() => Ok(name),
+            _ => Err(PackError::Config(format!(
+                "Invalid web output name '{}': must be a single directory name",
+                name
+            ))),
+        }
+    }
+
+    /// Frontend files plus hook-collected assets, keyed by relative path
+    fn collect_files(ctx: &BuildContext) -> BuildResult<BTreeMap<String, Vec<u8>>> {
+        let mut files = BTreeMap::new();
+
+        match (&ctx.frontend, &ctx.config.frontend) {
+            (Some(bundle), _) => {
+                files.extend(bundle.files.iter().cloned());
+            }
+            (None, Some(FrontendConfig::Path { path })) => {
+                files.extend(BundleBuilder::new(path).build()?.into_assets());
+            }
+            _ => {}
+        }
+        files.extend(ctx.assets.iter().cloned());
+
+        Ok(files
+            .into_iter()
+            .map(|(path, content)| (path.trim_start_matches('/').to_string(), content))
+            .collect())
+    }
+
+    /// Generate `manifest.webmanifest` from the app metadata
+    fn web_manifest(ctx: &BuildContext, icons: &[(u32, String)]) -> serde_json::Value {
+        let app = &ctx.config.app;
+        let name = ctx
+            .config
+            .window
+            .title
+            .clone()
+            .unwrap_or_else(|| app.name.clone());
+
+        let mut manifest = serde_json::json!({
+            "name": name,
+            "short_name": app.name,
+            "start_url": "./",
+            "scope": "./",
+            "display": if ctx.config.window.fullscreen { "fullscreen" } else { "standalone" },
+            "background_color": DEFAULT_COLOR,
+            "theme_color": DEFAULT_COLOR,
+            "icons": icons
+                .iter()
+                .map(|(size, src)| serde_json::json!({
+                    "src": src,
+                    "sizes": format!("{size}x{size}"),
+                    "type": "image/png",
+                    "purpose": "any",
+                }))
+                .collect::<Vec<_>>(),
+        });
+        if let Some(ref description) = app.description {
+            manifest["description"] = description.clone().into();
+        }
+        if let Some(ref identifier) = app.identifier {
+            manifest["id"] = identifier.clone().into();
+        }
+        manifest
+    }
+}
+
+impl Default for WebBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Builder for WebBuilder {
+    fn id(&self) -> &'static str {
+        "web"
+    }
+    fn name(&self) -> &'static str {
+        "Web"
+    }
+    fn targets(&self) -> &'static [&'static str] {
+        &["web", "pwa", "static"]
+    }
+
+    fn capabilities(&self) -> Vec<BuilderCapability> {
+        vec![BuilderCapability::Standalone, BuilderCapability::HotReload]
+    }
+
+    fn is_available(&self) -> bool {
+        true
+    }
+
+    fn validate(&self, ctx: &BuildContext) -> BuildResult<()> {
+        Self::site_name(ctx)?;
+        if ctx.frontend.is_none() {
+            match &ctx.config.frontend {
+                Some(FrontendConfig::Path { path }) if !path.exists() => {
+                    return Err(PackError::FrontendNotFound(path.clone()));
+                }
+                Some(FrontendConfig::Path { .. }) => {}
+                Some(FrontendConfig::Url { .. }) => {
+                    return Err(PackError::Config(
+                        "Web target requires a local frontend path; URL frontends cannot be exported as a static site".into(),
+                    ));
+                }
+                None => {
+                    return Err(PackError::Config(
+                        "Web target requires a frontend path".into(),
+                    ));
+                }
+            }
+        }
+        if let Some(ref icon) = ctx.config.app.icon {
+            if !icon.exists() {
+                return Err(PackError::AssetNotFound(icon.clone()));
+            }
+        }
+        Ok(())
+    }
+
+    fn build(&self, ctx: &mut BuildContext) -> BuildResult<BuildOutput> {
+        self.validate(ctx)?;
+
+        let format = Self::output_format(ctx);
+        let service_worker = self.wants_service_worker(format);
+        let site_name = Self::site_name(ctx)?;
+        let site_dir = ctx.output_dir.join(&site_name);
+        tracing::info!(
+            "Building {} site: {}",
+            if format == OutputFormat::WebPwa {
+                "PWA"
+            } else {
+                "static"
+            },
+            site_dir.display()
+        );
+
+        let mut files = Self::collect_files(ctx)?;
+        if !files.contains_key("index.html") {
+            return Err(PackError::Bundle(
+                "Web target requires an index.html in the frontend".into(),
+            ));
+        }
+
+        let mut icons = Vec::new();
+        if let Some(ref icon_path) = ctx.config.app.icon {
+            let icon = load_icon(icon_path)?;
+            for &size in ICON_SIZES {
+                let src = format!("icons/icon-{size}.png");
+                files.insert(src.clone(), resize_png(&icon.png_data, size)?);
+                icons.push((size, src));
+            }
+        } else {
+            tracing::warn!("No icon configured; browsers will not offer to install the app");
+        }
+
+        let manifest = serde_json::to_vec_pretty(&Self::web_manifest(ctx, &icons))?;
+        insert_generated(&mut files, MANIFEST_FILE, manifest);
+        insert_generated(&mut files, SHIM_FILE, SHIM_JS.as_bytes().to_vec());
+
+        for (path, content) in files.iter_mut() {
+            if is_html(path) {
+                let html = String::from_utf8_lossy(content);
+                *content = inject_head(&html, &head_tags(path, service_worker)).into_bytes();
+            }
+        }
+
+        if service_worker {
+            let sw = service_worker_js(&site_name, &ctx.config.app.version, &files);
+            insert_generated(&mut files, SERVICE_WORKER_FILE, sw.into_bytes());
+        }
+
+        if site_dir.exists() {
+            fs::remove_dir_all(&site_dir)?;
+        }
+        let mut total_size = 0u64;
+        for (path, content) in &files {
+            let dest = site_dir.join(path);
+            if let Some(parent) = dest.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            fs::write(&dest, content)?;
+            total_size += content.len() as u64;
+        }
+
+        tracing::info!(
+            "Web build complete: {} files ({:.2} MB)",
+            files.len(),
+            total_size as f64 / 1_048_576.0
+        );
+
+        let mut output = BuildOutput::new(
+            site_dir.clone(),
+            if format == OutputFormat::WebPwa {
+                "pwa"
+            } else {
+                "static"
+            },
+        )
+        .with_info("manifest", &site_dir.join(MANIFEST_FILE).to_string_lossy());
+        if service_worker {
+            output = output.with_info(
+                "service_worker",
+                &site_dir.join(SERVICE_WORKER_FILE).to_string_lossy(),
+            );
+        }
+
+        Ok(output
+            .with_size(total_size)
+            .with_assets(files.len())
+            .with_duration(ctx.elapsed()))
+    }
+}
+
+/// Insert a generated file, warning when it replaces a frontend file
+fn insert_generated(files: &mut BTreeMap<String, Vec<u8>>, path: &str, content: Vec<u8>) {
+    if files.insert(path.to_string(), content).is_some() {
+        tracing::warn!("Frontend file '{}' replaced by the generated one", path);
+    }
+}
+
+fn is_html(path: &str) -> bool {
+    let lower = path.to_ascii_lowercase();
+    lower.ends_with(".html") || lower.ends_with(".htm")
+}
+
+/// Tags injected into an HTML page, with URLs relative to the page
+fn head_tags(page: &str, service_worker: bool) -> String {
+    let prefix = "../".repeat(page.matches('/').count());
+    let mut tags = format!(
+        "<link rel=\"manifest\" href=\"{prefix}{MANIFEST_FILE}\">\n\
+         <script src=\"{prefix}{SHIM_FILE}\"></script>\n"
+    );
+    if service_worker {
+        tags.push_str(&format!(
+            "<script>if ('serviceWorker' in navigator) {{ navigator.serviceWorker.register('{prefix}{SERVICE_WORKER_FILE}'); }}</script>\n"
+        ));
+    }
+    tags
+}
+
+/// Insert `tags` right after the opening `<head>` so the shim runs before
+/// any page script. Falls back to before `<body>`, then to the very start.
+/// Pages that already reference the shim are left untouched.
+fn inject_head(html: &str, tags: &str) -> String {
+    if html.contains(SHIM_FILE) {
+        return html.to_string();
+    }
+
+    // ASCII lowercasing keeps byte offsets valid for the original string
+    let lower = html.to_ascii_lowercase();
+    let head_end = lower.match_indices("<head").find_map(|(start, _)| {
+        let rest = &lower[start + 5..];
+        match rest.chars().next() {
+            Some('>') => Some(start + 6),
+            Some(c) if c.is_ascii_whitespace() => rest.find('>').map(|i| start + 5 + i + 1),
+            _ => None,
+        }
+    });
+    let at = head_end.or_else(|| lower.find("<body")).unwrap_or(0);
+
+    let mut out = String::with_capacity(html.len() + tags.len() + 1);
+    out.push_str(&html[..at]);
+    if head_end.is_some() {
+        out.push('\n');
+    }
+    out.push_str(tags);
+    out.push_str(&html[at..]);
+    out
+}
+
+/// Render the service worker with a precache list of every emitted file.
+///
+/// The cache name embeds a content hash so any asset change installs a
+/// fresh cache and the activate handler evicts the old one.
+fn service_worker_js(name: &str, version: &str, files: &BTreeMap<String, Vec<u8>>) -> String {
+    let mut hasher = blake3::Hasher::new();
+    for (path, content) in files {
+        hasher.update(path.as_bytes());
+        hasher.update(&(content.len() as u64).to_le_bytes());
+        hasher.update(content);
+    }
+    let hash = hasher.finalize().to_hex();
+    let cache_name = format!("{}-{}-{}", name, version, &hash[..12]);
+
+    let precache: Vec<String> = std::iter::once("./".to_string())
+        .chain(files.keys().map(|path| format!("./{}", path)))
+        .collect();
+
+    SERVICE_WORKER_JS
+        .replace(
+            "__CACHE_NAME__",
+            &serde_json::Value::from(cache_name).to_string(),
+        )
+        .replace(
+            "__PRECACHE__",
+            &serde_json::to_string_pretty(&precache).unwrap_or_else(|_| "[]".into()),
+        )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn inject_head_places_tags_after_head_open() {
+        let html = "<!DOCTYPE html><HTML><Head lang=\"en\"><script>x()</script></head></html>";
+        let out = inject_head(html, "<T>");
+        assert_eq!(
+            out,
+            "<!DOCTYPE html><HTML><Head lang=\"en\">\n<T><script>x()</script></head></html>"
+        );
+    }
+
+    #[test]
+    fn inject_head_ignores_header_and_falls_back() {
+        assert_eq!(
+            inject_head("<header></header><body>x</body>", "<T>"),
+            "<header></header><T><body>x</body>"
+        );
+        assert_eq!(inject_head("plain", "<T>"), "<T>plain");
+    }
+
+    #[test]
+    fn inject_head_is_idempotent() {
+        let once = inject_head("<head></head>", &head_tags("index.html", false));
+        assert_eq!(inject_head(&once, &head_tags("index.html", false)), once);
+    }
+
+    #[test]
+    fn head_tags_are_relative_to_page() {
+        let tags = head_tags("docs/guide/page.html", true);
+        assert!(tags.contains("href=\"../../manifest.webmanifest\""));
+        assert!(tags.contains("src=\"../../auroraview-shim.js\""));
+        assert!(tags.contains("register('../../sw.js')"));
+        assert!(!head_tags("index.html", false).contains("serviceWorker"));
+    }
+
+    #[test]
+    fn service_worker_cache_name_tracks_content() {
+        let mut files = BTreeMap::new();
+        files.insert("index.html".to_string(), b"a".to_vec());
+        let first = service_worker_js("app", "1.0.0", &files);
+        assert!(first.contains("\"./index.html\""));
+        assert!(!first.contains("__PRECACHE__"));
+
+        files.insert("index.html".to_string(), b"b".to_vec());
+        let second = service_worker_js("app", "1.0.0", &files);
+        assert_ne!(first, second);
+    }
+}
@@ -0,0 +1,113 @@
+(function () {
+  'use strict';
+
+  // Browser fallback for window.auroraview, emitted by the web/pwa pack target.
+  // Inside the native host the real bridge is injected first and this is a no-op.
+  if (window.auroraview) {
+    return;
+  }
+
+  var eventHandlers = {};
+  var warned = {};
+
+  function unavailable(method) {
+    if (!warned[method]) {
+      warned[method] = true;
+      console.warn('[AuroraView Web] "' + method + '" requires the native host; ignoring in browser');
+    }
+    var error = new Error('AuroraView native host is not available: ' + method);
+    error.code = 'NATIVE_HOST_UNAVAILABLE';
+    return Promise.reject(error);
+  }
+
+  function dispatch(event, detail) {
+    var handlers = eventHandlers[event];
+    if (!handlers) {
+      return;
+    }
+    handlers.slice().forEach(function (handler) {
+      try {
+        handler(detail);
+      } catch (e) {
+        console.error('[AuroraView Web] Error in event handler:', e);
+      }
+    });
+  }
+
+  function off(event, handler) {
+    var handlers = eventHandlers[event];
+    if (!handlers) {
+      return;
+    }
+    if (!handler) {
+      delete eventHandlers[event];
+      return;
+    }
+    var index = handlers.indexOf(handler);
+    if (index !== -1) {
+      handlers.splice(index, 1);
+    }
+  }
+
+  // auroraview.api.<method>() resolves to a rejected promise instead of a TypeError
+  var api = typeof Proxy === 'function'
+    ? new Proxy({}, {
+        get: function (target, name) {
+          if (name in target || typeof name !== 'string') {
+            return target[name];
+          }
+          return function () {
+            return unavailable('api.' + name);
+          };
+        }
+      })
+    : {};
+
+  window.auroraview = {
+    _isStub: false,
+    _isWeb: true,
+    _ready: true,
+    call: function (method, _params) {
+      return unavailable(method);
+    },
+    invoke: function (cmd, _args) {
+      return unavailable(cmd);
+    },
+    // Events stay inside the page so UI code wired through on()/send_event() keeps working
+    send_event: function (event, detail) {
+      dispatch(event, detail);
+    },
+    on: function (event, handler) {
+      (eventHandlers[event] = eventHandlers[event] || []).push(handler);
+      return function () {
+        off(event, handler);
+      };
+    },
+    off: off,
+    trigger: function (event, detail) {
+      dispatch(event, detail);
+    },
+    whenReady: function () {
+      return Promise.resolve(window.auroraview);
+    },
+    isReady: function () {
+      return true;
+    },
+    _registerApiMethods: function (_namespace, _methods) {},
+    api: api
+  };
+
+  function dispatchReadyEvent() {
+    window.dispatchEvent(
+      new CustomEvent('auroraviewready', {
+        detail: { timestamp: Date.now(), url: window.location.href, web: true }
+      })
+    );
+  }
+
+  if (document.readyState === 'loading') {
+    document.addEventListener('DOMContentLoaded', dispatchReadyEvent);
+  } else {
+    setTimeout(dispatchReadyEvent, 0);
+  }
+})();
@@ -0,0 +1,40 @@
+'use strict';
+
+// Generated by auroraview-pack. The cache name changes whenever any asset does.
+var CACHE_NAME = __CACHE_NAME__;
+var PRECACHE = __PRECACHE__;
+
+self.addEventListener('install', function (event) {
+  event.waitUntil(
+    caches.open(CACHE_NAME).then(function (cache) {
+      return cache.addAll(PRECACHE);
+    }).then(function () {
+      return self.skipWaiting();
+    })
+  );
+});
+
+self.addEventListener('activate', function (event) {
+  event.waitUntil(
+    caches.keys().then(function (names) {
+      return Promise.all(names.filter(function (name) {
+        return name !== CACHE_NAME;
+      }).map(function (name) {
+        return caches.delete(name);
+      }));
+    }).then(function () {
+      return self.clients.claim();
+    })
+  );
+});
+
+self.addEventListener('fetch', function (event) {
+  if (event.request.method !== 'GET') {
+    return;
+  }
+  event.respondWith(
+    caches.match(event.request, { ignoreSearch: true }).then(function (cached) {
+      return cached || fetch(event.request);
+    })
+  );
+});
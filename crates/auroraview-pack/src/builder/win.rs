@@ -118,46 +118,7 @@ impl Builder for WinBuilder {
         fs::create_dir_all(&ctx.output_dir)?;
         fs::copy(&current_exe, &output_path)?;
 
-        // Build overlay config
-        let overlay_config = self.build_overlay_config(ctx);
-        let mut overlay = OverlayData::new(overlay_config.clone());
-
-        // Bundle frontend
-        if let Some(super::common::FrontendConfig::Path { path }) = &ctx.config.frontend {
-            let bundle = BundleBuilder::new(path).build()?;
-            for (p, content) in bundle.into_assets() {
-                overlay.add_asset(p, content);
-            }
-        }
-
-        // Add Python runtime for FullStack mode with Standalone strategy
-        if let PackMode::FullStack { ref python, .. } = &overlay_config.mode {
-            if python.strategy == BundleStrategy::Standalone {
-                self.add_python_runtime(&mut overlay, &python.version)?;
-            }
-            // Add Python source files from include_paths
-            self.add_python_files(&mut overlay, python)?;
-
-            // Install pip packages if specified
-            if !python.packages.is_empty() {
-                self.add_pip_packages(&mut overlay, python, &ctx.output_dir)?;
-            }
-        }
-
-        // Bundle configured extensions into overlay
-        self.add_configured_extensions(&mut overlay, &overlay_config)?;
-
-        // Add collected assets
-        for (path, content) in &ctx.assets {
-            overlay.add_asset(path.clone(), content.clone());
-        }
-
-        // RFC 0018 §5: harvest CLI command metadata into the overlay so the
-        // runtime `-h`/`list` path is zero-latency. Best-effort — a skip just
-        // leaves `cli_commands` empty (see `collect_cli_metadata`).
-        if let PackMode::FullStack { ref python, .. } = &overlay_config.mode {
-            self.embed_cli_metadata(&mut overlay, python)?;
-        }
+        let overlay = self.assemble_overlay(ctx)?;
 
         // Apply Windows resources
         #[cfg(target_os = "windows")]
@@ -201,6 +162,55 @@ impl Builder for WinBuilder {
 }
 
 impl WinBuilder {
+    /// Assemble the overlay (config + assets) for a packed executable.
+    ///
+    /// The overlay itself is platform-independent, so the Linux builder reuses
+    /// this to produce the binary it wraps into AppImage/deb/rpm packages.
+    pub(super) fn assemble_overlay(&self, ctx: &BuildContext) -> BuildResult<OverlayData> {
+        // Build overlay config
+        let overlay_config = self.build_overlay_config(ctx);
+        let mut overlay = OverlayData::new(overlay_config.clone());
+
+        // Bundle frontend
+        if let Some(super::common::FrontendConfig::Path { path }) = &ctx.config.frontend {
+            let bundle = BundleBuilder::new(path).build()?;
+            for (p, content) in bundle.into_assets() {
+                overlay.add_asset(p, content);
+            }
+        }
+
+        // Add Python runtime for FullStack mode with Standalone strategy
+        if let PackMode::FullStack { ref python, .. } = &overlay_config.mode {
+            if python.strategy == BundleStrategy::Standalone {
+                self.add_python_runtime(&mut overlay, &python.version)?;
+            }
+            // Add Python source files from include_paths
+            self.add_python_files(&mut overlay, python)?;
+
+            // Install pip packages if specified
+            if !python.packages.is_empty() {
+                self.add_pip_packages(&mut overlay, python, &ctx.output_dir)?;
+            }
+        }
+
+        // Bundle configured extensions into overlay
+        self.add_configured_extensions(&mut overlay, &overlay_config)?;
+
+        // Add collected assets
+        for (path, content) in &ctx.assets {
+            overlay.add_asset(path.clone(), content.clone());
+        }
+
+        // RFC 0018 §5: harvest CLI command metadata into the overlay so the
+        // runtime `-h`/`list` path is zero-latency. Best-effort — a skip just
+        // leaves `cli_commands` empty (see `collect_cli_metadata`).
+        if let PackMode::FullStack { ref python, .. } = &overlay_config.mode {
+            self.embed_cli_metadata(&mut overlay, python)?;
+        }
+
+        Ok(overlay)
+    }
+
     fn add_configured_extensions(
         &self,
         overlay: &mut OverlayData,
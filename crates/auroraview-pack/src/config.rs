@@ -465,6 +465,13 @@ pub struct PackConfig {
     /// cross-platform packing).
     #[serde(default)]
     pub cli_commands: Vec<CliCommandMeta>,
+
+    /// Ed25519 private key file used to sign the overlay at pack time.
+    ///
+    /// Only read by the packer; it is cleared before the config is embedded,
+    /// so the key path never ends up in the packed executable.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub signing_key: Option<PathBuf>,
 }
 
 /// Default compression level (19 = high compression, good for releases)
@@ -537,6 +544,7 @@ impl PackConfig {
             content_security_policy: None,
             capture_file_drop: false,
             cli_commands: Vec::new(),
+            signing_key: None,
         }
     }
 
@@ -712,6 +720,12 @@ impl PackConfig {
         self
     }
 
+    /// Sign the overlay with the Ed25519 private key in `path`
+    pub fn with_signing_key(mut self, path: impl Into<PathBuf>) -> Self {
+        self.signing_key = Some(path.into());
+        self
+    }
+
     /// Get debug configuration
     pub fn debug_config(&self) -> DebugConfig {
         DebugConfig {
@@ -854,6 +868,7 @@ impl PackConfig {
                 .unwrap_or(false),
             // RFC 0018: populated later in the pack flow by the pack-time dump.
             cli_commands: Vec::new(),
+            signing_key: manifest.build.signing_key.as_ref().map(&resolve_path),
         };
 
         Ok(config)
@@ -72,6 +72,14 @@ pub enum PackError {
     /// vx.ensure validation failed
     #[error("vx.ensure validation failed: {0}")]
     VxEnsureFailed(String),
+
+    /// Delta patch creation/application error
+    #[error("Patch error: {0}")]
+    Patch(String),
+
+    /// Overlay signing/verification error
+    #[error("Signature error: {0}")]
+    Signature(String),
 }
 
 impl Clone for PackError {
@@ -93,6 +101,8 @@ impl Clone for PackError {
             PackError::Download(s) => PackError::Download(s.clone()),
             PackError::ResourceEdit(s) => PackError::ResourceEdit(s.clone()),
             PackError::VxEnsureFailed(s) => PackError::VxEnsureFailed(s.clone()),
+            PackError::Patch(s) => PackError::Patch(s.clone()),
+            PackError::Signature(s) => PackError::Signature(s.clone()),
         }
     }
 }
@@ -241,6 +241,16 @@ fn image_to_png(img: &DynamicImage) -> PackResult<Vec<u8>> {
     Ok(buffer)
 }
 
+/// Resize PNG data to a square `size`x`size` PNG
+///
+/// Used for freedesktop `hicolor` icon themes and web manifests, which expect
+/// one PNG per resolution.
+pub fn resize_png(png_data: &[u8], size: u32) -> PackResult<Vec<u8>> {
+    let img = load_image(png_data, IconFormat::Png)?;
+    let resized = img.resize_exact(size, size, image::imageops::FilterType::Lanczos3);
+    image_to_png(&resized)
+}
+
 /// Save ICO data to file
 pub fn save_ico(data: &[u8], path: &Path) -> PackResult<()> {
     fs::write(path, data)
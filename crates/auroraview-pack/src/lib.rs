@@ -98,11 +98,13 @@ mod manifest;
 mod metrics;
 mod overlay;
 mod packer;
+mod patch;
 pub mod progress;
 mod protection;
 mod pyoxidizer;
 mod python_standalone;
 mod resource_editor;
+mod signing;
 mod vx_tool;
 
 // Re-export public API
@@ -126,7 +128,7 @@ pub use cli_dump::{collect_cli_metadata, CliDumpOutcome};
 pub use deps_collector::{CollectedDeps, DepsCollector, FileHashCache};
 pub use downloader::Downloader;
 pub use error::{PackError, PackResult};
-pub use icon::{convert_icon_data, load_icon, IconData, IconFormat};
+pub use icon::{convert_icon_data, load_icon, resize_png, IconData, IconFormat};
 pub use license::{get_machine_id, LicenseReason, LicenseStatus, LicenseValidator};
 
 // Re-export manifest types (TOML parsing)
@@ -151,6 +153,10 @@ pub use packer::{
     PackContext, PackHook, PackManager, PackOutput, PackPlugin, PackTarget, Packer, PluginRegistry,
     TargetPacker,
 };
+pub use patch::{
+    ApplyOptions, Patch, PatchAction, PatchAsset, PatchFingerprint, PatchManifest, PatchStats,
+    PATCH_MAGIC, PATCH_VERSION,
+};
 pub use progress::{progress_bar, spinner, PackProgress, ProgressExt, ProgressStyles};
 pub use protection::{
     check_build_tools_available, is_protection_available, protect_python_code,
@@ -165,6 +171,10 @@ pub use python_standalone::{
     PythonStandaloneConfig, PythonTarget,
 };
 pub use resource_editor::{ResourceConfig, ResourceEditor};
+pub use signing::{
+    empty_key_slot, pin_runtime_keys, pinned_runtime_keys, OverlaySignature, OverlaySigningKey,
+    OverlayVerifyingKey, SignaturePolicy, MAX_PINNED_KEYS, SIGNATURE_ALGORITHM,
+};
 pub use vx_tool::VxTool;
 
 /// Alias for backward compatibility with CLI
@@ -183,9 +193,12 @@ pub fn is_packed() -> bool {
 }
 
 /// Read overlay data from the current executable
+///
+/// The signature is checked against [`SignaturePolicy::runtime`], so signed
+/// apps and builds with pinned keys refuse unsigned or foreign overlays.
 pub fn read_overlay() -> PackResult<Option<OverlayData>> {
     let exe_path = std::env::current_exe()?;
-    OverlayReader::read(&exe_path)
+    OverlayReader::read_with_policy(&exe_path, &SignaturePolicy::runtime()?, None)
 }
 
 /// Platform-specific builders (Windows, macOS, Linux, mobile, web, mini-programs).
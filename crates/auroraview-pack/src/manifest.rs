@@ -1167,6 +1167,11 @@ pub struct BuildConfig {
     /// Recommended: 19 for release, 3 for development
     #[serde(default = "default_compression_level")]
     pub compression_level: i32,
+
+    /// Ed25519 private key file used to sign the overlay
+    /// (relative to the manifest directory)
+    #[serde(default)]
+    pub signing_key: Option<PathBuf>,
 }
 
 fn default_compression_level() -> i32 {
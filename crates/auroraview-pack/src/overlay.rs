@@ -12,8 +12,10 @@
 //!   - Version: u32 LE (4 bytes)
 //!   - Config Length: u64 LE (8 bytes)
 //!   - Assets Length: u64 LE (8 bytes)
+//!   - Signature Length: u64 LE (8 bytes, version 2+, 0 if unsigned)
 //! [Config Data] (JSON, zstd compressed)
 //! [Assets Data] (tar archive, zstd compressed)
+//! [Signature Block] (JSON, optional)
 //! [Footer]
 //!   - Overlay Start Offset: u64 LE (8 bytes)
 //!   - Magic: "AVPK" (4 bytes)
@@ -26,6 +28,14 @@
 //! - Cache reuse: Same content → same hash → skip extraction
 //! - Conflict avoidance: Different content → different hash → new directory
 //! - Multi-version support: Multiple versions can coexist
+//!
+//! ## Signature
+//!
+//! When packed with a signing key, the overlay carries an Ed25519 signature
+//! over the config and asset hashes (see [`OverlaySignature`]), and the
+//! signer's public key is pinned in the executable's trusted-key slot.
+//! Readers verify it according to a [`SignaturePolicy`]; version 1 overlays
+//! are unsigned.
 
 use std::fs::File;
 use std::io::{BufReader, BufWriter, Read, Seek, SeekFrom, Write};
@@ -35,13 +45,17 @@ use std::time::Instant;
 use serde::{Deserialize, Serialize};
 
 use crate::metrics::PackedMetrics;
+use crate::signing::{pin_runtime_keys, OverlaySignature, OverlaySigningKey, SignaturePolicy};
 use crate::{PackConfig, PackError, PackResult};
 
 /// Magic bytes for overlay identification
 pub const OVERLAY_MAGIC: &[u8; 4] = b"AVPK";
 
 /// Current overlay format version
-pub const OVERLAY_VERSION: u32 = 1;
+pub const OVERLAY_VERSION: u32 = 2;
+
+/// Oldest overlay format version that can still be read
+const MIN_OVERLAY_VERSION: u32 = 1;
 
 /// Footer size in bytes (offset: 8 + magic: 4)
 const FOOTER_SIZE: u64 = 12;
@@ -57,6 +71,9 @@ pub struct OverlayData {
     /// Embedded assets (file path -> content)
     #[serde(skip)]
     pub assets: Vec<(String, Vec<u8>)>,
+    /// Signature block, if the overlay is signed
+    #[serde(skip)]
+    pub signature: Option<OverlaySignature>,
 }
 
 impl OverlayData {
@@ -66,6 +83,7 @@ impl OverlayData {
             config,
             content_hash: String::new(),
             assets: Vec::new(),
+            signature: None,
         }
     }
 
@@ -142,6 +160,9 @@ impl OverlayWriter {
     /// the original executable content.
     ///
     /// The content hash is computed before writing if not already set.
+    /// If `config.signing_key` is set, the overlay is signed with that key and
+    /// the key is pinned in the executable (see [`pin_runtime_keys`]);
+    /// otherwise an existing `signature` is written as-is.
     /// Uses zstd compression with configurable level (default 19 for high compression).
     pub fn write(exe_path: &Path, data: &OverlayData) -> PackResult<()> {
         Self::write_with_level(exe_path, data, data.config.compression_level)
@@ -159,6 +180,17 @@ impl OverlayWriter {
         let mut data = data.clone();
         let content_hash = data.get_content_hash();
 
+        // The key path is a pack-time input only; never embed it
+        if let Some(key_path) = data.config.signing_key.take() {
+            let key = OverlaySigningKey::from_file(&key_path)?;
+            data.signature = Some(key.sign(&data.config, &data.assets)?);
+            pin_runtime_keys(exe_path, &[key.verifying_key()])?;
+        }
+        let signature_json = match data.signature {
+            Some(ref signature) => serde_json::to_vec(signature)?,
+            None => Vec::new(),
+        };
+
         let file = File::options().append(true).open(exe_path)?;
         let mut writer = BufWriter::new(file);
 
@@ -208,10 +240,12 @@ impl OverlayWriter {
         writer.write_all(&OVERLAY_VERSION.to_le_bytes())?;
         writer.write_all(&(config_compressed.len() as u64).to_le_bytes())?;
         writer.write_all(&(assets_compressed.len() as u64).to_le_bytes())?;
+        writer.write_all(&(signature_json.len() as u64).to_le_bytes())?;
 
         // Write data
         writer.write_all(&config_compressed)?;
         writer.write_all(&assets_compressed)?;
+        writer.write_all(&signature_json)?;
 
         // Write footer
         writer.write_all(&overlay_start.to_le_bytes())?;
@@ -228,11 +262,12 @@ impl OverlayWriter {
         drop(file);
 
         tracing::info!(
-            "Overlay written: config={} bytes, assets={} bytes, hash={}, title={}",
+            "Overlay written: config={} bytes, assets={} bytes, hash={}, title={}, signed by={}",
             config_compressed.len(),
             assets_compressed.len(),
             content_hash,
-            data.config.window.title
+            data.config.window.title,
+            data.signature.as_ref().map_or("-", |s| s.key_id.as_str())
         );
 
         Ok(())
@@ -288,6 +323,10 @@ impl OverlayReader {
     }
 
     /// Read overlay data from a file
+    ///
+    /// The signature is checked against the keys pinned in the file's
+    /// trusted-key slot ([`SignaturePolicy::for_executable`]), as the packed
+    /// runtime does. Use [`OverlayReader::read_with_policy`] to pin other keys.
     pub fn read(path: &Path) -> PackResult<Option<OverlayData>> {
         Self::read_with_metrics(path, None)
     }
@@ -295,6 +334,15 @@ impl OverlayReader {
     /// Read overlay data from a file with performance metrics
     pub fn read_with_metrics(
         path: &Path,
+        metrics: Option<&mut PackedMetrics>,
+    ) -> PackResult<Option<OverlayData>> {
+        Self::read_with_policy(path, &SignaturePolicy::for_executable(path)?, metrics)
+    }
+
+    /// Read overlay data, checking its signature against `policy`
+    pub fn read_with_policy(
+        path: &Path,
+        policy: &SignaturePolicy,
         mut metrics: Option<&mut PackedMetrics>,
     ) -> PackResult<Option<OverlayData>> {
         let file = File::open(path)?;
@@ -339,15 +387,22 @@ impl OverlayReader {
         }
 
         let version = u32::from_le_bytes(version_bytes);
-        if version != OVERLAY_VERSION {
+        if !(MIN_OVERLAY_VERSION..=OVERLAY_VERSION).contains(&version) {
             return Err(PackError::InvalidOverlay(format!(
-                "Unsupported version: {} (expected {})",
-                version, OVERLAY_VERSION
+                "Unsupported version: {} (expected {}-{})",
+                version, MIN_OVERLAY_VERSION, OVERLAY_VERSION
             )));
         }
 
         let config_len = u64::from_le_bytes(config_len_bytes) as usize;
         let assets_len = u64::from_le_bytes(assets_len_bytes) as usize;
+        let signature_len = if version >= 2 {
+            let mut signature_len_bytes = [0u8; 8];
+            reader.read_exact(&mut signature_len_bytes)?;
+            u64::from_le_bytes(signature_len_bytes) as usize
+        } else {
+            0
+        };
 
         // Read config data
         let read_start = Instant::now();
@@ -402,10 +457,32 @@ impl OverlayReader {
             assets.len()
         );
 
+        // Read and verify the signature block
+        let verify_start = Instant::now();
+        let signature = if signature_len > 0 {
+            let mut signature_json = vec![0u8; signature_len];
+            reader.read_exact(&mut signature_json)?;
+            let signature: OverlaySignature = serde_json::from_slice(&signature_json)
+                .map_err(|e| PackError::Signature(format!("Malformed signature block: {}", e)))?;
+            Some(signature)
+        } else {
+            None
+        };
+        policy.check(signature.as_ref(), &config, &assets)?;
+
+        if let Some(ref mut m) = metrics {
+            m.add_phase("signature_verify", verify_start.elapsed());
+        }
+
+        if let Some(ref signature) = signature {
+            tracing::debug!("Overlay signature verified (key {})", signature.key_id);
+        }
+
         Ok(Some(OverlayData {
             config,
             content_hash,
             assets,
+            signature,
         }))
     }
 
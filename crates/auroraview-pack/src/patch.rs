@@ -0,0 +1,675 @@
+//! Delta patches between packed executables
+//!
+//! A patch upgrades one packed executable to another without shipping the
+//! whole file. It records the target overlay's asset table and carries only
+//! the assets that were added or changed (plus the runtime, if the base
+//! executable itself differs). Unchanged assets are taken from the
+//! executable being patched.
+//!
+//! ## Format
+//!
+//! ```text
+//! [Header]
+//!   - Magic: "AVPT" (4 bytes)
+//!   - Version: u32 LE (4 bytes)
+//!   - Manifest Length: u64 LE (8 bytes)
+//!   - Runtime Length: u64 LE (8 bytes, 0 if unchanged)
+//!   - Blobs Length: u64 LE (8 bytes)
+//! [Manifest] (JSON, zstd compressed)
+//! [Runtime] (original executable bytes, zstd compressed)
+//! [Blobs] (tar archive of changed assets, zstd compressed)
+//! ```
+//!
+//! ## Verification
+//!
+//! Before anything is written, the executable being patched must match the
+//! patch's base fingerprint (runtime, overlay content and config hashes). After the
+//! new overlay is assembled, and again after the patched file is written,
+//! the result must match the target fingerprint. The original file is only
+//! replaced once the staged copy has been verified.
+//!
+//! The patch manifest itself is not authenticated, so signatures are checked
+//! against the keys pinned in the executable being patched, never in the
+//! runtime the patch ships. A replacement runtime that pins different keys
+//! is rejected unless key rotation is explicitly allowed
+//! ([`ApplyOptions::with_key_rotation`]).
+
+use std::collections::HashMap;
+use std::fs::{self, File};
+use std::io::Read;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::overlay::{OverlayData, OverlayReader, OverlayWriter};
+use crate::signing::{config_digest, pinned_keys_in, OverlaySignature, SignaturePolicy};
+use crate::{PackConfig, PackError, PackResult};
+
+/// Magic bytes for patch identification
+pub const PATCH_MAGIC: &[u8; 4] = b"AVPT";
+
+/// Current patch format version
+pub const PATCH_VERSION: u32 = 1;
+
+/// Header size in bytes (magic + version + 3 lengths)
+const HEADER_SIZE: usize = 4 + 4 + 8 * 3;
+
+/// Identity of one side of a patch (the executable before or after)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PatchFingerprint {
+    /// BLAKE3 hash (hex) of the executable bytes before the overlay
+    pub runtime_hash: String,
+    /// Overlay content hash (see [`OverlayData::compute_content_hash`])
+    pub content_hash: String,
+    /// Number of assets in the overlay
+    pub asset_count: usize,
+    /// BLAKE3 hash (hex) of the pack configuration, serialized with sorted keys
+    pub config_hash: String,
+}
+
+/// How a target asset is produced when applying the patch
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PatchAction {
+    /// Unchanged, copied from the base executable
+    Keep,
+    /// New in the target, carried in the patch
+    Add,
+    /// Changed content, carried in the patch
+    Replace,
+}
+
+/// One entry of the target asset table
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PatchAsset {
+    /// Asset path inside the overlay
+    pub path: String,
+    /// BLAKE3 hash (hex) of the target content
+    pub hash: String,
+    /// Target content size in bytes
+    pub size: u64,
+    /// Where the content comes from
+    pub action: PatchAction,
+}
+
+/// Patch metadata: fingerprints, target config and asset table
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PatchManifest {
+    /// Executable the patch applies to
+    pub base: PatchFingerprint,
+    /// Executable the patch produces
+    pub target: PatchFingerprint,
+    /// Target pack configuration (always shipped; it is small)
+    pub config: PackConfig,
+    /// Target asset table, in overlay order
+    pub assets: Vec<PatchAsset>,
+    /// Base asset paths that are not in the target
+    #[serde(default)]
+    pub removed: Vec<String>,
+    /// Target overlay signature, so patched executables stay signed
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub signature: Option<OverlaySignature>,
+}
+
+/// Summary of what a patch changes
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PatchStats {
+    /// Assets reused from the base executable
+    pub kept: usize,
+    /// Assets added
+    pub added: usize,
+    /// Assets replaced
+    pub replaced: usize,
+    /// Assets removed
+    pub removed: usize,
+    /// Whether the runtime (executable before the overlay) is replaced
+    pub runtime_changed: bool,
+    /// Uncompressed bytes carried by the patch (assets + runtime)
+    pub payload_bytes: u64,
+}
+
+/// Options for applying a patch
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ApplyOptions {
+    /// Accept a replacement runtime that pins different signing keys
+    pub allow_key_rotation: bool,
+}
+
+impl ApplyOptions {
+    /// Default options: the pinned signing keys must not change
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Accept a replacement runtime that pins different signing keys
+    ///
+    /// The target overlay is then verified against the new runtime's keys.
+    pub fn with_key_rotation(mut self, allow: bool) -> Self {
+        self.allow_key_rotation = allow;
+        self
+    }
+}
+
+/// A delta patch between two packed executables
+#[derive(Debug, Clone)]
+pub struct Patch {
+    /// Patch metadata
+    pub manifest: PatchManifest,
+    /// New runtime bytes, if the base executable changed
+    pub runtime: Option<Vec<u8>>,
+    /// Added/replaced asset contents (path -> content)
+    pub blobs: Vec<(String, Vec<u8>)>,
+}
+
+/// A packed executable split into runtime bytes and overlay
+struct PackedExecutable {
+    runtime: Vec<u8>,
+    overlay: OverlayData,
+}
+
+impl PackedExecutable {
+    fn read(path: &Path) -> PackResult<Self> {
+        Self::read_with_policy(path, &SignaturePolicy::for_executable(path)?)
+    }
+
+    fn read_with_policy(path: &Path, policy: &SignaturePolicy) -> PackResult<Self> {
+        let overlay =
+            OverlayReader::read_with_policy(path, policy, None)?.ok_or_else(|| not_packed(path))?;
+        let runtime_len =
+            OverlayReader::get_original_size(path)?.ok_or_else(|| not_packed(path))?;
+
+        let mut runtime = vec![0u8; runtime_len as usize];
+        File::open(path)?.read_exact(&mut runtime)?;
+
+        Ok(Self { runtime, overlay })
+    }
+
+    fn fingerprint(&mut self) -> PackResult<PatchFingerprint> {
+        Ok(PatchFingerprint {
+            runtime_hash: hash_hex(&self.runtime),
+            // Recompute rather than trust the stored hash
+            content_hash: self.overlay.compute_content_hash(),
+            asset_count: self.overlay.assets.len(),
+            config_hash: config_digest(&self.overlay.config)?.to_hex().to_string(),
+        })
+    }
+}
+
+impl PatchManifest {
+    /// Summarize the changes described by this manifest
+    pub fn stats(&self) -> PatchStats {
+        let count = |action| self.assets.iter().filter(|a| a.action == action).count();
+        PatchStats {
+            kept: count(PatchAction::Keep),
+            added: count(PatchAction::Add),
+            replaced: count(PatchAction::Replace),
+            removed: self.removed.len(),
+            runtime_changed: self.base.runtime_hash != self.target.runtime_hash,
+            payload_bytes: 0,
+        }
+    }
+}
+
+impl Patch {
+    /// Create a patch that turns `old_exe` into `new_exe`
+    pub fn create(old_exe: &Path, new_exe: &Path) -> PackResult<Self> {
+        let mut old = PackedExecutable::read(old_exe)?;
+        let mut new = PackedExecutable::read(new_exe)?;
+        let base = old.fingerprint()?;
+        let target = new.fingerprint()?;
+
+        let old_hashes: HashMap<&str, String> = old
+            .overlay
+            .assets
+            .iter()
+            .map(|(path, content)| (path.as_str(), hash_hex(content)))
+            .collect();
+
+        let mut assets = Vec::with_capacity(new.overlay.assets.len());
+        let mut blobs = Vec::new();
+        for (path, content) in &new.overlay.assets {
+            let hash = hash_hex(content);
+            let action = match old_hashes.get(path.as_str()) {
+                Some(old_hash) if *old_hash == hash => PatchAction::Keep,
+                Some(_) => PatchAction::Replace,
+                None => PatchAction::Add,
+            };
+            if action != PatchAction::Keep {
+                blobs.push((path.clone(), content.clone()));
+            }
+            assets.push(PatchAsset {
+                path: path.clone(),
+                hash,
+                size: content.len() as u64,
+                action,
+            });
+        }
+
+        let target_paths: std::collections::HashSet<&str> =
+            new.overlay.assets.iter().map(|(p, _)| p.as_str()).collect();
+        let removed = old
+            .overlay
+            .assets
+            .iter()
+            .map(|(path, _)| path.clone())
+            .filter(|path| !target_paths.contains(path.as_str()))
+            .collect();
+
+        let runtime = (base.runtime_hash != target.runtime_hash).then(|| new.runtime.clone());
+
+        let patch = Self {
+            manifest: PatchManifest {
+                base,
+                target,
+                config: new.overlay.config,
+                assets,
+                removed,
+                signature: new.overlay.signature,
+            },
+            runtime,
+            blobs,
+        };
+
+        let stats = patch.stats();
+        tracing::info!(
+            "Created patch: {} kept, {} added, {} replaced, {} removed, runtime {}, {:.2} MB payload",
+            stats.kept,
+            stats.added,
+            stats.replaced,
+            stats.removed,
+            if stats.runtime_changed { "replaced" } else { "unchanged" },
+            stats.payload_bytes as f64 / (1024.0 * 1024.0)
+        );
+
+        Ok(patch)
+    }
+
+    /// Summarize the changes carried by this patch
+    pub fn stats(&self) -> PatchStats {
+        let mut stats = self.manifest.stats();
+        stats.payload_bytes = self.blobs.iter().map(|(_, c)| c.len() as u64).sum::<u64>()
+            + self.runtime.as_ref().map_or(0, |r| r.len() as u64);
+        stats
+    }
+
+    /// Serialize the patch, compressing the payload at `level` (1-22)
+    pub fn to_bytes(&self, level: i32) -> PackResult<Vec<u8>> {
+        let level = level.clamp(1, 22);
+
+        let manifest_json = serde_json::to_vec(&self.manifest)?;
+        let manifest = zstd::encode_all(&manifest_json[..], 3)
+            .map_err(|e| PackError::Compression(e.to_string()))?;
+
+        let runtime = match self.runtime {
+            Some(ref runtime) => zstd::encode_all(&runtime[..], level)
+                .map_err(|e| PackError::Compression(e.to_string()))?,
+            None => Vec::new(),
+        };
+
+        let mut archive = tar::Builder::new(Vec::new());
+        for (path, content) in &self.blobs {
+            let mut header = tar::Header::new_gnu();
+            header.set_size(content.len() as u64);
+            header.set_mode(0o644);
+            header.set_mtime(0);
+            header.set_cksum();
+            archive.append_data(&mut header, path, &content[..])?;
+        }
+        let blobs_tar = archive
+            .into_inner()
+            .map_err(|e| PackError::Bundle(e.to_string()))?;
+        let blobs = zstd::encode_all(&blobs_tar[..], level)
+            .map_err(|e| PackError::Compression(e.to_string()))?;
+
+        let mut out =
+            Vec::with_capacity(HEADER_SIZE + manifest.len() + runtime.len() + blobs.len());
+        out.extend_from_slice(PATCH_MAGIC);
+        out.extend_from_slice(&PATCH_VERSION.to_le_bytes());
+        out.extend_from_slice(&(manifest.len() as u64).to_le_bytes());
+        out.extend_from_slice(&(runtime.len() as u64).to_le_bytes());
+        out.extend_from_slice(&(blobs.len() as u64).to_le_bytes());
+        out.extend_from_slice(&manifest);
+        out.extend_from_slice(&runtime);
+        out.extend_from_slice(&blobs);
+        Ok(out)
+    }
+
+    /// Parse a serialized patch
+    pub fn from_bytes(data: &[u8]) -> PackResult<Self> {
+        if data.len() < HEADER_SIZE || &data[..4] != PATCH_MAGIC {
+            return Err(PackError::Patch("Not an AuroraView patch file".to_string()));
+        }
+        let version = u32::from_le_bytes(data[4..8].try_into().expect("4 bytes"));
+        if version != PATCH_VERSION {
+            return Err(PackError::Patch(format!(
+                "Unsupported patch version: {} (expected {})",
+                version, PATCH_VERSION
+            )));
+        }
+
+        let len_at = |offset: usize| {
+            u64::from_le_bytes(data[offset..offset + 8].try_into().expect("8 bytes")) as usize
+        };
+        let manifest_len = len_at(8);
+        let runtime_len = len_at(16);
+        let blobs_len = len_at(24);
+
+        let manifest_end = HEADER_SIZE
+            .checked_add(manifest_len)
+            .ok_or_else(|| PackError::Patch("Corrupt patch header".to_string()))?;
+        let runtime_end = manifest_end
+            .checked_add(runtime_len)
+            .ok_or_else(|| PackError::Patch("Corrupt patch header".to_string()))?;
+        let blobs_end = runtime_end
+            .checked_add(blobs_len)
+            .ok_or_else(|| PackError::Patch("Corrupt patch header".to_string()))?;
+        if blobs_end > data.len() {
+            return Err(PackError::Patch("Patch file is truncated".to_string()));
+        }
+
+        let manifest_json = zstd::decode_all(&data[HEADER_SIZE..manifest_end])
+            .map_err(|e| PackError::Compression(e.to_string()))?;
+        let manifest: PatchManifest = serde_json::from_slice(&manifest_json)?;
+
+        let runtime = if runtime_len > 0 {
+            Some(
+                zstd::decode_all(&data[manifest_end..runtime_end])
+                    .map_err(|e| PackError::Compression(e.to_string()))?,
+            )
+        } else {
+            None
+        };
+
+        let decoder = zstd::stream::Decoder::new(&data[runtime_end..blobs_end])
+            .map_err(|e| PackError::Compression(e.to_string()))?;
+        let mut archive = tar::Archive::new(decoder);
+        let mut blobs = Vec::new();
+        for entry in archive.entries()? {
+            let mut entry = entry?;
+            let path = entry.path()?.to_string_lossy().to_string();
+            let mut content = Vec::with_capacity(entry.size() as usize);
+            entry.read_to_end(&mut content)?;
+            blobs.push((path, content));
+        }
+
+        Ok(Self {
+            manifest,
+            runtime,
+            blobs,
+        })
+    }
+
+    /// Write the patch to a file, returning its size
+    pub fn write(&self, path: &Path, level: i32) -> PackResult<u64> {
+        let bytes = self.to_bytes(level)?;
+        fs::write(path, &bytes)?;
+        Ok(bytes.len() as u64)
+    }
+
+    /// Read a patch from a file
+    pub fn read(path: &Path) -> PackResult<Self> {
+        Self::from_bytes(&fs::read(path)?)
+    }
+
+    /// Check that `exe_path` is the executable this patch was created from
+    pub fn verify_base(&self, exe_path: &Path) -> PackResult<()> {
+        let mut exe = PackedExecutable::read(exe_path)?;
+        check_fingerprint("base", &self.manifest.base, &exe.fingerprint()?)
+    }
+
+    /// Apply the patch to `exe_path`, writing the result to `output`.
+    ///
+    /// `exe_path` is left untouched. Fails without writing `output` if the
+    /// base or assembled target does not match the patch fingerprints.
+    pub fn apply_to(&self, exe_path: &Path, output: &Path) -> PackResult<PatchStats> {
+        self.apply_to_with_options(exe_path, output, &ApplyOptions::default())
+    }
+
+    /// Apply the patch to `exe_path` with `options`, writing the result to `output`.
+    ///
+    /// The target overlay must be accepted by the signing keys pinned in
+    /// `exe_path` (or, with key rotation allowed, in the replacement runtime).
+    pub fn apply_to_with_options(
+        &self,
+        exe_path: &Path,
+        output: &Path,
+        options: &ApplyOptions,
+    ) -> PackResult<PatchStats> {
+        // Trust comes from the installed executable, not from the patch
+        let base_policy = SignaturePolicy::for_executable(exe_path)?;
+        let mut base = PackedExecutable::read_with_policy(exe_path, &base_policy)?;
+        check_fingerprint("base", &self.manifest.base, &base.fingerprint()?)?;
+        let policy = self.target_policy(base_policy, options)?;
+
+        let mut target = self.assemble(base)?;
+        check_fingerprint("target", &self.manifest.target, &target.fingerprint()?)?;
+        policy.check(
+            target.overlay.signature.as_ref(),
+            &target.overlay.config,
+            &target.overlay.assets,
+        )?;
+
+        let staged = staging_path(output);
+        if let Err(e) = self.write_target(&target, &staged, &policy) {
+            let _ = fs::remove_file(&staged);
+            return Err(e);
+        }
+        replace_file(&staged, output)?;
+        Ok(self.stats())
+    }
+
+    /// Apply the patch to `exe_path` in place.
+    ///
+    /// The patched executable is staged next to the original and verified
+    /// before it replaces it. Works on a running executable: on Windows the
+    /// original is moved aside (`<name>.old`) and removed on a best-effort
+    /// basis.
+    pub fn apply(&self, exe_path: &Path) -> PackResult<PatchStats> {
+        self.apply_to(exe_path, exe_path)
+    }
+
+    /// Apply the patch to `exe_path` in place with `options`
+    pub fn apply_with_options(
+        &self,
+        exe_path: &Path,
+        options: &ApplyOptions,
+    ) -> PackResult<PatchStats> {
+        self.apply_to_with_options(exe_path, exe_path, options)
+    }
+
+    /// Signature policy for the target, starting from the base executable's
+    ///
+    /// A replacement runtime must pin the same keys as the base unless
+    /// key rotation is allowed.
+    fn target_policy(
+        &self,
+        base_policy: SignaturePolicy,
+        options: &ApplyOptions,
+    ) -> PackResult<SignaturePolicy> {
+        let Some(ref runtime) = self.runtime else {
+            return Ok(base_policy);
+        };
+        let keys = pinned_keys_in("replacement runtime", runtime)?;
+        if keys == base_policy.trusted_keys() {
+            return Ok(base_policy);
+        }
+        if !options.allow_key_rotation {
+            return Err(PackError::Patch(
+                "Patch replaces the runtime with one that pins different signing keys; \
+                 allow key rotation to apply it"
+                    .to_string(),
+            ));
+        }
+        tracing::warn!(
+            "Patch rotates the pinned signing keys ({} -> {} keys)",
+            base_policy.trusted_keys().len(),
+            keys.len()
+        );
+        Ok(if keys.is_empty() {
+            SignaturePolicy::new()
+        } else {
+            SignaturePolicy::pinned(keys)
+        })
+    }
+
+    /// Build the target executable in memory from the base
+    fn assemble(&self, base: PackedExecutable) -> PackResult<PackedExecutable> {
+        let mut base_assets: HashMap<String, Vec<u8>> = base.overlay.assets.into_iter().collect();
+        let mut blobs: HashMap<&str, &Vec<u8>> =
+            self.blobs.iter().map(|(p, c)| (p.as_str(), c)).collect();
+
+        let mut overlay = OverlayData::new(self.manifest.config.clone());
+        for asset in &self.manifest.assets {
+            let content = match asset.action {
+                PatchAction::Keep => base_assets.remove(&asset.path),
+                PatchAction::Add | PatchAction::Replace => {
+                    blobs.remove(asset.path.as_str()).cloned()
+                }
+            }
+            .ok_or_else(|| {
+                PackError::Patch(format!("Patch is missing content for '{}'", asset.path))
+            })?;
+
+            let hash = hash_hex(&content);
+            if hash != asset.hash {
+                return Err(PackError::Patch(format!(
+                    "Hash mismatch for '{}': expected {}, got {}",
+                    asset.path, asset.hash, hash
+                )));
+            }
+            overlay.add_asset(asset.path.clone(), content);
+        }
+        overlay.signature = self.manifest.signature.clone();
+
+        let runtime = match self.runtime {
+            Some(ref runtime) => runtime.clone(),
+            None => base.runtime,
+        };
+
+        Ok(PackedExecutable { runtime, overlay })
+    }
+
+    /// Write and re-verify the target executable at `path` against `policy`
+    fn write_target(
+        &self,
+        target: &PackedExecutable,
+        path: &Path,
+        policy: &SignaturePolicy,
+    ) -> PackResult<()> {
+        fs::write(path, &target.runtime)?;
+        OverlayWriter::write(path, &target.overlay)?;
+
+        let mut written = PackedExecutable::read_with_policy(path, policy)?;
+        check_fingerprint("patched", &self.manifest.target, &written.fingerprint()?)
+    }
+}
+
+/// Move `staged` over `dest`, keeping `dest`'s permissions
+fn replace_file(staged: &Path, dest: &Path) -> PackResult<()> {
+    if let Ok(meta) = fs::metadata(dest) {
+        fs::set_permissions(staged, meta.permissions())?;
+    }
+
+    if !dest.exists() {
+        fs::rename(staged, dest)?;
+        return Ok(());
+    }
+
+    // A running executable cannot be overwritten on Windows, but it can be
+    // renamed; move it aside first and restore it if the swap fails.
+    let backup = sibling_path(dest, "old");
+    if backup.exists() {
+        fs::remove_file(&backup)?;
+    }
+    fs::rename(dest, &backup)?;
+    if let Err(e) = fs::rename(staged, dest) {
+        let _ = fs::rename(&backup, dest);
+        return Err(e.into());
+    }
+    if let Err(e) = fs::remove_file(&backup) {
+        tracing::debug!("Keeping {} until next run: {}", backup.display(), e);
+    }
+    Ok(())
+}
+
+fn staging_path(dest: &Path) -> PathBuf {
+    sibling_path(dest, "patching")
+}
+
+fn sibling_path(path: &Path, suffix: &str) -> PathBuf {
+    let mut name = path.file_name().unwrap_or_default().to_os_string();
+    name.push(".");
+    name.push(suffix);
+    path.with_file_name(name)
+}
+
+fn check_fingerprint(
+    what: &str,
+    expected: &PatchFingerprint,
+    actual: &PatchFingerprint,
+) -> PackResult<()> {
+    if expected.runtime_hash != actual.runtime_hash {
+        return Err(PackError::Patch(format!(
+            "{} runtime hash mismatch: expected {}, got {}",
+            what, expected.runtime_hash, actual.runtime_hash
+        )));
+    }
+    if expected.content_hash != actual.content_hash || expected.asset_count != actual.asset_count {
+        return Err(PackError::Patch(format!(
+            "{} content hash mismatch: expected {} ({} assets), got {} ({} assets)",
+            what,
+            expected.content_hash,
+            expected.asset_count,
+            actual.content_hash,
+            actual.asset_count
+        )));
+    }
+    if expected.config_hash != actual.config_hash {
+        return Err(PackError::Patch(format!(
+            "{} config hash mismatch: expected {}, got {}",
+            what, expected.config_hash, actual.config_hash
+        )));
+    }
+    Ok(())
+}
+
+fn hash_hex(data: &[u8]) -> String {
+    blake3::hash(data).to_hex().to_string()
+}
+
+fn not_packed(path: &Path) -> PackError {
+    PackError::Patch(format!(
+        "Not a packed executable (no overlay): {}",
+        path.display()
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sibling_path_appends_suffix() {
+        assert_eq!(
+            sibling_path(Path::new("/opt/app/tool.exe"), "old"),
+            PathBuf::from("/opt/app/tool.exe.old")
+        );
+    }
+
+    #[test]
+    fn from_bytes_rejects_bad_magic_and_truncation() {
+        assert!(matches!(
+            Patch::from_bytes(b"AVPK\x01\0\0\0"),
+            Err(PackError::Patch(_))
+        ));
+
+        let mut header = Vec::new();
+        header.extend_from_slice(PATCH_MAGIC);
+        header.extend_from_slice(&PATCH_VERSION.to_le_bytes());
+        header.extend_from_slice(&100u64.to_le_bytes());
+        header.extend_from_slice(&0u64.to_le_bytes());
+        header.extend_from_slice(&0u64.to_le_bytes());
+        let err = Patch::from_bytes(&header).unwrap_err();
+        assert!(err.to_string().contains("truncated"));
+    }
+}
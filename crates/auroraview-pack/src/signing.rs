@@ -0,0 +1,658 @@
+//! Ed25519 signatures for overlay data
+//!
+//! A signed overlay carries a small signature block (key id, public key and
+//! Ed25519 signature) after the assets. The signature covers a canonical
+//! digest of the pack configuration and of every asset (path, size and
+//! BLAKE3 hash), so swapping assets or editing the config invalidates it.
+//!
+//! ## Trust
+//!
+//! The public key embedded in the block is supplied by the overlay itself, so
+//! it is never used for verification. Signatures are checked against keys
+//! pinned in the runtime, outside the overlay:
+//!
+//! - Every runtime contains a fixed-size trusted-key slot. Signing an overlay
+//!   also writes the signer's public key into that slot of the executable
+//!   (see [`pin_runtime_keys`]), so a signed app refuses unsigned overlays and
+//!   overlays signed by any other key.
+//! - Builds compiled with `AURORAVIEW_TRUSTED_OVERLAY_KEYS` (comma-separated
+//!   public keys in hex) trust those keys as well.
+//!
+//! Without pinned keys there is nothing to verify against: signature blocks
+//! are reported but not trusted.
+//!
+//! ## Key files
+//!
+//! A private key file holds the 32-byte Ed25519 seed as 64 hex characters.
+//! Blank lines and lines starting with `#` are ignored.
+
+use std::fmt;
+use std::fs;
+use std::io::{Read, Seek, SeekFrom, Write};
+use std::path::Path;
+
+use ed25519_dalek::{Signature, Signer, SigningKey, Verifier, VerifyingKey};
+use serde::{Deserialize, Serialize};
+
+use crate::overlay::OverlayReader;
+use crate::{PackConfig, PackError, PackResult};
+
+/// Signature algorithm identifier stored in the signature block
+pub const SIGNATURE_ALGORITHM: &str = "ed25519";
+
+/// Domain separator for the signed message
+const SIGNATURE_DOMAIN: &[u8] = b"auroraview-overlay-signature-v1\0";
+
+/// Public keys trusted by this build (set at compile time)
+const TRUSTED_KEYS_ENV: Option<&str> = option_env!("AURORAVIEW_TRUSTED_OVERLAY_KEYS");
+
+/// Maximum number of public keys the runtime key slot can pin
+pub const MAX_PINNED_KEYS: usize = 8;
+
+/// Marker that starts the trusted-key slot
+const KEY_SLOT_MARKER: [u8; 32] = *b"AURORAVIEW_TRUSTED_KEY_SLOT_V1\0\0";
+
+/// Bytes read at a time when scanning a runtime for its key slot
+const KEY_SLOT_SCAN_CHUNK: usize = 64 * 1024;
+
+/// Slot layout: marker, key count (u8), `MAX_PINNED_KEYS` 32-byte keys
+const KEY_SLOT_LEN: usize = KEY_SLOT_MARKER.len() + 1 + MAX_PINNED_KEYS * 32;
+
+/// Trusted-key slot of this runtime, patched in the executable file at pack time
+#[used]
+static KEY_SLOT: [u8; KEY_SLOT_LEN] = {
+    let mut slot = [0u8; KEY_SLOT_LEN];
+    let mut i = 0;
+    while i < KEY_SLOT_MARKER.len() {
+        slot[i] = KEY_SLOT_MARKER[i];
+        i += 1;
+    }
+    slot
+};
+
+/// Signature block stored in the overlay
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OverlaySignature {
+    /// Signature algorithm (always "ed25519")
+    pub algorithm: String,
+    /// Short key identifier (see [`OverlayVerifyingKey::key_id`])
+    pub key_id: String,
+    /// Ed25519 public key (hex)
+    pub public_key: String,
+    /// Ed25519 signature (hex)
+    pub signature: String,
+}
+
+/// Private key used to sign overlays at pack time
+#[derive(Clone)]
+pub struct OverlaySigningKey {
+    inner: SigningKey,
+}
+
+/// Public key used to verify overlay signatures
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OverlayVerifyingKey {
+    inner: VerifyingKey,
+}
+
+impl OverlaySigningKey {
+    /// Generate a new random signing key
+    pub fn generate() -> Self {
+        let seed: [u8; 32] = rand::random();
+        Self::from_seed(&seed)
+    }
+
+    /// Create a signing key from a 32-byte Ed25519 seed
+    pub fn from_seed(seed: &[u8; 32]) -> Self {
+        Self {
+            inner: SigningKey::from_bytes(seed),
+        }
+    }
+
+    /// Load a signing key from a private key file
+    pub fn from_file(path: &Path) -> PackResult<Self> {
+        let content = fs::read_to_string(path).map_err(|e| {
+            PackError::Signature(format!(
+                "Failed to read signing key {}: {}",
+                path.display(),
+                e
+            ))
+        })?;
+        Self::from_hex(&strip_comments(&content)).map_err(|e| {
+            PackError::Signature(format!("Invalid signing key {}: {}", path.display(), e))
+        })
+    }
+
+    /// Parse a signing key from a hex-encoded seed
+    pub fn from_hex(hex: &str) -> PackResult<Self> {
+        let seed = decode_hex::<32>(hex.trim())?;
+        Ok(Self::from_seed(&seed))
+    }
+
+    /// Hex-encoded seed
+    pub fn to_hex(&self) -> String {
+        encode_hex(self.inner.as_bytes())
+    }
+
+    /// Write the key to `path` as a private key file
+    pub fn write_to_file(&self, path: &Path) -> PackResult<()> {
+        let public = self.verifying_key();
+        let content = format!(
+            "# AuroraView overlay signing key (Ed25519). Keep this file secret.\n\
+             # key id: {}\n\
+             # public key: {}\n\
+             {}\n",
+            public.key_id(),
+            public.to_hex(),
+            self.to_hex()
+        );
+        fs::write(path, content)?;
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            fs::set_permissions(path, fs::Permissions::from_mode(0o600))?;
+        }
+
+        Ok(())
+    }
+
+    /// Public half of this key
+    pub fn verifying_key(&self) -> OverlayVerifyingKey {
+        OverlayVerifyingKey {
+            inner: self.inner.verifying_key(),
+        }
+    }
+
+    /// Sign the overlay made of `config` and `assets`
+    pub fn sign(
+        &self,
+        config: &PackConfig,
+        assets: &[(String, Vec<u8>)],
+    ) -> PackResult<OverlaySignature> {
+        let message = signed_message(config, assets)?;
+        let signature = self.inner.sign(&message);
+        let public = self.verifying_key();
+        Ok(OverlaySignature {
+            algorithm: SIGNATURE_ALGORITHM.to_string(),
+            key_id: public.key_id(),
+            public_key: public.to_hex(),
+            signature: encode_hex(&signature.to_bytes()),
+        })
+    }
+}
+
+impl fmt::Debug for OverlaySigningKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("OverlaySigningKey")
+            .field("key_id", &self.verifying_key().key_id())
+            .finish_non_exhaustive()
+    }
+}
+
+impl OverlayVerifyingKey {
+    /// Parse a public key from hex
+    pub fn from_hex(hex: &str) -> PackResult<Self> {
+        let bytes = decode_hex::<32>(hex.trim())?;
+        let inner = VerifyingKey::from_bytes(&bytes)
+            .map_err(|e| PackError::Signature(format!("Invalid public key: {}", e)))?;
+        Ok(Self { inner })
+    }
+
+    /// Hex-encoded public key
+    pub fn to_hex(&self) -> String {
+        encode_hex(self.inner.as_bytes())
+    }
+
+    /// Short identifier: first 8 bytes of the BLAKE3 hash of the public key (hex)
+    pub fn key_id(&self) -> String {
+        encode_hex(&blake3::hash(self.inner.as_bytes()).as_bytes()[..8])
+    }
+
+    /// Verify `signature` against the overlay made of `config` and `assets`
+    pub fn verify(
+        &self,
+        signature: &OverlaySignature,
+        config: &PackConfig,
+        assets: &[(String, Vec<u8>)],
+    ) -> PackResult<()> {
+        if signature.algorithm != SIGNATURE_ALGORITHM {
+            return Err(PackError::Signature(format!(
+                "Unsupported signature algorithm: {}",
+                signature.algorithm
+            )));
+        }
+        let bytes = decode_hex::<64>(&signature.signature)?;
+        let message = signed_message(config, assets)?;
+        self.inner
+            .verify(&message, &Signature::from_bytes(&bytes))
+            .map_err(|_| {
+                PackError::Signature(format!(
+                    "Signature by key {} does not match overlay contents",
+                    signature.key_id
+                ))
+            })
+    }
+}
+
+/// Which overlay signatures are accepted when reading
+#[derive(Debug, Clone, Default)]
+pub struct SignaturePolicy {
+    trusted_keys: Vec<OverlayVerifyingKey>,
+    require_signature: bool,
+}
+
+impl SignaturePolicy {
+    /// Accept any overlay; without trusted keys, signatures are not verified
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Require a valid signature from one of `keys`
+    pub fn pinned(keys: Vec<OverlayVerifyingKey>) -> Self {
+        Self {
+            trusted_keys: keys,
+            require_signature: true,
+        }
+    }
+
+    /// Policy for a packed app starting up
+    ///
+    /// Pinned to the keys in this runtime's key slot (written when the app was
+    /// packed with a signing key) and to `AURORAVIEW_TRUSTED_OVERLAY_KEYS`.
+    /// Without either, the same as [`SignaturePolicy::new`].
+    pub fn runtime() -> PackResult<Self> {
+        let mut keys = match TRUSTED_KEYS_ENV {
+            Some(keys) => parse_key_list(keys)?,
+            None => Vec::new(),
+        };
+        keys.extend(slot_keys(&runtime_key_slot())?);
+        if keys.is_empty() {
+            Ok(Self::new())
+        } else {
+            Ok(Self::pinned(keys))
+        }
+    }
+
+    /// Policy of the runtime executable at `exe_path`
+    ///
+    /// Pinned to the keys in its key slot, as the runtime itself would be;
+    /// the same as [`SignaturePolicy::new`] when none are pinned.
+    pub fn for_executable(exe_path: &Path) -> PackResult<Self> {
+        let keys = pinned_runtime_keys(exe_path)?;
+        if keys.is_empty() {
+            Ok(Self::new())
+        } else {
+            Ok(Self::pinned(keys))
+        }
+    }
+
+    /// Reject overlays without a signature
+    pub fn require_signature(mut self, require: bool) -> Self {
+        self.require_signature = require;
+        self
+    }
+
+    /// Add a trusted public key; once any key is trusted, others are rejected
+    pub fn trust(mut self, key: OverlayVerifyingKey) -> Self {
+        self.trusted_keys.push(key);
+        self
+    }
+
+    /// Whether unsigned overlays are rejected
+    pub fn requires_signature(&self) -> bool {
+        self.require_signature
+    }
+
+    /// Trusted public keys (empty means any key)
+    pub fn trusted_keys(&self) -> &[OverlayVerifyingKey] {
+        &self.trusted_keys
+    }
+
+    /// Check an overlay's signature block against this policy
+    pub fn check(
+        &self,
+        signature: Option<&OverlaySignature>,
+        config: &PackConfig,
+        assets: &[(String, Vec<u8>)],
+    ) -> PackResult<()> {
+        let Some(signature) = signature else {
+            if self.require_signature {
+                return Err(PackError::Signature(
+                    "Overlay is not signed, but this runtime requires a signature".to_string(),
+                ));
+            }
+            return Ok(());
+        };
+
+        // The public key in the block comes from the overlay; only pinned keys count
+        if self.trusted_keys.is_empty() {
+            if self.require_signature {
+                return Err(PackError::Signature(
+                    "No trusted keys to verify the overlay signature against".to_string(),
+                ));
+            }
+            return Ok(());
+        }
+        let key = self
+            .trusted_keys
+            .iter()
+            .find(|key| key.to_hex().eq_ignore_ascii_case(&signature.public_key))
+            .ok_or_else(|| {
+                PackError::Signature(format!(
+                    "Overlay is signed by untrusted key {}",
+                    signature.key_id
+                ))
+            })?;
+        key.verify(signature, config, assets)
+    }
+}
+
+/// Pin `keys` in the runtime executable at `exe_path`
+///
+/// Writes them into the executable's trusted-key slot, which lies outside
+/// any overlay, so the runtime only starts overlays signed by one of them.
+pub fn pin_runtime_keys(exe_path: &Path, keys: &[OverlayVerifyingKey]) -> PackResult<()> {
+    if keys.is_empty() || keys.len() > MAX_PINNED_KEYS {
+        return Err(PackError::Signature(format!(
+            "Expected 1-{} keys to pin, got {}",
+            MAX_PINNED_KEYS,
+            keys.len()
+        )));
+    }
+    let mut file = fs::File::options().read(true).write(true).open(exe_path)?;
+    let offset = locate_key_slot(exe_path, &mut file)?.ok_or_else(|| {
+        PackError::Signature(format!(
+            "{} has no trusted key slot; pack with a runtime built from this version",
+            exe_path.display()
+        ))
+    })?;
+
+    let mut tail = Vec::with_capacity(KEY_SLOT_LEN - KEY_SLOT_MARKER.len());
+    tail.push(keys.len() as u8);
+    for key in keys {
+        tail.extend_from_slice(key.inner.as_bytes());
+    }
+    tail.resize(KEY_SLOT_LEN - KEY_SLOT_MARKER.len(), 0);
+
+    file.seek(SeekFrom::Start(offset + KEY_SLOT_MARKER.len() as u64))?;
+    file.write_all(&tail)?;
+    file.sync_all()?;
+    Ok(())
+}
+
+/// Keys pinned in the runtime executable at `exe_path` (empty without a slot)
+///
+/// Only the runtime part of the file (before any overlay) is read.
+pub fn pinned_runtime_keys(exe_path: &Path) -> PackResult<Vec<OverlayVerifyingKey>> {
+    let mut file = fs::File::open(exe_path)?;
+    let Some(offset) = locate_key_slot(exe_path, &mut file)? else {
+        return Ok(Vec::new());
+    };
+    let mut slot = [0u8; KEY_SLOT_LEN];
+    file.seek(SeekFrom::Start(offset))?;
+    file.read_exact(&mut slot)?;
+    slot_keys(&slot)
+}
+
+/// Keys pinned in runtime executable bytes (without an overlay)
+pub(crate) fn pinned_keys_in(name: &str, runtime: &[u8]) -> PackResult<Vec<OverlayVerifyingKey>> {
+    match find_key_slot(name, runtime, runtime.len() as u64)? {
+        Some(offset) => {
+            let offset = offset as usize;
+            slot_keys(&runtime[offset..offset + KEY_SLOT_LEN])
+        }
+        None => Ok(Vec::new()),
+    }
+}
+
+/// An empty trusted-key slot, as compiled into every runtime
+pub fn empty_key_slot() -> Vec<u8> {
+    let mut slot = runtime_key_slot().to_vec();
+    slot[KEY_SLOT_MARKER.len()..].fill(0);
+    slot
+}
+
+/// This runtime's key slot
+///
+/// Read through a volatile load: the static is patched in the executable
+/// file, so its compile-time value must not be folded in.
+fn runtime_key_slot() -> [u8; KEY_SLOT_LEN] {
+    // SAFETY: `KEY_SLOT` is a valid, aligned, initialized static
+    unsafe { std::ptr::read_volatile(&KEY_SLOT) }
+}
+
+/// Offset of the key slot in the runtime part of `file`, if any
+///
+/// The overlay is skipped: its assets may contain other executables, and
+/// with them other key slots.
+fn locate_key_slot(exe_path: &Path, file: &mut fs::File) -> PackResult<Option<u64>> {
+    let runtime_len = match OverlayReader::get_original_size(exe_path)? {
+        Some(len) => len,
+        None => file.metadata()?.len(),
+    };
+    file.seek(SeekFrom::Start(0))?;
+    find_key_slot(&exe_path.display().to_string(), &mut *file, runtime_len)
+}
+
+/// Offset of the key slot in the first `len` bytes of `reader`, if any
+///
+/// Reads in chunks, so large runtimes are never loaded whole. The marker is
+/// taken from this runtime's slot rather than a separate constant, so it
+/// occurs only once in an executable.
+fn find_key_slot(name: &str, reader: impl Read, len: u64) -> PackResult<Option<u64>> {
+    let slot = runtime_key_slot();
+    let marker = &slot[..KEY_SLOT_MARKER.len()];
+    let mut reader = reader.take(len);
+    let mut buf = vec![0u8; KEY_SLOT_SCAN_CHUNK];
+    // `buf[..filled]` starts at `start` in the file; the tail of each chunk
+    // is carried over so markers across chunk boundaries are found
+    let (mut start, mut filled) = (0u64, 0usize);
+    let mut found = None;
+    loop {
+        let read = reader.read(&mut buf[filled..])?;
+        if read == 0 {
+            return Ok(found);
+        }
+        filled += read;
+        for (i, _) in buf[..filled]
+            .windows(marker.len())
+            .enumerate()
+            .filter(|(_, window)| *window == marker)
+        {
+            let offset = start + i as u64;
+            if offset + KEY_SLOT_LEN as u64 > len {
+                continue;
+            }
+            if found.replace(offset).is_some() {
+                return Err(PackError::Signature(format!(
+                    "{} has more than one trusted key slot",
+                    name
+                )));
+            }
+        }
+        let carry = filled.min(marker.len() - 1);
+        buf.copy_within(filled - carry..filled, 0);
+        start += (filled - carry) as u64;
+        filled = carry;
+    }
+}
+
+/// Parse the keys stored in a key slot
+fn slot_keys(slot: &[u8]) -> PackResult<Vec<OverlayVerifyingKey>> {
+    let count = slot[KEY_SLOT_MARKER.len()] as usize;
+    if count > MAX_PINNED_KEYS {
+        return Err(PackError::Signature(format!(
+            "Corrupt trusted key slot ({} keys)",
+            count
+        )));
+    }
+    slot[KEY_SLOT_MARKER.len() + 1..]
+        .chunks_exact(32)
+        .take(count)
+        .map(|bytes| {
+            let bytes: &[u8; 32] = bytes.try_into().expect("chunks are 32 bytes");
+            VerifyingKey::from_bytes(bytes)
+                .map(|inner| OverlayVerifyingKey { inner })
+                .map_err(|e| PackError::Signature(format!("Invalid pinned key: {}", e)))
+        })
+        .collect()
+}
+
+/// Parse a comma-separated list of hex public keys
+fn parse_key_list(keys: &str) -> PackResult<Vec<OverlayVerifyingKey>> {
+    keys.split(',')
+        .map(str::trim)
+        .filter(|key| !key.is_empty())
+        .map(OverlayVerifyingKey::from_hex)
+        .collect()
+}
+
+/// BLAKE3 hash of `config` serialized with sorted keys
+pub(crate) fn config_digest(config: &PackConfig) -> PackResult<blake3::Hash> {
+    // Round-trip through Value so map keys are emitted in sorted order
+    let json = serde_json::to_vec(&serde_json::to_value(config)?)?;
+    Ok(blake3::hash(&json))
+}
+
+/// Message covered by the signature
+///
+/// `domain || blake3(config JSON) || blake3(asset table)`, where the config is
+/// serialized with sorted keys and the asset table lists, in path order, each
+/// asset's path, a NUL separator, its length (u64 LE) and its BLAKE3 hash.
+fn signed_message(config: &PackConfig, assets: &[(String, Vec<u8>)]) -> PackResult<Vec<u8>> {
+    let mut sorted: Vec<_> = assets.iter().collect();
+    sorted.sort_by(|a, b| a.0.cmp(&b.0));
+    let mut table = blake3::Hasher::new();
+    for (path, content) in sorted {
+        table.update(path.as_bytes());
+        table.update(&[0]);
+        table.update(&(content.len() as u64).to_le_bytes());
+        table.update(blake3::hash(content).as_bytes());
+    }
+
+    let mut message = Vec::with_capacity(SIGNATURE_DOMAIN.len() + 64);
+    message.extend_from_slice(SIGNATURE_DOMAIN);
+    message.extend_from_slice(config_digest(config)?.as_bytes());
+    message.extend_from_slice(table.finalize().as_bytes());
+    Ok(message)
+}
+
+/// Drop blank and `#` comment lines from a key file
+fn strip_comments(content: &str) -> String {
+    content
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .collect()
+}
+
+fn encode_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+fn decode_hex<const N: usize>(hex: &str) -> PackResult<[u8; N]> {
+    if hex.len() != N * 2 || !hex.is_ascii() {
+        return Err(PackError::Signature(format!(
+            "Expected {} hex characters, got {}",
+            N * 2,
+            hex.len()
+        )));
+    }
+    let mut out = [0u8; N];
+    for (i, byte) in out.iter_mut().enumerate() {
+        *byte = u8::from_str_radix(&hex[i * 2..i * 2 + 2], 16)
+            .map_err(|_| PackError::Signature(format!("Invalid hex: {}", hex)))?;
+    }
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assets() -> Vec<(String, Vec<u8>)> {
+        vec![
+            ("index.html".to_string(), b"<html></html>".to_vec()),
+            ("app.js".to_string(), b"console.log(1)".to_vec()),
+        ]
+    }
+
+    #[test]
+    fn hex_roundtrip() {
+        let bytes = [0u8, 1, 0xab, 0xff];
+        assert_eq!(encode_hex(&bytes), "0001abff");
+        assert_eq!(decode_hex::<4>("0001abff").unwrap(), bytes);
+        assert!(decode_hex::<4>("0001ab").is_err());
+        assert!(decode_hex::<2>("zz00").is_err());
+    }
+
+    #[test]
+    fn signed_message_ignores_asset_order() {
+        let config = PackConfig::url("https://example.com");
+        let mut reversed = assets();
+        reversed.reverse();
+        assert_eq!(
+            signed_message(&config, &assets()).unwrap(),
+            signed_message(&config, &reversed).unwrap()
+        );
+    }
+
+    #[test]
+    fn strip_comments_keeps_key_material() {
+        let content = "# comment\n\n  abcd  \n# other\nef\n";
+        assert_eq!(strip_comments(content), "abcdef");
+    }
+
+    #[test]
+    fn key_list_parsing() {
+        let a = OverlaySigningKey::from_seed(&[1; 32]).verifying_key();
+        let b = OverlaySigningKey::from_seed(&[2; 32]).verifying_key();
+        let list = format!(" {} ,{},", a.to_hex(), b.to_hex());
+        assert_eq!(parse_key_list(&list).unwrap(), vec![a, b]);
+        assert!(parse_key_list("nothex").is_err());
+    }
+
+    #[test]
+    fn runtime_slot_is_empty_until_packed() {
+        let slot = runtime_key_slot();
+        assert_eq!(&slot[..KEY_SLOT_MARKER.len()], &KEY_SLOT_MARKER);
+        assert!(slot_keys(&slot).unwrap().is_empty());
+        assert_eq!(empty_key_slot(), slot.to_vec());
+    }
+
+    #[test]
+    fn corrupt_slot_is_rejected() {
+        let mut slot = empty_key_slot();
+        slot[KEY_SLOT_MARKER.len()] = MAX_PINNED_KEYS as u8 + 1;
+        assert!(slot_keys(&slot).is_err());
+    }
+
+    #[test]
+    fn key_slot_found_across_scan_chunks() {
+        for padding in [0, 1, KEY_SLOT_SCAN_CHUNK - 10, 2 * KEY_SLOT_SCAN_CHUNK + 3] {
+            let mut bytes = vec![0u8; padding];
+            bytes.extend(empty_key_slot());
+            bytes.extend([0u8; 16]);
+            let found = find_key_slot("runtime", &bytes[..], bytes.len() as u64).unwrap();
+            assert_eq!(found, Some(padding as u64));
+        }
+
+        // A slot that does not fit within the scanned length is ignored
+        let slot = empty_key_slot();
+        assert_eq!(
+            find_key_slot("runtime", &slot[..], slot.len() as u64 - 1).unwrap(),
+            None
+        );
+
+        let mut twice = empty_key_slot();
+        twice.extend(empty_key_slot());
+        assert!(find_key_slot("runtime", &twice[..], twice.len() as u64).is_err());
+    }
+
+    #[test]
+    fn debug_does_not_leak_seed() {
+        let key = OverlaySigningKey::from_seed(&[7; 32]);
+        assert!(!format!("{:?}", key).contains(&key.to_hex()));
+    }
+}
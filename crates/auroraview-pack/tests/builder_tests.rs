@@ -14,6 +14,7 @@ use auroraview_pack::builder::{
     IOSBuilder, LinuxBuilder, MacBuilder, WeChatBuilder, WebBuilder, WinBuilder,
 };
 use auroraview_pack::PackError;
+use rstest::rstest;
 use tempfile::tempdir;
 
 fn minimal_build_config(platform: &str, output_dir: PathBuf) -> BuildConfig {
@@ -228,6 +229,238 @@ fn linux_builder_capabilities() {
     assert!(!caps.is_empty());
 }
 
+/// Split an `ar` archive into (name, data) members
+fn parse_ar(data: &[u8]) -> Vec<(String, Vec<u8>)> {
+    assert!(data.starts_with(b"!<arch>\n"));
+    let mut members = Vec::new();
+    let mut pos = 8;
+    while pos + 60 <= data.len() {
+        let header = &data[pos..pos + 60];
+        let name = String::from_utf8_lossy(&header[..16]).trim().to_string();
+        let size: usize = String::from_utf8_lossy(&header[48..58])
+            .trim()
+            .parse()
+            .unwrap();
+        pos += 60;
+        members.push((name, data[pos..pos + size].to_vec()));
+        pos += size + size % 2;
+    }
+    members
+}
+
+fn read_tar_gz(data: &[u8]) -> Vec<(String, Vec<u8>)> {
+    use std::io::Read;
+    let mut archive = tar::Archive::new(flate2::read::GzDecoder::new(data));
+    archive
+        .entries()
+        .unwrap()
+        .map(|e| {
+            let mut e = e.unwrap();
+            let path = String::from_utf8_lossy(&e.path_bytes()).to_string();
+            let mut content = Vec::new();
+            e.read_to_end(&mut content).unwrap();
+            (path, content)
+        })
+        .collect()
+}
+
+fn linux_build_context(temp: &std::path::Path, format: Option<&str>) -> BuildContext {
+    let frontend = temp.join("dist");
+    fs::create_dir_all(&frontend).unwrap();
+    fs::write(frontend.join("index.html"), "<html>linux</html>").unwrap();
+
+    let icon = temp.join("icon.png");
+    image::RgbaImage::from_pixel(64, 64, image::Rgba([10, 20, 30, 255]))
+        .save(&icon)
+        .unwrap();
+
+    let output_dir = temp.join("out");
+    let mut config = minimal_build_config("linux", output_dir.clone());
+    config.app.version = "2.1.0".to_string();
+    config.app.description = Some("Review tool".to_string());
+    config.app.icon = Some(icon);
+    config.target.format = format.map(str::to_string);
+    config.frontend = Some(FrontendConfig::Path { path: frontend });
+    BuildContext::new(config, output_dir)
+}
+
+fn fake_base_executable(temp: &std::path::Path) -> PathBuf {
+    let base = temp.join("auroraview-base");
+    fs::write(&base, b"\x7fELF fake runtime").unwrap();
+    base
+}
+
+#[test]
+fn linux_builder_build_writes_all_formats() {
+    let temp = tempdir().unwrap();
+    let mut ctx = linux_build_context(temp.path(), None);
+    let builder = LinuxBuilder::new().base_executable(fake_base_executable(temp.path()));
+
+    let output = builder.build(&mut ctx).unwrap();
+    assert_eq!(output.format, "linux");
+    assert!(output.size > 0);
+    for key in ["appimage", "deb", "rpm"] {
+        let path = PathBuf::from(output.info.get(key).unwrap());
+        assert!(
+            path.exists(),
+            "{} artifact missing: {}",
+            key,
+            path.display()
+        );
+    }
+
+    builder.cleanup(&ctx).unwrap();
+    assert!(!ctx.temp_dir.exists());
+}
+
+#[test]
+fn linux_builder_appdir_layout_contains_packed_binary() {
+    let temp = tempdir().unwrap();
+    let mut ctx = linux_build_context(temp.path(), Some("appimage"));
+    let output = LinuxBuilder::new()
+        .base_executable(fake_base_executable(temp.path()))
+        .build(&mut ctx)
+        .unwrap();
+
+    let appdir = temp.path().join("out").join("auroraview-test.AppDir");
+    assert_eq!(output.path, appdir);
+    assert_eq!(output.format, "appimage");
+
+    let app_run = fs::read_to_string(appdir.join("AppRun")).unwrap();
+    assert!(app_run.contains("usr/bin/auroraview-test"));
+    assert!(appdir.join("auroraview-test.desktop").exists());
+    assert!(appdir.join("auroraview-test.png").exists());
+    assert!(appdir.join(".DirIcon").exists());
+    assert!(appdir
+        .join("usr/share/icons/hicolor/128x128/apps/auroraview-test.png")
+        .exists());
+
+    let binary = appdir.join("usr/bin/auroraview-test");
+    let overlay = auroraview_pack::OverlayReader::read(&binary)
+        .unwrap()
+        .expect("packed binary should carry an overlay");
+    assert!(overlay
+        .assets
+        .iter()
+        .any(|(path, content)| path.ends_with("index.html") && content == b"<html>linux</html>"));
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        let mode = fs::metadata(&binary).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o755);
+    }
+}
+
+#[test]
+fn linux_builder_deb_has_control_md5sums_and_data() {
+    let temp = tempdir().unwrap();
+    let mut ctx = linux_build_context(temp.path(), Some("deb"));
+    let output = LinuxBuilder::new()
+        .base_executable(fake_base_executable(temp.path()))
+        .release("3")
+        .build(&mut ctx)
+        .unwrap();
+
+    assert_eq!(output.format, "deb");
+    let file_name = output
+        .path
+        .file_name()
+        .unwrap()
+        .to_string_lossy()
+        .to_string();
+    assert!(file_name.starts_with("auroraview-test_2.1.0-3_"));
+    assert!(file_name.ends_with(".deb"));
+
+    let members = parse_ar(&fs::read(&output.path).unwrap());
+    let names: Vec<&str> = members.iter().map(|(n, _)| n.as_str()).collect();
+    assert_eq!(
+        names,
+        vec!["debian-binary", "control.tar.gz", "data.tar.gz"]
+    );
+    assert_eq!(members[0].1, b"2.0\n");
+
+    let control = read_tar_gz(&members[1].1);
+    let control_file = control
+        .iter()
+        .find(|(p, _)| p == "control")
+        .map(|(_, c)| String::from_utf8(c.clone()).unwrap())
+        .unwrap();
+    assert!(control_file.contains("Package: auroraview-test\n"));
+    assert!(control_file.contains("Version: 2.1.0-3\n"));
+    assert!(control_file.contains("Description: Review tool\n"));
+    let md5sums = control
+        .iter()
+        .find(|(p, _)| p == "md5sums")
+        .map(|(_, c)| String::from_utf8(c.clone()).unwrap())
+        .unwrap();
+    assert!(md5sums.contains("  usr/bin/auroraview-test\n"));
+
+    let data = read_tar_gz(&members[2].1);
+    let paths: Vec<&str> = data.iter().map(|(p, _)| p.as_str()).collect();
+    assert!(paths.contains(&"usr/bin/auroraview-test"));
+    assert!(paths.contains(&"usr/share/applications/auroraview-test.desktop"));
+    assert!(paths.contains(&"usr/share/icons/hicolor/256x256/apps/auroraview-test.png"));
+}
+
+#[test]
+fn linux_builder_rpm_has_lead_headers_and_payload() {
+    let temp = tempdir().unwrap();
+    let mut ctx = linux_build_context(temp.path(), Some("rpm"));
+    let output = LinuxBuilder::new()
+        .base_executable(fake_base_executable(temp.path()))
+        .build(&mut ctx)
+        .unwrap();
+
+    assert_eq!(output.format, "rpm");
+    let file_name = output
+        .path
+        .file_name()
+        .unwrap()
+        .to_string_lossy()
+        .to_string();
+    assert!(file_name.starts_with("auroraview-test-2.1.0-1."));
+
+    let rpm = fs::read(&output.path).unwrap();
+    assert_eq!(output.size, rpm.len() as u64);
+    assert_eq!(&rpm[..4], &[0xed, 0xab, 0xee, 0xdb]);
+
+    // Signature header follows the 96-byte lead and is padded to 8 bytes
+    let header_len = |offset: usize| {
+        let nindex = u32::from_be_bytes(rpm[offset + 8..offset + 12].try_into().unwrap());
+        let hsize = u32::from_be_bytes(rpm[offset + 12..offset + 16].try_into().unwrap());
+        16 + nindex as usize * 16 + hsize as usize
+    };
+    assert_eq!(&rpm[96..100], &[0x8e, 0xad, 0xe8, 0x01]);
+    let sig_len = header_len(96).next_multiple_of(8);
+    let main = 96 + sig_len;
+    assert_eq!(&rpm[main..main + 4], &[0x8e, 0xad, 0xe8, 0x01]);
+
+    let payload = &rpm[main + header_len(main)..];
+    assert_eq!(&payload[..2], &[0x1f, 0x8b], "payload should be gzip");
+
+    use std::io::Read;
+    let mut cpio = Vec::new();
+    flate2::read::GzDecoder::new(payload)
+        .read_to_end(&mut cpio)
+        .unwrap();
+    assert!(cpio.starts_with(b"070701"));
+    let cpio_text = String::from_utf8_lossy(&cpio);
+    assert!(cpio_text.contains("./usr/bin/auroraview-test"));
+    assert!(cpio_text.contains("TRAILER!!!"));
+}
+
+#[test]
+fn linux_builder_validate_rejects_missing_icon() {
+    let temp = tempdir().unwrap();
+    let mut config = minimal_build_config("linux", temp.path().join("out"));
+    config.app.icon = Some(temp.path().join("missing.png"));
+    let ctx = BuildContext::new(config, temp.path().join("out"));
+
+    let err = LinuxBuilder::new().validate(&ctx).unwrap_err();
+    assert!(matches!(err, PackError::AssetNotFound(path) if path.ends_with("missing.png")));
+}
+
 // ─────────────────────────────────────────────────────────────
 // IOSBuilder
 // ─────────────────────────────────────────────────────────────
@@ -307,6 +540,168 @@ fn web_builder_is_always_available() {
     assert!(b.is_available());
 }
 
+fn web_build_context(temp: &std::path::Path, platform: &str) -> BuildContext {
+    let frontend = temp.join("dist");
+    fs::create_dir_all(frontend.join("docs")).unwrap();
+    fs::write(
+        frontend.join("index.html"),
+        "<html><head><title>Demo</title></head><body></body></html>",
+    )
+    .unwrap();
+    fs::write(frontend.join("docs/help.html"), "<head></head>help").unwrap();
+    fs::write(frontend.join("app.js"), "window.auroraview.call('ping')").unwrap();
+
+    let icon = temp.join("icon.png");
+    image::RgbaImage::from_pixel(64, 64, image::Rgba([10, 20, 30, 255]))
+        .save(&icon)
+        .unwrap();
+
+    let output_dir = temp.join("out");
+    let mut config = minimal_build_config(platform, output_dir.clone());
+    config.app.description = Some("Review tool".to_string());
+    config.app.icon = Some(icon);
+    config.window.title = Some("AuroraView Review".to_string());
+    config.frontend = Some(FrontendConfig::Path { path: frontend });
+    BuildContext::new(config, output_dir)
+}
+
+#[test]
+fn web_builder_output_format_follows_target() {
+    let temp = tempdir().unwrap();
+    let mut ctx = minimal_build_context("web", temp.path().to_path_buf());
+    assert_eq!(WebBuilder::output_format(&ctx), OutputFormat::WebStatic);
+    ctx.config.target.format = Some("pwa".to_string());
+    assert_eq!(WebBuilder::output_format(&ctx), OutputFormat::WebPwa);
+    ctx.config.target.format = None;
+    ctx.config.target.platform = "pwa".to_string();
+    assert_eq!(WebBuilder::output_format(&ctx), OutputFormat::WebPwa);
+}
+
+#[test]
+fn web_builder_static_build_writes_site_manifest_and_shim() {
+    let temp = tempdir().unwrap();
+    let mut ctx = web_build_context(temp.path(), "web");
+    ctx.add_asset("data/config.json", b"{}".to_vec());
+
+    let output = WebBuilder::new().build(&mut ctx).unwrap();
+    assert_eq!(output.format, "static");
+    let site = temp.path().join("out/auroraview-test");
+    assert_eq!(output.path, site);
+    assert!(!output.info.contains_key("service_worker"));
+
+    assert!(site.join("app.js").is_file());
+    assert!(site.join("data/config.json").is_file());
+    assert!(site.join("auroraview-shim.js").is_file());
+    assert!(!site.join("sw.js").exists());
+
+    let manifest: serde_json::Value =
+        serde_json::from_slice(&fs::read(site.join("manifest.webmanifest")).unwrap()).unwrap();
+    assert_eq!(manifest["name"], "AuroraView Review");
+    assert_eq!(manifest["short_name"], "AuroraView");
+    assert_eq!(manifest["description"], "Review tool");
+    assert_eq!(manifest["display"], "standalone");
+    let icons = manifest["icons"].as_array().unwrap();
+    assert_eq!(icons.len(), 2);
+    for icon in icons {
+        let src = icon["src"].as_str().unwrap();
+        let size = icon["sizes"].as_str().unwrap().split('x').next().unwrap();
+        let img = image::open(site.join(src)).unwrap();
+        assert_eq!(img.width().to_string(), size);
+    }
+
+    let index = fs::read_to_string(site.join("index.html")).unwrap();
+    assert!(index.contains("<link rel=\"manifest\" href=\"manifest.webmanifest\">"));
+    assert!(index.contains("<script src=\"auroraview-shim.js\"></script>"));
+    assert!(index.find("auroraview-shim.js").unwrap() < index.find("<title>").unwrap());
+    assert!(!index.contains("serviceWorker"));
+
+    let help = fs::read_to_string(site.join("docs/help.html")).unwrap();
+    assert!(help.contains("src=\"../auroraview-shim.js\""));
+}
+
+#[test]
+fn web_builder_pwa_build_precaches_every_file() {
+    let temp = tempdir().unwrap();
+    let mut ctx = web_build_context(temp.path(), "pwa");
+
+    let output = WebBuilder::new().build(&mut ctx).unwrap();
+    assert_eq!(output.format, "pwa");
+    let site = output.path.clone();
+    assert_eq!(
+        output.info.get("service_worker").map(PathBuf::from),
+        Some(site.join("sw.js"))
+    );
+
+    let sw = fs::read_to_string(site.join("sw.js")).unwrap();
+    for path in [
+        "./",
+        "./index.html",
+        "./app.js",
+        "./docs/help.html",
+        "./auroraview-shim.js",
+        "./manifest.webmanifest",
+        "./icons/icon-192.png",
+        "./icons/icon-512.png",
+    ] {
+        assert!(sw.contains(&format!("\"{}\"", path)), "missing {}", path);
+    }
+    assert!(!sw.contains("\"./sw.js\""));
+
+    let index = fs::read_to_string(site.join("index.html")).unwrap();
+    assert!(index.contains("navigator.serviceWorker.register('sw.js')"));
+}
+
+#[test]
+fn web_builder_service_worker_can_be_forced_for_static() {
+    let temp = tempdir().unwrap();
+    let mut ctx = web_build_context(temp.path(), "static");
+    let output = WebBuilder::new()
+        .service_worker(true)
+        .build(&mut ctx)
+        .unwrap();
+    assert_eq!(output.format, "static");
+    assert!(output.path.join("sw.js").is_file());
+}
+
+#[test]
+fn web_builder_validate_rejects_url_frontend() {
+    let temp = tempdir().unwrap();
+    let mut ctx = minimal_build_context("web", temp.path().to_path_buf());
+    ctx.config.frontend = Some(FrontendConfig::Url {
+        url: "https://example.com".to_string(),
+    });
+    assert!(matches!(
+        WebBuilder::new().validate(&ctx),
+        Err(PackError::Config(_))
+    ));
+}
+
+#[rstest]
+#[case("..")]
+#[case(".")]
+#[case("../victim")]
+#[case("nested/site")]
+#[case("site/")]
+#[case("/tmp/victim")]
+fn web_builder_rejects_output_name_outside_output_dir(#[case] name: &str) {
+    let temp = tempdir().unwrap();
+    let victim = temp.path().join("victim");
+    fs::create_dir_all(&victim).unwrap();
+    fs::write(victim.join("keep.txt"), "keep").unwrap();
+
+    let mut ctx = web_build_context(temp.path(), "web");
+    ctx.config.target.output_name = Some(name.to_string());
+    assert!(matches!(
+        WebBuilder::new().validate(&ctx),
+        Err(PackError::Config(_))
+    ));
+    assert!(matches!(
+        WebBuilder::new().build(&mut ctx),
+        Err(PackError::Config(_))
+    ));
+    assert!(victim.join("keep.txt").is_file());
+}
+
 // ─────────────────────────────────────────────────────────────
 // BuilderRegistry
 // ─────────────────────────────────────────────────────────────
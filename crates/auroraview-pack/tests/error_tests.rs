@@ -96,6 +96,18 @@ fn display_vx_ensure_failed() {
     );
 }
 
+#[rstest]
+fn display_patch() {
+    let e = PackError::Patch("base runtime hash mismatch".to_string());
+    assert_eq!(e.to_string(), "Patch error: base runtime hash mismatch");
+}
+
+#[rstest]
+fn display_signature() {
+    let e = PackError::Signature("overlay is not signed".to_string());
+    assert_eq!(e.to_string(), "Signature error: overlay is not signed");
+}
+
 // ============================================================================
 // Debug trait
 // ============================================================================
@@ -210,6 +222,20 @@ fn clone_vx_ensure_failed() {
     assert_eq!(c.to_string(), e.to_string());
 }
 
+#[rstest]
+fn clone_patch() {
+    let e = PackError::Patch("p".to_string());
+    let c = e.clone();
+    assert_eq!(c.to_string(), e.to_string());
+}
+
+#[rstest]
+fn clone_signature() {
+    let e = PackError::Signature("s".to_string());
+    let c = e.clone();
+    assert_eq!(c.to_string(), e.to_string());
+}
+
 /// Io variant clones to Config (documented behavior in Clone impl)
 #[rstest]
 fn clone_io_becomes_config() {
@@ -331,6 +357,8 @@ fn pack_error_is_send_sync() {
 #[case(PackError::Download("d".to_string()), "d")]
 #[case(PackError::ResourceEdit("r".to_string()), "r")]
 #[case(PackError::VxEnsureFailed("v".to_string()), "v")]
+#[case(PackError::Patch("p".to_string()), "p")]
+#[case(PackError::Signature("s".to_string()), "s")]
 fn string_variant_message_in_display(#[case] e: PackError, #[case] fragment: &str) {
     assert!(e.to_string().contains(fragment));
 }
@@ -0,0 +1,280 @@
+//! Tests for delta patches between packed executables
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use auroraview_pack::{
+    OverlayData, OverlayReader, OverlayWriter, PackConfig, PackError, Patch, PatchAction,
+};
+use tempfile::{tempdir, TempDir};
+
+fn packed_exe(
+    dir: &Path,
+    name: &str,
+    runtime: &[u8],
+    title: &str,
+    assets: &[(&str, &[u8])],
+) -> PathBuf {
+    let path = dir.join(name);
+    fs::write(&path, runtime).unwrap();
+    let mut data = OverlayData::new(PackConfig::url("https://example.com").with_title(title));
+    for (asset, content) in assets {
+        data.add_asset(*asset, content.to_vec());
+    }
+    OverlayWriter::write_with_level(&path, &data, 1).unwrap();
+    path
+}
+
+/// Incompressible stand-in for a bundled runtime/site-packages blob
+fn large_asset() -> Vec<u8> {
+    let mut state = 0x2545_f491_4f6c_dd1du64;
+    (0..200_000)
+        .map(|_| {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            state as u8
+        })
+        .collect()
+}
+
+/// v1 and v2 share the runtime and `python/big.bin`; `app.js` changes,
+/// `new.css` is added and `old.css` is removed.
+fn versions() -> (TempDir, PathBuf, PathBuf) {
+    let temp = tempdir().unwrap();
+    let big = large_asset();
+    let v1 = packed_exe(
+        temp.path(),
+        "v1.exe",
+        b"runtime-1",
+        "V1",
+        &[
+            ("index.html", b"<html>v1</html>"),
+            ("app.js", b"console.log(1)"),
+            ("old.css", b"body{}"),
+            ("python/big.bin", &big),
+        ],
+    );
+    let v2 = packed_exe(
+        temp.path(),
+        "v2.exe",
+        b"runtime-1",
+        "V2",
+        &[
+            ("index.html", b"<html>v1</html>"),
+            ("app.js", b"console.log(2)"),
+            ("new.css", b"main{}"),
+            ("python/big.bin", &big),
+        ],
+    );
+    (temp, v1, v2)
+}
+
+#[test]
+fn patch_create_records_asset_table_diff() {
+    let (_temp, v1, v2) = versions();
+    let patch = Patch::create(&v1, &v2).unwrap();
+
+    let actions: Vec<(&str, PatchAction)> = patch
+        .manifest
+        .assets
+        .iter()
+        .map(|a| (a.path.as_str(), a.action))
+        .collect();
+    assert_eq!(
+        actions,
+        vec![
+            ("index.html", PatchAction::Keep),
+            ("app.js", PatchAction::Replace),
+            ("new.css", PatchAction::Add),
+            ("python/big.bin", PatchAction::Keep),
+        ]
+    );
+    assert_eq!(patch.manifest.removed, vec!["old.css".to_string()]);
+    assert!(patch.runtime.is_none());
+
+    let stats = patch.stats();
+    assert_eq!(stats.kept, 2);
+    assert_eq!(stats.added, 1);
+    assert_eq!(stats.replaced, 1);
+    assert_eq!(stats.removed, 1);
+    assert!(!stats.runtime_changed);
+    assert_eq!(
+        stats.payload_bytes,
+        (b"console.log(2)".len() + b"main{}".len()) as u64
+    );
+}
+
+#[test]
+fn patch_is_much_smaller_than_target() {
+    let (temp, v1, v2) = versions();
+    let patch = Patch::create(&v1, &v2).unwrap();
+    let patch_path = temp.path().join("update.avpt");
+    let size = patch.write(&patch_path, 19).unwrap();
+    assert!(size < fs::metadata(&v2).unwrap().len() / 4);
+}
+
+#[test]
+fn patch_roundtrips_through_bytes() {
+    let (_temp, v1, v2) = versions();
+    let patch = Patch::create(&v1, &v2).unwrap();
+    let parsed = Patch::from_bytes(&patch.to_bytes(3).unwrap()).unwrap();
+    assert_eq!(parsed.manifest.assets, patch.manifest.assets);
+    assert_eq!(parsed.manifest.base, patch.manifest.base);
+    assert_eq!(parsed.manifest.target, patch.manifest.target);
+    assert_eq!(parsed.blobs, patch.blobs);
+    assert_eq!(parsed.runtime, patch.runtime);
+}
+
+#[test]
+fn patch_apply_in_place_produces_target() {
+    let (temp, v1, v2) = versions();
+    let patch_path = temp.path().join("update.avpt");
+    Patch::create(&v1, &v2)
+        .unwrap()
+        .write(&patch_path, 3)
+        .unwrap();
+
+    let patch = Patch::read(&patch_path).unwrap();
+    patch.verify_base(&v1).unwrap();
+    let stats = patch.apply(&v1).unwrap();
+    assert_eq!(stats.replaced, 1);
+
+    let mut patched = OverlayReader::read(&v1).unwrap().unwrap();
+    let mut expected = OverlayReader::read(&v2).unwrap().unwrap();
+    assert_eq!(patched.config.window.title, "V2");
+    assert_eq!(patched.assets, expected.assets);
+    assert_eq!(
+        patched.compute_content_hash(),
+        expected.compute_content_hash()
+    );
+    assert_eq!(
+        OverlayReader::get_original_size(&v1).unwrap(),
+        Some(b"runtime-1".len() as u64)
+    );
+
+    // No staging or backup files left behind
+    assert!(!temp.path().join("v1.exe.patching").exists());
+    assert!(!temp.path().join("v1.exe.old").exists());
+}
+
+#[test]
+fn patch_replaces_changed_runtime() {
+    let temp = tempdir().unwrap();
+    let v1 = packed_exe(temp.path(), "a.exe", b"runtime-1", "A", &[("a", b"1")]);
+    let v2 = packed_exe(
+        temp.path(),
+        "b.exe",
+        b"runtime-2-larger",
+        "A",
+        &[("a", b"1")],
+    );
+
+    let patch = Patch::create(&v1, &v2).unwrap();
+    assert!(patch.stats().runtime_changed);
+    assert_eq!(patch.runtime.as_deref(), Some(&b"runtime-2-larger"[..]));
+
+    let out = temp.path().join("patched.exe");
+    patch.apply_to(&v1, &out).unwrap();
+    assert!(fs::read(&out).unwrap().starts_with(b"runtime-2-larger"));
+    assert_eq!(
+        OverlayReader::get_original_size(&out).unwrap(),
+        Some(b"runtime-2-larger".len() as u64)
+    );
+    assert_eq!(
+        OverlayReader::read(&out).unwrap().unwrap().assets,
+        OverlayReader::read(&v2).unwrap().unwrap().assets
+    );
+    // apply_to leaves the source untouched
+    assert!(fs::read(&v1).unwrap().starts_with(b"runtime-1"));
+}
+
+#[test]
+fn patch_rejects_wrong_base_without_modifying_it() {
+    let (temp, v1, v2) = versions();
+    let other = packed_exe(
+        temp.path(),
+        "other.exe",
+        b"runtime-1",
+        "V1",
+        &[("index.html", b"<html>other</html>")],
+    );
+    let before = fs::read(&other).unwrap();
+
+    let patch = Patch::create(&v1, &v2).unwrap();
+    let err = patch.apply(&other).unwrap_err();
+    assert!(matches!(err, PackError::Patch(ref m) if m.contains("base content hash mismatch")));
+    assert_eq!(fs::read(&other).unwrap(), before);
+    assert!(!temp.path().join("other.exe.patching").exists());
+}
+
+#[test]
+fn patch_rejects_base_with_other_config() {
+    let (temp, v1, v2) = versions();
+    let big = large_asset();
+    let other = packed_exe(
+        temp.path(),
+        "other.exe",
+        b"runtime-1",
+        "Other",
+        &[
+            ("index.html", b"<html>v1</html>"),
+            ("app.js", b"console.log(1)"),
+            ("old.css", b"body{}"),
+            ("python/big.bin", &big),
+        ],
+    );
+    let before = fs::read(&other).unwrap();
+
+    let patch = Patch::create(&v1, &v2).unwrap();
+    let err = patch.apply(&other).unwrap_err();
+    assert!(matches!(err, PackError::Patch(ref m) if m.contains("base config hash mismatch")));
+    assert_eq!(fs::read(&other).unwrap(), before);
+}
+
+#[test]
+fn patch_rejects_tampered_config() {
+    let (temp, v1, v2) = versions();
+    let mut patch = Patch::create(&v1, &v2).unwrap();
+    patch.manifest.config = PackConfig::url("https://evil.example").with_title("V2");
+
+    let before = fs::read(&v1).unwrap();
+    let err = patch.apply(&v1).unwrap_err();
+    assert!(matches!(err, PackError::Patch(ref m) if m.contains("target config hash mismatch")));
+    assert_eq!(fs::read(&v1).unwrap(), before);
+    assert!(!temp.path().join("v1.exe.patching").exists());
+}
+
+#[test]
+fn patch_rejects_tampered_blob() {
+    let (temp, v1, v2) = versions();
+    let mut patch = Patch::create(&v1, &v2).unwrap();
+    patch.blobs[0].1 = b"console.log('evil')".to_vec();
+
+    let before = fs::read(&v1).unwrap();
+    let err = patch.apply(&v1).unwrap_err();
+    assert!(matches!(err, PackError::Patch(ref m) if m.contains("Hash mismatch for 'app.js'")));
+    assert_eq!(fs::read(&v1).unwrap(), before);
+    assert!(!temp.path().join("v1.exe.patching").exists());
+}
+
+#[test]
+fn patch_create_requires_packed_executables() {
+    let temp = tempdir().unwrap();
+    let plain = temp.path().join("plain.exe");
+    fs::write(&plain, b"no overlay here").unwrap();
+    let packed = packed_exe(temp.path(), "p.exe", b"rt", "P", &[]);
+
+    assert!(matches!(
+        Patch::create(&plain, &packed),
+        Err(PackError::Patch(_))
+    ));
+}
+
+#[test]
+fn patch_read_rejects_non_patch_file() {
+    let temp = tempdir().unwrap();
+    let path = temp.path().join("bogus.avpt");
+    fs::write(&path, b"definitely not a patch file at all").unwrap();
+    assert!(matches!(Patch::read(&path), Err(PackError::Patch(_))));
+}
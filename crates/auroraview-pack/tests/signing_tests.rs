@@ -0,0 +1,367 @@
+//! Tests for signed overlays
+
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use auroraview_pack::{
+    empty_key_slot, pinned_runtime_keys, ApplyOptions, OverlayData, OverlayReader,
+    OverlaySigningKey, OverlayVerifyingKey, OverlayWriter, PackConfig, PackError, Patch,
+    SignaturePolicy, OVERLAY_MAGIC,
+};
+use tempfile::{tempdir, TempDir};
+
+fn key_file(dir: &Path, name: &str, seed: u8) -> (PathBuf, OverlayVerifyingKey) {
+    let key = OverlaySigningKey::from_seed(&[seed; 32]);
+    let path = dir.join(name);
+    key.write_to_file(&path).unwrap();
+    (path, key.verifying_key())
+}
+
+fn overlay(title: &str, signing_key: Option<&Path>) -> OverlayData {
+    let mut config = PackConfig::url("https://example.com").with_title(title);
+    config.signing_key = signing_key.map(Path::to_path_buf);
+    let mut data = OverlayData::new(config);
+    data.add_asset("index.html", b"<html>app</html>".to_vec());
+    data.add_asset("app.js", b"console.log(1)".to_vec());
+    data
+}
+
+/// A fake runtime with an empty trusted-key slot
+fn runtime() -> Vec<u8> {
+    let mut bytes = b"runtime".to_vec();
+    bytes.extend(empty_key_slot());
+    bytes
+}
+
+fn write_exe(dir: &Path, name: &str, data: &OverlayData) -> PathBuf {
+    write_on_runtime(dir, name, &runtime(), data)
+}
+
+/// Append `data` to a copy of `runtime` (e.g. one with pinned keys)
+fn write_on_runtime(dir: &Path, name: &str, runtime: &[u8], data: &OverlayData) -> PathBuf {
+    let path = dir.join(name);
+    fs::write(&path, runtime).unwrap();
+    OverlayWriter::write_with_level(&path, data, 1).unwrap();
+    path
+}
+
+/// Runtime part (before the overlay) of an executable written by `write_exe`
+fn runtime_of(exe: &Path) -> Vec<u8> {
+    fs::read(exe).unwrap()[..runtime().len()].to_vec()
+}
+
+fn signed_exe() -> (TempDir, PathBuf, OverlayVerifyingKey) {
+    let temp = tempdir().unwrap();
+    let (key_path, public) = key_file(temp.path(), "app.key", 1);
+    let exe = write_exe(temp.path(), "app.exe", &overlay("App", Some(&key_path)));
+    (temp, exe, public)
+}
+
+#[test]
+fn signed_overlay_roundtrip() {
+    let (_temp, exe, public) = signed_exe();
+
+    let data = OverlayReader::read(&exe).unwrap().unwrap();
+    let signature = data.signature.expect("overlay should be signed");
+    assert_eq!(signature.algorithm, "ed25519");
+    assert_eq!(signature.key_id, public.key_id());
+    assert_eq!(signature.public_key, public.to_hex());
+    // The key path is never embedded; the public key is pinned in the runtime
+    assert!(data.config.signing_key.is_none());
+    assert_eq!(pinned_runtime_keys(&exe).unwrap(), vec![public]);
+
+    let policy = SignaturePolicy::pinned(vec![public]);
+    assert!(OverlayReader::read_with_policy(&exe, &policy, None).is_ok());
+}
+
+#[test]
+fn swapped_assets_are_rejected() {
+    let (temp, exe, _public) = signed_exe();
+
+    let mut data = OverlayReader::read(&exe).unwrap().unwrap();
+    data.assets[1].1 = b"console.log('evil')".to_vec();
+    data.content_hash.clear();
+    let tampered = write_on_runtime(temp.path(), "tampered.exe", &runtime_of(&exe), &data);
+
+    let err = OverlayReader::read(&tampered).unwrap_err();
+    assert!(matches!(err, PackError::Signature(ref m) if m.contains("does not match")));
+}
+
+#[test]
+fn edited_config_is_rejected() {
+    let (temp, exe, _public) = signed_exe();
+
+    let mut data = OverlayReader::read(&exe).unwrap().unwrap();
+    data.config.window.title = "Evil".to_string();
+    let tampered = write_on_runtime(temp.path(), "tampered.exe", &runtime_of(&exe), &data);
+
+    assert!(matches!(
+        OverlayReader::read(&tampered),
+        Err(PackError::Signature(_))
+    ));
+}
+
+#[test]
+fn resigned_with_untrusted_key_is_rejected() {
+    let (temp, exe, public) = signed_exe();
+    let (other_key, _) = key_file(temp.path(), "other.key", 2);
+    let other = write_exe(temp.path(), "other.exe", &overlay("App", Some(&other_key)));
+
+    // Valid on its own, but not signed by the pinned key
+    assert!(OverlayReader::read(&other).is_ok());
+    let policy = SignaturePolicy::pinned(vec![public]);
+    let err = OverlayReader::read_with_policy(&other, &policy, None).unwrap_err();
+    assert!(matches!(err, PackError::Signature(ref m) if m.contains("untrusted key")));
+
+    // The overlay's own public key is never trusted by the signed runtime
+    let foreign = OverlayReader::read(&other).unwrap().unwrap();
+    let swapped = write_on_runtime(temp.path(), "swapped.exe", &runtime_of(&exe), &foreign);
+    let err = OverlayReader::read(&swapped).unwrap_err();
+    assert!(matches!(err, PackError::Signature(ref m) if m.contains("untrusted key")));
+}
+
+#[test]
+fn signed_runtime_refuses_unsigned_overlay() {
+    let (temp, exe, _public) = signed_exe();
+    let unsigned = write_on_runtime(
+        temp.path(),
+        "unsigned.exe",
+        &runtime_of(&exe),
+        &overlay("Evil", None),
+    );
+
+    let err = OverlayReader::read(&unsigned).unwrap_err();
+    assert!(matches!(err, PackError::Signature(ref m) if m.contains("not signed")));
+}
+
+#[test]
+fn unpinned_policy_ignores_embedded_key() {
+    let (temp, exe, _public) = signed_exe();
+    let mut data = OverlayReader::read(&exe).unwrap().unwrap();
+    data.config.window.title = "Evil".to_string();
+    let tampered = write_exe(temp.path(), "tampered.exe", &data);
+
+    // Nothing is pinned, so the block is not trusted either way
+    assert!(OverlayReader::read(&tampered).is_ok());
+    let policy = SignaturePolicy::new().require_signature(true);
+    let err = OverlayReader::read_with_policy(&tampered, &policy, None).unwrap_err();
+    assert!(matches!(err, PackError::Signature(ref m) if m.contains("No trusted keys")));
+}
+
+#[test]
+fn signing_requires_key_slot() {
+    let temp = tempdir().unwrap();
+    let (key_path, _) = key_file(temp.path(), "app.key", 1);
+    let path = temp.path().join("app.exe");
+    fs::write(&path, b"runtime").unwrap();
+
+    let err = OverlayWriter::write(&path, &overlay("App", Some(&key_path))).unwrap_err();
+    assert!(matches!(err, PackError::Signature(ref m) if m.contains("no trusted key slot")));
+}
+
+#[test]
+fn bundled_runtime_asset_is_not_scanned() {
+    let temp = tempdir().unwrap();
+    let (key_path, public) = key_file(temp.path(), "app.key", 1);
+    // An asset that is itself an AuroraView runtime carries its own key slot
+    let mut data = overlay("App", Some(&key_path));
+    data.add_asset("tools/helper.exe", runtime());
+    let exe = write_exe(temp.path(), "app.exe", &data);
+
+    assert_eq!(pinned_runtime_keys(&exe).unwrap(), vec![public]);
+    assert!(OverlayReader::read(&exe).unwrap().is_some());
+}
+
+#[test]
+fn unsigned_overlay_requires_policy_opt_in() {
+    let temp = tempdir().unwrap();
+    let exe = write_exe(temp.path(), "app.exe", &overlay("App", None));
+
+    let data = OverlayReader::read(&exe).unwrap().unwrap();
+    assert!(data.signature.is_none());
+
+    let policy = SignaturePolicy::new().require_signature(true);
+    let err = OverlayReader::read_with_policy(&exe, &policy, None).unwrap_err();
+    assert!(matches!(err, PackError::Signature(ref m) if m.contains("not signed")));
+}
+
+#[test]
+fn version_1_overlay_is_still_readable() {
+    let temp = tempdir().unwrap();
+    let exe = temp.path().join("v1.exe");
+    fs::write(&exe, b"runtime").unwrap();
+
+    let mut metadata =
+        serde_json::to_value(PackConfig::url("https://example.com").with_output("legacy")).unwrap();
+    metadata["content_hash"] = "0123456789abcdef".into();
+    let config = zstd::encode_all(&serde_json::to_vec(&metadata).unwrap()[..], 3).unwrap();
+    let mut tar = tar::Builder::new(Vec::new());
+    let mut header = tar::Header::new_gnu();
+    header.set_size(2);
+    header.set_cksum();
+    tar.append_data(&mut header, "a.txt", &b"hi"[..]).unwrap();
+    let assets = zstd::encode_all(&tar.into_inner().unwrap()[..], 3).unwrap();
+
+    let mut file = fs::OpenOptions::new().append(true).open(&exe).unwrap();
+    file.write_all(OVERLAY_MAGIC).unwrap();
+    file.write_all(&1u32.to_le_bytes()).unwrap();
+    file.write_all(&(config.len() as u64).to_le_bytes())
+        .unwrap();
+    file.write_all(&(assets.len() as u64).to_le_bytes())
+        .unwrap();
+    file.write_all(&config).unwrap();
+    file.write_all(&assets).unwrap();
+    file.write_all(&(b"runtime".len() as u64).to_le_bytes())
+        .unwrap();
+    file.write_all(OVERLAY_MAGIC).unwrap();
+    drop(file);
+
+    let data = OverlayReader::read(&exe).unwrap().unwrap();
+    assert_eq!(data.config.output_name, "legacy");
+    assert_eq!(data.assets, vec![("a.txt".to_string(), b"hi".to_vec())]);
+    assert!(data.signature.is_none());
+}
+
+#[test]
+fn signing_key_file_roundtrip() {
+    let temp = tempdir().unwrap();
+    let key = OverlaySigningKey::generate();
+    let path = temp.path().join("generated.key");
+    key.write_to_file(&path).unwrap();
+
+    let loaded = OverlaySigningKey::from_file(&path).unwrap();
+    assert_eq!(loaded.verifying_key(), key.verifying_key());
+    assert_eq!(
+        OverlayVerifyingKey::from_hex(&key.verifying_key().to_hex()).unwrap(),
+        key.verifying_key()
+    );
+    assert_eq!(key.verifying_key().key_id().len(), 16);
+
+    fs::write(&path, "# not a key\nxyz\n").unwrap();
+    assert!(matches!(
+        OverlaySigningKey::from_file(&path),
+        Err(PackError::Signature(_))
+    ));
+}
+
+#[test]
+fn missing_signing_key_fails_pack() {
+    let temp = tempdir().unwrap();
+    let path = temp.path().join("app.exe");
+    fs::write(&path, b"runtime").unwrap();
+    let data = overlay("App", Some(&temp.path().join("missing.key")));
+    assert!(matches!(
+        OverlayWriter::write(&path, &data),
+        Err(PackError::Signature(_))
+    ));
+}
+
+#[test]
+fn patched_executable_stays_signed() {
+    let temp = tempdir().unwrap();
+    let (key_path, public) = key_file(temp.path(), "app.key", 1);
+    let v1 = write_exe(temp.path(), "v1.exe", &overlay("V1", Some(&key_path)));
+    let mut v2_data = overlay("V2", Some(&key_path));
+    v2_data.add_asset("new.css", b"main{}".to_vec());
+    let v2 = write_exe(temp.path(), "v2.exe", &v2_data);
+
+    Patch::create(&v1, &v2).unwrap().apply(&v1).unwrap();
+
+    let policy = SignaturePolicy::pinned(vec![public]);
+    let patched = OverlayReader::read_with_policy(&v1, &policy, None)
+        .unwrap()
+        .unwrap();
+    assert_eq!(patched.config.window.title, "V2");
+    assert_eq!(
+        patched.signature,
+        OverlayReader::read(&v2).unwrap().unwrap().signature
+    );
+}
+
+/// A different fake runtime build, so patches carry the runtime
+fn runtime_v2() -> Vec<u8> {
+    let mut bytes = b"runtime v2".to_vec();
+    bytes.extend(empty_key_slot());
+    bytes
+}
+
+#[test]
+fn patch_runtime_keeping_pinned_keys_applies() {
+    let temp = tempdir().unwrap();
+    let (key_path, public) = key_file(temp.path(), "app.key", 1);
+    let v1 = write_exe(temp.path(), "v1.exe", &overlay("V1", Some(&key_path)));
+    let v2 = write_on_runtime(
+        temp.path(),
+        "v2.exe",
+        &runtime_v2(),
+        &overlay("V2", Some(&key_path)),
+    );
+
+    let patch = Patch::create(&v1, &v2).unwrap();
+    assert!(patch.stats().runtime_changed);
+    patch.apply(&v1).unwrap();
+    assert_eq!(pinned_runtime_keys(&v1).unwrap(), vec![public]);
+    assert_eq!(
+        OverlayReader::read(&v1)
+            .unwrap()
+            .unwrap()
+            .config
+            .window
+            .title,
+        "V2"
+    );
+}
+
+#[test]
+fn patch_runtime_with_other_pinned_key_is_rejected() {
+    let temp = tempdir().unwrap();
+    let (key_path, public) = key_file(temp.path(), "app.key", 1);
+    let (attacker_key, attacker) = key_file(temp.path(), "attacker.key", 2);
+    let v1 = write_exe(temp.path(), "v1.exe", &overlay("V1", Some(&key_path)));
+    // Self-consistent executable: its own runtime pins the key that signed it
+    let evil = write_on_runtime(
+        temp.path(),
+        "evil.exe",
+        &runtime_v2(),
+        &overlay("Evil", Some(&attacker_key)),
+    );
+    let original = fs::read(&v1).unwrap();
+
+    let patch = Patch::create(&v1, &evil).unwrap();
+    let err = patch.apply(&v1).unwrap_err();
+    assert!(matches!(err, PackError::Patch(ref m) if m.contains("different signing keys")));
+    assert_eq!(fs::read(&v1).unwrap(), original);
+    assert_eq!(pinned_runtime_keys(&v1).unwrap(), vec![public]);
+
+    // Rotation has to be asked for explicitly
+    let options = ApplyOptions::new().with_key_rotation(true);
+    patch.apply_with_options(&v1, &options).unwrap();
+    assert_eq!(pinned_runtime_keys(&v1).unwrap(), vec![attacker]);
+}
+
+#[test]
+fn patch_signature_is_checked_against_base_keys() {
+    let temp = tempdir().unwrap();
+    let (key_path, _) = key_file(temp.path(), "app.key", 1);
+    let v1 = write_exe(temp.path(), "v1.exe", &overlay("V1", Some(&key_path)));
+    let v2 = write_exe(temp.path(), "v2.exe", &overlay("V2", Some(&key_path)));
+
+    // Re-sign the target with a key the installed runtime does not pin
+    let mut patch = Patch::create(&v1, &v2).unwrap();
+    let target = OverlayReader::read(&v2).unwrap().unwrap();
+    let other = OverlaySigningKey::from_seed(&[2; 32]);
+    patch.manifest.signature = Some(other.sign(&target.config, &target.assets).unwrap());
+
+    let err = patch.apply(&v1).unwrap_err();
+    assert!(matches!(err, PackError::Signature(ref m) if m.contains("untrusted key")));
+    assert_eq!(
+        OverlayReader::read(&v1)
+            .unwrap()
+            .unwrap()
+            .config
+            .window
+            .title,
+        "V1"
+    );
+}
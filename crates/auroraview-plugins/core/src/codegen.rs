@@ -0,0 +1,527 @@
+//! TypeScript and Python type generation from command specifications
+//!
+//! The TypeScript output augments the global `AuroraViewCommands` interface
+//! declared in `auroraview.d.ts`, which types `auroraview.invoke()` per
+//! command. The Python output is a `.pyi` stub with `TypedDict`s for
+//! arguments and results plus an overloaded `invoke` protocol.
+
+use std::fmt::Write;
+
+use serde_json::{Map, Value};
+
+use crate::PluginCommand;
+
+/// Full invoke name of a plugin command (`plugin:<plugin>|<command>`)
+pub fn invoke_name(plugin: &str, command: &str) -> String {
+    format!("plugin:{}|{}", plugin, command)
+}
+
+/// PascalCase type name prefix of a command (e.g. `FsReadFile`)
+pub fn type_prefix(plugin: &str, command: &str) -> String {
+    format!("{}{}", pascal_case(plugin), pascal_case(command))
+}
+
+fn pascal_case(name: &str) -> String {
+    name.split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|part| !part.is_empty())
+        .map(|part| {
+            let mut chars = part.chars();
+            match chars.next() {
+                Some(first) => first.to_ascii_uppercase().to_string() + chars.as_str(),
+                None => String::new(),
+            }
+        })
+        .collect()
+}
+
+/// Object schema with declared properties, if `schema` is one
+fn object_properties(schema: &Value) -> Option<&Map<String, Value>> {
+    let schema = schema.as_object()?;
+    let is_object = match schema.get("type") {
+        Some(Value::String(t)) => t == "object",
+        None => true,
+        _ => false,
+    };
+    schema
+        .get("properties")
+        .and_then(Value::as_object)
+        .filter(|properties| is_object && !properties.is_empty())
+}
+
+fn required_names(schema: &Map<String, Value>) -> Vec<&str> {
+    schema
+        .get("required")
+        .and_then(Value::as_array)
+        .map(|names| names.iter().filter_map(Value::as_str).collect())
+        .unwrap_or_default()
+}
+
+fn description(schema: &Value) -> Option<&str> {
+    schema.get("description").and_then(Value::as_str)
+}
+
+fn dedup(items: impl IntoIterator<Item = String>) -> Vec<String> {
+    let mut out: Vec<String> = Vec::new();
+    for item in items {
+        if !out.contains(&item) {
+            out.push(item);
+        }
+    }
+    out
+}
+
+// ========== TypeScript ==========
+
+/// Generate TypeScript declarations for `(plugin, command)` pairs
+pub fn typescript_definitions<'a>(
+    commands: impl IntoIterator<Item = (&'a str, &'a PluginCommand)>,
+) -> String {
+    let mut out = String::from(
+        "/**\n * AuroraView plugin command types\n *\n \
+         * Generated from the plugin command registry. Do not edit by hand.\n */\n",
+    );
+    let mut entries = String::new();
+
+    for (plugin, command) in commands {
+        let prefix = type_prefix(plugin, &command.name);
+        let name = invoke_name(plugin, &command.name);
+        let args_type = format!("{}Args", prefix);
+        let result_type = format!("{}Result", prefix);
+
+        out.push('\n');
+        ts_named_type(
+            &mut out,
+            &args_type,
+            &command.effective_args_schema(),
+            &format!("Arguments of `{}`", name),
+        );
+        out.push('\n');
+        ts_named_type(
+            &mut out,
+            &result_type,
+            command.result_schema.as_ref().unwrap_or(&Value::Bool(true)),
+            &format!("Result of `{}`", name),
+        );
+
+        if !command.description.is_empty() {
+            let _ = writeln!(entries, "    /** {} */", command.description);
+        }
+        let _ = writeln!(
+            entries,
+            "    {}: {{\n      args: {};\n      result: {};\n    }};",
+            Value::String(name),
+            args_type,
+            result_type
+        );
+    }
+
+    let _ = write!(
+        out,
+        "\ndeclare global {{\n  interface AuroraViewCommands {{\n{}  }}\n}}\n\nexport {{}};\n",
+        entries
+    );
+    out
+}
+
+fn ts_named_type(out: &mut String, name: &str, schema: &Value, doc: &str) {
+    let _ = writeln!(out, "/** {} */", doc);
+    let Some(properties) = object_properties(schema) else {
+        let _ = writeln!(out, "export type {} = {};", name, ts_type(schema));
+        return;
+    };
+    let required = schema.as_object().map(required_names).unwrap_or_default();
+    let _ = writeln!(out, "export interface {} {{", name);
+    for (key, property) in properties {
+        if let Some(text) = description(property) {
+            let _ = writeln!(out, "  /** {} */", text);
+        }
+        let optional = if required.contains(&key.as_str()) {
+            ""
+        } else {
+            "?"
+        };
+        let _ = writeln!(out, "  {}{}: {};", ts_key(key), optional, ts_type(property));
+    }
+    out.push_str("}\n");
+}
+
+fn ts_key(key: &str) -> String {
+    let mut chars = key.chars();
+    let valid = chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_' || c == '$')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '$');
+    if valid {
+        key.to_string()
+    } else {
+        Value::String(key.to_string()).to_string()
+    }
+}
+
+fn ts_union(types: impl IntoIterator<Item = String>) -> String {
+    let types = dedup(types);
+    if types.is_empty() {
+        "never".to_string()
+    } else {
+        types.join(" | ")
+    }
+}
+
+/// Inline TypeScript type of a schema
+pub fn schema_to_typescript(schema: &Value) -> String {
+    ts_type(schema)
+}
+
+fn ts_type(schema: &Value) -> String {
+    let Value::Object(map) = schema else {
+        return if schema == &Value::Bool(false) {
+            "never".to_string()
+        } else {
+            "unknown".to_string()
+        };
+    };
+    if let Some(value) = map.get("const") {
+        return value.to_string();
+    }
+    if let Some(Value::Array(values)) = map.get("enum") {
+        return ts_union(values.iter().map(Value::to_string));
+    }
+    for key in ["anyOf", "oneOf"] {
+        if let Some(Value::Array(options)) = map.get(key) {
+            return ts_union(options.iter().map(ts_type));
+        }
+    }
+    match map.get("type") {
+        Some(Value::String(t)) => ts_for_type(t, map),
+        Some(Value::Array(types)) => ts_union(
+            types
+                .iter()
+                .filter_map(Value::as_str)
+                .map(|t| ts_for_type(t, map)),
+        ),
+        _ if map.contains_key("properties") => ts_for_type("object", map),
+        _ => "unknown".to_string(),
+    }
+}
+
+fn ts_for_type(t: &str, schema: &Map<String, Value>) -> String {
+    match t {
+        "string" => "string".to_string(),
+        "number" | "integer" => "number".to_string(),
+        "boolean" => "boolean".to_string(),
+        "null" => "null".to_string(),
+        "array" => format!(
+            "Array<{}>",
+            schema
+                .get("items")
+                .map(ts_type)
+                .unwrap_or_else(|| "unknown".to_string())
+        ),
+        "object" => {
+            let empty = Map::new();
+            let properties = schema
+                .get("properties")
+                .and_then(Value::as_object)
+                .unwrap_or(&empty);
+            if properties.is_empty() {
+                let values = match schema.get("additionalProperties") {
+                    Some(extra @ Value::Object(_)) => ts_type(extra),
+                    _ => "unknown".to_string(),
+                };
+                return format!("Record<string, {}>", values);
+            }
+            let required = required_names(schema);
+            let members: Vec<String> = properties
+                .iter()
+                .map(|(key, property)| {
+                    let optional = if required.contains(&key.as_str()) {
+                        ""
+                    } else {
+                        "?"
+                    };
+                    format!("{}{}: {}", ts_key(key), optional, ts_type(property))
+                })
+                .collect();
+            format!("{{ {} }}", members.join("; "))
+        }
+        _ => "unknown".to_string(),
+    }
+}
+
+// ========== Python ==========
+
+const PYTHON_KEYWORDS: &[&str] = &[
+    "False", "None", "True", "and", "as", "assert", "async", "await", "break", "class", "continue",
+    "def", "del", "elif", "else", "except", "finally", "for", "from", "global", "if", "import",
+    "in", "is", "lambda", "nonlocal", "not", "or", "pass", "raise", "return", "try", "while",
+    "with", "yield",
+];
+
+fn is_python_identifier(key: &str) -> bool {
+    let mut chars = key.chars();
+    chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+        && !PYTHON_KEYWORDS.contains(&key)
+}
+
+/// Generate a Python type stub (`.pyi`) for `(plugin, command)` pairs
+pub fn python_stubs<'a>(
+    commands: impl IntoIterator<Item = (&'a str, &'a PluginCommand)>,
+) -> String {
+    let mut out = String::from(
+        "\"\"\"AuroraView plugin command types.\n\n\
+         Generated from the plugin command registry. Do not edit by hand.\n\"\"\"\n\n\
+         import sys\n\
+         from typing import Any, Dict, List, Optional, Union, overload\n\n\
+         if sys.version_info >= (3, 8):\n    \
+         from typing import Literal, Protocol, TypedDict\n\
+         else:\n    \
+         from typing_extensions import Literal, Protocol, TypedDict\n",
+    );
+    let mut names = Vec::new();
+    let mut overloads = String::new();
+
+    for (plugin, command) in commands {
+        let prefix = type_prefix(plugin, &command.name);
+        let name = Value::String(invoke_name(plugin, &command.name)).to_string();
+        let args_type = format!("{}Args", prefix);
+        let result_type = format!("{}Result", prefix);
+        let args_schema = command.effective_args_schema();
+
+        py_named_type(&mut out, &args_type, &args_schema);
+        py_named_type(
+            &mut out,
+            &result_type,
+            command.result_schema.as_ref().unwrap_or(&Value::Bool(true)),
+        );
+
+        let has_required = args_schema
+            .as_object()
+            .is_some_and(|schema| !required_names(schema).is_empty());
+        let args_param = if !has_required {
+            format!("args: {} = ...", args_type)
+        } else {
+            format!("args: {}", args_type)
+        };
+        let _ = write!(
+            overloads,
+            "    @overload\n    def invoke(self, command: Literal[{}], {}) -> {}: ...\n",
+            name, args_param, result_type
+        );
+        names.push(name);
+    }
+
+    out.push_str("\n\nCommandName = Literal[\n");
+    for name in &names {
+        let _ = writeln!(out, "    {},", name);
+    }
+    out.push_str("]\n\n\nclass PluginCommands(Protocol):\n");
+    out.push_str("    \"\"\"Typed `invoke` signatures of the registered commands.\"\"\"\n\n");
+    out.push_str(&overloads);
+    if !names.is_empty() {
+        out.push_str("    @overload\n");
+    }
+    out.push_str(
+        "    def invoke(self, command: str, args: Optional[Dict[str, Any]] = None) -> Any: ...\n",
+    );
+    out
+}
+
+fn py_named_type(out: &mut String, name: &str, schema: &Value) {
+    out.push_str("\n\n");
+    let Some(properties) = object_properties(schema) else {
+        let _ = writeln!(out, "{} = {}", name, py_type(schema));
+        return;
+    };
+    let required = schema.as_object().map(required_names).unwrap_or_default();
+
+    if !properties.keys().all(|key| is_python_identifier(key)) {
+        // Keys such as `from` or `dry-run` need the functional syntax
+        let fields: Vec<String> = properties
+            .iter()
+            .map(|(key, property)| format!("{}: {}", Value::String(key.clone()), py_type(property)))
+            .collect();
+        let _ = writeln!(
+            out,
+            "{} = TypedDict(\"{}\", {{{}}}, total={})",
+            name,
+            name,
+            fields.join(", "),
+            if required.is_empty() { "False" } else { "True" }
+        );
+        return;
+    }
+
+    let field = |out: &mut String, key: &str, property: &Value| {
+        if let Some(text) = description(property) {
+            let _ = writeln!(out, "    # {}", text);
+        }
+        let _ = writeln!(out, "    {}: {}", key, py_type(property));
+    };
+    let (mandatory, optional): (Vec<_>, Vec<_>) = properties
+        .iter()
+        .partition(|(key, _)| required.contains(&key.as_str()));
+
+    match (mandatory.is_empty(), optional.is_empty()) {
+        (false, false) => {
+            let _ = writeln!(out, "class _{}Required(TypedDict):", name);
+            for (key, property) in &mandatory {
+                field(out, key, property);
+            }
+            let _ = writeln!(out, "\n\nclass {}(_{}Required, total=False):", name, name);
+            for (key, property) in &optional {
+                field(out, key, property);
+            }
+        }
+        (false, true) => {
+            let _ = writeln!(out, "class {}(TypedDict):", name);
+            for (key, property) in &mandatory {
+                field(out, key, property);
+            }
+        }
+        _ => {
+            let _ = writeln!(out, "class {}(TypedDict, total=False):", name);
+            for (key, property) in &optional {
+                field(out, key, property);
+            }
+        }
+    }
+}
+
+fn py_literal(value: &Value) -> Option<String> {
+    match value {
+        Value::String(_) => Some(value.to_string()),
+        Value::Number(n) if n.is_i64() || n.is_u64() => Some(n.to_string()),
+        Value::Bool(true) => Some("True".to_string()),
+        Value::Bool(false) => Some("False".to_string()),
+        _ => None,
+    }
+}
+
+fn py_literal_union(values: &[Value]) -> String {
+    let has_null = values.iter().any(Value::is_null);
+    let literals: Option<Vec<String>> = values
+        .iter()
+        .filter(|v| !v.is_null())
+        .map(py_literal)
+        .collect();
+    match literals {
+        Some(literals) if !literals.is_empty() => {
+            let literal = format!("Literal[{}]", literals.join(", "));
+            if has_null {
+                format!("Optional[{}]", literal)
+            } else {
+                literal
+            }
+        }
+        Some(_) if has_null => "None".to_string(),
+        _ => "Any".to_string(),
+    }
+}
+
+fn py_union(types: impl IntoIterator<Item = String>) -> String {
+    let types = dedup(types);
+    if types.iter().any(|t| t == "Any") {
+        return "Any".to_string();
+    }
+    let has_none = types.iter().any(|t| t == "None");
+    let others: Vec<&String> = types.iter().filter(|t| *t != "None").collect();
+    match (others.as_slice(), has_none) {
+        ([], true) => "None".to_string(),
+        ([], false) => "Any".to_string(),
+        ([single], false) => (*single).clone(),
+        ([single], true) => format!("Optional[{}]", single),
+        (many, false) => format!(
+            "Union[{}]",
+            many.iter()
+                .map(|t| t.as_str())
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+        (many, true) => format!(
+            "Optional[Union[{}]]",
+            many.iter()
+                .map(|t| t.as_str())
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+    }
+}
+
+/// Inline Python annotation of a schema
+pub fn schema_to_python(schema: &Value) -> String {
+    py_type(schema)
+}
+
+fn py_type(schema: &Value) -> String {
+    let Value::Object(map) = schema else {
+        return "Any".to_string();
+    };
+    if let Some(value) = map.get("const") {
+        return py_literal_union(std::slice::from_ref(value));
+    }
+    if let Some(Value::Array(values)) = map.get("enum") {
+        return py_literal_union(values);
+    }
+    for key in ["anyOf", "oneOf"] {
+        if let Some(Value::Array(options)) = map.get(key) {
+            return py_union(options.iter().map(py_type));
+        }
+    }
+    match map.get("type") {
+        Some(Value::String(t)) => py_for_type(t, map),
+        Some(Value::Array(types)) => py_union(
+            types
+                .iter()
+                .filter_map(Value::as_str)
+                .map(|t| py_for_type(t, map)),
+        ),
+        _ if map.contains_key("properties") => py_for_type("object", map),
+        _ => "Any".to_string(),
+    }
+}
+
+fn py_for_type(t: &str, schema: &Map<String, Value>) -> String {
+    match t {
+        "string" => "str".to_string(),
+        "integer" => "int".to_string(),
+        "number" => "float".to_string(),
+        "boolean" => "bool".to_string(),
+        "null" => "None".to_string(),
+        "array" => format!(
+            "List[{}]",
+            schema
+                .get("items")
+                .map(py_type)
+                .unwrap_or_else(|| "Any".to_string())
+        ),
+        "object" => match schema.get("additionalProperties") {
+            Some(extra @ Value::Object(_)) if !schema.contains_key("properties") => {
+                format!("Dict[str, {}]", py_type(extra))
+            }
+            _ => "Dict[str, Any]".to_string(),
+        },
+        _ => "Any".to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_type_prefix() {
+        assert_eq!(type_prefix("fs", "read_file"), "FsReadFile");
+        assert_eq!(type_prefix("my-plugin", "get.value"), "MyPluginGetValue");
+    }
+
+    #[test]
+    fn test_python_identifier() {
+        assert!(is_python_identifier("path"));
+        assert!(!is_python_identifier("from"));
+        assert!(!is_python_identifier("dry-run"));
+        assert!(!is_python_identifier("1st"));
+    }
+}
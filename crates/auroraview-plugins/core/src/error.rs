@@ -3,6 +3,7 @@
 //! Common error handling for the plugin system.
 
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
 use std::fmt;
 use thiserror::Error;
 
@@ -71,6 +72,8 @@ pub struct PluginError {
     code: PluginErrorCode,
     /// Error message
     message: String,
+    /// Structured details (e.g. schema violations)
+    details: Option<Value>,
 }
 
 impl PluginError {
@@ -79,9 +82,21 @@ impl PluginError {
         Self {
             code,
             message: message.into(),
+            details: None,
         }
     }
 
+    /// Attach structured details
+    pub fn with_details(mut self, details: Value) -> Self {
+        self.details = Some(details);
+        self
+    }
+
+    /// Get the structured details
+    pub fn details(&self) -> Option<&Value> {
+        self.details.as_ref()
+    }
+
     /// Get the error code
     pub fn code(&self) -> String {
         self.code.as_str().to_string()
@@ -1,6 +1,6 @@
 //! Plugin handler trait
 
-use crate::{PluginResult, ScopeConfig};
+use crate::{PluginCommand, PluginResult, ScopeConfig};
 use serde_json::Value;
 
 /// Trait for plugin implementations
@@ -13,4 +13,12 @@ pub trait PluginHandler: Send + Sync {
 
     /// Get supported commands
     fn commands(&self) -> Vec<&str>;
+
+    /// Typed command specifications
+    ///
+    /// Commands listed here are validated by the router before dispatch and
+    /// included in generated TypeScript/Python type definitions.
+    fn command_specs(&self) -> Vec<PluginCommand> {
+        Vec::new()
+    }
 }
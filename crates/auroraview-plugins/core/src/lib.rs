@@ -27,10 +27,12 @@
 //!
 //! Example: `plugin:fs|read_file`
 
+pub mod codegen;
 mod error;
 mod handler;
 mod request;
 mod router;
+pub mod schema;
 mod scope;
 mod types;
 
@@ -42,6 +44,8 @@ pub use handler::PluginHandler;
 pub use request::{PluginRequest, PluginResponse};
 /// Plugin command router and event callback registration.
 pub use router::{PluginEventCallback, PluginRouter};
+/// JSON Schema validation of command arguments and results.
+pub use schema::SchemaViolation;
 /// Security scope types for filesystem and shell access control.
 pub use scope::{PathScope, ScopeConfig, ScopeError, ShellScope};
 /// Plugin command descriptor type.
@@ -3,6 +3,8 @@
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
+use crate::PluginError;
+
 /// Plugin command request
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PluginRequest {
@@ -65,6 +67,9 @@ pub struct PluginResponse {
     pub error: Option<String>,
     /// Error code (if failure)
     pub code: Option<String>,
+    /// Structured error details (e.g. schema violations)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub details: Option<Value>,
     /// Request ID (echoed from request)
     pub id: Option<String>,
 }
@@ -77,6 +82,7 @@ impl PluginResponse {
             data: Some(data),
             error: None,
             code: None,
+            details: None,
             id: None,
         }
     }
@@ -88,10 +94,19 @@ impl PluginResponse {
             data: None,
             error: Some(error.into()),
             code: Some(code.into()),
+            details: None,
             id: None,
         }
     }
 
+    /// Create an error response from a plugin error
+    pub fn from_error(error: &PluginError) -> Self {
+        Self {
+            details: error.details().cloned(),
+            ..Self::err(error.message(), error.code())
+        }
+    }
+
     /// Set the request ID
     pub fn with_id(mut self, id: Option<String>) -> Self {
         self.id = id;
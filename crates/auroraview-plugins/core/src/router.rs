@@ -1,12 +1,13 @@
 //! Plugin router for dispatching commands to plugins
 
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::sync::Arc;
 
 use parking_lot::RwLock;
 use serde_json::Value;
 
-use crate::{PluginHandler, PluginRequest, PluginResponse, ScopeConfig};
+use crate::codegen;
+use crate::{PluginCommand, PluginHandler, PluginRequest, PluginResponse, ScopeConfig};
 
 /// Event callback type for plugins to emit events
 pub type PluginEventCallback = Arc<dyn Fn(&str, Value) + Send + Sync>;
@@ -19,6 +20,8 @@ pub struct PluginRouter {
     scope: ScopeConfig,
     /// Event callback for plugins to emit events to frontend
     event_callback: Arc<RwLock<Option<PluginEventCallback>>>,
+    /// Typed command specifications by plugin and command name
+    command_specs: BTreeMap<String, BTreeMap<String, PluginCommand>>,
 }
 
 impl Default for PluginRouter {
@@ -34,6 +37,7 @@ impl PluginRouter {
             plugins: HashMap::new(),
             scope: ScopeConfig::new(),
             event_callback: Arc::new(RwLock::new(None)),
+            command_specs: BTreeMap::new(),
         }
     }
 
@@ -73,16 +77,60 @@ impl PluginRouter {
         }
     }
 
-    /// Register a plugin
+    /// Register a plugin (and the command specifications it declares)
     pub fn register(&mut self, name: impl Into<String>, plugin: Arc<dyn PluginHandler>) {
-        self.plugins.insert(name.into(), plugin);
+        let name = name.into();
+        for spec in plugin.command_specs() {
+            self.register_command(name.clone(), spec);
+        }
+        self.plugins.insert(name, plugin);
     }
 
     /// Unregister a plugin
     pub fn unregister(&mut self, name: &str) -> Option<Arc<dyn PluginHandler>> {
+        self.command_specs.remove(name);
         self.plugins.remove(name)
     }
 
+    /// Register or replace a typed command specification
+    ///
+    /// Arguments of registered commands are validated before dispatch;
+    /// invalid calls fail with `INVALID_ARGS` and the list of violations in
+    /// the response details.
+    pub fn register_command(&mut self, plugin: impl Into<String>, command: PluginCommand) {
+        self.command_specs
+            .entry(plugin.into())
+            .or_default()
+            .insert(command.name.clone(), command);
+    }
+
+    /// Get the specification of a command
+    pub fn command_spec(&self, plugin: &str, command: &str) -> Option<&PluginCommand> {
+        self.command_specs.get(plugin)?.get(command)
+    }
+
+    /// All command specifications as `(plugin, command)`, sorted
+    pub fn command_specs(&self) -> Vec<(&str, &PluginCommand)> {
+        self.command_specs
+            .iter()
+            .flat_map(|(plugin, commands)| {
+                commands
+                    .values()
+                    .map(move |command| (plugin.as_str(), command))
+            })
+            .collect()
+    }
+
+    /// Generate TypeScript declarations for the registered commands
+    pub fn typescript_definitions(&self) -> String {
+        codegen::typescript_definitions(self.command_specs())
+    }
+
+    /// Generate Python type stubs for the registered commands
+    pub fn python_stubs(&self) -> String {
+        codegen::python_stubs(self.command_specs())
+    }
+
     /// Handle a plugin command
     pub fn handle(&self, request: PluginRequest) -> PluginResponse {
         tracing::debug!(
@@ -117,9 +165,34 @@ impl PluginRouter {
             }
         };
 
+        let spec = self.command_spec(&request.plugin, &request.command);
+        if let Some(spec) = spec {
+            if let Err(e) = spec.validate_args(&request.args) {
+                tracing::debug!(
+                    "[PluginRouter] Rejected plugin:{}|{}: {}",
+                    request.plugin,
+                    request.command,
+                    e
+                );
+                return PluginResponse::from_error(&e).with_id(request.id);
+            }
+        }
+
         match plugin.handle(&request.command, request.args.clone(), &self.scope) {
-            Ok(data) => PluginResponse::ok(data).with_id(request.id),
-            Err(e) => PluginResponse::err(e.message(), e.code()).with_id(request.id),
+            Ok(data) => {
+                if let Some(spec) = spec {
+                    for violation in spec.validate_result(&data) {
+                        tracing::warn!(
+                            "[PluginRouter] plugin:{}|{} result does not match its schema: {}",
+                            request.plugin,
+                            request.command,
+                            violation
+                        );
+                    }
+                }
+                PluginResponse::ok(data).with_id(request.id)
+            }
+            Err(e) => PluginResponse::from_error(&e).with_id(request.id),
         }
     }
 
@@ -0,0 +1,293 @@
+//! JSON Schema validation for command arguments and results
+//!
+//! Supports the subset of JSON Schema used to describe commands:
+//! `type` (including type arrays), `properties`, `required`,
+//! `additionalProperties`, `items`, `enum`, `const`, `anyOf`, `oneOf`,
+//! `minimum`/`maximum`, `exclusiveMinimum`/`exclusiveMaximum`,
+//! `minLength`/`maxLength` and `minItems`/`maxItems`. Other keywords
+//! (e.g. `description`) are ignored by validation.
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+/// A single validation failure
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SchemaViolation {
+    /// JSON Pointer of the offending value (empty for the root)
+    pub path: String,
+    /// Human-readable description
+    pub message: String,
+}
+
+impl std::fmt::Display for SchemaViolation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.path.is_empty() {
+            write!(f, "{}", self.message)
+        } else {
+            write!(f, "{}: {}", self.path, self.message)
+        }
+    }
+}
+
+/// Validate `value` against `schema`, returning every violation found
+pub fn validate(schema: &Value, value: &Value) -> Vec<SchemaViolation> {
+    let mut violations = Vec::new();
+    validate_at(schema, value, "", &mut violations);
+    violations
+}
+
+/// JSON Schema type name of a value
+pub fn json_type_name(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "boolean",
+        Value::Number(n) if n.is_i64() || n.is_u64() => "integer",
+        Value::Number(_) => "number",
+        Value::String(_) => "string",
+        Value::Array(_) => "array",
+        Value::Object(_) => "object",
+    }
+}
+
+fn matches_type(expected: &str, value: &Value) -> bool {
+    match expected {
+        "integer" => match value {
+            Value::Number(n) => {
+                n.is_i64() || n.is_u64() || n.as_f64().is_some_and(|f| f.fract() == 0.0)
+            }
+            _ => false,
+        },
+        "number" => value.is_number(),
+        other => json_type_name(value) == other,
+    }
+}
+
+fn push(out: &mut Vec<SchemaViolation>, path: &str, message: String) {
+    out.push(SchemaViolation {
+        path: path.to_string(),
+        message,
+    });
+}
+
+fn child_path(path: &str, token: &str) -> String {
+    format!("{}/{}", path, token.replace('~', "~0").replace('/', "~1"))
+}
+
+fn validate_at(schema: &Value, value: &Value, path: &str, out: &mut Vec<SchemaViolation>) {
+    let schema = match schema {
+        Value::Bool(true) => return,
+        Value::Bool(false) => {
+            push(out, path, "no value is allowed here".to_string());
+            return;
+        }
+        Value::Object(schema) => schema,
+        // Anything else is not a schema; accept rather than reject every call
+        _ => return,
+    };
+
+    if let Some(expected) = schema.get("type") {
+        let types: Vec<&str> = match expected {
+            Value::String(t) => vec![t.as_str()],
+            Value::Array(ts) => ts.iter().filter_map(Value::as_str).collect(),
+            _ => Vec::new(),
+        };
+        if !types.is_empty() && !types.iter().any(|t| matches_type(t, value)) {
+            push(
+                out,
+                path,
+                format!(
+                    "expected {}, got {}",
+                    types.join(" or "),
+                    json_type_name(value)
+                ),
+            );
+            return;
+        }
+    }
+
+    if let Some(expected) = schema.get("const") {
+        if expected != value {
+            push(out, path, format!("expected {}", expected));
+        }
+    }
+
+    if let Some(Value::Array(allowed)) = schema.get("enum") {
+        if !allowed.contains(value) {
+            let allowed: Vec<String> = allowed.iter().map(Value::to_string).collect();
+            push(
+                out,
+                path,
+                format!("expected one of {}, got {}", allowed.join(", "), value),
+            );
+        }
+    }
+
+    if let Some(Value::Array(options)) = schema.get("anyOf") {
+        if !options.iter().any(|s| validate(s, value).is_empty()) {
+            push(out, path, "does not match any allowed schema".to_string());
+        }
+    }
+
+    if let Some(Value::Array(options)) = schema.get("oneOf") {
+        let matching = options
+            .iter()
+            .filter(|s| validate(s, value).is_empty())
+            .count();
+        if matching != 1 {
+            push(
+                out,
+                path,
+                format!("must match exactly one schema, matched {}", matching),
+            );
+        }
+    }
+
+    match value {
+        Value::Object(map) => validate_object(schema, map, path, out),
+        Value::Array(items) => validate_array(schema, items, path, out),
+        Value::String(s) => {
+            let len = s.chars().count() as u64;
+            if let Some(min) = schema.get("minLength").and_then(Value::as_u64) {
+                if len < min {
+                    push(out, path, format!("must be at least {} characters", min));
+                }
+            }
+            if let Some(max) = schema.get("maxLength").and_then(Value::as_u64) {
+                if len > max {
+                    push(out, path, format!("must be at most {} characters", max));
+                }
+            }
+        }
+        Value::Number(n) => {
+            let n = n.as_f64().unwrap_or_default();
+            let bound = |key: &str| schema.get(key).and_then(Value::as_f64);
+            if let Some(min) = bound("minimum").filter(|min| n < *min) {
+                push(out, path, format!("must be >= {}", min));
+            }
+            if let Some(max) = bound("maximum").filter(|max| n > *max) {
+                push(out, path, format!("must be <= {}", max));
+            }
+            if let Some(min) = bound("exclusiveMinimum").filter(|min| n <= *min) {
+                push(out, path, format!("must be > {}", min));
+            }
+            if let Some(max) = bound("exclusiveMaximum").filter(|max| n >= *max) {
+                push(out, path, format!("must be < {}", max));
+            }
+        }
+        _ => {}
+    }
+}
+
+fn validate_object(
+    schema: &Map<String, Value>,
+    map: &Map<String, Value>,
+    path: &str,
+    out: &mut Vec<SchemaViolation>,
+) {
+    let empty = Map::new();
+    let properties = schema
+        .get("properties")
+        .and_then(Value::as_object)
+        .unwrap_or(&empty);
+
+    if let Some(Value::Array(required)) = schema.get("required") {
+        for name in required.iter().filter_map(Value::as_str) {
+            if !map.contains_key(name) {
+                push(out, path, format!("missing required property '{}'", name));
+            }
+        }
+    }
+
+    let additional = schema.get("additionalProperties");
+    for (key, value) in map {
+        if let Some(property) = properties.get(key) {
+            validate_at(property, value, &child_path(path, key), out);
+            continue;
+        }
+        match additional {
+            Some(Value::Bool(false)) => {
+                let mut message = format!("unknown property '{}'", key);
+                if let Some(suggestion) = closest_name(key, properties.keys()) {
+                    message.push_str(&format!(", did you mean '{}'?", suggestion));
+                }
+                push(out, path, message);
+            }
+            Some(extra @ Value::Object(_)) => {
+                validate_at(extra, value, &child_path(path, key), out);
+            }
+            _ => {}
+        }
+    }
+}
+
+fn validate_array(
+    schema: &Map<String, Value>,
+    items: &[Value],
+    path: &str,
+    out: &mut Vec<SchemaViolation>,
+) {
+    let len = items.len() as u64;
+    if let Some(min) = schema.get("minItems").and_then(Value::as_u64) {
+        if len < min {
+            push(out, path, format!("must have at least {} items", min));
+        }
+    }
+    if let Some(max) = schema.get("maxItems").and_then(Value::as_u64) {
+        if len > max {
+            push(out, path, format!("must have at most {} items", max));
+        }
+    }
+    if let Some(item_schema) = schema.get("items") {
+        for (i, item) in items.iter().enumerate() {
+            validate_at(item_schema, item, &format!("{}/{}", path, i), out);
+        }
+    }
+}
+
+/// Closest declared name within a small edit distance (for typo hints)
+fn closest_name<'a>(name: &str, candidates: impl Iterator<Item = &'a String>) -> Option<&'a str> {
+    let lowered = name.to_lowercase();
+    candidates
+        .map(|candidate| {
+            (
+                edit_distance(&lowered, &candidate.to_lowercase()),
+                candidate,
+            )
+        })
+        .filter(|(distance, candidate)| *distance <= 2.max(candidate.len() / 4))
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, candidate)| candidate.as_str())
+}
+
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut current = vec![i + 1; b.len() + 1];
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(ca != *cb);
+            current[j + 1] = substitution.min(previous[j + 1] + 1).min(current[j] + 1);
+        }
+        previous = current;
+    }
+    previous[b.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_edit_distance() {
+        assert_eq!(edit_distance("path", "path"), 0);
+        assert_eq!(edit_distance("paht", "path"), 2);
+        assert_eq!(edit_distance("filepath", "path"), 4);
+    }
+
+    #[test]
+    fn test_closest_name_ignores_case() {
+        let names = ["filePath".to_string(), "encoding".to_string()];
+        assert_eq!(closest_name("filepath", names.iter()), Some("filePath"));
+        assert_eq!(closest_name("xyz", names.iter()), None);
+    }
+}
@@ -1,8 +1,16 @@
 //! Plugin type definitions
 
 use serde::{Deserialize, Serialize};
+use serde_json::{json, Map, Value};
+
+use crate::schema::{self, SchemaViolation};
+use crate::{PluginError, PluginResult};
 
 /// Plugin command specification
+///
+/// Arguments are described either by name ([`with_required`](Self::with_required)
+/// / [`with_optional`](Self::with_optional)) or by a full JSON Schema
+/// ([`with_args_schema`](Self::with_args_schema)), which takes precedence.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PluginCommand {
     /// Command name
@@ -13,6 +21,12 @@ pub struct PluginCommand {
     pub required_args: Vec<String>,
     /// Optional arguments
     pub optional_args: Vec<String>,
+    /// JSON Schema of the arguments object
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub args_schema: Option<Value>,
+    /// JSON Schema of the result
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub result_schema: Option<Value>,
 }
 
 impl PluginCommand {
@@ -23,6 +37,8 @@ impl PluginCommand {
             description: description.into(),
             required_args: Vec::new(),
             optional_args: Vec::new(),
+            args_schema: None,
+            result_schema: None,
         }
     }
 
@@ -37,4 +53,72 @@ impl PluginCommand {
         self.optional_args = args.iter().map(|s| s.to_string()).collect();
         self
     }
+
+    /// Describe the arguments with a JSON Schema (an object schema)
+    pub fn with_args_schema(mut self, schema: Value) -> Self {
+        self.args_schema = Some(schema);
+        self
+    }
+
+    /// Describe the result with a JSON Schema
+    pub fn with_result_schema(mut self, schema: Value) -> Self {
+        self.result_schema = Some(schema);
+        self
+    }
+
+    /// Effective argument schema
+    ///
+    /// Without an explicit schema, one is derived from the argument names:
+    /// values are untyped, but missing required and unknown arguments are
+    /// still rejected.
+    pub fn effective_args_schema(&self) -> Value {
+        if let Some(schema) = &self.args_schema {
+            return schema.clone();
+        }
+        let properties: Map<String, Value> = self
+            .required_args
+            .iter()
+            .chain(&self.optional_args)
+            .map(|name| (name.clone(), json!({})))
+            .collect();
+        json!({
+            "type": "object",
+            "properties": properties,
+            "required": self.required_args,
+            "additionalProperties": false,
+        })
+    }
+
+    /// Check arguments against the schema
+    ///
+    /// A `null` payload is treated as an empty object, as JavaScript sends
+    /// no arguments for commands invoked without them.
+    pub fn validate_args(&self, args: &Value) -> PluginResult<()> {
+        let empty = Value::Object(Map::new());
+        let args = if args.is_null() { &empty } else { args };
+        let violations = schema::validate(&self.effective_args_schema(), args);
+        if violations.is_empty() {
+            return Ok(());
+        }
+        Err(invalid_args_error(&self.name, &violations))
+    }
+
+    /// Check a result against the result schema (if any)
+    pub fn validate_result(&self, result: &Value) -> Vec<SchemaViolation> {
+        self.result_schema
+            .as_ref()
+            .map(|schema| schema::validate(schema, result))
+            .unwrap_or_default()
+    }
+}
+
+/// Structured `InvalidArgs` error listing every violation
+fn invalid_args_error(command: &str, violations: &[SchemaViolation]) -> PluginError {
+    let summary: Vec<String> = violations.iter().map(ToString::to_string).collect();
+    PluginError::invalid_args(format!(
+        "Invalid arguments for '{}': {}",
+        command,
+        summary.join("; ")
+    ))
+    .with_details(json!({ "violations": violations }))
 }
@@ -0,0 +1,405 @@
+//! Tests for command schemas, argument validation and stub generation
+
+use std::sync::Arc;
+
+use auroraview_plugin_core::codegen::{python_stubs, typescript_definitions};
+use auroraview_plugin_core::schema::{json_type_name, validate};
+use auroraview_plugin_core::{
+    PluginCommand, PluginError, PluginErrorCode, PluginHandler, PluginRequest, PluginResponse,
+    PluginResult, PluginRouter, ScopeConfig,
+};
+use rstest::rstest;
+use serde_json::{json, Value};
+
+// ── Typed mock handler ────────────────────────────────────────────────────────
+
+struct GreetPlugin;
+
+impl PluginHandler for GreetPlugin {
+    fn name(&self) -> &str {
+        "greet"
+    }
+
+    fn handle(&self, command: &str, args: Value, _scope: &ScopeConfig) -> PluginResult<Value> {
+        match command {
+            "hello" => Ok(
+                json!({"message": format!("Hello, {}!", args["name"].as_str().unwrap_or_default())}),
+            ),
+            "untyped" => Ok(args),
+            _ => Err(PluginError::command_not_found(command)),
+        }
+    }
+
+    fn commands(&self) -> Vec<&str> {
+        vec!["hello", "untyped"]
+    }
+
+    fn command_specs(&self) -> Vec<PluginCommand> {
+        vec![hello_command()]
+    }
+}
+
+fn hello_command() -> PluginCommand {
+    PluginCommand::new("hello", "Greet someone")
+        .with_args_schema(json!({
+            "type": "object",
+            "properties": {
+                "name": {"type": "string", "minLength": 1},
+                "times": {"type": "integer", "minimum": 1},
+            },
+            "required": ["name"],
+            "additionalProperties": false,
+        }))
+        .with_result_schema(json!({
+            "type": "object",
+            "properties": {"message": {"type": "string"}},
+            "required": ["message"],
+        }))
+}
+
+fn router() -> PluginRouter {
+    let mut router = PluginRouter::new();
+    router.scope_mut().enable_plugin("greet");
+    router.register("greet", Arc::new(GreetPlugin));
+    router
+}
+
+fn messages(schema: Value, value: Value) -> Vec<String> {
+    validate(&schema, &value)
+        .iter()
+        .map(ToString::to_string)
+        .collect()
+}
+
+// ── Schema validation ─────────────────────────────────────────────────────────
+
+#[rstest]
+#[case(json!({"type": "string"}), json!("a"))]
+#[case(json!({"type": "integer"}), json!(3))]
+#[case(json!({"type": "integer"}), json!(3.0))]
+#[case(json!({"type": "number"}), json!(3))]
+#[case(json!({"type": ["string", "null"]}), json!(null))]
+#[case(json!({"enum": ["a", "b"]}), json!("b"))]
+#[case(json!({"const": 1}), json!(1))]
+#[case(json!({"anyOf": [{"type": "string"}, {"type": "boolean"}]}), json!(true))]
+#[case(json!({"type": "array", "items": {"type": "integer"}}), json!([1, 2]))]
+#[case(json!({"type": "string", "minLength": 1, "maxLength": 3}), json!("abc"))]
+#[case(json!({}), json!({"anything": [1]}))]
+#[case(json!(true), json!(null))]
+fn validate_accepts(#[case] schema: Value, #[case] value: Value) {
+    assert!(validate(&schema, &value).is_empty());
+}
+
+#[rstest]
+#[case(json!({"type": "string"}), json!(1), "expected string, got integer")]
+#[case(json!({"type": "integer"}), json!(1.5), "expected integer, got number")]
+#[case(json!({"type": ["string", "null"]}), json!(false), "expected string or null, got boolean")]
+#[case(json!({"enum": ["a", "b"]}), json!("c"), "expected one of \"a\", \"b\", got \"c\"")]
+#[case(json!({"minimum": 1}), json!(0), "must be >= 1")]
+#[case(json!({"exclusiveMaximum": 1}), json!(1), "must be < 1")]
+#[case(json!({"minLength": 2}), json!("a"), "must be at least 2 characters")]
+#[case(json!({"maxItems": 1}), json!([1, 2]), "must have at most 1 items")]
+#[case(json!({"oneOf": [{"type": "integer"}, {"type": "number"}]}), json!(1), "must match exactly one schema, matched 2")]
+#[case(json!(false), json!(1), "no value is allowed here")]
+fn validate_rejects(#[case] schema: Value, #[case] value: Value, #[case] expected: &str) {
+    assert_eq!(messages(schema, value), vec![expected.to_string()]);
+}
+
+#[test]
+fn validate_reports_nested_paths() {
+    let schema = json!({
+        "type": "object",
+        "properties": {
+            "items": {
+                "type": "array",
+                "items": {"type": "object", "properties": {"id": {"type": "integer"}}},
+            },
+        },
+    });
+    let value = json!({"items": [{"id": 1}, {"id": "two"}]});
+    assert_eq!(
+        messages(schema, value),
+        vec!["/items/1/id: expected integer, got string"]
+    );
+}
+
+#[test]
+fn validate_escapes_pointer_tokens() {
+    let schema = json!({"properties": {"a/b": {"type": "string"}}});
+    assert_eq!(
+        messages(schema, json!({"a/b": 1})),
+        vec!["/a~1b: expected string, got integer"]
+    );
+}
+
+#[test]
+fn validate_collects_every_violation() {
+    let schema = hello_command().effective_args_schema();
+    let violations = validate(&schema, &json!({"times": 0, "nmae": "x"}));
+    assert_eq!(violations.len(), 3);
+}
+
+#[test]
+fn validate_suggests_close_property_names() {
+    let schema = hello_command().effective_args_schema();
+    assert_eq!(
+        messages(schema.clone(), json!({"name": "a", "Times": 2})),
+        vec!["unknown property 'Times', did you mean 'times'?"]
+    );
+    assert_eq!(
+        messages(schema, json!({"name": "a", "color": "red"})),
+        vec!["unknown property 'color'"]
+    );
+}
+
+#[test]
+fn validate_additional_properties_schema() {
+    let schema = json!({"type": "object", "additionalProperties": {"type": "number"}});
+    assert!(validate(&schema, &json!({"a": 1, "b": 2.5})).is_empty());
+    assert_eq!(
+        messages(schema, json!({"a": "x"})),
+        vec!["/a: expected number, got string"]
+    );
+}
+
+#[rstest]
+#[case(json!(null), "null")]
+#[case(json!(true), "boolean")]
+#[case(json!(1), "integer")]
+#[case(json!(1.5), "number")]
+#[case(json!("s"), "string")]
+#[case(json!([]), "array")]
+#[case(json!({}), "object")]
+fn json_type_names(#[case] value: Value, #[case] expected: &str) {
+    assert_eq!(json_type_name(&value), expected);
+}
+
+// ── PluginCommand schemas ─────────────────────────────────────────────────────
+
+#[test]
+fn effective_schema_derived_from_names() {
+    let cmd = PluginCommand::new("copy", "Copy")
+        .with_required(&["from", "to"])
+        .with_optional(&["overwrite"]);
+    let schema = cmd.effective_args_schema();
+    assert_eq!(schema["required"], json!(["from", "to"]));
+    assert_eq!(schema["additionalProperties"], json!(false));
+    assert!(schema["properties"].get("overwrite").is_some());
+}
+
+#[test]
+fn explicit_schema_overrides_names() {
+    let schema = json!({"type": "object"});
+    let cmd = PluginCommand::new("x", "X")
+        .with_required(&["a"])
+        .with_args_schema(schema.clone());
+    assert_eq!(cmd.effective_args_schema(), schema);
+}
+
+#[test]
+fn validate_args_treats_null_as_empty_object() {
+    let cmd = PluginCommand::new("ping", "Ping");
+    assert!(cmd.validate_args(&Value::Null).is_ok());
+    let cmd = PluginCommand::new("read", "Read").with_required(&["path"]);
+    assert!(cmd.validate_args(&Value::Null).is_err());
+}
+
+#[test]
+fn validate_args_error_is_structured() {
+    let err = hello_command()
+        .validate_args(&json!({"nmae": "Alice"}))
+        .unwrap_err();
+    assert_eq!(err.error_code(), PluginErrorCode::InvalidArgs);
+    assert!(err.message().starts_with("Invalid arguments for 'hello'"));
+
+    let violations = &err.details().unwrap()["violations"];
+    assert_eq!(
+        violations,
+        &json!([
+            {"path": "", "message": "missing required property 'name'"},
+            {"path": "", "message": "unknown property 'nmae', did you mean 'name'?"},
+        ])
+    );
+}
+
+#[test]
+fn validate_result_reports_violations() {
+    let cmd = hello_command();
+    assert!(cmd.validate_result(&json!({"message": "hi"})).is_empty());
+    assert_eq!(cmd.validate_result(&json!({"message": 1})).len(), 1);
+    assert!(PluginCommand::new("x", "X")
+        .validate_result(&json!(1))
+        .is_empty());
+}
+
+#[test]
+fn command_schema_serde_roundtrip() {
+    let cmd = hello_command();
+    let json = serde_json::to_value(&cmd).unwrap();
+    assert!(json.get("args_schema").is_some());
+    let back: PluginCommand = serde_json::from_value(json).unwrap();
+    assert_eq!(back.args_schema, cmd.args_schema);
+    assert_eq!(back.result_schema, cmd.result_schema);
+
+    let plain = serde_json::to_value(PluginCommand::new("x", "X")).unwrap();
+    assert!(plain.get("args_schema").is_none());
+}
+
+// ── Router validation ─────────────────────────────────────────────────────────
+
+#[test]
+fn router_imports_handler_specs() {
+    let router = router();
+    assert!(router.command_spec("greet", "hello").is_some());
+    assert!(router.command_spec("greet", "untyped").is_none());
+    assert_eq!(router.command_specs().len(), 1);
+}
+
+#[test]
+fn router_accepts_valid_args() {
+    let resp = router().handle(PluginRequest::new(
+        "greet",
+        "hello",
+        json!({"name": "Alice", "times": 2}),
+    ));
+    assert!(resp.success);
+    assert_eq!(resp.data.unwrap()["message"], "Hello, Alice!");
+}
+
+#[test]
+fn router_rejects_misnamed_args_before_dispatch() {
+    let resp = router()
+        .handle(PluginRequest::new("greet", "hello", json!({"nmae": "Alice"})).with_id("req-1"));
+    assert!(!resp.success);
+    assert_eq!(resp.code.as_deref(), Some("INVALID_ARGS"));
+    assert_eq!(resp.id.as_deref(), Some("req-1"));
+    let details = resp.details.unwrap();
+    assert_eq!(details["violations"].as_array().unwrap().len(), 2);
+    assert!(resp.error.unwrap().contains("did you mean 'name'?"));
+}
+
+#[rstest]
+#[case(json!({"name": 42}))]
+#[case(json!({"name": ""}))]
+#[case(json!({"name": "a", "times": 1.5}))]
+#[case(json!(["Alice"]))]
+#[case(json!(null))]
+fn router_rejects_invalid_args(#[case] args: Value) {
+    let resp = router().handle(PluginRequest::new("greet", "hello", args));
+    assert_eq!(resp.code.as_deref(), Some("INVALID_ARGS"));
+}
+
+#[test]
+fn router_skips_validation_without_spec() {
+    let resp = router().handle(PluginRequest::new("greet", "untyped", json!({"any": 1})));
+    assert!(resp.success);
+}
+
+#[test]
+fn router_register_command_adds_spec() {
+    let mut router = router();
+    router.register_command(
+        "greet",
+        PluginCommand::new("untyped", "Now typed").with_required(&["value"]),
+    );
+    let resp = router.handle(PluginRequest::new("greet", "untyped", json!({})));
+    assert_eq!(resp.code.as_deref(), Some("INVALID_ARGS"));
+}
+
+#[test]
+fn router_unregister_removes_specs() {
+    let mut router = router();
+    router.unregister("greet");
+    assert!(router.command_spec("greet", "hello").is_none());
+}
+
+#[test]
+fn response_from_error_carries_details() {
+    let err = PluginError::invalid_args("bad").with_details(json!({"violations": []}));
+    let resp = PluginResponse::from_error(&err);
+    assert_eq!(resp.code.as_deref(), Some("INVALID_ARGS"));
+    assert_eq!(resp.details, Some(json!({"violations": []})));
+
+    let json = serde_json::to_value(PluginResponse::ok(json!(1))).unwrap();
+    assert!(json.get("details").is_none());
+}
+
+// ── Code generation ───────────────────────────────────────────────────────────
+
+#[test]
+fn typescript_declares_command_map() {
+    let cmd = hello_command();
+    let ts = typescript_definitions([("greet", &cmd)]);
+    assert!(ts.contains("export interface GreetHelloArgs {"));
+    assert!(ts.contains("  name: string;"));
+    assert!(ts.contains("  times?: number;"));
+    assert!(ts.contains("export interface GreetHelloResult {"));
+    assert!(ts.contains("interface AuroraViewCommands {"));
+    assert!(ts.contains("\"plugin:greet|hello\": {"));
+    assert!(ts.contains("args: GreetHelloArgs;"));
+    assert!(ts.contains("result: GreetHelloResult;"));
+    assert!(ts.contains("/** Greet someone */"));
+    assert!(ts.trim_end().ends_with("export {};"));
+}
+
+#[rstest]
+#[case(json!({"type": "string"}), "export type XYResult = string;")]
+#[case(json!({"type": ["string", "null"]}), "export type XYResult = string | null;")]
+#[case(json!({"enum": ["a", 1]}), "export type XYResult = \"a\" | 1;")]
+#[case(json!({"type": "array", "items": {"type": "integer"}}), "export type XYResult = Array<number>;")]
+#[case(json!({"type": "object", "additionalProperties": {"type": "boolean"}}), "export type XYResult = Record<string, boolean>;")]
+fn typescript_type_mapping(#[case] schema: Value, #[case] expected: &str) {
+    let cmd = PluginCommand::new("y", "").with_result_schema(schema);
+    let ts = typescript_definitions([("x", &cmd)]);
+    assert!(ts.contains(expected), "{}", ts);
+}
+
+#[test]
+fn typescript_quotes_non_identifier_keys() {
+    let cmd = PluginCommand::new("copy", "").with_required(&["dry-run"]);
+    let ts = typescript_definitions([("fs", &cmd)]);
+    assert!(ts.contains("  \"dry-run\": unknown;"));
+}
+
+#[test]
+fn python_stub_declares_typed_dicts_and_overloads() {
+    let cmd = hello_command();
+    let pyi = python_stubs([("greet", &cmd)]);
+    assert!(pyi.contains("class _GreetHelloArgsRequired(TypedDict):\n    name: str\n"));
+    assert!(pyi
+        .contains("class GreetHelloArgs(_GreetHelloArgsRequired, total=False):\n    times: int\n"));
+    assert!(pyi.contains("class GreetHelloResult(TypedDict):\n    message: str\n"));
+    assert!(pyi.contains("CommandName = Literal[\n    \"plugin:greet|hello\",\n]"));
+    assert!(pyi.contains(
+        "    def invoke(self, command: Literal[\"plugin:greet|hello\"], args: GreetHelloArgs) -> GreetHelloResult: ..."
+    ));
+    assert!(pyi.contains(
+        "def invoke(self, command: str, args: Optional[Dict[str, Any]] = None) -> Any: ..."
+    ));
+}
+
+#[test]
+fn python_stub_uses_functional_syntax_for_keywords() {
+    let cmd = PluginCommand::new("copy", "").with_required(&["from", "to"]);
+    let pyi = python_stubs([("fs", &cmd)]);
+    assert!(pyi.contains(
+        "FsCopyArgs = TypedDict(\"FsCopyArgs\", {\"from\": Any, \"to\": Any}, total=True)"
+    ));
+}
+
+#[test]
+fn python_stub_optional_args_have_default() {
+    let cmd = PluginCommand::new("ping", "");
+    let pyi = python_stubs([("net", &cmd)]);
+    assert!(pyi.contains("args: NetPingArgs = ..."));
+}
+
+#[test]
+fn router_generates_from_registry() {
+    let router = router();
+    assert!(router
+        .typescript_definitions()
+        .contains("\"plugin:greet|hello\""));
+    assert!(router.python_stubs().contains("\"plugin:greet|hello\""));
+    assert!(!router.typescript_definitions().contains("untyped"));
+}
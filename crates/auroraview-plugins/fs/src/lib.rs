@@ -27,8 +27,10 @@ pub use operations::*;
 /// Request/response types and options for file system commands.
 pub use types::*;
 
-use auroraview_plugin_core::{PluginError, PluginHandler, PluginResult, ScopeConfig};
-use serde_json::Value;
+use auroraview_plugin_core::{
+    PluginCommand, PluginError, PluginHandler, PluginResult, ScopeConfig,
+};
+use serde_json::{json, Value};
 
 /// File system plugin
 pub struct FsPlugin {
@@ -143,4 +145,130 @@ impl PluginHandler for FsPlugin {
             "stat",
         ]
     }
+
+    fn command_specs(&self) -> Vec<PluginCommand> {
+        let success = json!({
+            "type": "object",
+            "properties": {"success": {"type": "boolean"}},
+            "required": ["success"],
+        });
+        let dir_entry = json!({
+            "type": "object",
+            "properties": {
+                "name": {"type": "string"},
+                "path": {"type": "string"},
+                "isDirectory": {"type": "boolean"},
+                "isFile": {"type": "boolean"},
+                "isSymlink": {"type": "boolean"},
+            },
+            "required": ["name", "path", "isDirectory", "isFile", "isSymlink"],
+        });
+        let timestamp = json!({"type": "integer", "description": "Unix timestamp in ms"});
+
+        vec![
+            PluginCommand::new("read_file", "Read a text file")
+                .with_args_schema(path_args(json!({
+                    "encoding": {"type": ["string", "null"], "description": "Text encoding (default: utf-8)"},
+                })))
+                .with_result_schema(json!({"type": "string"})),
+            PluginCommand::new("read_file_binary", "Read a file as base64")
+                .with_args_schema(path_args(json!({})))
+                .with_result_schema(json!({"type": "string", "description": "Base64-encoded contents"})),
+            PluginCommand::new("write_file", "Write text to a file")
+                .with_args_schema(path_args_with(
+                    json!({
+                        "contents": {"type": "string"},
+                        "append": {"type": "boolean"},
+                    }),
+                    &["contents"],
+                ))
+                .with_result_schema(success.clone()),
+            PluginCommand::new("write_file_binary", "Write bytes to a file")
+                .with_args_schema(path_args_with(
+                    json!({
+                        "contents": {
+                            "type": "array",
+                            "items": {"type": "integer", "minimum": 0, "maximum": 255},
+                        },
+                        "append": {"type": "boolean"},
+                    }),
+                    &["contents"],
+                ))
+                .with_result_schema(success.clone()),
+            PluginCommand::new("read_dir", "List directory contents")
+                .with_args_schema(path_args(json!({"recursive": {"type": "boolean"}})))
+                .with_result_schema(json!({"type": "array", "items": dir_entry})),
+            PluginCommand::new("create_dir", "Create a directory")
+                .with_args_schema(path_args(json!({
+                    "recursive": {"type": "boolean", "description": "Create parents (default: true)"},
+                })))
+                .with_result_schema(success.clone()),
+            PluginCommand::new("remove", "Remove a file or directory")
+                .with_args_schema(path_args(json!({"recursive": {"type": "boolean"}})))
+                .with_result_schema(success.clone()),
+            PluginCommand::new("copy", "Copy a file or directory")
+                .with_args_schema(from_to_args())
+                .with_result_schema(success.clone()),
+            PluginCommand::new("rename", "Rename or move a file or directory")
+                .with_args_schema(from_to_args())
+                .with_result_schema(success),
+            PluginCommand::new("exists", "Check if a path exists")
+                .with_args_schema(path_args(json!({})))
+                .with_result_schema(json!({
+                    "type": "object",
+                    "properties": {"exists": {"type": "boolean"}},
+                    "required": ["exists"],
+                })),
+            PluginCommand::new("stat", "Get file or directory metadata")
+                .with_args_schema(path_args(json!({})))
+                .with_result_schema(json!({
+                    "type": "object",
+                    "properties": {
+                        "isDirectory": {"type": "boolean"},
+                        "isFile": {"type": "boolean"},
+                        "isSymlink": {"type": "boolean"},
+                        "size": {"type": "integer"},
+                        "modifiedAt": timestamp,
+                        "createdAt": timestamp,
+                        "accessedAt": timestamp,
+                        "readonly": {"type": "boolean"},
+                    },
+                    "required": ["isDirectory", "isFile", "isSymlink", "size", "readonly"],
+                })),
+        ]
+    }
+}
+
+/// Argument schema with a required `path` plus optional `extra` properties
+fn path_args(extra: Value) -> Value {
+    path_args_with(extra, &[])
+}
+
+/// Argument schema with a required `path` plus `extra` properties
+fn path_args_with(extra: Value, required: &[&str]) -> Value {
+    let mut properties = json!({"path": {"type": "string"}});
+    if let (Some(properties), Value::Object(extra)) = (properties.as_object_mut(), extra) {
+        properties.extend(extra);
+    }
+    let mut names = vec!["path"];
+    names.extend_from_slice(required);
+    json!({
+        "type": "object",
+        "properties": properties,
+        "required": names,
+        "additionalProperties": false,
+    })
+}
+
+/// Argument schema of `copy` and `rename`
+fn from_to_args() -> Value {
+    json!({
+        "type": "object",
+        "properties": {
+            "from": {"type": "string", "description": "Source path"},
+            "to": {"type": "string", "description": "Destination path"},
+        },
+        "required": ["from", "to"],
+        "additionalProperties": false,
+    })
 }
@@ -518,3 +518,75 @@ fn plugin_commands_contains(#[case] cmd: &str) {
     let plugin = FsPlugin::new();
     assert!(plugin.commands().contains(&cmd), "missing command: {cmd}");
 }
+
+// ─── command schemas ─────────────────────────────────────────────────────────
+
+#[test]
+fn plugin_command_specs_cover_commands() {
+    let plugin = FsPlugin::new();
+    let specs = plugin.command_specs();
+    let mut names: Vec<&str> = specs.iter().map(|spec| spec.name.as_str()).collect();
+    let mut commands = plugin.commands();
+    names.sort_unstable();
+    commands.sort_unstable();
+    assert_eq!(names, commands);
+    assert!(specs.iter().all(|spec| spec.result_schema.is_some()));
+}
+
+#[rstest]
+#[case("read_file", json!({"path": "/a", "encoding": null}))]
+#[case("write_file", json!({"path": "/a", "contents": "x", "append": true}))]
+#[case("write_file_binary", json!({"path": "/a", "contents": [0, 255]}))]
+#[case("copy", json!({"from": "/a", "to": "/b"}))]
+#[case("stat", json!({"path": "/a"}))]
+fn plugin_command_specs_accept_sdk_args(#[case] cmd: &str, #[case] args: serde_json::Value) {
+    let specs = FsPlugin::new().command_specs();
+    let spec = specs.iter().find(|spec| spec.name == cmd).unwrap();
+    assert!(spec.validate_args(&args).is_ok());
+}
+
+#[rstest]
+#[case("read_file", json!({"filePath": "/a"}))]
+#[case("write_file", json!({"path": "/a", "content": "x"}))]
+#[case("write_file_binary", json!({"path": "/a", "contents": [256]}))]
+#[case("copy", json!({"src": "/a", "to": "/b"}))]
+#[case("read_dir", json!({"path": "/a", "recursive": "yes"}))]
+fn plugin_command_specs_reject_bad_args(#[case] cmd: &str, #[case] args: serde_json::Value) {
+    let specs = FsPlugin::new().command_specs();
+    let spec = specs.iter().find(|spec| spec.name == cmd).unwrap();
+    assert!(spec.validate_args(&args).is_err());
+}
+
+#[test]
+fn plugin_results_match_result_schemas() {
+    let dir = TempDir::new().unwrap();
+    let path = dir.path().join("schema.txt");
+    let path = path.to_str().unwrap();
+    let plugin = FsPlugin::new();
+    let scope = make_scope_config(&dir);
+    let specs = plugin.command_specs();
+
+    let calls = [
+        ("write_file", json!({"path": path, "contents": "x"})),
+        ("read_file", json!({"path": path})),
+        ("read_file_binary", json!({"path": path})),
+        ("exists", json!({"path": path})),
+        ("stat", json!({"path": path})),
+        ("read_dir", json!({"path": dir.path().to_str().unwrap()})),
+    ];
+    for (cmd, args) in calls {
+        let spec = specs.iter().find(|spec| spec.name == cmd).unwrap();
+        let result = plugin.handle(cmd, args, &scope).unwrap();
+        assert_eq!(spec.validate_result(&result), vec![], "{cmd}");
+    }
+}
+
+#[test]
+fn router_rejects_misnamed_fs_args() {
+    let mut router = auroraview_plugin_core::PluginRouter::new();
+    router.register("fs", std::sync::Arc::new(FsPlugin::new()));
+    let req = auroraview_plugin_core::PluginRequest::new("fs", "read_file", json!({"pth": "/a"}));
+    let resp = router.handle(req);
+    assert_eq!(resp.code.as_deref(), Some("INVALID_ARGS"));
+    assert!(resp.error.unwrap().contains("did you mean 'path'?"));
+}
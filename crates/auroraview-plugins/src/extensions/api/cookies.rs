@@ -0,0 +1,24 @@
+use serde_json::Value;
+
+use crate::extensions::ExtensionsPlugin;
+use auroraview_extensions::ExtensionError;
+use auroraview_plugin_core::{PluginError, PluginResult};
+
+impl ExtensionsPlugin {
+    /// Handle cookies API calls
+    pub fn handle_cookies_api(
+        &self,
+        _extension_id: &str,
+        method: &str,
+        params: &Value,
+    ) -> PluginResult<Value> {
+        self.cookies
+            .handle(method, params.clone())
+            .map_err(|e| match e {
+                ExtensionError::UnknownMethod(method) => {
+                    PluginError::command_not_found(&format!("cookies.{}", method))
+                }
+                e => PluginError::invalid_args(e.to_string()),
+            })
+    }
+}
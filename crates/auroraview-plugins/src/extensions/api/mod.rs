@@ -9,6 +9,7 @@ mod action;
 mod alarms;
 mod commands;
 mod context_menus;
+mod cookies;
 mod declarative_net_request;
 mod identity;
 mod management;
@@ -52,6 +52,7 @@ mod plugin;
 use std::collections::HashMap;
 use std::sync::Arc;
 
+use auroraview_extensions::apis::cookies::{CookieStoreBackend, CookiesApi};
 use parking_lot::RwLock;
 use serde_json::Value;
 
@@ -105,6 +106,7 @@ pub struct ExtensionsPlugin {
     name: String,
     state: Arc<RwLock<ExtensionsState>>,
     callbacks: Arc<RwLock<ExtensionsCallbacks>>,
+    cookies: CookiesApi,
 }
 
 impl ExtensionsPlugin {
@@ -114,6 +116,7 @@ impl ExtensionsPlugin {
             name: "extensions".to_string(),
             state: Arc::new(RwLock::new(ExtensionsState::default())),
             callbacks: Arc::new(RwLock::new(ExtensionsCallbacks::default())),
+            cookies: CookiesApi::new(),
         }
     }
 
@@ -123,9 +126,18 @@ impl ExtensionsPlugin {
             name: "extensions".to_string(),
             state,
             callbacks: Arc::new(RwLock::new(ExtensionsCallbacks::default())),
+            cookies: CookiesApi::new(),
         }
     }
 
+    /// Serve `chrome.cookies` from `store`, e.g. the WebView's cookie jar
+    ///
+    /// Without it extensions get a private in-memory store.
+    pub fn with_cookie_store(mut self, store: Arc<dyn CookieStoreBackend>) -> Self {
+        self.cookies = CookiesApi::with_store(store);
+        self
+    }
+
     /// Get the shared state
     pub fn state(&self) -> Arc<RwLock<ExtensionsState>> {
         self.state.clone()
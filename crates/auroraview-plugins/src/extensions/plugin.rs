@@ -44,6 +44,9 @@ impl PluginHandler for crate::extensions::ExtensionsPlugin {
                         self.handle_scripting_api(&req.extension_id, &req.method, &req.params)
                     }
                     "alarms" => self.handle_alarms_api(&req.extension_id, &req.method, &req.params),
+                    "cookies" => {
+                        self.handle_cookies_api(&req.extension_id, &req.method, &req.params)
+                    }
                     "notifications" => {
                         self.handle_notifications_api(&req.extension_id, &req.method, &req.params)
                     }
@@ -45,7 +45,8 @@
 /// Core plugin framework types re-exported from `auroraview-plugin-core`.
 pub use auroraview_plugin_core::{
     PathScope, PluginCommand, PluginError, PluginErrorCode, PluginEventCallback, PluginHandler,
-    PluginRequest, PluginResponse, PluginResult, PluginRouter, ScopeConfig, ScopeError, ShellScope,
+    PluginRequest, PluginResponse, PluginResult, PluginRouter, SchemaViolation, ScopeConfig,
+    ScopeError, ShellScope,
 };
 
 /// File system plugin providing read, write, list, and metadata operations.
@@ -68,6 +69,14 @@ use std::sync::Arc;
 
 /// Create a plugin router with all built-in plugins registered
 pub fn create_router() -> PluginRouter {
+    create_router_with_extensions(extensions::ExtensionsPlugin::new())
+}
+
+/// Create a plugin router that serves the Chrome Extension APIs from `extensions`
+///
+/// Use it to share state with the host, such as the WebView's cookie jar via
+/// [`ExtensionsPlugin::with_cookie_store`](extensions::ExtensionsPlugin::with_cookie_store).
+pub fn create_router_with_extensions(extensions: extensions::ExtensionsPlugin) -> PluginRouter {
     let mut router = PluginRouter::new();
     let event_callback = router.event_callback_ref();
 
@@ -87,7 +96,7 @@ pub fn create_router() -> PluginRouter {
     router.register("browser_bridge", Arc::new(browser_bridge_plugin));
 
     // Register extensions plugin for Chrome Extension API compatibility
-    router.register("extensions", Arc::new(extensions::ExtensionsPlugin::new()));
+    router.register("extensions", Arc::new(extensions));
 
     router
 }
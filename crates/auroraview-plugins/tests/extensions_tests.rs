@@ -7,6 +7,7 @@ use parking_lot::RwLock;
 use rstest::*;
 use serde_json::{json, Value};
 
+use auroraview_extensions::apis::cookies::{CookieStoreBackend, MemoryCookieStore};
 use auroraview_plugins::extensions::*;
 use auroraview_plugins::PluginHandler;
 
@@ -566,6 +567,34 @@ fn test_web_request_add_listener(plugin: ExtensionsPlugin) {
     assert!(result.is_ok());
 }
 
+// ============================================================
+// Cookies API tests
+// ============================================================
+
+#[rstest]
+fn test_cookies_use_shared_store() {
+    let store: Arc<dyn CookieStoreBackend> = Arc::new(MemoryCookieStore::new());
+    let writer = ExtensionsPlugin::new().with_cookie_store(store.clone());
+    let reader = ExtensionsPlugin::new().with_cookie_store(store);
+
+    let params = json!({ "url": "https://example.com", "name": "sid", "value": "abc" });
+    writer
+        .handle_cookies_api("test-ext", "set", &params)
+        .unwrap();
+
+    let params = json!({ "url": "https://example.com", "name": "sid" });
+    let cookie = reader
+        .handle_cookies_api("test-ext", "get", &params)
+        .unwrap();
+    assert_eq!(cookie["value"], "abc");
+}
+
+#[rstest]
+fn test_cookies_unknown_method(plugin: ExtensionsPlugin) {
+    let result = plugin.handle_cookies_api("test-ext", "bogus", &json!({}));
+    assert!(result.is_err());
+}
+
 // ============================================================
 // Callback registration tests
 // ============================================================
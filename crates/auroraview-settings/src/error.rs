@@ -2,6 +2,8 @@
 
 use thiserror::Error;
 
+use crate::layer::SettingsLayer;
+
 /// Result type for settings operations.
 pub type Result<T> = std::result::Result<T, SettingsError>;
 
@@ -24,6 +26,18 @@ pub enum SettingsError {
     #[error("Validation failed for setting '{key}': {reason}")]
     ValidationFailed { key: String, reason: String },
 
+    /// Setting is locked by a lower layer.
+    #[error("Setting '{key}' is locked by the {layer} layer")]
+    Locked { key: String, layer: SettingsLayer },
+
+    /// Settings file was written by a newer schema version.
+    #[error("Settings version {found} is newer than supported version {supported}")]
+    UnsupportedVersion { found: u32, supported: u32 },
+
+    /// A migration step failed.
+    #[error("Migration failed: {0}")]
+    Migration(String),
+
     /// Invalid key format.
     #[error("Invalid setting key: {0}")]
     InvalidKey(String),
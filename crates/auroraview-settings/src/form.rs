@@ -0,0 +1,248 @@
+//! Settings form description for preference pages.
+//!
+//! [`SettingsManager::form`](crate::SettingsManager::form) turns the schema
+//! registry and the current values into a categorized form the frontend can
+//! render without knowing individual settings. Submissions come back through
+//! [`SettingsManager::submit_form`](crate::SettingsManager::submit_form), which
+//! reports validation errors per key.
+
+use std::collections::BTreeMap;
+
+use serde::Serialize;
+
+use crate::layer::{Provenance, SettingsLayer};
+use crate::schema::{SchemaRegistry, SchemaType, SettingSchema};
+use crate::value::SettingValue;
+
+/// Section id used for schemas without a category.
+pub const GENERAL_CATEGORY: &str = "general";
+
+/// Categorized form describing every registered setting.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SettingsForm {
+    /// Sections sorted by category, with uncategorized settings last.
+    pub sections: Vec<FormSection>,
+}
+
+impl SettingsForm {
+    /// Finds the field for a key.
+    pub fn field(&self, key: &str) -> Option<&FormField> {
+        self.sections
+            .iter()
+            .flat_map(|s| &s.fields)
+            .find(|f| f.key == key)
+    }
+}
+
+/// A group of fields sharing a category.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FormSection {
+    /// Category id.
+    pub id: String,
+    /// Display title derived from the id.
+    pub title: String,
+    /// Fields sorted by key.
+    pub fields: Vec<FormField>,
+}
+
+/// A single setting in the form.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FormField {
+    /// The setting key.
+    pub key: String,
+    /// Human-readable title.
+    pub title: String,
+    /// Description shown below the control.
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub description: String,
+    /// Control to render.
+    pub widget: FormWidget,
+    /// Current effective value.
+    pub value: SettingValue,
+    /// Schema default.
+    pub default: SettingValue,
+    /// Layer supplying the current value.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub source: Option<SettingsLayer>,
+    /// Layer locking the key; the control should be read-only.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub locked_by: Option<SettingsLayer>,
+    /// Whether changing the setting requires a restart.
+    pub requires_restart: bool,
+    /// Deprecation message, if deprecated.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub deprecated: Option<String>,
+}
+
+/// Control used to edit a setting.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "kind", rename_all = "camelCase")]
+pub enum FormWidget {
+    /// Checkbox or switch.
+    Toggle,
+    /// Numeric input.
+    #[serde(rename_all = "camelCase")]
+    Number {
+        #[serde(skip_serializing_if = "Option::is_none")]
+        min: Option<f64>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        max: Option<f64>,
+        /// Only whole numbers are accepted.
+        integer: bool,
+    },
+    /// Single-line text input.
+    #[serde(rename_all = "camelCase")]
+    Text {
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pattern: Option<String>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        max_length: Option<usize>,
+    },
+    /// Drop-down with fixed options.
+    Select { options: Vec<String> },
+    /// Editable list of values.
+    List,
+    /// Raw JSON editor.
+    Json,
+}
+
+impl From<&SchemaType> for FormWidget {
+    fn from(schema_type: &SchemaType) -> Self {
+        match schema_type {
+            SchemaType::Bool => Self::Toggle,
+            SchemaType::Integer { min, max } => Self::Number {
+                min: min.map(|v| v as f64),
+                max: max.map(|v| v as f64),
+                integer: true,
+            },
+            SchemaType::Float { min, max } => Self::Number {
+                min: *min,
+                max: *max,
+                integer: false,
+            },
+            SchemaType::String {
+                pattern,
+                max_length,
+            } => Self::Text {
+                pattern: pattern.clone(),
+                max_length: *max_length,
+            },
+            SchemaType::Enum { values } => Self::Select {
+                options: values.clone(),
+            },
+            SchemaType::Array { .. } => Self::List,
+            SchemaType::Object => Self::Json,
+        }
+    }
+}
+
+/// Outcome of a form submission.
+///
+/// Submissions are all-or-nothing: if any key fails, nothing is applied.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FormSubmission {
+    /// Keys that were written or reset.
+    pub applied: Vec<String>,
+    /// Error messages by key.
+    pub errors: BTreeMap<String, String>,
+}
+
+impl FormSubmission {
+    /// Returns true if the submission was applied.
+    pub fn is_ok(&self) -> bool {
+        self.errors.is_empty()
+    }
+}
+
+/// Builds the form for `registry`, looking up current values with `inspect`.
+pub(crate) fn build(
+    registry: &SchemaRegistry,
+    inspect: impl Fn(&str) -> Provenance,
+) -> SettingsForm {
+    let mut sections: BTreeMap<&str, Vec<FormField>> = BTreeMap::new();
+    for schema in registry.all() {
+        let category = schema.category.as_deref().unwrap_or(GENERAL_CATEGORY);
+        sections
+            .entry(category)
+            .or_default()
+            .push(field(schema, inspect(&schema.key)));
+    }
+
+    let mut sections: Vec<FormSection> = sections
+        .into_iter()
+        .map(|(id, mut fields)| {
+            fields.sort_by(|a, b| a.key.cmp(&b.key));
+            FormSection {
+                id: id.to_string(),
+                title: title_case(id),
+                fields,
+            }
+        })
+        .collect();
+    // Uncategorized settings go last
+    sections.sort_by_key(|s| s.id == GENERAL_CATEGORY);
+    SettingsForm { sections }
+}
+
+fn field(schema: &SettingSchema, provenance: Provenance) -> FormField {
+    FormField {
+        key: schema.key.clone(),
+        title: schema.title.clone(),
+        description: schema.description.clone(),
+        widget: FormWidget::from(&schema.schema_type),
+        value: provenance.value.unwrap_or_default(),
+        default: schema.default.clone(),
+        source: provenance.layer,
+        locked_by: provenance.locked_by,
+        requires_restart: schema.requires_restart,
+        deprecated: schema.deprecated.clone(),
+    }
+}
+
+/// Turns `network_proxy` or `dev-tools` into `Network Proxy` / `Dev Tools`.
+fn title_case(id: &str) -> String {
+    id.split(['_', '-', '.', ' '])
+        .filter(|w| !w.is_empty())
+        .map(|w| {
+            let mut chars = w.chars();
+            match chars.next() {
+                Some(first) => first.to_uppercase().chain(chars).collect(),
+                None => String::new(),
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_title_case() {
+        assert_eq!(title_case("appearance"), "Appearance");
+        assert_eq!(title_case("network_proxy"), "Network Proxy");
+        assert_eq!(title_case("dev-tools"), "Dev Tools");
+    }
+
+    #[test]
+    fn test_widget_from_type() {
+        assert_eq!(FormWidget::from(&SchemaType::Bool), FormWidget::Toggle);
+        assert_eq!(
+            FormWidget::from(&SchemaType::Integer {
+                min: Some(1),
+                max: None
+            }),
+            FormWidget::Number {
+                min: Some(1.0),
+                max: None,
+                integer: true
+            }
+        );
+        assert_eq!(FormWidget::from(&SchemaType::Object), FormWidget::Json);
+    }
+}
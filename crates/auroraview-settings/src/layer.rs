@@ -0,0 +1,291 @@
+//! Settings layers and value provenance.
+//!
+//! Settings are resolved from a stack of layers, lowest precedence first:
+//!
+//! | Layer | Typical source |
+//! |-------|----------------|
+//! | [`SettingsLayer::Default`] | Schema defaults |
+//! | [`SettingsLayer::System`] | Studio-wide file managed by pipeline TDs |
+//! | [`SettingsLayer::User`] | The artist's own settings file |
+//! | [`SettingsLayer::Workspace`] | Show or project file |
+//! | [`SettingsLayer::CommandLine`] | `key=value` arguments for one session |
+//!
+//! A layer may lock a key, which pins the value resolved at or below that
+//! layer and makes every higher layer ignore (and refuse) overrides.
+
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::{Result, SettingsError};
+use crate::migration::{SettingsMigrator, VERSION_KEY};
+use crate::schema::SchemaRegistry;
+use crate::store::SettingsStore;
+use crate::value::SettingValue;
+
+/// Reserved key in layer files that lists the keys locked by the layer.
+pub const LOCKED_KEY: &str = "$locked";
+
+/// A settings scope with a fixed precedence.
+///
+/// Variants are ordered by precedence, so `SettingsLayer::User <
+/// SettingsLayer::Workspace` means workspace values override user values.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum SettingsLayer {
+    /// Schema defaults.
+    Default,
+    /// System-wide settings.
+    System,
+    /// Per-user settings.
+    User,
+    /// Per-workspace (show or project) settings.
+    Workspace,
+    /// Settings passed on the command line.
+    CommandLine,
+}
+
+impl SettingsLayer {
+    /// All layers, lowest precedence first.
+    pub const ALL: [SettingsLayer; 5] = [
+        Self::Default,
+        Self::System,
+        Self::User,
+        Self::Workspace,
+        Self::CommandLine,
+    ];
+
+    /// Returns the layer name.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Default => "default",
+            Self::System => "system",
+            Self::User => "user",
+            Self::Workspace => "workspace",
+            Self::CommandLine => "commandLine",
+        }
+    }
+
+    pub(crate) fn index(self) -> usize {
+        self as usize
+    }
+}
+
+impl fmt::Display for SettingsLayer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Where the effective value of a setting comes from.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Provenance {
+    /// The setting key.
+    pub key: String,
+    /// The effective value, if any layer defines one.
+    pub value: Option<SettingValue>,
+    /// The layer that supplied the effective value.
+    pub layer: Option<SettingsLayer>,
+    /// Values defined by each layer, lowest precedence first.
+    pub layers: Vec<(SettingsLayer, SettingValue)>,
+    /// The lowest layer that locks the key.
+    pub locked_by: Option<SettingsLayer>,
+}
+
+impl Provenance {
+    /// Returns the value a layer defines for the key.
+    pub fn value_in(&self, layer: SettingsLayer) -> Option<&SettingValue> {
+        self.layers
+            .iter()
+            .find(|(l, _)| *l == layer)
+            .map(|(_, v)| v)
+    }
+
+    /// Returns layers whose value is ignored because of a lock.
+    pub fn overridden_by_lock(&self) -> Vec<SettingsLayer> {
+        match self.locked_by {
+            Some(lock) => self
+                .layers
+                .iter()
+                .map(|(l, _)| *l)
+                .filter(|l| *l > lock)
+                .collect(),
+            None => Vec::new(),
+        }
+    }
+}
+
+/// Values, locks and backing file of one layer.
+#[derive(Debug, Default, Clone)]
+pub(crate) struct LayerState {
+    pub store: SettingsStore,
+    pub locked: HashSet<String>,
+    pub path: Option<PathBuf>,
+    /// Modification time and length of the file when it was last read.
+    pub fingerprint: Option<(SystemTime, u64)>,
+}
+
+impl LayerState {
+    /// Reads the layer file, validating every value against `registry`.
+    ///
+    /// A missing file yields an empty layer. Outdated files are migrated
+    /// first; the original is kept next to the file as `<name>.v<N>.bak`
+    /// and the migrated contents are written back.
+    pub fn read(
+        path: &Path,
+        registry: &SchemaRegistry,
+        migrator: Option<&SettingsMigrator>,
+    ) -> Result<(SettingsStore, HashSet<String>)> {
+        if !path.exists() {
+            return Ok(Default::default());
+        }
+
+        let content = std::fs::read_to_string(path)?;
+        let mut map: HashMap<String, SettingValue> = serde_json::from_str(&content)?;
+
+        // Migrate in memory; the file is only rewritten once the result validates
+        let migrated = match migrator {
+            Some(migrator) if migrator.needs_migration(&map) => {
+                let version = migrator.migrate(&mut map)?;
+                Some((version, to_pretty_json(&map)?))
+            }
+            Some(migrator) => {
+                // Rejects files written by a newer version
+                migrator.migrate(&mut map)?;
+                None
+            }
+            None => None,
+        };
+        map.remove(VERSION_KEY);
+
+        let locked = match map.remove(LOCKED_KEY) {
+            None => HashSet::new(),
+            Some(SettingValue::Array(keys)) => keys
+                .into_iter()
+                .map(|k| match k {
+                    SettingValue::String(k) => Ok(k),
+                    other => Err(SettingsError::InvalidKey(format!(
+                        "{LOCKED_KEY} entry must be a string, got {}",
+                        other.type_name()
+                    ))),
+                })
+                .collect::<Result<_>>()?,
+            Some(other) => {
+                return Err(SettingsError::InvalidKey(format!(
+                    "{LOCKED_KEY} must be an array, got {}",
+                    other.type_name()
+                )))
+            }
+        };
+
+        for (key, value) in &map {
+            validate(registry, key, value)?;
+        }
+
+        if let Some((version, json)) = migrated {
+            let mut backup = path.as_os_str().to_owned();
+            backup.push(format!(".v{version}.bak"));
+            std::fs::write(&backup, &content)?;
+            std::fs::write(path, json)?;
+        }
+
+        Ok((SettingsStore::from_map(map), locked))
+    }
+
+    /// Serializes the layer in the file format read by [`LayerState::read`].
+    pub fn to_json(&self, version: Option<u32>) -> Result<String> {
+        let mut map: HashMap<String, SettingValue> = self.store.as_map().clone();
+        if !self.locked.is_empty() {
+            let mut locked: Vec<_> = self.locked.iter().cloned().collect();
+            locked.sort();
+            map.insert(LOCKED_KEY.to_string(), locked.into());
+        }
+        if let Some(version) = version {
+            map.insert(VERSION_KEY.to_string(), i64::from(version).into());
+        }
+        to_pretty_json(&map)
+    }
+}
+
+/// Serializes a settings map with sorted keys.
+fn to_pretty_json(map: &HashMap<String, SettingValue>) -> Result<String> {
+    let sorted: std::collections::BTreeMap<_, _> = map.iter().collect();
+    Ok(serde_json::to_string_pretty(&sorted)?)
+}
+
+/// Returns the modification time and length of a file, if it exists.
+pub(crate) fn fingerprint(path: &Path) -> Option<(SystemTime, u64)> {
+    let meta = std::fs::metadata(path).ok()?;
+    Some((meta.modified().ok()?, meta.len()))
+}
+
+/// Validates a value against its schema, if one is registered.
+pub(crate) fn validate(registry: &SchemaRegistry, key: &str, value: &SettingValue) -> Result<()> {
+    match registry.get(key) {
+        Some(schema) => schema
+            .validate(value)
+            .map_err(|reason| SettingsError::ValidationFailed {
+                key: key.to_string(),
+                reason,
+            }),
+        None => Ok(()),
+    }
+}
+
+/// Parses a `key=value` command-line argument.
+///
+/// The value is read as JSON when possible (`true`, `42`, `[1, 2]`) and as a
+/// plain string otherwise.
+pub(crate) fn parse_arg(arg: &str) -> Result<(String, SettingValue)> {
+    let (key, raw) = arg
+        .split_once('=')
+        .ok_or_else(|| SettingsError::InvalidKey(arg.to_string()))?;
+    let key = key.trim();
+    if key.is_empty() || key == LOCKED_KEY || key == VERSION_KEY {
+        return Err(SettingsError::InvalidKey(arg.to_string()));
+    }
+    let value = serde_json::from_str(raw).unwrap_or_else(|_| SettingValue::String(raw.into()));
+    Ok((key.to_string(), value))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_layer_order() {
+        assert!(SettingsLayer::Default < SettingsLayer::System);
+        assert!(SettingsLayer::Workspace < SettingsLayer::CommandLine);
+        assert_eq!(SettingsLayer::ALL.len(), 5);
+        for (i, layer) in SettingsLayer::ALL.iter().enumerate() {
+            assert_eq!(layer.index(), i);
+        }
+        assert_eq!(SettingsLayer::CommandLine.to_string(), "commandLine");
+    }
+
+    #[test]
+    fn test_parse_arg() {
+        assert_eq!(
+            parse_arg("a.b=true").unwrap(),
+            ("a.b".to_string(), SettingValue::Bool(true))
+        );
+        assert_eq!(
+            parse_arg("n=42").unwrap(),
+            ("n".to_string(), SettingValue::Integer(42))
+        );
+        assert_eq!(
+            parse_arg("theme=dark").unwrap(),
+            ("theme".to_string(), SettingValue::String("dark".into()))
+        );
+        assert_eq!(
+            parse_arg("url=a=b").unwrap(),
+            ("url".to_string(), SettingValue::String("a=b".into()))
+        );
+        assert!(parse_arg("novalue").is_err());
+        assert!(parse_arg("=1").is_err());
+    }
+}
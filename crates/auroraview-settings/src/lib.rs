@@ -6,6 +6,10 @@
 //! - Default values and validation
 //! - Persistence to JSON files
 //! - Change notifications
+//! - Layered scopes (default, system, user, workspace, command line) with
+//!   per-key provenance and locks
+//! - JSON Schema export and generated preference forms
+//! - Versioned migrations of persisted settings files
 //!
 //! # Example
 //!
@@ -25,17 +29,28 @@
 //! ```
 
 mod error;
+mod form;
+mod layer;
 mod manager;
+mod migration;
 mod schema;
 mod store;
 mod value;
 
 /// Error and result types for settings operations.
 pub use error::{Result, SettingsError};
+/// Preference form description and submission results.
+pub use form::{
+    FormField, FormSection, FormSubmission, FormWidget, SettingsForm, GENERAL_CATEGORY,
+};
+/// Settings layers, precedence and provenance.
+pub use layer::{Provenance, SettingsLayer, LOCKED_KEY};
 /// High-level settings manager with type-safe access and persistence.
-pub use manager::SettingsManager;
+pub use manager::{SettingsManager, SettingsWatcher};
+/// Versioned migrations for settings files.
+pub use migration::{Migration, SettingsMap, SettingsMigrator, VERSION_KEY};
 /// Schema registry and validation types for setting definitions.
-pub use schema::{SchemaRegistry, SchemaType, SettingSchema};
+pub use schema::{SchemaRegistry, SchemaType, SettingSchema, JSON_SCHEMA_DIALECT};
 /// Low-level key-value settings store with JSON persistence.
 pub use store::SettingsStore;
 /// Typed setting value enum supporting string, bool, integer, float, and array.
@@ -1,11 +1,22 @@
 //! Settings manager with schema validation and persistence.
+//!
+//! Values live in a stack of [`SettingsLayer`]s. Reads resolve the highest
+//! layer that defines a key (respecting locks); [`SettingsManager::set`]
+//! writes the user layer, as it did before layers existed.
 
+use std::collections::HashMap;
 use std::path::{Path, PathBuf};
-use std::sync::Arc;
+use std::sync::mpsc::{self, RecvTimeoutError, Sender};
+use std::sync::{Arc, Weak};
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
 
 use parking_lot::RwLock;
 
 use crate::error::{Result, SettingsError};
+use crate::form::{self, FormSubmission, SettingsForm};
+use crate::layer::{self, LayerState, Provenance, SettingsLayer};
+use crate::migration::SettingsMigrator;
 use crate::schema::{SchemaRegistry, SettingSchema};
 use crate::store::SettingsStore;
 use crate::value::SettingValue;
@@ -15,16 +26,154 @@ pub type ChangeCallback = Box<dyn Fn(&str, &SettingValue, &SettingValue) + Send
 
 /// Internal state for the settings manager.
 struct SettingsState {
-    store: SettingsStore,
-    defaults: SettingsStore,
+    layers: [LayerState; 5],
     registry: SchemaRegistry,
+    migrator: Option<SettingsMigrator>,
     callbacks: Vec<ChangeCallback>,
 }
 
+impl SettingsState {
+    fn layer(&self, layer: SettingsLayer) -> &LayerState {
+        &self.layers[layer.index()]
+    }
+
+    fn layer_mut(&mut self, layer: SettingsLayer) -> &mut LayerState {
+        &mut self.layers[layer.index()]
+    }
+
+    /// Returns the lowest layer that locks `key`.
+    fn locked_by(&self, key: &str) -> Option<SettingsLayer> {
+        SettingsLayer::ALL
+            .into_iter()
+            .find(|l| self.layer(*l).locked.contains(key))
+    }
+
+    /// Resolves the effective value of `key` and the layer it comes from.
+    fn resolve(&self, key: &str) -> Option<(SettingsLayer, &SettingValue)> {
+        let top = self.locked_by(key).unwrap_or(SettingsLayer::CommandLine);
+        SettingsLayer::ALL
+            .into_iter()
+            .rev()
+            .filter(|l| *l <= top)
+            .find_map(|l| self.layer(l).store.get(key).map(|v| (l, v)))
+    }
+
+    fn effective(&self, key: &str) -> SettingValue {
+        self.resolve(key)
+            .map(|(_, v)| v.clone())
+            .unwrap_or(SettingValue::Null)
+    }
+
+    /// Returns the effective value of every key defined by any layer.
+    fn snapshot(&self) -> HashMap<String, SettingValue> {
+        let mut keys: Vec<&str> = self.layers.iter().flat_map(|l| l.store.keys()).collect();
+        keys.sort_unstable();
+        keys.dedup();
+        keys.into_iter()
+            .filter_map(|k| self.resolve(k).map(|(_, v)| (k.to_string(), v.clone())))
+            .collect()
+    }
+
+    fn notify(&self, key: &str, old: &SettingValue, new: &SettingValue) {
+        for callback in &self.callbacks {
+            callback(key, old, new);
+        }
+    }
+
+    /// Notifies callbacks about every key whose effective value changed.
+    fn notify_diff(&self, before: &HashMap<String, SettingValue>) {
+        let after = self.snapshot();
+        let mut keys: Vec<&String> = before.keys().chain(after.keys()).collect();
+        keys.sort_unstable();
+        keys.dedup();
+        for key in keys {
+            let old = before.get(key).unwrap_or(&SettingValue::Null);
+            let new = after.get(key).unwrap_or(&SettingValue::Null);
+            if old != new {
+                self.notify(key, old, new);
+            }
+        }
+    }
+
+    fn inspect(&self, key: &str) -> Provenance {
+        let resolved = self.resolve(key);
+        Provenance {
+            key: key.to_string(),
+            value: resolved.map(|(_, v)| v.clone()),
+            layer: resolved.map(|(l, _)| l),
+            layers: SettingsLayer::ALL
+                .into_iter()
+                .filter_map(|l| self.layer(l).store.get(key).map(|v| (l, v.clone())))
+                .collect(),
+            locked_by: self.locked_by(key),
+        }
+    }
+
+    /// Checks that `layer` may define `key`.
+    fn check_writable(&self, layer: SettingsLayer, key: &str, value: &SettingValue) -> Result<()> {
+        layer::validate(&self.registry, key, value)?;
+        match self.locked_by(key) {
+            Some(lock) if lock < layer => Err(SettingsError::Locked {
+                key: key.to_string(),
+                layer: lock,
+            }),
+            _ => Ok(()),
+        }
+    }
+
+    /// Re-reads the file of `layer`, keeping the current values on error.
+    fn load_layer(&mut self, layer: SettingsLayer) -> Result<()> {
+        let Some(path) = self.layer(layer).path.clone() else {
+            return Ok(());
+        };
+        let (store, locked) = LayerState::read(&path, &self.registry, self.migrator.as_ref())?;
+        // Taken after reading so a migration rewrite is not seen as a change
+        let fingerprint = layer::fingerprint(&path);
+
+        let state = self.layer_mut(layer);
+        state.store = store;
+        state.locked = locked;
+        state.fingerprint = fingerprint;
+        Ok(())
+    }
+
+    /// Re-reads every layer file that changed on disk since it was last read.
+    fn reload_changed(&mut self) -> Result<Vec<SettingsLayer>> {
+        let before = self.snapshot();
+        let mut reloaded = Vec::new();
+        let mut first_error = None;
+
+        for layer in SettingsLayer::ALL {
+            let state = self.layer(layer);
+            let Some(path) = &state.path else {
+                continue;
+            };
+            if layer::fingerprint(path) == state.fingerprint {
+                continue;
+            }
+            match self.load_layer(layer) {
+                Ok(()) => reloaded.push(layer),
+                Err(e) => {
+                    // Don't retry a broken file until it changes again
+                    let path = self.layer(layer).path.clone();
+                    self.layer_mut(layer).fingerprint =
+                        path.as_deref().and_then(layer::fingerprint);
+                    first_error.get_or_insert(e);
+                }
+            }
+        }
+
+        self.notify_diff(&before);
+        match first_error {
+            Some(e) => Err(e),
+            None => Ok(reloaded),
+        }
+    }
+}
+
 /// Main settings manager with validation and persistence.
 pub struct SettingsManager {
     inner: Arc<RwLock<SettingsState>>,
-    storage_path: Option<PathBuf>,
 }
 
 impl SettingsManager {
@@ -32,20 +181,36 @@ impl SettingsManager {
     pub fn new() -> Self {
         Self {
             inner: Arc::new(RwLock::new(SettingsState {
-                store: SettingsStore::new(),
-                defaults: SettingsStore::new(),
+                layers: Default::default(),
                 registry: SchemaRegistry::new(),
+                migrator: None,
                 callbacks: Vec::new(),
             })),
-            storage_path: None,
         }
     }
 
-    /// Creates a new settings manager with a storage path.
+    /// Creates a new settings manager with a storage path for the user layer.
     pub fn with_storage(path: impl AsRef<Path>) -> Self {
-        let mut manager = Self::new();
-        manager.storage_path = Some(path.as_ref().to_path_buf());
-        manager
+        Self::new().with_layer_file(SettingsLayer::User, path)
+    }
+
+    /// Sets the file backing a layer.
+    pub fn with_layer_file(self, layer: SettingsLayer, path: impl AsRef<Path>) -> Self {
+        self.inner.write().layer_mut(layer).path = Some(path.as_ref().to_path_buf());
+        self
+    }
+
+    /// Sets the migrator that upgrades layer files when they are loaded.
+    ///
+    /// Saved files are stamped with the migrator's current version.
+    pub fn with_migrator(self, migrator: SettingsMigrator) -> Self {
+        self.inner.write().migrator = Some(migrator);
+        self
+    }
+
+    /// Returns the file backing a layer.
+    pub fn layer_path(&self, layer: SettingsLayer) -> Option<PathBuf> {
+        self.inner.read().layer(layer).path.clone()
     }
 
     /// Registers a setting schema.
@@ -53,7 +218,8 @@ impl SettingsManager {
         let mut state = self.inner.write();
         // Set the default value
         state
-            .defaults
+            .layer_mut(SettingsLayer::Default)
+            .store
             .set(schema.key.clone(), schema.default.clone());
         state.registry.register(schema);
     }
@@ -76,14 +242,15 @@ impl SettingsManager {
         registry
     }
 
-    /// Gets a setting value, falling back to default if not set.
+    /// Gets the effective value of a setting, falling back to default if not set.
     pub fn get(&self, key: &str) -> Option<SettingValue> {
         let state = self.inner.read();
-        state
-            .store
-            .get(key)
-            .or_else(|| state.defaults.get(key))
-            .cloned()
+        state.resolve(key).map(|(_, v)| v.clone())
+    }
+
+    /// Gets the value a single layer defines for a setting.
+    pub fn get_in(&self, layer: SettingsLayer, key: &str) -> Option<SettingValue> {
+        self.inner.read().layer(layer).store.get(key).cloned()
     }
 
     /// Gets a string setting.
@@ -106,67 +273,185 @@ impl SettingsManager {
         self.get(key).and_then(|v| v.as_float())
     }
 
-    /// Sets a setting value with validation.
+    /// Sets a user setting value with validation.
     pub fn set(&self, key: impl Into<String>, value: SettingValue) -> Result<()> {
+        self.set_in(SettingsLayer::User, key, value)
+    }
+
+    /// Sets a setting value in a layer with validation.
+    ///
+    /// Fails with [`SettingsError::Locked`] if a lower layer locks the key.
+    /// Callbacks fire only if the new value becomes the effective one.
+    pub fn set_in(
+        &self,
+        layer: SettingsLayer,
+        key: impl Into<String>,
+        value: SettingValue,
+    ) -> Result<()> {
         let key = key.into();
         let mut state = self.inner.write();
-
-        // Validate against schema if registered
-        if let Some(schema) = state.registry.get(&key) {
-            schema
-                .validate(&value)
-                .map_err(|reason| SettingsError::ValidationFailed {
-                    key: key.clone(),
-                    reason,
-                })?;
-        }
+        state.check_writable(layer, &key, &value)?;
 
         // Get old value for change notification
-        let old_value = state
-            .store
-            .get(&key)
-            .or_else(|| state.defaults.get(&key))
-            .cloned()
-            .unwrap_or(SettingValue::Null);
+        let old_value = state.effective(&key);
 
         // Set the new value
-        state.store.set(key.clone(), value.clone());
+        state.layer_mut(layer).store.set(key.clone(), value.clone());
 
-        // Notify callbacks
-        for callback in &state.callbacks {
-            callback(&key, &old_value, &value);
+        // Notify callbacks unless a higher layer shadows the value
+        if state.resolve(&key).map(|(l, _)| l) == Some(layer) {
+            state.notify(&key, &old_value, &value);
         }
 
         Ok(())
     }
 
-    /// Resets a setting to its default value.
-    pub fn reset(&self, key: &str) -> Result<()> {
+    /// Removes a setting from a layer, returning the removed value.
+    pub fn remove_in(&self, layer: SettingsLayer, key: &str) -> Option<SettingValue> {
         let mut state = self.inner.write();
+        let old_value = state.effective(key);
+        let removed = state.layer_mut(layer).store.remove(key)?;
 
-        let old_value = state.store.get(key).cloned();
-        let default_value = state.defaults.get(key).cloned();
+        let new_value = state.effective(key);
+        if old_value != new_value {
+            state.notify(key, &old_value, &new_value);
+        }
+        Some(removed)
+    }
 
-        if let Some(old) = old_value {
-            state.store.remove(key);
+    /// Applies `key=value` command-line arguments to the command-line layer.
+    ///
+    /// Values are parsed as JSON when possible and as strings otherwise.
+    /// Nothing is applied if any argument is malformed, invalid or locked.
+    pub fn apply_args<I, S>(&self, args: I) -> Result<()>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let parsed = args
+            .into_iter()
+            .map(|arg| layer::parse_arg(arg.as_ref()))
+            .collect::<Result<Vec<_>>>()?;
 
-            if let Some(default) = &default_value {
-                for callback in &state.callbacks {
-                    callback(key, &old, default);
-                }
-            }
+        let mut state = self.inner.write();
+        for (key, value) in &parsed {
+            state.check_writable(SettingsLayer::CommandLine, key, value)?;
+        }
+
+        let before = state.snapshot();
+        for (key, value) in parsed {
+            state
+                .layer_mut(SettingsLayer::CommandLine)
+                .store
+                .set(key, value);
         }
+        state.notify_diff(&before);
+        Ok(())
+    }
 
+    /// Resets a user setting so lower layers apply again.
+    pub fn reset(&self, key: &str) -> Result<()> {
+        self.remove_in(SettingsLayer::User, key);
         Ok(())
     }
 
-    /// Resets all settings to defaults.
+    /// Resets all user settings.
     pub fn reset_all(&self) {
         let mut state = self.inner.write();
-        state.store.clear();
+        let before = state.snapshot();
+        state.layer_mut(SettingsLayer::User).store.clear();
+        state.notify_diff(&before);
+    }
+
+    /// Locks a key at a layer so higher layers cannot override it.
+    pub fn lock(&self, layer: SettingsLayer, key: impl Into<String>) {
+        let mut state = self.inner.write();
+        let before = state.snapshot();
+        state.layer_mut(layer).locked.insert(key.into());
+        state.notify_diff(&before);
+    }
+
+    /// Removes a lock placed by a layer.
+    pub fn unlock(&self, layer: SettingsLayer, key: &str) {
+        let mut state = self.inner.write();
+        let before = state.snapshot();
+        state.layer_mut(layer).locked.remove(key);
+        state.notify_diff(&before);
+    }
+
+    /// Returns the lowest layer that locks a key.
+    pub fn locked_by(&self, key: &str) -> Option<SettingsLayer> {
+        self.inner.read().locked_by(key)
+    }
+
+    /// Returns the layer that supplies the effective value of a key.
+    pub fn source(&self, key: &str) -> Option<SettingsLayer> {
+        self.inner.read().resolve(key).map(|(l, _)| l)
+    }
+
+    /// Describes where the value of a key comes from.
+    pub fn inspect(&self, key: &str) -> Provenance {
+        self.inner.read().inspect(key)
+    }
+
+    /// Builds a categorized form of all registered settings.
+    pub fn form(&self) -> SettingsForm {
+        let state = self.inner.read();
+        form::build(&state.registry, |key| state.inspect(key))
+    }
+
+    /// Applies values submitted from a settings form to the user layer.
+    ///
+    /// `null` resets a key. Every value is checked first; if any key is
+    /// unknown, invalid or locked, nothing is applied and the errors are
+    /// returned by key.
+    pub fn submit_form(
+        &self,
+        values: impl IntoIterator<Item = (String, SettingValue)>,
+    ) -> FormSubmission {
+        let mut values: Vec<_> = values.into_iter().collect();
+        values.sort_by(|a, b| a.0.cmp(&b.0));
+
+        let mut state = self.inner.write();
+        let mut submission = FormSubmission::default();
+        for (key, value) in &values {
+            let checked = if state.registry.get(key).is_none() {
+                Err(SettingsError::SchemaNotFound(key.clone()))
+            } else if value.is_null() {
+                Ok(())
+            } else {
+                state.check_writable(SettingsLayer::User, key, value)
+            };
+            if let Err(e) = checked {
+                let message = match e {
+                    SettingsError::ValidationFailed { reason, .. } => reason,
+                    other => other.to_string(),
+                };
+                submission.errors.insert(key.clone(), message);
+            }
+        }
+        if !submission.is_ok() {
+            return submission;
+        }
+
+        let before = state.snapshot();
+        let user = state.layer_mut(SettingsLayer::User);
+        for (key, value) in values {
+            if value.is_null() {
+                user.store.remove(&key);
+            } else {
+                user.store.set(key.clone(), value);
+            }
+            submission.applied.push(key);
+        }
+        state.notify_diff(&before);
+        submission
     }
 
     /// Registers a change callback.
+    ///
+    /// Callbacks receive the old and new effective values, including changes
+    /// caused by reloading a layer file.
     pub fn on_change<F>(&self, callback: F)
     where
         F: Fn(&str, &SettingValue, &SettingValue) + Send + Sync + 'static,
@@ -175,55 +460,124 @@ impl SettingsManager {
         state.callbacks.push(Box::new(callback));
     }
 
-    /// Loads settings from the storage path.
+    /// Loads every layer that has a backing file.
+    ///
+    /// Outdated files are migrated first (see [`SettingsManager::with_migrator`]),
+    /// keeping a backup of the original. Values are validated against the
+    /// registered schemas. A layer whose file
+    /// is invalid keeps its previous values and the first error is returned
+    /// after the remaining layers are loaded.
     pub fn load(&self) -> Result<()> {
-        let Some(path) = &self.storage_path else {
-            return Ok(());
-        };
-
-        if !path.exists() {
-            return Ok(());
+        let mut state = self.inner.write();
+        let before = state.snapshot();
+        let mut first_error = None;
+        for layer in SettingsLayer::ALL {
+            if let Err(e) = state.load_layer(layer) {
+                first_error.get_or_insert(e);
+            }
         }
+        state.notify_diff(&before);
+        first_error.map_or(Ok(()), Err)
+    }
 
-        let content = std::fs::read_to_string(path)?;
-        let map: std::collections::HashMap<String, SettingValue> = serde_json::from_str(&content)?;
-
-        let mut state = self.inner.write();
-        state.store = SettingsStore::from_map(map);
+    /// Reloads layer files that changed on disk, returning the reloaded layers.
+    pub fn reload_changed(&self) -> Result<Vec<SettingsLayer>> {
+        self.inner.write().reload_changed()
+    }
 
-        Ok(())
+    /// Polls layer files in a background thread and reloads them on change.
+    ///
+    /// Reload errors keep the previous values of the affected layer. Polling
+    /// stops when the returned watcher or every manager clone is dropped.
+    pub fn watch(&self, interval: Duration) -> SettingsWatcher {
+        SettingsWatcher::spawn(Arc::downgrade(&self.inner), interval)
     }
 
-    /// Saves settings to the storage path.
+    /// Saves the user and workspace layers to their files.
     pub fn save(&self) -> Result<()> {
-        let Some(path) = &self.storage_path else {
+        self.save_layer(SettingsLayer::User)?;
+        self.save_layer(SettingsLayer::Workspace)
+    }
+
+    /// Saves one layer to its file.
+    pub fn save_layer(&self, layer: SettingsLayer) -> Result<()> {
+        let mut state = self.inner.write();
+        let Some(path) = state.layer(layer).path.clone() else {
             return Ok(());
         };
 
-        let state = self.inner.read();
-        let content = serde_json::to_string_pretty(&state.store.as_map())?;
+        let version = state
+            .migrator
+            .as_ref()
+            .map(SettingsMigrator::current_version);
+        let content = state.layer(layer).to_json(version)?;
 
         if let Some(parent) = path.parent() {
             std::fs::create_dir_all(parent)?;
         }
 
-        std::fs::write(path, content)?;
+        std::fs::write(&path, content)?;
+        // Our own write is not an external change
+        state.layer_mut(layer).fingerprint = layer::fingerprint(&path);
 
         Ok(())
     }
 
-    /// Returns all non-default settings.
+    /// Returns all user settings.
     pub fn user_settings(&self) -> SettingsStore {
-        let state = self.inner.read();
-        state.store.clone()
+        self.layer_settings(SettingsLayer::User)
+    }
+
+    /// Returns the settings defined by one layer.
+    pub fn layer_settings(&self, layer: SettingsLayer) -> SettingsStore {
+        self.inner.read().layer(layer).store.clone()
     }
 
-    /// Returns all settings including defaults.
+    /// Returns the effective value of all settings including defaults.
     pub fn all_settings(&self) -> SettingsStore {
-        let state = self.inner.read();
-        let mut result = state.defaults.clone();
-        result.merge_ref(&state.store);
-        result
+        SettingsStore::from_map(self.inner.read().snapshot())
+    }
+}
+
+/// Background poller started by [`SettingsManager::watch`].
+pub struct SettingsWatcher {
+    stop: Option<Sender<()>>,
+    handle: Option<JoinHandle<()>>,
+}
+
+impl SettingsWatcher {
+    fn spawn(inner: Weak<RwLock<SettingsState>>, interval: Duration) -> Self {
+        let (stop, stopped) = mpsc::channel::<()>();
+        let handle = thread::spawn(move || {
+            while let Err(RecvTimeoutError::Timeout) = stopped.recv_timeout(interval) {
+                let Some(inner) = inner.upgrade() else {
+                    break;
+                };
+                let _ = inner.write().reload_changed();
+            }
+        });
+        Self {
+            stop: Some(stop),
+            handle: Some(handle),
+        }
+    }
+
+    /// Stops polling and waits for the thread to exit.
+    pub fn stop(mut self) {
+        self.shutdown();
+    }
+
+    fn shutdown(&mut self) {
+        self.stop.take();
+        if let Some(handle) = self.handle.take() {
+            let _ = handle.join();
+        }
+    }
+}
+
+impl Drop for SettingsWatcher {
+    fn drop(&mut self) {
+        self.shutdown();
     }
 }
 
@@ -237,7 +591,6 @@ impl Clone for SettingsManager {
     fn clone(&self) -> Self {
         Self {
             inner: Arc::clone(&self.inner),
-            storage_path: self.storage_path.clone(),
         }
     }
 }
@@ -0,0 +1,325 @@
+//! Versioned migrations for persisted settings files.
+//!
+//! Settings files store their schema version under the reserved
+//! [`VERSION_KEY`]. Files written before versioning existed count as
+//! version 0. A [`SettingsMigrator`] holds one [`Migration`] per source
+//! version and upgrades files step by step to its current version.
+//!
+//! ```rust
+//! use auroraview_settings::{Migration, SettingsMigrator};
+//!
+//! let migrator = SettingsMigrator::new(1).with_migration(
+//!     0,
+//!     Migration::new()
+//!         .rename("theme", "ui.theme")
+//!         .map_values("ui.dark", [(true, "dark"), (false, "light")])
+//!         .remove("legacy.cache"),
+//! );
+//! assert_eq!(migrator.current_version(), 1);
+//! ```
+
+use std::collections::{BTreeMap, HashMap};
+
+use crate::error::{Result, SettingsError};
+use crate::layer::LOCKED_KEY;
+use crate::value::SettingValue;
+
+/// Reserved key in settings files holding the schema version.
+pub const VERSION_KEY: &str = "$version";
+
+/// Raw contents of a settings file, including reserved keys.
+pub type SettingsMap = HashMap<String, SettingValue>;
+
+type Step = Box<dyn Fn(&mut SettingsMap) -> Result<()> + Send + Sync>;
+
+/// Ordered steps that upgrade settings by one version.
+///
+/// Steps only touch keys that are present, so a migration can run on files
+/// that never set the affected settings. Keys listed under
+/// [`LOCKED_KEY`] follow renames, splits and merges.
+#[derive(Default)]
+pub struct Migration {
+    steps: Vec<Step>,
+}
+
+impl Migration {
+    /// Creates an empty migration.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Renames a key.
+    ///
+    /// If the new key is already set, its value wins and the old key is
+    /// dropped.
+    pub fn rename(self, from: impl Into<String>, to: impl Into<String>) -> Self {
+        let (from, to) = (from.into(), to.into());
+        self.step(move |map| {
+            if let Some(value) = map.remove(&from) {
+                map.entry(to.clone()).or_insert(value);
+                relock(map, &[from.as_str()], std::slice::from_ref(&to));
+            }
+            Ok(())
+        })
+    }
+
+    /// Converts the value of a key, e.g. from bool to an enum string.
+    ///
+    /// Returning `None` drops the key, so the schema default applies.
+    pub fn convert<F>(self, key: impl Into<String>, convert: F) -> Self
+    where
+        F: Fn(SettingValue) -> Option<SettingValue> + Send + Sync + 'static,
+    {
+        let key = key.into();
+        self.step(move |map| {
+            if let Some(value) = map.remove(&key) {
+                match convert(value) {
+                    Some(value) => {
+                        map.insert(key.clone(), value);
+                    }
+                    None => relock(map, &[key.as_str()], &[]),
+                }
+            }
+            Ok(())
+        })
+    }
+
+    /// Converts the value of a key through a lookup table.
+    ///
+    /// Values missing from the table are dropped.
+    pub fn map_values<A, B>(
+        self,
+        key: impl Into<String>,
+        table: impl IntoIterator<Item = (A, B)>,
+    ) -> Self
+    where
+        A: Into<SettingValue>,
+        B: Into<SettingValue>,
+    {
+        let table: Vec<(SettingValue, SettingValue)> = table
+            .into_iter()
+            .map(|(a, b)| (a.into(), b.into()))
+            .collect();
+        self.convert(key, move |value| {
+            table
+                .iter()
+                .find(|(from, _)| *from == value)
+                .map(|(_, to)| to.clone())
+        })
+    }
+
+    /// Splits one key into several.
+    ///
+    /// `split` returns the new keys and values; keys it does not return are
+    /// left unset. Existing values of the new keys win.
+    pub fn split<F>(self, key: impl Into<String>, split: F) -> Self
+    where
+        F: Fn(SettingValue) -> Vec<(String, SettingValue)> + Send + Sync + 'static,
+    {
+        let key = key.into();
+        self.step(move |map| {
+            if let Some(value) = map.remove(&key) {
+                let parts = split(value);
+                let targets: Vec<String> = parts.iter().map(|(k, _)| k.clone()).collect();
+                for (k, v) in parts {
+                    map.entry(k).or_insert(v);
+                }
+                relock(map, &[key.as_str()], &targets);
+            }
+            Ok(())
+        })
+    }
+
+    /// Merges several keys into one.
+    ///
+    /// `merge` receives the old values in the order of `from` (`None` for
+    /// unset keys) and runs only if at least one is set. Returning `None`
+    /// leaves the new key unset.
+    pub fn merge<F>(
+        self,
+        from: impl IntoIterator<Item = impl Into<String>>,
+        to: impl Into<String>,
+        merge: F,
+    ) -> Self
+    where
+        F: Fn(&[Option<SettingValue>]) -> Option<SettingValue> + Send + Sync + 'static,
+    {
+        let from: Vec<String> = from.into_iter().map(Into::into).collect();
+        let to = to.into();
+        self.step(move |map| {
+            let values: Vec<Option<SettingValue>> = from.iter().map(|k| map.remove(k)).collect();
+            if values.iter().all(Option::is_none) {
+                return Ok(());
+            }
+            let sources: Vec<&str> = from.iter().map(String::as_str).collect();
+            match merge(&values) {
+                Some(value) => {
+                    map.entry(to.clone()).or_insert(value);
+                    relock(map, &sources, std::slice::from_ref(&to));
+                }
+                None => relock(map, &sources, &[]),
+            }
+            Ok(())
+        })
+    }
+
+    /// Drops a key.
+    pub fn remove(self, key: impl Into<String>) -> Self {
+        let key = key.into();
+        self.step(move |map| {
+            if map.remove(&key).is_some() {
+                relock(map, &[key.as_str()], &[]);
+            }
+            Ok(())
+        })
+    }
+
+    /// Adds a custom step operating on the raw file contents.
+    pub fn custom<F>(self, step: F) -> Self
+    where
+        F: Fn(&mut SettingsMap) -> Result<()> + Send + Sync + 'static,
+    {
+        self.step(step)
+    }
+
+    fn step<F>(mut self, step: F) -> Self
+    where
+        F: Fn(&mut SettingsMap) -> Result<()> + Send + Sync + 'static,
+    {
+        self.steps.push(Box::new(step));
+        self
+    }
+
+    /// Applies all steps in order.
+    pub fn apply(&self, map: &mut SettingsMap) -> Result<()> {
+        for step in &self.steps {
+            step(map)?;
+        }
+        Ok(())
+    }
+}
+
+/// Replaces `from` entries of the lock list with `to`.
+fn relock(map: &mut SettingsMap, from: &[&str], to: &[String]) {
+    let Some(SettingValue::Array(locked)) = map.get_mut(LOCKED_KEY) else {
+        return;
+    };
+    let before = locked.len();
+    locked.retain(|k| !matches!(k, SettingValue::String(k) if from.contains(&k.as_str())));
+    if locked.len() == before {
+        return;
+    }
+    for key in to {
+        let key = SettingValue::String(key.clone());
+        if !locked.contains(&key) {
+            locked.push(key);
+        }
+    }
+}
+
+/// Upgrades settings files to the current schema version.
+pub struct SettingsMigrator {
+    version: u32,
+    migrations: BTreeMap<u32, Migration>,
+}
+
+impl SettingsMigrator {
+    /// Creates a migrator for the given current version.
+    pub fn new(current_version: u32) -> Self {
+        Self {
+            version: current_version,
+            migrations: BTreeMap::new(),
+        }
+    }
+
+    /// Returns the version files are migrated to.
+    pub fn current_version(&self) -> u32 {
+        self.version
+    }
+
+    /// Registers the migration from `from_version` to `from_version + 1`.
+    pub fn register(&mut self, from_version: u32, migration: Migration) {
+        self.migrations.insert(from_version, migration);
+    }
+
+    /// Registers a migration and returns the migrator.
+    pub fn with_migration(mut self, from_version: u32, migration: Migration) -> Self {
+        self.register(from_version, migration);
+        self
+    }
+
+    /// Returns the version stored in a settings file, 0 if there is none.
+    pub fn version_of(map: &SettingsMap) -> u32 {
+        map.get(VERSION_KEY)
+            .and_then(SettingValue::as_integer)
+            .and_then(|v| u32::try_from(v).ok())
+            .unwrap_or(0)
+    }
+
+    /// Checks if a settings file needs migration.
+    pub fn needs_migration(&self, map: &SettingsMap) -> bool {
+        Self::version_of(map) < self.version
+    }
+
+    /// Migrates a settings file to the current version.
+    ///
+    /// Returns the version the file had before. Files from a newer version
+    /// are rejected so an older build does not clobber them.
+    pub fn migrate(&self, map: &mut SettingsMap) -> Result<u32> {
+        let version = Self::version_of(map);
+        if version > self.version {
+            return Err(SettingsError::UnsupportedVersion {
+                found: version,
+                supported: self.version,
+            });
+        }
+
+        for (_, migration) in self.migrations.range(version..self.version) {
+            migration.apply(map)?;
+        }
+
+        map.insert(VERSION_KEY.to_string(), i64::from(self.version).into());
+        Ok(version)
+    }
+}
+
+impl Default for SettingsMigrator {
+    fn default() -> Self {
+        Self::new(0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn map(pairs: &[(&str, SettingValue)]) -> SettingsMap {
+        pairs
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.clone()))
+            .collect()
+    }
+
+    #[test]
+    fn test_relock_follows_rename() {
+        let mut values = map(&[("old", 1.into()), (LOCKED_KEY, vec!["old", "other"].into())]);
+        Migration::new()
+            .rename("old", "new")
+            .apply(&mut values)
+            .unwrap();
+        assert_eq!(values[LOCKED_KEY], vec!["other", "new"].into());
+    }
+
+    #[test]
+    fn test_version_of() {
+        assert_eq!(SettingsMigrator::version_of(&map(&[])), 0);
+        assert_eq!(
+            SettingsMigrator::version_of(&map(&[(VERSION_KEY, 3.into())])),
+            3
+        );
+        assert_eq!(
+            SettingsMigrator::version_of(&map(&[(VERSION_KEY, (-1).into())])),
+            0
+        );
+    }
+}
@@ -1,10 +1,14 @@
 //! Setting schema definitions for validation and documentation.
 
 use serde::{Deserialize, Serialize};
+use serde_json::{json, Map, Value};
 use std::collections::HashMap;
 
 use crate::value::SettingValue;
 
+/// Dialect URI of the JSON Schema produced by [`SchemaRegistry::to_json_schema`].
+pub const JSON_SCHEMA_DIALECT: &str = "https://json-schema.org/draft/2020-12/schema";
+
 /// Type specification for a setting.
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
@@ -52,6 +56,62 @@ impl Default for SchemaType {
     }
 }
 
+impl SchemaType {
+    /// Converts the type and its constraints to JSON Schema keywords.
+    pub fn to_json_schema(&self) -> Value {
+        let mut out = Map::new();
+        match self {
+            Self::Bool => {
+                out.insert("type".into(), json!("boolean"));
+            }
+            Self::Integer { min, max } => {
+                out.insert("type".into(), json!("integer"));
+                if let Some(min) = min {
+                    out.insert("minimum".into(), json!(min));
+                }
+                if let Some(max) = max {
+                    out.insert("maximum".into(), json!(max));
+                }
+            }
+            Self::Float { min, max } => {
+                out.insert("type".into(), json!("number"));
+                if let Some(min) = min {
+                    out.insert("minimum".into(), json!(min));
+                }
+                if let Some(max) = max {
+                    out.insert("maximum".into(), json!(max));
+                }
+            }
+            Self::String {
+                pattern,
+                max_length,
+            } => {
+                out.insert("type".into(), json!("string"));
+                if let Some(pattern) = pattern {
+                    out.insert("pattern".into(), json!(pattern));
+                }
+                if let Some(max_length) = max_length {
+                    out.insert("maxLength".into(), json!(max_length));
+                }
+            }
+            Self::Enum { values } => {
+                out.insert("type".into(), json!("string"));
+                out.insert("enum".into(), json!(values));
+            }
+            Self::Array { items } => {
+                out.insert("type".into(), json!("array"));
+                if let Some(items) = items {
+                    out.insert("items".into(), items.to_json_schema());
+                }
+            }
+            Self::Object => {
+                out.insert("type".into(), json!("object"));
+            }
+        }
+        Value::Object(out)
+    }
+}
+
 /// Schema definition for a setting.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SettingSchema {
@@ -83,6 +143,36 @@ impl SettingSchema {
         SchemaBuilder::new(key)
     }
 
+    /// Converts this schema to a JSON Schema property definition.
+    ///
+    /// Category and restart hints are kept as `x-category` and
+    /// `x-requires-restart`; the deprecation message uses the
+    /// `deprecationMessage` keyword understood by VS Code.
+    pub fn to_json_schema(&self) -> Value {
+        let mut out = match self.schema_type.to_json_schema() {
+            Value::Object(map) => map,
+            _ => Map::new(),
+        };
+        out.insert("title".into(), json!(self.title));
+        if !self.description.is_empty() {
+            out.insert("description".into(), json!(self.description));
+        }
+        if !self.default.is_null() {
+            out.insert("default".into(), json!(self.default));
+        }
+        if let Some(category) = &self.category {
+            out.insert("x-category".into(), json!(category));
+        }
+        if self.requires_restart {
+            out.insert("x-requires-restart".into(), json!(true));
+        }
+        if let Some(message) = &self.deprecated {
+            out.insert("deprecated".into(), json!(true));
+            out.insert("deprecationMessage".into(), json!(message));
+        }
+        Value::Object(out)
+    }
+
     /// Validates a value against this schema.
     pub fn validate(&self, value: &SettingValue) -> Result<(), String> {
         match (&self.schema_type, value) {
@@ -310,6 +400,47 @@ impl SchemaRegistry {
             .filter(move |s| s.category.as_deref() == Some(category))
     }
 
+    /// Exports the registry as a JSON Schema (draft 2020-12) for settings files.
+    ///
+    /// Settings files are flat objects keyed by setting key, so every schema
+    /// becomes a property. Unknown keys are allowed, the reserved
+    /// [`LOCKED_KEY`](crate::LOCKED_KEY) may list registered keys and
+    /// [`VERSION_KEY`](crate::VERSION_KEY) holds the file version.
+    pub fn to_json_schema(&self) -> Value {
+        let mut keys: Vec<&String> = self.schemas.keys().collect();
+        keys.sort();
+
+        let mut properties = Map::new();
+        for key in &keys {
+            properties.insert((*key).clone(), self.schemas[*key].to_json_schema());
+        }
+        properties.insert(
+            crate::LOCKED_KEY.into(),
+            json!({
+                "type": "array",
+                "description": "Keys that higher layers cannot override",
+                "items": { "type": "string", "enum": keys },
+                "uniqueItems": true,
+            }),
+        );
+
+        properties.insert(
+            crate::VERSION_KEY.into(),
+            json!({
+                "type": "integer",
+                "description": "Schema version the file was written with",
+                "minimum": 0,
+            }),
+        );
+
+        json!({
+            "$schema": JSON_SCHEMA_DIALECT,
+            "type": "object",
+            "properties": properties,
+            "additionalProperties": true,
+        })
+    }
+
     /// Returns all unique categories.
     pub fn categories(&self) -> Vec<String> {
         let mut cats: Vec<_> = self
@@ -0,0 +1,395 @@
+//! Tests for layered settings scopes, provenance, locks and file reloading
+
+use std::path::Path;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use auroraview_settings::{
+    SettingSchema, SettingValue, SettingsError, SettingsLayer, SettingsManager,
+};
+use parking_lot::Mutex;
+use rstest::rstest;
+use tempfile::TempDir;
+
+fn theme_schema() -> SettingSchema {
+    SettingSchema::builder("ui.theme")
+        .enum_type(vec!["light".into(), "dark".into(), "system".into()])
+        .default("light")
+        .build()
+}
+
+fn workers_schema() -> SettingSchema {
+    SettingSchema::builder("perf.workers")
+        .integer_type(Some(1), Some(64))
+        .default(4)
+        .build()
+}
+
+fn write(path: &Path, json: &str) {
+    std::fs::write(path, json).unwrap();
+}
+
+/// Manager with system, user and workspace files in a temp dir
+fn layered(dir: &TempDir) -> SettingsManager {
+    let manager = SettingsManager::new()
+        .with_layer_file(SettingsLayer::System, dir.path().join("system.json"))
+        .with_layer_file(SettingsLayer::User, dir.path().join("user.json"))
+        .with_layer_file(SettingsLayer::Workspace, dir.path().join("workspace.json"));
+    manager.register_schemas([theme_schema(), workers_schema()]);
+    manager
+}
+
+type Changes = Arc<Mutex<Vec<(String, SettingValue, SettingValue)>>>;
+
+fn record(manager: &SettingsManager) -> Changes {
+    let changes: Changes = Arc::default();
+    let c = changes.clone();
+    manager.on_change(move |key, old, new| c.lock().push((key.into(), old.clone(), new.clone())));
+    changes
+}
+
+// ---------------------------------------------------------------------------
+// Precedence and provenance
+// ---------------------------------------------------------------------------
+
+#[rstest]
+fn higher_layers_win() {
+    let manager = SettingsManager::new();
+    manager.register_schema(theme_schema());
+    assert_eq!(manager.source("ui.theme"), Some(SettingsLayer::Default));
+
+    manager
+        .set_in(SettingsLayer::System, "ui.theme", "dark".into())
+        .unwrap();
+    manager.set("ui.theme", "system".into()).unwrap();
+    assert_eq!(manager.get_string("ui.theme").as_deref(), Some("system"));
+    assert_eq!(manager.source("ui.theme"), Some(SettingsLayer::User));
+
+    manager
+        .set_in(SettingsLayer::Workspace, "ui.theme", "light".into())
+        .unwrap();
+    assert_eq!(manager.get_string("ui.theme").as_deref(), Some("light"));
+
+    manager.remove_in(SettingsLayer::Workspace, "ui.theme");
+    manager.reset("ui.theme").unwrap();
+    assert_eq!(manager.get_string("ui.theme").as_deref(), Some("dark"));
+    assert_eq!(manager.source("ui.theme"), Some(SettingsLayer::System));
+    assert_eq!(
+        manager.get_in(SettingsLayer::Default, "ui.theme"),
+        Some("light".into())
+    );
+}
+
+#[rstest]
+fn inspect_reports_every_layer() {
+    let manager = SettingsManager::new();
+    manager.register_schema(workers_schema());
+    manager
+        .set_in(SettingsLayer::System, "perf.workers", 8.into())
+        .unwrap();
+    manager.apply_args(["perf.workers=16"]).unwrap();
+
+    let info = manager.inspect("perf.workers");
+    assert_eq!(info.value, Some(SettingValue::Integer(16)));
+    assert_eq!(info.layer, Some(SettingsLayer::CommandLine));
+    assert_eq!(
+        info.layers,
+        vec![
+            (SettingsLayer::Default, SettingValue::Integer(4)),
+            (SettingsLayer::System, SettingValue::Integer(8)),
+            (SettingsLayer::CommandLine, SettingValue::Integer(16)),
+        ]
+    );
+    assert_eq!(info.value_in(SettingsLayer::User), None);
+    assert_eq!(info.locked_by, None);
+
+    let json = serde_json::to_value(&info).unwrap();
+    assert_eq!(json["layer"], "commandLine");
+    assert_eq!(json["lockedBy"], serde_json::Value::Null);
+
+    let missing = manager.inspect("missing");
+    assert_eq!(missing.value, None);
+    assert!(missing.layers.is_empty());
+}
+
+#[rstest]
+fn all_settings_is_effective_view() {
+    let manager = SettingsManager::new();
+    manager.register_schemas([theme_schema(), workers_schema()]);
+    manager
+        .set_in(SettingsLayer::Workspace, "perf.workers", 2.into())
+        .unwrap();
+    manager.set("extra", true.into()).unwrap();
+
+    let all = manager.all_settings();
+    assert_eq!(all.len(), 3);
+    assert_eq!(all.get("perf.workers"), Some(&SettingValue::Integer(2)));
+    assert_eq!(all.get("ui.theme"), Some(&"light".into()));
+    assert_eq!(manager.user_settings().len(), 1);
+    assert_eq!(manager.layer_settings(SettingsLayer::Workspace).len(), 1);
+}
+
+// ---------------------------------------------------------------------------
+// Validation
+// ---------------------------------------------------------------------------
+
+#[rstest]
+#[case(SettingsLayer::System)]
+#[case(SettingsLayer::User)]
+#[case(SettingsLayer::Workspace)]
+#[case(SettingsLayer::CommandLine)]
+fn schema_applies_to_every_layer(#[case] layer: SettingsLayer) {
+    let manager = SettingsManager::new();
+    manager.register_schema(workers_schema());
+
+    let err = manager
+        .set_in(layer, "perf.workers", 100.into())
+        .unwrap_err();
+    assert!(matches!(err, SettingsError::ValidationFailed { .. }));
+    assert_eq!(manager.get_in(layer, "perf.workers"), None);
+}
+
+#[rstest]
+fn invalid_layer_file_is_rejected() {
+    let dir = TempDir::new().unwrap();
+    write(&dir.path().join("system.json"), r#"{ "perf.workers": 8 }"#);
+    let manager = layered(&dir);
+    manager.load().unwrap();
+
+    write(&dir.path().join("system.json"), r#"{ "perf.workers": 0 }"#);
+    write(&dir.path().join("user.json"), r#"{ "ui.theme": "dark" }"#);
+    let err = manager.load().unwrap_err();
+    assert!(
+        matches!(err, SettingsError::ValidationFailed { ref key, .. } if key == "perf.workers")
+    );
+
+    // The broken layer keeps its previous values, others still load
+    assert_eq!(manager.get_integer("perf.workers"), Some(8));
+    assert_eq!(manager.get_string("ui.theme").as_deref(), Some("dark"));
+}
+
+#[rstest]
+fn command_line_args() {
+    let manager = SettingsManager::new();
+    manager.register_schema(workers_schema());
+
+    manager
+        .apply_args(["perf.workers=12", "ui.debug=true", "name=shot 010"])
+        .unwrap();
+    assert_eq!(manager.get_integer("perf.workers"), Some(12));
+    assert_eq!(manager.get_bool("ui.debug"), Some(true));
+    assert_eq!(manager.get_string("name").as_deref(), Some("shot 010"));
+
+    // All-or-nothing
+    let err = manager
+        .apply_args(["perf.workers=2", "perf.workers=99"])
+        .unwrap_err();
+    assert!(matches!(err, SettingsError::ValidationFailed { .. }));
+    assert_eq!(manager.get_integer("perf.workers"), Some(12));
+    assert!(matches!(
+        manager.apply_args(["oops"]),
+        Err(SettingsError::InvalidKey(_))
+    ));
+}
+
+// ---------------------------------------------------------------------------
+// Locks
+// ---------------------------------------------------------------------------
+
+#[rstest]
+fn lock_pins_lower_layer_value() {
+    let manager = SettingsManager::new();
+    manager.register_schema(theme_schema());
+    manager.set("ui.theme", "dark".into()).unwrap();
+    manager
+        .set_in(SettingsLayer::System, "ui.theme", "system".into())
+        .unwrap();
+
+    manager.lock(SettingsLayer::System, "ui.theme");
+    assert_eq!(manager.get_string("ui.theme").as_deref(), Some("system"));
+    assert_eq!(manager.locked_by("ui.theme"), Some(SettingsLayer::System));
+
+    let err = manager.set("ui.theme", "light".into()).unwrap_err();
+    assert!(matches!(
+        err,
+        SettingsError::Locked {
+            layer: SettingsLayer::System,
+            ..
+        }
+    ));
+    assert!(manager.apply_args(["ui.theme=light"]).is_err());
+
+    let info = manager.inspect("ui.theme");
+    assert_eq!(info.layer, Some(SettingsLayer::System));
+    assert_eq!(info.overridden_by_lock(), vec![SettingsLayer::User]);
+
+    // The locking layer itself can still change the value
+    manager
+        .set_in(SettingsLayer::System, "ui.theme", "light".into())
+        .unwrap();
+    assert_eq!(manager.get_string("ui.theme").as_deref(), Some("light"));
+
+    manager.unlock(SettingsLayer::System, "ui.theme");
+    assert_eq!(manager.get_string("ui.theme").as_deref(), Some("dark"));
+}
+
+#[rstest]
+fn lock_without_value_pins_default() {
+    let manager = SettingsManager::new();
+    manager.register_schema(workers_schema());
+    manager
+        .set_in(SettingsLayer::Workspace, "perf.workers", 32.into())
+        .unwrap();
+
+    manager.lock(SettingsLayer::System, "perf.workers");
+    assert_eq!(manager.get_integer("perf.workers"), Some(4));
+    assert_eq!(manager.source("perf.workers"), Some(SettingsLayer::Default));
+}
+
+#[rstest]
+fn locks_round_trip_through_files() {
+    let dir = TempDir::new().unwrap();
+    write(
+        &dir.path().join("system.json"),
+        r#"{ "ui.theme": "dark", "$locked": ["ui.theme"] }"#,
+    );
+    write(&dir.path().join("user.json"), r#"{ "ui.theme": "light" }"#);
+
+    let manager = layered(&dir);
+    manager.load().unwrap();
+    assert_eq!(manager.get_string("ui.theme").as_deref(), Some("dark"));
+    assert_eq!(manager.locked_by("ui.theme"), Some(SettingsLayer::System));
+    // The overridden user value is kept, not discarded
+    assert_eq!(
+        manager.get_in(SettingsLayer::User, "ui.theme"),
+        Some("light".into())
+    );
+
+    manager.lock(SettingsLayer::User, "perf.workers");
+    manager.save().unwrap();
+    let saved: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(dir.path().join("user.json")).unwrap())
+            .unwrap();
+    assert_eq!(saved["$locked"], serde_json::json!(["perf.workers"]));
+
+    // System files are not written by save()
+    manager
+        .set_in(SettingsLayer::System, "perf.workers", 2.into())
+        .unwrap();
+    manager.save().unwrap();
+    assert!(!std::fs::read_to_string(dir.path().join("system.json"))
+        .unwrap()
+        .contains("perf.workers"));
+}
+
+#[rstest]
+fn malformed_lock_list_is_rejected() {
+    let dir = TempDir::new().unwrap();
+    write(
+        &dir.path().join("system.json"),
+        r#"{ "$locked": "ui.theme" }"#,
+    );
+    let manager = layered(&dir);
+    assert!(matches!(manager.load(), Err(SettingsError::InvalidKey(_))));
+}
+
+// ---------------------------------------------------------------------------
+// Change notifications and reloading
+// ---------------------------------------------------------------------------
+
+#[rstest]
+fn shadowed_writes_do_not_notify() {
+    let manager = SettingsManager::new();
+    manager.register_schema(theme_schema());
+    manager
+        .set_in(SettingsLayer::Workspace, "ui.theme", "dark".into())
+        .unwrap();
+    let changes = record(&manager);
+
+    manager.set("ui.theme", "system".into()).unwrap();
+    assert!(changes.lock().is_empty());
+
+    manager.remove_in(SettingsLayer::Workspace, "ui.theme");
+    assert_eq!(
+        changes.lock().as_slice(),
+        &[("ui.theme".to_string(), "dark".into(), "system".into())]
+    );
+}
+
+#[rstest]
+fn reload_changed_fires_on_change() {
+    let dir = TempDir::new().unwrap();
+    let workspace = dir.path().join("workspace.json");
+    write(&workspace, r#"{ "perf.workers": 8 }"#);
+    let manager = layered(&dir);
+    manager.load().unwrap();
+    let changes = record(&manager);
+
+    assert!(manager.reload_changed().unwrap().is_empty());
+
+    write(&workspace, r#"{ "perf.workers": 16, "ui.theme": "dark" }"#);
+    assert_eq!(
+        manager.reload_changed().unwrap(),
+        vec![SettingsLayer::Workspace]
+    );
+    let mut seen = changes.lock().clone();
+    seen.sort_by(|a, b| a.0.cmp(&b.0));
+    assert_eq!(
+        seen,
+        vec![
+            ("perf.workers".to_string(), 8.into(), 16.into()),
+            ("ui.theme".to_string(), "light".into(), "dark".into()),
+        ]
+    );
+
+    // Deleting the file falls back to lower layers
+    changes.lock().clear();
+    std::fs::remove_file(&workspace).unwrap();
+    manager.reload_changed().unwrap();
+    assert_eq!(manager.get_integer("perf.workers"), Some(4));
+    assert_eq!(changes.lock().len(), 2);
+}
+
+#[rstest]
+fn own_saves_are_not_reloaded() {
+    let dir = TempDir::new().unwrap();
+    let manager = layered(&dir);
+    manager.set("ui.theme", "dark".into()).unwrap();
+    manager.save().unwrap();
+    assert!(manager.reload_changed().unwrap().is_empty());
+}
+
+#[rstest]
+fn invalid_edit_keeps_previous_values() {
+    let dir = TempDir::new().unwrap();
+    let user = dir.path().join("user.json");
+    write(&user, r#"{ "ui.theme": "dark" }"#);
+    let manager = layered(&dir);
+    manager.load().unwrap();
+
+    write(&user, r#"{ "ui.theme": "neon" }"#);
+    assert!(manager.reload_changed().is_err());
+    assert_eq!(manager.get_string("ui.theme").as_deref(), Some("dark"));
+    // Not retried until the file changes again
+    assert!(manager.reload_changed().unwrap().is_empty());
+}
+
+#[rstest]
+fn watcher_reloads_in_background() {
+    let dir = TempDir::new().unwrap();
+    let workspace = dir.path().join("workspace.json");
+    let manager = layered(&dir);
+    manager.load().unwrap();
+    let changes = record(&manager);
+
+    let watcher = manager.watch(Duration::from_millis(10));
+    write(&workspace, r#"{ "ui.theme": "system" }"#);
+
+    let deadline = Instant::now() + Duration::from_secs(5);
+    while changes.lock().is_empty() && Instant::now() < deadline {
+        std::thread::sleep(Duration::from_millis(10));
+    }
+    watcher.stop();
+    assert_eq!(manager.get_string("ui.theme").as_deref(), Some("system"));
+    assert_eq!(changes.lock()[0].0, "ui.theme");
+}
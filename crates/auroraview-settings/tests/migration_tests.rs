@@ -0,0 +1,295 @@
+//! Tests for settings schema versioning and migrations
+
+use std::path::Path;
+
+use auroraview_settings::{
+    Migration, SettingSchema, SettingValue, SettingsError, SettingsLayer, SettingsManager,
+    SettingsMap, SettingsMigrator, LOCKED_KEY, VERSION_KEY,
+};
+use rstest::rstest;
+use serde_json::{json, Value};
+use tempfile::TempDir;
+
+fn map(value: Value) -> SettingsMap {
+    serde_json::from_value(value).unwrap()
+}
+
+fn read_json(path: &Path) -> Value {
+    serde_json::from_str(&std::fs::read_to_string(path).unwrap()).unwrap()
+}
+
+/// v0 -> v1: `dark_mode` bool becomes the `ui.theme` enum
+/// v1 -> v2: `proxy` "host:port" splits, `legacy.cache` is dropped
+fn migrator() -> SettingsMigrator {
+    SettingsMigrator::new(2)
+        .with_migration(
+            0,
+            Migration::new()
+                .rename("dark_mode", "ui.theme")
+                .map_values("ui.theme", [(true, "dark"), (false, "light")]),
+        )
+        .with_migration(
+            1,
+            Migration::new()
+                .split("proxy", |value| {
+                    let Some((host, port)) = value.as_str().and_then(|s| s.split_once(':')) else {
+                        return Vec::new();
+                    };
+                    let mut parts = vec![("proxy.host".to_string(), host.into())];
+                    if let Ok(port) = port.parse::<i64>() {
+                        parts.push(("proxy.port".to_string(), port.into()));
+                    }
+                    parts
+                })
+                .remove("legacy.cache"),
+        )
+}
+
+fn schemas() -> Vec<SettingSchema> {
+    vec![
+        SettingSchema::builder("ui.theme")
+            .enum_type(vec!["light".into(), "dark".into()])
+            .default("light")
+            .build(),
+        SettingSchema::builder("proxy.port")
+            .integer_type(Some(1), Some(65535))
+            .default(8080)
+            .build(),
+    ]
+}
+
+fn manager(path: &Path) -> SettingsManager {
+    let manager = SettingsManager::with_storage(path).with_migrator(migrator());
+    manager.register_schemas(schemas());
+    manager
+}
+
+// ---------------------------------------------------------------------------
+// Migration steps
+// ---------------------------------------------------------------------------
+
+#[rstest]
+fn rename_keeps_existing_target() {
+    let mut values = map(json!({ "old": 1, "new": 2 }));
+    Migration::new()
+        .rename("old", "new")
+        .apply(&mut values)
+        .unwrap();
+    assert_eq!(values, map(json!({ "new": 2 })));
+}
+
+#[rstest]
+#[case(json!(true), Some(json!("dark")))]
+#[case(json!(false), Some(json!("light")))]
+#[case(json!("weird"), None)]
+fn map_values_converts_types(#[case] old: Value, #[case] new: Option<Value>) {
+    let mut values = map(json!({ "ui.dark": old }));
+    Migration::new()
+        .map_values("ui.dark", [(true, "dark"), (false, "light")])
+        .apply(&mut values)
+        .unwrap();
+    assert_eq!(
+        values.get("ui.dark").cloned(),
+        new.map(|v| serde_json::from_value(v).unwrap())
+    );
+}
+
+#[rstest]
+fn merge_combines_keys() {
+    let join = |values: &[Option<SettingValue>]| {
+        let parts: Vec<String> = values
+            .iter()
+            .flatten()
+            .filter_map(|v| v.as_str().map(String::from))
+            .collect();
+        Some(parts.join(":").into())
+    };
+
+    let mut values = map(json!({ "proxy.host": "example.com", "proxy.port": "3128" }));
+    Migration::new()
+        .merge(["proxy.host", "proxy.port"], "proxy", join)
+        .apply(&mut values)
+        .unwrap();
+    assert_eq!(values, map(json!({ "proxy": "example.com:3128" })));
+
+    // Not run when no source key is set
+    let mut values = map(json!({ "other": 1 }));
+    Migration::new()
+        .merge(["proxy.host", "proxy.port"], "proxy", join)
+        .apply(&mut values)
+        .unwrap();
+    assert_eq!(values, map(json!({ "other": 1 })));
+}
+
+#[rstest]
+fn locks_follow_keys() {
+    let mut values = map(json!({
+        "proxy": "h:1",
+        "legacy.cache": true,
+        LOCKED_KEY: ["proxy", "legacy.cache"],
+    }));
+    migrator().migrate(&mut values).unwrap();
+    assert_eq!(
+        values[LOCKED_KEY],
+        SettingValue::from(vec!["proxy.host", "proxy.port"])
+    );
+}
+
+#[rstest]
+fn custom_step_errors_propagate() {
+    let migrator = SettingsMigrator::new(1).with_migration(
+        0,
+        Migration::new().custom(|_| Err(SettingsError::Migration("boom".into()))),
+    );
+    let err = migrator.migrate(&mut SettingsMap::new()).unwrap_err();
+    assert!(matches!(err, SettingsError::Migration(ref m) if m == "boom"));
+}
+
+// ---------------------------------------------------------------------------
+// Versioning
+// ---------------------------------------------------------------------------
+
+#[rstest]
+fn migrates_through_every_version() {
+    let migrator = migrator();
+    let mut values = map(json!({
+        "dark_mode": true,
+        "proxy": "example.com:3128",
+        "legacy.cache": 1,
+        "untouched": "x",
+    }));
+    assert!(migrator.needs_migration(&values));
+    assert_eq!(migrator.migrate(&mut values).unwrap(), 0);
+    assert_eq!(
+        values,
+        map(json!({
+            "ui.theme": "dark",
+            "proxy.host": "example.com",
+            "proxy.port": 3128,
+            "untouched": "x",
+            VERSION_KEY: 2,
+        }))
+    );
+    assert!(!migrator.needs_migration(&values));
+}
+
+#[rstest]
+fn starts_from_stored_version() {
+    // dark_mode at v1 is not renamed again
+    let mut values = map(json!({ "dark_mode": true, VERSION_KEY: 1 }));
+    assert_eq!(migrator().migrate(&mut values).unwrap(), 1);
+    assert_eq!(values["dark_mode"], SettingValue::Bool(true));
+}
+
+#[rstest]
+fn rejects_newer_versions() {
+    let mut values = map(json!({ VERSION_KEY: 3 }));
+    let err = migrator().migrate(&mut values).unwrap_err();
+    assert!(matches!(
+        err,
+        SettingsError::UnsupportedVersion {
+            found: 3,
+            supported: 2
+        }
+    ));
+}
+
+// ---------------------------------------------------------------------------
+// SettingsManager integration
+// ---------------------------------------------------------------------------
+
+#[rstest]
+fn load_migrates_and_keeps_backup() {
+    let dir = TempDir::new().unwrap();
+    let path = dir.path().join("settings.json");
+    let original = r#"{ "dark_mode": true, "proxy": "example.com:3128" }"#;
+    std::fs::write(&path, original).unwrap();
+
+    let manager = manager(&path);
+    manager.load().unwrap();
+    assert_eq!(manager.get_string("ui.theme").as_deref(), Some("dark"));
+    assert_eq!(manager.get_integer("proxy.port"), Some(3128));
+    assert_eq!(manager.get("dark_mode"), None);
+    assert_eq!(manager.get_in(SettingsLayer::User, VERSION_KEY), None);
+
+    let backup = dir.path().join("settings.json.v0.bak");
+    assert_eq!(std::fs::read_to_string(&backup).unwrap(), original);
+    let migrated = read_json(&path);
+    assert_eq!(migrated[VERSION_KEY], 2);
+    assert!(migrated.get("dark_mode").is_none());
+
+    // Loading again does not migrate twice
+    std::fs::remove_file(&backup).unwrap();
+    manager.load().unwrap();
+    assert!(!backup.exists());
+}
+
+#[rstest]
+fn invalid_migration_result_leaves_file_alone() {
+    let dir = TempDir::new().unwrap();
+    let path = dir.path().join("settings.json");
+    let original = r#"{ "proxy": "example.com:99999" }"#;
+    std::fs::write(&path, original).unwrap();
+
+    let manager = manager(&path);
+    assert!(matches!(
+        manager.load(),
+        Err(SettingsError::ValidationFailed { .. })
+    ));
+    assert_eq!(std::fs::read_to_string(&path).unwrap(), original);
+    assert!(!dir.path().join("settings.json.v0.bak").exists());
+}
+
+#[rstest]
+fn newer_file_is_not_loaded() {
+    let dir = TempDir::new().unwrap();
+    let path = dir.path().join("settings.json");
+    std::fs::write(&path, r#"{ "$version": 5, "ui.theme": "dark" }"#).unwrap();
+
+    let manager = manager(&path);
+    assert!(matches!(
+        manager.load(),
+        Err(SettingsError::UnsupportedVersion { found: 5, .. })
+    ));
+    assert_eq!(manager.get_string("ui.theme").as_deref(), Some("light"));
+}
+
+#[rstest]
+fn save_stamps_version() {
+    let dir = TempDir::new().unwrap();
+    let path = dir.path().join("settings.json");
+
+    let manager = manager(&path);
+    manager.set("ui.theme", "dark".into()).unwrap();
+    manager.save().unwrap();
+    assert_eq!(
+        read_json(&path),
+        json!({ "ui.theme": "dark", VERSION_KEY: 2 })
+    );
+
+    // Without a migrator no version is written, and stored versions are ignored
+    let plain = SettingsManager::with_storage(&path);
+    plain.load().unwrap();
+    assert_eq!(plain.user_settings().len(), 1);
+    plain.save().unwrap();
+    assert_eq!(read_json(&path), json!({ "ui.theme": "dark" }));
+}
+
+#[rstest]
+fn every_layer_is_migrated() {
+    let dir = TempDir::new().unwrap();
+    let system = dir.path().join("system.json");
+    std::fs::write(&system, r#"{ "dark_mode": false }"#).unwrap();
+
+    let manager = SettingsManager::new()
+        .with_layer_file(SettingsLayer::System, &system)
+        .with_migrator(migrator());
+    manager.register_schemas(schemas());
+    manager.load().unwrap();
+
+    assert_eq!(
+        manager.get_in(SettingsLayer::System, "ui.theme"),
+        Some("light".into())
+    );
+    assert!(dir.path().join("system.json.v0.bak").exists());
+}
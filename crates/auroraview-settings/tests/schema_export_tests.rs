@@ -0,0 +1,242 @@
+//! Tests for JSON Schema export and generated settings forms
+
+use std::collections::HashMap;
+
+use auroraview_settings::{
+    FormWidget, SchemaRegistry, SchemaType, SettingSchema, SettingValue, SettingsLayer,
+    SettingsManager, GENERAL_CATEGORY, JSON_SCHEMA_DIALECT,
+};
+use rstest::rstest;
+use serde_json::json;
+
+fn schemas() -> Vec<SettingSchema> {
+    vec![
+        SettingSchema::builder("ui.theme")
+            .title("Theme")
+            .description("Color theme")
+            .enum_type(vec!["light".into(), "dark".into()])
+            .default("light")
+            .category("appearance")
+            .build(),
+        SettingSchema::builder("ui.font_size")
+            .title("Font size")
+            .integer_type(Some(8), Some(32))
+            .default(14)
+            .category("appearance")
+            .build(),
+        SettingSchema::builder("perf.gpu")
+            .bool_type()
+            .default(true)
+            .category("performance")
+            .requires_restart()
+            .build(),
+        SettingSchema::builder("misc.legacy_mode")
+            .bool_type()
+            .default(false)
+            .deprecated("Use perf.gpu instead")
+            .build(),
+    ]
+}
+
+fn registry() -> SchemaRegistry {
+    let mut registry = SchemaRegistry::new();
+    for schema in schemas() {
+        registry.register(schema);
+    }
+    registry
+}
+
+fn manager() -> SettingsManager {
+    let manager = SettingsManager::new();
+    manager.register_schemas(schemas());
+    manager
+}
+
+fn values(pairs: &[(&str, SettingValue)]) -> HashMap<String, SettingValue> {
+    pairs
+        .iter()
+        .map(|(k, v)| (k.to_string(), v.clone()))
+        .collect()
+}
+
+// ---------------------------------------------------------------------------
+// JSON Schema
+// ---------------------------------------------------------------------------
+
+#[rstest]
+fn registry_exports_draft_2020_12() {
+    let schema = registry().to_json_schema();
+    assert_eq!(schema["$schema"], JSON_SCHEMA_DIALECT);
+    assert_eq!(schema["type"], "object");
+    assert_eq!(schema["additionalProperties"], true);
+
+    let props = &schema["properties"];
+    assert_eq!(
+        props["ui.theme"],
+        json!({
+            "type": "string",
+            "enum": ["light", "dark"],
+            "title": "Theme",
+            "description": "Color theme",
+            "default": "light",
+            "x-category": "appearance",
+        })
+    );
+    assert_eq!(props["ui.font_size"]["minimum"], 8);
+    assert_eq!(props["ui.font_size"]["maximum"], 32);
+    assert_eq!(props["perf.gpu"]["x-requires-restart"], true);
+    assert_eq!(props["misc.legacy_mode"]["deprecated"], true);
+    assert_eq!(
+        props["misc.legacy_mode"]["deprecationMessage"],
+        "Use perf.gpu instead"
+    );
+    assert_eq!(
+        props["$locked"]["items"]["enum"],
+        json!(["misc.legacy_mode", "perf.gpu", "ui.font_size", "ui.theme"])
+    );
+}
+
+#[rstest]
+#[case(SchemaType::Bool, json!({"type": "boolean"}))]
+#[case(
+    SchemaType::Float { min: Some(0.5), max: None },
+    json!({"type": "number", "minimum": 0.5})
+)]
+#[case(
+    SchemaType::String { pattern: Some("^[a-z]+$".into()), max_length: Some(10) },
+    json!({"type": "string", "pattern": "^[a-z]+$", "maxLength": 10})
+)]
+#[case(
+    SchemaType::Array { items: Some(Box::new(SchemaType::Integer { min: None, max: Some(3) })) },
+    json!({"type": "array", "items": {"type": "integer", "maximum": 3}})
+)]
+#[case(SchemaType::Object, json!({"type": "object"}))]
+fn schema_type_keywords(#[case] schema_type: SchemaType, #[case] expected: serde_json::Value) {
+    assert_eq!(schema_type.to_json_schema(), expected);
+}
+
+#[rstest]
+fn empty_registry_still_describes_files() {
+    let schema = SchemaRegistry::new().to_json_schema();
+    let props = schema["properties"].as_object().unwrap();
+    assert_eq!(props.len(), 2);
+    assert_eq!(props["$version"]["type"], "integer");
+}
+
+// ---------------------------------------------------------------------------
+// Forms
+// ---------------------------------------------------------------------------
+
+#[rstest]
+fn form_groups_by_category() {
+    let form = manager().form();
+    let ids: Vec<_> = form.sections.iter().map(|s| s.id.as_str()).collect();
+    assert_eq!(ids, vec!["appearance", "performance", GENERAL_CATEGORY]);
+    assert_eq!(form.sections[0].title, "Appearance");
+
+    let keys: Vec<_> = form.sections[0]
+        .fields
+        .iter()
+        .map(|f| f.key.as_str())
+        .collect();
+    assert_eq!(keys, vec!["ui.font_size", "ui.theme"]);
+
+    let theme = form.field("ui.theme").unwrap();
+    assert_eq!(
+        theme.widget,
+        FormWidget::Select {
+            options: vec!["light".into(), "dark".into()]
+        }
+    );
+    assert_eq!(theme.value, "light".into());
+    assert_eq!(theme.source, Some(SettingsLayer::Default));
+
+    let gpu = form.field("perf.gpu").unwrap();
+    assert_eq!(gpu.widget, FormWidget::Toggle);
+    assert!(gpu.requires_restart);
+    assert_eq!(
+        form.field("misc.legacy_mode")
+            .unwrap()
+            .deprecated
+            .as_deref(),
+        Some("Use perf.gpu instead")
+    );
+}
+
+#[rstest]
+fn form_reflects_layers_and_locks() {
+    let manager = manager();
+    manager.set("ui.font_size", 18.into()).unwrap();
+    manager
+        .set_in(SettingsLayer::System, "perf.gpu", false.into())
+        .unwrap();
+    manager.lock(SettingsLayer::System, "perf.gpu");
+
+    let form = manager.form();
+    let size = form.field("ui.font_size").unwrap();
+    assert_eq!(size.value, 18.into());
+    assert_eq!(size.default, 14.into());
+    assert_eq!(size.source, Some(SettingsLayer::User));
+    assert_eq!(
+        form.field("perf.gpu").unwrap().locked_by,
+        Some(SettingsLayer::System)
+    );
+}
+
+#[rstest]
+fn form_serializes_for_frontend() {
+    let json = serde_json::to_value(manager().form()).unwrap();
+    let field = &json["sections"][0]["fields"][0];
+    assert_eq!(field["key"], "ui.font_size");
+    assert_eq!(
+        field["widget"],
+        json!({"kind": "number", "min": 8.0, "max": 32.0, "integer": true})
+    );
+    assert_eq!(field["requiresRestart"], false);
+    assert!(field.get("lockedBy").is_none());
+}
+
+#[rstest]
+fn submit_applies_valid_values() {
+    let manager = manager();
+    manager.set("ui.theme", "dark".into()).unwrap();
+
+    let result = manager.submit_form(values(&[
+        ("ui.font_size", 20.into()),
+        ("ui.theme", SettingValue::Null),
+    ]));
+    assert!(result.is_ok());
+    assert_eq!(result.applied, vec!["ui.font_size", "ui.theme"]);
+    assert_eq!(manager.get_integer("ui.font_size"), Some(20));
+    // null resets to the default
+    assert_eq!(manager.get_string("ui.theme").as_deref(), Some("light"));
+}
+
+#[rstest]
+fn submit_maps_errors_to_keys() {
+    let manager = manager();
+    manager.lock(SettingsLayer::System, "perf.gpu");
+
+    let result = manager.submit_form(values(&[
+        ("ui.font_size", 100.into()),
+        ("ui.theme", "light".into()),
+        ("perf.gpu", false.into()),
+        ("unknown.key", 1.into()),
+    ]));
+    assert!(!result.is_ok());
+    assert!(result.applied.is_empty());
+    assert_eq!(
+        result.errors.keys().collect::<Vec<_>>(),
+        vec!["perf.gpu", "ui.font_size", "unknown.key"]
+    );
+    assert_eq!(
+        result.errors["ui.font_size"],
+        "Value 100 is greater than maximum 32"
+    );
+    assert!(result.errors["perf.gpu"].contains("locked"));
+    // Nothing was applied
+    assert_eq!(manager.get_integer("ui.font_size"), Some(14));
+
+    let json = serde_json::to_value(&result).unwrap();
+    assert!(json["errors"]["unknown.key"].is_string());
+}
@@ -51,6 +51,16 @@ pub enum TabEvent {
     /// Tab audible state changed
     AudibleChanged { tab_id: TabId, audible: bool },
 
+    // === Tab Lifecycle State Events ===
+    /// Background tab frozen (execution suspended)
+    Frozen { tab_id: TabId },
+    /// Frozen tab resumed
+    Resumed { tab_id: TabId },
+    /// Tab discarded - the WebView can be released; title, favicon and URL are kept
+    Discarded { tab_id: TabId },
+    /// Discarded tab activated - a WebView must be created and `url` loaded
+    Reloaded { tab_id: TabId, url: String },
+
     // === Tab Group Events ===
     /// Tab added to group
     AddedToGroup { tab_id: TabId, group_id: TabGroupId },
@@ -147,6 +157,17 @@ impl TabEvent {
         )
     }
 
+    /// Check if this event is a freeze/discard event
+    pub fn is_discard_event(&self) -> bool {
+        matches!(
+            self,
+            Self::Frozen { .. }
+                | Self::Resumed { .. }
+                | Self::Discarded { .. }
+                | Self::Reloaded { .. }
+        )
+    }
+
     /// Check if this event is a group event
     pub fn is_group_event(&self) -> bool {
         matches!(
@@ -188,5 +209,11 @@ mod tests {
             name: "Work".to_string(),
         };
         assert!(group.is_group_event());
+
+        let discarded = TabEvent::Discarded {
+            tab_id: "1".to_string(),
+        };
+        assert!(discarded.is_discard_event());
+        assert!(!discarded.is_lifecycle_event());
     }
 }
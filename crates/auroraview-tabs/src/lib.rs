@@ -8,7 +8,9 @@
 //!
 //! - Tab state management
 //! - Tab groups
-//! - Session persistence
+//! - Session persistence and crash recovery
+//! - Per-tab navigation history and scroll position
+//! - Tab freezing and discarding under memory pressure
 //! - Tab events
 //!
 //! # Example
@@ -31,7 +33,9 @@
 mod error;
 mod event;
 mod group;
+mod lifecycle;
 mod manager;
+mod navigation;
 mod session;
 mod state;
 
@@ -41,9 +45,13 @@ pub use error::{Result, TabError};
 pub use event::TabEvent;
 /// Tab grouping types for organizing related tabs.
 pub use group::{TabGroup, TabGroupId};
+/// Tab lifecycle states and the freeze/discard policy.
+pub use lifecycle::{DiscardPolicy, TabLifecycle};
 /// Tab manager for creating, switching, reordering, and closing tabs.
 pub use manager::TabManager;
+/// Per-tab navigation stack and scroll position types.
+pub use navigation::{NavigationEntry, NavigationHistory, ScrollPosition};
 /// Session persistence types for saving and restoring tab state.
-pub use session::{Session, SessionManager};
+pub use session::{RecoveryInfo, Session, SessionEntry, SessionManager};
 /// Tab state types: identifier, loading state, and security indicators.
 pub use state::{SecurityState, TabId, TabState};
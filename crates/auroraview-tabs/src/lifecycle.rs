@@ -0,0 +1,210 @@
+//! Tab lifecycle and discard policy
+//!
+//! Background tabs are frozen and later discarded to bound memory use:
+//!
+//! - `Active` - the selected tab
+//! - `Background` - live but hidden
+//! - `Frozen` - live but suspended (no timers, no script execution)
+//! - `Discarded` - the WebView is released; title, favicon and URL are kept
+//!   and the page is reloaded when the tab is activated
+
+use crate::{TabId, TabState};
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+/// Lifecycle state of a tab
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TabLifecycle {
+    /// Selected tab
+    Active,
+    /// Live, hidden tab
+    #[default]
+    Background,
+    /// Live tab with execution suspended
+    Frozen,
+    /// Tab without a WebView
+    Discarded,
+}
+
+impl TabLifecycle {
+    /// Check if the tab holds a WebView
+    pub fn is_live(self) -> bool {
+        self != Self::Discarded
+    }
+}
+
+/// When background tabs are frozen and discarded
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DiscardPolicy {
+    /// Idle time after which a background tab is frozen
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub freeze_after: Option<Duration>,
+    /// Idle time after which a background tab is discarded
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub discard_after: Option<Duration>,
+    /// Maximum number of tabs holding a WebView
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_live_tabs: Option<usize>,
+    /// Never freeze or discard pinned tabs
+    pub exempt_pinned: bool,
+    /// Never freeze or discard tabs playing audio
+    pub exempt_audible: bool,
+}
+
+impl Default for DiscardPolicy {
+    fn default() -> Self {
+        Self {
+            freeze_after: Some(Duration::from_secs(5 * 60)),
+            discard_after: Some(Duration::from_secs(30 * 60)),
+            max_live_tabs: Some(10),
+            exempt_pinned: true,
+            exempt_audible: true,
+        }
+    }
+}
+
+impl DiscardPolicy {
+    /// Policy that never freezes or discards tabs
+    pub fn disabled() -> Self {
+        Self {
+            freeze_after: None,
+            discard_after: None,
+            max_live_tabs: None,
+            exempt_pinned: true,
+            exempt_audible: true,
+        }
+    }
+
+    /// Set the idle time before freezing
+    pub fn with_freeze_after(mut self, idle: Option<Duration>) -> Self {
+        self.freeze_after = idle;
+        self
+    }
+
+    /// Set the idle time before discarding
+    pub fn with_discard_after(mut self, idle: Option<Duration>) -> Self {
+        self.discard_after = idle;
+        self
+    }
+
+    /// Set the maximum number of live tabs
+    pub fn with_max_live_tabs(mut self, max: Option<usize>) -> Self {
+        self.max_live_tabs = max;
+        self
+    }
+
+    /// Set whether pinned tabs are exempt
+    pub fn with_exempt_pinned(mut self, exempt: bool) -> Self {
+        self.exempt_pinned = exempt;
+        self
+    }
+
+    /// Set whether audible tabs are exempt
+    pub fn with_exempt_audible(mut self, exempt: bool) -> Self {
+        self.exempt_audible = exempt;
+        self
+    }
+
+    /// Check if a tab may be frozen or discarded
+    pub fn is_exempt(&self, tab: &TabState) -> bool {
+        tab.lifecycle == TabLifecycle::Active
+            || (self.exempt_pinned && tab.pinned)
+            || (self.exempt_audible && tab.audible)
+    }
+
+    /// Compute the transitions for a set of tabs at `now` (ms since the epoch)
+    ///
+    /// Idle tabs are frozen or discarded first; then the least recently
+    /// active tabs are discarded until at most `max_live_tabs` remain.
+    pub fn plan(&self, tabs: &[TabState], now: i64) -> Vec<(TabId, TabLifecycle)> {
+        let idle = |tab: &TabState, after: Option<Duration>| {
+            after.is_some_and(|after| {
+                let elapsed = now.saturating_sub(tab.last_active_at).max(0) as u128;
+                elapsed >= after.as_millis()
+            })
+        };
+
+        let mut targets: Vec<(&TabState, TabLifecycle)> = tabs
+            .iter()
+            .map(|tab| {
+                let target = if self.is_exempt(tab) || !tab.lifecycle.is_live() {
+                    tab.lifecycle
+                } else if idle(tab, self.discard_after) {
+                    TabLifecycle::Discarded
+                } else if tab.lifecycle == TabLifecycle::Background && idle(tab, self.freeze_after)
+                {
+                    TabLifecycle::Frozen
+                } else {
+                    tab.lifecycle
+                };
+                (tab, target)
+            })
+            .collect();
+
+        if let Some(max) = self.max_live_tabs {
+            let mut live: Vec<usize> = (0..targets.len())
+                .filter(|&i| targets[i].1.is_live())
+                .collect();
+            let excess = live.len().saturating_sub(max);
+            live.retain(|&i| !self.is_exempt(targets[i].0));
+            live.sort_by_key(|&i| targets[i].0.last_active_at);
+            for i in live.into_iter().take(excess) {
+                targets[i].1 = TabLifecycle::Discarded;
+            }
+        }
+
+        targets
+            .into_iter()
+            .filter(|(tab, target)| tab.lifecycle != *target)
+            .map(|(tab, target)| (tab.id.clone(), target))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tab(id: &str, lifecycle: TabLifecycle, last_active_at: i64) -> TabState {
+        let mut tab = TabState::new(id.to_string(), "https://example.com");
+        tab.lifecycle = lifecycle;
+        tab.last_active_at = last_active_at;
+        tab
+    }
+
+    #[test]
+    fn test_idle_tabs_freeze_then_discard() {
+        let policy = DiscardPolicy::default().with_max_live_tabs(None);
+        let minute = 60_000;
+        let tabs = vec![
+            tab("a", TabLifecycle::Active, 0),
+            tab("b", TabLifecycle::Background, 50 * minute),
+            tab("c", TabLifecycle::Background, 40 * minute),
+            tab("d", TabLifecycle::Frozen, 0),
+        ];
+
+        let plan = policy.plan(&tabs, 50 * minute);
+        assert_eq!(
+            plan,
+            vec![
+                ("c".to_string(), TabLifecycle::Frozen),
+                ("d".to_string(), TabLifecycle::Discarded),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_max_live_tabs_discards_least_recent() {
+        let policy = DiscardPolicy::disabled().with_max_live_tabs(Some(2));
+        let tabs = vec![
+            tab("a", TabLifecycle::Active, 1),
+            tab("b", TabLifecycle::Background, 3),
+            tab("c", TabLifecycle::Frozen, 2),
+        ];
+
+        let plan = policy.plan(&tabs, 10);
+        assert_eq!(plan, vec![("c".to_string(), TabLifecycle::Discarded)]);
+    }
+}
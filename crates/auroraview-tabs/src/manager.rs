@@ -1,11 +1,15 @@
 //! Tab manager implementation
 
 use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::Arc;
 
 use dashmap::DashMap;
 use parking_lot::RwLock;
 
-use crate::{Result, TabError, TabEvent, TabGroup, TabGroupId, TabId, TabState};
+use crate::{
+    DiscardPolicy, NavigationHistory, Result, ScrollPosition, Session, SessionManager, TabError,
+    TabEvent, TabGroup, TabGroupId, TabId, TabLifecycle, TabState,
+};
 
 /// Tab manager - manages tab states without WebView dependency
 ///
@@ -14,6 +18,7 @@ use crate::{Result, TabError, TabEvent, TabGroup, TabGroupId, TabId, TabState};
 /// - Tab ordering
 /// - Tab groups
 /// - Active tab tracking
+/// - Tab freezing and discarding (see [`DiscardPolicy`])
 ///
 /// The actual WebView instances are managed by the Browser or application layer.
 pub struct TabManager {
@@ -30,6 +35,10 @@ pub struct TabManager {
     /// Event handlers
     #[allow(clippy::type_complexity)]
     event_handlers: RwLock<Vec<Box<dyn Fn(&TabEvent) + Send + Sync>>>,
+    /// Session snapshots for crash recovery
+    session: RwLock<Option<Arc<SessionManager>>>,
+    /// Freeze/discard policy
+    discard_policy: RwLock<DiscardPolicy>,
 }
 
 impl TabManager {
@@ -42,9 +51,26 @@ impl TabManager {
             groups: DashMap::new(),
             tab_counter: AtomicU32::new(0),
             event_handlers: RwLock::new(Vec::new()),
+            session: RwLock::new(None),
+            discard_policy: RwLock::new(DiscardPolicy::disabled()),
         }
     }
 
+    /// Set the freeze/discard policy (tabs are never discarded by default)
+    pub fn with_discard_policy(self, policy: DiscardPolicy) -> Self {
+        *self.discard_policy.write() = policy;
+        self
+    }
+
+    /// Snapshot the session on every tab change
+    ///
+    /// Snapshots are throttled by the session manager; call
+    /// [`TabManager::flush_session`] before shutdown to write the last one.
+    pub fn with_session(self, session: Arc<SessionManager>) -> Self {
+        *self.session.write() = Some(session);
+        self
+    }
+
     /// Generate a unique tab ID
     fn next_tab_id(&self) -> TabId {
         let id = self.tab_counter.fetch_add(1, Ordering::SeqCst);
@@ -59,6 +85,17 @@ impl TabManager {
         }
     }
 
+    /// Record a change for the session snapshot
+    ///
+    /// Must be called without holding any tab, group or order lock.
+    fn changed(&self) {
+        let session = self.session.read().clone();
+        if let Some(session) = session {
+            // Tab operations must not fail because a snapshot could not be written
+            let _ = session.record(|| self.snapshot());
+        }
+    }
+
     // ========== Tab Operations ==========
 
     /// Create a new tab
@@ -74,16 +111,22 @@ impl TabManager {
             let mut order = self.tab_order.write();
             order.push(tab_id.clone());
         }
-        {
+        let became_active = {
             let mut active = self.active_tab_id.write();
-            if active.is_none() {
+            let first = active.is_none();
+            if first {
                 *active = Some(tab_id.clone());
             }
-        }
+            first
+        };
 
         self.emit(&TabEvent::Created {
             tab_id: tab_id.clone(),
         });
+        if became_active {
+            self.transition(&tab_id, TabLifecycle::Active);
+        }
+        self.changed();
 
         tab_id
     }
@@ -100,16 +143,22 @@ impl TabManager {
             let mut order = self.tab_order.write();
             order.push(tab_id.clone());
         }
-        {
+        let became_active = {
             let mut active = self.active_tab_id.write();
-            if active.is_none() {
+            let first = active.is_none();
+            if first {
                 *active = Some(tab_id.clone());
             }
-        }
+            first
+        };
 
         self.emit(&TabEvent::Created {
             tab_id: tab_id.clone(),
         });
+        if became_active {
+            self.transition(&tab_id, TabLifecycle::Active);
+        }
+        self.changed();
 
         tab_id
     }
@@ -136,17 +185,24 @@ impl TabManager {
         self.tabs.remove(tab_id);
 
         // Update active tab
-        {
+        let next_active = {
             let mut active = self.active_tab_id.write();
             if active.as_ref() == Some(tab_id) {
                 let order = self.tab_order.read();
                 *active = order.first().cloned();
+                active.clone()
+            } else {
+                None
             }
-        }
+        };
 
         self.emit(&TabEvent::Closed {
             tab_id: tab_id.clone(),
         });
+        if let Some(next) = next_active {
+            self.transition(&next, TabLifecycle::Active);
+        }
+        self.changed();
 
         Ok(())
     }
@@ -166,13 +222,16 @@ impl TabManager {
 
         if let Some(old_id) = old_active {
             if old_id != *tab_id {
+                self.transition(&old_id, TabLifecycle::Background);
                 self.emit(&TabEvent::Deactivated { tab_id: old_id });
             }
         }
+        self.transition(tab_id, TabLifecycle::Active);
 
         self.emit(&TabEvent::Activated {
             tab_id: tab_id.clone(),
         });
+        self.changed();
 
         Ok(())
     }
@@ -187,9 +246,14 @@ impl TabManager {
     where
         F: FnOnce(&mut TabState) -> R,
     {
-        self.tabs
+        let result = self
+            .tabs
             .get_mut(tab_id)
-            .map(|mut entry| f(entry.value_mut()))
+            .map(|mut entry| f(entry.value_mut()));
+        if result.is_some() {
+            self.changed();
+        }
+        result
     }
 
     /// Get all tabs in order
@@ -240,6 +304,7 @@ impl TabManager {
             tab_id: tab_id.clone(),
             title,
         });
+        self.changed();
     }
 
     /// Update tab URL
@@ -253,6 +318,7 @@ impl TabManager {
             tab_id: tab_id.clone(),
             url,
         });
+        self.changed();
     }
 
     /// Update tab loading state
@@ -278,6 +344,7 @@ impl TabManager {
             can_go_back,
             can_go_forward,
         });
+        self.changed();
     }
 
     /// Update tab favicon
@@ -291,6 +358,7 @@ impl TabManager {
             tab_id: tab_id.clone(),
             favicon_url,
         });
+        self.changed();
     }
 
     // ========== Tab Actions ==========
@@ -300,6 +368,7 @@ impl TabManager {
         if let Some(mut tab) = self.tabs.get_mut(tab_id) {
             tab.set_pinned(pinned);
         }
+        self.changed();
     }
 
     /// Mute/unmute a tab
@@ -307,16 +376,49 @@ impl TabManager {
         if let Some(mut tab) = self.tabs.get_mut(tab_id) {
             tab.set_muted(muted);
         }
+        self.changed();
     }
 
     /// Reorder a tab
     pub fn reorder(&self, tab_id: &TabId, new_index: usize) {
-        let mut order = self.tab_order.write();
-        if let Some(old_index) = order.iter().position(|id| id == tab_id) {
-            let id = order.remove(old_index);
-            let new_index = new_index.min(order.len());
-            order.insert(new_index, id);
+        {
+            let mut order = self.tab_order.write();
+            if let Some(old_index) = order.iter().position(|id| id == tab_id) {
+                let id = order.remove(old_index);
+                let new_index = new_index.min(order.len());
+                order.insert(new_index, id);
+            }
+        }
+        self.changed();
+    }
+
+    /// Update the scroll position of the current page
+    pub fn update_scroll(&self, tab_id: &TabId, x: f64, y: f64) {
+        if let Some(mut tab) = self.tabs.get_mut(tab_id) {
+            tab.set_scroll_position(ScrollPosition::new(x, y));
         }
+        self.changed();
+    }
+
+    /// Replace the navigation stack of a tab
+    pub fn set_navigation(&self, tab_id: &TabId, navigation: NavigationHistory) -> Result<()> {
+        let (can_go_back, can_go_forward) = {
+            let mut tab = self
+                .tabs
+                .get_mut(tab_id)
+                .ok_or_else(|| TabError::NotFound(tab_id.clone()))?;
+            tab.set_navigation(navigation);
+            (tab.can_go_back, tab.can_go_forward)
+        };
+
+        self.emit(&TabEvent::HistoryChanged {
+            tab_id: tab_id.clone(),
+            can_go_back,
+            can_go_forward,
+        });
+        self.changed();
+
+        Ok(())
     }
 
     /// Duplicate a tab (returns new tab ID)
@@ -330,6 +432,113 @@ impl TabManager {
         Ok(self.create(url))
     }
 
+    // ========== Tab Lifecycle ==========
+
+    /// Move a tab to a lifecycle state, emitting the matching event
+    fn transition(&self, tab_id: &TabId, lifecycle: TabLifecycle) {
+        let (from, url) = {
+            let Some(mut tab) = self.tabs.get_mut(tab_id) else {
+                return;
+            };
+            let from = tab.lifecycle;
+            tab.set_lifecycle(lifecycle);
+            if lifecycle == TabLifecycle::Active && from == TabLifecycle::Discarded {
+                tab.is_loading = true;
+            }
+            (from, tab.url.clone())
+        };
+
+        let tab_id = tab_id.clone();
+        let event = match (from, lifecycle) {
+            (from, to) if from == to => return,
+            (_, TabLifecycle::Frozen) => TabEvent::Frozen { tab_id },
+            (_, TabLifecycle::Discarded) => TabEvent::Discarded { tab_id },
+            (TabLifecycle::Frozen, _) => TabEvent::Resumed { tab_id },
+            (TabLifecycle::Discarded, _) => TabEvent::Reloaded { tab_id, url },
+            _ => return,
+        };
+        self.emit(&event);
+    }
+
+    /// Get the freeze/discard policy
+    pub fn discard_policy(&self) -> DiscardPolicy {
+        self.discard_policy.read().clone()
+    }
+
+    /// Replace the freeze/discard policy
+    pub fn set_discard_policy(&self, policy: DiscardPolicy) {
+        *self.discard_policy.write() = policy;
+    }
+
+    /// Freeze a background tab
+    pub fn freeze(&self, tab_id: &TabId) -> Result<()> {
+        self.check_suspendable(tab_id)?;
+        self.transition(tab_id, TabLifecycle::Frozen);
+        Ok(())
+    }
+
+    /// Discard a background tab
+    ///
+    /// The tab keeps its title, favicon and URL and is reloaded when it is
+    /// activated again.
+    pub fn discard(&self, tab_id: &TabId) -> Result<()> {
+        self.check_suspendable(tab_id)?;
+        self.transition(tab_id, TabLifecycle::Discarded);
+        Ok(())
+    }
+
+    fn check_suspendable(&self, tab_id: &TabId) -> Result<()> {
+        let lifecycle = self
+            .tabs
+            .get(tab_id)
+            .map(|t| t.lifecycle)
+            .ok_or_else(|| TabError::NotFound(tab_id.clone()))?;
+        if lifecycle == TabLifecycle::Active {
+            return Err(TabError::InvalidOperation(format!(
+                "Cannot suspend the active tab: {}",
+                tab_id
+            )));
+        }
+        Ok(())
+    }
+
+    /// Freeze and discard background tabs according to the policy
+    ///
+    /// Call periodically (e.g. once a minute) and after opening tabs. Returns
+    /// the applied transitions.
+    pub fn apply_discard_policy(&self) -> Vec<(TabId, TabLifecycle)> {
+        let plan = self
+            .discard_policy
+            .read()
+            .plan(&self.all(), crate::session::current_timestamp());
+        for (tab_id, lifecycle) in &plan {
+            self.transition(tab_id, *lifecycle);
+        }
+        plan
+    }
+
+    /// Discard every background tab the policy does not exempt
+    ///
+    /// Call when the host reports memory pressure. Returns the discarded tabs.
+    pub fn handle_memory_pressure(&self) -> Vec<TabId> {
+        let policy = self.discard_policy.read().clone();
+        let discarded: Vec<TabId> = self
+            .all()
+            .into_iter()
+            .filter(|tab| tab.lifecycle.is_live() && !policy.is_exempt(tab))
+            .map(|tab| tab.id)
+            .collect();
+        for tab_id in &discarded {
+            self.transition(tab_id, TabLifecycle::Discarded);
+        }
+        discarded
+    }
+
+    /// Get the number of tabs holding a WebView
+    pub fn live_count(&self) -> usize {
+        self.tabs.iter().filter(|t| t.lifecycle.is_live()).count()
+    }
+
     // ========== Tab Groups ==========
 
     /// Create a tab group
@@ -337,6 +546,7 @@ impl TabManager {
         let group = TabGroup::new(name);
         let group_id = group.id.clone();
         self.groups.insert(group_id.clone(), group);
+        self.changed();
         group_id
     }
 
@@ -375,6 +585,7 @@ impl TabManager {
             tab_id: tab_id.clone(),
             group_id: group_id.clone(),
         });
+        self.changed();
 
         Ok(())
     }
@@ -396,6 +607,7 @@ impl TabManager {
                 tab_id: tab_id.clone(),
                 group_id,
             });
+            self.changed();
         }
 
         Ok(())
@@ -432,6 +644,7 @@ impl TabManager {
         self.emit(&TabEvent::GroupDeleted {
             group_id: group_id.clone(),
         });
+        self.changed();
 
         Ok(())
     }
@@ -446,6 +659,21 @@ impl TabManager {
                 group_id: group_id.clone(),
                 collapsed,
             });
+            self.changed();
+
+            Ok(())
+        } else {
+            Err(TabError::GroupNotFound(group_id.clone()))
+        }
+    }
+
+    /// Set the color of a group
+    pub fn set_group_color(&self, group_id: &TabGroupId, color: Option<String>) -> Result<()> {
+        if let Some(mut group) = self.groups.get_mut(group_id) {
+            group.set_color(color);
+            drop(group);
+
+            self.changed();
 
             Ok(())
         } else {
@@ -453,6 +681,94 @@ impl TabManager {
         }
     }
 
+    // ========== Session ==========
+
+    /// Take a snapshot of all tabs and groups
+    pub fn snapshot(&self) -> Session {
+        let order = self.order();
+        let position = |group: &TabGroup| {
+            group
+                .tab_ids
+                .iter()
+                .filter_map(|id| order.iter().position(|o| o == id))
+                .min()
+                .unwrap_or(usize::MAX)
+        };
+
+        let mut groups = self.all_groups();
+        groups.sort_by(|a, b| position(a).cmp(&position(b)).then(a.id.cmp(&b.id)));
+
+        Session::from_state(self.all(), self.active_id(), groups)
+    }
+
+    /// Replace all tabs and groups with a session
+    ///
+    /// Restores tab order, groups (including color and collapsed state),
+    /// navigation stacks and scroll positions. Tabs other than the active one
+    /// are restored discarded and load when activated. Emits `Created` for
+    /// every tab and `Activated` for the active tab.
+    pub fn restore(&self, session: &Session) {
+        self.tabs.clear();
+        self.groups.clear();
+
+        let mut order = Vec::with_capacity(session.tabs.len());
+        for tab in &session.tabs {
+            if self.tabs.contains_key(&tab.id) {
+                continue;
+            }
+            // Keep generated IDs from colliding with restored ones
+            if let Some(n) = tab
+                .id
+                .strip_prefix("tab_")
+                .and_then(|n| n.parse::<u32>().ok())
+            {
+                self.tab_counter
+                    .fetch_max(n.saturating_add(1), Ordering::SeqCst);
+            }
+            self.tabs.insert(tab.id.clone(), tab.clone());
+            order.push(tab.id.clone());
+        }
+
+        for group in &session.groups {
+            let mut group = group.clone();
+            group.tab_ids.retain(|id| self.tabs.contains_key(id));
+            self.groups.insert(group.id.clone(), group);
+        }
+
+        let active = session
+            .active_tab_id
+            .clone()
+            .filter(|id| self.tabs.contains_key(id))
+            .or_else(|| order.first().cloned());
+        for mut tab in self.tabs.iter_mut() {
+            tab.lifecycle = if Some(&tab.id) == active.as_ref() {
+                TabLifecycle::Active
+            } else {
+                TabLifecycle::Discarded
+            };
+            tab.is_loading = tab.lifecycle == TabLifecycle::Active;
+        }
+        *self.tab_order.write() = order.clone();
+        *self.active_tab_id.write() = active.clone();
+
+        for tab_id in order {
+            self.emit(&TabEvent::Created { tab_id });
+        }
+        if let Some(tab_id) = active {
+            self.emit(&TabEvent::Activated { tab_id });
+        }
+        self.changed();
+    }
+
+    /// Write a pending session snapshot
+    pub fn flush_session(&self) -> Result<()> {
+        let session = self.session.read().clone();
+        if let Some(session) = session {
+            session.flush(|| self.snapshot())?;
+        }
+        Ok(())
+    }
+
     // ========== Event Handling ==========
 
     /// Register an event handler
@@ -0,0 +1,167 @@
+//! Per-tab navigation stacks and scroll positions
+
+use serde::{Deserialize, Serialize};
+
+/// Scroll offset of a page, in CSS pixels
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct ScrollPosition {
+    /// Horizontal offset
+    pub x: f64,
+    /// Vertical offset
+    pub y: f64,
+}
+
+impl ScrollPosition {
+    /// Create a scroll position
+    pub fn new(x: f64, y: f64) -> Self {
+        Self { x, y }
+    }
+}
+
+/// A page in a tab's navigation stack
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NavigationEntry {
+    /// Page URL
+    pub url: String,
+    /// Page title
+    #[serde(default)]
+    pub title: String,
+    /// Scroll position when the page was left
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub scroll_position: Option<ScrollPosition>,
+}
+
+impl NavigationEntry {
+    /// Create an entry
+    pub fn new(url: impl Into<String>, title: impl Into<String>) -> Self {
+        Self {
+            url: url.into(),
+            title: title.into(),
+            scroll_position: None,
+        }
+    }
+}
+
+/// Back/forward navigation stack of a tab
+///
+/// `entries[index]` is the current page; entries before it form the back
+/// stack and entries after it the forward stack.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NavigationHistory {
+    /// Visited pages, oldest first
+    pub entries: Vec<NavigationEntry>,
+    /// Index of the current page
+    #[serde(default)]
+    pub index: usize,
+}
+
+impl NavigationHistory {
+    /// Create an empty history
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Navigate to a new page, dropping the forward stack
+    pub fn push(&mut self, entry: NavigationEntry) {
+        if !self.entries.is_empty() {
+            self.entries.truncate(self.index + 1);
+        }
+        self.entries.push(entry);
+        self.index = self.entries.len() - 1;
+    }
+
+    /// Go back one page
+    pub fn go_back(&mut self) -> Option<&NavigationEntry> {
+        if !self.can_go_back() {
+            return None;
+        }
+        self.index -= 1;
+        self.current()
+    }
+
+    /// Go forward one page
+    pub fn go_forward(&mut self) -> Option<&NavigationEntry> {
+        if !self.can_go_forward() {
+            return None;
+        }
+        self.index += 1;
+        self.current()
+    }
+
+    /// Get the current page
+    pub fn current(&self) -> Option<&NavigationEntry> {
+        self.entries.get(self.index)
+    }
+
+    /// Get mutable access to the current page
+    pub fn current_mut(&mut self) -> Option<&mut NavigationEntry> {
+        self.entries.get_mut(self.index)
+    }
+
+    /// Get the back stack, oldest first
+    pub fn back_entries(&self) -> &[NavigationEntry] {
+        &self.entries[..self.index.min(self.entries.len())]
+    }
+
+    /// Get the forward stack, nearest first
+    pub fn forward_entries(&self) -> &[NavigationEntry] {
+        self.entries.get(self.index + 1..).unwrap_or_default()
+    }
+
+    /// Check if back navigation is possible
+    pub fn can_go_back(&self) -> bool {
+        self.index > 0 && self.index < self.entries.len()
+    }
+
+    /// Check if forward navigation is possible
+    pub fn can_go_forward(&self) -> bool {
+        self.index + 1 < self.entries.len()
+    }
+
+    /// Check if the history is empty
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Get number of entries
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_push_and_navigate() {
+        let mut history = NavigationHistory::new();
+        history.push(NavigationEntry::new("https://a.com", "A"));
+        history.push(NavigationEntry::new("https://b.com", "B"));
+        history.push(NavigationEntry::new("https://c.com", "C"));
+
+        assert!(history.can_go_back());
+        assert!(!history.can_go_forward());
+        assert_eq!(history.go_back().unwrap().url, "https://b.com");
+        assert_eq!(history.back_entries().len(), 1);
+        assert_eq!(history.forward_entries()[0].url, "https://c.com");
+
+        // Navigating drops the forward stack
+        history.push(NavigationEntry::new("https://d.com", "D"));
+        assert_eq!(history.len(), 3);
+        assert!(!history.can_go_forward());
+        assert_eq!(history.current().unwrap().url, "https://d.com");
+    }
+
+    #[test]
+    fn test_empty_history() {
+        let mut history = NavigationHistory::new();
+        assert!(history.go_back().is_none());
+        assert!(history.go_forward().is_none());
+        assert!(history.current().is_none());
+        assert!(history.back_entries().is_empty());
+        assert!(history.forward_entries().is_empty());
+    }
+}
@@ -1,8 +1,26 @@
 //! Session management for tab persistence
+//!
+//! Besides saving and loading a [`Session`], the [`SessionManager`] supports
+//! crash recovery:
+//!
+//! - [`SessionManager::record`] writes rolling snapshots, at most once per
+//!   snapshot interval, as tabs change
+//! - [`SessionManager::start`] leaves a marker file that only a clean
+//!   [`SessionManager::shutdown`] removes, so the next start knows whether
+//!   the last run crashed
+//! - the last session of every run is archived, keeping the most recent ones
 
 use crate::{Result, TabError, TabGroup, TabId, TabState};
+use parking_lot::Mutex;
 use serde::{Deserialize, Serialize};
 use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+/// Default minimum time between two snapshots
+const DEFAULT_SNAPSHOT_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Default number of archived sessions
+const DEFAULT_HISTORY_LIMIT: usize = 5;
 
 /// Session data - serializable snapshot of tab state
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -69,21 +87,67 @@ impl Default for Session {
     }
 }
 
+/// An archived session
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SessionEntry {
+    /// Identifier for [`SessionManager::load_previous`]
+    pub id: String,
+    /// Archive file
+    pub path: PathBuf,
+    /// Time of the last snapshot (milliseconds since the Unix epoch)
+    pub timestamp: i64,
+    /// Number of tabs
+    pub tab_count: usize,
+    /// Whether the run that produced the session crashed
+    pub crashed: bool,
+}
+
+/// What [`SessionManager::start`] found about the previous run
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RecoveryInfo {
+    /// The previous run did not shut down cleanly
+    pub crashed: bool,
+    /// The last session of the previous run
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_session: Option<SessionEntry>,
+}
+
+impl RecoveryInfo {
+    /// Check if a "restore previous session" prompt should be shown
+    pub fn should_offer_restore(&self) -> bool {
+        self.crashed && self.last_session.as_ref().is_some_and(|s| s.tab_count > 0)
+    }
+}
+
+/// Snapshot throttling state
+#[derive(Debug, Default)]
+struct SnapshotState {
+    /// When the last snapshot was written
+    last_write: Option<Instant>,
+    /// Changes happened since the last snapshot
+    dirty: bool,
+}
+
 /// Session manager - handles session persistence
 pub struct SessionManager {
     /// Storage path
     storage_path: PathBuf,
     /// Auto-save enabled
     auto_save: bool,
+    /// Minimum time between snapshots
+    snapshot_interval: Duration,
+    /// Number of archived sessions to keep
+    history_limit: usize,
+    /// Snapshot throttling state
+    snapshots: Mutex<SnapshotState>,
 }
 
 impl SessionManager {
     /// Create a new session manager
     pub fn new(data_dir: &Path) -> Self {
-        Self {
-            storage_path: data_dir.join("session.json"),
-            auto_save: true,
-        }
+        Self::with_path(data_dir.join("session.json"))
     }
 
     /// Create session manager with custom path
@@ -91,9 +155,24 @@ impl SessionManager {
         Self {
             storage_path: path,
             auto_save: true,
+            snapshot_interval: DEFAULT_SNAPSHOT_INTERVAL,
+            history_limit: DEFAULT_HISTORY_LIMIT,
+            snapshots: Mutex::new(SnapshotState::default()),
         }
     }
 
+    /// Set the minimum time between snapshots
+    pub fn with_snapshot_interval(mut self, interval: Duration) -> Self {
+        self.snapshot_interval = interval;
+        self
+    }
+
+    /// Set how many previous sessions are kept
+    pub fn with_history_limit(mut self, limit: usize) -> Self {
+        self.history_limit = limit;
+        self
+    }
+
     /// Enable/disable auto-save
     pub fn set_auto_save(&mut self, enabled: bool) {
         self.auto_save = enabled;
@@ -105,6 +184,9 @@ impl SessionManager {
     }
 
     /// Save session
+    ///
+    /// The file is replaced atomically, so a crash while saving leaves the
+    /// previous snapshot intact.
     pub fn save(&self, session: &Session) -> Result<()> {
         // Ensure parent directory exists
         if let Some(parent) = self.storage_path.parent() {
@@ -112,7 +194,9 @@ impl SessionManager {
         }
 
         let json = serde_json::to_string_pretty(session)?;
-        std::fs::write(&self.storage_path, json)?;
+        let tmp = self.storage_path.with_extension("json.tmp");
+        std::fs::write(&tmp, json)?;
+        std::fs::rename(&tmp, &self.storage_path)?;
 
         Ok(())
     }
@@ -173,10 +257,207 @@ impl SessionManager {
 
         Ok(session)
     }
+
+    // ========== Crash Recovery ==========
+
+    /// Start a run
+    ///
+    /// Archives the last session of the previous run and writes the
+    /// dirty-shutdown marker. Call once at startup, before the first snapshot.
+    pub fn start(&self) -> Result<RecoveryInfo> {
+        let marker = self.marker_path();
+        let crashed = marker.exists();
+
+        // A corrupt snapshot must not prevent startup
+        let last_session = match self.load() {
+            Ok(session) if self.exists() => Some(self.archive(&session, crashed)?),
+            _ => None,
+        };
+        self.prune_history()?;
+
+        if let Some(parent) = marker.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(
+            &marker,
+            format!("{} {}", std::process::id(), current_timestamp()),
+        )?;
+        *self.snapshots.lock() = SnapshotState::default();
+
+        Ok(RecoveryInfo {
+            crashed,
+            last_session,
+        })
+    }
+
+    /// Record a clean shutdown by removing the dirty-shutdown marker
+    ///
+    /// Flush pending snapshots first (see [`SessionManager::flush`]).
+    pub fn shutdown(&self) -> Result<()> {
+        let marker = self.marker_path();
+        if marker.exists() {
+            std::fs::remove_file(marker)?;
+        }
+        Ok(())
+    }
+
+    /// Check if a run is in progress (or the last one crashed)
+    pub fn is_running(&self) -> bool {
+        self.marker_path().exists()
+    }
+
+    /// Record a change, writing a snapshot if the last one is old enough
+    ///
+    /// `snapshot` is only called when a snapshot is written. Otherwise the
+    /// session is marked dirty and written by the next due [`record`] or
+    /// [`flush`]. Returns whether a snapshot was written.
+    ///
+    /// [`record`]: SessionManager::record
+    /// [`flush`]: SessionManager::flush
+    pub fn record(&self, snapshot: impl FnOnce() -> Session) -> Result<bool> {
+        if !self.auto_save {
+            return Ok(false);
+        }
+
+        let mut state = self.snapshots.lock();
+        let due = state
+            .last_write
+            .is_none_or(|t| t.elapsed() >= self.snapshot_interval);
+        if !due {
+            state.dirty = true;
+            return Ok(false);
+        }
+        self.write_snapshot(&mut state, snapshot())
+    }
+
+    /// Write a snapshot if changes were recorded since the last one
+    ///
+    /// Call periodically (e.g. from an idle timer) and before shutdown.
+    pub fn flush(&self, snapshot: impl FnOnce() -> Session) -> Result<bool> {
+        let mut state = self.snapshots.lock();
+        if !state.dirty {
+            return Ok(false);
+        }
+        self.write_snapshot(&mut state, snapshot())
+    }
+
+    /// Check if changes are waiting for a snapshot
+    pub fn is_dirty(&self) -> bool {
+        self.snapshots.lock().dirty
+    }
+
+    fn write_snapshot(&self, state: &mut SnapshotState, session: Session) -> Result<bool> {
+        self.save(&session)?;
+        state.last_write = Some(Instant::now());
+        state.dirty = false;
+        Ok(true)
+    }
+
+    /// List archived sessions, newest first
+    pub fn previous_sessions(&self) -> Result<Vec<SessionEntry>> {
+        let dir = self.history_dir();
+        if !dir.exists() {
+            return Ok(Vec::new());
+        }
+
+        let mut entries = Vec::new();
+        for file in std::fs::read_dir(&dir)? {
+            let path = file?.path();
+            let Some(id) = history_id(&path) else {
+                continue;
+            };
+            // Skip unreadable archives
+            let Ok(session) = read_session(&path) else {
+                continue;
+            };
+            entries.push(SessionEntry {
+                crashed: id.ends_with(CRASHED_SUFFIX),
+                id,
+                path,
+                timestamp: session.timestamp,
+                tab_count: session.tab_count(),
+            });
+        }
+        entries.sort_by(|a, b| b.timestamp.cmp(&a.timestamp).then(b.id.cmp(&a.id)));
+        Ok(entries)
+    }
+
+    /// Load an archived session
+    pub fn load_previous(&self, id: &str) -> Result<Session> {
+        let path = self.history_dir().join(format!("{id}.json"));
+        if history_id(&path).as_deref() != Some(id) || !path.exists() {
+            return Err(TabError::Session(format!("No previous session: {id}")));
+        }
+        read_session(&path)
+    }
+
+    /// Get the directory of archived sessions
+    pub fn history_dir(&self) -> PathBuf {
+        let stem = self
+            .storage_path
+            .file_stem()
+            .and_then(|s| s.to_str())
+            .unwrap_or("session");
+        self.storage_path.with_file_name(format!("{stem}-history"))
+    }
+
+    fn marker_path(&self) -> PathBuf {
+        self.storage_path.with_extension("running")
+    }
+
+    /// Copy a session into the history directory
+    fn archive(&self, session: &Session, crashed: bool) -> Result<SessionEntry> {
+        let dir = self.history_dir();
+        std::fs::create_dir_all(&dir)?;
+
+        let suffix = if crashed { CRASHED_SUFFIX } else { "" };
+        let id = format!("{HISTORY_PREFIX}{}{suffix}", session.timestamp);
+        let path = dir.join(format!("{id}.json"));
+        // A run without changes leaves the same snapshot behind
+        if !path.exists() {
+            std::fs::copy(&self.storage_path, &path)?;
+        }
+
+        Ok(SessionEntry {
+            id,
+            path,
+            timestamp: session.timestamp,
+            tab_count: session.tab_count(),
+            crashed,
+        })
+    }
+
+    /// Delete archived sessions beyond the history limit
+    fn prune_history(&self) -> Result<()> {
+        for entry in self.previous_sessions()?.iter().skip(self.history_limit) {
+            std::fs::remove_file(&entry.path)?;
+        }
+        Ok(())
+    }
+}
+
+/// File name prefix of archived sessions
+const HISTORY_PREFIX: &str = "session-";
+
+/// File name suffix of sessions from crashed runs
+const CRASHED_SUFFIX: &str = "-crashed";
+
+/// Get the ID of an archive file, if the path is one
+fn history_id(path: &Path) -> Option<String> {
+    if path.extension()? != "json" {
+        return None;
+    }
+    let stem = path.file_stem()?.to_str()?;
+    stem.starts_with(HISTORY_PREFIX).then(|| stem.to_string())
+}
+
+fn read_session(path: &Path) -> Result<Session> {
+    let json = std::fs::read_to_string(path)?;
+    Ok(serde_json::from_str(&json)?)
 }
 
 /// Get current timestamp in milliseconds
-fn current_timestamp() -> i64 {
+pub(crate) fn current_timestamp() -> i64 {
     std::time::SystemTime::now()
         .duration_since(std::time::UNIX_EPOCH)
         .map(|d| d.as_millis() as i64)
@@ -244,4 +525,29 @@ mod tests {
         let restored = manager.restore_backup().unwrap();
         assert_eq!(restored.tab_count(), 1);
     }
+
+    #[test]
+    fn test_history_id() {
+        assert_eq!(
+            history_id(Path::new("/x/session-17-crashed.json")).as_deref(),
+            Some("session-17-crashed")
+        );
+        assert_eq!(history_id(Path::new("/x/session-17.json.tmp")), None);
+        assert_eq!(history_id(Path::new("/x/other.json")), None);
+    }
+
+    #[test]
+    fn test_snapshot_throttling() {
+        let dir = TempDir::new().unwrap();
+        let manager =
+            SessionManager::new(dir.path()).with_snapshot_interval(Duration::from_secs(60));
+
+        assert!(manager.record(Session::new).unwrap());
+        assert!(!manager.record(|| unreachable!()).unwrap());
+        assert!(manager.is_dirty());
+
+        assert!(manager.flush(Session::new).unwrap());
+        assert!(!manager.is_dirty());
+        assert!(!manager.flush(|| unreachable!()).unwrap());
+    }
 }
@@ -1,6 +1,7 @@
 //! Tab state data structures
 
-use crate::TabGroupId;
+use crate::session::current_timestamp;
+use crate::{NavigationHistory, ScrollPosition, TabGroupId, TabLifecycle};
 use serde::{Deserialize, Serialize};
 
 /// Unique identifier for a tab
@@ -43,6 +44,18 @@ pub struct TabState {
     /// Position in tab bar (for ordering)
     #[serde(default)]
     pub position: u32,
+    /// Scroll position of the current page
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub scroll_position: Option<ScrollPosition>,
+    /// Back/forward navigation stack
+    #[serde(default, skip_serializing_if = "NavigationHistory::is_empty")]
+    pub navigation: NavigationHistory,
+    /// Lifecycle state (active, background, frozen or discarded)
+    #[serde(default)]
+    pub lifecycle: TabLifecycle,
+    /// When the tab was last active (milliseconds since the Unix epoch)
+    #[serde(default)]
+    pub last_active_at: i64,
 }
 
 /// Security state of the current page
@@ -77,6 +90,10 @@ impl TabState {
             audible: false,
             group_id: None,
             position: 0,
+            scroll_position: None,
+            navigation: NavigationHistory::new(),
+            lifecycle: TabLifecycle::Background,
+            last_active_at: current_timestamp(),
         }
     }
 
@@ -159,6 +176,39 @@ impl TabState {
         self.position = position;
     }
 
+    /// Set scroll position (also stored on the current navigation entry)
+    pub fn set_scroll_position(&mut self, position: ScrollPosition) {
+        self.scroll_position = Some(position);
+        if let Some(entry) = self.navigation.current_mut() {
+            entry.scroll_position = Some(position);
+        }
+    }
+
+    /// Replace the navigation stack (also updates the history state)
+    pub fn set_navigation(&mut self, navigation: NavigationHistory) {
+        self.can_go_back = navigation.can_go_back();
+        self.can_go_forward = navigation.can_go_forward();
+        self.navigation = navigation;
+    }
+
+    /// Set the lifecycle state
+    ///
+    /// Leaving the active state records the time for idle tracking.
+    pub fn set_lifecycle(&mut self, lifecycle: TabLifecycle) {
+        if self.lifecycle == TabLifecycle::Active || lifecycle == TabLifecycle::Active {
+            self.last_active_at = current_timestamp();
+        }
+        if lifecycle == TabLifecycle::Discarded {
+            self.is_loading = false;
+        }
+        self.lifecycle = lifecycle;
+    }
+
+    /// Check if the tab holds a WebView
+    pub fn is_discarded(&self) -> bool {
+        self.lifecycle == TabLifecycle::Discarded
+    }
+
     /// Check if tab is secure
     pub fn is_secure(&self) -> bool {
         self.security_state == Some(SecurityState::Secure)
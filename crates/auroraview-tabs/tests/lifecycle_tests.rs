@@ -0,0 +1,274 @@
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use auroraview_tabs::{DiscardPolicy, TabError, TabEvent, TabLifecycle, TabManager};
+use rstest::*;
+
+const MINUTE: i64 = 60_000;
+
+fn recorder(manager: &TabManager) -> Arc<Mutex<Vec<TabEvent>>> {
+    let events = Arc::new(Mutex::new(Vec::new()));
+    let sink = events.clone();
+    manager.on_event(move |event| sink.lock().unwrap().push(event.clone()));
+    events
+}
+
+/// Pretend a tab was last active `minutes` ago
+fn idle_for(manager: &TabManager, tab_id: &String, minutes: i64) {
+    manager.update(tab_id, |tab| tab.last_active_at -= minutes * MINUTE);
+}
+
+fn lifecycle(manager: &TabManager, tab_id: &String) -> TabLifecycle {
+    manager.get(tab_id).unwrap().lifecycle
+}
+
+// ========== State Tests ==========
+
+#[test]
+fn test_first_tab_is_active_others_background() {
+    let manager = TabManager::new();
+    let a = manager.create("https://a.com");
+    let b = manager.create("https://b.com");
+
+    assert_eq!(lifecycle(&manager, &a), TabLifecycle::Active);
+    assert_eq!(lifecycle(&manager, &b), TabLifecycle::Background);
+
+    manager.activate(&b).unwrap();
+    assert_eq!(lifecycle(&manager, &a), TabLifecycle::Background);
+    assert_eq!(lifecycle(&manager, &b), TabLifecycle::Active);
+}
+
+#[test]
+fn test_closing_active_tab_activates_next() {
+    let manager = TabManager::new();
+    let a = manager.create("https://a.com");
+    let b = manager.create("https://b.com");
+    manager.discard(&b).unwrap();
+
+    let events = recorder(&manager);
+    manager.close(&a).unwrap();
+
+    assert_eq!(lifecycle(&manager, &b), TabLifecycle::Active);
+    assert!(events
+        .lock()
+        .unwrap()
+        .iter()
+        .any(|e| matches!(e, TabEvent::Reloaded { tab_id, .. } if *tab_id == b)));
+}
+
+#[test]
+fn test_lifecycle_serialization() {
+    assert_eq!(
+        serde_json::to_string(&TabLifecycle::Discarded).unwrap(),
+        "\"discarded\""
+    );
+    let json = r#"{"id":"t1","title":"T","url":"https://a.com","isLoading":false,"canGoBack":false,"canGoForward":false}"#;
+    let tab: auroraview_tabs::TabState = serde_json::from_str(json).unwrap();
+    assert_eq!(tab.lifecycle, TabLifecycle::Background);
+}
+
+// ========== Discard Tests ==========
+
+#[test]
+fn test_discard_keeps_title_favicon_and_url() {
+    let manager = TabManager::new();
+    manager.create("https://a.com");
+    let b = manager.create("https://b.com/page");
+    manager.update_title(&b, "Page");
+    manager.update_favicon(&b, "https://b.com/favicon.ico");
+
+    let events = recorder(&manager);
+    manager.discard(&b).unwrap();
+
+    let tab = manager.get(&b).unwrap();
+    assert!(tab.is_discarded());
+    assert_eq!(tab.title, "Page");
+    assert_eq!(tab.url, "https://b.com/page");
+    assert_eq!(tab.favicon.as_deref(), Some("https://b.com/favicon.ico"));
+    assert!(!tab.is_loading);
+    assert_eq!(manager.live_count(), 1);
+    assert!(matches!(
+        events.lock().unwrap().as_slice(),
+        [TabEvent::Discarded { tab_id }] if *tab_id == b
+    ));
+}
+
+#[test]
+fn test_activating_discarded_tab_reloads() {
+    let manager = TabManager::new();
+    manager.create("https://a.com");
+    let b = manager.create("https://b.com");
+    manager.discard(&b).unwrap();
+
+    let events = recorder(&manager);
+    manager.activate(&b).unwrap();
+
+    let tab = manager.get(&b).unwrap();
+    assert_eq!(tab.lifecycle, TabLifecycle::Active);
+    assert!(tab.is_loading);
+
+    let events = events.lock().unwrap();
+    let reloaded = events
+        .iter()
+        .position(|e| matches!(e, TabEvent::Reloaded { url, .. } if url == "https://b.com"))
+        .unwrap();
+    let activated = events
+        .iter()
+        .position(|e| matches!(e, TabEvent::Activated { .. }))
+        .unwrap();
+    assert!(reloaded < activated);
+}
+
+#[test]
+fn test_activating_frozen_tab_resumes() {
+    let manager = TabManager::new();
+    manager.create("https://a.com");
+    let b = manager.create("https://b.com");
+    manager.freeze(&b).unwrap();
+    assert_eq!(lifecycle(&manager, &b), TabLifecycle::Frozen);
+
+    let events = recorder(&manager);
+    manager.activate(&b).unwrap();
+    assert!(events
+        .lock()
+        .unwrap()
+        .iter()
+        .any(|e| matches!(e, TabEvent::Resumed { tab_id } if *tab_id == b)));
+}
+
+#[rstest]
+#[case::discard(true)]
+#[case::freeze(false)]
+fn test_cannot_suspend_active_tab(#[case] discard: bool) {
+    let manager = TabManager::new();
+    let a = manager.create("https://a.com");
+
+    let result = if discard {
+        manager.discard(&a)
+    } else {
+        manager.freeze(&a)
+    };
+    assert!(matches!(result, Err(TabError::InvalidOperation(_))));
+    assert!(matches!(
+        manager.discard(&"missing".to_string()),
+        Err(TabError::NotFound(_))
+    ));
+}
+
+// ========== Policy Tests ==========
+
+#[test]
+fn test_default_manager_never_discards() {
+    let manager = TabManager::new();
+    manager.create("https://a.com");
+    let b = manager.create("https://b.com");
+    idle_for(&manager, &b, 24 * 60);
+
+    assert!(manager.apply_discard_policy().is_empty());
+    assert_eq!(lifecycle(&manager, &b), TabLifecycle::Background);
+}
+
+#[test]
+fn test_policy_freezes_and_discards_idle_tabs() {
+    let manager = TabManager::new().with_discard_policy(
+        DiscardPolicy::default()
+            .with_freeze_after(Some(Duration::from_secs(60)))
+            .with_discard_after(Some(Duration::from_secs(600)))
+            .with_max_live_tabs(None),
+    );
+    manager.create("https://a.com");
+    let fresh = manager.create("https://b.com");
+    let idle = manager.create("https://c.com");
+    let stale = manager.create("https://d.com");
+    idle_for(&manager, &idle, 5);
+    idle_for(&manager, &stale, 20);
+
+    let plan = manager.apply_discard_policy();
+    assert_eq!(plan.len(), 2);
+    assert_eq!(lifecycle(&manager, &fresh), TabLifecycle::Background);
+    assert_eq!(lifecycle(&manager, &idle), TabLifecycle::Frozen);
+    assert_eq!(lifecycle(&manager, &stale), TabLifecycle::Discarded);
+
+    // Applying again changes nothing
+    assert!(manager.apply_discard_policy().is_empty());
+}
+
+#[test]
+fn test_max_live_tabs_discards_least_recently_active() {
+    let manager = TabManager::new()
+        .with_discard_policy(DiscardPolicy::disabled().with_max_live_tabs(Some(2)));
+    let a = manager.create("https://a.com");
+    let b = manager.create("https://b.com");
+    let c = manager.create("https://c.com");
+    idle_for(&manager, &b, 10);
+    idle_for(&manager, &c, 1);
+
+    assert_eq!(
+        manager.apply_discard_policy(),
+        vec![(b.clone(), TabLifecycle::Discarded)]
+    );
+    assert_eq!(manager.live_count(), 2);
+    assert_eq!(lifecycle(&manager, &a), TabLifecycle::Active);
+}
+
+#[rstest]
+#[case::pinned(true, false, true)]
+#[case::audible(false, true, true)]
+#[case::neither(false, false, false)]
+fn test_policy_exemptions(#[case] pinned: bool, #[case] audible: bool, #[case] exempt: bool) {
+    let manager = TabManager::new().with_discard_policy(
+        DiscardPolicy::disabled().with_discard_after(Some(Duration::from_secs(60))),
+    );
+    manager.create("https://a.com");
+    let b = manager.create("https://b.com");
+    manager.set_pinned(&b, pinned);
+    manager.update(&b, |tab| tab.set_audible(audible));
+    idle_for(&manager, &b, 10);
+
+    manager.apply_discard_policy();
+    assert_eq!(manager.get(&b).unwrap().is_discarded(), !exempt);
+}
+
+#[test]
+fn test_exemptions_can_be_disabled() {
+    let manager = TabManager::new().with_discard_policy(
+        DiscardPolicy::disabled()
+            .with_discard_after(Some(Duration::from_secs(60)))
+            .with_exempt_pinned(false),
+    );
+    manager.create("https://a.com");
+    let b = manager.create("https://b.com");
+    manager.set_pinned(&b, true);
+    idle_for(&manager, &b, 10);
+
+    manager.apply_discard_policy();
+    assert!(manager.get(&b).unwrap().is_discarded());
+}
+
+#[test]
+fn test_memory_pressure_discards_all_eligible_tabs() {
+    let manager = TabManager::new().with_discard_policy(DiscardPolicy::default());
+    let a = manager.create("https://a.com");
+    let b = manager.create("https://b.com");
+    let pinned = manager.create("https://c.com");
+    manager.set_pinned(&pinned, true);
+
+    assert_eq!(manager.handle_memory_pressure(), vec![b.clone()]);
+    assert_eq!(lifecycle(&manager, &a), TabLifecycle::Active);
+    assert_eq!(lifecycle(&manager, &pinned), TabLifecycle::Background);
+    assert!(manager.handle_memory_pressure().is_empty());
+}
+
+#[test]
+fn test_restored_background_tabs_are_discarded() {
+    let original = TabManager::new();
+    let a = original.create("https://a.com");
+    let b = original.create("https://b.com");
+
+    let restored = TabManager::new();
+    restored.restore(&original.snapshot());
+
+    assert_eq!(lifecycle(&restored, &a), TabLifecycle::Active);
+    assert_eq!(lifecycle(&restored, &b), TabLifecycle::Discarded);
+    assert_eq!(restored.live_count(), 1);
+}
@@ -0,0 +1,346 @@
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use auroraview_tabs::{
+    NavigationEntry, NavigationHistory, ScrollPosition, Session, SessionManager, TabEvent,
+    TabManager, TabState,
+};
+use rstest::*;
+use tempfile::TempDir;
+
+#[fixture]
+fn dir() -> TempDir {
+    TempDir::new().unwrap()
+}
+
+/// Session manager that snapshots on every change
+fn eager(dir: &TempDir) -> Arc<SessionManager> {
+    Arc::new(SessionManager::new(dir.path()).with_snapshot_interval(Duration::ZERO))
+}
+
+fn history(urls: &[&str], index: usize) -> NavigationHistory {
+    let mut history = NavigationHistory::new();
+    for url in urls {
+        history.push(NavigationEntry::new(*url, ""));
+    }
+    for _ in index + 1..urls.len() {
+        history.go_back();
+    }
+    history
+}
+
+// ========== Navigation Tests ==========
+
+#[test]
+fn test_navigation_push_truncates_forward() {
+    let mut nav = history(&["https://a.com", "https://b.com", "https://c.com"], 0);
+    assert!(nav.can_go_forward());
+    assert!(!nav.can_go_back());
+
+    nav.push(NavigationEntry::new("https://d.com", "D"));
+    assert_eq!(nav.len(), 2);
+    assert_eq!(nav.current().unwrap().url, "https://d.com");
+    assert!(!nav.can_go_forward());
+    assert_eq!(nav.back_entries().len(), 1);
+}
+
+#[test]
+fn test_scroll_position_stored_on_current_entry() {
+    let mut state = TabState::new("t1".to_string(), "https://a.com");
+    state.set_navigation(history(&["https://a.com", "https://b.com"], 1));
+    assert!(state.can_go_back);
+
+    state.set_scroll_position(ScrollPosition::new(0.0, 420.0));
+    assert_eq!(state.scroll_position, Some(ScrollPosition::new(0.0, 420.0)));
+    assert_eq!(
+        state.navigation.current().unwrap().scroll_position,
+        Some(ScrollPosition::new(0.0, 420.0))
+    );
+}
+
+// ========== Snapshot Tests ==========
+
+#[rstest]
+fn test_snapshot_written_on_change(dir: TempDir) {
+    let session = eager(&dir);
+    let tabs = TabManager::new().with_session(session.clone());
+
+    let id = tabs.create("https://github.com");
+    tabs.update_title(&id, "GitHub");
+
+    let saved = session.load().unwrap();
+    assert_eq!(saved.tab_count(), 1);
+    assert_eq!(saved.tabs[0].title, "GitHub");
+    assert_eq!(saved.active_tab_id, Some(id));
+}
+
+#[rstest]
+fn test_snapshots_throttled_until_flush(dir: TempDir) {
+    let session =
+        Arc::new(SessionManager::new(dir.path()).with_snapshot_interval(Duration::from_secs(60)));
+    let tabs = TabManager::new().with_session(session.clone());
+
+    tabs.create("https://a.com");
+    tabs.create("https://b.com");
+    assert_eq!(session.load().unwrap().tab_count(), 1);
+    assert!(session.is_dirty());
+
+    tabs.flush_session().unwrap();
+    assert_eq!(session.load().unwrap().tab_count(), 2);
+    assert!(!session.is_dirty());
+}
+
+#[rstest]
+fn test_no_snapshots_without_auto_save(dir: TempDir) {
+    let mut manager = SessionManager::new(dir.path()).with_snapshot_interval(Duration::ZERO);
+    manager.set_auto_save(false);
+    let session = Arc::new(manager);
+    let tabs = TabManager::new().with_session(session.clone());
+
+    tabs.create("https://a.com");
+    assert!(!session.exists());
+}
+
+#[rstest]
+fn test_snapshot_orders_groups_by_first_tab() {
+    let tabs = TabManager::new();
+    let a = tabs.create("https://a.com");
+    let b = tabs.create("https://b.com");
+    let late = tabs.create_group_with_tabs("Late", vec![b]);
+    let early = tabs.create_group_with_tabs("Early", vec![a]);
+
+    let snapshot = tabs.snapshot();
+    let ids: Vec<_> = snapshot.groups.iter().map(|g| g.id.clone()).collect();
+    assert_eq!(ids, vec![early, late]);
+}
+
+// ========== Restore Tests ==========
+
+#[test]
+fn test_restore_round_trip() {
+    let original = TabManager::new();
+    let a = original.create("https://a.com");
+    let b = original.create("https://b.com");
+    original.set_pinned(&a, true);
+    original.activate(&b).unwrap();
+    original.reorder(&b, 0);
+    original
+        .set_navigation(&b, history(&["https://x.com", "https://b.com"], 1))
+        .unwrap();
+    original.update_scroll(&b, 10.0, 250.0);
+
+    let group = original.create_group_with_tabs("Work", vec![a.clone()]);
+    original
+        .set_group_color(&group, Some("#ff0000".to_string()))
+        .unwrap();
+    original.set_group_collapsed(&group, true).unwrap();
+
+    let restored = TabManager::new();
+    restored.restore(&original.snapshot());
+
+    assert_eq!(restored.order(), vec![b.clone(), a.clone()]);
+    assert_eq!(restored.active_id(), Some(b.clone()));
+    assert!(restored.get(&a).unwrap().pinned);
+
+    let tab = restored.get(&b).unwrap();
+    assert_eq!(tab.scroll_position, Some(ScrollPosition::new(10.0, 250.0)));
+    assert_eq!(tab.navigation.len(), 2);
+    assert!(tab.can_go_back);
+    assert_eq!(
+        tab.navigation.current().unwrap().scroll_position,
+        Some(ScrollPosition::new(10.0, 250.0))
+    );
+
+    let group = restored.get_group(&group).unwrap();
+    assert_eq!(group.color.as_deref(), Some("#ff0000"));
+    assert!(group.collapsed);
+    assert_eq!(group.tab_ids, vec![a.clone()]);
+    assert_eq!(restored.get(&a).unwrap().group_id, Some(group.id));
+}
+
+#[test]
+fn test_restore_survives_serialization() {
+    let original = TabManager::new();
+    let id = original.create("https://a.com");
+    original
+        .set_navigation(&id, history(&["https://a.com", "https://b.com"], 0))
+        .unwrap();
+
+    let json = serde_json::to_string(&original.snapshot()).unwrap();
+    let session: Session = serde_json::from_str(&json).unwrap();
+
+    let restored = TabManager::new();
+    restored.restore(&session);
+    let tab = restored.get(&id).unwrap();
+    assert!(tab.can_go_forward);
+    assert_eq!(tab.navigation, original.get(&id).unwrap().navigation);
+}
+
+#[test]
+fn test_restore_does_not_reuse_tab_ids() {
+    let original = TabManager::new();
+    original.create("https://a.com");
+    original.create("https://b.com");
+
+    let restored = TabManager::new();
+    restored.restore(&original.snapshot());
+    let id = restored.create("https://c.com");
+
+    assert_eq!(id, "tab_2");
+    assert_eq!(restored.count(), 3);
+}
+
+#[test]
+fn test_restore_emits_events() {
+    let original = TabManager::new();
+    original.create("https://a.com");
+    let b = original.create("https://b.com");
+    original.activate(&b).unwrap();
+
+    let restored = TabManager::new();
+    let events = Arc::new(Mutex::new(Vec::new()));
+    let sink = events.clone();
+    restored.on_event(move |event| sink.lock().unwrap().push(event.clone()));
+    restored.restore(&original.snapshot());
+
+    let events = events.lock().unwrap();
+    let created = events
+        .iter()
+        .filter(|e| matches!(e, TabEvent::Created { .. }))
+        .count();
+    assert_eq!(created, 2);
+    assert!(matches!(events.last(), Some(TabEvent::Activated { tab_id }) if *tab_id == b));
+}
+
+#[test]
+fn test_restore_drops_unknown_active_tab() {
+    let tabs = vec![TabState::new("t1".to_string(), "https://a.com")];
+    let session = Session::from_state(tabs, Some("gone".to_string()), vec![]);
+
+    let manager = TabManager::new();
+    manager.restore(&session);
+    assert_eq!(manager.active_id(), Some("t1".to_string()));
+}
+
+// ========== Crash Recovery Tests ==========
+
+#[rstest]
+fn test_first_start_has_nothing_to_restore(dir: TempDir) {
+    let session = SessionManager::new(dir.path());
+    let info = session.start().unwrap();
+
+    assert!(!info.crashed);
+    assert!(info.last_session.is_none());
+    assert!(!info.should_offer_restore());
+    assert!(session.is_running());
+}
+
+#[rstest]
+fn test_clean_shutdown_is_not_a_crash(dir: TempDir) {
+    let session = eager(&dir);
+    session.start().unwrap();
+    let tabs = TabManager::new().with_session(session.clone());
+    tabs.create("https://a.com");
+    tabs.flush_session().unwrap();
+    session.shutdown().unwrap();
+    assert!(!session.is_running());
+
+    let info = SessionManager::new(dir.path()).start().unwrap();
+    assert!(!info.crashed);
+    assert!(!info.should_offer_restore());
+    assert_eq!(info.last_session.unwrap().tab_count, 1);
+}
+
+#[rstest]
+fn test_crash_offers_restore(dir: TempDir) {
+    {
+        let session = eager(&dir);
+        session.start().unwrap();
+        let tabs = TabManager::new().with_session(session.clone());
+        let id = tabs.create("https://a.com");
+        tabs.update_scroll(&id, 0.0, 99.0);
+        // Dropped without shutdown
+    }
+
+    let session = SessionManager::new(dir.path());
+    let info = session.start().unwrap();
+    assert!(info.crashed);
+    assert!(info.should_offer_restore());
+
+    let entry = info.last_session.unwrap();
+    assert!(entry.crashed);
+    assert!(entry.id.ends_with("-crashed"));
+
+    let previous = session.load_previous(&entry.id).unwrap();
+    let tabs = TabManager::new();
+    tabs.restore(&previous);
+    assert_eq!(
+        tabs.active().unwrap().scroll_position,
+        Some(ScrollPosition::new(0.0, 99.0))
+    );
+}
+
+#[rstest]
+fn test_crash_with_no_tabs_does_not_offer_restore(dir: TempDir) {
+    let session = SessionManager::new(dir.path());
+    session.start().unwrap();
+    session.save(&Session::new()).unwrap();
+
+    let info = SessionManager::new(dir.path()).start().unwrap();
+    assert!(info.crashed);
+    assert!(!info.should_offer_restore());
+}
+
+#[rstest]
+fn test_corrupt_snapshot_does_not_block_start(dir: TempDir) {
+    let session = SessionManager::new(dir.path());
+    std::fs::write(session.path(), "{ not json").unwrap();
+
+    let info = session.start().unwrap();
+    assert!(info.last_session.is_none());
+}
+
+#[rstest]
+#[case(3)]
+#[case(1)]
+fn test_session_history_is_pruned(dir: TempDir, #[case] limit: usize) {
+    let session = SessionManager::new(dir.path()).with_history_limit(limit);
+    for i in 0..5 {
+        let mut snapshot = Session::new();
+        snapshot.timestamp = 1_000 + i;
+        snapshot
+            .tabs
+            .push(TabState::new(format!("tab_{i}"), "https://a.com"));
+        session.save(&snapshot).unwrap();
+        session.start().unwrap();
+        session.shutdown().unwrap();
+    }
+
+    let previous = session.previous_sessions().unwrap();
+    assert_eq!(previous.len(), limit);
+    // Newest first
+    assert_eq!(previous[0].timestamp, 1_004);
+    assert!(previous.windows(2).all(|w| w[0].timestamp > w[1].timestamp));
+}
+
+#[rstest]
+fn test_unchanged_session_archived_once(dir: TempDir) {
+    let session = SessionManager::new(dir.path());
+    session.save(&Session::new()).unwrap();
+    for _ in 0..3 {
+        session.start().unwrap();
+        session.shutdown().unwrap();
+    }
+    assert_eq!(session.previous_sessions().unwrap().len(), 1);
+}
+
+#[rstest]
+#[case("missing")]
+#[case("../session")]
+fn test_load_previous_rejects_unknown_ids(dir: TempDir, #[case] id: &str) {
+    let session = SessionManager::new(dir.path());
+    session.save(&Session::new()).unwrap();
+    session.start().unwrap();
+
+    assert!(session.load_previous(id).is_err());
+}
@@ -4,6 +4,11 @@
  * Provides reactive shared state between Python and JavaScript.
  * Inspired by PyWebView's state mechanism.
  *
+ * When the window is attached to a Rust-side StateStore, changes are sent as
+ * JSON patches (`__state_patch__`) and patches from other windows arrive as
+ * `{type: 'patch'}` sync messages. Otherwise per-key `__state_update__`
+ * events are used.
+ *
  * @module state_bridge
  */
 
@@ -12,10 +17,19 @@
 
   type StateChangeHandler = (key: string, value: unknown, source: 'python' | 'javascript') => void;
 
+  interface PatchOp {
+    op: 'add' | 'remove' | 'replace';
+    path: string;
+    value?: unknown;
+  }
+
   // Internal state storage
   const _stateData: Record<string, unknown> = {};
   const _changeHandlers: StateChangeHandler[] = [];
 
+  // Version of the Rust-side store, null until a versioned sync arrives
+  let _version: number | null = null;
+
   /**
    * Notify all change handlers
    */
@@ -29,15 +43,81 @@
     });
   }
 
+  function escapeToken(token: string): string {
+    return token.replace(/~/g, '~0').replace(/\//g, '~1');
+  }
+
+  function parsePointer(path: string): string[] {
+    return path
+      .split('/')
+      .slice(1)
+      .map((token) => token.replace(/~1/g, '/').replace(/~0/g, '~'));
+  }
+
   /**
-   * Send state update to Python
+   * Send state update to Python (or the Rust store as a JSON patch)
    */
   function sendToPython(key: string, value: unknown): void {
-    if (window.auroraview && window.auroraview.send_event) {
+    if (!window.auroraview || !window.auroraview.send_event) return;
+    if (_version !== null) {
+      const path = '/' + escapeToken(key);
+      const op: PatchOp =
+        value === undefined ? { op: 'remove', path: path } : { op: 'add', path: path, value: value };
+      window.auroraview.send_event('__state_patch__', { patch: [op] });
+    } else {
       window.auroraview.send_event('__state_update__', { key: key, value: value });
     }
   }
 
+  /**
+   * Apply one patch operation from the store (add/remove/replace only;
+   * the store normalizes broadcasts to these). Returns the top-level key.
+   */
+  function applyOp(op: PatchOp): string | undefined {
+    const tokens = parsePointer(op.path);
+    const last = tokens.pop();
+    if (last === undefined) return undefined;
+    let parent: unknown = _stateData;
+    for (const token of tokens) {
+      parent = (parent as Record<string, unknown>)[token];
+      if (parent === null || typeof parent !== 'object') {
+        throw new Error('path not found: ' + op.path);
+      }
+    }
+    if (Array.isArray(parent)) {
+      const index = last === '-' ? parent.length : Number(last);
+      if (op.op === 'add') parent.splice(index, 0, op.value);
+      else if (op.op === 'remove') parent.splice(index, 1);
+      else parent[index] = op.value;
+    } else {
+      const container = parent as Record<string, unknown>;
+      if (op.op === 'remove') delete container[last];
+      else container[last] = op.value;
+    }
+    return tokens.length > 0 ? tokens[0] : last;
+  }
+
+  /**
+   * Apply a patch from the store and notify handlers once per changed key
+   */
+  function applyPatch(patch: PatchOp[]): void {
+    const changed: string[] = [];
+    for (const op of patch) {
+      const key = applyOp(op);
+      if (key !== undefined && changed.indexOf(key) === -1) changed.push(key);
+    }
+    changed.forEach((key) => notifyHandlers(key, _stateData[key], 'python'));
+  }
+
+  /**
+   * Ask the Rust-side store (if any) for the full state
+   */
+  function requestResync(): void {
+    if (window.auroraview && window.auroraview.send_event) {
+      window.auroraview.send_event('__state_patch__', { patch: [], resync: true });
+    }
+  }
+
   /**
    * Create a reactive proxy for state object
    */
@@ -103,14 +183,28 @@
 
   // Handle sync messages from Python
   function handleStateSync(data: {
-    type: 'set' | 'delete' | 'batch' | 'full' | 'clear';
+    type: 'set' | 'delete' | 'batch' | 'full' | 'clear' | 'patch';
     key?: string;
     value?: unknown;
     data?: Record<string, unknown>;
+    patch?: PatchOp[];
+    version?: number;
   }): void {
     if (!data || typeof data !== 'object') return;
 
     switch (data.type) {
+      case 'patch':
+        if (Array.isArray(data.patch)) {
+          try {
+            applyPatch(data.patch);
+            if (typeof data.version === 'number') _version = data.version;
+          } catch (e) {
+            console.warn('[AuroraView State] Patch failed, resyncing:', e);
+            requestResync();
+          }
+        }
+        break;
+
       case 'set':
         if (data.key) {
           _stateData[data.key] = data.value;
@@ -135,6 +229,7 @@
         break;
 
       case 'full':
+        if (typeof data.version === 'number') _version = data.version;
         // Clear and replace all state
         Object.keys(_stateData).forEach((key) => delete _stateData[key]);
         if (data.data && typeof data.data === 'object') {
@@ -159,6 +254,8 @@
   if (window.auroraview) {
     (window.auroraview as Record<string, unknown>).state = stateProxy;
     window.auroraview.on('__state_sync__', handleStateSync);
+    // Pick up the current state if a Rust-side store is attached
+    requestResync();
   } else {
     // Wait for auroraview to be available
     Object.defineProperty(window, 'auroraview', {
@@ -168,6 +265,7 @@
         window.auroraview = val;
         (window.auroraview as Record<string, unknown>).state = stateProxy;
         window.auroraview!.on('__state_sync__', handleStateSync);
+        requestResync();
       },
     });
   }
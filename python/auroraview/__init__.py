@@ -144,6 +144,10 @@ try:
         get_webview_data_dir,
         # Plugin system for native desktop operations
         PluginManager,
+        # Shared state store synchronized across WebViews
+        StateStore,
+        # Flow-controlled streaming channel (returned by WebView.open_channel)
+        FlowChannel,
         # Thread-safe event emitter for cross-thread operations
         EventEmitter,
         # High-performance JSON functions (orjson-equivalent, no Python deps)
@@ -194,6 +198,12 @@ except ImportError as e:
     # Placeholder for plugin system
     PluginManager = None  # type: ignore
 
+    # Placeholder for shared state store
+    StateStore = None  # type: ignore
+
+    # Placeholder for flow-controlled channels
+    FlowChannel = None  # type: ignore
+
     # Placeholder for JSON functions
     json_loads = None  # type: ignore
     json_dumps = None  # type: ignore
@@ -656,6 +666,14 @@ __all__ = [
     # ============================================================
     "PluginManager",
     # ============================================================
+    # Shared state store
+    # ============================================================
+    "StateStore",
+    # ============================================================
+    # Flow-controlled streaming channels
+    # ============================================================
+    "FlowChannel",
+    # ============================================================
     # Runtime classes (optional - require feature flags)
     # ============================================================
     # Desktop runtime
@@ -5,7 +5,8 @@ WebView backends, inspired by Qt WebView's factory pattern.
 
 Environment Variables:
     AURORAVIEW_BACKEND: Override the default backend selection.
-        Valid values: "wry", "webview2" (Windows), "wkwebview" (macOS)
+        Valid values: "wry", "webview2" (Windows), "wkwebview" (macOS),
+        "webkitgtk" (Linux), "headless" (all platforms, no window)
 
 Example:
     >>> from auroraview.backend import BackendType, get_backend_type
@@ -29,12 +30,14 @@ class BackendType(Enum):
         WEBVIEW2: Windows native WebView2 backend
         WKWEBVIEW: macOS native WKWebView backend
         WEBKITGTK: Linux native WebKitGTK backend
+        HEADLESS: In-memory backend without a window (testing/CI)
     """
 
     WRY = "wry"
     WEBVIEW2 = "webview2"
     WKWEBVIEW = "wkwebview"
     WEBKITGTK = "webkitgtk"
+    HEADLESS = "headless"
 
     @classmethod
     def from_string(cls, value: str) -> "BackendType":
@@ -60,6 +63,9 @@ class BackendType(Enum):
             "webkit": cls.WKWEBVIEW,
             "webkitgtk": cls.WEBKITGTK,
             "gtk": cls.WEBKITGTK,
+            "headless": cls.HEADLESS,
+            "null": cls.HEADLESS,
+            "offscreen": cls.HEADLESS,
         }
         if value_lower not in mapping:
             valid = ", ".join(sorted(set(mapping.keys())))
@@ -97,6 +103,8 @@ def get_available_backends() -> List[BackendType]:
     elif sys.platform.startswith("linux"):
         backends.append(BackendType.WEBKITGTK)
 
+    backends.append(BackendType.HEADLESS)
+
     return backends
 
 
@@ -20,13 +20,30 @@ Example:
     >>> # const channel = await auroraview.invoke("stream_file", {path: "/data.bin"});
     >>> # channel.onMessage((chunk) => console.log("Received chunk:", chunk.length));
     >>> # channel.onClose(() => console.log("Stream complete"));
+
+Flow control:
+    Passing ``credits`` opens the channel through the Rust core with
+    credit-based backpressure. JavaScript acknowledges each message its
+    handlers consumed; once ``credits`` messages are unacknowledged, new
+    messages wait in a buffer of ``capacity`` entries and ``policy`` decides
+    what happens when it is full ("block", "drop_oldest" or "coalesce").
+    JavaScript can cancel the stream with ``channel.cancel()``, which makes
+    ``send()`` return False and runs ``on_cancel`` handlers:
+
+    >>> channel = webview.create_channel("render_log", credits=16, policy="drop_oldest")
+    >>> channel.on_cancel(lambda reason: renderer.abort())
+    >>> for line in renderer.log_lines():
+    ...     if not channel.send(line):
+    ...         break
+    >>> channel.close()
 """
 
 from __future__ import annotations
 
+import asyncio
 import logging
 import uuid
-from typing import TYPE_CHECKING, Callable, Dict, Generic, List, Optional, TypeVar
+from typing import TYPE_CHECKING, Any, Callable, Dict, Generic, List, Optional, TypeVar
 
 if TYPE_CHECKING:
     from .webview import WebView
@@ -48,34 +65,72 @@ class Channel(Generic[T]):
         _webview: Associated WebView instance
         _closed: Whether the channel is closed
         _on_close_handlers: Handlers called when channel closes
+        _flow: Flow-controlled core channel (when ``credits`` is set)
     """
 
-    def __init__(self, webview: Optional[WebView] = None, channel_id: Optional[str] = None):
+    def __init__(
+        self,
+        webview: Optional[WebView] = None,
+        channel_id: Optional[str] = None,
+        *,
+        credits: Optional[int] = None,
+        capacity: int = 256,
+        policy: str = "block",
+        timeout: Optional[float] = None,
+    ):
         """Initialize a Channel.
 
         Args:
             webview: Associated WebView instance
             channel_id: Optional custom channel ID
+            credits: Messages JavaScript may have unacknowledged; enables
+                flow control when set
+            capacity: Messages buffered while out of credits
+            policy: Full-buffer behavior: "block", "drop_oldest" or "coalesce"
+            timeout: Seconds a blocking send may wait before TimeoutError
         """
         self.id: str = channel_id or f"channel_{uuid.uuid4().hex[:8]}"
         self._webview: Optional[WebView] = webview
         self._closed: bool = False
         self._on_close_handlers: List[Callable[[], None]] = []
+        self._on_cancel_handlers: List[Callable[[Optional[str]], None]] = []
         self._buffer: List[T] = []
+        self._flow_options: Optional[Dict[str, Any]] = None
+        self._flow: Any = None
+
+        if credits is not None:
+            self._flow_options = {
+                "credits": credits,
+                "capacity": capacity,
+                "policy": policy,
+                "timeout": timeout,
+            }
+            if webview is not None:
+                self._open_flow(webview)
 
     def send(self, data: T) -> bool:
         """Send data through the channel.
 
+        On a flow-controlled channel with the "block" policy this waits for
+        JavaScript to acknowledge messages, so call it from a worker thread
+        or use ``send_async``.
+
         Args:
             data: Data to send (will be JSON serialized)
 
         Returns:
-            True if sent successfully, False if channel is closed
+            True if sent successfully, False if channel is closed or cancelled
+
+        Raises:
+            TimeoutError: If a blocking send exceeded ``timeout``
         """
         if self._closed:
             logger.warning(f"Cannot send on closed channel: {self.id}")
             return False
 
+        if self._flow is not None:
+            return self._flow.send(data)
+
         if self._webview:
             self._webview.emit("__channel_message__", {"channel_id": self.id, "data": data})
         else:
@@ -93,6 +148,10 @@ class Channel(Generic[T]):
         Returns:
             True if sent successfully
         """
+        if self._flow is not None:
+            # Blocking sends wait for JavaScript; keep the event loop free
+            loop = asyncio.get_running_loop()
+            return await loop.run_in_executor(None, self.send, data)
         return self.send(data)
 
     def close(self) -> None:
@@ -105,16 +164,37 @@ class Channel(Generic[T]):
 
         self._closed = True
 
-        if self._webview:
+        if self._flow is not None:
+            # Sent by the core once buffered messages were delivered
+            self._flow.close()
+        elif self._webview:
             self._webview.emit("__channel_close__", {"channel_id": self.id})
 
-        # Call close handlers
+        self._run_close_handlers()
+
+    def _run_close_handlers(self) -> None:
         for handler in self._on_close_handlers:
             try:
                 handler()
             except Exception as e:
                 logger.error(f"Channel close handler error: {e}")
 
+    def wait_closed(self, timeout: Optional[float] = None) -> bool:
+        """Wait until JavaScript received all messages and the close event.
+
+        Only meaningful for flow-controlled channels; others return
+        immediately.
+
+        Args:
+            timeout: Maximum seconds to wait
+
+        Returns:
+            False if the timeout elapsed first
+        """
+        if self._flow is None:
+            return True
+        return self._flow.wait_closed(timeout)
+
     def on_close(self, handler: Callable[[], None]) -> Callable[[], None]:
         """Register a close handler.
 
@@ -127,11 +207,80 @@ class Channel(Generic[T]):
         self._on_close_handlers.append(handler)
         return handler
 
+    def on_cancel(
+        self, handler: Callable[[Optional[str]], None]
+    ) -> Callable[[Optional[str]], None]:
+        """Register a handler called when JavaScript cancels the channel.
+
+        The handler receives the reason given by JavaScript (may be None)
+        and runs on the thread handling IPC. Only flow-controlled channels
+        can be cancelled.
+
+        Args:
+            handler: Function to call with the cancel reason
+
+        Returns:
+            The handler function
+        """
+        self._on_cancel_handlers.append(handler)
+        return handler
+
     @property
     def is_closed(self) -> bool:
         """Check if channel is closed."""
         return self._closed
 
+    @property
+    def is_flow_controlled(self) -> bool:
+        """Check if the channel uses credit-based flow control."""
+        return self._flow_options is not None
+
+    @property
+    def is_cancelled(self) -> bool:
+        """Check if JavaScript cancelled the channel."""
+        return self._flow is not None and self._flow.is_cancelled
+
+    @property
+    def cancel_reason(self) -> Optional[str]:
+        """Reason given by JavaScript when cancelling."""
+        return self._flow.cancel_reason if self._flow is not None else None
+
+    def stats(self) -> Dict[str, int]:
+        """Flow-control counters (empty for channels without flow control).
+
+        Returns:
+            Dict with sent, delivered, dropped, coalesced, buffered and credits
+        """
+        return dict(self._flow.stats()) if self._flow is not None else {}
+
+    def _open_flow(self, webview: WebView) -> None:
+        """Open the flow-controlled core channel (emits the open event)."""
+        core = getattr(webview, "_core", None)
+        if core is None or not hasattr(core, "open_channel"):
+            raise RuntimeError("Flow-controlled channels require the native WebView core")
+        options = self._flow_options or {}
+        self._flow = core.open_channel(
+            self.id,
+            options["credits"],
+            options["capacity"],
+            options["policy"],
+            options["timeout"],
+        )
+        self._flow.on_cancel(self._handle_cancel)
+
+    def _handle_cancel(self, reason: Optional[str]) -> None:
+        """Called by the core when JavaScript cancels the channel."""
+        if self._closed:
+            return
+        self._closed = True
+        logger.debug(f"Channel {self.id} cancelled by JavaScript: {reason}")
+        for handler in self._on_cancel_handlers:
+            try:
+                handler(reason)
+            except Exception as e:
+                logger.error(f"Channel cancel handler error: {e}")
+        self._run_close_handlers()
+
     def _attach_webview(self, webview: WebView) -> None:
         """Attach a WebView and flush buffered data.
 
@@ -140,8 +289,11 @@ class Channel(Generic[T]):
         """
         self._webview = webview
 
-        # Notify JS about new channel
-        webview.emit("__channel_open__", {"channel_id": self.id})
+        if self._flow_options is not None:
+            self._open_flow(webview)
+        else:
+            # Notify JS about new channel
+            webview.emit("__channel_open__", {"channel_id": self.id})
 
         # Flush buffer
         for data in self._buffer:
@@ -159,6 +311,8 @@ class Channel(Generic[T]):
     def __repr__(self) -> str:
         """String representation."""
         status = "closed" if self._closed else "open"
+        if self.is_cancelled:
+            status = "cancelled"
         return f"Channel({self.id}, {status})"
 
 
@@ -193,16 +347,18 @@ class ChannelManager:
         for channel in self._channels.values():
             channel._attach_webview(webview)
 
-    def create(self, channel_id: Optional[str] = None) -> Channel:
+    def create(self, channel_id: Optional[str] = None, **flow_options: Any) -> Channel:
         """Create a new channel.
 
         Args:
             channel_id: Optional custom channel ID
+            **flow_options: ``credits``, ``capacity``, ``policy`` and
+                ``timeout`` for a flow-controlled channel (see ``Channel``)
 
         Returns:
             New Channel instance
         """
-        channel = Channel(self._webview, channel_id)
+        channel = Channel(self._webview, channel_id, **flow_options)
         self._channels[channel.id] = channel
 
         # Auto-remove on close
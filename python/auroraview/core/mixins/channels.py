@@ -4,7 +4,7 @@
 
 from __future__ import annotations
 
-from typing import TYPE_CHECKING, Optional
+from typing import TYPE_CHECKING, Any, Optional
 
 if TYPE_CHECKING:
     from ..channel import Channel, ChannelManager
@@ -35,13 +35,15 @@ class WebViewChannelsMixin:
             self._channels = ChannelManager(self)
         return self._channels
 
-    def create_channel(self, channel_id: Optional[str] = None) -> "Channel":
+    def create_channel(self, channel_id: Optional[str] = None, **flow_options: Any) -> "Channel":
         """Create a new streaming channel.
 
         This is a convenience shortcut for `webview.channels.create()`.
 
         Args:
             channel_id: Optional custom channel ID
+            **flow_options: ``credits``, ``capacity``, ``policy`` and
+                ``timeout`` to enable backpressure (see ``Channel``)
 
         Returns:
             New Channel instance
@@ -50,5 +52,8 @@ class WebViewChannelsMixin:
             >>> with webview.create_channel() as channel:
             ...     for chunk in read_large_file():
             ...         channel.send(chunk)
+
+            >>> # Throttle the producer to what JavaScript consumes
+            >>> channel = webview.create_channel(credits=8, policy="block")
         """
-        return self.channels.create(channel_id)
+        return self.channels.create(channel_id, **flow_options)
@@ -645,6 +645,9 @@ class WebView(
                     "message": result.get("error", "Unknown error"),
                     "code": result.get("code", "PLUGIN_ERROR"),
                 }
+                # Structured details (e.g. schema violations) become error.data in JS
+                if result.get("details") is not None:
+                    payload["error"]["data"] = result["details"]
 
             # Emit result via __invoke_result__ event
             self.emit("__invoke_result__", payload)
@@ -871,6 +874,53 @@ class WebView(
         """
         return self._core.create_emitter()
 
+    def create_blob(
+        self,
+        data: bytes,
+        mime_type: str = "application/octet-stream",
+        lifetime: str = "manual",
+    ) -> Dict[str, Any]:
+        """Store binary data for JavaScript without JSON/base64 encoding.
+
+        JavaScript fetches the bytes as an ``ArrayBuffer`` with
+        ``auroraview.blob.fetch(handle)``. Blobs are freed when released,
+        when their lifetime ends, or when the WebView is destroyed.
+
+        Args:
+            data: Payload bytes.
+            mime_type: MIME type served to JavaScript.
+            lifetime: ``"manual"`` (until released), ``"one-shot"`` (freed after
+                the first fetch) or ``"ttl:<seconds>"``.
+
+        Returns:
+            Handle dict with ``id``, ``size``, ``mime`` and ``url``.
+
+        Example:
+            >>> handle = webview.create_blob(preview_bytes, "image/png", "one-shot")
+            >>> webview.emit("preview_ready", handle)
+        """
+        return self._core.create_blob(data, mime_type, lifetime)
+
+    def read_blob(self, blob_id: str, release: bool = False) -> Optional[bytes]:
+        """Read a blob, e.g. one uploaded with ``auroraview.blob.upload()``.
+
+        Args:
+            blob_id: Blob ID from the handle.
+            release: Free the blob after reading.
+
+        Returns:
+            The blob bytes, or None if the blob does not exist.
+        """
+        return self._core.read_blob(blob_id, release)
+
+    def release_blob(self, blob_id: str) -> bool:
+        """Free a blob.
+
+        Returns:
+            True if the blob existed.
+        """
+        return self._core.release_blob(blob_id)
+
     def thread_safe(self) -> Any:
         """Get a thread-safe wrapper for cross-thread operations.
 
@@ -211,7 +211,12 @@ fn run_desktop(
         ipc_batch_size: 100,
         ipc_batch_interval_ms: 16,
         asset_root: effective_asset_root,
+        asset_cache_policy: Default::default(),
+        serve_precompressed: false,
+        blob_store: Default::default(),
+        channels: Default::default(),
         data_directory: None, // Use system default
+        cookie_jar: None,
         custom_protocols: std::collections::HashMap::new(),
         api_methods: std::collections::HashMap::new(),
         allow_new_window,
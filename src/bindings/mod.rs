@@ -16,6 +16,7 @@
 //! - `runtime_desktop` - Desktop runtime bindings (multi-window, IPC router)
 //! - `runtime_dcc` - DCC runtime bindings (Maya, Houdini, Nuke integration)
 //! - `cleanup` - WebView2 user data directory cleanup
+//! - `state_store` - Shared state store synchronized across WebViews
 
 pub mod assets;
 pub mod cleanup;
@@ -24,6 +25,7 @@ pub mod desktop_runner;
 pub mod ipc;
 pub mod ipc_metrics;
 pub mod service_discovery;
+pub mod state_store;
 pub mod tab_browser;
 pub mod timer;
 pub mod warmup;
@@ -0,0 +1,294 @@
+//! Python bindings for the shared state store
+//!
+//! Exposes `auroraview_core::state::StateStore` as `StateStore`. Attached
+//! WebViews receive the full state on attach and JSON patches afterwards
+//! through `__state_sync__`; their changes arrive as `__state_patch__`.
+
+use std::sync::Arc;
+
+use auroraview_core::state::{PatchOp, StateMessage, StateStore, RUST_ORIGIN};
+use pyo3::exceptions::{PyIOError, PyValueError};
+use pyo3::prelude::*;
+
+use crate::ipc::json::{json_to_python, python_to_json};
+use crate::ipc::{IpcMessage, MessageQueue, WebViewMessage};
+use crate::webview::AuroraView;
+
+/// Event carrying patches from JavaScript
+const STATE_PATCH_EVENT: &str = "__state_patch__";
+
+/// Event carrying sync messages to JavaScript
+const STATE_SYNC_EVENT: &str = "__state_sync__";
+
+fn state_error(e: auroraview_core::state::StateError) -> PyErr {
+    PyValueError::new_err(e.to_string())
+}
+
+/// Subscriber ID of a WebView (stable for the lifetime of its message queue)
+fn subscriber_id(queue: &Arc<MessageQueue>) -> String {
+    format!("webview-{:x}", Arc::as_ptr(queue) as usize)
+}
+
+/// Shared state store synchronized across WebViews
+///
+/// The store is the single source of truth: every attached WebView keeps a
+/// replica in `auroraview.state` and changes made in one window are
+/// broadcast to all others as minimal JSON patches.
+///
+/// Example:
+/// ```python
+/// from auroraview import StateStore
+///
+/// store = StateStore(os.path.join(data_dir, "state.json"))
+/// store.persist("layout")
+/// store.attach(outliner)
+/// store.attach(inspector)
+///
+/// store.watch("selection", lambda key, value, origin: select(value))
+/// store["selection"] = ["pCube1"]
+/// ```
+#[pyclass(name = "StateStore")]
+#[derive(Clone)]
+pub struct PyStateStore {
+    store: StateStore,
+}
+
+#[pymethods]
+impl PyStateStore {
+    /// Create a store, optionally persisted to `path`
+    #[new]
+    #[pyo3(signature = (path=None))]
+    fn new(path: Option<std::path::PathBuf>) -> PyResult<Self> {
+        let store = match path {
+            Some(path) => StateStore::open(path).map_err(|e| PyIOError::new_err(e.to_string()))?,
+            None => StateStore::new(),
+        };
+        Ok(Self { store })
+    }
+
+    /// Attach a WebView so its `auroraview.state` mirrors this store
+    ///
+    /// Accepts a `WebView` or its `_core` object.
+    fn attach(&self, webview: &Bound<'_, PyAny>) -> PyResult<String> {
+        let core = core_webview(webview)?;
+        let queue = core.message_queue.clone();
+        let id = subscriber_id(&queue);
+
+        let sink_queue = queue.clone();
+        self.store.subscribe(
+            id.clone(),
+            move |message: &StateMessage| match serde_json::to_value(message) {
+                Ok(data) => sink_queue.push(WebViewMessage::EmitEvent {
+                    event_name: STATE_SYNC_EVENT.to_string(),
+                    data,
+                }),
+                Err(e) => tracing::error!("[StateStore] Failed to serialize sync: {}", e),
+            },
+        );
+
+        let store = self.store.clone();
+        let origin = id.clone();
+        core.ipc_handler.off(STATE_PATCH_EVENT);
+        core.ipc_handler
+            .on(STATE_PATCH_EVENT, move |message: IpcMessage| {
+                if message.data.get("resync").and_then(|v| v.as_bool()) == Some(true) {
+                    store.resync(&origin);
+                    return Ok(serde_json::json!({"status": "ok"}));
+                }
+                let patch: Vec<PatchOp> = message
+                    .data
+                    .get("patch")
+                    .cloned()
+                    .map(serde_json::from_value)
+                    .transpose()
+                    .map_err(|e| format!("Invalid state patch: {}", e))?
+                    .unwrap_or_default();
+                match store.apply_patch(&patch, &origin) {
+                    Ok(_) => Ok(serde_json::json!({"status": "ok", "version": store.version()})),
+                    Err(e) => {
+                        tracing::warn!("[StateStore] Rejected patch from {}: {}", origin, e);
+                        store.resync(&origin);
+                        Err(e.to_string())
+                    }
+                }
+            });
+
+        tracing::debug!("[StateStore] Attached {}", id);
+        Ok(id)
+    }
+
+    /// Detach a WebView
+    fn detach(&self, webview: &Bound<'_, PyAny>) -> PyResult<bool> {
+        let core = core_webview(webview)?;
+        core.ipc_handler.off(STATE_PATCH_EVENT);
+        Ok(self.store.unsubscribe(&subscriber_id(&core.message_queue)))
+    }
+
+    /// Get a value (None if missing)
+    #[pyo3(signature = (key, default=None))]
+    fn get(&self, py: Python<'_>, key: &str, default: Option<Py<PyAny>>) -> PyResult<Py<PyAny>> {
+        match self.store.get(key) {
+            Some(value) => json_to_python(py, &value),
+            None => Ok(default.unwrap_or_else(|| py.None())),
+        }
+    }
+
+    /// Get a nested value by JSON Pointer (e.g. "/layout/panels/0")
+    fn get_path(&self, py: Python<'_>, pointer: &str) -> PyResult<Py<PyAny>> {
+        match self.store.get_path(pointer) {
+            Some(value) => json_to_python(py, &value),
+            None => Ok(py.None()),
+        }
+    }
+
+    /// Set a value and broadcast the change
+    fn set(&self, key: &str, value: &Bound<'_, PyAny>) -> PyResult<()> {
+        let value = python_to_json(value)?;
+        self.store
+            .set(key, value, RUST_ORIGIN)
+            .map(|_| ())
+            .map_err(state_error)
+    }
+
+    /// Delete a key
+    fn delete(&self, key: &str) -> PyResult<()> {
+        self.store
+            .delete(key, RUST_ORIGIN)
+            .map(|_| ())
+            .map_err(state_error)
+    }
+
+    /// Set several keys in one change
+    fn update(&self, values: &Bound<'_, PyAny>) -> PyResult<()> {
+        let serde_json::Value::Object(values) = python_to_json(values)? else {
+            return Err(PyValueError::new_err("update() expects a dict"));
+        };
+        self.store
+            .set_many(values, RUST_ORIGIN)
+            .map(|_| ())
+            .map_err(state_error)
+    }
+
+    /// Apply a JSON patch (list of RFC 6902 operations)
+    fn patch(&self, ops: &Bound<'_, PyAny>) -> PyResult<()> {
+        let ops: Vec<PatchOp> = serde_json::from_value(python_to_json(ops)?)
+            .map_err(|e| PyValueError::new_err(format!("Invalid patch: {}", e)))?;
+        self.store
+            .apply_patch(&ops, RUST_ORIGIN)
+            .map(|_| ())
+            .map_err(state_error)
+    }
+
+    /// Remove all keys
+    fn clear(&self) -> PyResult<()> {
+        self.store
+            .clear(RUST_ORIGIN)
+            .map(|_| ())
+            .map_err(state_error)
+    }
+
+    /// Copy of the whole state as a dict
+    fn snapshot(&self, py: Python<'_>) -> PyResult<Py<PyAny>> {
+        json_to_python(py, &self.store.snapshot())
+    }
+
+    /// Top-level keys
+    fn keys(&self) -> Vec<String> {
+        self.store.keys()
+    }
+
+    /// Current version (incremented by every change)
+    #[getter]
+    fn version(&self) -> u64 {
+        self.store.version()
+    }
+
+    /// Persist a key (or stop persisting it) to the backing file
+    #[pyo3(signature = (key, enabled=true))]
+    fn persist(&self, key: &str, enabled: bool) -> PyResult<()> {
+        self.store
+            .set_persistent(key, enabled)
+            .map_err(|e| PyIOError::new_err(e.to_string()))
+    }
+
+    /// Call `callback(key, value, origin)` when `key` changes ("*" for all)
+    ///
+    /// `origin` is "rust" for changes made from Python/Rust, otherwise the
+    /// ID returned by `attach()` of the WebView that made the change.
+    /// Returns an ID for `unwatch()`.
+    fn watch(&self, key: &str, callback: Py<PyAny>) -> u64 {
+        self.store.watch(key, move |change| {
+            Python::attach(|py| {
+                let value = match &change.value {
+                    Some(value) => json_to_python(py, value),
+                    None => Ok(py.None()),
+                };
+                let result = value.and_then(|value| {
+                    callback.call1(py, (change.key.as_str(), value, change.origin.as_str()))
+                });
+                if let Err(e) = result {
+                    tracing::error!("[StateStore] Watcher for '{}' failed: {}", change.key, e);
+                }
+            })
+        })
+    }
+
+    /// Remove a watcher
+    fn unwatch(&self, watch_id: u64) -> bool {
+        self.store.unwatch(watch_id)
+    }
+
+    fn __getitem__(&self, py: Python<'_>, key: &str) -> PyResult<Py<PyAny>> {
+        match self.store.get(key) {
+            Some(value) => json_to_python(py, &value),
+            None => Err(pyo3::exceptions::PyKeyError::new_err(key.to_string())),
+        }
+    }
+
+    fn __setitem__(&self, key: &str, value: &Bound<'_, PyAny>) -> PyResult<()> {
+        self.set(key, value)
+    }
+
+    fn __delitem__(&self, key: &str) -> PyResult<()> {
+        if !self.store.contains(key) {
+            return Err(pyo3::exceptions::PyKeyError::new_err(key.to_string()));
+        }
+        self.delete(key)
+    }
+
+    fn __contains__(&self, key: &str) -> bool {
+        self.store.contains(key)
+    }
+
+    fn __len__(&self) -> usize {
+        self.store.keys().len()
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "StateStore(keys={}, version={}, webviews={})",
+            self.store.keys().len(),
+            self.store.version(),
+            self.store.subscriber_count()
+        )
+    }
+}
+
+/// Resolve a `WebView` wrapper or core object to the core WebView
+fn core_webview<'py>(webview: &Bound<'py, PyAny>) -> PyResult<PyRef<'py, AuroraView>> {
+    let core = match webview.cast::<AuroraView>() {
+        Ok(core) => core.clone(),
+        Err(_) => webview
+            .getattr("_core")
+            .ok()
+            .and_then(|core| core.cast_into::<AuroraView>().ok())
+            .ok_or_else(|| PyValueError::new_err("attach() expects a WebView"))?,
+    };
+    Ok(core.try_borrow()?)
+}
+
+/// Register the state store class
+pub fn register_state_store(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_class::<PyStateStore>()?;
+    Ok(())
+}
@@ -60,6 +60,9 @@ fn _core(m: &Bound<'_, PyModule>) -> PyResult<()> {
     // Register PluginManager class (for file system and other native operations)
     m.add_class::<webview::PluginManager>()?;
 
+    // Register FlowChannel class (flow-controlled streaming channels)
+    m.add_class::<webview::PyFlowChannel>()?;
+
     // Register PyRegion class (for click-through interactive regions)
     m.add_class::<webview::PyRegion>()?;
 
@@ -97,6 +100,9 @@ fn _core(m: &Bound<'_, PyModule>) -> PyResult<()> {
     // Register WebView2 cleanup functions (stale directory cleanup)
     bindings::cleanup::register_cleanup_functions(m)?;
 
+    // Register shared state store (multi-window state sync)
+    bindings::state_store::register_state_store(m)?;
+
     // Register high-performance DOM batch operations
     dom::register_dom_module(m)?;
 
@@ -341,6 +347,7 @@ mod tests {
             let m = pyo3::types::PyModule::new(py, "auroraview_test").unwrap();
             _core(&m).expect("module init should succeed");
             assert!(m.getattr("get_all_windows").is_ok());
+            assert!(m.getattr("StateStore").is_ok());
             Ok::<(), pyo3::PyErr>(())
         })
         .unwrap();
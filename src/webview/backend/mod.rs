@@ -71,6 +71,7 @@ pub use auroraview_core::backend::{
     CookieInfo,
     EmbeddableBackend,
     EventLoopBackend,
+    HeadlessBackend,
     JavaScriptCallback,
     LifecycleEvent,
     LifecycleObserver,
@@ -926,8 +926,6 @@ impl NativeBackend {
             log_background_color(background_color);
         }
 
-        // Register auroraview:// protocol if asset_root is configured
-        //
         // SECURITY NOTE: On Windows, wry maps custom protocols to HTTP format:
         //   - "auroraview" scheme becomes "http://auroraview.<path>" by default
         //   - We use with_https_scheme() to use "https://auroraview.<path>" for better security
@@ -940,33 +938,38 @@ impl NativeBackend {
         //   2. The origin is "https://auroraview.<path>", not a real HTTPS site
         //   3. wry's https scheme provides secure context (needed for some Web APIs)
         //
-        // Register auroraview:// custom protocol for local asset loading
-        if let Some(asset_root) = &config.asset_root {
-            let asset_root = asset_root.clone();
-            tracing::debug!(
-                "[NativeBackend] Registering auroraview:// protocol (asset_root: {:?})",
-                asset_root
-            );
-
-            // On Windows, use HTTPS scheme for secure context support
-            #[cfg(target_os = "windows")]
-            {
-                builder = builder.with_https_scheme(true);
-            }
+        // Register auroraview:// custom protocol for local assets and blob
+        // payloads. It is always registered because binary IPC
+        // (`auroraview://type:blob/<id>`) works without an asset root; like
+        // desktop mode, assets are then served from the current directory.
+        let asset_root = config
+            .asset_root
+            .clone()
+            .unwrap_or_else(|| std::env::current_dir().unwrap_or_default());
+        let serve_options = auroraview_core::file_server::ServeOptions::default()
+            .with_cache_policy(config.asset_cache_policy)
+            .with_precompressed(config.serve_precompressed);
+        let blob_store = config.blob_store.clone();
+        tracing::debug!(
+            "[NativeBackend] Registering auroraview:// protocol (asset_root: {:?})",
+            asset_root
+        );
 
-            builder =
-                builder.with_custom_protocol("auroraview".into(), move |_webview_id, request| {
-                    crate::webview::protocol_handlers::handle_auroraview_protocol(
-                        &asset_root,
-                        request,
-                    )
-                });
-        } else {
-            tracing::debug!(
-                "[NativeBackend] asset_root is None, auroraview:// protocol not registered"
-            );
+        // On Windows, use HTTPS scheme for secure context support
+        #[cfg(target_os = "windows")]
+        {
+            builder = builder.with_https_scheme(true);
         }
 
+        builder = builder.with_custom_protocol("auroraview".into(), move |_webview_id, request| {
+            crate::webview::protocol_handlers::handle_auroraview_protocol_with_blobs(
+                &asset_root,
+                request,
+                &serve_options,
+                &blob_store,
+            )
+        });
+
         // Register custom protocols
         for (scheme, callback) in &config.custom_protocols {
             let callback_clone = callback.clone();
@@ -1045,6 +1048,20 @@ impl NativeBackend {
         let event_bridge_script = js_assets::build_init_script(config);
         builder = builder.with_initialization_script(&event_bridge_script);
 
+        // Streaming channels belong to the page: cancel them when a new one loads
+        let channels = config.channels.clone();
+        builder = builder.with_on_page_load_handler(move |event, _url| {
+            if matches!(event, wry::PageLoadEvent::Started) {
+                let cancelled = channels.handle_page_load();
+                if cancelled > 0 {
+                    tracing::debug!(
+                        "[NativeBackend] Cancelled {} channels on navigation",
+                        cancelled
+                    );
+                }
+            }
+        });
+
         // Set IPC handler
         let ipc_handler_clone = ipc_handler.clone();
         builder = builder.with_ipc_handler(move |request| {
@@ -1,5 +1,8 @@
 //! WebView configuration structures
 
+use auroraview_core::cookies::CookieJar;
+use auroraview_core::file_server::CachePolicy;
+use auroraview_core::ipc::{BlobStore, ChannelRegistry};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::path::PathBuf;
@@ -409,11 +412,34 @@ pub struct WebViewConfig {
     /// Asset root directory for auroraview:// protocol
     pub asset_root: Option<PathBuf>,
 
+    /// `Cache-Control` policy for files served from `asset_root`
+    pub asset_cache_policy: CachePolicy,
+
+    /// Serve pre-compressed `.br`/`.gz` siblings of files in `asset_root`
+    /// when the WebView accepts the encoding
+    pub serve_precompressed: bool,
+
+    /// Binary payloads served under `type:blob/` of the auroraview protocol
+    ///
+    /// Shared by clones of the config; cleared when the WebView is destroyed.
+    pub blob_store: BlobStore,
+
+    /// Flow-controlled streaming channels opened with `open_channel()`
+    ///
+    /// Shared by clones of the config; cancelled when the WebView is destroyed.
+    pub channels: ChannelRegistry,
+
     /// User data directory for WebView (cookies, cache, localStorage, etc.)
     /// If None, uses system default (usually %LOCALAPPDATA%\{app}\EBWebView on Windows)
     /// Set this to isolate WebView data per application or user profile
     pub data_directory: Option<PathBuf>,
 
+    /// Persistent cookie jar, seeded into the WebView on creation and
+    /// updated from it on destroy
+    ///
+    /// Opened from `data_directory` by [`WebViewConfig::cookie_jar`] when None.
+    pub cookie_jar: Option<Arc<CookieJar>>,
+
     /// Custom protocol handlers (scheme -> callback)
     #[allow(clippy::type_complexity)]
     pub custom_protocols: HashMap<String, ProtocolCallback>,
@@ -574,6 +600,32 @@ pub struct WebViewConfig {
     pub tray: Option<TrayConfig>,
 }
 
+impl WebViewConfig {
+    /// Cookie jar of this WebView, opened once from `data_directory`
+    ///
+    /// Session cookies are persisted with the rest. Falls back to an
+    /// in-memory jar without a data directory or when the stored jar cannot
+    /// be read.
+    pub fn cookie_jar(&mut self) -> Arc<CookieJar> {
+        let data_directory = self.data_directory.clone();
+        self.cookie_jar
+            .get_or_insert_with(|| {
+                let jar = match data_directory {
+                    Some(dir) => match CookieJar::for_profile(&dir) {
+                        Ok(jar) => jar.persist_session_cookies(true),
+                        Err(e) => {
+                            tracing::warn!("[WebView] Failed to open cookie jar: {}", e);
+                            CookieJar::new()
+                        }
+                    },
+                    None => CookieJar::new(),
+                };
+                Arc::new(jar)
+            })
+            .clone()
+    }
+}
+
 // Manual Debug implementation (ProtocolCallback doesn't implement Debug)
 impl std::fmt::Debug for WebViewConfig {
     fn fmt(&self, fmt: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
@@ -605,6 +657,10 @@ impl std::fmt::Debug for WebViewConfig {
             .field("ipc_batch_size", &self.ipc_batch_size)
             .field("ipc_batch_interval_ms", &self.ipc_batch_interval_ms)
             .field("asset_root", &self.asset_root)
+            .field("asset_cache_policy", &self.asset_cache_policy)
+            .field("serve_precompressed", &self.serve_precompressed)
+            .field("blob_store", &format!("{} blobs", self.blob_store.len()))
+            .field("channels", &format!("{} channels", self.channels.len()))
             .field(
                 "custom_protocols",
                 &format!("{} protocols", self.custom_protocols.len()),
@@ -646,7 +702,12 @@ impl Default for WebViewConfig {
             undecorated_shadow: false,
 
             asset_root: None,
+            asset_cache_policy: CachePolicy::default(),
+            serve_precompressed: false,
+            blob_store: BlobStore::new(),
+            channels: ChannelRegistry::new(),
             data_directory: None,
+            cookie_jar: None,
             custom_protocols: HashMap::new(),
             api_methods: HashMap::new(),
             allow_new_window: false, // Block new windows by default (deprecated)
@@ -759,6 +820,18 @@ impl WebViewBuilder {
         self
     }
 
+    /// Set the `Cache-Control` policy for files served from the asset root
+    pub fn asset_cache_policy(mut self, policy: CachePolicy) -> Self {
+        self.config.asset_cache_policy = policy;
+        self
+    }
+
+    /// Serve pre-compressed `.br`/`.gz` siblings of asset files
+    pub fn serve_precompressed(mut self, enabled: bool) -> Self {
+        self.config.serve_precompressed = enabled;
+        self
+    }
+
     /// Set user data directory for WebView (cookies, cache, localStorage, etc.)
     /// Use this to isolate WebView data per application or user profile
     pub fn data_directory(mut self, path: impl Into<PathBuf>) -> Self {
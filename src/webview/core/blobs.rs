@@ -0,0 +1,93 @@
+//! AuroraView Core - Binary Blob Methods
+//!
+//! This module contains methods for the IPC blob store:
+//! - `create_blob`: Store bytes for JavaScript to fetch as an `ArrayBuffer`
+//! - `read_blob`: Read bytes uploaded by JavaScript
+//! - `release_blob`: Free a blob
+
+use auroraview_core::ipc::{BlobLifetime, DEFAULT_BLOB_MIME};
+use pyo3::prelude::*;
+use pyo3::types::{PyBytes, PyDict};
+
+use super::AuroraView;
+
+#[pymethods]
+impl AuroraView {
+    /// Store binary data for JavaScript
+    ///
+    /// The returned handle can be emitted to JavaScript, which fetches the
+    /// bytes with `auroraview.blob.fetch(handle)` instead of decoding base64.
+    ///
+    /// Args:
+    ///     data (bytes): Payload
+    ///     mime_type (str, optional): MIME type (default: application/octet-stream)
+    ///     lifetime (str, optional): "manual" (until released), "one-shot"
+    ///         (freed after the first fetch) or "ttl:<seconds>"
+    ///
+    /// Returns:
+    ///     dict: Handle with `id`, `size`, `mime` and `url`
+    ///
+    /// Example:
+    ///     >>> handle = webview.create_blob(exr_preview, "image/x-exr", "one-shot")
+    ///     >>> webview.emit("preview_ready", handle)
+    #[pyo3(signature = (data, mime_type=DEFAULT_BLOB_MIME, lifetime="manual"))]
+    fn create_blob<'py>(
+        &self,
+        py: Python<'py>,
+        data: &[u8],
+        mime_type: &str,
+        lifetime: &str,
+    ) -> PyResult<Bound<'py, PyDict>> {
+        let lifetime = BlobLifetime::parse(lifetime).ok_or_else(|| {
+            pyo3::exceptions::PyValueError::new_err(format!(
+                "Invalid blob lifetime '{}', expected 'manual', 'one-shot' or 'ttl:<seconds>'",
+                lifetime
+            ))
+        })?;
+        let handle = self
+            .config
+            .borrow()
+            .blob_store
+            .insert_with_lifetime(data, mime_type, lifetime)
+            .map_err(|e| pyo3::exceptions::PyMemoryError::new_err(e.to_string()))?;
+
+        let dict = PyDict::new(py);
+        dict.set_item("id", handle.id)?;
+        dict.set_item("size", handle.size)?;
+        dict.set_item("mime", handle.mime)?;
+        dict.set_item("url", handle.url)?;
+        Ok(dict)
+    }
+
+    /// Read a blob, e.g. one uploaded with `auroraview.blob.upload()`
+    ///
+    /// Args:
+    ///     blob_id (str): Blob ID from the handle
+    ///     release (bool, optional): Free the blob after reading (default: False)
+    ///
+    /// Returns:
+    ///     bytes | None: Blob data, or None if the blob does not exist
+    #[pyo3(signature = (blob_id, release=false))]
+    fn read_blob<'py>(
+        &self,
+        py: Python<'py>,
+        blob_id: &str,
+        release: bool,
+    ) -> Option<Bound<'py, PyBytes>> {
+        let config = self.config.borrow();
+        let blob = if release {
+            config.blob_store.take(blob_id)
+        } else {
+            config.blob_store.get(blob_id)
+        }?;
+        Some(PyBytes::new(py, &blob.data))
+    }
+
+    /// Free a blob
+    ///
+    /// Returns:
+    ///     bool: Whether the blob existed
+    fn release_blob(&self, blob_id: &str) -> bool {
+        self.config.borrow().blob_store.release(blob_id)
+    }
+}
@@ -0,0 +1,241 @@
+//! AuroraView Core - Flow-Controlled Channel Methods
+//!
+//! This module contains methods for streaming with backpressure:
+//! - `open_channel`: Open a credit-based channel to JavaScript
+//! - `FlowChannel`: Producer handle returned by `open_channel`
+//!
+//! JavaScript acknowledges consumed messages with `__channel_ack__` and
+//! cancels with `__channel_cancel__`; both are handled in Rust so a
+//! producer blocked in `send()` is woken without needing the GIL.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use auroraview_core::ipc::{
+    ChannelConfig, ChannelError, ChannelFrame, ChannelRegistry, ChannelSink, FlowChannel,
+    OverflowPolicy, CHANNEL_ACK_EVENT, CHANNEL_CANCEL_EVENT, DEFAULT_CHANNEL_CAPACITY,
+    DEFAULT_CHANNEL_CREDITS,
+};
+use pyo3::exceptions::{PyTimeoutError, PyValueError};
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+
+use super::AuroraView;
+use crate::ipc::json::python_to_json;
+use crate::ipc::{IpcHandler, IpcMessage, MessageQueue, WebViewMessage};
+
+/// Producer side of a flow-controlled channel
+///
+/// Returned by `WebView.open_channel()`. `send()` delivers while JavaScript
+/// has credits and buffers otherwise; the overflow policy decides what
+/// happens when the buffer is full.
+#[pyclass(name = "FlowChannel")]
+pub struct PyFlowChannel {
+    channel: FlowChannel,
+}
+
+#[pymethods]
+impl PyFlowChannel {
+    /// Channel ID
+    #[getter]
+    fn id(&self) -> String {
+        self.channel.id().to_string()
+    }
+
+    /// Send a message
+    ///
+    /// With the "block" policy this waits (without holding the GIL) until
+    /// JavaScript acknowledges messages, so call it from a worker thread.
+    ///
+    /// Returns:
+    ///     bool: False if the channel is closed or was cancelled
+    ///
+    /// Raises:
+    ///     TimeoutError: If a blocking send exceeded the channel timeout
+    fn send(&self, py: Python<'_>, data: &Bound<'_, PyAny>) -> PyResult<bool> {
+        let value = python_to_json(data)?;
+        let channel = self.channel.clone();
+        match py.detach(move || channel.send(value)) {
+            Ok(_) => Ok(true),
+            Err(ChannelError::Timeout(id)) => Err(PyTimeoutError::new_err(format!(
+                "Timed out waiting for channel '{}' to drain",
+                id
+            ))),
+            Err(e) => {
+                tracing::debug!("[FlowChannel] Send rejected: {}", e);
+                Ok(false)
+            }
+        }
+    }
+
+    /// Close the channel
+    ///
+    /// JavaScript receives the close event after the buffered messages.
+    fn close(&self) {
+        self.channel.close();
+    }
+
+    /// Wait until JavaScript received everything and the close event
+    ///
+    /// Returns:
+    ///     bool: False if `timeout` (seconds) elapsed first
+    #[pyo3(signature = (timeout=None))]
+    fn wait_closed(&self, py: Python<'_>, timeout: Option<f64>) -> PyResult<bool> {
+        let timeout = timeout.map(seconds).transpose()?;
+        let channel = self.channel.clone();
+        Ok(py.detach(move || channel.wait_finished(timeout)))
+    }
+
+    /// Call `callback(reason)` when JavaScript cancels the channel
+    ///
+    /// Runs on the thread handling IPC; `reason` may be None.
+    fn on_cancel(&self, callback: Py<PyAny>) {
+        self.channel.on_cancel(move |reason| {
+            Python::attach(|py| {
+                if let Err(e) = callback.call1(py, (reason,)) {
+                    tracing::error!("[FlowChannel] Cancel handler failed: {}", e);
+                }
+            })
+        });
+    }
+
+    /// Whether JavaScript cancelled the channel
+    #[getter]
+    fn is_cancelled(&self) -> bool {
+        self.channel.is_cancelled()
+    }
+
+    /// Reason given by JavaScript when cancelling
+    #[getter]
+    fn cancel_reason(&self) -> Option<String> {
+        self.channel.cancel_reason()
+    }
+
+    /// Whether the channel was closed or cancelled
+    #[getter]
+    fn is_closed(&self) -> bool {
+        self.channel.is_closed()
+    }
+
+    /// Counters: sent, delivered, dropped, coalesced, buffered, credits
+    fn stats<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyDict>> {
+        let stats = self.channel.stats();
+        let dict = PyDict::new(py);
+        dict.set_item("sent", stats.sent)?;
+        dict.set_item("delivered", stats.delivered)?;
+        dict.set_item("dropped", stats.dropped)?;
+        dict.set_item("coalesced", stats.coalesced)?;
+        dict.set_item("buffered", stats.buffered)?;
+        dict.set_item("credits", stats.credits)?;
+        Ok(dict)
+    }
+
+    fn __repr__(&self) -> String {
+        let config = self.channel.config();
+        let status = if self.channel.is_cancelled() {
+            "cancelled"
+        } else if self.channel.is_closed() {
+            "closed"
+        } else {
+            "open"
+        };
+        format!(
+            "FlowChannel({}, {}, credits={}, capacity={}, policy={})",
+            self.channel.id(),
+            status,
+            config.credits,
+            config.capacity,
+            config.policy.as_str()
+        )
+    }
+}
+
+#[pymethods]
+impl AuroraView {
+    /// Open a flow-controlled streaming channel
+    ///
+    /// JavaScript receives messages through `auroraview.channel(id)` and
+    /// returns a credit for each message its handlers consumed, so a slow
+    /// page throttles the producer instead of losing data. The channel is
+    /// cancelled when the page navigates or reloads.
+    ///
+    /// Args:
+    ///     channel_id (str, optional): Channel ID (generated if omitted)
+    ///     credits (int, optional): Messages JavaScript may have in flight (default: 16)
+    ///     capacity (int, optional): Messages buffered without credits (default: 256)
+    ///     policy (str, optional): Full-buffer behavior: "block" (wait for
+    ///         JavaScript), "drop_oldest" (log tails) or "coalesce" (latest
+    ///         value wins, e.g. progress)
+    ///     timeout (float, optional): Seconds a blocking send may wait
+    ///
+    /// Returns:
+    ///     FlowChannel: Producer handle
+    ///
+    /// Example:
+    ///     >>> channel = webview.open_channel("render_log", policy="drop_oldest")
+    ///     >>> channel.on_cancel(lambda reason: renderer.abort())
+    ///     >>> for line in renderer.log_lines():
+    ///     ...     if not channel.send(line):
+    ///     ...         break
+    ///     >>> channel.close()
+    #[pyo3(signature = (channel_id=None, credits=DEFAULT_CHANNEL_CREDITS, capacity=DEFAULT_CHANNEL_CAPACITY, policy="block", timeout=None))]
+    fn open_channel(
+        &self,
+        channel_id: Option<String>,
+        credits: u32,
+        capacity: usize,
+        policy: &str,
+        timeout: Option<f64>,
+    ) -> PyResult<PyFlowChannel> {
+        let policy = OverflowPolicy::parse(policy).ok_or_else(|| {
+            PyValueError::new_err(format!(
+                "Invalid channel policy '{}', expected 'block', 'drop_oldest' or 'coalesce'",
+                policy
+            ))
+        })?;
+        let mut config = ChannelConfig::default()
+            .with_credits(credits)
+            .with_capacity(capacity)
+            .with_policy(policy);
+        if let Some(timeout) = timeout {
+            config = config.with_block_timeout(seconds(timeout)?);
+        }
+
+        let registry = self.config.borrow().channels.clone();
+        route_channel_events(&self.ipc_handler, &registry);
+        let channel = registry.open(channel_id, config, queue_sink(&self.message_queue));
+        tracing::debug!("[FlowChannel] Opened {:?}", channel);
+        Ok(PyFlowChannel { channel })
+    }
+}
+
+/// Sink emitting channel frames as WebView events
+fn queue_sink(queue: &Arc<MessageQueue>) -> ChannelSink {
+    let queue = queue.clone();
+    Arc::new(move |frame: ChannelFrame| {
+        queue.push(WebViewMessage::EmitEvent {
+            event_name: frame.event_name().to_string(),
+            data: frame.payload(),
+        })
+    })
+}
+
+/// Route acknowledgements and cancellations from JavaScript to the registry
+fn route_channel_events(ipc_handler: &IpcHandler, registry: &ChannelRegistry) {
+    let acks = registry.clone();
+    ipc_handler.off(CHANNEL_ACK_EVENT);
+    ipc_handler.on(CHANNEL_ACK_EVENT, move |message: IpcMessage| {
+        Ok(serde_json::json!({"ok": acks.handle_ack(&message.data)}))
+    });
+
+    let cancels = registry.clone();
+    ipc_handler.off(CHANNEL_CANCEL_EVENT);
+    ipc_handler.on(CHANNEL_CANCEL_EVENT, move |message: IpcMessage| {
+        Ok(serde_json::json!({"ok": cancels.handle_cancel(&message.data)}))
+    });
+}
+
+fn seconds(value: f64) -> PyResult<Duration> {
+    Duration::try_from_secs_f64(value)
+        .map_err(|_| PyValueError::new_err(format!("Invalid timeout: {}", value)))
+}
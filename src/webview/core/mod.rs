@@ -9,6 +9,8 @@
 //! - `bom.rs`: Browser Object Model APIs
 //! - `multiwindow.rs`: Multi-window management APIs
 //! - `plugins.rs`: Plugin system integration
+//! - `blobs.rs`: Binary blob store for large IPC payloads
+//! - `channels.rs`: Flow-controlled streaming channels
 
 use pyo3::prelude::*;
 use pyo3::types::PyDict;
@@ -25,7 +27,9 @@ use crate::ipc::{IpcHandler, JsCallbackManager, MessageQueue, WebViewMessage};
 // Sub-modules containing #[pymethods] implementations
 #[cfg(feature = "templates")]
 mod api; // API registration methods (uses Askama templates)
+mod blobs;
 mod bom;
+mod channels;
 mod dialogs;
 mod dom; // DOM operation methods (high-performance)
 mod effects; // Window effects (click-through, vibrancy)
@@ -36,6 +40,7 @@ mod multiwindow;
 pub mod plugins;
 mod storage;
 
+pub use channels::PyFlowChannel;
 pub use effects::PyRegion;
 pub use plugins::PluginManager;
 
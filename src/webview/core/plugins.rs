@@ -4,8 +4,8 @@
 //! allowing JavaScript to invoke plugin commands like file system operations.
 
 use auroraview_core::plugins::{
-    create_router, create_router_with_scope, PathScope, PluginEventCallback, PluginRequest,
-    PluginResponse, PluginRouter, ScopeConfig,
+    create_router, create_router_with_scope, PathScope, PluginCommand, PluginEventCallback,
+    PluginRequest, PluginResponse, PluginRouter, ScopeConfig,
 };
 use parking_lot::RwLock;
 use pyo3::prelude::*;
@@ -13,7 +13,7 @@ use serde_json::Value;
 use std::path::PathBuf;
 use std::sync::Arc;
 
-use crate::ipc::json::json_to_python;
+use crate::ipc::json::{json_to_python, python_to_json};
 
 /// Thread-safe plugin router wrapper
 #[pyclass]
@@ -183,6 +183,51 @@ impl PluginManager {
         Ok(router.scope().enabled_plugins.iter().cloned().collect())
     }
 
+    /// Register a typed command specification
+    ///
+    /// Arguments of `plugin:<plugin>|<name>` are validated against
+    /// `args_schema` (a JSON Schema dict) before dispatch; invalid calls are
+    /// rejected with `INVALID_ARGS`. Without a schema only the argument
+    /// names in `required`/`optional` are checked.
+    #[pyo3(signature = (plugin, name, description="", args_schema=None, result_schema=None, required=None, optional=None))]
+    #[allow(clippy::too_many_arguments)]
+    pub fn register_command(
+        &self,
+        plugin: &str,
+        name: &str,
+        description: &str,
+        args_schema: Option<&Bound<'_, PyAny>>,
+        result_schema: Option<&Bound<'_, PyAny>>,
+        required: Option<Vec<String>>,
+        optional: Option<Vec<String>>,
+    ) -> PyResult<()> {
+        let as_refs = |names: &[String]| names.iter().map(String::as_str).collect::<Vec<_>>();
+        let mut command = PluginCommand::new(name, description)
+            .with_required(&as_refs(&required.unwrap_or_default()))
+            .with_optional(&as_refs(&optional.unwrap_or_default()));
+        if let Some(schema) = args_schema {
+            command = command.with_args_schema(python_to_json(schema)?);
+        }
+        if let Some(schema) = result_schema {
+            command = command.with_result_schema(python_to_json(schema)?);
+        }
+        self.router.write().register_command(plugin, command);
+        Ok(())
+    }
+
+    /// TypeScript declarations (`.d.ts`) for all typed commands
+    ///
+    /// Augments the `AuroraViewCommands` interface of `auroraview.d.ts` so
+    /// that `auroraview.invoke()` is typed per command.
+    pub fn typescript_definitions(&self) -> String {
+        self.router.read().typescript_definitions()
+    }
+
+    /// Python type stubs (`.pyi`) for all typed commands
+    pub fn python_stubs(&self) -> String {
+        self.router.read().python_stubs()
+    }
+
     /// Handle a plugin command (internal use)
     pub fn handle_command(&self, invoke_cmd: &str, args_json: &str) -> PyResult<String> {
         let router = self.router.read();
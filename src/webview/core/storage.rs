@@ -3,9 +3,12 @@
 //! This module contains storage-related methods:
 //! - localStorage API
 //! - sessionStorage API
-//! - Cookie management
+//! - Cookie management (backed by the persistent cookie jar)
 
+use auroraview_core::cookies::{Cookie, CookieContext, CookieJar, CookieResult, SameSite};
+use pyo3::exceptions::{PyRuntimeError, PyValueError};
 use pyo3::prelude::*;
+use wry::WebView as WryWebView;
 
 use super::AuroraView;
 use crate::ipc::WebViewMessage;
@@ -102,6 +105,9 @@ impl AuroraView {
 
     /// Set a cookie
     ///
+    /// The cookie is stored in the persistent cookie jar and in the WebView.
+    /// Without `domain` it is a host-only cookie for the current page.
+    ///
     /// Args:
     ///     name (str): Cookie name
     ///     value (str): Cookie value
@@ -109,7 +115,9 @@ impl AuroraView {
     ///     path (str, optional): Cookie path (default: "/")
     ///     secure (bool, optional): Secure flag (default: False)
     ///     same_site (str, optional): SameSite attribute ("Strict", "Lax", "None")
-    #[pyo3(signature = (name, value, expires_days=None, path=None, secure=false, same_site=None))]
+    ///     domain (str, optional): Cookie domain; a leading dot includes subdomains
+    #[pyo3(signature = (name, value, expires_days=None, path=None, secure=false, same_site=None, domain=None))]
+    #[allow(clippy::too_many_arguments)]
     fn set_cookie(
         &self,
         name: &str,
@@ -118,76 +126,134 @@ impl AuroraView {
         path: Option<&str>,
         secure: bool,
         same_site: Option<&str>,
+        domain: Option<&str>,
     ) -> PyResult<()> {
-        let escaped_name = name.replace('\\', "\\\\").replace('\'', "\\'");
-        let escaped_value = value.replace('\\', "\\\\").replace('\'', "\\'");
-
-        let mut cookie_parts = vec![format!("{}={}", escaped_name, escaped_value)];
+        let domain = domain
+            .map(str::to_string)
+            .or_else(|| self.cookie_url().and_then(|url| host_of(&url)))
+            .ok_or_else(|| {
+                PyValueError::new_err("Cookie domain is required before a page is loaded")
+            })?;
 
+        let mut cookie = Cookie::new(name, value, &domain)
+            .with_path(path.unwrap_or("/"))
+            .secure(secure)
+            .with_same_site(parse_same_site(same_site)?);
         if let Some(days) = expires_days {
-            cookie_parts.push(format!(
-                "expires=' + new Date(Date.now() + {} * 864e5).toUTCString() + '",
-                days
-            ));
+            let now = std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .map(|d| d.as_secs() as i64)
+                .unwrap_or_default();
+            cookie = cookie.expires_at(now + i64::from(days) * 86_400);
         }
 
-        cookie_parts.push(format!("path={}", path.unwrap_or("/")));
+        self.with_cookie_store(|jar, webview| match webview {
+            Some(webview) => jar.set_webview_cookie(webview, cookie),
+            None => jar.set(cookie),
+        })
+    }
 
-        if secure {
-            cookie_parts.push("secure".to_string());
-        }
+    /// Get a cookie value
+    ///
+    /// Looks up the cookie sent to the current page and calls
+    /// `callback(value, error)`; `value` is None when the cookie is not set.
+    fn get_cookie(&self, py: Python<'_>, name: &str, callback: Py<PyAny>) -> PyResult<()> {
+        let value = match self.cookie_url() {
+            Some(url) => self.with_cookie_store(|jar, webview| match webview {
+                Some(webview) => jar.webview_cookie(webview, &url, name),
+                None => Ok(jar
+                    .cookies_for_url(&url, &CookieContext::default())?
+                    .into_iter()
+                    .find(|c| c.name == name)),
+            })?,
+            None => None,
+        };
+        callback.call1(py, (value.map(|c| c.value), py.None()))?;
+        Ok(())
+    }
 
-        if let Some(ss) = same_site {
-            cookie_parts.push(format!("SameSite={}", ss));
-        }
+    /// Delete a cookie
+    ///
+    /// Args:
+    ///     name (str): Cookie name
+    ///     path (str, optional): Cookie path (default: any path)
+    ///     domain (str, optional): Cookie domain (default: current page host)
+    #[pyo3(signature = (name, path=None, domain=None))]
+    fn delete_cookie(&self, name: &str, path: Option<&str>, domain: Option<&str>) -> PyResult<()> {
+        let Some(domain) = domain
+            .map(str::to_string)
+            .or_else(|| self.cookie_url().and_then(|url| host_of(&url)))
+        else {
+            return Ok(());
+        };
+        self.with_cookie_store(|jar, webview| match webview {
+            Some(webview) => jar.delete_webview_cookie(webview, &domain, path, name),
+            None => match path {
+                Some(path) => jar.remove(&domain, path, name, None).map(|_| ()),
+                None => jar.remove_named(&domain, name).map(|_| ()),
+            },
+        })
+    }
 
-        let script = format!("document.cookie = '{}'", cookie_parts.join("; "));
-        self.message_queue.push(WebViewMessage::EvalJs(script));
-        Ok(())
+    /// Clear all cookies from the cookie jar and the WebView
+    fn clear_cookies(&self) -> PyResult<()> {
+        self.with_cookie_store(|jar, webview| match webview {
+            Some(webview) => jar.clear_webview(webview),
+            None => jar.clear(),
+        })
     }
+}
 
-    /// Get a cookie value asynchronously
-    fn get_cookie(&self, name: &str, callback: Py<PyAny>) -> PyResult<()> {
-        let escaped_name = name.replace('\\', "\\\\").replace('\'', "\\'");
-        let script = format!(
-            r#"(function() {{
-                var name = '{}=';
-                var cookies = document.cookie.split(';');
-                for (var i = 0; i < cookies.length; i++) {{
-                    var c = cookies[i].trim();
-                    if (c.indexOf(name) === 0) {{
-                        return c.substring(name.length);
-                    }}
-                }}
-                return null;
-            }})()"#,
-            escaped_name
-        );
-        self.eval_js_async_internal(&script, callback, 5000)
+impl AuroraView {
+    /// Run `f` against the cookie jar and, once created, the WebView
+    ///
+    /// Before the WebView exists the jar is opened from the config and later
+    /// seeded into the WebView.
+    fn with_cookie_store<T>(
+        &self,
+        f: impl FnOnce(&CookieJar, Option<&WryWebView>) -> CookieResult<T>,
+    ) -> PyResult<T> {
+        {
+            let inner_ref = self.inner.borrow();
+            if let Some(inner) = inner_ref.as_ref() {
+                let webview = inner
+                    .webview
+                    .lock()
+                    .map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+                return f(&inner.cookie_jar, Some(&*webview))
+                    .map_err(|e| PyRuntimeError::new_err(e.to_string()));
+            }
+        }
+        let jar = self.config.borrow_mut().cookie_jar();
+        f(&jar, None).map_err(|e| PyRuntimeError::new_err(e.to_string()))
     }
 
-    /// Delete a cookie
-    #[pyo3(signature = (name, path=None))]
-    fn delete_cookie(&self, name: &str, path: Option<&str>) -> PyResult<()> {
-        let escaped_name = name.replace('\\', "\\\\").replace('\'', "\\'");
-        let cookie_path = path.unwrap_or("/");
-        let script = format!(
-            "document.cookie = '{}=; expires=Thu, 01 Jan 1970 00:00:00 GMT; path={}'",
-            escaped_name, cookie_path
-        );
-        self.message_queue.push(WebViewMessage::EvalJs(script));
-        Ok(())
+    /// URL of the loaded page, or the configured URL before the WebView exists
+    fn cookie_url(&self) -> Option<String> {
+        match self.inner.borrow().as_ref() {
+            Some(inner) => inner.webview.lock().ok().and_then(|wv| wv.url().ok()),
+            None => self.config.borrow().url.clone(),
+        }
     }
+}
 
-    /// Clear all cookies (for current domain)
-    fn clear_cookies(&self) -> PyResult<()> {
-        let script = r#"
-            document.cookie.split(';').forEach(function(c) {
-                document.cookie = c.trim().split('=')[0] + '=; expires=Thu, 01 Jan 1970 00:00:00 GMT; path=/';
-            });
-        "#;
-        self.message_queue
-            .push(WebViewMessage::EvalJs(script.to_string()));
-        Ok(())
+fn host_of(url: &str) -> Option<String> {
+    url::Url::parse(url)
+        .ok()?
+        .host_str()
+        .filter(|host| !host.is_empty())
+        .map(str::to_string)
+}
+
+fn parse_same_site(same_site: Option<&str>) -> PyResult<SameSite> {
+    match same_site.map(str::to_ascii_lowercase).as_deref() {
+        None => Ok(SameSite::Unspecified),
+        Some("strict") => Ok(SameSite::Strict),
+        Some("lax") => Ok(SameSite::Lax),
+        Some("none") => Ok(SameSite::None),
+        Some(other) => Err(PyValueError::new_err(format!(
+            "Invalid SameSite value: {}",
+            other
+        ))),
     }
 }
@@ -98,7 +98,7 @@ pub use window_builder::create_window_and_event_loop;
 /// // let webview = create_desktop(config, ipc_handler, message_queue).unwrap();
 /// ```
 pub fn create_desktop(
-    config: WebViewConfig,
+    mut config: WebViewConfig,
     ipc_handler: Arc<IpcHandler>,
     message_queue: Arc<MessageQueue>,
 ) -> Result<WebViewInner, Box<dyn std::error::Error>> {
@@ -191,10 +191,21 @@ pub fn create_desktop(
     // This trades disk space for reliability - each process gets isolated WebView2 state.
     // Use helper to configure WebView builder
     let build_start = std::time::Instant::now();
-    let webview_builder =
-        configure_webview_builder(&config, ipc_handler.clone(), message_queue.clone(), &window)?;
+    let cookie_jar = config.cookie_jar();
+    let webview_builder = configure_webview_builder(
+        &config,
+        ipc_handler.clone(),
+        message_queue.clone(),
+        cookie_jar.clone(),
+        &window,
+    )?;
     let webview = webview_builder.build(&window)?;
     tracing::info!("[standalone] webview_builder.build() returned successfully");
+
+    match cookie_jar.seed_webview(&webview) {
+        Ok(count) => tracing::debug!("[standalone] Restored {} cookies", count),
+        Err(e) => tracing::warn!("[standalone] Failed to restore cookies: {}", e),
+    }
     let build_duration = build_start.elapsed();
 
     tracing::info!(
@@ -218,6 +229,14 @@ pub fn create_desktop(
     let lifecycle = Arc::new(LifecycleManager::new());
     lifecycle.set_state(crate::webview::lifecycle::LifecycleState::Active);
 
+    // Free binary IPC payloads and stop streaming producers when the WebView is destroyed
+    let blob_store = config.blob_store.clone();
+    lifecycle.register_cleanup(move || blob_store.clear());
+    let channels = config.channels.clone();
+    lifecycle.register_cleanup(move || {
+        channels.cancel_all("webview destroyed");
+    });
+
     // Determine auto_show: false in headless mode
     let auto_show = config.auto_show && !config.headless;
 
@@ -365,6 +384,7 @@ pub fn create_desktop(
         #[cfg(target_os = "windows")]
         cached_hwnd,
         window_style_hints,
+        cookie_jar,
     })
 }
 
@@ -5,6 +5,9 @@
 
 use std::sync::{Arc, Mutex};
 
+use auroraview_core::cookies::CookieJar;
+use auroraview_core::ipc::ChannelRegistry;
+use auroraview_core::plugins::extensions::ExtensionsPlugin;
 use wry::WebViewBuilder as WryWebViewBuilder;
 #[cfg(target_os = "windows")]
 use wry::WebViewBuilderExtWindows;
@@ -15,10 +18,13 @@ use crate::webview::event_loop::UserEvent;
 use crate::webview::{child_window, js_assets};
 
 /// Configures and returns a WebViewBuilder with all handlers and settings.
+///
+/// `cookie_jar` backs `chrome.cookies` for extensions.
 pub fn configure_webview_builder(
     config: &WebViewConfig,
     ipc_handler: Arc<IpcHandler>,
     message_queue: Arc<MessageQueue>,
+    cookie_jar: Arc<CookieJar>,
     _window: &tao::window::Window,
 ) -> Result<wry::WebViewBuilder<'static>, Box<dyn std::error::Error>> {
     // Create WebContext with unique user data folder per process.
@@ -62,12 +68,18 @@ pub fn configure_webview_builder(
 
     let default_asset_root = std::env::current_dir().unwrap_or_default();
     let protocol_asset_root = asset_root_for_protocol.unwrap_or(default_asset_root);
+    let serve_options = auroraview_core::file_server::ServeOptions::default()
+        .with_cache_policy(config.asset_cache_policy)
+        .with_precompressed(config.serve_precompressed);
+    let blob_store = config.blob_store.clone();
 
     webview_builder =
         webview_builder.with_custom_protocol("auroraview".into(), move |_webview_id, request| {
-            crate::webview::protocol_handlers::handle_auroraview_protocol(
+            crate::webview::protocol_handlers::handle_auroraview_protocol_with_blobs(
                 &protocol_asset_root,
                 request,
+                &serve_options,
+                &blob_store,
             )
         });
 
@@ -110,7 +122,11 @@ pub fn configure_webview_builder(
     }
 
     // Add page load handler
-    webview_builder = add_page_load_handler(webview_builder, ipc_handler.clone());
+    webview_builder = add_page_load_handler(
+        webview_builder,
+        ipc_handler.clone(),
+        config.channels.clone(),
+    );
 
     // Add title change handler
     webview_builder = add_title_change_handler(webview_builder, ipc_handler.clone());
@@ -134,7 +150,7 @@ pub fn configure_webview_builder(
     );
 
     // Add IPC handler
-    webview_builder = add_ipc_handler(webview_builder, ipc_handler, message_queue);
+    webview_builder = add_ipc_handler(webview_builder, ipc_handler, message_queue, cookie_jar);
 
     Ok(webview_builder)
 }
@@ -259,13 +275,26 @@ fn configure_initial_content(
 }
 
 /// Add page load handler.
+///
+/// Streaming channels belong to the page, so they are cancelled when a new
+/// page starts loading.
 fn add_page_load_handler(
     mut builder: wry::WebViewBuilder<'static>,
     ipc_handler: Arc<IpcHandler>,
+    channels: ChannelRegistry,
 ) -> wry::WebViewBuilder<'static> {
     builder = builder.with_on_page_load_handler(move |event, url| {
         let event_name = match event {
-            wry::PageLoadEvent::Started => "page_load_started",
+            wry::PageLoadEvent::Started => {
+                let cancelled = channels.handle_page_load();
+                if cancelled > 0 {
+                    tracing::debug!(
+                        "[standalone] Cancelled {} channels on navigation",
+                        cancelled
+                    );
+                }
+                "page_load_started"
+            }
             wry::PageLoadEvent::Finished => "page_load_finished",
         };
 
@@ -399,18 +428,20 @@ fn add_ipc_handler(
     mut builder: wry::WebViewBuilder<'static>,
     ipc_handler: Arc<IpcHandler>,
     message_queue: Arc<MessageQueue>,
+    cookie_jar: Arc<CookieJar>,
 ) -> wry::WebViewBuilder<'static> {
     // Create event loop proxy holder for native window operations
     let event_loop_proxy_holder: Arc<Mutex<Option<tao::event_loop::EventLoopProxy<UserEvent>>>> =
         Arc::new(Mutex::new(None));
     let event_loop_proxy_for_ipc = event_loop_proxy_holder.clone();
 
-    // Create plugin router for handling plugin commands
-    let plugin_router = Arc::new(std::sync::RwLock::new(
-        auroraview_core::plugins::create_router_with_scope(
-            auroraview_core::plugins::ScopeConfig::permissive(),
-        ),
-    ));
+    // Create plugin router for handling plugin commands; `chrome.cookies`
+    // shares the WebView's cookie jar
+    let mut router = auroraview_core::plugins::create_router_with_extensions(
+        ExtensionsPlugin::new().with_cookie_store(cookie_jar),
+    );
+    router.set_scope(auroraview_core::plugins::ScopeConfig::permissive());
+    let plugin_router = Arc::new(std::sync::RwLock::new(router));
     let plugin_router_clone = plugin_router.clone();
 
     builder = builder.with_ipc_handler(move |request| {
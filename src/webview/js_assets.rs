@@ -36,11 +36,11 @@ use askama::Template;
 
 // Re-export from auroraview-core for convenience
 pub use auroraview_core::assets::{
-    get_all_plugins_js, get_bridge_stub_js, get_browsing_data_js, get_channel_bridge_js,
-    get_clipboard_plugin_js, get_command_bridge_js, get_context_menu_js, get_dialog_plugin_js,
-    get_dom_events_js, get_emit_event_js, get_event_bridge_js, get_event_utils_js,
-    get_file_drop_js, get_fs_plugin_js, get_js_asset, get_load_url_js, get_loading_html,
-    get_midscene_bridge_js, get_navigation_api_js, get_navigation_tracker_js,
+    get_all_plugins_js, get_blob_bridge_js, get_bridge_stub_js, get_browsing_data_js,
+    get_channel_bridge_js, get_clipboard_plugin_js, get_command_bridge_js, get_context_menu_js,
+    get_dialog_plugin_js, get_dom_events_js, get_emit_event_js, get_event_bridge_js,
+    get_event_utils_js, get_file_drop_js, get_fs_plugin_js, get_js_asset, get_load_url_js,
+    get_loading_html, get_midscene_bridge_js, get_navigation_api_js, get_navigation_tracker_js,
     get_network_intercept_js, get_plugin_js, get_screenshot_js, get_shell_plugin_js,
     get_state_bridge_js, get_test_callback_js, get_typescript_definitions, get_zoom_api_js,
     plugin_names,
@@ -167,6 +167,10 @@ pub fn build_init_script(config: &WebViewConfig) -> String {
     script.push_str(&get_channel_bridge_js());
     script.push('\n');
 
+    // Blob bridge for binary payloads
+    script.push_str(&get_blob_bridge_js());
+    script.push('\n');
+
     // Event utilities - debounce, throttle, once
     script.push_str(&get_event_utils_js());
     script.push('\n');
@@ -47,6 +47,8 @@ pub use core::EventEmitter;
 #[cfg(feature = "python-bindings")]
 pub use core::PluginManager;
 #[cfg(feature = "python-bindings")]
+pub use core::PyFlowChannel;
+#[cfg(feature = "python-bindings")]
 pub use core::PyRegion;
 pub use devtools::{DevToolsManager, DevToolsWindowConfig, DevToolsWindowInfo};
 pub use event_loop::{EventLoopError, EventLoopResult};
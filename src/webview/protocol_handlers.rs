@@ -14,14 +14,18 @@
 //!   - `https://auroraview.localhost/extension/my-extension/sidepanel.html`
 //!   - Maps to `%LOCALAPPDATA%/AuroraView/Extensions/{extensionId}/{path}`
 //!
+//! - `type:blob/{blobId}` - For binary IPC payloads in the WebView's blob store
+//!   - `https://auroraview.localhost/type:blob/3f2a...`
+//!   - See [`handle_auroraview_protocol_with_blobs`]
+//!
 //! Both type prefixes allow loading arbitrary local files through the custom protocol.
 //! The type prefix helps distinguish the source of the path for debugging.
 
 use auroraview_core::assets::build_error_page;
-use mime_guess::from_path;
+use auroraview_core::file_server::{serve_file, ServeOptions};
+use auroraview_core::ipc::{is_blob_uri, BlobStore};
 use path_clean::PathClean;
 use std::borrow::Cow;
-use std::fs;
 use std::path::{Path, PathBuf};
 use wry::http::{Request, Response};
 
@@ -45,21 +49,6 @@ fn build_response(
         .unwrap_or_else(|_| Response::new(Cow::Borrowed(b"Internal Server Error" as &[u8])))
 }
 
-/// Build an HTTP response with extra headers.
-fn build_response_with_headers(
-    status: u16,
-    headers: &[(&str, &str)],
-    body: Cow<'static, [u8]>,
-) -> Response<Cow<'static, [u8]>> {
-    let mut builder = Response::builder().status(status);
-    for (name, value) in headers {
-        builder = builder.header(*name, *value);
-    }
-    builder
-        .body(body)
-        .unwrap_or_else(|_| Response::new(Cow::Borrowed(b"Internal Server Error" as &[u8])))
-}
-
 /// Build a simple text response (no Content-Type header needed).
 fn build_plain_response(status: u16, body: Cow<'static, [u8]>) -> Response<Cow<'static, [u8]>> {
     Response::builder()
@@ -68,6 +57,16 @@ fn build_plain_response(status: u16, body: Cow<'static, [u8]>) -> Response<Cow<'
         .unwrap_or_else(|_| Response::new(Cow::Borrowed(b"Internal Server Error" as &[u8])))
 }
 
+/// Only GET and HEAD can be served from files
+fn is_read_request(request: &Request<Vec<u8>>) -> bool {
+    request.method() == "GET" || request.method() == "HEAD"
+}
+
+/// Options for files reached through `type:file`, `type:local` and extensions
+fn local_file_options() -> ServeOptions {
+    ServeOptions::default().with_cors(true)
+}
+
 /// Handle auroraview:// protocol requests
 ///
 /// Maps URLs like `auroraview://css/style.css` to `{asset_root}/css/style.css`
@@ -75,8 +74,43 @@ pub fn handle_auroraview_protocol(
     asset_root: &Path,
     request: Request<Vec<u8>>,
 ) -> Response<Cow<'static, [u8]>> {
-    // Only handle GET requests
-    if request.method() != "GET" {
+    handle_auroraview_protocol_with_options(asset_root, request, &ServeOptions::default())
+}
+
+/// Handle auroraview:// protocol requests, routing `type:blob/` URLs to a blob store
+///
+/// Blob URLs accept `GET`/`HEAD` (fetch), `POST` (upload from JavaScript) and
+/// `DELETE` (release); everything else is served from `asset_root`.
+pub fn handle_auroraview_protocol_with_blobs(
+    asset_root: &Path,
+    request: Request<Vec<u8>>,
+    options: &ServeOptions,
+    blobs: &BlobStore,
+) -> Response<Cow<'static, [u8]>> {
+    if is_blob_uri(&request.uri().to_string()) {
+        tracing::debug!(
+            "[Protocol] blob request: {} {}",
+            request.method(),
+            request.uri()
+        );
+        return blobs.handle_request(request);
+    }
+    handle_auroraview_protocol_with_options(asset_root, request, options)
+}
+
+/// Handle auroraview:// protocol requests with a serving policy for the asset root
+///
+/// Files are served with `ETag`/`Last-Modified` validators and byte ranges,
+/// so `<video>` elements can seek through large media without the whole
+/// file being loaded. `options` sets the `Cache-Control` policy and whether
+/// pre-compressed `.br`/`.gz` siblings are used for assets under `asset_root`.
+pub fn handle_auroraview_protocol_with_options(
+    asset_root: &Path,
+    request: Request<Vec<u8>>,
+    options: &ServeOptions,
+) -> Response<Cow<'static, [u8]>> {
+    // Only handle GET and HEAD requests
+    if !is_read_request(&request) {
         return build_plain_response(405, Cow::Borrowed(b"Method Not Allowed" as &[u8]));
     }
 
@@ -158,12 +192,12 @@ pub fn handle_auroraview_protocol(
 
     if let Some(file_path) = path.strip_prefix(&type_file_prefix) {
         tracing::debug!("[Protocol] {} request: {}", PROTOCOL_TYPE_FILE, file_path);
-        return handle_file_path_request(file_path);
+        return handle_file_path_request(file_path, &request);
     }
 
     if let Some(file_path) = path.strip_prefix(&type_local_prefix) {
         tracing::debug!("[Protocol] {} request: {}", PROTOCOL_TYPE_LOCAL, file_path);
-        return handle_file_path_request(file_path);
+        return handle_file_path_request(file_path, &request);
     }
 
     // Check for extension/ prefix - serves Chrome extension resources
@@ -171,7 +205,7 @@ pub fn handle_auroraview_protocol(
     // Maps to: %LOCALAPPDATA%/AuroraView/Extensions/{extensionId}/{path}
     if let Some(ext_path) = path.strip_prefix("extension/") {
         tracing::debug!("[Protocol] extension request: {}", ext_path);
-        return handle_extension_request(ext_path);
+        return handle_extension_request(ext_path, &request);
     }
 
     // Build full path
@@ -258,18 +292,16 @@ pub fn handle_auroraview_protocol(
         );
     }
 
-    // Read file
-    match fs::read(&full_path) {
-        Ok(data) => {
-            let mime_type = guess_mime_type(&full_path);
+    // Serve file
+    match serve_file(&full_path, &request, options) {
+        Ok(response) => {
             tracing::debug!(
-                "[Protocol] Loaded {} ({} bytes, {})",
+                "[Protocol] Served {} ({}, {} bytes)",
                 path,
-                data.len(),
-                mime_type
+                response.status(),
+                response.body().len()
             );
-
-            build_response(200, mime_type.as_str(), Cow::Owned(data))
+            response
         }
         Err(e) => {
             tracing::warn!("[Protocol] File not found: {:?} ({})", full_path, e);
@@ -290,8 +322,8 @@ pub fn handle_auroraview_protocol(
 /// Maps URLs like `file:///C:/path/to/file.txt` to local file system
 /// WARNING: This bypasses WebView's default security restrictions
 pub fn handle_file_protocol(request: Request<Vec<u8>>) -> Response<Cow<'static, [u8]>> {
-    // Only handle GET requests
-    if request.method() != "GET" {
+    // Only handle GET and HEAD requests
+    if !is_read_request(&request) {
         return build_plain_response(405, Cow::Borrowed(b"Method Not Allowed" as &[u8]));
     }
 
@@ -334,18 +366,16 @@ pub fn handle_file_protocol(request: Request<Vec<u8>>) -> Response<Cow<'static,
     let file_path = Path::new(&decoded_path);
     tracing::debug!("[Protocol] Resolved file path: {:?}", file_path);
 
-    // Read file
-    match fs::read(file_path) {
-        Ok(data) => {
-            let mime_type = guess_mime_type(file_path);
+    // Serve file
+    match serve_file(file_path, &request, &ServeOptions::default()) {
+        Ok(response) => {
             tracing::debug!(
-                "[Protocol] Loaded file:// {} ({} bytes, {})",
+                "[Protocol] Served file:// {} ({}, {} bytes)",
                 decoded_path,
-                data.len(),
-                mime_type
+                response.status(),
+                response.body().len()
             );
-
-            build_response(200, mime_type.as_str(), Cow::Owned(data))
+            response
         }
         Err(e) => {
             tracing::warn!("[Protocol] File not found: {:?} ({})", file_path, e);
@@ -390,7 +420,10 @@ pub fn handle_custom_protocol(
 /// - Returns the file content with appropriate MIME type
 ///
 /// **Security Note**: This bypasses asset_root restrictions. Use with caution.
-fn handle_file_path_request(file_path: &str) -> Response<Cow<'static, [u8]>> {
+fn handle_file_path_request(
+    file_path: &str,
+    request: &Request<Vec<u8>>,
+) -> Response<Cow<'static, [u8]>> {
     tracing::debug!("[Protocol] /file/ request: {}", file_path);
 
     // URL decode the path (handle %20 etc.)
@@ -419,25 +452,16 @@ fn handle_file_path_request(file_path: &str) -> Response<Cow<'static, [u8]>> {
     let path = Path::new(&normalized_path);
     tracing::debug!("[Protocol] Resolved /file/ path: {:?}", path);
 
-    // Read file
-    match fs::read(path) {
-        Ok(data) => {
-            let mime_type = guess_mime_type(path);
+    // Serve file
+    match serve_file(path, request, &local_file_options()) {
+        Ok(response) => {
             tracing::debug!(
-                "[Protocol] Loaded /file/ {} ({} bytes, {})",
+                "[Protocol] Served /file/ {} ({}, {} bytes)",
                 normalized_path,
-                data.len(),
-                mime_type
+                response.status(),
+                response.body().len()
             );
-
-            build_response_with_headers(
-                200,
-                &[
-                    ("Content-Type", mime_type.as_str()),
-                    ("Access-Control-Allow-Origin", "*"),
-                ],
-                Cow::Owned(data),
-            )
+            response
         }
         Err(e) => {
             tracing::warn!("[Protocol] /file/ not found: {:?} ({})", path, e);
@@ -453,7 +477,10 @@ fn handle_file_path_request(file_path: &str) -> Response<Cow<'static, [u8]>> {
 ///
 /// This allows Chrome extensions to load their resources through the custom protocol,
 /// avoiding the "Not allowed to load local resource" error for file:// URLs.
-fn handle_extension_request(ext_path: &str) -> Response<Cow<'static, [u8]>> {
+fn handle_extension_request(
+    ext_path: &str,
+    request: &Request<Vec<u8>>,
+) -> Response<Cow<'static, [u8]>> {
     tracing::debug!("[Protocol] extension request: {}", ext_path);
 
     // Parse extension ID and resource path
@@ -529,25 +556,16 @@ fn handle_extension_request(ext_path: &str) -> Response<Cow<'static, [u8]>> {
         );
     }
 
-    // Read and serve the file
-    match fs::read(&full_path) {
-        Ok(data) => {
-            let mime_type = guess_mime_type(&full_path);
+    // Serve the file
+    match serve_file(&full_path, request, &local_file_options()) {
+        Ok(response) => {
             tracing::debug!(
-                "[Protocol] Loaded extension resource: {} ({} bytes, {})",
+                "[Protocol] Served extension resource: {} ({}, {} bytes)",
                 ext_path,
-                data.len(),
-                mime_type
+                response.status(),
+                response.body().len()
             );
-
-            build_response_with_headers(
-                200,
-                &[
-                    ("Content-Type", mime_type.as_str()),
-                    ("Access-Control-Allow-Origin", "*"),
-                ],
-                Cow::Owned(data),
-            )
+            response
         }
         Err(e) => {
             tracing::warn!(
@@ -560,15 +578,6 @@ fn handle_extension_request(ext_path: &str) -> Response<Cow<'static, [u8]>> {
     }
 }
 
-/// Guess MIME type from file extension using mime_guess crate
-///
-/// This function uses the `mime_guess` crate which maintains a comprehensive
-/// database of MIME types based on file extensions. It supports 1000+ file types
-/// and is regularly updated.
-fn guess_mime_type(path: &Path) -> String {
-    from_path(path).first_or_octet_stream().to_string()
-}
-
 /// Parse a protocol path and resolve it to a full file system path
 ///
 /// This function handles both relative and absolute paths in the protocol URL:
@@ -81,6 +81,7 @@
 //! - wry WebView library: <https://github.com/nicholaswilson/wry>
 
 use std::collections::HashMap;
+use std::time::{Duration, Instant};
 
 use auroraview_core::assets::get_browser_controller_html;
 use auroraview_core::builder::{get_background_color, log_background_color};
@@ -117,6 +118,12 @@ pub struct TabState {
     /// Security state (optional, mirrors Security.securityStateChanged in Tab.cpp)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub security_state: Option<String>,
+    /// Whether the tab's WebView was released to save memory
+    ///
+    /// Discarded tabs keep their title, favicon and URL and are reloaded
+    /// when activated.
+    #[serde(default)]
+    pub discarded: bool,
 }
 
 /// Bookmark entry for the browser
@@ -138,6 +145,7 @@ impl TabState {
             can_go_forward: false,
             favicon: None,
             security_state: None,
+            discarded: false,
         }
     }
 
@@ -168,7 +176,10 @@ impl TabState {
 /// A tab with its WebView
 struct Tab {
     state: TabState,
-    webview: WryWebView,
+    /// `None` while the tab is discarded
+    webview: Option<WryWebView>,
+    /// When the tab was last shown
+    last_active: Instant,
 }
 
 /// User events for the tab manager event loop
@@ -265,6 +276,14 @@ pub struct TabManagerConfig {
     pub restore_session: bool,
     /// Frameless window (no native title bar)
     pub frameless: bool,
+    /// Maximum number of tabs holding a WebView (`None` = unlimited)
+    ///
+    /// The least recently shown tabs beyond this limit are discarded.
+    pub max_live_tabs: Option<usize>,
+    /// Discard background tabs not shown for this long (`None` = never)
+    ///
+    /// Checked whenever tabs are opened or switched.
+    pub discard_after: Option<Duration>,
 }
 
 impl Default for TabManagerConfig {
@@ -279,6 +298,8 @@ impl Default for TabManagerConfig {
             initial_urls: vec![], // Empty = open home page
             restore_session: false,
             frameless: true, // Default to frameless for modern look
+            max_live_tabs: None,
+            discard_after: None,
         }
     }
 }
@@ -320,6 +341,18 @@ impl TabManagerConfig {
         self.frameless = frameless;
         self
     }
+
+    /// Limit the number of tabs holding a WebView
+    pub fn with_max_live_tabs(mut self, max: usize) -> Self {
+        self.max_live_tabs = Some(max);
+        self
+    }
+
+    /// Discard background tabs after an idle period
+    pub fn with_discard_after(mut self, idle: Duration) -> Self {
+        self.discard_after = Some(idle);
+        self
+    }
 }
 
 /// Tab Manager - manages multiple WebViews in a single window
@@ -339,6 +372,8 @@ impl TabManagerConfig {
 /// 3. **Tab Visibility Management**
 ///    - Active tab: visible, receives input
 ///    - Inactive tabs: hidden, preserved state
+///    - Discarded tabs: WebView released, reloaded when activated
+///      (see [`TabManagerConfig::max_live_tabs`])
 ///
 /// Reference: `BrowserWindow.cpp` and `BrowserWindow.h` from
 /// <https://github.com/MicrosoftEdge/WebView2Browser>
@@ -433,6 +468,63 @@ impl TabManager {
         } else {
             url.to_string()
         };
+
+        tracing::info!(
+            "[TabManager] Creating tab {} with URL: {}",
+            tab_id,
+            actual_url
+        );
+
+        let webview = self.build_tab_webview(&tab_id, &actual_url)?;
+        Some(self.insert_tab(tab_id, actual_url, Some(webview)))
+    }
+
+    /// Create a discarded tab (no WebView until it is activated)
+    fn create_discarded_tab(&mut self, url: &str) -> String {
+        let tab_id = self.next_tab_id();
+        let url = if url.is_empty() {
+            self.config.home_url.clone()
+        } else {
+            url.to_string()
+        };
+        tracing::info!(
+            "[TabManager] Creating discarded tab {} with URL: {}",
+            tab_id,
+            url
+        );
+        self.insert_tab(tab_id, url, None)
+    }
+
+    /// Register a tab and add it to the tab order
+    fn insert_tab(&mut self, tab_id: String, url: String, webview: Option<WryWebView>) -> String {
+        let mut state = TabState::new(tab_id.clone(), url);
+        if webview.is_none() {
+            state.discarded = true;
+            state.is_loading = false;
+        }
+        self.tabs.insert(
+            tab_id.clone(),
+            Tab {
+                state,
+                webview,
+                last_active: Instant::now(),
+            },
+        );
+
+        // Add to tab order
+        self.tab_order.push(tab_id.clone());
+
+        // If this is the first tab, make it active
+        if self.active_tab_id.is_none() {
+            self.active_tab_id = Some(tab_id.clone());
+        }
+
+        tab_id
+    }
+
+    /// Build the content WebView for a tab
+    fn build_tab_webview(&self, tab_id: &str, url: &str) -> Option<WryWebView> {
+        let tab_id = tab_id.to_string();
         let debug = self.config.debug;
         #[cfg(target_os = "windows")]
         let header_height = self.config.header_height;
@@ -443,12 +535,6 @@ impl TabManager {
         // Now get window reference for the build phase
         let window = self.window.as_ref()?;
 
-        tracing::info!(
-            "[TabManager] Creating tab {} with URL: {}",
-            tab_id,
-            actual_url
-        );
-
         // Calculate content area bounds
         // Reference: BrowserWindow::ResizeUIWebViews calculates content area
         #[cfg(target_os = "windows")]
@@ -467,7 +553,7 @@ impl TabManager {
         // across WebViews in the same process, matching the pattern from
         // BrowserWindow.cpp where m_contentEnv is passed to Tab::CreateNewTab
         let mut builder = WebViewBuilder::new()
-            .with_url(&actual_url)
+            .with_url(url)
             .with_devtools(debug)
             .with_visible(false) // Start hidden (Tab.cpp: initial state)
             .with_background_color(background_color);
@@ -533,19 +619,8 @@ impl TabManager {
         // Use build_as_child() to ensure bounds are respected
         match builder.build_as_child(window) {
             Ok(webview) => {
-                let state = TabState::new(tab_id.clone(), actual_url);
-                self.tabs.insert(tab_id.clone(), Tab { state, webview });
-
-                // Add to tab order
-                self.tab_order.push(tab_id.clone());
-
-                // If this is the first tab, make it active
-                if self.active_tab_id.is_none() {
-                    self.active_tab_id = Some(tab_id.clone());
-                }
-
-                tracing::info!("[TabManager] Tab {} created successfully", tab_id);
-                Some(tab_id)
+                tracing::info!("[TabManager] Tab {} WebView created successfully", tab_id);
+                Some(webview)
             }
             Err(e) => {
                 tracing::error!("[TabManager] Failed to create tab WebView: {}", e);
@@ -570,6 +645,9 @@ impl TabManager {
             if self.active_tab_id.as_deref() == Some(tab_id) {
                 // Try to activate the next tab, or the previous one
                 self.active_tab_id = self.tab_order.first().cloned();
+                if let Some(next) = self.active_tab_id.clone() {
+                    self.reload_discarded(&next);
+                }
                 self.show_active_tab();
             }
         }
@@ -579,8 +657,18 @@ impl TabManager {
     fn activate_tab(&mut self, tab_id: &str) {
         if self.tabs.contains_key(tab_id) {
             tracing::info!("[TabManager] Activating tab: {}", tab_id);
+            // Start the idle clock of the tab being hidden
+            if let Some(old) = self
+                .active_tab_id
+                .as_ref()
+                .and_then(|id| self.tabs.get_mut(id))
+            {
+                old.last_active = Instant::now();
+            }
             self.active_tab_id = Some(tab_id.to_string());
+            self.reload_discarded(tab_id);
             self.show_active_tab();
+            self.discard_inactive_tabs();
         }
     }
 
@@ -589,7 +677,78 @@ impl TabManager {
         let active_id = self.active_tab_id.clone();
         for (id, tab) in &mut self.tabs {
             let is_active = active_id.as_deref() == Some(id.as_str());
-            let _ = tab.webview.set_visible(is_active);
+            if let Some(webview) = &tab.webview {
+                let _ = webview.set_visible(is_active);
+            }
+        }
+    }
+
+    /// Release a background tab's WebView, keeping its title, favicon and URL
+    fn discard_tab(&mut self, tab_id: &str) {
+        if self.active_tab_id.as_deref() == Some(tab_id) {
+            return;
+        }
+        if let Some(tab) = self.tabs.get_mut(tab_id) {
+            if tab.webview.take().is_some() {
+                tracing::info!("[TabManager] Discarding tab: {}", tab_id);
+                tab.state.discarded = true;
+                tab.state.is_loading = false;
+            }
+        }
+    }
+
+    /// Recreate the WebView of a discarded tab and load its last URL
+    fn reload_discarded(&mut self, tab_id: &str) {
+        let url = match self.tabs.get(tab_id) {
+            Some(tab) if tab.webview.is_none() => tab.state.url.clone(),
+            _ => return,
+        };
+
+        tracing::info!("[TabManager] Reloading discarded tab {}: {}", tab_id, url);
+        let Some(webview) = self.build_tab_webview(tab_id, &url) else {
+            return;
+        };
+        if let Some(tab) = self.tabs.get_mut(tab_id) {
+            tab.webview = Some(webview);
+            tab.state.discarded = false;
+            tab.state.is_loading = true;
+        }
+        // The new WebView is created on top of the controller
+        self.bring_controller_to_top();
+    }
+
+    /// Discard background tabs according to `max_live_tabs` and `discard_after`
+    fn discard_inactive_tabs(&mut self) {
+        let active_id = self.active_tab_id.clone();
+        let live = self.tabs.values().filter(|t| t.webview.is_some()).count();
+
+        // Least recently shown first
+        let mut candidates: Vec<(Instant, String)> = self
+            .tabs
+            .iter()
+            .filter(|(id, tab)| tab.webview.is_some() && active_id.as_ref() != Some(*id))
+            .map(|(id, tab)| (tab.last_active, id.clone()))
+            .collect();
+        candidates.sort();
+
+        let excess = self
+            .config
+            .max_live_tabs
+            .map_or(0, |max| live.saturating_sub(max));
+        let idle = |last_active: &Instant| {
+            self.config
+                .discard_after
+                .is_some_and(|after| last_active.elapsed() >= after)
+        };
+        let discard: Vec<String> = candidates
+            .iter()
+            .enumerate()
+            .filter(|(i, (last_active, _))| *i < excess || idle(last_active))
+            .map(|(_, (_, id))| id.clone())
+            .collect();
+
+        for tab_id in discard {
+            self.discard_tab(&tab_id);
         }
     }
 
@@ -612,7 +771,9 @@ impl TabManager {
                 tracing::info!("[TabManager] Navigating to: {}", url);
                 tab.state.url = url.clone();
                 tab.state.is_loading = true;
-                let _ = tab.webview.load_url(&url);
+                if let Some(webview) = &tab.webview {
+                    let _ = webview.load_url(&url);
+                }
             }
         }
     }
@@ -621,7 +782,9 @@ impl TabManager {
     fn go_back(&self) {
         if let Some(tab_id) = &self.active_tab_id {
             if let Some(tab) = self.tabs.get(tab_id) {
-                let _ = tab.webview.evaluate_script("history.back()");
+                if let Some(webview) = &tab.webview {
+                    let _ = webview.evaluate_script("history.back()");
+                }
             }
         }
     }
@@ -630,7 +793,9 @@ impl TabManager {
     fn go_forward(&self) {
         if let Some(tab_id) = &self.active_tab_id {
             if let Some(tab) = self.tabs.get(tab_id) {
-                let _ = tab.webview.evaluate_script("history.forward()");
+                if let Some(webview) = &tab.webview {
+                    let _ = webview.evaluate_script("history.forward()");
+                }
             }
         }
     }
@@ -639,7 +804,9 @@ impl TabManager {
     fn reload(&self) {
         if let Some(tab_id) = &self.active_tab_id {
             if let Some(tab) = self.tabs.get(tab_id) {
-                let _ = tab.webview.evaluate_script("location.reload()");
+                if let Some(webview) = &tab.webview {
+                    let _ = webview.evaluate_script("location.reload()");
+                }
             }
         }
     }
@@ -647,8 +814,8 @@ impl TabManager {
     /// Resize all tab WebViews to match content area
     #[cfg(target_os = "windows")]
     fn resize_tabs(&mut self, x: i32, y: i32, width: u32, height: u32) {
-        for tab in self.tabs.values() {
-            let _ = tab.webview.set_bounds(wry::Rect {
+        for webview in self.tabs.values().filter_map(|t| t.webview.as_ref()) {
+            let _ = webview.set_bounds(wry::Rect {
                 position: wry::dpi::Position::Logical(wry::dpi::LogicalPosition::new(
                     x as f64, y as f64,
                 )),
@@ -1023,11 +1190,18 @@ impl TabManager {
         };
 
         for url in &initial_urls {
-            if let Some(tab_id) = self.create_tab(url) {
-                // Activate the first tab
-                if self.active_tab_id.is_none() {
-                    self.active_tab_id = Some(tab_id);
-                }
+            // Tabs beyond the live limit load when first activated
+            let live = self.tabs.values().filter(|t| t.webview.is_some()).count();
+            // The active (first) tab always gets a WebView
+            if self
+                .config
+                .max_live_tabs
+                .is_some_and(|max| live >= max.max(1))
+            {
+                self.create_discarded_tab(url);
+            } else {
+                // The first tab is activated by `insert_tab`
+                self.create_tab(url);
             }
         }
 
@@ -1103,7 +1277,7 @@ impl TabManager {
             TabManagerEvent::NewTab { url } => {
                 // Reference: MG_CREATE_TAB in BrowserWindow.cpp
                 if let Some(tab_id) = self.create_tab(&url) {
-                    self.active_tab_id = Some(tab_id);
+                    self.activate_tab(&tab_id);
                 }
                 self.show_active_tab();
                 // Bring controller to top after creating new tab
@@ -1269,7 +1443,9 @@ impl TabManager {
     fn stop(&self) {
         if let Some(tab_id) = &self.active_tab_id {
             if let Some(tab) = self.tabs.get(tab_id) {
-                let _ = tab.webview.evaluate_script("window.stop()");
+                if let Some(webview) = &tab.webview {
+                    let _ = webview.evaluate_script("window.stop()");
+                }
             }
         }
     }
@@ -1330,6 +1506,33 @@ mod tests {
         assert!(config.debug);
     }
 
+    #[test]
+    fn test_tab_manager_config_discarding() {
+        let config = TabManagerConfig::default();
+        assert_eq!(config.max_live_tabs, None);
+        assert_eq!(config.discard_after, None);
+
+        let config = config
+            .with_max_live_tabs(8)
+            .with_discard_after(Duration::from_secs(600));
+        assert_eq!(config.max_live_tabs, Some(8));
+        assert_eq!(config.discard_after, Some(Duration::from_secs(600)));
+    }
+
+    #[test]
+    fn test_tab_state_discarded_serialization() {
+        let mut state = TabState::new("tab_1".to_string(), "https://example.com".to_string());
+        state.discarded = true;
+        let json = serde_json::to_value(&state).unwrap();
+        assert_eq!(json["discarded"], true);
+
+        let restored: TabState = serde_json::from_str(
+            r#"{"id":"tab_1","title":"T","url":"u","isLoading":false,"canGoBack":false,"canGoForward":false}"#,
+        )
+        .unwrap();
+        assert!(!restored.discarded);
+    }
+
     #[test]
     fn test_tab_manager_next_tab_id() {
         let mut manager = TabManager::new(TabManagerConfig::default());
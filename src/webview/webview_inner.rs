@@ -2,6 +2,7 @@
 //!
 //! This module contains the internal WebView structure and core operations.
 
+use auroraview_core::cookies::CookieJar;
 use pyo3::prelude::*;
 use std::sync::{Arc, Mutex};
 use wry::WebView as WryWebView;
@@ -44,6 +45,9 @@ pub struct WebViewInner {
 
     /// Hints used to re-apply Win32 window styles right after showing the window.
     pub(crate) window_style_hints: Option<WindowStyleHints>,
+
+    /// Persistent cookie jar, updated from the WebView when it is dropped
+    pub(crate) cookie_jar: Arc<CookieJar>,
 }
 
 impl Drop for WebViewInner {
@@ -59,6 +63,9 @@ impl Drop for WebViewInner {
         tracing::warn!("========================================");
         tracing::info!("[CLOSE] [WebViewInner::drop] Cleaning up WebView resources");
 
+        // Persist cookies while the WebView is still alive
+        self.save_cookies();
+
         // Execute lifecycle cleanup handlers
         self.lifecycle.execute_cleanup();
 
@@ -147,6 +154,17 @@ impl Drop for WebViewInner {
 }
 
 impl WebViewInner {
+    /// Write the WebView's cookies back to its cookie jar
+    pub fn save_cookies(&self) {
+        let Ok(webview) = self.webview.lock() else {
+            return;
+        };
+        match self.cookie_jar.save_webview(&webview) {
+            Ok(count) => tracing::debug!("[WebViewInner] Saved {} cookies", count),
+            Err(e) => tracing::warn!("[WebViewInner] Failed to save cookies: {}", e),
+        }
+    }
+
     /// Create standalone WebView with its own window
     pub fn create_standalone(
         config: WebViewConfig,
@@ -174,7 +192,7 @@ impl WebViewInner {
     #[cfg(target_os = "windows")]
     pub fn create_embedded(
         parent_hwnd: u64,
-        config: WebViewConfig,
+        mut config: WebViewConfig,
         ipc_handler: Arc<IpcHandler>,
         message_queue: Arc<MessageQueue>,
         on_created: Option<Box<dyn Fn(u64) + Send + Sync>>,
@@ -204,6 +222,23 @@ impl WebViewInner {
             "[OK] [create_embedded] process_events() will delegate to backend.process_events()"
         );
 
+        let cookie_jar = config.cookie_jar();
+        if let Ok(webview) = webview.lock() {
+            match cookie_jar.seed_webview(&webview) {
+                Ok(count) => tracing::debug!("[create_embedded] Restored {} cookies", count),
+                Err(e) => tracing::warn!("[create_embedded] Failed to restore cookies: {}", e),
+            }
+        }
+
+        // Free binary IPC payloads and stop streaming producers when the WebView is destroyed
+        let lifecycle = Arc::new(LifecycleManager::new());
+        let blob_store = config.blob_store.clone();
+        lifecycle.register_cleanup(move || blob_store.clear());
+        let channels = config.channels.clone();
+        lifecycle.register_cleanup(move || {
+            channels.cancel_all("webview destroyed");
+        });
+
         let window_style_hints = Some(WindowStyleHints {
             #[cfg(target_os = "windows")]
             decorations: config.decorations,
@@ -221,11 +256,12 @@ impl WebViewInner {
             event_loop: None, // Event loop is owned by backend
             message_queue,
             event_loop_proxy: None,
-            lifecycle: Arc::new(LifecycleManager::new()),
+            lifecycle,
             auto_show: true, // Embedded mode: visibility controlled by host
             backend: Some(Box::new(backend)), // CRITICAL: Keep backend alive!
             cached_hwnd,
             window_style_hints,
+            cookie_jar,
         })
     }
 
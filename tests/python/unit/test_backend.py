@@ -84,6 +84,11 @@ class TestGetAvailableBackends:
         assert BackendType.WRY in backends
         assert BackendType.WEBKITGTK in backends
 
+    def test_headless_always_available(self):
+        """Test that HEADLESS is available on every platform."""
+        assert BackendType.HEADLESS in get_available_backends()
+        assert BackendType.from_string("null") == BackendType.HEADLESS
+
 
 class TestGetBackendType:
     """Tests for get_backend_type function."""
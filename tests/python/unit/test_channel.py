@@ -2,6 +2,8 @@
 
 from __future__ import annotations
 
+import pytest
+
 from auroraview.core.channel import Channel, ChannelManager
 
 
@@ -148,3 +150,147 @@ class TestChannelManager:
 
         assert "ChannelManager" in repr(manager)
         assert "2" in repr(manager)
+
+
+class FakeFlowChannel:
+    """Stand-in for the native FlowChannel returned by open_channel."""
+
+    def __init__(self, channel_id, credits, capacity, policy, timeout):
+        self.id = channel_id
+        self.options = (credits, capacity, policy, timeout)
+        self.sent = []
+        self.closed = False
+        self.is_cancelled = False
+        self.cancel_reason = None
+        self._cancel_handlers = []
+
+    def send(self, data):
+        if self.closed or self.is_cancelled:
+            return False
+        self.sent.append(data)
+        return True
+
+    def close(self):
+        self.closed = True
+
+    def wait_closed(self, timeout=None):
+        return self.closed
+
+    def on_cancel(self, callback):
+        self._cancel_handlers.append(callback)
+
+    def stats(self):
+        return {"sent": len(self.sent), "buffered": 0}
+
+    def cancel_from_js(self, reason=None):
+        self.is_cancelled = True
+        self.cancel_reason = reason
+        for callback in self._cancel_handlers:
+            callback(reason)
+
+
+class FakeCore:
+    def __init__(self):
+        self.opened = []
+
+    def open_channel(self, channel_id, credits, capacity, policy, timeout):
+        flow = FakeFlowChannel(channel_id, credits, capacity, policy, timeout)
+        self.opened.append(flow)
+        return flow
+
+
+class FakeWebView:
+    def __init__(self):
+        self._core = FakeCore()
+        self.events = []
+
+    def emit(self, event, data):
+        self.events.append((event, data))
+
+
+class TestFlowControlledChannel:
+    """Test channels opened with credit-based flow control."""
+
+    def test_opens_core_channel(self):
+        webview = FakeWebView()
+        channel = Channel(webview, "log", credits=4, capacity=32, policy="drop_oldest")
+        assert channel.is_flow_controlled
+        flow = webview._core.opened[0]
+        assert flow.id == "log"
+        assert flow.options == (4, 32, "drop_oldest", None)
+        # The core emits the open event, not the Python wrapper
+        assert webview.events == []
+
+    def test_send_and_close_delegate(self):
+        webview = FakeWebView()
+        channel = Channel(webview, credits=2)
+        assert channel.send({"n": 1}) is True
+        channel.close()
+        flow = webview._core.opened[0]
+        assert flow.sent == [{"n": 1}]
+        assert flow.closed
+        assert channel.wait_closed(1.0) is True
+        assert webview.events == []
+
+    def test_stats(self):
+        webview = FakeWebView()
+        channel = Channel(webview, credits=2)
+        channel.send("a")
+        assert channel.stats()["sent"] == 1
+        assert Channel().stats() == {}
+
+    def test_cancel_from_javascript(self):
+        webview = FakeWebView()
+        channel = Channel(webview, credits=2)
+        reasons = []
+        closed = []
+        channel.on_cancel(reasons.append)
+        channel.on_close(lambda: closed.append(True))
+
+        webview._core.opened[0].cancel_from_js("user aborted")
+
+        assert reasons == ["user aborted"]
+        assert closed == [True]
+        assert channel.is_closed
+        assert channel.is_cancelled
+        assert channel.cancel_reason == "user aborted"
+        assert channel.send("more") is False
+        assert "cancelled" in repr(channel)
+
+    def test_attach_webview_opens_and_flushes(self):
+        channel = Channel(credits=8)
+        channel.send("early")
+        assert channel._buffer == ["early"]
+
+        webview = FakeWebView()
+        channel._attach_webview(webview)
+
+        assert webview._core.opened[0].sent == ["early"]
+        assert channel._buffer == []
+
+    def test_requires_native_core(self):
+        class NoCore:
+            def emit(self, event, data):
+                pass
+
+        with pytest.raises(RuntimeError, match="native WebView core"):
+            Channel(NoCore(), credits=1)
+
+    def test_legacy_channel_unaffected(self):
+        webview = FakeWebView()
+        channel = Channel(webview, "plain")
+        channel.send(1)
+        assert not channel.is_flow_controlled
+        assert webview._core.opened == []
+        assert webview.events == [("__channel_message__", {"channel_id": "plain", "data": 1})]
+
+    def test_manager_and_cancel_removes_channel(self):
+        webview = FakeWebView()
+        manager = ChannelManager(webview)
+        channel = manager.create("progress", credits=1, policy="coalesce")
+        assert webview._core.opened[0].options == (1, 256, "coalesce", None)
+        assert "progress" in manager
+
+        webview._core.opened[0].cancel_from_js()
+        assert "progress" not in manager
+        assert channel.cancel_reason is None
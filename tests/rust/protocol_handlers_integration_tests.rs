@@ -11,9 +11,13 @@ use wry::http::Request;
 // Import the protocol handler functions
 // Note: These need to be public in the source file
 use _core::webview::protocol_handlers::{
-    handle_auroraview_protocol, handle_custom_protocol, is_windows_absolute_path_without_colon,
-    normalize_windows_path_without_colon, parse_protocol_path,
+    handle_auroraview_protocol, handle_auroraview_protocol_with_blobs,
+    handle_auroraview_protocol_with_options, handle_custom_protocol,
+    is_windows_absolute_path_without_colon, normalize_windows_path_without_colon,
+    parse_protocol_path,
 };
+use auroraview_core::file_server::{CachePolicy, ServeOptions};
+use auroraview_core::ipc::BlobStore;
 
 #[rstest]
 fn handle_auroraview_protocol_security() {
@@ -579,3 +583,116 @@ fn auroraview_protocol_fallback_path() {
         "URI with just path should work via fallback"
     );
 }
+
+#[rstest]
+fn auroraview_protocol_range_request() {
+    let temp_dir = TempDir::new().unwrap();
+    let asset_root = temp_dir.path();
+    fs::write(asset_root.join("playblast.mp4"), b"0123456789").unwrap();
+
+    let request = Request::builder()
+        .method("GET")
+        .uri("auroraview://playblast.mp4")
+        .header("Range", "bytes=4-")
+        .body(vec![])
+        .unwrap();
+
+    let response = handle_auroraview_protocol(asset_root, request);
+    assert_eq!(response.status(), 206);
+    assert_eq!(response.body().as_ref(), b"456789");
+    assert_eq!(response.headers()["Content-Range"], "bytes 4-9/10");
+    assert_eq!(response.headers()["Content-Type"], "video/mp4");
+}
+
+#[rstest]
+fn auroraview_protocol_head_and_not_modified() {
+    let temp_dir = TempDir::new().unwrap();
+    let asset_root = temp_dir.path();
+    fs::write(asset_root.join("index.html"), b"<html></html>").unwrap();
+
+    let request = Request::builder()
+        .method("HEAD")
+        .uri("auroraview://index.html")
+        .body(vec![])
+        .unwrap();
+    let response = handle_auroraview_protocol(asset_root, request);
+    assert_eq!(response.status(), 200);
+    assert!(response.body().is_empty());
+    let etag = response.headers()["ETag"].to_str().unwrap().to_string();
+
+    let request = Request::builder()
+        .method("GET")
+        .uri("auroraview://index.html")
+        .header("If-None-Match", etag)
+        .body(vec![])
+        .unwrap();
+    let response = handle_auroraview_protocol(asset_root, request);
+    assert_eq!(response.status(), 304);
+    assert!(response.body().is_empty());
+}
+
+#[rstest]
+fn auroraview_protocol_with_serve_options() {
+    let temp_dir = TempDir::new().unwrap();
+    let asset_root = temp_dir.path();
+    fs::write(asset_root.join("app.js"), b"console.log(1)").unwrap();
+    fs::write(asset_root.join("app.js.br"), b"compressed").unwrap();
+
+    let options = ServeOptions::default()
+        .with_cache_policy(CachePolicy::Immutable(std::time::Duration::from_secs(3600)))
+        .with_precompressed(true);
+    let request = Request::builder()
+        .method("GET")
+        .uri("auroraview://app.js")
+        .header("Accept-Encoding", "gzip, br")
+        .body(vec![])
+        .unwrap();
+
+    let response = handle_auroraview_protocol_with_options(asset_root, request, &options);
+    assert_eq!(response.status(), 200);
+    assert_eq!(response.body().as_ref(), b"compressed");
+    assert_eq!(response.headers()["Content-Encoding"], "br");
+    assert_eq!(
+        response.headers()["Cache-Control"],
+        "max-age=3600, immutable"
+    );
+}
+
+#[rstest]
+fn auroraview_protocol_routes_blob_requests() {
+    let temp_dir = TempDir::new().unwrap();
+    let asset_root = temp_dir.path();
+    fs::write(asset_root.join("index.html"), b"<html></html>").unwrap();
+    let blobs = BlobStore::new();
+    let handle = blobs.insert(vec![1, 2, 3], "image/png").unwrap();
+    let options = ServeOptions::default();
+
+    let request = Request::builder()
+        .method("GET")
+        .uri(&handle.url)
+        .body(vec![])
+        .unwrap();
+    let response = handle_auroraview_protocol_with_blobs(asset_root, request, &options, &blobs);
+    assert_eq!(response.status(), 200);
+    assert_eq!(response.body().as_ref(), &[1, 2, 3]);
+    assert_eq!(response.headers()["Content-Type"], "image/png");
+
+    let request = Request::builder()
+        .method("POST")
+        .uri("auroraview://localhost/type:blob/")
+        .body(vec![4, 5])
+        .unwrap();
+    let response = handle_auroraview_protocol_with_blobs(asset_root, request, &options, &blobs);
+    assert_eq!(response.status(), 201);
+    assert_eq!(blobs.len(), 2);
+
+    // Other paths are still served from the asset root
+    let request = Request::builder()
+        .method("GET")
+        .uri("auroraview://localhost/index.html")
+        .body(vec![])
+        .unwrap();
+    let response = handle_auroraview_protocol_with_blobs(asset_root, request, &options, &blobs);
+    assert_eq!(response.status(), 200);
+    assert_eq!(response.body().as_ref(), b"<html></html>");
+}